@@ -0,0 +1,785 @@
+//! Client commands - operational checks, balance watching and keypair import
+//! for a configured executor
+
+use anchor_lang::AccountDeserialize;
+use antegen_cli_core::commands::{expand_tilde, MIN_BALANCE_LAMPORTS};
+use antegen_cli_core::output::{self, OutputFormat};
+use antegen_client::alerts::{Alert, AlertSink, AlertStatus, BalanceThreshold, WebhookSink};
+use antegen_client::config::EndpointRole;
+use antegen_client::fee_payers::MIN_FEE_PAYER_BALANCE;
+use antegen_client::rpc::{
+    preflight_endpoint, require_passing, EndpointConfig, EndpointPreflight, EndpointState, RpcPool,
+    RpcPoolConfig,
+};
+use antegen_client::{ClientConfig, TpuClient, TpuClientConfig};
+use antegen_thread_program::state::{Thread, ThreadConfig};
+use antegen_thread_program::SEED_CONFIG;
+use anyhow::{anyhow, Context, Result};
+use futures::StreamExt;
+use serde::Serialize;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signature, Signer};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Recent executor transactions sampled for threads it executed
+const SIGNATURE_SAMPLE: usize = 25;
+
+/// Time allowed for each endpoint and TPU check
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// SOL requested by `import --airdrop-if-empty`
+const IMPORT_AIRDROP_SOL: f64 = 1.0;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// One line of the status report
+#[derive(Serialize)]
+pub struct StatusCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    /// A failed critical check makes the command exit non-zero
+    pub critical: bool,
+    pub detail: String,
+}
+
+#[derive(Serialize)]
+pub struct StatusOutput {
+    pub config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executor: Option<String>,
+    /// False if any critical check failed
+    pub healthy: bool,
+    pub checks: Vec<StatusCheck>,
+}
+
+impl StatusOutput {
+    fn push(
+        &mut self,
+        name: impl Into<String>,
+        status: CheckStatus,
+        critical: bool,
+        detail: String,
+    ) {
+        self.checks.push(StatusCheck {
+            name: name.into(),
+            status,
+            critical,
+            detail,
+        });
+    }
+}
+
+/// Check whether the executor described by `config_path` can do its job
+///
+/// Prints every check, then fails if any critical one did: executor keypair
+/// and balance, at least one passing endpoint for submission and for data,
+/// and the thread program config. Threads recently executed, TPU
+/// connectivity and the control API are informational.
+pub async fn status(config_path: PathBuf, output: OutputFormat) -> Result<()> {
+    let config = ClientConfig::load(&config_path)
+        .with_context(|| format!("Failed to load config from {:?}", config_path))?;
+    let mut report = StatusOutput {
+        config: config_path.display().to_string(),
+        executor: None,
+        healthy: true,
+        checks: Vec::new(),
+    };
+    output.note(format_args!(
+        "Checking client from {}",
+        config_path.display()
+    ));
+
+    let executor = match expand_tilde(&config.executor.keypair_path)
+        .and_then(|path| read_keypair_file(&path).map_err(|e| anyhow!("{}: {}", path.display(), e)))
+    {
+        Ok(keypair) => {
+            report.executor = Some(keypair.pubkey().to_string());
+            report.push(
+                "executor keypair",
+                CheckStatus::Ok,
+                true,
+                keypair.pubkey().to_string(),
+            );
+            Some(keypair.pubkey())
+        }
+        Err(e) => {
+            report.push("executor keypair", CheckStatus::Fail, true, e.to_string());
+            None
+        }
+    };
+
+    // Additional fee payers; the client refuses to start without all of them
+    let mut fee_payers = Vec::new();
+    for path in &config.executor.keypairs {
+        match expand_tilde(path).and_then(|path| {
+            read_keypair_file(&path).map_err(|e| anyhow!("{}: {}", path.display(), e))
+        }) {
+            Ok(keypair) => fee_payers.push(keypair.pubkey()),
+            Err(e) => report.push("fee payer keypair", CheckStatus::Fail, true, e.to_string()),
+        }
+    }
+
+    // Endpoints: each one individually, then whether every role is covered
+    let preflights = futures::future::join_all(
+        EndpointConfig::from_rpc_config(&config.rpc)
+            .into_iter()
+            .map(|endpoint| async move {
+                preflight_endpoint(&EndpointState::new(endpoint), CHECK_TIMEOUT).await
+            }),
+    )
+    .await;
+    for preflight in &preflights {
+        let (status, detail) = endpoint_detail(preflight);
+        report.push(format!("rpc {}", preflight.url), status, false, detail);
+    }
+    match require_passing(&preflights) {
+        Ok(()) => report.push(
+            "rpc coverage",
+            CheckStatus::Ok,
+            true,
+            "submission and data endpoints reachable".into(),
+        ),
+        Err(e) => report.push("rpc coverage", CheckStatus::Fail, true, e.to_string()),
+    }
+
+    let rpc_client = Arc::new(
+        RpcPool::new(
+            EndpointConfig::from_rpc_config(&config.rpc),
+            RpcPoolConfig::from_rpc_config(&config.rpc),
+        )
+        .map_err(|e| anyhow!("Failed to create RPC client: {}", e))?,
+    );
+    for preflight in preflights.iter().filter(|p| !p.passed()) {
+        rpc_client.mark_unhealthy(&preflight.url);
+    }
+
+    let program_id = config.datasources.program_id;
+    check_thread_config(&rpc_client, &program_id, &mut report).await;
+    if let Some(executor) = executor {
+        check_balance(&rpc_client, &executor, &mut report).await;
+        check_recent_threads(&rpc_client, &executor, &program_id, &mut report).await;
+    }
+    for fee_payer in &fee_payers {
+        check_fee_payer_balance(&rpc_client, fee_payer, &mut report).await;
+    }
+
+    if config.tpu.enabled {
+        check_tpu(&config, rpc_client.clone(), &mut report).await;
+    }
+    if config.control.enabled {
+        let address = &config.control.bind_address;
+        match tokio::time::timeout(CHECK_TIMEOUT, tokio::net::TcpStream::connect(address)).await {
+            Ok(Ok(_)) => report.push(
+                "control api",
+                CheckStatus::Ok,
+                false,
+                format!("client listening on {}", address),
+            ),
+            _ => report.push(
+                "control api",
+                CheckStatus::Warn,
+                false,
+                format!("nothing listening on {}; is the client running?", address),
+            ),
+        }
+    }
+
+    report.healthy = !report
+        .checks
+        .iter()
+        .any(|check| check.critical && check.status == CheckStatus::Fail);
+    output.print(&report, print_status)?;
+
+    if !report.healthy {
+        let failed = report
+            .checks
+            .iter()
+            .filter(|check| check.critical && check.status == CheckStatus::Fail)
+            .count();
+        return Err(anyhow!("{} critical check(s) failed", failed));
+    }
+    Ok(())
+}
+
+/// Status and summary of one endpoint's preflight
+fn endpoint_detail(preflight: &EndpointPreflight) -> (CheckStatus, String) {
+    let role = format!("{:?}", preflight.role).to_lowercase();
+    let failures: Vec<String> = preflight
+        .results
+        .iter()
+        .filter_map(|(check, result)| {
+            result
+                .as_ref()
+                .err()
+                .map(|reason| format!("{} ({})", check, reason))
+        })
+        .collect();
+    if failures.is_empty() {
+        (CheckStatus::Ok, format!("{}, all checks passed", role))
+    } else {
+        (
+            CheckStatus::Warn,
+            format!("{}, failed: {}", role, failures.join(", ")),
+        )
+    }
+}
+
+/// Fetch the thread program config of `program_id`
+async fn check_thread_config(rpc_client: &RpcPool, program_id: &Pubkey, report: &mut StatusOutput) {
+    let address = Pubkey::find_program_address(&[SEED_CONFIG], program_id).0;
+    let result = match rpc_client.get_account(&address).await {
+        Ok(Some(account)) => account
+            .decode_data()
+            .map_err(|e| format!("Failed to decode {}: {}", address, e))
+            .and_then(|data| {
+                ThreadConfig::try_deserialize(&mut data.as_slice())
+                    .map_err(|e| format!("Failed to deserialize {}: {}", address, e))
+            }),
+        Ok(None) => Err(format!(
+            "{} not found; run 'antegen program config init'",
+            address
+        )),
+        Err(e) => Err(format!("Failed to fetch {}: {}", address, e)),
+    };
+
+    match result {
+        Ok(thread_config) if thread_config.paused => {
+            report.push(
+                "thread config",
+                CheckStatus::Warn,
+                true,
+                format!("{} (globally paused)", address),
+            );
+        }
+        Ok(_) => {
+            report.push("thread config", CheckStatus::Ok, true, address.to_string());
+        }
+        Err(e) => report.push("thread config", CheckStatus::Fail, true, e),
+    }
+}
+
+/// The executor pays transaction fees, so a low balance stops execution
+async fn check_balance(rpc_client: &RpcPool, executor: &Pubkey, report: &mut StatusOutput) {
+    match rpc_client.get_balance(executor).await {
+        Ok(balance) => {
+            let status = if balance >= MIN_BALANCE_LAMPORTS {
+                CheckStatus::Ok
+            } else {
+                CheckStatus::Fail
+            };
+            report.push(
+                "executor balance",
+                status,
+                true,
+                format!(
+                    "{:.4} SOL (minimum {:.4} SOL)",
+                    balance as f64 / LAMPORTS_PER_SOL as f64,
+                    MIN_BALANCE_LAMPORTS as f64 / LAMPORTS_PER_SOL as f64
+                ),
+            );
+        }
+        Err(e) => report.push(
+            "executor balance",
+            CheckStatus::Fail,
+            true,
+            format!("Failed to fetch balance: {}", e),
+        ),
+    }
+}
+
+/// Drained fee payers are skipped in rotation, so a low one only warns
+async fn check_fee_payer_balance(
+    rpc_client: &RpcPool,
+    fee_payer: &Pubkey,
+    report: &mut StatusOutput,
+) {
+    let name = format!("fee payer {}", fee_payer);
+    match rpc_client.get_balance(fee_payer).await {
+        Ok(balance) => {
+            let status = if balance >= MIN_FEE_PAYER_BALANCE {
+                CheckStatus::Ok
+            } else {
+                CheckStatus::Warn
+            };
+            report.push(
+                name,
+                status,
+                false,
+                format!(
+                    "{:.4} SOL (skipped below {:.4} SOL)",
+                    balance as f64 / LAMPORTS_PER_SOL as f64,
+                    MIN_FEE_PAYER_BALANCE as f64 / LAMPORTS_PER_SOL as f64
+                ),
+            );
+        }
+        Err(e) => report.push(
+            name,
+            CheckStatus::Warn,
+            false,
+            format!("Failed to fetch balance: {}", e),
+        ),
+    }
+}
+
+/// Count threads last executed by `executor`, from its most recent
+/// transactions
+async fn check_recent_threads(
+    rpc_client: &RpcPool,
+    executor: &Pubkey,
+    program_id: &Pubkey,
+    report: &mut StatusOutput,
+) {
+    let signatures = match rpc_client
+        .get_signatures_for_address(executor, None, SIGNATURE_SAMPLE)
+        .await
+    {
+        Ok(signatures) => signatures,
+        Err(e) => {
+            return report.push(
+                "recent threads",
+                CheckStatus::Warn,
+                false,
+                format!("Failed to fetch executor transactions: {}", e),
+            )
+        }
+    };
+
+    let transactions = futures::future::join_all(
+        signatures
+            .iter()
+            .filter(|info| info.err.is_none())
+            .filter_map(|info| Signature::from_str(&info.signature).ok())
+            .map(|signature| async move { rpc_client.get_transaction(&signature).await }),
+    )
+    .await;
+    let candidates: Vec<Pubkey> = transactions
+        .into_iter()
+        .filter_map(|tx| tx.ok().flatten())
+        .flat_map(|tx| tx.transaction.message.account_keys)
+        .filter_map(|key| Pubkey::from_str(&key).ok())
+        .filter(|key| key != executor)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut threads = 0;
+    for chunk in candidates.chunks(100) {
+        let Ok(accounts) = rpc_client.get_multiple_accounts(chunk).await else {
+            continue;
+        };
+        threads += accounts
+            .iter()
+            .flatten()
+            .filter(|account| account.owner_pubkey().ok().as_ref() == Some(program_id))
+            .filter_map(|account| account.decode_data().ok())
+            .filter_map(|data| Thread::try_deserialize(&mut data.as_slice()).ok())
+            .filter(|thread| thread.last_executor == *executor)
+            .count();
+    }
+
+    let status = if threads > 0 {
+        CheckStatus::Ok
+    } else {
+        CheckStatus::Warn
+    };
+    report.push(
+        "recent threads",
+        status,
+        false,
+        format!(
+            "{} thread(s) last executed by this executor in its {} most recent transactions",
+            threads,
+            signatures.len()
+        ),
+    );
+}
+
+/// Start a TPU client the way the executor does, then shut it down
+async fn check_tpu(config: &ClientConfig, rpc_client: Arc<RpcPool>, report: &mut StatusOutput) {
+    let Some(endpoint) = config
+        .rpc
+        .endpoints
+        .iter()
+        .find(|e| matches!(e.role, EndpointRole::Submission | EndpointRole::Both))
+    else {
+        return report.push(
+            "tpu",
+            CheckStatus::Warn,
+            false,
+            "no submission endpoint to track leaders from".into(),
+        );
+    };
+
+    let tpu_config = TpuClientConfig {
+        rpc_url: endpoint.url.clone(),
+        websocket_url: endpoint.get_ws_url(),
+        num_connections: config.tpu.num_connections,
+        leaders_fanout: config.tpu.leaders_fanout,
+        worker_channel_size: config.tpu.worker_channel_size,
+        slot_receiver: None,
+        confirmation_rpc: rpc_client,
+        confirmation_poll_interval_ms: config.tpu.confirmation_poll_interval_ms,
+        confirmation_timeout_ms: config.tpu.confirmation_timeout_ms,
+        auto_disable: config.tpu.auto_disable,
+    };
+    match tokio::time::timeout(CHECK_TIMEOUT, TpuClient::new(tpu_config)).await {
+        Ok(Ok(client)) => {
+            client.shutdown();
+            report.push(
+                "tpu",
+                CheckStatus::Ok,
+                false,
+                "leader schedule and slot updates available".into(),
+            );
+        }
+        Ok(Err(e)) => report.push(
+            "tpu",
+            CheckStatus::Warn,
+            false,
+            format!("{:#}; submissions fall back to RPC", e),
+        ),
+        Err(_) => report.push(
+            "tpu",
+            CheckStatus::Warn,
+            false,
+            format!(
+                "timed out after {:?}; submissions fall back to RPC",
+                CHECK_TIMEOUT
+            ),
+        ),
+    }
+}
+
+fn print_status(report: &StatusOutput) {
+    if let Some(executor) = &report.executor {
+        println!("Executor: {}", executor);
+    }
+    println!("Config:   {}\n", report.config);
+
+    let width = report
+        .checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or(0);
+    println!("{:<width$}  {:<6}  DETAIL", "CHECK", "STATUS");
+    for check in &report.checks {
+        let status = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+        };
+        println!("{:<width$}  {:<6}  {}", check.name, status, check.detail);
+    }
+
+    println!();
+    if report.healthy {
+        println!("Healthy");
+    } else {
+        println!("Unhealthy: a critical check failed");
+    }
+}
+
+/// One signer's balance in `client balance`
+#[derive(Serialize)]
+pub struct SignerBalance {
+    /// `executor` or `fee_payer`
+    pub role: &'static str,
+    pub pubkey: String,
+    #[serde(with = "output::as_string")]
+    pub lamports: u64,
+    /// Below the threshold
+    pub low: bool,
+}
+
+#[derive(Serialize)]
+pub struct BalanceOutput {
+    #[serde(with = "output::as_string")]
+    pub threshold: u64,
+    pub signers: Vec<SignerBalance>,
+}
+
+/// A balance change seen by `client balance --watch`
+#[derive(Serialize)]
+struct BalanceChange {
+    role: &'static str,
+    pubkey: String,
+    slot: u64,
+    #[serde(with = "output::as_string")]
+    lamports: u64,
+    /// Lamports before the change, as a string like `lamports`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous: Option<String>,
+}
+
+/// Show the balance of every signer of the client at `config_path`, the
+/// executor and any additional fee payers
+///
+/// Signers below `threshold` SOL (default: the funding minimum) are marked.
+/// With `check`, fails if any is. With `watch`, subscribes to every signer
+/// and prints balance changes until interrupted, raising an alert when a
+/// signer drops below the threshold and a recovery notice once it is back
+/// above it. Alerts also go to `webhook` as JSON.
+pub async fn balance(
+    config_path: PathBuf,
+    threshold: Option<f64>,
+    watch: bool,
+    check: bool,
+    webhook: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = ClientConfig::load(&config_path)
+        .with_context(|| format!("Failed to load config from {:?}", config_path))?;
+    let threshold = threshold
+        .map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64)
+        .unwrap_or(MIN_BALANCE_LAMPORTS);
+    let webhook = webhook.map(WebhookSink::new).transpose()?;
+
+    let mut signers = Vec::new();
+    for (index, path) in config.executor.keypair_paths().enumerate() {
+        let path = expand_tilde(path)?;
+        let keypair = read_keypair_file(&path)
+            .map_err(|e| anyhow!("Failed to read keypair from {:?}: {}", path, e))?;
+        let role = if index == 0 { "executor" } else { "fee_payer" };
+        signers.push((role, keypair.pubkey()));
+    }
+
+    let rpc_client = RpcPool::new(
+        EndpointConfig::from_rpc_config(&config.rpc),
+        RpcPoolConfig::from_rpc_config(&config.rpc),
+    )
+    .map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let mut detector = BalanceThreshold::new(threshold);
+    let mut alerts = Vec::new();
+    let mut report = BalanceOutput {
+        threshold,
+        signers: Vec::new(),
+    };
+    let mut last = HashMap::new();
+    for (role, pubkey) in &signers {
+        let lamports = rpc_client
+            .get_balance(pubkey)
+            .await
+            .with_context(|| format!("Failed to get balance of {}", pubkey))?;
+        alerts.extend(detector.observe(*pubkey, lamports));
+        last.insert(*pubkey, lamports);
+        report.signers.push(SignerBalance {
+            role,
+            pubkey: pubkey.to_string(),
+            lamports,
+            low: detector.is_low(pubkey),
+        });
+    }
+    output.print(&report, print_balances)?;
+    for alert in &alerts {
+        announce(alert, webhook.as_ref(), output).await;
+    }
+
+    if check {
+        let low = report.signers.iter().filter(|signer| signer.low).count();
+        if low > 0 {
+            return Err(anyhow!(
+                "{} signer(s) below {:.4} SOL",
+                low,
+                threshold as f64 / LAMPORTS_PER_SOL as f64
+            ));
+        }
+        return Ok(());
+    }
+    if !watch {
+        return Ok(());
+    }
+
+    output.note(format_args!(
+        "\nWatching {} signer(s), alerting below {:.4} SOL (Ctrl+C to stop)",
+        signers.len(),
+        threshold as f64 / LAMPORTS_PER_SOL as f64
+    ));
+    let roles: HashMap<Pubkey, &'static str> = signers
+        .iter()
+        .map(|(role, pubkey)| (*pubkey, *role))
+        .collect();
+    let mut updates = futures::stream::select_all(signers.iter().map(|(_, pubkey)| {
+        Box::pin(rpc_client.watch_account(*pubkey, CommitmentConfig::confirmed()))
+    }));
+    loop {
+        let update = tokio::select! {
+            update = updates.next() => update,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let Some(update) = update else {
+            return Err(anyhow!("Balance watch closed"));
+        };
+        // The notification carries the account data; read the balance it left
+        let lamports = match rpc_client.get_balance(&update.pubkey).await {
+            Ok(lamports) => lamports,
+            Err(e) => {
+                output.note(format_args!(
+                    "warning: failed to get balance of {}: {}",
+                    update.pubkey, e
+                ));
+                continue;
+            }
+        };
+        let previous = last.insert(update.pubkey, lamports);
+        if previous == Some(lamports) {
+            continue;
+        }
+
+        let role = roles.get(&update.pubkey).copied().unwrap_or("signer");
+        if output.is_json() {
+            output.print_json(&BalanceChange {
+                role,
+                pubkey: update.pubkey.to_string(),
+                slot: update.slot,
+                lamports,
+                previous: previous.map(|lamports| lamports.to_string()),
+            })?;
+        } else {
+            let delta = previous
+                .map(|previous| {
+                    format!(
+                        " ({:+.9})",
+                        (lamports as f64 - previous as f64) / LAMPORTS_PER_SOL as f64
+                    )
+                })
+                .unwrap_or_default();
+            println!(
+                "slot {}  {:<9}  {}  {:.9} SOL{}",
+                update.slot,
+                role,
+                update.pubkey,
+                lamports as f64 / LAMPORTS_PER_SOL as f64,
+                delta
+            );
+        }
+        if let Some(alert) = detector.observe(update.pubkey, lamports) {
+            announce(&alert, webhook.as_ref(), output).await;
+        }
+    }
+}
+
+fn print_balances(report: &BalanceOutput) {
+    println!("{:<9}  {:<44}  {:>16}", "ROLE", "PUBKEY", "BALANCE (SOL)");
+    for signer in &report.signers {
+        println!(
+            "{:<9}  {:<44}  {:>16.9}{}",
+            signer.role,
+            signer.pubkey,
+            signer.lamports as f64 / LAMPORTS_PER_SOL as f64,
+            if signer.low { "  LOW" } else { "" }
+        );
+    }
+}
+
+/// Print an alert line and post it to the webhook, if any
+async fn announce(alert: &Alert, webhook: Option<&WebhookSink>, output: OutputFormat) {
+    if output.is_json() {
+        let _ = output.print_json(alert);
+    } else {
+        let marker = match alert.status {
+            AlertStatus::Firing => "!!! ALERT",
+            AlertStatus::Resolved => "--- RESOLVED",
+        };
+        println!("{} {}", marker, alert);
+    }
+    if let Some(webhook) = webhook {
+        if let Err(e) = webhook.send(alert).await {
+            output.note(format_args!("warning: failed to post alert: {}", e));
+        }
+    }
+}
+
+/// Install `keypair_path` as the executor keypair of the client at
+/// `config_path`
+///
+/// Refuses to replace a different executor keypair unless `force` is set.
+/// With `validate_cluster`, the keypair's balance is then checked on that
+/// cluster: an unfunded keypair only gets a warning, or an airdrop of
+/// [`IMPORT_AIRDROP_SOL`] with `airdrop_if_empty`.
+pub async fn import(
+    config_path: PathBuf,
+    keypair_path: PathBuf,
+    force: bool,
+    validate_cluster: Option<String>,
+    airdrop_if_empty: bool,
+) -> Result<()> {
+    let config = ClientConfig::load(&config_path)
+        .with_context(|| format!("Failed to load config from {:?}", config_path))?;
+    let keypair = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair from {:?}: {}", keypair_path, e))?;
+    let executor_path = expand_tilde(&config.executor.keypair_path)?;
+
+    if let Ok(existing) = read_keypair_file(&executor_path) {
+        if existing.pubkey() == keypair.pubkey() {
+            println!(
+                "Executor keypair {} is already installed at {}",
+                keypair.pubkey(),
+                executor_path.display()
+            );
+        } else if !force {
+            return Err(anyhow!(
+                "{} already holds executor {}; pass --force to replace it with {}",
+                executor_path.display(),
+                existing.pubkey(),
+                keypair.pubkey()
+            ));
+        }
+    }
+    if let Some(parent) = executor_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let json = serde_json::to_string(&keypair.to_bytes().to_vec())?;
+    std::fs::write(&executor_path, json)
+        .with_context(|| format!("Failed to write keypair to: {}", executor_path.display()))?;
+    println!(
+        "Imported executor {} to {}",
+        keypair.pubkey(),
+        executor_path.display()
+    );
+
+    let Some(cluster) = validate_cluster else {
+        return Ok(());
+    };
+    let rpc_client = RpcPool::with_url(&cluster).context("Failed to create RPC client")?;
+    let balance = rpc_client
+        .get_balance(&keypair.pubkey())
+        .await
+        .with_context(|| format!("Failed to get balance on {}", cluster))?;
+    if balance > 0 {
+        println!(
+            "Balance on {}: {:.9} SOL",
+            cluster,
+            balance as f64 / LAMPORTS_PER_SOL as f64
+        );
+        return Ok(());
+    }
+
+    if !airdrop_if_empty {
+        println!(
+            "warning: Account not funded on target cluster, run `antegenctl fund` after import"
+        );
+        return Ok(());
+    }
+    antegen_cli_core::commands::client::airdrop(
+        config_path,
+        vec![keypair_path],
+        IMPORT_AIRDROP_SOL,
+        Some(cluster),
+    )
+    .await
+}
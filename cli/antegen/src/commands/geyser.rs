@@ -1,14 +1,16 @@
 //! Geyser plugin commands
 
+use antegen_cli_core::abi::{self, AbiCompatibility};
 use antegen_cli_core::download::{
     current_version, download_geyser_plugin, get_library_filename, needs_update, save_version_info,
+    verify_geyser_plugin,
 };
 use antegen_client::ClientConfig;
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
 /// Initialize Geyser plugin for validator
-pub async fn init(output: PathBuf, config_path: PathBuf) -> Result<()> {
+pub async fn init(output: PathBuf, config_path: PathBuf, skip_abi_check: bool) -> Result<()> {
     println!("Initializing Geyser plugin...");
 
     // Determine plugin directory
@@ -33,6 +35,15 @@ pub async fn init(output: PathBuf, config_path: PathBuf) -> Result<()> {
     }
     println!("  Plugin: {}", so_path.display());
 
+    // The validator loads libpath as its own user
+    abi::check_libpath_readable(&so_path)?;
+
+    if skip_abi_check {
+        println!("  Skipping ABI compatibility check");
+    } else {
+        check_abi(&so_path, true)?;
+    }
+
     // Determine config file path
     // If default "antegen.toml", put it in the plugin directory
     // Otherwise use the user-specified path
@@ -84,15 +95,82 @@ pub async fn init(output: PathBuf, config_path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Compare the agave version the plugin was built against with the host's
+/// `solana --version`
+///
+/// A major/minor mismatch is an error when `strict`, otherwise a warning.
+fn check_abi(so_path: &Path, strict: bool) -> Result<()> {
+    let Some(info) = abi::read_plugin_abi(so_path)? else {
+        println!("  Warning: plugin has no embedded ABI metadata, skipping compatibility check");
+        return Ok(());
+    };
+
+    let host = match abi::host_solana_version() {
+        Ok(version) => version,
+        Err(e) => {
+            println!("  Warning: could not determine validator version: {}", e);
+            return Ok(());
+        }
+    };
+
+    match abi::check_compatibility(&info.agave_version, &host) {
+        AbiCompatibility::Compatible => {
+            println!("  ABI check: plugin built for agave {}, matches host", host);
+            Ok(())
+        }
+        AbiCompatibility::PatchMismatch { plugin, host } => {
+            println!(
+                "  Warning: plugin built for agave {}, host is {} (patch mismatch)",
+                plugin, host
+            );
+            Ok(())
+        }
+        AbiCompatibility::Incompatible { plugin, host } if strict => Err(anyhow::anyhow!(
+            "Plugin was built for agave {} but the host validator is {}; \
+             the validator will likely fail to load it. \
+             Use --skip-abi-check to write the config anyway.",
+            plugin,
+            host
+        )),
+        AbiCompatibility::Incompatible { plugin, host } => {
+            println!(
+                "  Warning: plugin built for agave {}, host is {}; the validator will likely fail to load it",
+                plugin, host
+            );
+            Ok(())
+        }
+        AbiCompatibility::Unknown { reason } => {
+            println!("  Warning: skipping ABI check, {}", reason);
+            Ok(())
+        }
+    }
+}
+
 /// Extract plugin .so to custom location
-pub async fn extract(output: PathBuf) -> Result<()> {
+///
+/// With `verify`, the file is checked against the release's checksum
+/// manifest and removed on mismatch. With `check_abi_version`, an agave
+/// version mismatch with the host is reported as a warning.
+pub async fn extract(output: PathBuf, verify: bool, check_abi_version: bool) -> Result<()> {
     println!("Downloading Geyser plugin...");
 
     let version = current_version();
     download_geyser_plugin(version, &output).await?;
+
+    if verify {
+        if let Err(e) = verify_geyser_plugin(version, &output).await {
+            let _ = std::fs::remove_file(&output);
+            return Err(e);
+        }
+        println!("  Checksum verified against the {} release", version);
+    }
     save_version_info(&output, version)?;
 
     println!("  Extracted plugin to: {}", output.display());
 
+    if check_abi_version {
+        check_abi(&output, false)?;
+    }
+
     Ok(())
 }
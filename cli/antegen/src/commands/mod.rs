@@ -1,3 +1,5 @@
+pub mod client;
 pub mod geyser;
 pub mod program;
+pub mod simulate;
 pub mod thread;
@@ -1,20 +1,25 @@
-//! Program configuration and deployment commands
+//! Program configuration, deployment and upgrade commands
 //!
 //! Commands for managing the thread program's global configuration and deploying via Anchor.
 
 use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
 use antegen_client::rpc::RpcPool;
-use antegen_thread_program::state::ThreadConfig;
+use antegen_thread_program::state::{ThreadConfig, CURRENT_CONFIG_VERSION};
 use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::Serialize;
 use solana_sdk::{
-    instruction::Instruction, message::Message, pubkey::Pubkey, signer::Signer,
-    transaction::Transaction,
+    instruction::Instruction, message::Message, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signature, signer::Signer, transaction::Transaction,
 };
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
+use std::time::Duration;
 
 use antegen_cli_core::commands::{get_keypair, get_rpc_url};
+use antegen_cli_core::output::{as_string, OutputFormat};
 
 // =============================================================================
 // Deploy helpers
@@ -156,6 +161,106 @@ async fn verify_program(pubkey: Pubkey, rpc_url: &str) -> Result<()> {
     Ok(())
 }
 
+// =============================================================================
+// ThreadConfig init after deploy
+// =============================================================================
+
+/// How `deploy` retries ThreadConfig init while the program comes up
+#[derive(Debug, Clone, Copy)]
+pub struct InitRetry {
+    /// Total attempts, including the first
+    pub attempts: u32,
+    /// Delay between attempts
+    pub delay: Duration,
+}
+
+/// How a failed ThreadConfig init attempt should be handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InitFailure {
+    /// The program isn't deployed or executable yet; try again
+    NotDeployed,
+    /// The config account already exists; nothing left to do
+    AlreadyInitialized,
+    /// Permission errors and anything unrecognized; retrying won't help
+    Fatal,
+}
+
+impl InitFailure {
+    fn classify(error: &str) -> Self {
+        const NOT_DEPLOYED: &[&str] = &[
+            "program that does not exist",
+            "ProgramAccountNotFound",
+            "InvalidProgramForExecution",
+            "is not executable",
+            "Blockhash not found",
+            "Connection refused",
+        ];
+        const ALREADY_INITIALIZED: &[&str] = &["already in use", "AccountAlreadyInUse"];
+
+        if ALREADY_INITIALIZED.iter().any(|m| error.contains(m)) {
+            Self::AlreadyInitialized
+        } else if NOT_DEPLOYED.iter().any(|m| error.contains(m)) {
+            Self::NotDeployed
+        } else {
+            Self::Fatal
+        }
+    }
+}
+
+/// Run `init` until it succeeds or fails for good
+///
+/// "Already initialized" counts as success. Not-yet-deployed errors are
+/// retried up to `retry.attempts` times; anything else fails immediately.
+async fn retry_config_init<F, Fut>(retry: InitRetry, mut init: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let attempts = retry.attempts.max(1);
+    for attempt in 1..=attempts {
+        let Err(e) = init().await else {
+            return Ok(());
+        };
+        match InitFailure::classify(&format!("{:#}", e)) {
+            InitFailure::AlreadyInitialized => {
+                println!("ThreadConfig already initialized");
+                return Ok(());
+            }
+            InitFailure::NotDeployed if attempt < attempts => {
+                println!(
+                    "Thread program not ready ({}), retrying in {}s ({}/{})",
+                    e,
+                    retry.delay.as_secs_f32(),
+                    attempt,
+                    attempts
+                );
+                tokio::time::sleep(retry.delay).await;
+            }
+            InitFailure::NotDeployed => {
+                return Err(e.context(format!(
+                    "Thread program still not deployed after {} attempts",
+                    attempts
+                )));
+            }
+            InitFailure::Fatal => return Err(e.context("ThreadConfig init failed")),
+        }
+    }
+    unreachable!("the last attempt always returns")
+}
+
+/// Initialize the ThreadConfig after deploying the thread program
+async fn init_after_deploy(
+    rpc_url: &str,
+    keypair_path: &Option<PathBuf>,
+    retry: InitRetry,
+) -> Result<()> {
+    retry_config_init(retry, || {
+        config_init(Some(rpc_url.to_string()), keypair_path.clone())
+    })
+    .await
+    .map_err(|e| e.context("Run it manually once fixed: antegen program config init"))
+}
+
 // =============================================================================
 // Deploy command
 // =============================================================================
@@ -167,6 +272,10 @@ async fn verify_program(pubkey: Pubkey, rpc_url: &str) -> Result<()> {
 /// - **Full deploy**: omit the binary → auto-discovers fiber + thread from
 ///   `target/deploy/`, resolves keypairs from `--keys-dir`, and deploys both
 ///   in dependency order (fiber → thread → ThreadConfig init).
+///
+/// ThreadConfig init is retried per `init_retry` while the program comes up;
+/// a failure that retrying can't fix fails the deploy.
+#[allow(clippy::too_many_arguments)]
 pub async fn deploy(
     program_binary: Option<PathBuf>,
     rpc: Option<String>,
@@ -175,6 +284,7 @@ pub async fn deploy(
     keys_dir: Option<PathBuf>,
     skip_init: bool,
     skip_verify: bool,
+    init_retry: InitRetry,
 ) -> Result<()> {
     check_solana_cli()?;
 
@@ -190,13 +300,7 @@ pub async fn deploy(
         if !skip_init {
             if let DetectedProgram::Thread = detected {
                 println!("\n--- Initializing ThreadConfig ---");
-                match config_init(Some(rpc_url.clone()), keypair_path.clone()).await {
-                    Ok(()) => {}
-                    Err(e) => {
-                        println!("Warning: config init failed: {}", e);
-                        println!("You can run it manually: antegen program config init");
-                    }
-                }
+                init_after_deploy(&rpc_url, &keypair_path, init_retry).await?;
             }
         } else {
             println!("\nSkipping config init (--skip-init)");
@@ -290,13 +394,7 @@ pub async fn deploy(
         // Step 3: init
         if !skip_init {
             println!("\n--- Step 3/3: Initializing ThreadConfig ---");
-            match config_init(Some(rpc_url.clone()), keypair_path.clone()).await {
-                Ok(()) => {}
-                Err(e) => {
-                    println!("Warning: config init failed: {}", e);
-                    println!("You can run it manually: antegen program config init");
-                }
-            }
+            init_after_deploy(&rpc_url, &keypair_path, init_retry).await?;
         } else {
             println!("\nSkipping config init (--skip-init)");
         }
@@ -306,6 +404,293 @@ pub async fn deploy(
     Ok(())
 }
 
+// =============================================================================
+// Upgrade command
+// =============================================================================
+
+/// Bytes an upgradeable loader buffer holds ahead of the program data
+const BUFFER_METADATA_SIZE: usize = 37;
+
+/// How long `upgrade` waits for the upgrade transaction to confirm
+const UPGRADE_CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A program `antegen program upgrade` can upgrade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UpgradeProgram {
+    Thread,
+    Fiber,
+}
+
+impl UpgradeProgram {
+    pub fn id(self) -> Pubkey {
+        match self {
+            Self::Thread => antegen_thread_program::ID,
+            Self::Fiber => antegen_fiber_program::ID,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Thread => "thread",
+            Self::Fiber => "fiber",
+        }
+    }
+}
+
+/// Check that `binary` is an ELF that declares `program_id`
+///
+/// `declare_id!` compiles the id into the binary's read-only data, so a
+/// build for another program (or another cluster's id) won't contain it.
+fn verify_program_binary(binary: &[u8], program_id: &Pubkey) -> Result<()> {
+    if !binary.starts_with(b"\x7fELF") {
+        return Err(anyhow!("not an ELF program binary"));
+    }
+    let id = program_id.to_bytes();
+    if !binary.windows(id.len()).any(|window| window == id) {
+        return Err(anyhow!(
+            "binary does not declare program id {}; was it built for this program?",
+            program_id
+        ));
+    }
+    Ok(())
+}
+
+/// Program address an Anchor IDL was generated for
+fn idl_address(idl: &str) -> Result<Pubkey> {
+    let idl: serde_json::Value =
+        serde_json::from_str(idl).map_err(|e| anyhow!("invalid IDL JSON: {}", e))?;
+    let address = idl
+        .get("address")
+        .and_then(|a| a.as_str())
+        .ok_or_else(|| anyhow!("IDL has no \"address\" field"))?;
+    Pubkey::from_str(address).map_err(|e| anyhow!("invalid IDL address '{}': {}", address, e))
+}
+
+/// Whether an RPC URL points at mainnet
+fn is_mainnet_url(url: &str) -> bool {
+    url.to_lowercase().contains("mainnet")
+}
+
+/// Lamports to keep a buffer holding `program_len` bytes rent exempt
+fn buffer_rent(program_len: usize) -> u64 {
+    anchor_lang::prelude::Rent::default().minimum_balance(BUFFER_METADATA_SIZE + program_len)
+}
+
+/// Shared `--url`/`--keypair` arguments for solana and anchor subprocesses
+fn cluster_args(rpc_url: &str, keypair_path: &Option<PathBuf>) -> Vec<String> {
+    let mut args = vec!["--url".to_string(), rpc_url.to_string()];
+    if let Some(kp) = keypair_path {
+        args.push("--keypair".into());
+        args.push(kp.to_string_lossy().to_string());
+    }
+    args
+}
+
+/// Run `solana <args> --output json` and parse what it prints
+fn run_solana_json(args: &[String]) -> Result<serde_json::Value> {
+    let output = Command::new("solana")
+        .args(args)
+        .args(["--output", "json"])
+        .output()
+        .map_err(|e| anyhow!("Failed to run 'solana {}': {}", args[..2].join(" "), e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "'solana {}' failed with status {}: {}",
+            args[..2].join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        anyhow!(
+            "Unexpected output from 'solana {}' ({}): {}",
+            args[..2].join(" "),
+            e,
+            String::from_utf8_lossy(&output.stdout).trim()
+        )
+    })
+}
+
+/// Wait until `signature` confirms, failing if it landed with an error
+async fn wait_for_signature(client: &RpcPool, signature: &Signature) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + UPGRADE_CONFIRM_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        match client.get_signature_status(signature).await {
+            Ok(Some(Ok(()))) => return Ok(()),
+            Ok(Some(Err(e))) => return Err(anyhow!("Transaction {} failed: {}", signature, e)),
+            Ok(None) | Err(_) => tokio::time::sleep(Duration::from_secs(2)).await,
+        }
+    }
+    Err(anyhow!(
+        "Transaction {} not confirmed after {}s",
+        signature,
+        UPGRADE_CONFIRM_TIMEOUT.as_secs()
+    ))
+}
+
+/// Upgrade a deployed program from a new build, then publish its IDL.
+///
+/// Writes the binary to a buffer with `solana program write-buffer`,
+/// upgrades the program from it, waits for the upgrade to confirm and
+/// finally runs `anchor idl upgrade` when `idl` is given.
+///
+/// Before sending anything the binary must declare the program's id and the
+/// IDL must name the same address. Mainnet RPC URLs need `yes`. A dry run
+/// stops after the checks and prints what would be sent.
+#[allow(clippy::too_many_arguments)]
+pub async fn upgrade(
+    program: UpgradeProgram,
+    so: PathBuf,
+    idl: Option<PathBuf>,
+    yes: bool,
+    dry_run: bool,
+    rpc: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    let program_id = program.id();
+    let rpc_url = get_rpc_url(rpc)?;
+    println!("Program: {} ({})", program.name(), program_id);
+    println!("RPC: {}", rpc_url);
+
+    let binary =
+        std::fs::read(&so).map_err(|e| anyhow!("Failed to read {}: {}", so.display(), e))?;
+    verify_program_binary(&binary, &program_id).map_err(|e| anyhow!("{}: {}", so.display(), e))?;
+    println!("Binary: {} ({} bytes)", so.display(), binary.len());
+
+    if let Some(idl) = &idl {
+        let text = std::fs::read_to_string(idl)
+            .map_err(|e| anyhow!("Failed to read {}: {}", idl.display(), e))?;
+        let address = idl_address(&text).map_err(|e| anyhow!("{}: {}", idl.display(), e))?;
+        if address != program_id {
+            return Err(anyhow!(
+                "{} is for {}, not the {} program {}",
+                idl.display(),
+                address,
+                program.name(),
+                program_id
+            ));
+        }
+        println!("IDL: {}", idl.display());
+    }
+
+    let rent = buffer_rent(binary.len());
+    println!(
+        "Buffer rent: {:.9} SOL (returned to the payer by the upgrade)",
+        rent as f64 / LAMPORTS_PER_SOL as f64
+    );
+
+    if is_mainnet_url(&rpc_url) && !yes && !dry_run {
+        return Err(anyhow!(
+            "{} looks like mainnet; pass --yes to upgrade the {} program there",
+            rpc_url,
+            program.name()
+        ));
+    }
+
+    let cluster = cluster_args(&rpc_url, &keypair_path);
+    let mut write_buffer: Vec<String> = vec![
+        "program".into(),
+        "write-buffer".into(),
+        so.to_string_lossy().to_string(),
+    ];
+    write_buffer.extend(cluster.iter().cloned());
+
+    if dry_run {
+        println!("\nDry run, nothing sent. Would run:");
+        println!("  solana {}", write_buffer.join(" "));
+        println!(
+            "  solana program upgrade <BUFFER> {} --url {}",
+            program_id, rpc_url
+        );
+        if let Some(idl) = &idl {
+            println!(
+                "  anchor idl upgrade {} --filepath {}",
+                program_id,
+                idl.display()
+            );
+        }
+        return Ok(());
+    }
+
+    check_solana_cli()?;
+
+    println!("\n--- Step 1/3: Writing buffer ---");
+    let output = run_solana_json(&write_buffer)?;
+    let buffer = output
+        .get("buffer")
+        .and_then(|b| b.as_str())
+        .ok_or_else(|| {
+            anyhow!(
+                "'solana program write-buffer' printed no buffer: {}",
+                output
+            )
+        })?
+        .to_string();
+    println!("Buffer: {}", buffer);
+
+    println!("\n--- Step 2/3: Upgrading program ---");
+    let mut upgrade_args: Vec<String> = vec![
+        "program".into(),
+        "upgrade".into(),
+        buffer.clone(),
+        program_id.to_string(),
+    ];
+    upgrade_args.extend(cluster.iter().cloned());
+    let output = run_solana_json(&upgrade_args).map_err(|e| {
+        e.context(format!(
+            "Buffer {} still holds the program; close it with 'solana program close {}'",
+            buffer, buffer
+        ))
+    })?;
+    let signature = output
+        .get("signature")
+        .and_then(|s| s.as_str())
+        .ok_or_else(|| anyhow!("'solana program upgrade' printed no signature: {}", output))?;
+    let signature = Signature::from_str(signature)
+        .map_err(|e| anyhow!("Invalid upgrade signature '{}': {}", signature, e))?;
+    println!("Upgrade transaction: {}", signature);
+
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+    wait_for_signature(&client, &signature).await?;
+    println!("Upgrade confirmed.");
+
+    match &idl {
+        Some(idl) => {
+            println!("\n--- Step 3/3: Publishing IDL ---");
+            let mut args: Vec<String> = vec![
+                "idl".into(),
+                "upgrade".into(),
+                program_id.to_string(),
+                "--filepath".into(),
+                idl.to_string_lossy().to_string(),
+                "--provider.cluster".into(),
+                rpc_url.clone(),
+            ];
+            if let Some(kp) = &keypair_path {
+                args.push("--provider.wallet".into());
+                args.push(kp.to_string_lossy().to_string());
+            }
+            // anchor prints the signatures of the IDL transactions it sends
+            let status = Command::new("anchor")
+                .args(&args)
+                .status()
+                .map_err(|e| anyhow!("Failed to run 'anchor idl upgrade': {}", e))?;
+            if !status.success() {
+                return Err(anyhow!(
+                    "'anchor idl upgrade' failed with status {}; the program is already \
+                     upgraded, rerun it once fixed",
+                    status
+                ));
+            }
+        }
+        None => println!("\nSkipping IDL publishing (no --idl)"),
+    }
+
+    println!("\nDone.");
+    Ok(())
+}
+
 // =============================================================================
 // Config commands
 // =============================================================================
@@ -374,35 +759,77 @@ pub async fn config_init(rpc: Option<String>, keypair_path: Option<PathBuf>) ->
 }
 
 /// Display the current ThreadConfig
-pub async fn config_get(rpc: Option<String>) -> Result<()> {
+pub async fn config_get(rpc: Option<String>, output: OutputFormat) -> Result<()> {
     let rpc_url = get_rpc_url(rpc)?;
-    println!("RPC: {}", rpc_url);
+    output.note(format_args!("RPC: {}", rpc_url));
 
     let client =
         RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
 
     let config_pubkey = ThreadConfig::pubkey();
-    println!("Config PDA: {}", config_pubkey);
+    output.note(format_args!("Config PDA: {}", config_pubkey));
 
-    let account = client
-        .get_account(&config_pubkey)
-        .await
-        .map_err(|e| anyhow!("Failed to fetch config: {}", e))?
-        .ok_or_else(|| {
-            anyhow!("ThreadConfig not found. Run 'antegen program config init' to initialize.")
-        })?;
+    let config = fetch_config(&client).await?;
+    let result = ConfigOutput::new(&config_pubkey, &config);
+    output.print(&result, |_| print_config(&config))
+}
 
-    let data = account
-        .decode_data()
-        .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
+/// `program config get` result for JSON output
+#[derive(Serialize)]
+struct ConfigOutput {
+    address: String,
+    version: u64,
+    bump: u8,
+    admin: String,
+    pending_admin: Option<String>,
+    paused: bool,
+    #[serde(with = "as_string")]
+    commission_fee: u64,
+    executor_fee_bps: u64,
+    core_team_bps: u64,
+    waive_core_team_on_forgo: bool,
+    fee_payer_mode: String,
+    #[serde(with = "as_string")]
+    base_fee_lamports: u64,
+    max_chain_depth: u8,
+    max_fiber_failures: u8,
+    grace_period_seconds: i64,
+    fee_decay_seconds: i64,
+    streak_half_life_execs: u64,
+}
 
-    let config = ThreadConfig::try_deserialize(&mut data.as_slice())
-        .map_err(|e| anyhow!("Failed to deserialize ThreadConfig: {}", e))?;
+impl ConfigOutput {
+    fn new(address: &Pubkey, config: &ThreadConfig) -> Self {
+        Self {
+            address: address.to_string(),
+            version: config.version,
+            bump: config.bump,
+            admin: config.admin.to_string(),
+            pending_admin: config.pending_admin.map(|admin| admin.to_string()),
+            paused: config.paused,
+            commission_fee: config.commission_fee,
+            executor_fee_bps: config.executor_fee_bps,
+            core_team_bps: config.core_team_bps,
+            waive_core_team_on_forgo: config.waive_core_team_on_forgo,
+            fee_payer_mode: format!("{:?}", config.fee_payer_mode),
+            base_fee_lamports: config.base_fee_lamports,
+            max_chain_depth: config.max_chain_depth,
+            max_fiber_failures: config.max_fiber_failures,
+            grace_period_seconds: config.grace_period_seconds,
+            fee_decay_seconds: config.fee_decay_seconds,
+            streak_half_life_execs: config.streak_half_life_execs,
+        }
+    }
+}
 
+fn print_config(config: &ThreadConfig) {
     println!("\n=== ThreadConfig ===");
     println!("Version: {}", config.version);
     println!("Bump: {}", config.bump);
     println!("Admin: {}", config.admin);
+    if let Some(pending_admin) = config.pending_admin {
+        println!("Pending Admin: {} (awaiting accept)", pending_admin);
+    }
     println!("Paused: {}", config.paused);
     println!();
     println!("=== Commission Settings ===");
@@ -417,6 +844,24 @@ pub async fn config_get(rpc: Option<String>) -> Result<()> {
         config.core_team_bps / 100,
         config.core_team_bps
     );
+    println!(
+        "Waive Core Team Fee on Forgo: {}",
+        config.waive_core_team_on_forgo
+    );
+    println!("Fee Payer Mode: {:?}", config.fee_payer_mode);
+    println!("Base Fee: {} lamports", config.base_fee_lamports);
+    println!(
+        "Max Chain Depth: {} fibers per slot",
+        config.max_chain_depth
+    );
+    if config.max_fiber_failures == 0 {
+        println!("Max Fiber Failures: disabled");
+    } else {
+        println!(
+            "Max Fiber Failures: {} reported failures",
+            config.max_fiber_failures
+        );
+    }
     println!();
     println!("=== Timing ===");
     println!("Grace Period: {} seconds", config.grace_period_seconds);
@@ -425,6 +870,411 @@ pub async fn config_get(rpc: Option<String>) -> Result<()> {
         "Total Window: {} seconds",
         config.grace_period_seconds + config.fee_decay_seconds
     );
+    if config.streak_half_life_execs == 0 {
+        println!("Streak Discount: disabled");
+    } else {
+        println!(
+            "Streak Discount: executor commission halves every {} consecutive execs",
+            config.streak_half_life_execs
+        );
+    }
+}
 
+/// Fetch and deserialize the ThreadConfig account
+async fn fetch_config(client: &RpcPool) -> Result<ThreadConfig> {
+    let account = client
+        .get_account(&ThreadConfig::pubkey())
+        .await
+        .map_err(|e| anyhow!("Failed to fetch config: {}", e))?
+        .ok_or_else(|| {
+            anyhow!("ThreadConfig not found. Run 'antegen program config init' to initialize.")
+        })?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
+    ThreadConfig::try_deserialize(&mut data.as_slice())
+        .map_err(|e| {
+            anyhow!(
+                "Failed to deserialize ThreadConfig (older layouts need 'antegen program config migrate'): {}",
+                e
+            )
+        })
+}
+
+/// Sign and send a single config instruction, returning the signature
+async fn send_config_instruction(
+    client: &RpcPool,
+    signer: &solana_sdk::signature::Keypair,
+    ix: Instruction,
+) -> Result<solana_sdk::signature::Signature> {
+    let (blockhash, _) = client.get_latest_blockhash().await?;
+    let message = Message::new(&[ix], Some(&signer.pubkey()));
+    let tx = Transaction::new(&[signer], message, blockhash);
+    client.send_and_confirm_transaction(&tx).await
+}
+
+/// Propose a new ThreadConfig admin (step one of the handover)
+///
+/// The proposed admin takes over only after running `config accept-admin`
+/// with their own keypair, so a mistyped address cannot lock out control.
+pub async fn config_set_admin(
+    new_admin: String,
+    rpc: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    let new_admin =
+        Pubkey::from_str(&new_admin).map_err(|e| anyhow!("Invalid admin pubkey: {}", e))?;
+    let rpc_url = get_rpc_url(rpc)?;
+    let admin = get_keypair(keypair_path)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let config = fetch_config(&client).await?;
+    if config.admin != admin.pubkey() {
+        return Err(anyhow!(
+            "{} is not the config admin ({})",
+            admin.pubkey(),
+            config.admin
+        ));
+    }
+    if let Some(pending_admin) = config.pending_admin {
+        println!("Replacing pending proposal for {}", pending_admin);
+    }
+
+    let ix = Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts: antegen_thread_program::accounts::ConfigProposeAdmin {
+            admin: admin.pubkey(),
+            config: ThreadConfig::pubkey(),
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::ProposeAdmin { new_admin }.data(),
+    };
+    let sig = send_config_instruction(&client, &admin, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to propose admin: {}", e))?;
+
+    println!("Admin handover proposed to {}", new_admin);
+    println!("Transaction: {}", sig);
+    println!("\nThe new admin must run 'antegen program config accept-admin' with their keypair.");
     Ok(())
 }
+
+/// Migrate the ThreadConfig account to the current layout
+///
+/// Configs written by an older program release are grown to the current size
+/// (the admin pays the added rent) and their newer fields set to defaults.
+pub async fn config_migrate(
+    dry_run: bool,
+    rpc: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    let rpc_url = get_rpc_url(rpc)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let config_pubkey = ThreadConfig::pubkey();
+    let account = client
+        .get_account(&config_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch config: {}", e))?
+        .ok_or_else(|| {
+            anyhow!("ThreadConfig not found. Run 'antegen program config init' to initialize.")
+        })?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
+
+    // The version follows the discriminator in every layout
+    let version = data
+        .get(8..16)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| anyhow!("ThreadConfig account is too short"))?;
+    if version >= CURRENT_CONFIG_VERSION && data.len() >= ThreadConfig::space() {
+        println!("ThreadConfig is already at version {}", version);
+        return Ok(());
+    }
+
+    println!(
+        "ThreadConfig at version {} ({} bytes); current is version {} ({} bytes)",
+        version,
+        data.len(),
+        CURRENT_CONFIG_VERSION,
+        ThreadConfig::space()
+    );
+    if dry_run {
+        println!("Dry run: no transaction sent.");
+        return Ok(());
+    }
+
+    let admin = get_keypair(keypair_path)?;
+    let ix = Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts: antegen_thread_program::accounts::ConfigMigrate {
+            admin: admin.pubkey(),
+            config: config_pubkey,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::MigrateConfig {}.data(),
+    };
+    let sig = send_config_instruction(&client, &admin, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to migrate config: {}", e))?;
+
+    println!(
+        "ThreadConfig migrated to version {}",
+        CURRENT_CONFIG_VERSION
+    );
+    println!("Transaction: {}", sig);
+    Ok(())
+}
+
+/// Accept a pending admin proposal with the proposed admin's keypair
+pub async fn config_accept_admin(rpc: Option<String>, keypair_path: Option<PathBuf>) -> Result<()> {
+    let rpc_url = get_rpc_url(rpc)?;
+    let pending_admin = get_keypair(keypair_path)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let config = fetch_config(&client).await?;
+    match config.pending_admin {
+        Some(pending) if pending == pending_admin.pubkey() => {}
+        Some(pending) => {
+            return Err(anyhow!(
+                "Pending admin is {}, not {}",
+                pending,
+                pending_admin.pubkey()
+            ))
+        }
+        None => return Err(anyhow!("No admin handover is pending")),
+    }
+
+    let ix = Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts: antegen_thread_program::accounts::ConfigAcceptAdmin {
+            pending_admin: pending_admin.pubkey(),
+            config: ThreadConfig::pubkey(),
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::AcceptAdmin {}.data(),
+    };
+    let sig = send_config_instruction(&client, &pending_admin, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to accept admin: {}", e))?;
+
+    println!(
+        "Config admin changed from {} to {}",
+        config.admin,
+        pending_admin.pubkey()
+    );
+    println!("Transaction: {}", sig);
+    Ok(())
+}
+
+/// Withdraw a pending admin proposal
+pub async fn config_cancel_admin(rpc: Option<String>, keypair_path: Option<PathBuf>) -> Result<()> {
+    let rpc_url = get_rpc_url(rpc)?;
+    let admin = get_keypair(keypair_path)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let config = fetch_config(&client).await?;
+    let pending_admin = config
+        .pending_admin
+        .ok_or_else(|| anyhow!("No admin handover is pending"))?;
+
+    let ix = Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts: antegen_thread_program::accounts::ConfigCancelAdmin {
+            admin: admin.pubkey(),
+            config: ThreadConfig::pubkey(),
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::CancelAdminProposal {}.data(),
+    };
+    let sig = send_config_instruction(&client, &admin, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to cancel admin proposal: {}", e))?;
+
+    println!("Admin handover to {} cancelled", pending_admin);
+    println!("Transaction: {}", sig);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use antegen_thread_program::state::FeePayerMode;
+
+    #[test]
+    fn test_config_output_json() {
+        let config = ThreadConfig {
+            version: 1,
+            bump: 254,
+            admin: Pubkey::new_unique(),
+            paused: true,
+            commission_fee: 1000,
+            executor_fee_bps: 9000,
+            core_team_bps: 1000,
+            grace_period_seconds: 5,
+            fee_decay_seconds: 295,
+            waive_core_team_on_forgo: false,
+            fee_payer_mode: FeePayerMode::ThreadReimburses,
+            base_fee_lamports: 5000,
+            pending_admin: None,
+            max_chain_depth: 16,
+            max_fiber_failures: 0,
+            streak_half_life_execs: 1_000,
+        };
+        let address = ThreadConfig::pubkey();
+        let json = serde_json::to_string(&ConfigOutput::new(&address, &config)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["address"], address.to_string());
+        assert_eq!(value["admin"], config.admin.to_string());
+        assert_eq!(value["paused"], true);
+        assert_eq!(value["pending_admin"], serde_json::Value::Null);
+        assert_eq!(value["commission_fee"], "1000"); // lamports as strings
+        assert_eq!(value["executor_fee_bps"], 9000);
+        assert_eq!(value["core_team_bps"], 1000);
+        assert_eq!(value["grace_period_seconds"], 5);
+        assert_eq!(value["fee_payer_mode"], "ThreadReimburses");
+    }
+
+    #[test]
+    fn test_classify_init_failure() {
+        assert_eq!(
+            InitFailure::classify(
+                "Failed to initialize config: Allocate: account Address { address: x, base: None } already in use"
+            ),
+            InitFailure::AlreadyInitialized
+        );
+        assert_eq!(
+            InitFailure::classify("Attempt to load a program that does not exist"),
+            InitFailure::NotDeployed
+        );
+        assert_eq!(
+            InitFailure::classify("Transaction signature verification failure"),
+            InitFailure::Fatal
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_already_initialized_is_success() {
+        let retry = InitRetry {
+            attempts: 3,
+            delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let result = retry_config_init(retry, || {
+            calls += 1;
+            async {
+                Err(anyhow!(
+                    "Failed to initialize config: custom program error: 0x0 (account already in use)"
+                ))
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_init_retries_until_deployed_then_fails_loudly() {
+        let retry = InitRetry {
+            attempts: 3,
+            delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let result = retry_config_init(retry, || {
+            calls += 1;
+            let deployed = calls == 2;
+            async move {
+                if deployed {
+                    Ok(())
+                } else {
+                    Err(anyhow!("Attempt to load a program that does not exist"))
+                }
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 2);
+
+        // Never deployed: every attempt is used, then the error surfaces
+        let mut calls = 0;
+        let result = retry_config_init(retry, || {
+            calls += 1;
+            async { Err(anyhow!("Attempt to load a program that does not exist")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        // Permission errors are not retried
+        let mut calls = 0;
+        let result = retry_config_init(retry, || {
+            calls += 1;
+            async { Err(anyhow!("missing required signature for instruction")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    /// A minimal stand-in for an SBF binary with `id` in its data
+    fn fake_binary(id: &Pubkey) -> Vec<u8> {
+        let mut binary = b"\x7fELF".to_vec();
+        binary.extend_from_slice(&[0; 64]);
+        binary.extend_from_slice(id.as_ref());
+        binary.extend_from_slice(&[0; 16]);
+        binary
+    }
+
+    #[test]
+    fn test_verify_program_binary() {
+        let id = antegen_thread_program::ID;
+        assert!(verify_program_binary(&fake_binary(&id), &id).is_ok());
+
+        // Built for another program
+        let err = verify_program_binary(&fake_binary(&antegen_fiber_program::ID), &id)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("does not declare program id"), "{}", err);
+
+        // Not an ELF at all
+        let mut not_elf = fake_binary(&id);
+        not_elf[0] = 0;
+        assert!(verify_program_binary(&not_elf, &id).is_err());
+    }
+
+    #[test]
+    fn test_idl_address() {
+        let id = antegen_thread_program::ID;
+        let idl = format!(
+            r#"{{"address":"{}","metadata":{{"name":"antegen_thread_program"}}}}"#,
+            id
+        );
+        assert_eq!(idl_address(&idl).unwrap(), id);
+
+        assert!(idl_address(r#"{"metadata":{}}"#).is_err());
+        assert!(idl_address(r#"{"address":"not-a-pubkey"}"#).is_err());
+        assert!(idl_address("not json").is_err());
+    }
+
+    #[test]
+    fn test_upgrade_safety_rails() {
+        assert!(is_mainnet_url("https://api.mainnet-beta.solana.com"));
+        assert!(is_mainnet_url("https://Mainnet.helius-rpc.com/?api-key=x"));
+        assert!(!is_mainnet_url("https://api.devnet.solana.com"));
+        assert!(!is_mainnet_url("http://127.0.0.1:8899"));
+
+        // Rent covers the buffer header as well as the program
+        assert!(buffer_rent(1_000) > buffer_rent(0));
+        assert_eq!(
+            buffer_rent(0),
+            anchor_lang::prelude::Rent::default().minimum_balance(BUFFER_METADATA_SIZE)
+        );
+    }
+}
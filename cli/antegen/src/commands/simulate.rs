@@ -0,0 +1,375 @@
+//! `antegen thread simulate`
+//!
+//! Runs a thread's next exec against a local bank seeded with accounts cloned
+//! from the cluster, then reports the logs, compute units and what changed.
+//! The bank comes from `solana-program-test`, which is heavy, so the command
+//! is only functional with the `simulate` feature.
+
+use antegen_cli_core::output::OutputFormat;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Accounts and programs to clone beyond what the exec references
+#[cfg_attr(not(feature = "simulate"), allow(dead_code))]
+pub struct SimulateOptions {
+    /// Extra accounts to clone, e.g. ones a CPI reads that the fiber doesn't list
+    pub clone: Vec<String>,
+    /// Thread program binary to load instead of the deployed one
+    pub thread_program: Option<PathBuf>,
+    /// Fiber program binary to load instead of the deployed one
+    pub fiber_program: Option<PathBuf>,
+    /// Clock timestamp to run at instead of the cluster's
+    pub unix_timestamp: Option<i64>,
+}
+
+#[cfg(not(feature = "simulate"))]
+pub async fn simulate(
+    _address: String,
+    _options: SimulateOptions,
+    _rpc_url: Option<String>,
+    _output: OutputFormat,
+) -> Result<()> {
+    Err(anyhow::anyhow!(
+        "thread simulate is not built into this binary; reinstall with \
+         `cargo install antegen-cli --features simulate`"
+    ))
+}
+
+#[cfg(feature = "simulate")]
+pub use bank::simulate;
+
+#[cfg(feature = "simulate")]
+mod bank {
+    use super::*;
+    use crate::commands::thread::fetch_clock;
+    use anchor_lang::AccountDeserialize;
+    use antegen_cli_core::commands::get_rpc_url;
+    use antegen_client::helpers::ExecThreadBuilder;
+    use antegen_client::rpc::RpcPool;
+    use antegen_thread_program::fiber::Fiber;
+    use antegen_thread_program::state::{Thread, ThreadConfig};
+    use anyhow::anyhow;
+    use serde::Serialize;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_program_test::ProgramTest;
+    use solana_sdk::account::Account;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+    use solana_sdk_ids::{bpf_loader, bpf_loader_upgradeable, native_loader, sysvar};
+    use std::collections::{BTreeMap, HashSet};
+    use std::str::FromStr;
+
+    /// Most accounts `getMultipleAccounts` returns per call
+    const MAX_ACCOUNTS_PER_CALL: usize = 100;
+
+    /// Upgradeable loader program account: 4-byte state tag, then the
+    /// programdata address
+    const PROGRAM_DATA_ADDRESS: std::ops::Range<usize> = 4..36;
+
+    /// Upgradeable loader programdata header (tag, slot, optional authority)
+    /// ahead of the ELF
+    const PROGRAM_DATA_HEADER_LEN: usize = 45;
+
+    /// Lamports the simulated executor starts with
+    const EXECUTOR_LAMPORTS: u64 = 10_000_000_000;
+
+    /// `thread simulate` result for JSON output
+    #[derive(Serialize)]
+    struct SimulateOutput {
+        thread: String,
+        fiber_index: u8,
+        success: bool,
+        error: Option<String>,
+        compute_units: u64,
+        unix_timestamp: i64,
+        cloned_accounts: usize,
+        cloned_programs: usize,
+        logs: Vec<String>,
+        changes: Vec<AccountChange>,
+    }
+
+    /// A writable account's state before and after the exec
+    #[derive(Serialize)]
+    struct AccountChange {
+        address: String,
+        lamports_before: u64,
+        lamports_after: u64,
+        data_len_before: usize,
+        data_len_after: usize,
+        data_changed: bool,
+    }
+
+    pub async fn simulate(
+        address: String,
+        options: SimulateOptions,
+        rpc_url: Option<String>,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let thread_pubkey = Pubkey::from_str(&address)
+            .map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+        let extra = options
+            .clone
+            .iter()
+            .map(|s| Pubkey::from_str(s).map_err(|e| anyhow!("Invalid pubkey '{}': {}", s, e)))
+            .collect::<Result<Vec<_>>>()?;
+        let rpc_url = get_rpc_url(rpc_url)?;
+        output.note(format_args!(
+            "Simulating thread {} against {}",
+            thread_pubkey, rpc_url
+        ));
+        let client = RpcPool::with_url(&rpc_url)
+            .map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+        let thread_account = fetch_account(&client, &thread_pubkey)
+            .await?
+            .ok_or_else(|| anyhow!("Thread not found: {}", thread_pubkey))?;
+        let thread = Thread::try_deserialize(&mut thread_account.data.as_slice())
+            .map_err(|e| anyhow!("Failed to deserialize thread: {}", e))?;
+        let config_account = fetch_account(&client, &ThreadConfig::pubkey())
+            .await?
+            .ok_or_else(|| anyhow!("Thread config not found, is the program initialized?"))?;
+        let config = ThreadConfig::try_deserialize(&mut config_account.data.as_slice())
+            .map_err(|e| anyhow!("Failed to deserialize thread config: {}", e))?;
+
+        let fiber_index = thread.fiber_cursor;
+        let fiber_pubkey = thread.fiber_at_index(&thread_pubkey, fiber_index);
+        let fiber_account = fetch_account(&client, &fiber_pubkey)
+            .await?
+            .ok_or_else(|| anyhow!("Fiber {} not found: {}", fiber_index, fiber_pubkey))?;
+        let fiber = Fiber::try_deserialize(&mut fiber_account.data.as_slice())
+            .map_err(|e| anyhow!("Failed to deserialize fiber {}: {}", fiber_pubkey, e))?;
+
+        let executor = Keypair::new();
+        let exec_ix = ExecThreadBuilder::new(
+            thread_pubkey,
+            &thread,
+            fiber_index,
+            &fiber,
+            executor.pubkey(),
+        )
+        .build(&config)?
+        .ok_or_else(|| anyhow!("Fiber {} is cleared, nothing to run", fiber_index))?;
+
+        // Everything the exec touches, plus the programs the thread program
+        // calls into
+        let mut wanted: Vec<Pubkey> = Vec::new();
+        let mut seen = HashSet::from([executor.pubkey()]);
+        for pubkey in exec_ix
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .chain([
+                antegen_thread_program::ID,
+                antegen_thread_program::fiber::ID,
+            ])
+            .chain(extra)
+        {
+            if seen.insert(pubkey) {
+                wanted.push(pubkey);
+            }
+        }
+
+        let overrides = [
+            (antegen_thread_program::ID, options.thread_program.as_ref()),
+            (
+                antegen_thread_program::fiber::ID,
+                options.fiber_program.as_ref(),
+            ),
+        ];
+
+        let mut program_test = ProgramTest::default();
+        let mut before: BTreeMap<Pubkey, Account> = BTreeMap::new();
+        let mut cloned_programs = 0;
+
+        for (pubkey, path) in overrides {
+            if let Some(path) = path {
+                let elf = std::fs::read(path)
+                    .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+                program_test.add_account(pubkey, program_account(elf));
+                wanted.retain(|p| *p != pubkey);
+                cloned_programs += 1;
+            }
+        }
+
+        for chunk in wanted.chunks(MAX_ACCOUNTS_PER_CALL) {
+            let accounts = client
+                .get_multiple_accounts(chunk)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch accounts: {}", e))?;
+            for (pubkey, account) in chunk.iter().zip(accounts) {
+                // Missing accounts may be created by the exec itself
+                let Some(account) = account else {
+                    continue;
+                };
+                let account = account
+                    .to_account()
+                    .map_err(|e| anyhow!("Failed to decode account {}: {}", pubkey, e))?;
+                // Builtins and sysvars come with the bank
+                if account.owner == native_loader::ID || account.owner == sysvar::ID {
+                    continue;
+                }
+                if account.executable && account.owner == bpf_loader_upgradeable::ID {
+                    let elf = fetch_program_elf(&client, pubkey, &account).await?;
+                    program_test.add_account(*pubkey, program_account(elf));
+                    cloned_programs += 1;
+                    continue;
+                }
+                before.insert(*pubkey, account.clone());
+                program_test.add_account(*pubkey, account);
+            }
+        }
+
+        let executor_account =
+            Account::new(EXECUTOR_LAMPORTS, 0, &solana_system_interface::program::ID);
+        before.insert(executor.pubkey(), executor_account.clone());
+        program_test.add_account(executor.pubkey(), executor_account);
+
+        let mut context = program_test.start_with_context().await;
+        let mut clock = fetch_clock(&client).await?;
+        if let Some(unix_timestamp) = options.unix_timestamp {
+            clock.unix_timestamp = unix_timestamp;
+        }
+        context.set_sysvar(&clock);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                exec_ix.clone(),
+            ],
+            Some(&executor.pubkey()),
+            &[&executor],
+            context.last_blockhash,
+        );
+        let result = context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .map_err(|e| anyhow!("Failed to run the exec: {}", e))?;
+        let (logs, compute_units) = result
+            .metadata
+            .map(|m| (m.log_messages, m.compute_units_consumed))
+            .unwrap_or_default();
+
+        let mut changes = Vec::new();
+        let mut reported = HashSet::new();
+        for meta in exec_ix.accounts.iter().filter(|meta| meta.is_writable) {
+            if !reported.insert(meta.pubkey) {
+                continue;
+            }
+            let after = context
+                .banks_client
+                .get_account(meta.pubkey)
+                .await
+                .map_err(|e| anyhow!("Failed to read {} after the exec: {}", meta.pubkey, e))?
+                .unwrap_or_default();
+            let before = before.get(&meta.pubkey).cloned().unwrap_or_default();
+            changes.push(AccountChange {
+                address: meta.pubkey.to_string(),
+                lamports_before: before.lamports,
+                lamports_after: after.lamports,
+                data_len_before: before.data.len(),
+                data_len_after: after.data.len(),
+                data_changed: before.data != after.data,
+            });
+        }
+
+        let result = SimulateOutput {
+            thread: thread_pubkey.to_string(),
+            fiber_index,
+            success: result.result.is_ok(),
+            error: result.result.err().map(|e| e.to_string()),
+            compute_units,
+            unix_timestamp: clock.unix_timestamp,
+            cloned_accounts: before.len() - 1,
+            cloned_programs,
+            logs,
+            changes,
+        };
+        output.print(&result, print_simulate)
+    }
+
+    async fn fetch_account(client: &RpcPool, pubkey: &Pubkey) -> Result<Option<Account>> {
+        client
+            .get_account(pubkey)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch {}: {}", pubkey, e))?
+            .map(|account| {
+                account
+                    .to_account()
+                    .map_err(|e| anyhow!("Failed to decode account {}: {}", pubkey, e))
+            })
+            .transpose()
+    }
+
+    /// The ELF behind an upgradeable program, read from its programdata account
+    async fn fetch_program_elf(
+        client: &RpcPool,
+        program_id: &Pubkey,
+        program: &Account,
+    ) -> Result<Vec<u8>> {
+        let programdata = program
+            .data
+            .get(PROGRAM_DATA_ADDRESS)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or_else(|| anyhow!("Program {} has no programdata address", program_id))?;
+        let account = fetch_account(client, &programdata)
+            .await?
+            .ok_or_else(|| anyhow!("Programdata {} not found", programdata))?;
+        account
+            .data
+            .get(PROGRAM_DATA_HEADER_LEN..)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| anyhow!("Programdata {} is truncated", programdata))
+    }
+
+    /// An executable account loading `elf` directly, which needs no
+    /// programdata and is usable from the bank's first slot
+    fn program_account(elf: Vec<u8>) -> Account {
+        Account {
+            lamports: 1_000_000_000,
+            data: elf,
+            owner: bpf_loader::ID,
+            executable: true,
+            rent_epoch: 0,
+        }
+    }
+
+    fn print_simulate(result: &SimulateOutput) {
+        println!("Thread:    {}", result.thread);
+        println!("Fiber:     {}", result.fiber_index);
+        println!("Timestamp: {}", result.unix_timestamp);
+        println!(
+            "Cloned:    {} accounts, {} programs",
+            result.cloned_accounts, result.cloned_programs
+        );
+        match &result.error {
+            None => println!("Result:    success"),
+            Some(error) => println!("Result:    failed: {}", error),
+        }
+        println!("Compute:   {} CU", result.compute_units);
+
+        println!("\nLogs:");
+        for line in &result.logs {
+            println!("  {}", line);
+        }
+
+        println!("\nWritable accounts:");
+        for change in &result.changes {
+            let delta = change.lamports_after as i128 - change.lamports_before as i128;
+            println!(
+                "  {}  lamports {:+}  data {} -> {} bytes{}",
+                change.address,
+                delta,
+                change.data_len_before,
+                change.data_len_after,
+                if change.data_changed {
+                    " (changed)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+}
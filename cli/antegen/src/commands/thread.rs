@@ -1,33 +1,61 @@
 //! Thread inspection and test commands
 
-use anchor_lang::AccountDeserialize;
+use anchor_lang::{AccountDeserialize, Space};
+use antegen_client::config::LoadBalancerConfigFile;
+use antegen_client::costs::{fetch_thread_costs, CostLedger, ThreadCosts};
+use antegen_client::decoders::{to_hex, AccountView, DecoderRegistry};
+use antegen_client::executor::reimbursement_floor;
+use antegen_client::helpers::THREAD_AUTHORITY_OFFSET;
 use antegen_client::rpc::RpcPool;
-use antegen_thread_program::state::Thread;
+use antegen_client::trigger_str;
+use antegen_client::ClientConfig;
+use antegen_thread_program::pda::fiber_pubkey;
+use antegen_thread_program::state::{
+    compiled_instruction_size, ExecutableBy, FeePayerMode, Schedule, SerializableAccountMeta,
+    SerializableInstruction, Thread, ThreadConfig, Trigger, TriggerProcessor,
+    CURRENT_THREAD_VERSION, MAX_COMPILED_INSTRUCTION_SIZE,
+};
+use antegen_thread_program::utils::calculate_jitter_offset;
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::clock::Clock;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::read_keypair_file;
 #[cfg(feature = "dev")]
-use solana_sdk::signature::{read_keypair_file, Keypair};
-#[cfg(feature = "dev")]
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::sysvar;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-#[cfg(feature = "dev")]
-use antegen_cli_core::commands::get_keypair;
-use antegen_cli_core::commands::get_rpc_url;
+use antegen_cli_core::commands::{default_config_path, expand_tilde, get_keypair, get_rpc_url};
+use antegen_cli_core::output::{as_string, OutputFormat};
 
 // =============================================================================
 // Thread inspection commands (always available)
 // =============================================================================
 
 /// Fetch and display a thread account
-pub async fn get(address: String, rpc_url: Option<String>) -> Result<()> {
+///
+/// With `decode_target`, also fetches the account an account trigger
+/// watches and decodes it with the built-in decoders.
+pub async fn get(
+    address: String,
+    decode_target: bool,
+    rpc_url: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
     // Parse the public key
     let thread_pubkey =
         Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
 
     // Get RPC URL
     let rpc_url = get_rpc_url(rpc_url)?;
-    println!("Fetching thread {} from {}", thread_pubkey, rpc_url);
+    output.note(format_args!(
+        "Fetching thread {} from {}",
+        thread_pubkey, rpc_url
+    ));
 
     let client =
         RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
@@ -47,6 +75,24 @@ pub async fn get(address: String, rpc_url: Option<String>) -> Result<()> {
         .owner_pubkey()
         .map_err(|e| anyhow!("Failed to parse owner: {}", e))?;
 
+    if output.is_json() {
+        let thread = Thread::try_deserialize(&mut data.as_slice())
+            .map_err(|e| anyhow!("Failed to deserialize thread: {}", e))?;
+        let config = fetch_config(&client).await;
+        let target = match decode_target {
+            true => fetch_target(&client, &thread).await?,
+            false => None,
+        };
+        return output.print_json(&ThreadOutput {
+            address: thread_pubkey.to_string(),
+            owner: owner.to_string(),
+            lamports: account.lamports,
+            data_len: data.len(),
+            thread: ThreadState::new(&thread, config.as_ref()),
+            target,
+        });
+    }
+
     println!("\n=== Account Info ===");
     println!("Owner: {}", owner);
     println!("Lamports: {}", account.lamports);
@@ -58,7 +104,15 @@ pub async fn get(address: String, rpc_url: Option<String>) -> Result<()> {
     match Thread::try_deserialize(&mut data.as_slice()) {
         Ok(thread) => {
             println!("Success!\n");
-            print_thread(&thread);
+            let config = fetch_config(&client).await;
+            print_thread(&thread, config.as_ref());
+            if decode_target {
+                println!();
+                match fetch_target(&client, &thread).await? {
+                    Some(target) => print_target(&target),
+                    None => println!("Thread has no account trigger, no target to decode"),
+                }
+            }
         }
         Err(e) => {
             println!("Failed to deserialize: {:?}", e);
@@ -72,7 +126,190 @@ pub async fn get(address: String, rpc_url: Option<String>) -> Result<()> {
     Ok(())
 }
 
-fn print_thread(thread: &Thread) {
+/// Fetch the thread config, if initialized
+async fn fetch_config(client: &RpcPool) -> Option<ThreadConfig> {
+    let account = client.get_account(&ThreadConfig::pubkey()).await.ok()??;
+    let data = account.decode_data().ok()?;
+    ThreadConfig::try_deserialize(&mut data.as_slice()).ok()
+}
+
+/// Fetch the account `thread`'s account trigger watches
+///
+/// None when the thread has another trigger.
+async fn fetch_target(client: &RpcPool, thread: &Thread) -> Result<Option<TargetAccount>> {
+    let Trigger::Account {
+        address,
+        offset,
+        size,
+    } = thread.trigger
+    else {
+        return Ok(None);
+    };
+    let account = client
+        .get_account(&address)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch target account {}: {}", address, e))?;
+    let Some(account) = account else {
+        return Ok(Some(TargetAccount {
+            address: address.to_string(),
+            owner: None,
+            lamports: 0,
+            watched: None,
+            contents: None,
+        }));
+    };
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode target account data: {}", e))?;
+    let owner = account
+        .owner_pubkey()
+        .map_err(|e| anyhow!("Failed to parse target owner: {}", e))?;
+    let watched = usize::try_from(offset.saturating_add(size))
+        .ok()
+        .and_then(|end| data.get(offset as usize..end))
+        .map(to_hex);
+    Ok(Some(TargetAccount {
+        address: address.to_string(),
+        owner: Some(owner.to_string()),
+        lamports: account.lamports,
+        watched,
+        contents: Some(DecoderRegistry::with_builtin().view(&owner, &data)),
+    }))
+}
+
+/// `thread get` result for JSON output
+#[derive(Serialize)]
+struct ThreadOutput {
+    address: String,
+    owner: String,
+    #[serde(with = "as_string")]
+    lamports: u64,
+    data_len: usize,
+    thread: ThreadState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<TargetAccount>,
+}
+
+/// The account an account trigger watches
+#[derive(Serialize)]
+struct TargetAccount {
+    address: String,
+    /// None if the account doesn't exist
+    owner: Option<String>,
+    #[serde(with = "as_string")]
+    lamports: u64,
+    /// Hex of the bytes the trigger watches, None if the account is shorter
+    watched: Option<String>,
+    contents: Option<AccountView>,
+}
+
+fn print_target(target: &TargetAccount) {
+    println!("=== Trigger Target ===");
+    println!("  address: {}", target.address);
+    let Some(owner) = &target.owner else {
+        println!("  account not found");
+        return;
+    };
+    println!("  owner: {}", owner);
+    println!("  lamports: {}", target.lamports);
+    match &target.watched {
+        Some(watched) => println!("  watched bytes: {}", watched),
+        None => println!("  watched bytes: out of range"),
+    }
+    match &target.contents {
+        Some(AccountView::Decoded(decoded)) => {
+            println!("  type: {}", decoded.kind);
+            for field in &decoded.fields {
+                println!("    {}: {}", field.name, field.value);
+            }
+        }
+        Some(AccountView::Raw { hex, base64 }) => {
+            println!("  type: unknown");
+            println!("    hex: {}", hex);
+            println!("    base64: {}", base64);
+        }
+        None => {}
+    }
+}
+
+/// Decoded thread fields, with pubkeys as base58 strings
+#[derive(Serialize)]
+struct ThreadState {
+    version: u8,
+    bump: u8,
+    authority: String,
+    id: Vec<u8>,
+    name: String,
+    created_at: i64,
+    trigger: String,
+    schedule: ScheduleState,
+    fiber_ids: Vec<u8>,
+    fiber_cursor: u8,
+    fiber_next_id: u8,
+    fiber_signal: String,
+    disabled_fibers: Vec<u8>,
+    failing_fiber: u8,
+    fiber_failures: u8,
+    paused: bool,
+    resume_at: Option<i64>,
+    exec_count: u64,
+    consecutive_execs: u64,
+    last_executor: String,
+    /// Effective mode; `None` only if the thread defers to an unreadable config
+    fee_payer_mode: Option<String>,
+    fee_payer_mode_override: bool,
+    nonce_account: String,
+    last_nonce: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ScheduleState {
+    OnChange { prev: u64 },
+    Timed { prev: i64, next: i64 },
+    Block { prev: u64, next: u64 },
+}
+
+impl ThreadState {
+    fn new(thread: &Thread, config: Option<&ThreadConfig>) -> Self {
+        let fee_payer_mode = thread
+            .fee_payer_mode
+            .or(config.map(|c| c.fee_payer_mode))
+            .map(|mode| format!("{:?}", mode));
+        Self {
+            version: thread.version,
+            bump: thread.bump,
+            authority: thread.authority.to_string(),
+            id: thread.id.clone(),
+            name: thread.name.clone(),
+            created_at: thread.created_at,
+            trigger: trigger_str::format(&thread.trigger),
+            schedule: match thread.schedule {
+                Schedule::OnChange { prev } => ScheduleState::OnChange { prev },
+                Schedule::Timed { prev, next } => ScheduleState::Timed { prev, next },
+                Schedule::Block { prev, next } => ScheduleState::Block { prev, next },
+            },
+            fiber_ids: thread.fiber_ids.clone(),
+            fiber_cursor: thread.fiber_cursor,
+            fiber_next_id: thread.fiber_next_id,
+            fiber_signal: format!("{:?}", thread.fiber_signal),
+            disabled_fibers: thread.disabled_fiber_ids(),
+            failing_fiber: thread.failing_fiber,
+            fiber_failures: thread.fiber_failures,
+            paused: thread.paused,
+            resume_at: thread.resume_at,
+            exec_count: thread.exec_count,
+            consecutive_execs: thread.consecutive_execs,
+            last_executor: thread.last_executor.to_string(),
+            fee_payer_mode,
+            fee_payer_mode_override: thread.fee_payer_mode.is_some(),
+            nonce_account: thread.nonce_account.to_string(),
+            last_nonce: thread.last_nonce.clone(),
+        }
+    }
+}
+
+fn print_thread(thread: &Thread, config: Option<&ThreadConfig>) {
     println!("=== Thread State ===");
     println!();
 
@@ -92,7 +329,7 @@ fn print_thread(thread: &Thread) {
 
     // Scheduling
     println!("--- Scheduling ---");
-    println!("  trigger: {:?}", thread.trigger);
+    println!("  trigger: {}", trigger_str::format(&thread.trigger));
     println!("  schedule: {:?}", thread.schedule);
     println!();
 
@@ -102,30 +339,1395 @@ fn print_thread(thread: &Thread) {
     println!("  fiber_cursor: {}", thread.fiber_cursor);
     println!("  fiber_next_id: {}", thread.fiber_next_id);
     println!("  fiber_signal: {:?}", thread.fiber_signal);
+    println!("  disabled_fibers: {:?}", thread.disabled_fiber_ids());
+    if thread.fiber_failures > 0 {
+        match config.filter(|config| config.max_fiber_failures > 0) {
+            Some(config) => println!(
+                "  fiber_failures: {} of {} (fiber {})",
+                thread.fiber_failures, config.max_fiber_failures, thread.failing_fiber
+            ),
+            None => println!(
+                "  fiber_failures: {} (fiber {})",
+                thread.fiber_failures, thread.failing_fiber
+            ),
+        }
+    }
     println!();
 
     // Lifecycle
     println!("--- Lifecycle ---");
     println!("  paused: {}", thread.paused);
+    if let Some(resume_at) = thread.resume_at {
+        let at = chrono::DateTime::from_timestamp(resume_at, 0)
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| "invalid".to_string());
+        println!("  resume_at: {} ({})", resume_at, at);
+    }
     println!();
 
     // Execution tracking
     println!("--- Execution Tracking ---");
     println!("  exec_count: {}", thread.exec_count);
+    println!("  consecutive_execs: {}", thread.consecutive_execs);
     println!("  last_executor: {}", thread.last_executor);
     println!();
 
+    // Fees
+    println!("--- Fees ---");
+    match (thread.fee_payer_mode, config) {
+        (Some(mode), _) => println!("  fee_payer_mode: {:?} (thread override)", mode),
+        (None, Some(config)) => println!(
+            "  fee_payer_mode: {:?} (config default)",
+            config.fee_payer_mode
+        ),
+        (None, None) => println!("  fee_payer_mode: config default (config unavailable)"),
+    }
+    println!();
+
     // Nonce
     println!("--- Nonce ---");
     println!("  nonce_account: {}", thread.nonce_account);
     println!("  last_nonce: {}", thread.last_nonce);
 }
 
+/// Explain what an executor would do with a thread right now
+///
+/// Runs the same readiness check the client's staging actor uses against
+/// the live clock and config, then reports when the trigger is due, who
+/// holds the claim, and whether the balances allow an execution. The claim
+/// window and executor balance are read from the local client config when
+/// one is found.
+pub async fn debug(
+    address: String,
+    config_path: Option<PathBuf>,
+    rpc_url: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let thread_pubkey =
+        Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+    let rpc_url = get_rpc_url(rpc_url)?;
+    output.note(format_args!(
+        "Debugging thread {} on {}",
+        thread_pubkey, rpc_url
+    ));
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let account = client
+        .get_account(&thread_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch account: {}", e))?
+        .ok_or_else(|| anyhow!("Thread not found: {}", thread_pubkey))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
+    let thread = Thread::try_deserialize(&mut data.as_slice())
+        .map_err(|e| anyhow!("Failed to deserialize thread: {}", e))?;
+    let config = fetch_config(&client)
+        .await
+        .ok_or_else(|| anyhow!("Thread config not found, is the program initialized?"))?;
+    let clock = fetch_clock(&client).await?;
+    let target = fetch_target(&client, &thread).await?;
+
+    // Executor identity and claim window from the local client config
+    let client_config = config_path
+        .or_else(|| default_config_path().ok())
+        .filter(|p| p.exists())
+        .and_then(|p| ClientConfig::load(p).ok());
+    let executor = client_config.as_ref().and_then(|c| {
+        expand_tilde(&c.executor.keypair_path)
+            .ok()
+            .and_then(|path| read_keypair_file(&path).ok())
+            .map(|keypair| keypair.pubkey())
+    });
+    // The client uses the grace period as its takeover delay
+    let takeover_delay = client_config
+        .as_ref()
+        .map(|c| c.load_balancer.grace_period)
+        .unwrap_or(LoadBalancerConfigFile::default().grace_period) as i64;
+
+    let verdict = thread.check_executable(&clock, &config);
+    let due_at = timestamp_due_at(&thread, &thread_pubkey);
+    let overdue_seconds = match verdict {
+        Ok(ExecutableBy::Trigger) => due_at.map(|at| (clock.unix_timestamp - at).max(0)),
+        _ => None,
+    };
+    let claim = if verdict.is_err() {
+        ClaimState::NotExecutable
+    } else if thread.last_executor == Pubkey::default() {
+        ClaimState::Unclaimed
+    } else if Some(thread.last_executor) == executor {
+        ClaimState::Owned
+    } else if overdue_seconds.unwrap_or(0) > takeover_delay {
+        ClaimState::TakeoverOpen
+    } else {
+        ClaimState::Held {
+            seconds_left: takeover_delay - overdue_seconds.unwrap_or(0),
+        }
+    };
+
+    let fee_payer_mode = thread.effective_fee_payer_mode(&config);
+    let required_lamports = match fee_payer_mode {
+        FeePayerMode::ThreadReimburses => reimbursement_floor(&config, data.len()),
+        FeePayerMode::ExecutorPays => Rent::default().minimum_balance(data.len()),
+    };
+    let executor_lamports = match executor {
+        Some(executor) => Some(
+            client
+                .get_balance(&executor)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch executor balance: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let fiber_pubkeys: Vec<Pubkey> = thread
+        .fiber_ids
+        .iter()
+        .map(|&index| fiber_pubkey(&thread_pubkey, index).0)
+        .collect();
+    let fiber_accounts = client
+        .get_multiple_accounts(&fiber_pubkeys)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch fibers: {}", e))?;
+    let fibers = thread
+        .fiber_ids
+        .iter()
+        .zip(fiber_pubkeys.iter().zip(fiber_accounts))
+        .map(|(&index, (address, account))| FiberDebug {
+            index,
+            address: address.to_string(),
+            exists: account.is_some(),
+            cursor: index == thread.fiber_cursor,
+            disabled: thread.is_fiber_disabled(index),
+            failures: if thread.fiber_failures > 0 && thread.failing_fiber == index {
+                thread.fiber_failures
+            } else {
+                0
+            },
+        })
+        .collect();
+
+    let result = DebugOutput {
+        thread: thread_pubkey.to_string(),
+        executable: verdict.is_ok(),
+        reason: match verdict {
+            Ok(by) => format!("{:?}", by).to_lowercase(),
+            Err(reason) => reason.to_string(),
+        },
+        trigger: trigger_str::format(&thread.trigger),
+        clock: ClockDebug {
+            slot: clock.slot,
+            epoch: clock.epoch,
+            unix_timestamp: clock.unix_timestamp,
+        },
+        due_at,
+        due_in_seconds: due_at
+            .map(|at| at - clock.unix_timestamp)
+            .filter(|&secs| secs > 0),
+        overdue_seconds,
+        paused: thread.paused,
+        resume_at: thread.resume_at,
+        global_pause: config.paused,
+        last_executor: thread.last_executor.to_string(),
+        executor: executor.map(|e| e.to_string()),
+        takeover_delay,
+        claim,
+        fee_payer_mode: format!("{:?}", fee_payer_mode),
+        thread_lamports: account.lamports,
+        required_lamports,
+        executor_lamports,
+        fibers,
+        target,
+    };
+    output.print(&result, print_debug)
+}
+
+/// Fetch the current clock sysvar
+pub(crate) async fn fetch_clock(client: &RpcPool) -> Result<Clock> {
+    let account = client
+        .get_account(&sysvar::clock::ID)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch clock sysvar: {}", e))?
+        .ok_or_else(|| anyhow!("Clock sysvar not found"))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode clock sysvar: {}", e))?;
+    bincode::deserialize(&data).map_err(|e| anyhow!("Failed to deserialize clock sysvar: {}", e))
+}
+
+/// Unix timestamp a wall-clock trigger comes due at, jitter included
+fn timestamp_due_at(thread: &Thread, thread_pubkey: &Pubkey) -> Option<i64> {
+    match (&thread.trigger, &thread.schedule) {
+        (Trigger::Timestamp { unix_ts, jitter }, _) => Some(unix_ts.saturating_add(
+            calculate_jitter_offset(thread.get_last_started_at(), thread_pubkey, *jitter),
+        )),
+        (Trigger::Interval { .. } | Trigger::Cron { .. }, Schedule::Timed { next, .. }) => {
+            Some(*next)
+        }
+        _ => None,
+    }
+}
+
+/// `thread debug` result for JSON output
+#[derive(Serialize)]
+struct DebugOutput {
+    thread: String,
+    executable: bool,
+    reason: String,
+    trigger: String,
+    clock: ClockDebug,
+    due_at: Option<i64>,
+    due_in_seconds: Option<i64>,
+    overdue_seconds: Option<i64>,
+    paused: bool,
+    resume_at: Option<i64>,
+    global_pause: bool,
+    last_executor: String,
+    /// Executor from the local client config, if one was found
+    executor: Option<String>,
+    takeover_delay: i64,
+    claim: ClaimState,
+    fee_payer_mode: String,
+    #[serde(with = "as_string")]
+    thread_lamports: u64,
+    #[serde(with = "as_string")]
+    required_lamports: u64,
+    executor_lamports: Option<u64>,
+    fibers: Vec<FiberDebug>,
+    /// The watched account, for account triggers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<TargetAccount>,
+}
+
+#[derive(Serialize)]
+struct ClockDebug {
+    slot: u64,
+    epoch: u64,
+    unix_timestamp: i64,
+}
+
+/// Whether an executor would race for the thread, mirroring the load balancer
+#[derive(Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum ClaimState {
+    NotExecutable,
+    /// Nobody has executed it yet, any executor claims it
+    Unclaimed,
+    /// The configured executor ran it last and keeps it
+    Owned,
+    /// Overdue past the takeover delay, any executor may take it
+    TakeoverOpen,
+    /// The last executor keeps it until it is this much further overdue
+    Held {
+        seconds_left: i64,
+    },
+}
+
+#[derive(Serialize)]
+struct FiberDebug {
+    index: u8,
+    address: String,
+    exists: bool,
+    cursor: bool,
+    disabled: bool,
+    failures: u8,
+}
+
+fn print_debug(result: &DebugOutput) {
+    println!("=== Thread Debug ===");
+    println!("  thread: {}", result.thread);
+    println!(
+        "  clock: slot {}, epoch {}, unix {}",
+        result.clock.slot, result.clock.epoch, result.clock.unix_timestamp
+    );
+    println!();
+
+    println!("--- Verdict ---");
+    if result.executable {
+        println!("  executable: yes ({})", result.reason);
+    } else {
+        println!("  executable: no, {}", result.reason);
+    }
+    println!();
+
+    println!("--- Trigger ---");
+    println!("  trigger: {}", result.trigger);
+    match (result.due_at, result.due_in_seconds, result.overdue_seconds) {
+        (Some(at), Some(secs), _) => println!("  due at: {} (in {}s)", at, secs),
+        (Some(at), None, Some(secs)) => println!("  due at: {} (overdue by {}s)", at, secs),
+        (Some(at), None, None) => println!("  due at: {}", at),
+        (None, ..) => {}
+    }
+    println!("  paused: {}", result.paused);
+    if let Some(resume_at) = result.resume_at {
+        println!("  resume_at: {}", resume_at);
+    }
+    if result.global_pause {
+        println!("  global pause: all threads are paused by the config");
+    }
+    println!();
+
+    println!("--- Claim ---");
+    println!("  last_executor: {}", result.last_executor);
+    match &result.executor {
+        Some(executor) => println!("  executor: {}", executor),
+        None => println!("  executor: unknown (no client config)"),
+    }
+    println!("  takeover delay: {}s", result.takeover_delay);
+    match result.claim {
+        ClaimState::NotExecutable => println!("  claim: not executable, nothing to claim"),
+        ClaimState::Unclaimed => println!("  claim: unclaimed, any executor claims it"),
+        ClaimState::Owned => println!("  claim: owned by this executor"),
+        ClaimState::TakeoverOpen => println!("  claim: overdue past the delay, open for takeover"),
+        ClaimState::Held { seconds_left } => println!(
+            "  claim: held by the last executor for {}s more",
+            seconds_left
+        ),
+    }
+    println!();
+
+    println!("--- Balances ---");
+    println!("  fee_payer_mode: {}", result.fee_payer_mode);
+    let funded = if result.thread_lamports >= result.required_lamports {
+        "ok"
+    } else {
+        "INSUFFICIENT"
+    };
+    println!(
+        "  thread: {} lamports, needs {} ({})",
+        result.thread_lamports, result.required_lamports, funded
+    );
+    if let Some(lamports) = result.executor_lamports {
+        println!("  executor: {} lamports", lamports);
+    }
+    println!();
+
+    println!("--- Fibers ---");
+    for fiber in &result.fibers {
+        let mut notes = Vec::new();
+        if fiber.cursor {
+            notes.push("cursor".to_string());
+        }
+        if !fiber.exists {
+            notes.push("MISSING".to_string());
+        }
+        if fiber.disabled {
+            notes.push("disabled".to_string());
+        }
+        if fiber.failures > 0 {
+            notes.push(format!("{} failures", fiber.failures));
+        }
+        println!("  [{}] {} {}", fiber.index, fiber.address, notes.join(", "));
+    }
+
+    if let Some(target) = &result.target {
+        println!();
+        print_target(target);
+    }
+}
+
+/// Show accumulated execution costs for a thread
+///
+/// Reads the local cost ledger when it has history for the thread, otherwise
+/// rebuilds the totals from the thread's on-chain signature history.
+pub async fn costs(
+    address: String,
+    since: String,
+    rpc_url: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let thread_pubkey =
+        Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+    let window = parse_since(&since)?;
+    let since_ts = chrono::Utc::now().timestamp() - window;
+
+    let local = default_config_path()
+        .ok()
+        .filter(|p| p.exists())
+        .and_then(|p| ClientConfig::load(p).ok())
+        .and_then(|config| CostLedger::from_config(&config.costs).ok())
+        .and_then(|ledger| ledger.summary(&thread_pubkey, since_ts));
+
+    let (costs, source) = match local {
+        Some(costs) => (costs, "local ledger".to_string()),
+        None => {
+            let rpc_url = get_rpc_url(rpc_url)?;
+            output.note(format_args!(
+                "No local cost history, walking signatures via {}...",
+                rpc_url
+            ));
+            let client = RpcPool::with_url(&rpc_url)
+                .map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+            let costs = fetch_thread_costs(&client, &thread_pubkey, since_ts)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch thread history: {}", e))?;
+            (costs, rpc_url)
+        }
+    };
+
+    let result = CostsOutput {
+        thread: thread_pubkey.to_string(),
+        since,
+        source,
+        executions: costs.executions,
+        failures: costs.failures,
+        fees_paid: costs.fees_paid,
+        thread_payout: costs.thread_payout,
+    };
+    output.print(&result, |result| {
+        print_costs(&thread_pubkey, &result.since, &result.source, &costs)
+    })
+}
+
+/// `thread costs` result for JSON output
+#[derive(Serialize)]
+struct CostsOutput {
+    thread: String,
+    since: String,
+    source: String,
+    executions: u64,
+    failures: u64,
+    #[serde(with = "as_string")]
+    fees_paid: u64,
+    #[serde(with = "as_string")]
+    thread_payout: u64,
+}
+
+fn print_costs(thread_pubkey: &Pubkey, since: &str, source: &str, costs: &ThreadCosts) {
+    const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+    println!("=== Thread Costs (last {}) ===", since);
+    println!("  thread: {}", thread_pubkey);
+    println!("  source: {}", source);
+    println!();
+    println!("  executions: {}", costs.executions);
+    println!("  failures: {}", costs.failures);
+    println!(
+        "  fees paid: {} lamports ({:.6} SOL)",
+        costs.fees_paid,
+        costs.fees_paid as f64 / LAMPORTS_PER_SOL
+    );
+    println!(
+        "  paid by thread: {} lamports ({:.6} SOL)",
+        costs.thread_payout,
+        costs.thread_payout as f64 / LAMPORTS_PER_SOL
+    );
+}
+
+/// Parse a lookback window like `30d`, `12h` or `45m` into seconds
+fn parse_since(since: &str) -> Result<i64> {
+    let (value, unit) = since.split_at(since.len().saturating_sub(1));
+    let multiplier = match unit {
+        "d" => 86_400,
+        "h" => 3_600,
+        "m" => 60,
+        _ => {
+            return Err(anyhow!(
+                "Invalid --since '{}': expected a number followed by d, h or m",
+                since
+            ))
+        }
+    };
+    let value: i64 = value
+        .parse()
+        .map_err(|_| anyhow!("Invalid --since '{}': expected e.g. 30d", since))?;
+    Ok(value * multiplier)
+}
+
+/// Whether a thread account predates the current layout or version
+///
+/// Checked on the raw data (the version byte follows the discriminator), since
+/// an older layout does not deserialize as the current `Thread`.
+fn needs_migration(data: &[u8], version: u8) -> bool {
+    data.len() < 8 + Thread::INIT_SPACE || version < CURRENT_THREAD_VERSION
+}
+
+/// Migrate an owner's thread accounts to the current account layout
+///
+/// Finds Thread accounts whose allocation is smaller than the current layout
+/// or whose version is behind, and sends `migrate_thread` for each, up to
+/// `batch_size` at a time.
+pub async fn migrate(
+    owner: Option<String>,
+    dry_run: bool,
+    batch_size: usize,
+    rpc_url: Option<String>,
+    keypair_path: Option<std::path::PathBuf>,
+) -> Result<()> {
+    use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+    use futures::stream::{self, StreamExt};
+    use indicatif::{ProgressBar, ProgressStyle};
+    use solana_sdk::{
+        instruction::Instruction, message::Message, signer::Signer, transaction::Transaction,
+    };
+
+    if batch_size == 0 {
+        return Err(anyhow!("--batch-size must be at least 1"));
+    }
+
+    let owner = owner
+        .map(|o| Pubkey::from_str(&o).map_err(|e| anyhow!("Invalid owner '{}': {}", o, e)))
+        .transpose()?;
+
+    // Only the authority can migrate; a dry run can inspect any owner
+    let keypair = match (owner, dry_run) {
+        (Some(_), true) => None,
+        _ => Some(get_keypair(keypair_path)?),
+    };
+    let owner = owner
+        .or_else(|| keypair.as_ref().map(|k| k.pubkey()))
+        .ok_or_else(|| anyhow!("No owner given and no keypair available"))?;
+    if let Some(keypair) = &keypair {
+        if keypair.pubkey() != owner {
+            return Err(anyhow!(
+                "Keypair {} is not the owner {}; only the thread authority can migrate",
+                keypair.pubkey(),
+                owner
+            ));
+        }
+    }
+
+    let rpc_url = get_rpc_url(rpc_url)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    println!("Scanning threads owned by {} via {}...", owner, rpc_url);
+    let filters = vec![
+        serde_json::json!({
+            "memcmp": { "offset": 0, "bytes": bs58::encode(Thread::DISCRIMINATOR).into_string() }
+        }),
+        serde_json::json!({
+            "memcmp": { "offset": THREAD_AUTHORITY_OFFSET, "bytes": owner.to_string() }
+        }),
+    ];
+    let accounts = client
+        .get_program_accounts(&antegen_thread_program::ID, Some(filters))
+        .await
+        .map_err(|e| anyhow!("Failed to fetch thread accounts: {}", e))?;
+
+    let mut pending = Vec::new();
+    for (pubkey, account) in &accounts {
+        let data = match account.decode_data() {
+            Ok(d) => d,
+            Err(e) => {
+                println!("  ! {}: failed to decode account data: {}", pubkey, e);
+                continue;
+            }
+        };
+        match data.get(8) {
+            Some(&version) if needs_migration(&data, version) => {
+                pending.push((*pubkey, version, data.len()))
+            }
+            Some(_) => {}
+            None => println!("  ! {}: account data too short", pubkey),
+        }
+    }
+
+    println!(
+        "Found {} thread(s), {} need migration (current version {}, {} bytes)",
+        accounts.len(),
+        pending.len(),
+        CURRENT_THREAD_VERSION,
+        8 + Thread::INIT_SPACE
+    );
+
+    if dry_run || pending.is_empty() {
+        for (pubkey, version, len) in &pending {
+            println!("  {} (version {}, {} bytes)", pubkey, version, len);
+        }
+        return Ok(());
+    }
+    let keypair = keypair.ok_or_else(|| anyhow!("A keypair is required to migrate"))?;
+
+    let progress = ProgressBar::new(pending.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} {msg}")
+            .map_err(|e| anyhow!("Invalid progress template: {}", e))?,
+    );
+
+    let results: Vec<(Pubkey, Result<solana_sdk::signature::Signature>)> =
+        stream::iter(pending.iter().map(|(pubkey, _, _)| *pubkey))
+            .map(|thread_pubkey| {
+                let client = &client;
+                let keypair = &keypair;
+                let progress = &progress;
+                async move {
+                    let ix = Instruction {
+                        program_id: antegen_thread_program::ID,
+                        accounts: antegen_thread_program::accounts::ThreadMigrate {
+                            authority: keypair.pubkey(),
+                            thread: thread_pubkey,
+                            system_program: anchor_lang::system_program::ID,
+                        }
+                        .to_account_metas(None),
+                        data: antegen_thread_program::instruction::MigrateThread {}.data(),
+                    };
+
+                    let result = async {
+                        let (blockhash, _) = client.get_latest_blockhash().await?;
+                        let message = Message::new(&[ix], Some(&keypair.pubkey()));
+                        let tx = Transaction::new(&[keypair], message, blockhash);
+                        client.send_and_confirm_transaction(&tx).await
+                    }
+                    .await;
+
+                    progress.inc(1);
+                    (thread_pubkey, result)
+                }
+            })
+            .buffer_unordered(batch_size)
+            .collect()
+            .await;
+    progress.finish_and_clear();
+
+    let mut failed = 0;
+    for (thread_pubkey, result) in &results {
+        match result {
+            Ok(sig) => println!("  ✓ {} ({})", thread_pubkey, sig),
+            Err(e) => {
+                failed += 1;
+                println!("  ✗ {}: {}", thread_pubkey, e);
+            }
+        }
+    }
+
+    println!(
+        "\nMigrated {} of {} thread(s)",
+        results.len() - failed,
+        results.len()
+    );
+    if failed > 0 {
+        return Err(anyhow!("{} migration(s) failed", failed));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Export / import
+// =============================================================================
+
+/// Version of the `thread export` file format
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Portable snapshot of a thread and its fibers, written by `thread export`
+#[derive(Serialize, Deserialize)]
+struct ThreadExport {
+    format_version: u32,
+    exported_at: String,
+    thread: String,
+    authority: String,
+    /// Thread id bytes as hex, exactly as used in the PDA seeds
+    id_hex: String,
+    name: String,
+    trigger: TriggerExport,
+    paused: bool,
+    /// Thread-level fee payer override, if any
+    fee_payer_mode: Option<String>,
+    exec_count: u64,
+    /// Lamports held by the thread account
+    balance: u64,
+    /// Whether the thread uses a durable nonce (not recreated on import)
+    durable_nonce: bool,
+    fibers: Vec<FiberExport>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TriggerExport {
+    /// Trigger in `trigger_str` form; this is what import reads
+    spec: String,
+    description: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FiberExport {
+    index: u8,
+    address: String,
+    priority_fee: u64,
+    #[serde(default)]
+    lookup_tables: Vec<String>,
+    /// `None` for an idle fiber without an instruction
+    instruction: Option<InstructionExport>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstructionExport {
+    program_id: String,
+    accounts: Vec<AccountMetaExport>,
+    data_hex: String,
+    /// Best-effort reading of `data` for known instructions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    decoded: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountMetaExport {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl From<&solana_sdk::instruction::Instruction> for InstructionExport {
+    fn from(ix: &solana_sdk::instruction::Instruction) -> Self {
+        Self {
+            program_id: ix.program_id.to_string(),
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaExport {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data_hex: to_hex(&ix.data),
+            decoded: decode_instruction_data(ix),
+        }
+    }
+}
+
+impl InstructionExport {
+    /// Parse into an instruction, rejecting one too large for a fiber account
+    fn to_serializable(&self) -> Result<SerializableInstruction> {
+        let parse =
+            |s: &str| Pubkey::from_str(s).map_err(|e| anyhow!("Invalid pubkey '{}': {}", s, e));
+        let ix = SerializableInstruction {
+            program_id: parse(&self.program_id)?,
+            accounts: self
+                .accounts
+                .iter()
+                .map(|meta| {
+                    Ok(SerializableAccountMeta {
+                        pubkey: parse(&meta.pubkey)?,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            data: from_hex(&self.data_hex)?,
+        };
+        let size = compiled_instruction_size(&ix);
+        if size > MAX_COMPILED_INSTRUCTION_SIZE {
+            return Err(anyhow!(
+                "Instruction for program {} compiles to {} bytes; a fiber holds at most {}",
+                ix.program_id,
+                size,
+                MAX_COMPILED_INSTRUCTION_SIZE
+            ));
+        }
+        Ok(ix)
+    }
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(anyhow!("Invalid hex string '{}'", hex));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| anyhow!("Invalid hex string '{}': {}", hex, e))
+        })
+        .collect()
+}
+
+/// Human-readable reading of instruction data, for instructions we know
+fn decode_instruction_data(ix: &solana_sdk::instruction::Instruction) -> Option<String> {
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+    use antegen_thread_program::instruction::ThreadMemo;
+
+    if ix.program_id == antegen_thread_program::ID && ix.data.starts_with(ThreadMemo::DISCRIMINATOR)
+    {
+        let memo = ThreadMemo::try_from_slice(&ix.data[ThreadMemo::DISCRIMINATOR.len()..]).ok()?;
+        return Some(format!(
+            "thread_memo {{ memo: {:?}, signal: {:?} }}",
+            memo.memo, memo.signal
+        ));
+    }
+
+    // System transfer: u32 instruction tag 2, then u64 lamports
+    if ix.program_id == anchor_lang::system_program::ID
+        && ix.data.len() == 12
+        && ix.data[..4] == 2u32.to_le_bytes()
+    {
+        let lamports = u64::from_le_bytes(ix.data[4..].try_into().ok()?);
+        return Some(format!("system transfer {{ lamports: {} }}", lamports));
+    }
+
+    // Memo-style payloads
+    let text = std::str::from_utf8(&ix.data).ok()?;
+    (!text.is_empty() && !text.chars().any(char::is_control)).then(|| format!("utf8 {:?}", text))
+}
+
+/// Fetch a fiber account and decompile its instruction
+async fn export_fiber(client: &RpcPool, thread_pubkey: Pubkey, index: u8) -> Result<FiberExport> {
+    use anchor_lang::AnchorDeserialize;
+    use antegen_thread_program::fiber::{decompile_instruction, CompiledInstructionV0, Fiber};
+
+    let fiber_pubkey = antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, index);
+    let account = client
+        .get_account(&fiber_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch fiber {}: {}", index, e))?
+        .ok_or_else(|| anyhow!("Fiber {} not found at {}", index, fiber_pubkey))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode fiber {} data: {}", index, e))?;
+    let fiber = Fiber::try_deserialize(&mut data.as_slice())
+        .map_err(|e| anyhow!("Failed to deserialize fiber {}: {:?}", index, e))?;
+
+    let instruction = if fiber.compiled_instruction().is_empty() {
+        None
+    } else {
+        let compiled = CompiledInstructionV0::deserialize(&mut fiber.compiled_instruction())
+            .map_err(|e| anyhow!("Failed to read fiber {} instruction: {}", index, e))?;
+        let ix = decompile_instruction(&compiled)
+            .map_err(|e| anyhow!("Failed to decompile fiber {} instruction: {:?}", index, e))?;
+        Some(InstructionExport::from(&ix))
+    };
+
+    Ok(FiberExport {
+        index,
+        address: fiber_pubkey.to_string(),
+        priority_fee: fiber.priority_fee(),
+        lookup_tables: fiber
+            .lookup_tables()
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+        instruction,
+    })
+}
+
+/// Save a thread and its fibers as a portable JSON file
+pub async fn export(address: String, output: PathBuf, rpc_url: Option<String>) -> Result<()> {
+    let thread_pubkey =
+        Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+    let rpc_url = get_rpc_url(rpc_url)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let account = client
+        .get_account(&thread_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch account: {}", e))?
+        .ok_or_else(|| anyhow!("Account not found: {}", thread_pubkey))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
+    let thread = Thread::try_deserialize(&mut data.as_slice())
+        .map_err(|e| anyhow!("Failed to deserialize thread: {:?}", e))?;
+
+    let mut fibers = Vec::with_capacity(thread.fiber_ids.len());
+    for &index in &thread.fiber_ids {
+        fibers.push(export_fiber(&client, thread_pubkey, index).await?);
+    }
+
+    let export = ThreadExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        thread: thread_pubkey.to_string(),
+        authority: thread.authority.to_string(),
+        id_hex: to_hex(&thread.id),
+        name: thread.name.clone(),
+        trigger: TriggerExport {
+            spec: trigger_str::format(&thread.trigger),
+            description: trigger_str::describe(&thread.trigger),
+        },
+        paused: thread.paused,
+        fee_payer_mode: thread.fee_payer_mode.map(|mode| format!("{:?}", mode)),
+        exec_count: thread.exec_count,
+        balance: account.lamports,
+        durable_nonce: thread.nonce_account != antegen_thread_program::ID,
+        fibers,
+    };
+
+    let file = std::fs::File::create(&output)
+        .map_err(|e| anyhow!("Failed to create {}: {}", output.display(), e))?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &export)
+        .map_err(|e| anyhow!("Failed to write {}: {}", output.display(), e))?;
+
+    println!(
+        "Exported thread {} ({} fiber(s)) to {}",
+        thread_pubkey,
+        export.fibers.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Re-create a thread from a `thread export` file
+///
+/// The thread is created paused under the keypair's authority, its fibers are
+/// added in their original order, and the exported paused state and fee payer
+/// override are applied last. It is funded so that its balance matches the
+/// export once fiber rent has been drawn.
+pub async fn import(
+    file: PathBuf,
+    dry_run: bool,
+    rpc_url: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use antegen_thread_program::instructions::ThreadUpdateParams;
+    use antegen_thread_program::state::FeePayerMode;
+    use antegen_thread_program::ThreadId;
+    use solana_sdk::{
+        instruction::Instruction, message::Message, rent::Rent, signer::Signer,
+        transaction::Transaction,
+    };
+
+    let reader = std::fs::File::open(&file)
+        .map_err(|e| anyhow!("Failed to open {}: {}", file.display(), e))?;
+    let export: ThreadExport = serde_json::from_reader(std::io::BufReader::new(reader))
+        .map_err(|e| anyhow!("Failed to parse {}: {}", file.display(), e))?;
+    if export.format_version != EXPORT_FORMAT_VERSION {
+        return Err(anyhow!(
+            "Unsupported export format version {} (expected {})",
+            export.format_version,
+            EXPORT_FORMAT_VERSION
+        ));
+    }
+
+    let trigger = trigger_str::parse(&export.trigger.spec)
+        .map_err(|e| anyhow!("Invalid trigger '{}': {}", export.trigger.spec, e))?;
+    let id = from_hex(&export.id_hex)?;
+    // Threads created with a pubkey id are named after it
+    let thread_id = match Pubkey::try_from(id.as_slice()) {
+        Ok(pubkey) if pubkey.to_string() == export.name => ThreadId::Pubkey(pubkey),
+        _ => ThreadId::Bytes(id.clone()),
+    };
+    let fee_payer_mode = match export.fee_payer_mode.as_deref() {
+        None => None,
+        Some("ExecutorPays") => Some(FeePayerMode::ExecutorPays),
+        Some("ThreadReimburses") => Some(FeePayerMode::ThreadReimburses),
+        Some(other) => return Err(anyhow!("Unknown fee payer mode '{}'", other)),
+    };
+    let fibers = export
+        .fibers
+        .iter()
+        .map(|fiber| {
+            let instruction = fiber
+                .instruction
+                .as_ref()
+                .map(InstructionExport::to_serializable)
+                .transpose()?;
+            let lookup_tables = fiber
+                .lookup_tables
+                .iter()
+                .map(|k| Pubkey::from_str(k).map_err(|e| anyhow!("Invalid lookup table: {}", e)))
+                .collect::<Result<Vec<_>>>()?;
+            Ok((fiber.index, instruction, fiber.priority_fee, lookup_tables))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Dry runs work without a keypair, assuming the exported authority
+    let keypair = match get_keypair(keypair_path) {
+        Ok(keypair) => Some(keypair),
+        Err(e) if dry_run => {
+            println!("No keypair ({}), assuming the exported authority", e);
+            None
+        }
+        Err(e) => return Err(e),
+    };
+    let authority = match &keypair {
+        Some(keypair) => keypair.pubkey(),
+        None => Pubkey::from_str(&export.authority)
+            .map_err(|e| anyhow!("Invalid authority in export: {}", e))?,
+    };
+    let (thread_pubkey, _) = Pubkey::find_program_address(
+        &[antegen_thread_program::SEED_THREAD, authority.as_ref(), &id],
+        &antegen_thread_program::ID,
+    );
+
+    let rent = Rent::default();
+    let fiber_rent =
+        rent.minimum_balance(8 + antegen_fiber_program::state::FiberVersionedState::INIT_SPACE);
+    let amount = export
+        .balance
+        .saturating_sub(rent.minimum_balance(8 + Thread::INIT_SPACE))
+        .saturating_add(fiber_rent * fibers.len() as u64);
+
+    println!("Importing thread '{}' from {}", export.name, file.display());
+    println!("  address: {}", thread_pubkey);
+    if thread_pubkey.to_string() != export.thread {
+        println!(
+            "  (exported from {} under authority {})",
+            export.thread, export.authority
+        );
+    }
+    println!(
+        "  trigger: {} ({})",
+        export.trigger.spec, export.trigger.description
+    );
+    println!("  funding: {} lamports", amount);
+    for (index, instruction, _, _) in &fibers {
+        match instruction {
+            Some(ix) => println!("  fiber {}: {}", index, ix.program_id),
+            None => println!("  fiber {}: idle", index),
+        }
+    }
+    if export.durable_nonce {
+        println!("  note: the exported thread used a durable nonce; the import does not");
+    }
+
+    let rpc_url = get_rpc_url(rpc_url)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+    if client
+        .get_account(&thread_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to check thread address: {}", e))?
+        .is_some()
+    {
+        return Err(anyhow!("A thread already exists at {}", thread_pubkey));
+    }
+
+    let Some(authority) = keypair.filter(|_| !dry_run) else {
+        println!("\nDry run: nothing sent");
+        return Ok(());
+    };
+
+    let send = |ix: Instruction, what: String| {
+        let client = &client;
+        let authority = &authority;
+        async move {
+            let (blockhash, _) = client.get_latest_blockhash().await?;
+            let message = Message::new(&[ix], Some(&authority.pubkey()));
+            let tx = Transaction::new(&[authority], message, blockhash);
+            let sig = client
+                .send_and_confirm_transaction(&tx)
+                .await
+                .map_err(|e| anyhow!("Failed to {}: {}", what, e))?;
+            println!("  {}: {}", what, sig);
+            Ok::<_, anyhow::Error>(())
+        }
+    };
+
+    println!();
+    send(
+        Instruction {
+            program_id: antegen_thread_program::ID,
+            accounts: antegen_thread_program::accounts::ThreadCreate {
+                authority: authority.pubkey(),
+                payer: authority.pubkey(),
+                thread: thread_pubkey,
+                nonce_account: None,
+                recent_blockhashes: None,
+                rent: None,
+                system_program: anchor_lang::system_program::ID,
+                fiber: None,
+                fiber_program: None,
+            }
+            .to_account_metas(Some(false)),
+            data: antegen_thread_program::instruction::CreateThread {
+                amount,
+                id: thread_id,
+                trigger,
+                paused: Some(true),
+                instruction: None,
+                priority_fee: None,
+                lookup_tables: Vec::new(),
+            }
+            .data(),
+        },
+        "create thread".to_string(),
+    )
+    .await?;
+
+    for (fiber_index, instruction, priority_fee, lookup_tables) in fibers {
+        let fiber = antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, fiber_index);
+        let ix = match instruction {
+            Some(instruction) => Instruction {
+                program_id: antegen_thread_program::ID,
+                accounts: antegen_thread_program::accounts::FiberCreate {
+                    authority: authority.pubkey(),
+                    thread: thread_pubkey,
+                    fiber,
+                    fiber_program: antegen_fiber_program::ID,
+                    system_program: anchor_lang::system_program::ID,
+                }
+                .to_account_metas(Some(false)),
+                data: antegen_thread_program::instruction::CreateFiber {
+                    fiber_index,
+                    instruction,
+                    priority_fee,
+                    lookup_tables,
+                }
+                .data(),
+            },
+            // Idle fibers can only be created through update_fiber
+            None => Instruction {
+                program_id: antegen_thread_program::ID,
+                accounts: antegen_thread_program::accounts::FiberUpdate {
+                    authority: authority.pubkey(),
+                    thread: thread_pubkey,
+                    fiber,
+                    fiber_program: antegen_fiber_program::ID,
+                    system_program: anchor_lang::system_program::ID,
+                }
+                .to_account_metas(Some(false)),
+                data: antegen_thread_program::instruction::UpdateFiber {
+                    fiber_index,
+                    instruction: None,
+                    priority_fee: Some(priority_fee),
+                    track: true,
+                    lookup_tables: Some(lookup_tables),
+                }
+                .data(),
+            },
+        };
+        send(ix, format!("create fiber {}", fiber_index)).await?;
+    }
+
+    if !export.paused || fee_payer_mode.is_some() {
+        send(
+            Instruction {
+                program_id: antegen_thread_program::ID,
+                accounts: antegen_thread_program::accounts::ThreadUpdate {
+                    authority: authority.pubkey(),
+                    thread: thread_pubkey,
+                }
+                .to_account_metas(Some(false)),
+                data: antegen_thread_program::instruction::UpdateThread {
+                    params: ThreadUpdateParams {
+                        paused: Some(export.paused),
+                        trigger: None,
+                        fee_payer_mode: fee_payer_mode.map(Some),
+                        resume_at: None,
+                    },
+                }
+                .data(),
+            },
+            "apply thread settings".to_string(),
+        )
+        .await?;
+    }
+
+    println!("\nThread imported: {}", thread_pubkey);
+    Ok(())
+}
+
+/// Change a thread's trigger and/or add fibers in one transaction
+///
+/// Each `--add-fiber` file holds one instruction in the `thread export`
+/// format (`program_id`, `accounts`, `data_hex`). New fibers take the next
+/// free indices; their rent is transferred to the thread in the same
+/// transaction.
+pub async fn update(
+    address: String,
+    trigger: Option<String>,
+    paused: Option<bool>,
+    add_fiber: Vec<PathBuf>,
+    priority_fee: u64,
+    rpc_url: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use antegen_thread_program::instructions::{FiberCreateParams, ThreadUpdateParams};
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        rent::Rent,
+        signer::Signer,
+        transaction::Transaction,
+    };
+
+    let thread_pubkey =
+        Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+    if trigger.is_none() && paused.is_none() && add_fiber.is_empty() {
+        return Err(anyhow!(
+            "Nothing to update: pass --trigger, --paused or --add-fiber"
+        ));
+    }
+    let trigger = trigger
+        .map(|spec| {
+            trigger_str::parse(&spec).map_err(|e| anyhow!("Invalid trigger '{}': {}", spec, e))
+        })
+        .transpose()?;
+    let fibers = add_fiber
+        .iter()
+        .map(|path| {
+            let reader = std::fs::File::open(path)
+                .map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+            let ix: InstructionExport = serde_json::from_reader(std::io::BufReader::new(reader))
+                .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+            Ok(FiberCreateParams {
+                instruction: ix.to_serializable()?,
+                priority_fee,
+                lookup_tables: Vec::new(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let authority = get_keypair(keypair_path)?;
+    let rpc_url = get_rpc_url(rpc_url)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let account = client
+        .get_account(&thread_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch thread: {}", e))?
+        .ok_or_else(|| anyhow!("Thread not found: {}", thread_pubkey))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode thread data: {}", e))?;
+    let thread = Thread::try_deserialize(&mut data.as_slice())
+        .map_err(|e| anyhow!("Failed to deserialize thread: {}", e))?;
+    if thread.authority != authority.pubkey() {
+        return Err(anyhow!(
+            "Thread {} belongs to {}, not {}",
+            thread_pubkey,
+            thread.authority,
+            authority.pubkey()
+        ));
+    }
+    if thread.fiber_ids.len() + fibers.len() > antegen_thread_program::MAX_FIBERS {
+        return Err(anyhow!(
+            "Thread has {} fibers; adding {} would exceed the limit of {}",
+            thread.fiber_ids.len(),
+            fibers.len(),
+            antegen_thread_program::MAX_FIBERS
+        ));
+    }
+
+    let mut accounts = antegen_thread_program::accounts::ThreadUpdateAtomic {
+        authority: authority.pubkey(),
+        thread: thread_pubkey,
+        fiber_program: antegen_fiber_program::ID,
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(Some(false));
+    for offset in 0..fibers.len() {
+        let index = thread.fiber_next_id as usize + offset;
+        let index = u8::try_from(index).map_err(|_| anyhow!("No free fiber index left"))?;
+        let fiber = antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, index);
+        println!("  fiber {}: {}", index, fiber);
+        accounts.push(AccountMeta::new(fiber, false));
+    }
+
+    let mut instructions = Vec::new();
+    if !fibers.is_empty() {
+        let fiber_rent = Rent::default()
+            .minimum_balance(8 + antegen_fiber_program::state::FiberVersionedState::INIT_SPACE);
+        instructions.push(solana_system_interface::instruction::transfer(
+            &authority.pubkey(),
+            &thread_pubkey,
+            fiber_rent * fibers.len() as u64,
+        ));
+    }
+    instructions.push(Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts,
+        data: antegen_thread_program::instruction::UpdateThreadAtomic {
+            params: ThreadUpdateParams {
+                paused,
+                trigger,
+                fee_payer_mode: None,
+                resume_at: None,
+            },
+            fibers,
+        }
+        .data(),
+    });
+
+    let (blockhash, _) = client.get_latest_blockhash().await?;
+    let message = Message::new(&instructions, Some(&authority.pubkey()));
+    let tx = Transaction::new(&[&authority], message, blockhash);
+    let sig = client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .map_err(|e| anyhow!("Failed to update thread: {}", e))?;
+
+    println!("Thread updated: {}", thread_pubkey);
+    println!("  signature: {}", sig);
+    Ok(())
+}
+
+/// Pause a thread, optionally until a unix or RFC 3339 timestamp
+///
+/// With `--until` the thread resumes on its own: the first exec after that
+/// time unpauses it, no further transaction needed.
+pub async fn pause(
+    address: String,
+    until: Option<String>,
+    rpc_url: Option<String>,
+    keypair_path: Option<PathBuf>,
+) -> Result<()> {
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use antegen_thread_program::instructions::ThreadUpdateParams;
+    use solana_sdk::{
+        instruction::Instruction, message::Message, signer::Signer, transaction::Transaction,
+    };
+
+    let thread_pubkey =
+        Pubkey::from_str(&address).map_err(|e| anyhow!("Invalid pubkey '{}': {}", address, e))?;
+    let resume_at = until.as_deref().map(parse_resume_at).transpose()?;
+    if let Some(resume_at) = resume_at {
+        if resume_at <= chrono::Utc::now().timestamp() {
+            return Err(anyhow!("--until must be in the future"));
+        }
+    }
+
+    let authority = get_keypair(keypair_path)?;
+    let rpc_url = get_rpc_url(rpc_url)?;
+    let client =
+        RpcPool::with_url(&rpc_url).map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
+
+    let ix = Instruction {
+        program_id: antegen_thread_program::ID,
+        accounts: antegen_thread_program::accounts::ThreadUpdate {
+            authority: authority.pubkey(),
+            thread: thread_pubkey,
+        }
+        .to_account_metas(Some(false)),
+        data: antegen_thread_program::instruction::UpdateThread {
+            params: ThreadUpdateParams {
+                paused: Some(true),
+                resume_at,
+                ..Default::default()
+            },
+        }
+        .data(),
+    };
+
+    let (blockhash, _) = client.get_latest_blockhash().await?;
+    let message = Message::new(&[ix], Some(&authority.pubkey()));
+    let tx = Transaction::new(&[&authority], message, blockhash);
+    let sig = client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .map_err(|e| anyhow!("Failed to pause thread: {}", e))?;
+
+    println!("Thread paused: {}", thread_pubkey);
+    if let Some(until) = until {
+        println!("  resumes at: {}", until);
+    }
+    println!("  signature: {}", sig);
+    Ok(())
+}
+
+/// Parse `--until` as unix seconds or an RFC 3339 timestamp
+fn parse_resume_at(s: &str) -> Result<i64> {
+    if let Ok(ts) = s.parse::<i64>() {
+        return Ok(ts);
+    }
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.timestamp())
+        .map_err(|e| anyhow!("Invalid --until '{}': {}", s, e))
+}
+
 // =============================================================================
 // Admin commands (only available with `dev` feature)
 // =============================================================================
 
 /// Admin: force delete a thread (skips all checks)
+///
+/// The thread's lamports go back to its authority. If the thread still
+/// deserializes, all of its fibers are closed with it and their rent goes to
+/// the authority as well; otherwise they are left behind.
 #[cfg(feature = "dev")]
 pub async fn admin_delete(
     address: String,
@@ -134,7 +1736,10 @@ pub async fn admin_delete(
 ) -> Result<()> {
     use anchor_lang::{InstructionData, ToAccountMetas};
     use solana_sdk::{
-        instruction::Instruction, message::Message, signer::Signer, transaction::Transaction,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        signer::Signer,
+        transaction::Transaction,
     };
 
     let thread_pubkey =
@@ -156,18 +1761,51 @@ pub async fn admin_delete(
         &antegen_thread_program::ID,
     );
 
+    let data = client
+        .get_account(&thread_pubkey)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch thread: {}", e))?
+        .ok_or_else(|| anyhow!("Thread {} not found", thread_pubkey))?
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode thread data: {}", e))?;
+
+    // The program only pays the authority, read from the raw data
+    let rent_recipient = Thread::authority_from_data(&data)
+        .ok_or_else(|| anyhow!("Account {} is not a thread", thread_pubkey))?;
+    println!("Rent recipient (authority): {}", rent_recipient);
+
+    // Fibers can only be closed if the thread deserializes (it signs the close)
+    let thread = Thread::try_deserialize(&mut data.as_slice()).ok();
+    let fiber_accounts: Vec<Pubkey> = match &thread {
+        Some(thread) => thread
+            .fiber_ids
+            .iter()
+            .map(|&idx| antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, idx))
+            .collect(),
+        None => {
+            println!("Thread does not deserialize; its fibers will be left behind");
+            Vec::new()
+        }
+    };
+
     // Build ThreadDelete instruction
-    let accounts = antegen_thread_program::accounts::ThreadDelete {
+    let mut accounts = antegen_thread_program::accounts::ThreadDelete {
         admin: admin.pubkey(),
+        rent_recipient,
         config: config_pubkey,
         thread: thread_pubkey,
-    };
+        fiber_program: (!fiber_accounts.is_empty()).then_some(antegen_fiber_program::ID),
+    }
+    .to_account_metas(None);
+    for fiber in &fiber_accounts {
+        accounts.push(AccountMeta::new(*fiber, false));
+    }
 
     let data = antegen_thread_program::instruction::DeleteThread {}.data();
 
     let ix = Instruction {
         program_id: antegen_thread_program::ID,
-        accounts: accounts.to_account_metas(None),
+        accounts,
         data,
     };
 
@@ -186,6 +1824,9 @@ pub async fn admin_delete(
         .map_err(|e| anyhow!("Failed to delete thread: {}", e))?;
 
     println!("\n✓ Thread deleted successfully!");
+    if !fiber_accounts.is_empty() {
+        println!("Closed {} fiber(s)", fiber_accounts.len());
+    }
     println!("Signature: {}", sig);
 
     Ok(())
@@ -331,62 +1972,8 @@ mod test_commands {
     }
 
     /// Parse trigger string into Trigger enum
-    fn parse_trigger(trigger_str: &str) -> Result<Trigger> {
-        match trigger_str {
-            "immediate" => Ok(Trigger::Immediate { jitter: 0 }),
-            s if s.starts_with("cron:") => Ok(Trigger::Cron {
-                schedule: s.trim_start_matches("cron:").to_string(),
-                skippable: true,
-                jitter: 0,
-            }),
-            s if s.starts_with("interval:") => {
-                let seconds: i64 = s
-                    .trim_start_matches("interval:")
-                    .parse()
-                    .map_err(|_| anyhow!("Invalid interval seconds"))?;
-                Ok(Trigger::Interval {
-                    seconds,
-                    skippable: true,
-                    jitter: 0,
-                })
-            }
-            s if s.starts_with("timestamp:") => {
-                let unix_ts: i64 = s
-                    .trim_start_matches("timestamp:")
-                    .parse()
-                    .map_err(|_| anyhow!("Invalid timestamp"))?;
-                Ok(Trigger::Timestamp { unix_ts, jitter: 0 })
-            }
-            s if s.starts_with("slot:") => {
-                let slot: u64 = s
-                    .trim_start_matches("slot:")
-                    .parse()
-                    .map_err(|_| anyhow!("Invalid slot number"))?;
-                Ok(Trigger::Slot { slot })
-            }
-            s if s.starts_with("epoch:") => {
-                let epoch: u64 = s
-                    .trim_start_matches("epoch:")
-                    .parse()
-                    .map_err(|_| anyhow!("Invalid epoch number"))?;
-                Ok(Trigger::Epoch { epoch })
-            }
-            s if s.starts_with("account:") => {
-                let address = s.trim_start_matches("account:");
-                let pubkey = Pubkey::from_str(address)
-                    .map_err(|e| anyhow!("Invalid account pubkey: {}", e))?;
-                Ok(Trigger::Account {
-                    address: pubkey,
-                    offset: 0,
-                    size: 100, // Watch first 100 bytes by default
-                })
-            }
-            _ => Err(anyhow!(
-                "Unknown trigger: {}. Options: immediate, cron:<schedule>, interval:<secs>, \
-                 timestamp:<unix>, slot:<num>, epoch:<num>, account:<pubkey>",
-                trigger_str
-            )),
-        }
+    fn parse_trigger(input: &str) -> Result<Trigger> {
+        trigger_str::parse(input).map_err(|e| anyhow!("Invalid trigger '{}': {}", input, e))
     }
 
     /// Parse signal string into Signal enum (for single fiber signals like fiber add)
@@ -404,8 +1991,15 @@ mod test_commands {
                     .map_err(|_| anyhow!("Invalid next index"))?;
                 Ok(Some(Signal::Next { index: idx }))
             }
+            Some(sig) if sig.starts_with("goto:") => {
+                let idx: u8 = sig
+                    .trim_start_matches("goto:")
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid goto index"))?;
+                Ok(Some(Signal::Goto { index: idx }))
+            }
             Some(sig) => Err(anyhow!(
-                "Unknown signal: {}. Options: chain, next:T, repeat, close",
+                "Unknown signal: {}. Options: chain, next:T, goto:T, repeat, close",
                 sig
             )),
         }
@@ -427,8 +2021,8 @@ mod test_commands {
             let mut max_idx: u8 = 0;
             for (&fiber_idx, signal) in &self.per_fiber_signals {
                 max_idx = max_idx.max(fiber_idx);
-                // Also check target indices in Next signals (Chain always targets next fiber)
-                if let Signal::Next { index } = signal {
+                // Also check target indices in Next/Goto signals (Chain always targets next fiber)
+                if let Signal::Next { index } | Signal::Goto { index } = signal {
                     max_idx = max_idx.max(*index);
                 }
             }
@@ -437,7 +2031,7 @@ mod test_commands {
     }
 
     /// Parse repeated --signal flags into FiberSignalConfig
-    /// Formats: "repeat", "close" (fiber 0), or "F:chain", "F:next:T" (specific fiber)
+    /// Formats: "repeat", "close" (fiber 0), or "F:chain", "F:next:T", "F:goto:T" (specific fiber)
     fn parse_fiber_signals(signals: &[String]) -> Result<FiberSignalConfig> {
         let mut config = FiberSignalConfig {
             per_fiber_signals: HashMap::new(),
@@ -457,7 +2051,7 @@ mod test_commands {
     }
 
     /// Parse a single signal string that may include fiber index
-    /// Formats: "repeat", "close", "none" (fiber 0), "F:chain", "F:repeat", "F:close", "F:none", or "F:next:T"
+    /// Formats: "repeat", "close", "none" (fiber 0), "F:chain", "F:repeat", "F:close", "F:none", "F:next:T", or "F:goto:T"
     fn parse_signal_with_fiber(s: &str) -> Result<(u8, Signal)> {
         match s {
             // Simple signals → apply to fiber 0
@@ -465,7 +2059,7 @@ mod test_commands {
             "close" => Ok((0, Signal::Close)),
             "none" => Ok((0, Signal::None)),
 
-            // Per-fiber formats: "F:signal" or "F:next:T"
+            // Per-fiber formats: "F:signal", "F:next:T" or "F:goto:T"
             _ => {
                 let parts: Vec<&str> = s.split(':').collect();
 
@@ -489,19 +2083,24 @@ mod test_commands {
                     return Ok((fiber_idx, signal));
                 }
 
-                // "F:next:T" format (3 parts)
-                if parts.len() == 3 && parts[1] == "next" {
+                // "F:next:T" / "F:goto:T" format (3 parts)
+                if parts.len() == 3 && matches!(parts[1], "next" | "goto") {
                     let fiber_idx: u8 = parts[0]
                         .parse()
                         .map_err(|_| anyhow!("Invalid fiber index: {}", parts[0]))?;
                     let target: u8 = parts[2]
                         .parse()
                         .map_err(|_| anyhow!("Invalid target index: {}", parts[2]))?;
-                    return Ok((fiber_idx, Signal::Next { index: target }));
+                    let signal = if parts[1] == "next" {
+                        Signal::Next { index: target }
+                    } else {
+                        Signal::Goto { index: target }
+                    };
+                    return Ok((fiber_idx, signal));
                 }
 
                 Err(anyhow!(
-                    "Invalid signal format: '{}'. Expected F:signal or F:next:T",
+                    "Invalid signal format: '{}'. Expected F:signal, F:next:T or F:goto:T",
                     s
                 ))
             }
@@ -624,6 +2223,7 @@ mod test_commands {
     pub async fn test(
         rpc: Option<String>,
         keypair_path: Option<PathBuf>,
+        output: OutputFormat,
         cmd: crate::TestCommands,
     ) -> Result<()> {
         use crate::TestCommands;
@@ -637,13 +2237,13 @@ mod test_commands {
         let rpc_url = get_rpc_url(rpc)?;
         let payer = get_keypair(keypair_path)?;
 
-        println!("RPC: {}", rpc_url);
-        println!("Payer: {}", payer.pubkey());
+        output.note(format!("RPC: {}", rpc_url));
+        output.note(format!("Payer: {}", payer.pubkey()));
 
         // Get or create the CLI test keypair for thread authority
         let test_authority = get_or_create_test_keypair()?;
         let authority = test_authority.pubkey();
-        println!("Thread authority (test keypair): {}", authority);
+        output.note(format!("Thread authority (test keypair): {}", authority));
 
         let client = RpcPool::with_url(&rpc_url)
             .map_err(|e| anyhow!("Failed to create RPC client: {}", e))?;
@@ -760,6 +2360,47 @@ mod test_commands {
                     ));
                 }
             }
+            TestCommands::Stress {
+                count,
+                interval,
+                jitter,
+                fibers,
+                batch_size,
+                durable_ratio,
+                max_tps,
+                manifest,
+                verify_sample,
+                teardown,
+            } => {
+                if let Some(manifest) = teardown {
+                    return stress_teardown(
+                        &client,
+                        &payer,
+                        &test_authority,
+                        &manifest,
+                        batch_size,
+                        max_tps,
+                    )
+                    .await;
+                }
+                return stress_create(
+                    &client,
+                    &payer,
+                    &test_authority,
+                    StressOptions {
+                        count,
+                        interval,
+                        jitter,
+                        fibers,
+                        batch_size,
+                        durable_ratio,
+                        max_tps,
+                        manifest,
+                        verify_sample,
+                    },
+                )
+                .await;
+            }
             TestCommands::List => {
                 // Already handled above, but keep for exhaustiveness
                 return list_test_threads();
@@ -778,7 +2419,7 @@ mod test_commands {
                         .await;
                     }
                     TestFiberCommands::List { id } => {
-                        return test_fiber_list(&client, &id).await;
+                        return test_fiber_list(&client, &authority, &id, output).await;
                     }
                     TestFiberCommands::Delete { id, index } => {
                         return test_fiber_delete(&client, &payer, &test_authority, &id, index)
@@ -919,24 +2560,40 @@ mod test_commands {
 
         // Parse trigger
         let trigger = parse_trigger(trigger_str)?;
-        println!("Trigger: {:?}", trigger);
+        println!("Trigger: {}", trigger_str::format(&trigger));
 
         // Get signal for default fiber (index 0) if specified
-        let default_signal = signal_config.per_fiber_signals.get(&0).cloned();
-        if let Some(ref sig) = default_signal {
+        if let Some(sig) = signal_config.per_fiber_signals.get(&0) {
             println!("Default fiber signal: {:?}", sig);
         }
 
-        // Build thread_memo instruction as default fiber
-        let memo_instruction = build_thread_memo_instruction(
-            thread_pubkey,
-            format!("Test thread '{}' fiber 0 executed", thread_id),
-            default_signal,
-        );
-        let serializable_ix: SerializableInstruction = memo_instruction.into();
+        // Build a thread_memo instruction per fiber
+        let memos: Vec<SerializableInstruction> = (0..fiber_count.max(1))
+            .map(|i| {
+                let message = if i == 0 {
+                    format!("Test thread '{}' fiber 0 executed", thread_id)
+                } else {
+                    format!("Fiber {} executed", i)
+                };
+                let signal = signal_config.per_fiber_signals.get(&i).cloned();
+                build_thread_memo_instruction(thread_pubkey, message, signal).into()
+            })
+            .collect();
+
+        // Create as many fibers as fit along with the thread
+        let mut initial = 0;
+        while initial < memos.len().min(antegen_thread_program::MAX_INITIAL_FIBERS)
+            && memos[..=initial]
+                .iter()
+                .map(compiled_instruction_size)
+                .sum::<usize>()
+                <= antegen_thread_program::MAX_INITIAL_INSTRUCTIONS_SIZE
+        {
+            initial += 1;
+        }
 
-        // Build ThreadCreate accounts
-        let accounts = antegen_thread_program::accounts::ThreadCreate {
+        // Build ThreadCreate accounts, fiber accounts go in remaining_accounts
+        let mut accounts = antegen_thread_program::accounts::ThreadCreate {
             authority: authority.pubkey(),
             payer: payer.pubkey(),
             thread: thread_pubkey,
@@ -945,17 +2602,23 @@ mod test_commands {
             rent: None,
             system_program: anchor_lang::system_program::ID,
             fiber: None,
-            fiber_program: None,
+            fiber_program: Some(antegen_fiber_program::ID),
         }
         .to_account_metas(Some(false));
+        for i in 0..initial as u8 {
+            accounts.push(solana_sdk::instruction::AccountMeta::new(
+                antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, i),
+                false,
+            ));
+        }
 
         // Build instruction data - use custom thread_id
-        let data = antegen_thread_program::instruction::CreateThread {
+        let data = antegen_thread_program::instruction::CreateThreadWithFibers {
             amount: LAMPORTS_PER_SOL / 10, // 0.1 SOL
             id: thread_id.into(),
             trigger,
             paused: None,
-            instruction: Some(serializable_ix),
+            instructions: memos[..initial].to_vec(),
             priority_fee: Some(0),
             lookup_tables: Vec::new(),
         }
@@ -970,8 +2633,8 @@ mod test_commands {
         // Build all instructions (thread_create + fiber_creates)
         let mut instructions = vec![thread_create_ix];
 
-        // Add fiber_create instructions for additional fibers
-        for i in 1..fiber_count {
+        // Add fiber_create instructions for fibers that didn't fit
+        for i in initial as u8..fiber_count {
             let fiber_signal = signal_config.per_fiber_signals.get(&i).cloned();
             let fiber_ix =
                 build_fiber_create_instruction(payer, authority, thread_pubkey, i, fiber_signal);
@@ -997,6 +2660,43 @@ mod test_commands {
         Ok(())
     }
 
+    /// Build a thread_close instruction that also closes all of the thread's fibers
+    fn build_close_thread_instruction(
+        authority: Pubkey,
+        close_to: Pubkey,
+        thread_pubkey: Pubkey,
+        thread: &Thread,
+    ) -> Instruction {
+        use solana_sdk::instruction::AccountMeta;
+
+        let fiber_program = if thread.fiber_ids.is_empty() {
+            None
+        } else {
+            Some(antegen_fiber_program::ID)
+        };
+
+        let mut accounts = antegen_thread_program::accounts::ThreadClose {
+            authority,
+            close_to,
+            thread: thread_pubkey,
+            fiber_program,
+        }
+        .to_account_metas(Some(false));
+
+        // Add fiber accounts to remaining_accounts so they can be closed
+        for fiber_id in &thread.fiber_ids {
+            let fiber_pubkey =
+                antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, *fiber_id);
+            accounts.push(AccountMeta::new(fiber_pubkey, false));
+        }
+
+        Instruction {
+            program_id: antegen_thread_program::ID,
+            accounts,
+            data: antegen_thread_program::instruction::CloseThread {}.data(),
+        }
+    }
+
     /// Delete the test thread
     async fn delete_test_thread(
         client: &RpcPool,
@@ -1004,8 +2704,6 @@ mod test_commands {
         authority: &Keypair,
         thread_pubkey: Pubkey,
     ) -> Result<()> {
-        use solana_sdk::instruction::AccountMeta;
-
         println!("\nDeleting test thread...");
 
         // Fetch thread to get fiber_ids
@@ -1020,37 +2718,13 @@ mod test_commands {
         let thread = Thread::try_deserialize(&mut data.as_slice())
             .map_err(|e| anyhow!("Failed to deserialize thread: {:?}", e))?;
 
-        // Build ThreadClose accounts
         // Close_to receives the lamports - send to payer
-        let fiber_program = if thread.fiber_ids.is_empty() {
-            None
-        } else {
-            Some(antegen_fiber_program::ID)
-        };
-
-        let mut accounts = antegen_thread_program::accounts::ThreadClose {
-            authority: authority.pubkey(),
-            close_to: payer.pubkey(),
-            thread: thread_pubkey,
-            fiber_program,
-        }
-        .to_account_metas(Some(false));
-
-        // Add fiber accounts to remaining_accounts so they can be closed
-        for fiber_id in &thread.fiber_ids {
-            let fiber_pubkey =
-                antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, *fiber_id);
-            accounts.push(AccountMeta::new(fiber_pubkey, false));
-        }
-
-        // Build instruction data
-        let data = antegen_thread_program::instruction::CloseThread {}.data();
-
-        let ix = Instruction {
-            program_id: antegen_thread_program::ID,
-            accounts,
-            data,
-        };
+        let ix = build_close_thread_instruction(
+            authority.pubkey(),
+            payer.pubkey(),
+            thread_pubkey,
+            &thread,
+        );
 
         // Send transaction - authority must sign, payer pays
         let (blockhash, _) = client.get_latest_blockhash().await?;
@@ -1099,8 +2773,11 @@ mod test_commands {
             recent_blockhashes: None,
             rent: None,
             system_program: anchor_lang::system_program::ID,
-            fiber: None,
-            fiber_program: None,
+            fiber: Some(antegen_fiber_program::state::FiberState::pubkey(
+                thread_a_pubkey,
+                0,
+            )),
+            fiber_program: Some(antegen_fiber_program::ID),
         }
         .to_account_metas(Some(false));
 
@@ -1152,8 +2829,11 @@ mod test_commands {
             recent_blockhashes: None,
             rent: None,
             system_program: anchor_lang::system_program::ID,
-            fiber: None,
-            fiber_program: None,
+            fiber: Some(antegen_fiber_program::state::FiberState::pubkey(
+                thread_b_pubkey,
+                0,
+            )),
+            fiber_program: Some(antegen_fiber_program::ID),
         }
         .to_account_metas(Some(false));
 
@@ -1229,8 +2909,11 @@ mod test_commands {
             recent_blockhashes: None,
             rent: None,
             system_program: anchor_lang::system_program::ID,
-            fiber: None,
-            fiber_program: None,
+            fiber: Some(antegen_fiber_program::state::FiberState::pubkey(
+                thread_pubkey,
+                0,
+            )),
+            fiber_program: Some(antegen_fiber_program::ID),
         }
         .to_account_metas(Some(false));
 
@@ -1408,56 +3091,222 @@ mod test_commands {
         Ok(())
     }
 
-    /// List fibers on a test thread
-    async fn test_fiber_list(client: &RpcPool, thread_id: &str) -> Result<()> {
-        // Look up thread from registry
-        let registry = TestThreadRegistry::load()?;
-        let entry = registry
-            .get_thread(thread_id)
-            .ok_or_else(|| anyhow!("Thread '{}' not found in registry", thread_id))?;
-        let thread_pubkey = Pubkey::from_str(&entry.pubkey)
-            .map_err(|e| anyhow!("Invalid pubkey in registry: {}", e))?;
+    /// One fiber in `thread test fiber list`
+    #[derive(Serialize)]
+    struct TestFiberRow {
+        index: u8,
+        address: String,
+        /// `None` for idle fibers with no instruction
+        program_id: Option<String>,
+        accounts: usize,
+        /// Hex of the first 16 bytes of instruction data
+        data_preview: String,
+        data_len: usize,
+        exec_count: u64,
+        #[serde(with = "as_string")]
+        lamports: u64,
+        /// Consecutive failed executions reported against this fiber
+        failures: u8,
+        disabled: bool,
+    }
+
+    /// `thread test fiber list` result for JSON output
+    #[derive(Serialize)]
+    struct TestFiberList {
+        id: String,
+        thread: String,
+        fiber_cursor: u8,
+        fibers: Vec<TestFiberRow>,
+        #[serde(with = "as_string")]
+        total_lamports: u64,
+    }
+
+    /// Bytes of instruction data shown in the fiber list preview
+    const FIBER_DATA_PREVIEW: usize = 16;
+
+    /// Decode a fiber account into a list row
+    fn test_fiber_row(
+        thread: &Thread,
+        index: u8,
+        address: Pubkey,
+        lamports: u64,
+        data: &[u8],
+    ) -> Result<TestFiberRow> {
+        use anchor_lang::AnchorDeserialize;
+        use antegen_thread_program::fiber::{decompile_instruction, CompiledInstructionV0, Fiber};
+
+        let fiber = Fiber::try_deserialize(&mut &data[..])
+            .map_err(|e| anyhow!("Failed to deserialize fiber {}: {:?}", index, e))?;
+        let instruction = if fiber.compiled_instruction().is_empty() {
+            None
+        } else {
+            let compiled = CompiledInstructionV0::deserialize(&mut fiber.compiled_instruction())
+                .map_err(|e| anyhow!("Failed to read fiber {} instruction: {}", index, e))?;
+            Some(
+                decompile_instruction(&compiled).map_err(|e| {
+                    anyhow!("Failed to decompile fiber {} instruction: {:?}", index, e)
+                })?,
+            )
+        };
+        let exec_count = match &fiber {
+            Fiber::Legacy(state) => state.exec_count,
+            Fiber::V1(state) => state.exec_count,
+        };
+
+        Ok(TestFiberRow {
+            index,
+            address: address.to_string(),
+            program_id: instruction.as_ref().map(|ix| ix.program_id.to_string()),
+            accounts: instruction.as_ref().map_or(0, |ix| ix.accounts.len()),
+            data_preview: instruction.as_ref().map_or_else(String::new, |ix| {
+                to_hex(&ix.data[..ix.data.len().min(FIBER_DATA_PREVIEW)])
+            }),
+            data_len: instruction.as_ref().map_or(0, |ix| ix.data.len()),
+            exec_count,
+            lamports,
+            failures: if thread.failing_fiber == index {
+                thread.fiber_failures
+            } else {
+                0
+            },
+            disabled: thread.is_fiber_disabled(index),
+        })
+    }
+
+    /// List a test thread's fiber accounts with their execution stats
+    ///
+    /// Fibers are found with `getProgramAccounts` on the fiber program, so
+    /// accounts the thread no longer tracks show up too.
+    async fn test_fiber_list(
+        client: &RpcPool,
+        authority: &Pubkey,
+        thread_id: &str,
+        output: OutputFormat,
+    ) -> Result<()> {
+        use anchor_lang::Discriminator;
+        use antegen_fiber_program::state::{FiberState, FiberVersionedState};
+        use antegen_thread_program::ThreadId;
+
+        if thread_id.len() > 32 {
+            return Err(anyhow!("Thread ID '{}' is longer than 32 bytes", thread_id));
+        }
+        let (thread_pubkey, _) = antegen_thread_program::pda::thread_pubkey(
+            authority,
+            &ThreadId::Bytes(thread_id.as_bytes().to_vec()),
+        );
 
-        // Fetch thread to get fiber info
         let account = client
             .get_account(&thread_pubkey)
             .await
             .map_err(|e| anyhow!("Failed to fetch thread: {}", e))?
-            .ok_or_else(|| anyhow!("Thread account not found"))?;
+            .ok_or_else(|| anyhow!("Thread '{}' not found at {}", thread_id, thread_pubkey))?;
         let data = account
             .decode_data()
             .map_err(|e| anyhow!("Failed to decode account data: {}", e))?;
         let thread = Thread::try_deserialize(&mut data.as_slice())
             .map_err(|e| anyhow!("Failed to deserialize thread: {:?}", e))?;
 
-        println!("Fibers for thread '{}' ({}):", thread_id, thread_pubkey);
-        println!();
-        println!("  fiber_ids: {:?}", thread.fiber_ids);
-        println!("  fiber_cursor: {}", thread.fiber_cursor);
-        println!("  fiber_next_id: {}", thread.fiber_next_id);
-        println!("  fiber_signal: {:?}", thread.fiber_signal);
-        println!();
-        // Try to fetch individual fiber accounts
-        println!("\nFiber accounts:");
-        for &fiber_id in &thread.fiber_ids {
-            let fiber_pubkey =
-                antegen_fiber_program::state::FiberState::pubkey(thread_pubkey, fiber_id);
-
-            match client.get_account(&fiber_pubkey).await {
-                Ok(Some(fiber_account)) => {
-                    let data_len = fiber_account.decode_data().map(|d| d.len()).unwrap_or(0);
-                    println!(
-                        "  Fiber {}: {} ({} bytes)",
-                        fiber_id, fiber_pubkey, data_len
-                    );
-                }
-                Ok(None) | Err(_) => {
-                    println!("  Fiber {}: {} (not found)", fiber_id, fiber_pubkey);
-                }
-            }
+        // Legacy fibers store the thread right after the discriminator,
+        // versioned fibers after their version byte
+        let mut accounts = Vec::new();
+        for (discriminator, thread_offset) in [
+            (FiberVersionedState::DISCRIMINATOR, 9),
+            (FiberState::DISCRIMINATOR, 8),
+        ] {
+            let filters = vec![
+                serde_json::json!({
+                    "memcmp": { "offset": 0, "bytes": bs58::encode(discriminator).into_string() }
+                }),
+                serde_json::json!({
+                    "memcmp": { "offset": thread_offset, "bytes": thread_pubkey.to_string() }
+                }),
+            ];
+            accounts.extend(
+                client
+                    .get_program_accounts(&antegen_fiber_program::ID, Some(filters))
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch fiber accounts: {}", e))?,
+            );
         }
 
-        Ok(())
+        let indexes: HashMap<Pubkey, u8> = (0..=u8::MAX)
+            .map(|index| (fiber_pubkey(&thread_pubkey, index).0, index))
+            .collect();
+        let mut fibers = Vec::with_capacity(accounts.len());
+        for (address, account) in &accounts {
+            let Some(&index) = indexes.get(address) else {
+                continue;
+            };
+            let data = account
+                .decode_data()
+                .map_err(|e| anyhow!("Failed to decode fiber {} data: {}", index, e))?;
+            fibers.push(test_fiber_row(
+                &thread,
+                index,
+                *address,
+                account.lamports,
+                &data,
+            )?);
+        }
+        fibers.sort_by_key(|fiber| fiber.index);
+
+        let list = TestFiberList {
+            id: thread_id.to_string(),
+            thread: thread_pubkey.to_string(),
+            fiber_cursor: thread.fiber_cursor,
+            total_lamports: fibers.iter().map(|fiber| fiber.lamports).sum(),
+            fibers,
+        };
+        output.print(&list, print_test_fiber_list)
+    }
+
+    fn print_test_fiber_list(list: &TestFiberList) {
+        println!("Fibers for thread '{}' ({}):", list.id, list.thread);
+        println!();
+        if list.fibers.is_empty() {
+            println!("  No fiber accounts found.");
+            return;
+        }
+        println!(
+            "  {:>3}  {:<44}  {:>5}  {:<32}  {:>6}  {}",
+            "IDX", "PROGRAM", "ACCTS", "DATA", "EXECS", "STATUS"
+        );
+        for fiber in &list.fibers {
+            let status = if fiber.disabled {
+                "disabled".to_string()
+            } else if fiber.failures > 0 {
+                format!("{} failures", fiber.failures)
+            } else if fiber.program_id.is_none() {
+                "idle".to_string()
+            } else {
+                "ok".to_string()
+            };
+            let data = if fiber.data_len > FIBER_DATA_PREVIEW {
+                format!("{}…", fiber.data_preview)
+            } else {
+                fiber.data_preview.clone()
+            };
+            println!(
+                "{} {:>3}  {:<44}  {:>5}  {:<32}  {:>6}  {}",
+                if fiber.index == list.fiber_cursor {
+                    ">"
+                } else {
+                    " "
+                },
+                fiber.index,
+                fiber.program_id.as_deref().unwrap_or("-"),
+                fiber.accounts,
+                data,
+                fiber.exec_count,
+                status
+            );
+        }
+        println!();
+        println!(
+            "Total fiber lamports: {} ({:.9} SOL)",
+            list.total_lamports,
+            list.total_lamports as f64 / LAMPORTS_PER_SOL as f64
+        );
     }
 
     /// Delete a fiber from a test thread
@@ -1516,6 +3365,526 @@ mod test_commands {
 
         Ok(())
     }
+
+    // =========================================================================
+    // Stress test
+    // =========================================================================
+
+    /// Largest serialized transaction accepted by the cluster
+    const MAX_TRANSACTION_SIZE: usize = 1232;
+
+    /// Lamports each stress thread is funded with
+    const STRESS_THREAD_FUNDING: u64 = LAMPORTS_PER_SOL / 100;
+
+    /// Creation/teardown transactions in flight at once
+    const STRESS_MAX_IN_FLIGHT: usize = 8;
+
+    /// How often sampled threads are polled while verifying execution
+    const STRESS_VERIFY_POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+    /// Extra time (beyond interval + jitter) allowed for the first execution
+    const STRESS_VERIFY_GRACE_SECS: u64 = 60;
+
+    /// Threads created by a stress run, written so they can be verified and
+    /// torn down later
+    #[derive(Serialize, Deserialize)]
+    struct StressManifest {
+        authority: String,
+        created_at: String,
+        trigger: String,
+        fibers: u8,
+        threads: Vec<StressThread>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    struct StressThread {
+        id: String,
+        pubkey: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nonce_account: Option<String>,
+    }
+
+    /// Options for `antegen thread test stress`
+    struct StressOptions {
+        count: u32,
+        interval: i64,
+        jitter: u64,
+        fibers: u8,
+        batch_size: usize,
+        durable_ratio: f64,
+        max_tps: f64,
+        manifest: PathBuf,
+        verify_sample: usize,
+    }
+
+    /// One thread's share of a batched transaction
+    struct StressItem {
+        thread: StressThread,
+        instructions: Vec<Instruction>,
+        nonce: Option<Keypair>,
+    }
+
+    /// Whether thread `index` gets a durable nonce, spreading durable threads
+    /// evenly through the run
+    fn is_durable(index: u32, ratio: f64) -> bool {
+        ((index + 1) as f64 * ratio).floor() > (index as f64 * ratio).floor()
+    }
+
+    /// Build thread_create (with fiber 0) plus fiber_create for the remaining fibers
+    fn build_stress_thread(
+        payer: &Keypair,
+        authority: &Keypair,
+        thread_id: &str,
+        trigger: &Trigger,
+        fibers: u8,
+        durable: bool,
+    ) -> StressItem {
+        use solana_sdk::sysvar;
+
+        let (thread_pubkey, _) = derive_thread_pda(authority.pubkey(), thread_id);
+        let nonce = durable.then(Keypair::new);
+
+        let memo_ix = build_thread_memo_instruction(
+            thread_pubkey,
+            format!("Stress thread '{}' fiber 0 executed", thread_id),
+            None,
+        );
+
+        let accounts = antegen_thread_program::accounts::ThreadCreate {
+            authority: authority.pubkey(),
+            payer: payer.pubkey(),
+            thread: thread_pubkey,
+            nonce_account: nonce.as_ref().map(|n| n.pubkey()),
+            recent_blockhashes: nonce.as_ref().map(|_| sysvar::recent_blockhashes::ID),
+            rent: nonce.as_ref().map(|_| sysvar::rent::ID),
+            system_program: anchor_lang::system_program::ID,
+            fiber: Some(antegen_fiber_program::state::FiberState::pubkey(
+                thread_pubkey,
+                0,
+            )),
+            fiber_program: Some(antegen_fiber_program::ID),
+        }
+        .to_account_metas(Some(false));
+
+        let data = antegen_thread_program::instruction::CreateThread {
+            amount: STRESS_THREAD_FUNDING,
+            id: thread_id.into(),
+            trigger: trigger.clone(),
+            paused: None,
+            instruction: Some(memo_ix.into()),
+            priority_fee: Some(0),
+            lookup_tables: Vec::new(),
+        }
+        .data();
+
+        let mut instructions = vec![Instruction {
+            program_id: antegen_thread_program::ID,
+            accounts,
+            data,
+        }];
+        for i in 1..fibers {
+            instructions.push(build_fiber_create_instruction(
+                payer,
+                authority,
+                thread_pubkey,
+                i,
+                None,
+            ));
+        }
+
+        StressItem {
+            thread: StressThread {
+                id: thread_id.to_string(),
+                pubkey: thread_pubkey.to_string(),
+                nonce_account: nonce.as_ref().map(|n| n.pubkey().to_string()),
+            },
+            instructions,
+            nonce,
+        }
+    }
+
+    /// Serialized size of a transaction carrying `instructions`
+    fn transaction_size(payer: &Pubkey, instructions: &[Instruction]) -> usize {
+        let message = Message::new(instructions, Some(payer));
+        // Signature count (shortvec, one byte below 128) plus the signatures
+        1 + 64 * message.header.num_required_signatures as usize + message.serialize().len()
+    }
+
+    /// Group items into transactions of at most `max_per_tx` threads that fit
+    /// within the transaction size limit
+    fn pack_transactions(
+        payer: &Pubkey,
+        items: Vec<StressItem>,
+        max_per_tx: usize,
+    ) -> Result<Vec<Vec<StressItem>>> {
+        let mut batches: Vec<Vec<StressItem>> = Vec::new();
+        let mut current: Vec<StressItem> = Vec::new();
+        let mut instructions: Vec<Instruction> = Vec::new();
+
+        for item in items {
+            let mut candidate = instructions.clone();
+            candidate.extend(item.instructions.iter().cloned());
+
+            if !current.is_empty()
+                && (current.len() >= max_per_tx
+                    || transaction_size(payer, &candidate) > MAX_TRANSACTION_SIZE)
+            {
+                batches.push(std::mem::take(&mut current));
+                candidate = item.instructions.clone();
+            }
+            if transaction_size(payer, &candidate) > MAX_TRANSACTION_SIZE {
+                return Err(anyhow!(
+                    "Thread '{}' does not fit in a single transaction; use fewer fibers",
+                    item.thread.id
+                ));
+            }
+
+            instructions = candidate;
+            current.push(item);
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+
+        Ok(batches)
+    }
+
+    /// Send packed transactions, at most `max_tps` per second, returning the
+    /// threads of each transaction with its result
+    async fn send_stress_batches(
+        client: &RpcPool,
+        payer: &Keypair,
+        authority: &Keypair,
+        batches: Vec<Vec<StressItem>>,
+        max_tps: f64,
+        progress: &indicatif::ProgressBar,
+    ) -> Vec<(Vec<StressThread>, Result<solana_sdk::signature::Signature>)> {
+        use futures::stream::{self, StreamExt};
+        use tokio::time::{interval, MissedTickBehavior};
+
+        let mut ticker = interval(std::time::Duration::from_secs_f64(1.0 / max_tps));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let limiter = tokio::sync::Mutex::new(ticker);
+
+        stream::iter(batches)
+            .map(|batch| {
+                let limiter = &limiter;
+                async move {
+                    limiter.lock().await.tick().await;
+
+                    let instructions: Vec<Instruction> = batch
+                        .iter()
+                        .flat_map(|item| item.instructions.iter().cloned())
+                        .collect();
+                    let mut signers: Vec<&Keypair> = vec![payer, authority];
+                    signers.extend(batch.iter().filter_map(|item| item.nonce.as_ref()));
+
+                    let result = async {
+                        let (blockhash, _) = client.get_latest_blockhash().await?;
+                        let message = Message::new(&instructions, Some(&payer.pubkey()));
+                        let tx = Transaction::new(signers.as_slice(), message, blockhash);
+                        client.send_and_confirm_transaction(&tx).await
+                    }
+                    .await;
+
+                    progress.inc(batch.len() as u64);
+                    let threads = batch.into_iter().map(|item| item.thread).collect();
+                    (threads, result)
+                }
+            })
+            .buffer_unordered(STRESS_MAX_IN_FLIGHT)
+            .collect()
+            .await
+    }
+
+    fn stress_progress_bar(len: u64) -> Result<indicatif::ProgressBar> {
+        let progress = indicatif::ProgressBar::new(len);
+        progress.set_style(
+            indicatif::ProgressStyle::with_template("{bar:40} {pos}/{len} threads {msg}")
+                .map_err(|e| anyhow!("Invalid progress template: {}", e))?,
+        );
+        Ok(progress)
+    }
+
+    /// Create `count` interval threads in batched transactions, write them to
+    /// a manifest, and check that a sample of them executes
+    async fn stress_create(
+        client: &RpcPool,
+        payer: &Keypair,
+        authority: &Keypair,
+        opts: StressOptions,
+    ) -> Result<()> {
+        if opts.count == 0 {
+            return Err(anyhow!("--count must be at least 1"));
+        }
+        if opts.interval <= 0 {
+            return Err(anyhow!("--interval must be at least 1 second"));
+        }
+        if opts.fibers == 0 {
+            return Err(anyhow!("--fibers must be at least 1"));
+        }
+        if opts.batch_size == 0 {
+            return Err(anyhow!("--batch-size must be at least 1"));
+        }
+        if !(0.0..=1.0).contains(&opts.durable_ratio) {
+            return Err(anyhow!("--durable-ratio must be between 0.0 and 1.0"));
+        }
+        if opts.max_tps <= 0.0 {
+            return Err(anyhow!("--max-tps must be greater than 0"));
+        }
+        if opts.manifest.exists() {
+            return Err(anyhow!(
+                "Manifest {} already exists; tear down that run first or pass --manifest",
+                opts.manifest.display()
+            ));
+        }
+
+        let trigger = Trigger::Interval {
+            seconds: opts.interval,
+            skippable: true,
+            jitter: opts.jitter,
+        };
+        let run_id = Utc::now().timestamp();
+        let items: Vec<StressItem> = (0..opts.count)
+            .map(|i| {
+                build_stress_thread(
+                    payer,
+                    authority,
+                    &format!("stress-{}-{}", run_id, i),
+                    &trigger,
+                    opts.fibers,
+                    is_durable(i, opts.durable_ratio),
+                )
+            })
+            .collect();
+        let durable = items.iter().filter(|item| item.nonce.is_some()).count();
+
+        let batches = pack_transactions(&payer.pubkey(), items, opts.batch_size)?;
+        println!(
+            "\nCreating {} threads ({} durable, {} fibers each, trigger {}) in {} transactions",
+            opts.count,
+            durable,
+            opts.fibers,
+            trigger_str::format(&trigger),
+            batches.len()
+        );
+        println!(
+            "Funding: {:.3} SOL plus rent",
+            (opts.count as u64 * STRESS_THREAD_FUNDING) as f64 / LAMPORTS_PER_SOL as f64
+        );
+
+        let progress = stress_progress_bar(opts.count as u64)?;
+        let results =
+            send_stress_batches(client, payer, authority, batches, opts.max_tps, &progress).await;
+        progress.finish_and_clear();
+
+        let mut created = Vec::new();
+        let mut errors = Vec::new();
+        for (threads, result) in results {
+            match result {
+                Ok(_) => created.extend(threads),
+                Err(e) => errors.push((threads.len(), e)),
+            }
+        }
+        let failed: usize = errors.iter().map(|(n, _)| n).sum();
+        for (n, e) in errors.iter().take(5) {
+            println!("  ✗ {} thread(s): {}", n, e);
+        }
+
+        // Write the manifest even for a partial run so nothing is orphaned
+        let manifest = StressManifest {
+            authority: authority.pubkey().to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            trigger: trigger_str::format(&trigger),
+            fibers: opts.fibers,
+            threads: created,
+        };
+        std::fs::write(&opts.manifest, serde_json::to_string_pretty(&manifest)?)
+            .map_err(|e| anyhow!("Failed to write {}: {}", opts.manifest.display(), e))?;
+
+        println!(
+            "Created {} of {} threads; manifest written to {}",
+            manifest.threads.len(),
+            opts.count,
+            opts.manifest.display()
+        );
+        println!(
+            "Tear down with: antegen thread test stress --teardown {}",
+            opts.manifest.display()
+        );
+
+        if opts.verify_sample > 0 && !manifest.threads.is_empty() {
+            let timeout = std::time::Duration::from_secs(
+                opts.interval as u64 + opts.jitter + STRESS_VERIFY_GRACE_SECS,
+            );
+            verify_stress_execution(client, &manifest.threads, opts.verify_sample, timeout).await?;
+        }
+
+        if failed > 0 {
+            return Err(anyhow!("{} thread(s) failed to create", failed));
+        }
+        Ok(())
+    }
+
+    /// Poll an evenly spaced sample of threads until each has executed at
+    /// least once, or `timeout` elapses
+    async fn verify_stress_execution(
+        client: &RpcPool,
+        threads: &[StressThread],
+        sample: usize,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        let step = (threads.len() / sample.min(threads.len())).max(1);
+        let sampled: Vec<Pubkey> = threads
+            .iter()
+            .step_by(step)
+            .take(sample)
+            .filter_map(|t| Pubkey::from_str(&t.pubkey).ok())
+            .collect();
+
+        println!(
+            "\nWaiting up to {}s for {} sampled threads to execute...",
+            timeout.as_secs(),
+            sampled.len()
+        );
+
+        let start = std::time::Instant::now();
+        loop {
+            let accounts = client.get_multiple_accounts(&sampled).await?;
+            let exec_counts: Vec<u64> = accounts
+                .iter()
+                .map(|account| {
+                    account
+                        .as_ref()
+                        .and_then(|a| a.decode_data().ok())
+                        .and_then(|data| Thread::try_deserialize(&mut data.as_slice()).ok())
+                        .map_or(0, |thread| thread.exec_count)
+                })
+                .collect();
+            let executed = exec_counts.iter().filter(|&&n| n > 0).count();
+
+            if executed == sampled.len() {
+                println!("  ✓ All {} sampled threads executed", executed);
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                for (pubkey, _) in sampled.iter().zip(&exec_counts).filter(|(_, n)| **n == 0) {
+                    println!("  ✗ {} has not executed", pubkey);
+                }
+                return Err(anyhow!(
+                    "{} of {} sampled threads did not execute within {}s",
+                    sampled.len() - executed,
+                    sampled.len(),
+                    timeout.as_secs()
+                ));
+            }
+            tokio::time::sleep(STRESS_VERIFY_POLL).await;
+        }
+    }
+
+    /// Close every thread listed in a stress manifest and reclaim its rent
+    async fn stress_teardown(
+        client: &RpcPool,
+        payer: &Keypair,
+        authority: &Keypair,
+        manifest_path: &std::path::Path,
+        batch_size: usize,
+        max_tps: f64,
+    ) -> Result<()> {
+        if batch_size == 0 {
+            return Err(anyhow!("--batch-size must be at least 1"));
+        }
+        if max_tps <= 0.0 {
+            return Err(anyhow!("--max-tps must be greater than 0"));
+        }
+
+        let content = std::fs::read_to_string(manifest_path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", manifest_path.display(), e))?;
+        let manifest: StressManifest = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", manifest_path.display(), e))?;
+        if manifest.authority != authority.pubkey().to_string() {
+            return Err(anyhow!(
+                "Manifest threads belong to {}, not the test keypair {}",
+                manifest.authority,
+                authority.pubkey()
+            ));
+        }
+
+        // Fetch the threads (for their fiber ids), skipping ones already closed
+        let mut items = Vec::new();
+        for chunk in manifest.threads.chunks(100) {
+            let pubkeys = chunk
+                .iter()
+                .map(|t| {
+                    Pubkey::from_str(&t.pubkey)
+                        .map_err(|e| anyhow!("Invalid pubkey in manifest: {}", e))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let accounts = client.get_multiple_accounts(&pubkeys).await?;
+            for ((entry, pubkey), account) in chunk.iter().zip(&pubkeys).zip(accounts) {
+                let Some(thread) = account
+                    .and_then(|a| a.decode_data().ok())
+                    .and_then(|data| Thread::try_deserialize(&mut data.as_slice()).ok())
+                else {
+                    continue;
+                };
+                items.push(StressItem {
+                    thread: entry.clone(),
+                    instructions: vec![build_close_thread_instruction(
+                        authority.pubkey(),
+                        payer.pubkey(),
+                        *pubkey,
+                        &thread,
+                    )],
+                    nonce: None,
+                });
+            }
+        }
+
+        let already_closed = manifest.threads.len() - items.len();
+        if items.is_empty() {
+            println!("All {} threads are already closed.", manifest.threads.len());
+            return Ok(());
+        }
+
+        let total = items.len();
+        let batches = pack_transactions(&payer.pubkey(), items, batch_size)?;
+        println!(
+            "\nClosing {} threads in {} transactions ({} already closed)",
+            total,
+            batches.len(),
+            already_closed
+        );
+
+        let balance_before = client.get_balance(&payer.pubkey()).await?;
+        let progress = stress_progress_bar(total as u64)?;
+        let results =
+            send_stress_batches(client, payer, authority, batches, max_tps, &progress).await;
+        progress.finish_and_clear();
+        let balance_after = client.get_balance(&payer.pubkey()).await?;
+
+        let mut failed = 0;
+        for (threads, result) in &results {
+            if let Err(e) = result {
+                failed += threads.len();
+                println!("  ✗ {} thread(s): {}", threads.len(), e);
+            }
+        }
+
+        println!(
+            "Closed {} of {} threads; reclaimed {:.6} SOL (net of fees)",
+            total - failed,
+            total,
+            balance_after.saturating_sub(balance_before) as f64 / LAMPORTS_PER_SOL as f64
+        );
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} thread(s) failed to close; re-run the teardown to retry",
+                failed
+            ));
+        }
+        Ok(())
+    }
 }
 
 // Re-export the test function when dev feature is enabled
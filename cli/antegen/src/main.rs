@@ -1,6 +1,6 @@
 //! Antegen CLI — developer-facing: program, thread, geyser commands
 
-use antegen_cli_core::{dispatch_config, LogLevel, NodeConfigCommands};
+use antegen_cli_core::{dispatch_config, LogLevel, NodeConfigCommands, OutputFormat};
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
@@ -37,6 +37,16 @@ struct Cli {
     #[arg(long, global = true)]
     keypair: Option<PathBuf>,
 
+    /// Output format for read commands
+    #[arg(
+        long,
+        alias = "format",
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text
+    )]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -55,6 +65,10 @@ enum Commands {
     #[command(subcommand)]
     Geyser(GeyserCommands),
 
+    /// Executor client checks
+    #[command(subcommand)]
+    Client(ClientCommands),
+
     // =========================================================================
     // Hidden: executor runtime (service invokes versioned binary with `run`)
     // =========================================================================
@@ -99,6 +113,9 @@ enum Commands {
     Logs {
         #[arg(short, long)]
         follow: bool,
+
+        #[arg(short = 'n', long, default_value_t = 100)]
+        tail: u64,
     },
 
     /// Stop the antegen service
@@ -186,6 +203,45 @@ enum ProgramCommands {
         /// Skip on-chain verification after deploy
         #[arg(long)]
         skip_verify: bool,
+
+        /// Attempts at `config init` while the program comes up
+        #[arg(long, default_value_t = 3)]
+        init_retries: u32,
+
+        /// Seconds between `config init` attempts
+        #[arg(long, default_value_t = 2)]
+        init_retry_delay: u64,
+    },
+
+    /// Upgrade a deployed program from a new build and publish its IDL
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen program upgrade --program thread --so target/deploy/antegen_thread_program.so \\
+        --idl target/idl/antegen_thread_program.json
+    antegen program upgrade --program fiber --so target/deploy/antegen_fiber_program.so --dry-run
+
+The binary must declare the program's id and the IDL must name the same
+address. Mainnet RPC URLs need --yes.")]
+    Upgrade {
+        /// Program to upgrade
+        #[arg(long, value_enum)]
+        program: commands::program::UpgradeProgram,
+
+        /// New program binary
+        #[arg(long)]
+        so: PathBuf,
+
+        /// Anchor IDL to publish once the upgrade confirms
+        #[arg(long)]
+        idl: Option<PathBuf>,
+
+        /// Confirm upgrading a program on mainnet
+        #[arg(long)]
+        yes: bool,
+
+        /// Check the build and show what would be sent without sending it
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Program configuration management
@@ -199,7 +255,31 @@ enum ProgramConfigCommands {
     Init,
 
     /// Display the current ThreadConfig settings
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen program config get
+    antegen program config get --format json | jq .paused
+")]
     Get,
+
+    /// Grow a ThreadConfig written by an older release to the current layout
+    Migrate {
+        /// Show the config's version without sending a transaction
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Propose a new config admin; takes effect once they run accept-admin
+    SetAdmin {
+        /// Pubkey of the proposed admin
+        new_admin: String,
+    },
+
+    /// Accept a pending admin proposal (sign with the proposed admin's keypair)
+    AcceptAdmin,
+
+    /// Cancel a pending admin proposal
+    CancelAdmin,
 }
 
 // =============================================================================
@@ -217,6 +297,10 @@ enum GeyserCommands {
         /// Path to antegen.toml config file
         #[arg(short, long, default_value = "antegen.toml")]
         config: PathBuf,
+
+        /// Write the config even if the plugin was built for a different agave version
+        #[arg(long)]
+        skip_abi_check: bool,
     },
 
     /// Extract plugin .so to custom location
@@ -224,6 +308,91 @@ enum GeyserCommands {
         /// Output path for the .so file
         #[arg(short, long)]
         output: PathBuf,
+
+        /// Check the file against the release's SHA-256 checksums
+        #[arg(long)]
+        verify: bool,
+
+        /// Warn if the plugin was built for a different agave version than
+        /// the installed `solana` CLI
+        #[arg(long)]
+        check_abi_version: bool,
+    },
+}
+
+// =============================================================================
+// Client commands
+// =============================================================================
+
+#[derive(Subcommand)]
+enum ClientCommands {
+    /// Check executor balance, endpoints, thread config and TPU; exits
+    /// non-zero if a critical check fails
+    Status {
+        /// Path to antegen.toml config file (default: user config)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the executor's and fee payers' balances, optionally watching
+    /// them and alerting when one drops below a threshold
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen client balance
+    antegen client balance --check --threshold 0.5
+    antegen client balance --watch --threshold 0.5 --webhook https://hooks.example.com/antegen
+
+--check exits non-zero if any signer is below the threshold. --watch prints
+every balance change until interrupted; an alert fires once when a signer
+drops below the threshold and resolves once it is 10% above it.
+")]
+    Balance {
+        /// Path to antegen.toml config file (default: user config)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Alert threshold in SOL (default: the funding minimum)
+        #[arg(long)]
+        threshold: Option<f64>,
+
+        /// Subscribe to every signer and print balance changes
+        #[arg(long, conflicts_with = "check")]
+        watch: bool,
+
+        /// Exit non-zero if any signer is below the threshold
+        #[arg(long)]
+        check: bool,
+
+        /// POST alerts as JSON to this URL
+        #[arg(long)]
+        webhook: Option<String>,
+    },
+
+    /// Install a keypair file as the client's executor keypair
+    Import {
+        /// Keypair file to import
+        keypair: PathBuf,
+
+        /// Path to antegen.toml config file (default: user config)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Replace a different executor keypair already installed
+        #[arg(long)]
+        force: bool,
+
+        /// Check the keypair's balance on this cluster after importing
+        #[arg(long, value_name = "RPC_URL")]
+        validate_cluster: Option<String>,
+
+        /// Airdrop 1 SOL if the balance is zero (devnet/testnet); requires
+        /// --validate-cluster
+        #[arg(long, requires = "validate_cluster")]
+        airdrop_if_empty: bool,
     },
 }
 
@@ -237,12 +406,157 @@ enum ThreadCommands {
     Get {
         /// Thread public key
         address: String,
+
+        /// Also decode the account an account trigger watches
+        #[arg(long)]
+        decode_target: bool,
+    },
+
+    /// Explain whether a thread would execute now and why not
+    Debug {
+        /// Thread public key
+        address: String,
+
+        /// Client config for the executor and claim window (default: user config)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Run a thread's next exec against a local bank cloned from the cluster
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread simulate <ADDRESS>
+    antegen thread simulate <ADDRESS> --clone <ORACLE> --at 1767225600
+    antegen thread simulate <ADDRESS> --thread-program target/deploy/antegen_thread_program.so
+
+Clones the thread, its fiber, the ThreadConfig and every account the fiber
+lists, plus the programs they reference. Use --clone for accounts a CPI reads
+that the fiber doesn't list. Needs a build with `--features simulate`.
+")]
+    Simulate {
+        /// Thread public key
+        address: String,
+
+        /// Extra account or program to clone (can be repeated)
+        #[arg(long)]
+        clone: Vec<String>,
+
+        /// Thread program binary to run instead of the deployed one
+        #[arg(long)]
+        thread_program: Option<PathBuf>,
+
+        /// Fiber program binary to run instead of the deployed one
+        #[arg(long)]
+        fiber_program: Option<PathBuf>,
+
+        /// Unix timestamp to run at instead of the cluster clock
+        #[arg(long)]
+        at: Option<i64>,
+    },
+
+    /// Show execution costs (fees, executions, failures, payouts) for a thread
+    Costs {
+        /// Thread public key
+        address: String,
+
+        /// Lookback window, e.g. 30d, 12h, 45m
+        #[arg(long, default_value = "30d")]
+        since: String,
+    },
+
+    /// Save a thread and its fibers as a portable JSON file
+    Export {
+        /// Thread public key
+        address: String,
+
+        /// File to write
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Re-create a thread from a `thread export` file under your keypair
+    Import {
+        /// Export file to read
+        file: PathBuf,
+
+        /// Show what would be created without sending transactions
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Change a thread's trigger and add fibers in one transaction
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread update <ADDRESS> --trigger \"interval:1h\"
+    antegen thread update <ADDRESS> --trigger \"cron:0 0 * * *\" --add-fiber step2.json
+    antegen thread update <ADDRESS> --paused false
+
+Fiber files hold one instruction in the `thread export` format:
+    {\"program_id\": \"...\", \"accounts\": [...], \"data_hex\": \"...\"}
+")]
+    Update {
+        /// Thread public key
+        address: String,
+
+        /// New trigger: immediate, cron:<schedule>, interval:<duration>, timestamp:<unix|rfc3339>,
+        /// slot:<num>, epoch:<num>, epoch:every:<num>, account:<pubkey>; options follow as ;key=value
+        #[arg(long)]
+        trigger: Option<String>,
+
+        /// Pause or resume the thread (a new trigger resumes it unless set)
+        #[arg(long)]
+        paused: Option<bool>,
+
+        /// Instruction file for a fiber to append (can be repeated)
+        #[arg(long)]
+        add_fiber: Vec<PathBuf>,
+
+        /// Priority fee for the added fibers (micro-lamports per CU)
+        #[arg(long, default_value = "0")]
+        priority_fee: u64,
+    },
+
+    /// Pause a thread, optionally until a given time
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread pause <ADDRESS>
+    antegen thread pause <ADDRESS> --until \"2024-07-01T00:00:00Z\"
+    antegen thread pause <ADDRESS> --until 1719792000
+")]
+    Pause {
+        /// Thread public key
+        address: String,
+
+        /// Resume on its own at this time (unix seconds or RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Migrate your thread accounts to the current account layout
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread migrate --dry-run
+    antegen thread migrate --owner <PUBKEY> --dry-run
+    antegen thread migrate --batch-size 20
+")]
+    Migrate {
+        /// Thread authority to scan (defaults to the keypair's pubkey)
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Only report which threads need migration
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Number of migration transactions in flight at once
+        #[arg(long, default_value = "10")]
+        batch_size: usize,
     },
 
     /// Admin: force delete a thread (skips all checks)
     #[cfg(feature = "dev")]
     Delete {
-        /// Thread public key to delete
+        /// Thread public key to delete; its lamports go back to its authority
         address: String,
     },
 
@@ -262,6 +576,8 @@ EXAMPLES:
     antegen thread test create --trigger immediate
     antegen thread test create --trigger \"interval:30\"
     antegen thread test create --trigger \"interval:30\" --signal repeat
+    antegen thread test create --trigger \"interval:1h30m;skippable=false;jitter=10\"
+    antegen thread test create --trigger \"timestamp:2030-01-01T00:00:00Z\"
 
     # Multi-fiber with signals (fiber count inferred from signals)
     antegen thread test create --trigger \"interval:30\" --signal 0:chain:1 --signal 1:chain:2
@@ -274,12 +590,13 @@ EXAMPLES:
     antegen thread test create --test-type chain     # Creates 3-fiber chain test
 ")]
     Create {
-        /// Trigger type: immediate, cron:<schedule>, interval:<secs>, timestamp:<unix>, slot:<num>, epoch:<num>, account:<pubkey>
+        /// Trigger type: immediate, cron:<schedule>, interval:<duration>, timestamp:<unix|rfc3339>, slot:<num>, epoch:<num>, epoch:every:<num>, account:<pubkey>.
+        /// Options follow as ;key=value (skippable, jitter, offset, size)
         #[arg(long, default_value = "immediate")]
         trigger: String,
 
         /// Signal configuration (can be repeated). Simple: repeat, close.
-        /// Per-fiber: F:chain:T or F:next:T (e.g., 0:chain:1, 1:next:0)
+        /// Per-fiber: F:chain:T, F:next:T or F:goto:T (e.g., 0:chain:1, 1:next:0, 0:goto:2)
         #[arg(long)]
         signal: Vec<String>,
 
@@ -319,6 +636,55 @@ EXAMPLES:
         test_type: Option<String>,
     },
 
+    /// Create many interval threads to load-test executors, or tear a run down
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread test stress --count 500 --interval 10 --jitter 3 --fibers 2 --batch-size 25
+    antegen thread test stress --count 100 --durable-ratio 0.2 --manifest run-1.json
+    antegen thread test stress --teardown stress-manifest.json
+")]
+    Stress {
+        /// Number of threads to create
+        #[arg(long, default_value = "100")]
+        count: u32,
+
+        /// Trigger interval in seconds
+        #[arg(long, default_value = "10")]
+        interval: i64,
+
+        /// Trigger jitter in seconds
+        #[arg(long, default_value = "0")]
+        jitter: u64,
+
+        /// Fibers per thread
+        #[arg(long, default_value = "1")]
+        fibers: u8,
+
+        /// Maximum threads per transaction (fewer if they don't fit)
+        #[arg(long, default_value = "25")]
+        batch_size: usize,
+
+        /// Fraction of threads created with a durable nonce (0.0 to 1.0)
+        #[arg(long, default_value = "0")]
+        durable_ratio: f64,
+
+        /// Maximum transactions sent per second
+        #[arg(long, default_value = "10")]
+        max_tps: f64,
+
+        /// File listing the created threads, for verification and teardown
+        #[arg(long, default_value = "stress-manifest.json")]
+        manifest: PathBuf,
+
+        /// Number of threads polled to confirm they execute (0 to skip)
+        #[arg(long, default_value = "5")]
+        verify_sample: usize,
+
+        /// Close the threads listed in this manifest and reclaim their rent
+        #[arg(long, value_name = "MANIFEST")]
+        teardown: Option<PathBuf>,
+    },
+
     /// Fiber management for test threads
     #[command(subcommand)]
     Fiber(TestFiberCommands),
@@ -339,12 +705,17 @@ EXAMPLES:
         /// Test thread ID (e.g., \"test-1\")
         id: String,
 
-        /// Signal for the fiber: chain:T, next:T, repeat, close
+        /// Signal for the fiber: chain:T, next:T, goto:T, repeat, close
         #[arg(long)]
         signal: Option<String>,
     },
 
-    /// List fibers on a test thread
+    /// List a test thread's fiber accounts with their execution stats
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen thread test fiber list test-1
+    antegen thread test fiber list test-1 --format json
+")]
     List {
         /// Test thread ID
         id: String,
@@ -396,6 +767,8 @@ async fn run_antegen() -> Result<()> {
                 keys_dir,
                 skip_init,
                 skip_verify,
+                init_retries,
+                init_retry_delay,
             } => {
                 commands::program::deploy(
                     program_binary,
@@ -405,14 +778,42 @@ async fn run_antegen() -> Result<()> {
                     keys_dir,
                     skip_init,
                     skip_verify,
+                    commands::program::InitRetry {
+                        attempts: init_retries,
+                        delay: std::time::Duration::from_secs(init_retry_delay),
+                    },
                 )
                 .await
             }
+            ProgramCommands::Upgrade {
+                program,
+                so,
+                idl,
+                yes,
+                dry_run,
+            } => {
+                commands::program::upgrade(program, so, idl, yes, dry_run, cli.rpc, cli.keypair)
+                    .await
+            }
             ProgramCommands::Config(config_cmd) => match config_cmd {
                 ProgramConfigCommands::Init => {
                     commands::program::config_init(cli.rpc, cli.keypair).await
                 }
-                ProgramConfigCommands::Get => commands::program::config_get(cli.rpc).await,
+                ProgramConfigCommands::Get => {
+                    commands::program::config_get(cli.rpc, cli.output).await
+                }
+                ProgramConfigCommands::Migrate { dry_run } => {
+                    commands::program::config_migrate(dry_run, cli.rpc, cli.keypair).await
+                }
+                ProgramConfigCommands::SetAdmin { new_admin } => {
+                    commands::program::config_set_admin(new_admin, cli.rpc, cli.keypair).await
+                }
+                ProgramConfigCommands::AcceptAdmin => {
+                    commands::program::config_accept_admin(cli.rpc, cli.keypair).await
+                }
+                ProgramConfigCommands::CancelAdmin => {
+                    commands::program::config_cancel_admin(cli.rpc, cli.keypair).await
+                }
             },
         },
 
@@ -420,14 +821,70 @@ async fn run_antegen() -> Result<()> {
         // Thread commands
         // =================================================================
         Commands::Thread(thread_cmd) => match thread_cmd {
-            ThreadCommands::Get { address } => commands::thread::get(address, cli.rpc).await,
+            ThreadCommands::Get {
+                address,
+                decode_target,
+            } => commands::thread::get(address, decode_target, cli.rpc, cli.output).await,
+            ThreadCommands::Debug { address, config } => {
+                commands::thread::debug(address, config, cli.rpc, cli.output).await
+            }
+            ThreadCommands::Simulate {
+                address,
+                clone,
+                thread_program,
+                fiber_program,
+                at,
+            } => {
+                let options = commands::simulate::SimulateOptions {
+                    clone,
+                    thread_program,
+                    fiber_program,
+                    unix_timestamp: at,
+                };
+                commands::simulate::simulate(address, options, cli.rpc, cli.output).await
+            }
+            ThreadCommands::Costs { address, since } => {
+                commands::thread::costs(address, since, cli.rpc, cli.output).await
+            }
+            ThreadCommands::Export { address, output } => {
+                commands::thread::export(address, output, cli.rpc).await
+            }
+            ThreadCommands::Import { file, dry_run } => {
+                commands::thread::import(file, dry_run, cli.rpc, cli.keypair).await
+            }
+            ThreadCommands::Update {
+                address,
+                trigger,
+                paused,
+                add_fiber,
+                priority_fee,
+            } => {
+                commands::thread::update(
+                    address,
+                    trigger,
+                    paused,
+                    add_fiber,
+                    priority_fee,
+                    cli.rpc,
+                    cli.keypair,
+                )
+                .await
+            }
+            ThreadCommands::Pause { address, until } => {
+                commands::thread::pause(address, until, cli.rpc, cli.keypair).await
+            }
+            ThreadCommands::Migrate {
+                owner,
+                dry_run,
+                batch_size,
+            } => commands::thread::migrate(owner, dry_run, batch_size, cli.rpc, cli.keypair).await,
             #[cfg(feature = "dev")]
             ThreadCommands::Delete { address } => {
                 commands::thread::admin_delete(address, cli.rpc, cli.keypair).await
             }
             #[cfg(feature = "dev")]
             ThreadCommands::Test(test_cmd) => {
-                commands::thread::test(cli.rpc, cli.keypair, test_cmd).await
+                commands::thread::test(cli.rpc, cli.keypair, cli.output, test_cmd).await
             }
         },
 
@@ -435,8 +892,62 @@ async fn run_antegen() -> Result<()> {
         // Geyser commands
         // =================================================================
         Commands::Geyser(geyser_cmd) => match geyser_cmd {
-            GeyserCommands::Init { output, config } => commands::geyser::init(output, config).await,
-            GeyserCommands::Extract { output } => commands::geyser::extract(output).await,
+            GeyserCommands::Init {
+                output,
+                config,
+                skip_abi_check,
+            } => commands::geyser::init(output, config, skip_abi_check).await,
+            GeyserCommands::Extract {
+                output,
+                verify,
+                check_abi_version,
+            } => commands::geyser::extract(output, verify, check_abi_version).await,
+        },
+
+        // =================================================================
+        // Client commands
+        // =================================================================
+        Commands::Client(client_cmd) => match client_cmd {
+            ClientCommands::Status { config, json } => {
+                let config = match config {
+                    Some(p) => p,
+                    None => antegen_cli_core::commands::default_config_path()?,
+                };
+                let output = if json {
+                    OutputFormat::JsonPretty
+                } else {
+                    cli.output
+                };
+                commands::client::status(config, output).await
+            }
+            ClientCommands::Balance {
+                config,
+                threshold,
+                watch,
+                check,
+                webhook,
+            } => {
+                let config = match config {
+                    Some(p) => p,
+                    None => antegen_cli_core::commands::default_config_path()?,
+                };
+                commands::client::balance(config, threshold, watch, check, webhook, cli.output)
+                    .await
+            }
+            ClientCommands::Import {
+                keypair,
+                config,
+                force,
+                validate_cluster,
+                airdrop_if_empty,
+            } => {
+                let config = match config {
+                    Some(p) => p,
+                    None => antegen_cli_core::commands::default_config_path()?,
+                };
+                commands::client::import(config, keypair, force, validate_cluster, airdrop_if_empty)
+                    .await
+            }
         },
 
         // =================================================================
@@ -474,9 +985,9 @@ async fn run_antegen() -> Result<()> {
             deprecation_warning("status", "status");
             antegen_cli_core::commands::service::status()
         }
-        Commands::Logs { follow } => {
+        Commands::Logs { follow, tail } => {
             deprecation_warning("logs", "logs");
-            antegen_cli_core::commands::service::logs(follow)
+            antegen_cli_core::commands::service::logs(follow, tail)
         }
         Commands::Stop => {
             deprecation_warning("stop", "stop");
@@ -501,7 +1012,12 @@ async fn run_antegen() -> Result<()> {
         }
         Commands::Info { json } => {
             deprecation_warning("info", "info");
-            antegen_cli_core::commands::info::info(json).await
+            let output = if json {
+                OutputFormat::JsonPretty
+            } else {
+                cli.output
+            };
+            antegen_cli_core::commands::info::info(output).await
         }
         Commands::Fund { amount } => {
             deprecation_warning("fund", "fund");
@@ -515,7 +1031,7 @@ async fn run_antegen() -> Result<()> {
         }
         Commands::Config(config_cmd) => {
             deprecation_warning("config", "config");
-            dispatch_config(config_cmd, cli.rpc)
+            dispatch_config(config_cmd, cli.rpc, cli.output)
         }
     }
 }
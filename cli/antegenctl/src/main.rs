@@ -1,6 +1,6 @@
 //! antegenctl — Antegen system controller: node version management and service control
 
-use antegen_cli_core::{dispatch_config, LogLevel, NodeConfigCommands};
+use antegen_cli_core::{dispatch_config, LogLevel, NodeConfigCommands, OutputFormat};
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
@@ -28,6 +28,10 @@ struct AntegenctlCli {
     #[arg(long, global = true)]
     keypair: Option<PathBuf>,
 
+    /// Output format for read commands
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: AntegenctlCommands,
 }
@@ -59,7 +63,7 @@ enum AntegenctlCommands {
 
     /// Show info (CLI version, node version, executor, balance)
     Info {
-        /// Output as JSON
+        /// Output as JSON (same as --output json-pretty)
         #[arg(long)]
         json: bool,
     },
@@ -93,6 +97,10 @@ enum AntegenctlCommands {
         /// Follow log output (like tail -f)
         #[arg(short, long)]
         follow: bool,
+
+        /// Number of trailing lines to show
+        #[arg(short = 'n', long, default_value_t = 100)]
+        tail: u64,
     },
 
     /// Uninstall the antegen service
@@ -110,6 +118,16 @@ enum AntegenctlCommands {
         amount: Option<f64>,
     },
 
+    /// Airdrop SOL to keypairs from the cluster faucet (localnet/devnet)
+    Airdrop {
+        /// Keypairs to fund, all in parallel (defaults to the executor keypair)
+        keypairs: Vec<PathBuf>,
+
+        /// Amount of SOL per keypair
+        #[arg(long, default_value_t = 1.0)]
+        amount: f64,
+    },
+
     /// Update node to latest version
     Update {
         /// Update to a specific version (e.g., v4.1.1)
@@ -171,9 +189,18 @@ async fn run_antegenctl() -> Result<()> {
         AntegenctlCommands::Stop => antegen_cli_core::commands::service::stop(),
         AntegenctlCommands::Restart => antegen_cli_core::commands::service::restart(),
         AntegenctlCommands::Status => antegen_cli_core::commands::service::status(),
-        AntegenctlCommands::Logs { follow } => antegen_cli_core::commands::service::logs(follow),
+        AntegenctlCommands::Logs { follow, tail } => {
+            antegen_cli_core::commands::service::logs(follow, tail)
+        }
         AntegenctlCommands::Uninstall => antegen_cli_core::commands::service::uninstall(),
-        AntegenctlCommands::Info { json } => antegen_cli_core::commands::info::info(json).await,
+        AntegenctlCommands::Info { json } => {
+            let output = if json {
+                OutputFormat::JsonPretty
+            } else {
+                cli.output
+            };
+            antegen_cli_core::commands::info::info(output).await
+        }
         AntegenctlCommands::Fund { amount } => {
             let config = antegen_cli_core::commands::default_config_path()?;
             antegen_cli_core::commands::client::fund(config, amount, cli.keypair, cli.rpc).await
@@ -182,6 +209,10 @@ async fn run_antegenctl() -> Result<()> {
             let config = antegen_cli_core::commands::default_config_path()?;
             antegen_cli_core::commands::client::withdraw(config, amount, cli.rpc).await
         }
+        AntegenctlCommands::Airdrop { keypairs, amount } => {
+            let config = antegen_cli_core::commands::default_config_path()?;
+            antegen_cli_core::commands::client::airdrop(config, keypairs, amount, cli.rpc).await
+        }
         AntegenctlCommands::Update { version, local } => {
             antegen_cli_core::commands::update::update_node(version, local).await
         }
@@ -192,6 +223,6 @@ async fn run_antegenctl() -> Result<()> {
         AntegenctlCommands::Install { version, local } => {
             antegen_cli_core::commands::update::install_node_version(version, local).await
         }
-        AntegenctlCommands::Config(config_cmd) => dispatch_config(config_cmd, cli.rpc),
+        AntegenctlCommands::Config(config_cmd) => dispatch_config(config_cmd, cli.rpc, cli.output),
     }
 }
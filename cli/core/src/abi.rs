@@ -0,0 +1,205 @@
+//! Geyser plugin ABI compatibility checks
+//!
+//! The plugin's build script embeds a JSON blob describing what it was built
+//! against, prefixed with [`ABI_MARKER`] and terminated by a NUL byte. A
+//! validator loading a plugin built against a different agave minor version
+//! usually crashes with an unhelpful error, so `antegen geyser init` reads
+//! the blob back and compares it with the host's `solana --version`.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Prefix of the build metadata blob embedded in the plugin
+pub const ABI_MARKER: &[u8] = b"ANTEGEN_ABI_INFO:";
+
+/// Build metadata embedded in the plugin `.so`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginAbiInfo {
+    /// antegen-geyser-plugin crate version
+    pub plugin_version: String,
+    /// agave-geyser-plugin-interface version the plugin was built against
+    pub agave_version: String,
+    /// `rustc --version` of the compiler that built the plugin
+    pub rustc_version: String,
+}
+
+/// Result of comparing the plugin's agave version with the host's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiCompatibility {
+    /// Same agave version
+    Compatible,
+    /// Same major.minor, different patch; the interface is stable across
+    /// patch releases but the mismatch is worth mentioning
+    PatchMismatch { plugin: String, host: String },
+    /// Different major or minor version; the validator will likely fail to
+    /// load the plugin
+    Incompatible { plugin: String, host: String },
+    /// One of the versions could not be parsed
+    Unknown { reason: String },
+}
+
+/// Extract the embedded build metadata from a plugin binary
+///
+/// Returns `Ok(None)` for plugins built before the metadata was embedded.
+pub fn read_plugin_abi(so_path: &Path) -> Result<Option<PluginAbiInfo>> {
+    let bytes =
+        fs::read(so_path).with_context(|| format!("Failed to read {}", so_path.display()))?;
+    parse_plugin_abi(&bytes)
+}
+
+/// Find and decode the metadata blob in raw plugin bytes
+pub fn parse_plugin_abi(bytes: &[u8]) -> Result<Option<PluginAbiInfo>> {
+    let Some(start) = bytes
+        .windows(ABI_MARKER.len())
+        .position(|window| window == ABI_MARKER)
+    else {
+        return Ok(None);
+    };
+
+    let blob = &bytes[start + ABI_MARKER.len()..];
+    let end = blob
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| anyhow!("Unterminated ABI metadata in plugin"))?;
+    let info = serde_json::from_slice(&blob[..end]).context("Malformed ABI metadata in plugin")?;
+    Ok(Some(info))
+}
+
+/// Extract the version from `solana --version` output
+///
+/// e.g. `solana-cli 3.1.4 (src:00000000; feat:3294202862, client:Agave)` -> `3.1.4`
+pub fn parse_solana_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .find(|word| parse_semver(word).is_some())
+        .map(str::to_string)
+}
+
+/// Run `solana --version` on the host
+pub fn host_solana_version() -> Result<String> {
+    let output = std::process::Command::new("solana")
+        .arg("--version")
+        .output()
+        .context("Failed to run `solana --version` (is the Solana CLI installed?)")?;
+    if !output.status.success() {
+        return Err(anyhow!("`solana --version` exited with {}", output.status));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_solana_version(&stdout)
+        .ok_or_else(|| anyhow!("Unrecognized `solana --version` output: {}", stdout.trim()))
+}
+
+/// Compare the plugin's agave version with the host's
+pub fn check_compatibility(plugin: &str, host: &str) -> AbiCompatibility {
+    let (Some(p), Some(h)) = (parse_semver(plugin), parse_semver(host)) else {
+        return AbiCompatibility::Unknown {
+            reason: format!("cannot compare versions {} and {}", plugin, host),
+        };
+    };
+
+    if p == h {
+        AbiCompatibility::Compatible
+    } else if p.0 == h.0 && p.1 == h.1 {
+        AbiCompatibility::PatchMismatch {
+            plugin: plugin.to_string(),
+            host: host.to_string(),
+        }
+    } else {
+        AbiCompatibility::Incompatible {
+            plugin: plugin.to_string(),
+            host: host.to_string(),
+        }
+    }
+}
+
+/// Check that the plugin file can be opened, and on Unix that it is readable
+/// by users other than its owner (the validator often runs as its own user)
+pub fn check_libpath_readable(so_path: &Path) -> Result<()> {
+    fs::File::open(so_path)
+        .with_context(|| format!("Plugin {} is not readable", so_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(so_path)?.permissions().mode();
+        if mode & 0o004 == 0 {
+            return Err(anyhow!(
+                "Plugin {} is not world-readable (mode {:o}); the validator user may not be able to load it",
+                so_path.display(),
+                mode & 0o777
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `major.minor.patch`, ignoring a leading `v` and any pre-release suffix
+fn parse_semver(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_solana_version() {
+        assert_eq!(
+            parse_solana_version("solana-cli 3.1.4 (src:00000000; feat:3294202862, client:Agave)"),
+            Some("3.1.4".to_string())
+        );
+        assert_eq!(parse_solana_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_check_compatibility() {
+        assert_eq!(
+            check_compatibility("3.1.4", "3.1.4"),
+            AbiCompatibility::Compatible
+        );
+        assert!(matches!(
+            check_compatibility("3.1.4", "3.1.7"),
+            AbiCompatibility::PatchMismatch { .. }
+        ));
+        assert!(matches!(
+            check_compatibility("3.1.4", "3.0.9"),
+            AbiCompatibility::Incompatible { .. }
+        ));
+        assert!(matches!(
+            check_compatibility("3.1.4", "2.1.4"),
+            AbiCompatibility::Incompatible { .. }
+        ));
+        assert!(matches!(
+            check_compatibility("3.1", "3.1.4"),
+            AbiCompatibility::Unknown { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_plugin_abi() {
+        let mut bytes = vec![0x7f, b'E', b'L', b'F', 0, 1, 2];
+        bytes.extend_from_slice(ABI_MARKER);
+        bytes.extend_from_slice(
+            br#"{"plugin_version":"5.1.0","agave_version":"3.1.4","rustc_version":"rustc 1.86.0"}"#,
+        );
+        bytes.extend_from_slice(&[0, 9, 9]);
+
+        let info = parse_plugin_abi(&bytes).unwrap().unwrap();
+        assert_eq!(info.plugin_version, "5.1.0");
+        assert_eq!(info.agave_version, "3.1.4");
+
+        assert!(parse_plugin_abi(b"no metadata here").unwrap().is_none());
+    }
+}
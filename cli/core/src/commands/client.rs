@@ -1,13 +1,28 @@
-//! Client commands - executor fund and withdraw operations
+//! Client commands - executor fund, withdraw and airdrop operations
 
 use antegen_client::rpc::RpcPool;
 use antegen_client::ClientConfig;
 use anyhow::{Context, Result};
+use solana_commitment_config::CommitmentConfig;
 use solana_sdk::message::Message;
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
-use solana_sdk::signature::{read_keypair_file, Signer};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
 use solana_sdk::transaction::Transaction;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Attempts per airdrop request before giving up on that keypair
+const AIRDROP_ATTEMPTS: u32 = 5;
+
+/// Spacing after the faucet rate limits a request, doubled on each repeat
+const AIRDROP_RATE_LIMIT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How long to wait for all airdrops to confirm
+const AIRDROP_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Expand ~ in path to home directory
 fn expand_path(path: &str) -> Result<PathBuf> {
@@ -15,17 +30,24 @@ fn expand_path(path: &str) -> Result<PathBuf> {
     Ok(PathBuf::from(expanded.as_ref()))
 }
 
-/// Load keypair from config's keypair_path
-fn load_keypair_from_config(config: &ClientConfig) -> Result<solana_sdk::signature::Keypair> {
-    let keypair_path = expand_path(&config.executor.keypair_path)?;
-    read_keypair_file(&keypair_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read keypair from {:?}: {}", keypair_path, e))
+/// Load every fee payer keypair from the config, the primary first
+fn load_fee_payers_from_config(config: &ClientConfig) -> Result<Vec<Keypair>> {
+    config
+        .executor
+        .keypair_paths()
+        .map(|path| {
+            let path = expand_path(path)?;
+            read_keypair_file(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to read keypair from {:?}: {}", path, e))
+        })
+        .collect()
 }
 
 /// Fund the executor with SOL from Solana CLI wallet
 ///
 /// - `amount = Some(x)`: transfers exactly `x` SOL to executor
-/// - `amount = None`: checks executor balance, tops up deficit to MIN_BALANCE_LAMPORTS
+/// - `amount = None`: tops up the executor and every additional fee payer
+///   below MIN_BALANCE_LAMPORTS, in one transaction
 pub async fn fund(
     config_path: PathBuf,
     amount: Option<f64>,
@@ -46,8 +68,10 @@ pub async fn fund(
         );
     }
 
-    let executor_keypair = load_keypair_from_config(&config)?;
-    let destination = executor_keypair.pubkey();
+    let fee_payers: Vec<Pubkey> = load_fee_payers_from_config(&config)?
+        .iter()
+        .map(|keypair| keypair.pubkey())
+        .collect();
 
     // Funding keypair: flag → Solana CLI config
     let funding_keypair = super::get_keypair(keypair_path)?;
@@ -56,39 +80,51 @@ pub async fn fund(
     let rpc_url = super::get_rpc_url(rpc_override)?;
     let client = RpcPool::with_url(&rpc_url).context("Failed to create RPC client")?;
 
-    // Determine lamports to transfer
-    let lamports = if let Some(sol) = amount {
-        (sol * LAMPORTS_PER_SOL as f64) as u64
+    // Determine lamports to transfer to each destination
+    let transfers: Vec<(Pubkey, u64)> = if let Some(sol) = amount {
+        vec![(fee_payers[0], (sol * LAMPORTS_PER_SOL as f64) as u64)]
     } else {
-        // Smart top-up: check current balance and top up to minimum
-        let balance = client
-            .get_balance(&destination)
-            .await
-            .context("Failed to get executor balance")?;
-
-        if balance >= MIN_BALANCE_LAMPORTS {
-            let sol = balance as f64 / LAMPORTS_PER_SOL as f64;
-            println!("Executor balance: {:.9} SOL ({} lamports)", sol, balance);
+        // Smart top-up: check current balances and top up to minimum
+        let mut transfers = Vec::new();
+        for (index, pubkey) in fee_payers.iter().enumerate() {
+            let name = if index == 0 { "Executor" } else { "Fee payer" };
+            let balance = client
+                .get_balance(pubkey)
+                .await
+                .with_context(|| format!("Failed to get {} balance", pubkey))?;
+            if balance >= MIN_BALANCE_LAMPORTS {
+                let sol = balance as f64 / LAMPORTS_PER_SOL as f64;
+                println!(
+                    "{} {} balance: {:.9} SOL ({} lamports)",
+                    name, pubkey, sol, balance
+                );
+            } else {
+                println!(
+                    "{} {} balance: {} lamports (below minimum {})",
+                    name, pubkey, balance, MIN_BALANCE_LAMPORTS
+                );
+                transfers.push((*pubkey, MIN_BALANCE_LAMPORTS - balance));
+            }
+        }
+        if transfers.is_empty() {
             println!(
                 "Already at or above minimum ({} lamports). No funding needed.",
                 MIN_BALANCE_LAMPORTS
             );
             return Ok(());
         }
-
-        let deficit = MIN_BALANCE_LAMPORTS - balance;
-        println!(
-            "Executor balance: {} lamports (below minimum {})",
-            balance, MIN_BALANCE_LAMPORTS
-        );
-        deficit
+        transfers
     };
 
-    let sol_amount = lamports as f64 / LAMPORTS_PER_SOL as f64;
-
-    println!("Transferring {:.9} SOL ({} lamports)", sol_amount, lamports);
     println!("  From (CLI wallet): {}", funding_keypair.pubkey());
-    println!("  To (executor):     {}", destination);
+    for (destination, lamports) in &transfers {
+        println!(
+            "Transferring {:.9} SOL ({} lamports) to {}",
+            *lamports as f64 / LAMPORTS_PER_SOL as f64,
+            lamports,
+            destination
+        );
+    }
 
     // Create and send transfer
     let (recent_blockhash, _) = client
@@ -96,13 +132,18 @@ pub async fn fund(
         .await
         .context("Failed to get recent blockhash")?;
 
-    let transfer_ix = solana_system_interface::instruction::transfer(
-        &funding_keypair.pubkey(),
-        &destination,
-        lamports,
-    );
-
-    let message = Message::new(&[transfer_ix], Some(&funding_keypair.pubkey()));
+    let transfer_ixs: Vec<_> = transfers
+        .iter()
+        .map(|(destination, lamports)| {
+            solana_system_interface::instruction::transfer(
+                &funding_keypair.pubkey(),
+                destination,
+                *lamports,
+            )
+        })
+        .collect();
+
+    let message = Message::new(&transfer_ixs, Some(&funding_keypair.pubkey()));
     let tx = Transaction::new(&[&funding_keypair], message, recent_blockhash);
 
     let signature = client
@@ -110,15 +151,19 @@ pub async fn fund(
         .await
         .context("Failed to send transaction")?;
 
-    let new_balance = client
-        .get_balance(&destination)
-        .await
-        .context("Failed to get new balance")?;
-    let new_sol = new_balance as f64 / LAMPORTS_PER_SOL as f64;
-
     println!();
     println!("Transaction: {}", signature);
-    println!("Executor balance: {:.9} SOL", new_sol);
+    for (destination, _) in &transfers {
+        let new_balance = client
+            .get_balance(destination)
+            .await
+            .context("Failed to get new balance")?;
+        println!(
+            "Balance of {}: {:.9} SOL",
+            destination,
+            new_balance as f64 / LAMPORTS_PER_SOL as f64
+        );
+    }
 
     Ok(())
 }
@@ -126,7 +171,8 @@ pub async fn fund(
 /// Withdraw SOL from executor to Solana CLI keypair
 ///
 /// - `amount = Some(x)`: withdraws exactly `x` SOL from executor
-/// - `amount = None`: withdraws everything above MIN_BALANCE_LAMPORTS + fee buffer
+/// - `amount = None`: withdraws everything above MIN_BALANCE_LAMPORTS + fee
+///   buffer from the executor and every additional fee payer
 pub async fn withdraw(
     config_path: PathBuf,
     amount: Option<f64>,
@@ -148,9 +194,8 @@ pub async fn withdraw(
         );
     }
 
-    // Load executor keypair (source)
-    let executor_keypair = load_keypair_from_config(&config)?;
-    let executor_pubkey = executor_keypair.pubkey();
+    // Load executor keypairs (sources)
+    let fee_payers = load_fee_payers_from_config(&config)?;
 
     // Load Solana CLI keypair for destination
     let destination_keypair = super::get_keypair(None)?;
@@ -161,15 +206,15 @@ pub async fn withdraw(
 
     let client = RpcPool::with_url(&rpc_url).context("Failed to create RPC client")?;
 
-    // Get current balance
-    let balance = client
-        .get_balance(&executor_pubkey)
-        .await
-        .context("Failed to get executor balance")?;
-
-    // Calculate amount to withdraw
+    // Calculate amount to withdraw from each source
     let fee_buffer = 5000u64;
-    let lamports = if let Some(sol) = amount {
+    let mut withdrawals: Vec<(&Keypair, u64)> = Vec::new();
+    if let Some(sol) = amount {
+        let executor_keypair = &fee_payers[0];
+        let balance = client
+            .get_balance(&executor_keypair.pubkey())
+            .await
+            .context("Failed to get executor balance")?;
         let requested = (sol * LAMPORTS_PER_SOL as f64) as u64;
         if requested + fee_buffer > balance {
             anyhow::bail!(
@@ -179,66 +224,277 @@ pub async fn withdraw(
                 balance
             );
         }
-        requested
+        withdrawals.push((executor_keypair, requested));
     } else {
         // Smart withdraw: everything above minimum + fee buffer
         let reserve = MIN_BALANCE_LAMPORTS + fee_buffer;
-        if balance <= reserve {
-            let sol = balance as f64 / LAMPORTS_PER_SOL as f64;
+        for keypair in &fee_payers {
+            let balance = client
+                .get_balance(&keypair.pubkey())
+                .await
+                .with_context(|| format!("Failed to get {} balance", keypair.pubkey()))?;
+            if balance > reserve {
+                withdrawals.push((keypair, balance - reserve));
+            }
+        }
+        if withdrawals.is_empty() {
             anyhow::bail!(
-                "Balance ({:.9} SOL) is at or below minimum reserve ({} lamports). Nothing to withdraw.",
-                sol,
+                "Every executor keypair is at or below the minimum reserve ({} lamports). Nothing to withdraw.",
                 reserve
             );
         }
-        balance - reserve
-    };
-
-    let sol_amount = lamports as f64 / LAMPORTS_PER_SOL as f64;
+    }
 
-    println!("Withdrawing {:.9} SOL ({} lamports)", sol_amount, lamports);
-    println!("  From (executor): {}", executor_pubkey);
-    println!("  To (CLI wallet): {}", destination);
+    // One transfer per source, each paying its own fee
+    for (source, lamports) in withdrawals {
+        let source_pubkey = source.pubkey();
+        println!();
+        println!(
+            "Withdrawing {:.9} SOL ({} lamports)",
+            lamports as f64 / LAMPORTS_PER_SOL as f64,
+            lamports
+        );
+        println!("  From (executor): {}", source_pubkey);
+        println!("  To (CLI wallet): {}", destination);
 
-    // Create and send transfer
-    let (recent_blockhash, _) = client
-        .get_latest_blockhash()
-        .await
-        .context("Failed to get recent blockhash")?;
+        let (recent_blockhash, _) = client
+            .get_latest_blockhash()
+            .await
+            .context("Failed to get recent blockhash")?;
 
-    let transfer_ix =
-        solana_system_interface::instruction::transfer(&executor_pubkey, &destination, lamports);
+        let transfer_ix =
+            solana_system_interface::instruction::transfer(&source_pubkey, &destination, lamports);
 
-    let message = Message::new(&[transfer_ix], Some(&executor_pubkey));
-    let tx = Transaction::new(&[&executor_keypair], message, recent_blockhash);
+        let message = Message::new(&[transfer_ix], Some(&source_pubkey));
+        let tx = Transaction::new(&[source], message, recent_blockhash);
 
-    let signature = client
-        .send_and_confirm_transaction(&tx)
-        .await
-        .context("Failed to send transaction")?;
+        let signature = client
+            .send_and_confirm_transaction(&tx)
+            .await
+            .context("Failed to send transaction")?;
+        let new_balance = client
+            .get_balance(&source_pubkey)
+            .await
+            .context("Failed to get executor balance")?;
+        println!("Transaction: {}", signature);
+        println!(
+            "  Executor:   {:.9} SOL",
+            new_balance as f64 / LAMPORTS_PER_SOL as f64
+        );
+    }
 
-    // Get new balances
-    let new_executor_balance = client
-        .get_balance(&executor_pubkey)
-        .await
-        .context("Failed to get executor balance")?;
     let new_destination_balance = client
         .get_balance(&destination)
         .await
         .context("Failed to get destination balance")?;
+    println!(
+        "  CLI wallet: {:.9} SOL",
+        new_destination_balance as f64 / LAMPORTS_PER_SOL as f64
+    );
+
+    Ok(())
+}
+
+/// Airdrop SOL to several keypairs at once through the RPC faucet
+///
+/// Requests go out in parallel with `requestAirdrop` and are confirmed
+/// together, so funding many local clients doesn't need the `solana` binary.
+/// When the faucet rate limits a request, every request waits out the
+/// backoff before the next one is sent. Funds the configured executor and
+/// its fee payers if no keypairs are given.
+pub async fn airdrop(
+    config_path: PathBuf,
+    keypairs: Vec<PathBuf>,
+    amount: f64,
+    rpc_override: Option<String>,
+) -> Result<()> {
+    let pubkeys = if keypairs.is_empty() {
+        let config = ClientConfig::load(&config_path)
+            .with_context(|| format!("Failed to load config from {:?}", config_path))?;
+        load_fee_payers_from_config(&config)?
+            .iter()
+            .map(|keypair| keypair.pubkey())
+            .collect()
+    } else {
+        keypairs
+            .iter()
+            .map(|path| {
+                read_keypair_file(path)
+                    .map(|keypair| keypair.pubkey())
+                    .map_err(|e| anyhow::anyhow!("Failed to read keypair from {:?}: {}", path, e))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let rpc_url = super::get_rpc_url(rpc_override)?;
+    let client = Arc::new(RpcPool::with_url(&rpc_url).context("Failed to create RPC client")?);
+    let lamports = (amount * LAMPORTS_PER_SOL as f64) as u64;
 
-    println!();
-    println!("Transaction: {}", signature);
-    println!();
-    println!("New balances:");
     println!(
-        "  Executor:   {:.9} SOL",
-        new_executor_balance as f64 / LAMPORTS_PER_SOL as f64
+        "Airdropping {:.9} SOL to {} keypair(s) via {}",
+        amount,
+        pubkeys.len(),
+        rpc_url
     );
+
+    // Send every request in parallel, sharing one rate limit gate
+    let gate = Arc::new(Mutex::new(Instant::now()));
+    let mut requests = tokio::task::JoinSet::new();
+    for (index, pubkey) in pubkeys.iter().enumerate() {
+        let client = client.clone();
+        let gate = gate.clone();
+        let pubkey = *pubkey;
+        requests.spawn(async move {
+            let result = request_airdrop_with_retry(&client, &gate, &pubkey, lamports).await;
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Result<Signature>> = pubkeys
+        .iter()
+        .map(|_| Err(anyhow::anyhow!("Airdrop task did not finish")))
+        .collect();
+    while let Some(joined) = requests.join_next().await {
+        if let Ok((index, result)) = joined {
+            results[index] = result;
+        }
+    }
+
+    // Confirm the accepted requests together
+    let pending: Vec<(usize, Signature)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| result.as_ref().ok().map(|sig| (index, *sig)))
+        .collect();
+    for (index, error) in confirm_airdrops(&client, &pending).await {
+        results[index] = Err(error);
+    }
+
+    println!();
+    let mut failed = 0;
+    for (pubkey, result) in pubkeys.iter().zip(&results) {
+        match result {
+            Ok(signature) => println!("  ✓ {} ({})", pubkey, signature),
+            Err(e) => {
+                failed += 1;
+                println!("  ✗ {}: {}", pubkey, e);
+            }
+        }
+    }
+    println!();
     println!(
-        "  CLI wallet: {:.9} SOL",
-        new_destination_balance as f64 / LAMPORTS_PER_SOL as f64
+        "Funded {}/{} keypair(s)",
+        pubkeys.len() - failed,
+        pubkeys.len()
     );
 
+    if failed > 0 {
+        anyhow::bail!("{} airdrop(s) failed", failed);
+    }
     Ok(())
 }
+
+/// Whether an RPC error is the faucet rejecting a request for rate limiting
+fn is_rate_limited(error: &anyhow::Error) -> bool {
+    let message = error.to_string();
+    message.contains("429") || message.to_lowercase().contains("too many requests")
+}
+
+/// Request one airdrop, retrying failures and spacing requests after a 429
+async fn request_airdrop_with_retry(
+    client: &RpcPool,
+    gate: &Mutex<Instant>,
+    pubkey: &Pubkey,
+    lamports: u64,
+) -> Result<Signature> {
+    let mut backoff = AIRDROP_RATE_LIMIT_BACKOFF;
+    let mut last_error = None;
+
+    for _ in 0..AIRDROP_ATTEMPTS {
+        let not_before = *gate.lock().await;
+        tokio::time::sleep_until(not_before).await;
+
+        match client.request_airdrop(pubkey, lamports).await {
+            Ok(signature) => return Ok(signature),
+            Err(e) => {
+                if is_rate_limited(&e) {
+                    // Hold back every request, not just this one
+                    let mut not_before = gate.lock().await;
+                    *not_before = (*not_before).max(Instant::now() + backoff);
+                } else {
+                    tokio::time::sleep(backoff).await;
+                }
+                backoff *= 2;
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No airdrop attempts made")))
+}
+
+/// Poll until every airdrop confirms or the timeout passes
+///
+/// Returns the index and error of each airdrop that failed or didn't confirm.
+async fn confirm_airdrops(
+    client: &RpcPool,
+    pending: &[(usize, Signature)],
+) -> Vec<(usize, anyhow::Error)> {
+    let mut pending = pending.to_vec();
+    let mut failed = Vec::new();
+    let start = Instant::now();
+
+    while !pending.is_empty() {
+        if start.elapsed() > AIRDROP_CONFIRM_TIMEOUT {
+            failed.extend(
+                pending
+                    .drain(..)
+                    .map(|(index, sig)| (index, anyhow::anyhow!("Confirmation timeout: {}", sig))),
+            );
+            break;
+        }
+
+        let signatures: Vec<Signature> = pending.iter().map(|(_, sig)| *sig).collect();
+        if let Ok(statuses) = client.get_signature_statuses(&signatures).await {
+            let mut still_pending = Vec::new();
+            for ((index, sig), status) in pending
+                .into_iter()
+                .zip(statuses.into_iter().chain(std::iter::repeat_with(|| None)))
+            {
+                match status {
+                    Some(status) if status.reached(CommitmentConfig::confirmed()) => {
+                        if let Some(e) = status.transaction_error() {
+                            failed.push((index, anyhow::anyhow!("Airdrop failed: {:?}", e)));
+                        }
+                    }
+                    _ => still_pending.push((index, sig)),
+                }
+            }
+            pending = still_pending;
+        }
+
+        if !pending.is_empty() {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rate_limited() {
+        assert!(is_rate_limited(&anyhow::anyhow!(
+            "HTTP error: 429 Too Many Requests - "
+        )));
+        assert!(is_rate_limited(&anyhow::anyhow!(
+            "RPC error: code -32603: Too many requests for a specific RPC call"
+        )));
+        assert!(!is_rate_limited(&anyhow::anyhow!(
+            "RPC error: code -32600: airdrop request failed"
+        )));
+    }
+}
@@ -1,8 +1,10 @@
 //! Config file commands
 
+use crate::output::OutputFormat;
+use antegen_client::config_migrate::{self, CURRENT_CONFIG_VERSION};
 use antegen_client::ClientConfig;
-use anyhow::Result;
-use std::path::PathBuf;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 
 /// Strip surrounding quotes from a string (handles user input with accidental quotes)
 fn strip_quotes(s: String) -> String {
@@ -73,9 +75,12 @@ pub fn init(
 }
 
 /// Display the current configuration
-pub fn get(config_path: PathBuf) -> Result<()> {
+pub fn get(config_path: PathBuf, output: OutputFormat) -> Result<()> {
     let config = ClientConfig::load(&config_path)?;
+    output.print(&config, |config| print_config(&config_path, config))
+}
 
+fn print_config(config_path: &Path, config: &ClientConfig) {
     println!("Config: {}", config_path.display());
     println!();
 
@@ -86,6 +91,10 @@ pub fn get(config_path: PathBuf) -> Result<()> {
         "  forgo_commission   = {}",
         config.executor.forgo_commission
     );
+    println!(
+        "  result_sinks       = {}",
+        config.executor.results.sinks.len()
+    );
     println!();
 
     // RPC endpoints
@@ -98,21 +107,106 @@ pub fn get(config_path: PathBuf) -> Result<()> {
         }
         println!("    role     = {:?}", ep.role);
         println!("    priority = {}", ep.priority);
+        if let Some(limits) = &ep.rate_limits {
+            let methods: Vec<String> = limits
+                .methods
+                .iter()
+                .map(|(method, rps)| format!("{}={}", method, rps))
+                .collect();
+            println!(
+                "    rate_limits = default={} burst={} {}",
+                limits.default,
+                limits.burst,
+                methods.join(" ")
+            );
+        }
     }
+    println!(
+        "  send_rate_limit_deadline_ms = {}",
+        config.rpc.send_rate_limit_deadline_ms
+    );
     println!();
 
     // Datasources
     println!("[datasources]");
-    println!("  commitment = {}", config.datasources.commitment);
-    println!("  program_id = {}", config.datasources.program_id);
+    println!(
+        "  commitment             = {}",
+        config.datasources.commitment
+    );
+    println!(
+        "  program_id             = {}",
+        config.datasources.program_id
+    );
+    println!(
+        "  clock_poll_interval_ms = {}",
+        config.datasources.clock_poll_interval_ms
+    );
     println!();
 
     // Processor
     println!("[processor]");
     println!(
-        "  max_concurrent_threads = {}",
+        "  max_concurrent_threads     = {}",
         config.processor.max_concurrent_threads
     );
+    println!(
+        "  fiber_execution_timeout_ms = {}",
+        config.processor.fiber_execution_timeout_ms
+    );
+    println!(
+        "  submission_timeout_ms      = {}",
+        config.processor.submission_timeout_ms
+    );
+    println!(
+        "  submission_timeout_factor  = {}",
+        config.processor.submission_timeout_factor
+    );
+    println!(
+        "  simulate_before_send       = {}",
+        config.processor.simulate_before_send
+    );
+    println!(
+        "  reconcile_on_start         = {}",
+        config.processor.reconcile_on_start
+    );
+    println!(
+        "  dedup_window_ms            = {}",
+        config.processor.dedup_window_ms
+    );
+    println!(
+        "  replay_guard_slots         = {}",
+        config.processor.replay_guard_slots
+    );
+    println!(
+        "  thread_filter              = {}",
+        config.processor.thread_filter.join(", ")
+    );
+    let gap = &config.processor.gap_backfill;
+    println!("  [gap_backfill]");
+    println!("    max_slot_gap = {}", gap.max_slot_gap);
+    println!("    time         = {:?}", gap.time);
+    println!("    slot         = {:?}", gap.slot);
+    println!("    epoch        = {:?}", gap.epoch);
+    let fees = &config.processor.fee_ceiling;
+    println!("  [fee_ceiling]");
+    match fees.max_priority_fee_lamports {
+        Some(cap) => println!("    max_priority_fee_lamports = {}", cap),
+        None => println!("    max_priority_fee_lamports = (none)"),
+    }
+    match fees.max_balance_fraction {
+        Some(fraction) => println!("    max_balance_fraction      = {}", fraction),
+        None => println!("    max_balance_fraction      = (none)"),
+    }
+    println!(
+        "    min_viable_cu_price       = {}",
+        fees.min_viable_cu_price
+    );
+    if let Some(adaptive) = &config.processor.adaptive_concurrency {
+        println!("  [adaptive_concurrency]");
+        println!("    min_workers       = {}", adaptive.min_workers);
+        println!("    target_latency_ms = {}", adaptive.target_latency_ms);
+        println!("    backoff           = {}", adaptive.backoff);
+    }
     println!();
 
     // Cache
@@ -140,6 +234,12 @@ pub fn get(config_path: PathBuf) -> Result<()> {
     println!("[observability]");
     println!("  enabled      = {}", config.observability.enabled);
     println!("  storage_path = {}", config.observability.storage_path);
+    println!("  log_format   = {:?}", config.observability.log_format);
+    let mut actor_levels: Vec<_> = config.observability.actor_log_levels.iter().collect();
+    actor_levels.sort();
+    for (actor, level) in actor_levels {
+        println!("  {} = {}", actor, level);
+    }
     println!();
 
     // TPU
@@ -148,8 +248,89 @@ pub fn get(config_path: PathBuf) -> Result<()> {
     println!("  num_connections     = {}", config.tpu.num_connections);
     println!("  leaders_fanout     = {}", config.tpu.leaders_fanout);
     println!("  worker_channel_size = {}", config.tpu.worker_channel_size);
+    println!("  submission_mode     = {:?}", config.tpu.submission_mode);
+    println!(
+        "  confirmation_poll_interval_ms = {}",
+        config.tpu.confirmation_poll_interval_ms
+    );
+    println!(
+        "  confirmation_timeout_ms = {}",
+        config.tpu.confirmation_timeout_ms
+    );
+    if let Some(auto_disable) = &config.tpu.auto_disable {
+        println!(
+            "  auto_disable        = window {}s, max error rate {}, min samples {}, cooldown {}s",
+            auto_disable.window_secs,
+            auto_disable.max_error_rate,
+            auto_disable.min_samples,
+            auto_disable.cooldown_secs
+        );
+    }
+    if let Some(alignment) = &config.tpu.leader_alignment {
+        println!(
+            "  leader_alignment    = max hold {}ms, latency-sensitive {:?}",
+            alignment.max_hold_ms, alignment.latency_sensitive_triggers
+        );
+    }
+    println!();
 
-    Ok(())
+    // Submission
+    println!("[submission]");
+    println!("  broadcast          = {}", config.submission.broadcast);
+    println!(
+        "  broadcast_triggers = {:?}",
+        config.submission.broadcast_triggers
+    );
+    println!("  batch_exec         = {}", config.submission.batch_exec);
+    println!(
+        "  batch_window_ms    = {}",
+        config.submission.batch_window_ms
+    );
+    println!();
+
+    // Costs
+    println!("[costs]");
+    println!("  enabled        = {}", config.costs.enabled);
+    println!("  storage_path   = {}", config.costs.storage_path);
+    println!("  retention_days = {}", config.costs.retention_days);
+    println!();
+
+    // Transaction log
+    println!("[tx_log]");
+    println!("  enabled   = {}", config.tx_log.enabled);
+    println!("  path      = {}", config.tx_log.path);
+    println!("  max_bytes = {}", config.tx_log.max_bytes);
+    println!();
+
+    // Alerts
+    let threshold = |value: Option<String>| value.unwrap_or_else(|| "off".to_string());
+    println!("[alerts]");
+    println!(
+        "  check_interval_secs      = {}",
+        config.alerts.check_interval_secs
+    );
+    println!(
+        "  confirmation_p95_ms      = {}",
+        threshold(config.alerts.confirmation_p95_ms.map(|v| v.to_string()))
+    );
+    println!(
+        "  max_consecutive_failures = {}",
+        threshold(
+            config
+                .alerts
+                .max_consecutive_failures
+                .map(|v| v.to_string())
+        )
+    );
+    println!(
+        "  min_executor_balance     = {}",
+        threshold(config.alerts.min_executor_balance.map(|v| v.to_string()))
+    );
+    println!(
+        "  max_update_silence_secs  = {}",
+        threshold(config.alerts.max_update_silence_secs.map(|v| v.to_string()))
+    );
+    println!("  sinks                    = {}", config.alerts.sinks.len());
 }
 
 /// Update configuration values
@@ -307,3 +488,58 @@ pub fn validate(config_path: PathBuf) -> Result<()> {
 
     Ok(())
 }
+
+/// Upgrade a config file to the current layout
+///
+/// Prints the upgraded file unless `write` is set, in which case the original
+/// is kept as `<file>.bak` and the upgraded file written in its place.
+pub fn migrate(config_path: PathBuf, write: bool) -> Result<()> {
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+    let mut doc: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
+
+    let from = config_migrate::migrate(&mut doc)?;
+    if from == CURRENT_CONFIG_VERSION {
+        println!(
+            "✓ {} is already at config_version {}",
+            config_path.display(),
+            CURRENT_CONFIG_VERSION
+        );
+        return Ok(());
+    }
+
+    // Refuse to write anything the client couldn't load
+    let migrated = doc.to_string();
+    let config: ClientConfig =
+        toml::from_str(&migrated).context("Upgraded config does not parse")?;
+    config.validate()?;
+
+    if !write {
+        print!("{}", migrated);
+        eprintln!();
+        eprintln!(
+            "Upgraded config_version {} -> {}. Re-run with --write to save it.",
+            from, CURRENT_CONFIG_VERSION
+        );
+        return Ok(());
+    }
+
+    let mut backup = config_path.clone().into_os_string();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    std::fs::copy(&config_path, &backup)
+        .with_context(|| format!("Failed to back up config to {}", backup.display()))?;
+    std::fs::write(&config_path, migrated)
+        .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+
+    println!(
+        "✓ Upgraded {} from config_version {} to {}",
+        config_path.display(),
+        from,
+        CURRENT_CONFIG_VERSION
+    );
+    println!("✓ Original saved to {}", backup.display());
+    Ok(())
+}
@@ -1,5 +1,6 @@
 //! Info command - Show antegen configuration and status
 
+use crate::output::OutputFormat;
 use antegen_client::ClientConfig;
 use anyhow::Result;
 use serde::Serialize;
@@ -17,6 +18,9 @@ pub struct InfoOutput {
     pub executor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub balance_sol: Option<f64>,
+    /// Exact balance, as a string so JSON consumers keep full precision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_lamports: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rpc: Option<String>,
     pub service: String,
@@ -112,6 +116,7 @@ async fn gather_info() -> Result<InfoOutput> {
             service_version,
             executor: None,
             balance_sol: None,
+            balance_lamports: None,
             rpc: None,
             service,
             observability: ObservabilityInfo {
@@ -139,7 +144,7 @@ async fn gather_info() -> Result<InfoOutput> {
     };
 
     // Get balance
-    let balance_sol = if let Some(ref _executor) = executor {
+    let balance = if let Some(ref _executor) = executor {
         get_balance(&config).await.ok()
     } else {
         None
@@ -155,7 +160,8 @@ async fn gather_info() -> Result<InfoOutput> {
         version,
         service_version,
         executor,
-        balance_sol,
+        balance_sol: balance.map(|lamports| lamports as f64 / LAMPORTS_PER_SOL as f64),
+        balance_lamports: balance.map(|lamports| lamports.to_string()),
         rpc,
         service,
         observability,
@@ -165,8 +171,8 @@ async fn gather_info() -> Result<InfoOutput> {
     })
 }
 
-/// Get executor balance
-async fn get_balance(config: &ClientConfig) -> Result<f64> {
+/// Get executor balance in lamports
+async fn get_balance(config: &ClientConfig) -> Result<u64> {
     let keypair_path = super::expand_tilde(&config.executor.keypair_path)?;
     let keypair = read_keypair_file(&keypair_path)
         .map_err(|e| anyhow::anyhow!("Failed to read keypair: {}", e))?;
@@ -179,9 +185,7 @@ async fn get_balance(config: &ClientConfig) -> Result<f64> {
         .ok_or_else(|| anyhow::anyhow!("No RPC endpoints configured"))?;
 
     let client = antegen_client::rpc::RpcPool::with_url(&rpc_url.url)?;
-    let balance = client.get_balance(&pubkey).await?;
-
-    Ok(balance as f64 / LAMPORTS_PER_SOL as f64)
+    Ok(client.get_balance(&pubkey).await?)
 }
 
 /// Get observability info from LOA
@@ -307,14 +311,7 @@ fn print_info(info: &InfoOutput) {
 }
 
 /// Execute the info command
-pub async fn info(json: bool) -> Result<()> {
+pub async fn info(output: OutputFormat) -> Result<()> {
     let info = gather_info().await?;
-
-    if json {
-        println!("{}", serde_json::to_string_pretty(&info)?);
-    } else {
-        print_info(&info);
-    }
-
-    Ok(())
+    output.print(&info, print_info)
 }
@@ -447,8 +447,8 @@ fn get_log_path() -> Result<PathBuf> {
         .context("Could not determine log directory")
 }
 
-/// View service logs
-pub fn logs(follow: bool) -> Result<()> {
+/// View service logs, starting with the last `tail` lines
+pub fn logs(follow: bool, tail: u64) -> Result<()> {
     #[cfg(target_os = "macos")]
     {
         let log_file = get_log_path()?;
@@ -459,10 +459,9 @@ pub fn logs(follow: bool) -> Result<()> {
         }
 
         let mut cmd = std::process::Command::new("tail");
+        cmd.arg("-n").arg(tail.to_string());
         if follow {
-            cmd.arg("-n").arg("50").arg("-f");
-        } else {
-            cmd.arg("-n").arg("100");
+            cmd.arg("-f");
         }
         cmd.arg(&log_file);
         cmd.status().context("Failed to run tail")?;
@@ -479,10 +478,9 @@ pub fn logs(follow: bool) -> Result<()> {
             // User service
             cmd.arg("--user").arg("-u").arg(SERVICE_LABEL);
         }
+        cmd.arg("-n").arg(tail.to_string());
         if follow {
             cmd.arg("-f");
-        } else {
-            cmd.arg("-n").arg("100");
         }
         cmd.status().context("Failed to run journalctl")?;
     }
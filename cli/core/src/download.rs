@@ -1,6 +1,7 @@
 //! Download module for fetching the Geyser plugin from GitHub releases
 
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -8,6 +9,9 @@ use std::path::Path;
 /// GitHub repository for releases
 const GITHUB_REPO: &str = "wuwei-labs/antegen";
 
+/// `sha256sum` manifest attached to each geyser release
+const CHECKSUMS_FILE: &str = "checksums.txt";
+
 /// Get the current CLI version (used to match plugin version)
 pub fn current_version() -> &'static str {
     concat!("v", env!("CARGO_PKG_VERSION"))
@@ -57,13 +61,19 @@ pub fn get_library_filename() -> String {
     format!("libantegen_geyser_plugin.{}", ext)
 }
 
+/// Release asset name of the geyser plugin for this platform
+fn asset_name(version: &str) -> String {
+    // Note: Release artifacts use .so extension even for macOS dylib
+    format!("antegen-geyser-{}-{}.so", version, get_platform_target())
+}
+
 /// Build the download URL for the geyser plugin
 fn build_download_url(version: &str) -> String {
-    let target = get_platform_target();
-    // Note: Release artifacts use .so extension even for macOS dylib
     format!(
-        "https://github.com/{}/releases/download/{}/antegen-geyser-{}-{}.so",
-        GITHUB_REPO, version, version, target
+        "https://github.com/{}/releases/download/{}/{}",
+        GITHUB_REPO,
+        version,
+        asset_name(version)
     )
 }
 
@@ -129,6 +139,73 @@ pub async fn download_geyser_plugin(version: &str, dest: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Check a downloaded plugin against the release's checksum manifest
+///
+/// Fetches the `sha256sum` manifest from the same GitHub release and fails
+/// if it has no entry for this platform's asset or the digest differs.
+pub async fn verify_geyser_plugin(version: &str, plugin_path: &Path) -> Result<()> {
+    let url = format!(
+        "https://github.com/{}/releases/download/{}/{}",
+        GITHUB_REPO, version, CHECKSUMS_FILE
+    );
+    log::info!("Downloading checksums from: {}", url);
+
+    let response = reqwest::get(&url)
+        .await
+        .context("Failed to connect to GitHub releases")?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to download {} for {}: HTTP {}",
+            CHECKSUMS_FILE,
+            version,
+            response.status()
+        ));
+    }
+    let manifest = response
+        .text()
+        .await
+        .context("Failed to read checksum manifest")?;
+
+    let asset = asset_name(version);
+    let expected = find_checksum(&manifest, &asset).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} for {} has no entry for {}",
+            CHECKSUMS_FILE,
+            version,
+            asset
+        )
+    })?;
+    let actual = sha256_file(plugin_path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(anyhow::anyhow!(
+            "Checksum mismatch for {}:\n  expected {}\n  actual   {}\n\
+             The download may be corrupt or tampered with; do not load it.",
+            plugin_path.display(),
+            expected,
+            actual
+        ));
+    }
+    Ok(())
+}
+
+/// Look up `asset` in `sha256sum` output (`<hex digest>  [*]<file name>` per line)
+fn find_checksum<'a>(manifest: &'a str, asset: &str) -> Option<&'a str> {
+    manifest.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset).then_some(digest)
+    })
+}
+
+/// Hex-encoded SHA-256 of a file
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
 /// Check if the plugin needs to be updated (version mismatch)
 pub fn needs_update(plugin_path: &Path, expected_version: &str) -> Result<bool> {
     // For now, just check if the file exists
@@ -182,6 +259,27 @@ mod tests {
         assert!(url.contains("antegen-geyser"));
     }
 
+    #[test]
+    fn test_find_checksum() {
+        let asset = asset_name("v3.0.0");
+        let manifest = format!(
+            "1111  antegen-geyser-v3.0.0-other-target.so\nABCD *{}\n",
+            asset
+        );
+        assert_eq!(find_checksum(&manifest, &asset), Some("ABCD"));
+        assert_eq!(find_checksum(&manifest, "missing.so"), None);
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), b"abc").unwrap();
+        assert_eq!(
+            sha256_file(file.path()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
     #[test]
     fn test_get_library_filename() {
         let filename = get_library_filename();
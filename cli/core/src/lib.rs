@@ -3,8 +3,12 @@
 use clap::{Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+pub mod abi;
 pub mod commands;
 pub mod download;
+pub mod output;
+
+pub use output::OutputFormat;
 
 #[derive(Clone, Debug, ValueEnum)]
 pub enum LogLevel {
@@ -144,19 +148,37 @@ EXAMPLES:
         #[arg(short, long, default_value = "antegen.toml")]
         config: PathBuf,
     },
+
+    /// Upgrade a config file written for an older release
+    #[command(after_long_help = "\
+EXAMPLES:
+    antegen config migrate --config antegen.toml
+    antegen config migrate --config antegen.toml --write
+")]
+    Migrate {
+        /// Path to config file (defaults to ~/.config/antegen/antegen.toml)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Back up the original and write the upgraded file in its place,
+        /// instead of printing it
+        #[arg(long)]
+        write: bool,
+    },
 }
 
 /// Dispatch a NodeConfigCommands variant to the appropriate handler
 pub fn dispatch_config(
     config_cmd: NodeConfigCommands,
     global_rpc: Option<String>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     match config_cmd {
         NodeConfigCommands::Get { config } => {
             let path = config
                 .map(Ok)
                 .unwrap_or_else(commands::default_config_path)?;
-            commands::config::get(path)
+            commands::config::get(path, output)
         }
         NodeConfigCommands::Set {
             config,
@@ -208,5 +230,11 @@ pub fn dispatch_config(
             commands::config::init(path, rpc, keypair_path, storage_path, force)
         }
         NodeConfigCommands::Validate { config } => commands::config::validate(config),
+        NodeConfigCommands::Migrate { config, write } => {
+            let path = config
+                .map(Ok)
+                .unwrap_or_else(commands::default_config_path)?;
+            commands::config::migrate(path, write)
+        }
     }
 }
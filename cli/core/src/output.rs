@@ -0,0 +1,106 @@
+//! Output formats shared by read commands
+//!
+//! Read commands build a serializable result and hand it to
+//! [`OutputFormat::print`], which prints it as JSON or falls back to the
+//! command's human-readable printer. In JSON modes, progress notes go to
+//! stderr (see [`OutputFormat::note`]) so stdout stays parseable.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// Compact JSON on a single line
+    Json,
+    /// Indented JSON
+    JsonPretty,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self != OutputFormat::Text
+    }
+
+    /// Print `value` as JSON, or call `text` to print it for humans
+    pub fn print<T: Serialize>(self, value: &T, text: impl FnOnce(&T)) -> Result<()> {
+        match self {
+            OutputFormat::Text => {
+                text(value);
+                Ok(())
+            }
+            _ => self.print_json(value),
+        }
+    }
+
+    /// Print `value` as JSON, indented for `JsonPretty`
+    pub fn print_json<T: Serialize>(self, value: &T) -> Result<()> {
+        match self {
+            OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(value)?),
+            _ => println!("{}", serde_json::to_string(value)?),
+        }
+        Ok(())
+    }
+
+    /// Print a progress note: stdout for text, stderr for JSON
+    pub fn note(self, message: impl std::fmt::Display) {
+        if self.is_json() {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+}
+
+/// Serialize integers (e.g. lamports) as strings, so JavaScript consumers
+/// don't lose precision above 2^53
+///
+/// Use with `#[serde(with = "antegen_cli_core::output::as_string")]`.
+pub mod as_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Balance {
+        #[serde(with = "as_string")]
+        lamports: u64,
+    }
+
+    #[test]
+    fn test_lamports_round_trip_as_string() {
+        let balance = Balance { lamports: u64::MAX };
+        let json = serde_json::to_string(&balance).unwrap();
+        assert_eq!(json, r#"{"lamports":"18446744073709551615"}"#);
+
+        let parsed: Balance = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.lamports, u64::MAX);
+    }
+}
@@ -0,0 +1,12 @@
+fn main() {
+    // The control API is generated from its proto only when it is built
+    #[cfg(feature = "control")]
+    {
+        println!("cargo:rerun-if-changed=proto/control.proto");
+        tonic_prost_build::configure()
+            .build_client(true)
+            .build_server(true)
+            .compile_protos(&["proto/control.proto"], &["proto"])
+            .expect("Failed to compile proto/control.proto");
+    }
+}
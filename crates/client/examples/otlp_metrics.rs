@@ -0,0 +1,47 @@
+//! Push sample client metrics to a local OpenTelemetry collector
+//!
+//! Start a collector that accepts OTLP over HTTP on port 4318, e.g.
+//!
+//! ```sh
+//! docker run --rm -p 4318:4318 otel/opentelemetry-collector
+//! ```
+//!
+//! then run
+//!
+//! ```sh
+//! cargo run -p antegen-client --example otlp_metrics --features metrics
+//! ```
+
+use antegen_client::config::OtlpConfig;
+use antegen_client::metrics;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config = OtlpConfig {
+        export_interval_secs: 1,
+        ..OtlpConfig::default()
+    };
+    metrics::init(&config)?;
+
+    for round in 0..20u64 {
+        let outcome = if round % 5 == 4 {
+            metrics::OUTCOME_FAILED
+        } else {
+            metrics::OUTCOME_SUCCESS
+        };
+        metrics::record_thread_execution(outcome);
+        metrics::record_submission_send(metrics::PATH_TPU);
+        metrics::record_submission_latency(
+            metrics::PATH_TPU,
+            Duration::from_millis(400 + round * 20),
+        );
+        metrics::record_rpc_call("getLatestBlockhash", true, Duration::from_millis(30));
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    // Flushes the last export before exiting
+    metrics::shutdown();
+    println!("Sent sample metrics to {}", config.endpoint);
+    Ok(())
+}
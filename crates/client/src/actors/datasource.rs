@@ -1,37 +1,47 @@
 //! Datasource Supervisor and Source Actors
 //!
 //! The DatasourceSupervisor dynamically spawns source actors based on configuration:
-//! - RpcSourceActor: Listens to WebSocket streams for account updates
-//! - GeyserSourceActor: Consumes mpsc channel from Geyser plugin
+//! - RpcSourceActor: Listens to WebSocket streams for account updates, from an
+//!   RPC endpoint or from Helius
+//! - GeyserSourceActor: Consumes the priority channel from the Geyser plugin,
+//!   clock updates first. Startup snapshot
+//!   accounts are written straight to the cache and announced to StagingActor in
+//!   one `InitialLoadComplete` message once the validator signals end of startup
 //!
 //! All source actors push updates through the shared cache for deduplication
 //! before forwarding to StagingActor.
 
 use crate::actors::messages::{
-    DatasourceMessage, GeyserSourceMessage, RpcSourceMessage, StagingMessage,
+    ActorCounters, DatasourceMessage, GeyserSourceMessage, RpcSourceMessage, StagingMessage,
 };
 use crate::config::{ClientConfig, EndpointRole, RpcEndpoint};
-use crate::datasources::RpcSubscription;
+use crate::datasources::clock::{run_clock_poller, run_clock_watcher, ClockSource};
+use crate::datasources::{HeliusDatasource, PriorityReceiver, RpcSubscription};
 use crate::resources::SharedResources;
-use crate::types::AccountUpdate;
+use crate::types::PluginEvent;
 use ractor::{Actor, ActorProcessingErr, ActorRef};
 use std::collections::HashMap;
 use std::error::Error;
-use tokio::sync::mpsc;
+use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 
 // ============================================================================
 // Datasource Supervisor
 // ============================================================================
 
+/// Name the DatasourceSupervisor reports its stats under
+pub const DATASOURCE_ACTOR_NAME: &str = "datasource";
+
 #[derive(Default)]
 pub struct DatasourceSupervisor;
 
 pub struct DatasourceState {
-    #[allow(dead_code)] // Kept for future supervisor functionality (stop/restart children)
     rpc_sources: HashMap<String, ActorRef<RpcSourceMessage>>,
-    #[allow(dead_code)] // Kept for future supervisor functionality (stop/restart children)
     geyser_source: Option<ActorRef<GeyserSourceMessage>>,
+    // Stops the clock poller
+    clock_poller_cancel: CancellationToken,
+    // Message counts reported to CollectMetrics
+    counters: ActorCounters,
 }
 
 impl Actor for DatasourceSupervisor {
@@ -41,7 +51,7 @@ impl Actor for DatasourceSupervisor {
         ClientConfig,
         SharedResources,
         ActorRef<StagingMessage>,
-        Option<mpsc::Receiver<AccountUpdate>>,
+        Option<PriorityReceiver>,
     );
 
     async fn pre_start(
@@ -65,7 +75,11 @@ impl Actor for DatasourceSupervisor {
                 let (rpc_ref, _handle) = Actor::spawn_linked(
                     Some(actor_name.clone()),
                     RpcSourceActor,
-                    (endpoint.clone(), resources.clone(), staging_ref.clone()),
+                    (
+                        RpcSource::Endpoint(endpoint.clone()),
+                        resources.clone(),
+                        staging_ref.clone(),
+                    ),
                     supervisor.clone(),
                 )
                 .await
@@ -76,6 +90,31 @@ impl Actor for DatasourceSupervisor {
             }
         }
 
+        if let Some(helius) = &config.datasources.helius {
+            let datasource =
+                HeliusDatasource::new(helius, resources.program_id, &config.datasources.commitment)
+                    .map_err(|e| format!("Invalid Helius datasource: {}", e))?;
+            let actor_name = "rpc-source-helius".to_string();
+
+            log::debug!("Spawning RpcSourceActor for Helius: {}", datasource.label());
+
+            let (rpc_ref, _handle) = Actor::spawn_linked(
+                Some(actor_name.clone()),
+                RpcSourceActor,
+                (
+                    RpcSource::Helius(Arc::new(datasource)),
+                    resources.clone(),
+                    staging_ref.clone(),
+                ),
+                supervisor.clone(),
+            )
+            .await
+            .map_err(|e| format!("Failed to spawn RpcSourceActor: {}", e))?;
+
+            rpc_sources.insert(actor_name, rpc_ref);
+            datasource_count += 1;
+        }
+
         log::debug!("Spawned {} RPC datasource actors", datasource_count);
 
         // Optionally spawn GeyserSourceActor if we have a channel from the plugin (linked)
@@ -96,9 +135,33 @@ impl Actor for DatasourceSupervisor {
             None
         };
 
+        // Fallback clock source, in case no datasource delivers clock updates
+        let clock_poller_cancel = CancellationToken::new();
+        let poll_interval = config.datasources.clock_poll_interval_ms;
+        if poll_interval > 0 {
+            let interval = std::time::Duration::from_millis(poll_interval);
+            if config.datasources.clock_use_websocket {
+                tokio::spawn(run_clock_watcher(
+                    resources.clone(),
+                    staging_ref.clone(),
+                    interval,
+                    clock_poller_cancel.clone(),
+                ));
+            } else {
+                tokio::spawn(run_clock_poller(
+                    resources.clone(),
+                    staging_ref.clone(),
+                    interval,
+                    clock_poller_cancel.clone(),
+                ));
+            }
+        }
+
         Ok(DatasourceState {
             rpc_sources,
             geyser_source,
+            clock_poller_cancel,
+            counters: ActorCounters::new(),
         })
     }
 
@@ -106,19 +169,32 @@ impl Actor for DatasourceSupervisor {
         &self,
         _myself: ActorRef<Self::Msg>,
         message: Self::Msg,
-        _state: &mut Self::State,
+        state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
-        match message {
+        let result = match message {
             DatasourceMessage::AccountUpdate(_update) => {
                 // Datasource supervisor doesn't need to handle updates directly
                 // RpcSourceActors send directly to StagingActor
                 Ok(())
             }
+            DatasourceMessage::CollectMetrics(tx) => {
+                let stats = state.counters.stats(
+                    DATASOURCE_ACTOR_NAME,
+                    [
+                        ("rpc_sources", state.rpc_sources.len() as f64),
+                        ("geyser_sources", state.geyser_source.is_some() as u8 as f64),
+                    ],
+                );
+                let _ = tx.send(stats);
+                Ok(())
+            }
             DatasourceMessage::Shutdown => {
                 log::info!("DatasourceSupervisor shutting down...");
                 Err(From::from("Shutdown signal received"))
             }
-        }
+        };
+        state.counters.record(result.is_err());
+        result
     }
 
     async fn post_stop(
@@ -126,6 +202,7 @@ impl Actor for DatasourceSupervisor {
         _myself: ActorRef<Self::Msg>,
         state: &mut Self::State,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        state.clock_poller_cancel.cancel();
         log::info!(
             "DatasourceSupervisor stopped. {} RPC sources cleaned up",
             state.rpc_sources.len()
@@ -141,11 +218,21 @@ impl Actor for DatasourceSupervisor {
 #[derive(Default)]
 pub struct RpcSourceActor;
 
+/// Where an RpcSourceActor streams from
+#[derive(Clone)]
+pub enum RpcSource {
+    /// Separate program and clock subscriptions on an endpoint's websocket
+    Endpoint(RpcEndpoint),
+    /// Both subscriptions on one Helius connection
+    Helius(Arc<HeliusDatasource>),
+}
+
 /// Maximum number of subscription restart attempts before the actor gives up
 const MAX_SUBSCRIPTION_RESTARTS: u32 = 3;
 
 pub struct RpcSourceState {
     ws_url: String,
+    source: RpcSource,
     staging_ref: ActorRef<StagingMessage>,
     resources: SharedResources,
     cancel_token: CancellationToken,
@@ -156,30 +243,45 @@ pub struct RpcSourceState {
 impl Actor for RpcSourceActor {
     type Msg = RpcSourceMessage;
     type State = RpcSourceState;
-    type Arguments = (RpcEndpoint, SharedResources, ActorRef<StagingMessage>);
+    type Arguments = (RpcSource, SharedResources, ActorRef<StagingMessage>);
 
     async fn pre_start(
         &self,
         myself: ActorRef<Self::Msg>,
-        (endpoint, resources, staging_ref): Self::Arguments,
+        (source, resources, staging_ref): Self::Arguments,
     ) -> Result<Self::State, Box<dyn Error + Send + Sync>> {
-        let ws_url = endpoint.get_ws_url();
-        log::debug!(
-            "RpcSourceActor starting for: {} (ws: {})",
-            endpoint.url,
-            ws_url
-        );
-        log::debug!("  - Thread program: {}", resources.program_id);
-        log::debug!("  - Clock sysvar: {}", solana_sdk::sysvar::clock::ID);
-
         let cancel_token = CancellationToken::new();
 
         // Spawn monitored subscription tasks
-        spawn_program_subscription(&ws_url, &resources, myself.clone(), cancel_token.clone());
-        spawn_clock_subscription(&ws_url, &resources, myself.clone(), cancel_token.clone());
+        let ws_url = match &source {
+            RpcSource::Endpoint(endpoint) => {
+                let ws_url = endpoint.get_ws_url();
+                log::debug!(
+                    "RpcSourceActor starting for: {} (ws: {})",
+                    endpoint.url,
+                    ws_url
+                );
+                spawn_program_subscription(
+                    &ws_url,
+                    &resources,
+                    myself.clone(),
+                    cancel_token.clone(),
+                );
+                spawn_clock_subscription(&ws_url, &resources, myself.clone(), cancel_token.clone());
+                ws_url
+            }
+            RpcSource::Helius(datasource) => {
+                log::debug!("RpcSourceActor starting for Helius: {}", datasource.label());
+                spawn_helius_subscription(datasource.clone(), myself.clone(), cancel_token.clone());
+                datasource.label().to_string()
+            }
+        };
+        log::debug!("  - Thread program: {}", resources.program_id);
+        log::debug!("  - Clock sysvar: {}", solana_sdk::sysvar::clock::ID);
 
         Ok(RpcSourceState {
             ws_url,
+            source,
             staging_ref,
             resources,
             cancel_token,
@@ -244,6 +346,10 @@ impl Actor for RpcSourceActor {
                 );
 
                 // Clock is NOT cached - always forward fresh to StagingActor
+                state
+                    .resources
+                    .clock_monitor
+                    .record(ClockSource::Datasource);
                 state
                     .staging_ref
                     .send_message(StagingMessage::ClockTick(clock))
@@ -268,6 +374,8 @@ impl Actor for RpcSourceActor {
                 // A subscription background task has exited — restart it if under retry limit
                 let (restart_count, limit_name) = match which.as_str() {
                     "program" => (&mut state.program_restart_count, "program"),
+                    // Helius carries the program subscription
+                    "helius" => (&mut state.program_restart_count, "helius"),
                     "clock" => (&mut state.clock_restart_count, "clock"),
                     other => {
                         log::warn!("[{}] Unknown subscription died: {}", state.ws_url, other);
@@ -314,6 +422,15 @@ impl Actor for RpcSourceActor {
                             state.cancel_token.clone(),
                         );
                     }
+                    "helius" => {
+                        if let RpcSource::Helius(datasource) = &state.source {
+                            spawn_helius_subscription(
+                                datasource.clone(),
+                                myself.clone(),
+                                state.cancel_token.clone(),
+                            );
+                        }
+                    }
                     _ => {}
                 }
 
@@ -395,6 +512,32 @@ fn spawn_clock_subscription(
     });
 }
 
+/// Spawn a monitored Helius subscription task, which carries both the
+/// program and the clock subscription.
+/// Same pattern as `spawn_program_subscription`.
+fn spawn_helius_subscription(
+    datasource: Arc<HeliusDatasource>,
+    actor_ref: ActorRef<RpcSourceMessage>,
+    cancel_token: CancellationToken,
+) {
+    let sub_actor_ref = actor_ref.clone();
+
+    let handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = datasource.run(sub_actor_ref) => {}
+            _ = cancel_token.cancelled() => {
+                log::debug!("Helius subscription cancelled");
+            }
+        }
+    });
+
+    // Watcher: notify the actor when the subscription task exits
+    tokio::spawn(async move {
+        let _ = handle.await;
+        let _ = actor_ref.send_message(RpcSourceMessage::SubscriptionDied("helius".to_string()));
+    });
+}
+
 // ============================================================================
 // Geyser Source Actor
 // ============================================================================
@@ -414,11 +557,7 @@ pub struct GeyserSourceState {
 impl Actor for GeyserSourceActor {
     type Msg = GeyserSourceMessage;
     type State = GeyserSourceState;
-    type Arguments = (
-        mpsc::Receiver<AccountUpdate>,
-        SharedResources,
-        ActorRef<StagingMessage>,
-    );
+    type Arguments = (PriorityReceiver, SharedResources, ActorRef<StagingMessage>);
 
     async fn pre_start(
         &self,
@@ -438,13 +577,29 @@ impl Actor for GeyserSourceActor {
         tokio::spawn(async move {
             log::info!("GeyserSourceActor channel consumer started");
 
+            // Snapshot accounts written straight to the cache while startup is in progress
+            let mut startup_loaded: Option<usize> = None;
+
             loop {
                 tokio::select! {
-                    update = receiver.recv() => {
-                        let Some(update) = update else {
+                    event = receiver.recv() => {
+                        let Some(event) = event else {
                             break; // Channel closed
                         };
 
+                        let (update, is_startup) = match event {
+                            PluginEvent::AccountUpdate { update, is_startup } => (update, is_startup),
+                            PluginEvent::EndOfStartup => {
+                                let count = startup_loaded.take().unwrap_or(0);
+                                log::info!("[Geyser] Startup complete: {} accounts bulk-loaded", count);
+                                if let Err(e) = staging.send_message(StagingMessage::InitialLoadComplete { count }) {
+                                    log::error!("[Geyser] Failed to send to staging: {}", e);
+                                    break;
+                                }
+                                continue;
+                            }
+                        };
+
                         log::trace!(
                             "[Geyser] Received account update: pubkey={}, slot={}, data_len={}",
                             update.pubkey,
@@ -457,6 +612,15 @@ impl Actor for GeyserSourceActor {
                             .put_if_newer(update.pubkey, update.data.clone(), update.slot)
                             .await;
 
+                        // Bulk-load mode: staging evaluates everything at once on EndOfStartup
+                        if is_startup || startup_loaded.is_some() {
+                            let loaded = startup_loaded.get_or_insert(0);
+                            if is_new {
+                                *loaded += 1;
+                            }
+                            continue;
+                        }
+
                         if is_new {
                             log::debug!(
                                 "[Geyser] New/updated account: pubkey={}, slot={}",
@@ -522,3 +686,124 @@ impl Actor for GeyserSourceActor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasources::{priority_channel, PriorityLevel};
+    use crate::resources::AccountCache;
+    use crate::rpc::{EndpointConfig, RpcPool, RpcPoolConfig};
+    use crate::types::AccountUpdate;
+    use solana_sdk::pubkey::Pubkey;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Stand-in for StagingActor that records what reaches it
+    struct RecordingStaging;
+
+    impl Actor for RecordingStaging {
+        type Msg = StagingMessage;
+        type State = (Arc<AtomicUsize>, mpsc::UnboundedSender<usize>);
+        type Arguments = (Arc<AtomicUsize>, mpsc::UnboundedSender<usize>);
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            args: Self::Arguments,
+        ) -> Result<Self::State, Box<dyn Error + Send + Sync>> {
+            Ok(args)
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            (updates, initial_loads): &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            match message {
+                StagingMessage::AccountUpdate(_) => {
+                    updates.fetch_add(1, Ordering::SeqCst);
+                }
+                StagingMessage::InitialLoadComplete { count } => {
+                    let _ = initial_loads.send(count);
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_startup_snapshot_is_bulk_loaded() {
+        const STARTUP_ACCOUNTS: usize = 50_000;
+
+        let rpc = RpcPool::new(
+            vec![EndpointConfig::new("http://localhost:8899")],
+            RpcPoolConfig::default(),
+        )
+        .unwrap();
+        let cache = Arc::new(AccountCache::with_capacity(2 * STARTUP_ACCOUNTS as u64));
+        let resources = SharedResources::with_custom(Arc::new(rpc), cache.clone());
+
+        let updates = Arc::new(AtomicUsize::new(0));
+        let (load_tx, mut load_rx) = mpsc::unbounded_channel();
+        let (staging_ref, _) = Actor::spawn(None, RecordingStaging, (updates.clone(), load_tx))
+            .await
+            .unwrap();
+
+        let (tx, rx) = priority_channel(1000);
+        let (geyser_ref, _) = Actor::spawn(
+            None,
+            GeyserSourceActor,
+            (rx, resources, staging_ref.clone()),
+        )
+        .await
+        .unwrap();
+
+        for i in 0..STARTUP_ACCOUNTS as u64 {
+            let update = AccountUpdate::new(Pubkey::new_unique(), i.to_le_bytes().to_vec(), 1);
+            tx.send(
+                PluginEvent::AccountUpdate {
+                    update,
+                    is_startup: true,
+                },
+                PriorityLevel::Normal,
+            )
+            .await
+            .unwrap();
+        }
+        tx.send(PluginEvent::EndOfStartup, PriorityLevel::Normal)
+            .await
+            .unwrap();
+
+        // One summary message, no per-account messages
+        assert_eq!(load_rx.recv().await, Some(STARTUP_ACCOUNTS));
+        assert_eq!(updates.load(Ordering::SeqCst), 0);
+        cache.run_pending_tasks().await;
+        assert_eq!(cache.entry_count(), STARTUP_ACCOUNTS as u64);
+
+        // Live updates after startup are forwarded individually again
+        let update = AccountUpdate::new(Pubkey::new_unique(), vec![1, 2, 3], 2);
+        tx.send(
+            PluginEvent::AccountUpdate {
+                update,
+                is_startup: false,
+            },
+            PriorityLevel::Normal,
+        )
+        .await
+        .unwrap();
+        for _ in 0..100 {
+            if updates.load(Ordering::SeqCst) == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(updates.load(Ordering::SeqCst), 1);
+
+        geyser_ref.stop(None);
+        staging_ref.stop(None);
+    }
+}
@@ -1,7 +1,11 @@
 //! Message types for actor communication
 
+use crate::lanes::{LaneStatus, WorkerLane};
+use crate::thread_filter::ThreadFilter;
 use crate::types::AccountUpdate;
 use solana_sdk::{clock::Clock, pubkey::Pubkey};
+use std::collections::HashMap;
+use std::time::Instant;
 use tokio::sync::oneshot;
 
 // ============================================================================
@@ -17,9 +21,10 @@ pub enum RootMessage {
 // Datasource Supervisor Messages
 // ============================================================================
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum DatasourceMessage {
     AccountUpdate(AccountUpdate),
+    CollectMetrics(oneshot::Sender<ActorStats>),
     Shutdown,
 }
 
@@ -56,12 +61,29 @@ pub enum CompletionReason {
 pub enum StagingMessage {
     AccountUpdate(AccountUpdate),
     ClockTick(Clock),
+    /// Startup snapshot accounts were bulk-loaded into the cache; schedule
+    /// every cached thread in one pass
+    InitialLoadComplete {
+        count: usize,
+    },
     ThreadCompleted {
         thread_pubkey: Pubkey,
         reason: CompletionReason,
     },
     SetProcessorRef(ractor::ActorRef<ProcessorMessage>),
+    /// Replace the set of followed threads
+    SetThreadFilter(ThreadFilter),
+    /// Hold ready threads in their queues (`true`) or resume dispatching
+    /// them (`false`); replies with the previous setting
+    SetProcessingPaused(bool, oneshot::Sender<bool>),
+    /// The worker pool is saturated: hold incoming account updates, keeping
+    /// the newest per account, until `BackpressureStop`
+    BackpressureStart,
+    /// Workers freed up: process the held account updates and resume
+    BackpressureStop,
     QueryStatus(oneshot::Sender<StagingStatus>),
+    ListThreads(oneshot::Sender<Vec<TrackedThreadInfo>>),
+    CollectMetrics(oneshot::Sender<ActorStats>),
     Shutdown,
 }
 
@@ -72,6 +94,21 @@ pub struct StagingStatus {
     pub time_queue_size: usize,
     pub slot_queue_size: usize,
     pub epoch_queue_size: usize,
+    /// Thread updates ignored because the thread filter excluded them
+    pub filtered_updates: u64,
+    /// Whether dispatching is paused (see [`StagingMessage::SetProcessingPaused`])
+    pub processing_paused: bool,
+}
+
+/// A thread tracked by staging, see [`StagingMessage::ListThreads`]
+#[derive(Debug, Clone)]
+pub struct TrackedThreadInfo {
+    pub pubkey: Pubkey,
+    pub exec_count: u64,
+    pub paused: bool,
+    pub resume_at: Option<i64>,
+    /// Handed to the processor and not yet completed
+    pub queued: bool,
 }
 
 // ============================================================================
@@ -85,6 +122,7 @@ pub enum ProcessorMessage {
     CancelThread(Pubkey),
     WorkerCompleted(ExecutionResult),
     QueryStatus(oneshot::Sender<ProcessorStatus>),
+    CollectMetrics(oneshot::Sender<ActorStats>),
     Shutdown,
 }
 
@@ -93,15 +131,132 @@ pub struct ProcessorStatus {
     pub pending_queue_size: usize,
     pub active_workers: usize,
     pub available_permits: usize,
+    /// Threads skipped because they could not reimburse execution fees
+    pub underfunded_skips: u64,
+    /// Executions aborted by the fiber chain depth limit or cycle check
+    pub chain_limit_failures: u64,
+    /// Confirmed both-mode submissions where TPU accepted the transaction first
+    pub both_mode_tpu_wins: u64,
+    /// Confirmed both-mode submissions where RPC accepted the transaction first
+    pub both_mode_rpc_wins: u64,
+    /// Submissions whose priority fee was clamped to the fee ceiling
+    pub fee_clamped: u64,
+    /// Executions deferred because the fee ceiling was too low to land
+    pub fee_deferred: u64,
+    /// Duplicate executions dropped by the dedup window
+    pub dedup_hits: u64,
+    /// Executions refused because the cached thread predated its last
+    /// confirmed execution
+    pub replay_guard_hits: u64,
+    /// Packed transactions confirmed (`submission.batch_exec`)
+    pub batched_transactions: u64,
+    /// Average threads per packed transaction
+    pub threads_per_batch: f64,
+    /// Per-lane workers and saturation (`processor.lanes`)
+    pub lanes: Vec<LaneStatus>,
+    /// Most threads ever queued at once
+    pub queue_high_water: usize,
+    /// Threads shed from the full queue (`processor.max_queued_threads`)
+    pub queue_shed: u64,
+    /// Adaptive concurrency controller (`processor.adaptive_concurrency`)
+    pub concurrency: Option<crate::concurrency::ConcurrencyStatus>,
 }
 
 // ============================================================================
 // Worker Messages
 // ============================================================================
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum WorkerMessage {
     Cancel,
+    /// Finish the current execution, then reply
+    /// (see [`crate::load_balancer::LoadBalancer::drain`])
+    Drain(oneshot::Sender<Drained>),
+    CollectMetrics(oneshot::Sender<ActorStats>),
+}
+
+/// Reply to [`WorkerMessage::Drain`] once the worker's execution is over
+#[derive(Debug)]
+pub struct Drained;
+
+// ============================================================================
+// Metrics Collection
+// ============================================================================
+
+/// One actor's local stats, the reply to a `CollectMetrics` message
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActorStats {
+    pub actor_name: String,
+    pub messages_processed: u64,
+    /// Messages whose handling returned an error
+    pub errors: u64,
+    pub uptime_secs: u64,
+    /// Actor-specific values, such as queue sizes
+    pub custom: HashMap<String, f64>,
+}
+
+/// Stats of every actor that answered one collection round
+/// (see [`crate::actors::observability::collect_system_stats`])
+#[derive(Debug, Clone, Default)]
+pub struct SystemStats {
+    pub actors: Vec<ActorStats>,
+}
+
+impl SystemStats {
+    pub fn get(&self, actor_name: &str) -> Option<&ActorStats> {
+        self.actors.iter().find(|a| a.actor_name == actor_name)
+    }
+
+    pub fn messages_processed(&self) -> u64 {
+        self.actors.iter().map(|a| a.messages_processed).sum()
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.actors.iter().map(|a| a.errors).sum()
+    }
+}
+
+/// Message counts an actor keeps to answer `CollectMetrics`
+#[derive(Debug)]
+pub(crate) struct ActorCounters {
+    started_at: Instant,
+    messages_processed: u64,
+    errors: u64,
+}
+
+impl ActorCounters {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            messages_processed: 0,
+            errors: 0,
+        }
+    }
+
+    /// Count a handled message, and whether handling it failed
+    pub fn record(&mut self, failed: bool) {
+        self.messages_processed += 1;
+        if failed {
+            self.errors += 1;
+        }
+    }
+
+    pub fn stats<'a>(
+        &self,
+        actor_name: &str,
+        custom: impl IntoIterator<Item = (&'a str, f64)>,
+    ) -> ActorStats {
+        ActorStats {
+            actor_name: actor_name.to_string(),
+            messages_processed: self.messages_processed,
+            errors: self.errors,
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            custom: custom
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
 }
 
 // ============================================================================
@@ -116,6 +271,8 @@ pub struct ReadyThread {
     pub exec_count: u64,
     pub is_overdue: bool,
     pub overdue_seconds: i64,
+    /// Worker lane, from the trigger type recorded in the cache
+    pub lane: WorkerLane,
 }
 
 /// Result of worker execution (sent from Worker to Processor)
@@ -6,7 +6,7 @@ pub mod root;
 pub mod staging;
 pub mod worker;
 
-pub use datasource::{DatasourceSupervisor, GeyserSourceActor, RpcSourceActor};
+pub use datasource::{DatasourceSupervisor, GeyserSourceActor, RpcSource, RpcSourceActor};
 pub use messages::*;
 pub use observability::ObservabilityActor;
 pub use processor::ProcessorFactory;
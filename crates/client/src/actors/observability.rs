@@ -1,14 +1,58 @@
 //! Observability Actor
 //!
 //! Wraps the loa-core agent and runs it within the antegen actor hierarchy.
+//! With `observability.otlp` set, it also owns the OpenTelemetry metrics
+//! pipeline (see [`crate::metrics`]) and refreshes the submission latency
+//! percentile gauges once per export interval.
+//!
+//! When the TPU client is running, it logs a TPU send summary every
+//! [`TPU_HEALTH_INTERVAL`]: connection errors from the scheduler, per-leader
+//! outcomes, and sends made while no upcoming leader was known.
+//!
+//! With `observability.otlp` set, it also asks every actor for its
+//! [`ActorStats`] once per export interval (see [`collect_system_stats`]) and
+//! exports them as the `antegen.actor.*` gauges.
 
+use crate::actors::messages::{
+    ActorStats, DatasourceMessage, ProcessorMessage, StagingMessage, SystemStats, WorkerMessage,
+};
+use crate::actors::worker::WORKER_ACTOR_NAME;
 use crate::config::ObservabilityConfig;
+use crate::load_balancer::LoadBalancer;
+use crate::tpu::{SubmissionMetrics, TpuClient, TpuSendSummary};
 use loa_core::Agent;
+use ractor::concurrency::JoinHandle;
 use ractor::{Actor, ActorProcessingErr, ActorRef};
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// How often the TPU send summary is logged
+pub const TPU_HEALTH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long an actor has to answer `CollectMetrics`
+pub const COLLECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Actors asked for their stats in each collection round
+#[derive(Clone)]
+pub struct CollectTargets {
+    pub staging: ActorRef<StagingMessage>,
+    pub processor: ActorRef<ProcessorMessage>,
+    pub datasource: ActorRef<DatasourceMessage>,
+    /// Source of the in-flight workers
+    pub load_balancer: Arc<LoadBalancer>,
+}
 
 /// Messages for the ObservabilityActor
 pub enum ObservabilityMessage {
+    /// Log the TPU send summary since the last report
+    ReportTpuHealth,
+    /// Export P50/P95/P99 of recent submission latencies
+    UpdateLatencyGauges,
+    /// Ask every actor for its stats and export them
+    CollectMetrics,
     Shutdown,
 }
 
@@ -17,20 +61,41 @@ pub struct ObservabilityActor;
 pub struct ObservabilityState {
     #[allow(dead_code)] // Agent kept alive to run loa-core's actor tree
     _agent: Agent,
+    tpu_client: Option<Arc<TpuClient>>,
+    tpu_report: Option<JoinHandle<()>>,
+    submission_metrics: Arc<SubmissionMetrics>,
+    latency_report: Option<JoinHandle<()>>,
+    collect_targets: CollectTargets,
+    collect_report: Option<JoinHandle<()>>,
+    /// Totals at the previous report, to log deltas
+    last_summary: TpuSendSummary,
+    last_no_leader_sends: u64,
 }
 
 impl Actor for ObservabilityActor {
     type Msg = ObservabilityMessage;
     type State = ObservabilityState;
-    type Arguments = ObservabilityConfig;
+    type Arguments = (
+        ObservabilityConfig,
+        Option<Arc<TpuClient>>,
+        Arc<SubmissionMetrics>,
+        CollectTargets,
+    );
 
     async fn pre_start(
         &self,
-        _myself: ActorRef<Self::Msg>,
-        config: Self::Arguments,
+        myself: ActorRef<Self::Msg>,
+        (config, tpu_client, submission_metrics, collect_targets): Self::Arguments,
     ) -> Result<Self::State, Box<dyn Error + Send + Sync>> {
         log::info!("ObservabilityActor starting...");
 
+        // Metrics export doesn't depend on the loa-core agent
+        if let Some(otlp) = &config.otlp {
+            if let Err(e) = crate::metrics::init(otlp) {
+                log::warn!("Failed to start OTLP metrics export: {}", e);
+            }
+        }
+
         // Expand the storage path
         let storage_path = shellexpand::tilde(&config.storage_path).to_string();
         log::debug!("Loa storage path: {}", storage_path);
@@ -45,16 +110,65 @@ impl Actor for ObservabilityActor {
 
         log::info!("Loa observability agent started");
 
-        Ok(ObservabilityState { _agent: agent })
+        let tpu_report = tpu_client.as_ref().map(|_| {
+            myself.send_interval(TPU_HEALTH_INTERVAL, || {
+                ObservabilityMessage::ReportTpuHealth
+            })
+        });
+
+        let latency_report = config.otlp.as_ref().map(|otlp| {
+            myself.send_interval(Duration::from_secs(otlp.export_interval_secs), || {
+                ObservabilityMessage::UpdateLatencyGauges
+            })
+        });
+
+        let collect_report = config.otlp.as_ref().map(|otlp| {
+            myself.send_interval(Duration::from_secs(otlp.export_interval_secs), || {
+                ObservabilityMessage::CollectMetrics
+            })
+        });
+
+        Ok(ObservabilityState {
+            _agent: agent,
+            tpu_client,
+            tpu_report,
+            submission_metrics,
+            latency_report,
+            collect_targets,
+            collect_report,
+            last_summary: TpuSendSummary::default(),
+            last_no_leader_sends: 0,
+        })
     }
 
     async fn handle(
         &self,
         myself: ActorRef<Self::Msg>,
         message: Self::Msg,
-        _state: &mut Self::State,
+        state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
         match message {
+            ObservabilityMessage::ReportTpuHealth => {
+                report_tpu_health(state);
+                Ok(())
+            }
+            ObservabilityMessage::UpdateLatencyGauges => {
+                update_latency_gauges(&state.submission_metrics);
+                Ok(())
+            }
+            ObservabilityMessage::CollectMetrics => {
+                let stats = collect_system_stats(&state.collect_targets).await;
+                log::debug!(
+                    "Collected stats from {} actors: {} messages, {} errors",
+                    stats.actors.len(),
+                    stats.messages_processed(),
+                    stats.errors()
+                );
+                for actor in &stats.actors {
+                    crate::metrics::record_actor_stats(actor);
+                }
+                Ok(())
+            }
             ObservabilityMessage::Shutdown => {
                 log::info!("ObservabilityActor received shutdown signal");
                 myself.stop(Some("Shutdown requested".to_string()));
@@ -66,9 +180,289 @@ impl Actor for ObservabilityActor {
     async fn post_stop(
         &self,
         _myself: ActorRef<Self::Msg>,
-        _state: &mut Self::State,
+        state: &mut Self::State,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for handle in [
+            state.tpu_report.take(),
+            state.latency_report.take(),
+            state.collect_report.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            handle.abort();
+        }
+        crate::metrics::shutdown();
         log::info!("ObservabilityActor stopped");
         Ok(())
     }
 }
+
+/// Ask `actor` for its stats, giving up after [`COLLECT_TIMEOUT`]
+async fn ask_stats<M: ractor::Message>(
+    actor: &ActorRef<M>,
+    message: impl FnOnce(oneshot::Sender<ActorStats>) -> M,
+) -> Option<ActorStats> {
+    let (tx, rx) = oneshot::channel();
+    actor.send_message(message(tx)).ok()?;
+    tokio::time::timeout(COLLECT_TIMEOUT, rx).await.ok()?.ok()
+}
+
+/// Send `CollectMetrics` to every actor and gather the replies
+///
+/// Workers come and go with each execution, so their replies are summed into
+/// one `worker` entry with the number of workers as its `active` stat. An
+/// actor that does not answer in time is left out of the round.
+pub async fn collect_system_stats(targets: &CollectTargets) -> SystemStats {
+    let (staging, processor, datasource) = tokio::join!(
+        ask_stats(&targets.staging, StagingMessage::CollectMetrics),
+        ask_stats(&targets.processor, ProcessorMessage::CollectMetrics),
+        ask_stats(&targets.datasource, DatasourceMessage::CollectMetrics),
+    );
+    let workers = futures::future::join_all(
+        targets
+            .load_balancer
+            .workers()
+            .await
+            .iter()
+            .map(|worker| ask_stats(worker, WorkerMessage::CollectMetrics)),
+    )
+    .await;
+
+    let mut actors: Vec<ActorStats> = [staging, processor, datasource]
+        .into_iter()
+        .flatten()
+        .collect();
+    actors.push(combine_worker_stats(workers.into_iter().flatten()));
+    SystemStats { actors }
+}
+
+/// Sum the stats of individual workers into one entry
+fn combine_worker_stats(workers: impl Iterator<Item = ActorStats>) -> ActorStats {
+    let mut combined = ActorStats {
+        actor_name: WORKER_ACTOR_NAME.to_string(),
+        custom: HashMap::from([("active".to_string(), 0.0)]),
+        ..Default::default()
+    };
+    for worker in workers {
+        combined.messages_processed += worker.messages_processed;
+        combined.errors += worker.errors;
+        combined.uptime_secs = combined.uptime_secs.max(worker.uptime_secs);
+        *combined.custom.entry("active".to_string()).or_default() += 1.0;
+        for (stat, value) in worker.custom {
+            *combined.custom.entry(stat).or_default() += value;
+        }
+    }
+    combined
+}
+
+/// Export submission latency percentiles, once any submission has landed
+fn update_latency_gauges(submission_metrics: &SubmissionMetrics) {
+    if submission_metrics.latency_samples() == 0 {
+        return;
+    }
+    let percentile = |p| Duration::from_micros(submission_metrics.percentile(p));
+    crate::metrics::record_submission_latency_percentiles(
+        percentile(50.0),
+        percentile(95.0),
+        percentile(99.0),
+    );
+}
+
+/// Log TPU send activity since the previous report
+fn report_tpu_health(state: &mut ObservabilityState) {
+    let Some(tpu_client) = &state.tpu_client else {
+        return;
+    };
+
+    let summary = tpu_client.send_summary();
+    let last = std::mem::replace(&mut state.last_summary, summary);
+    log::info!(
+        "TPU: {} sent, {} connect errors, {} connection errors in the last {:?}{}",
+        summary.sent.saturating_sub(last.sent),
+        summary.connect_errors.saturating_sub(last.connect_errors),
+        summary
+            .connection_errors
+            .saturating_sub(last.connection_errors),
+        TPU_HEALTH_INTERVAL,
+        if tpu_client.is_enabled() {
+            ""
+        } else {
+            " (auto-disabled, using RPC only)"
+        }
+    );
+
+    let no_leader_sends = tpu_client.no_leader_sends();
+    let missed = no_leader_sends.saturating_sub(state.last_no_leader_sends);
+    state.last_no_leader_sends = no_leader_sends;
+    if missed > 0 {
+        log::warn!(
+            "TPU: {} sends with no upcoming leader known (leader schedule gap)",
+            missed
+        );
+    }
+
+    for leader in tpu_client.leader_health() {
+        log::debug!(
+            "TPU leader {}: attempted={} succeeded={} failed={}",
+            leader.address,
+            leader.attempted,
+            leader.succeeded,
+            leader.failed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::datasource::DATASOURCE_ACTOR_NAME;
+    use crate::actors::processor::PROCESSOR_ACTOR_NAME;
+    use crate::actors::staging::STAGING_ACTOR_NAME;
+    use crate::actors::worker::tests::{new_thread, Collector};
+    use crate::actors::worker::WorkerArgs;
+    use crate::actors::{DatasourceSupervisor, ProcessorFactory, StagingActor, WorkerActor};
+    use crate::config::{ClientConfig, EndpointRole, FeeCeilingConfig, SubmissionMode};
+    use crate::executor::ExecutorLogic;
+    use crate::load_balancer::LoadBalancerConfig;
+    use crate::resources::{AccountCache, SharedResources};
+    use crate::rpc::mock::spawn_mock_rpc;
+    use crate::rpc::RpcPool;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use tokio::sync::{broadcast, mpsc, Semaphore};
+
+    const RPC_ERROR: &str =
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#;
+
+    #[tokio::test]
+    async fn test_collect_metrics_from_every_actor() {
+        let url = spawn_mock_rpc(RPC_ERROR).await;
+        let resources = SharedResources::with_custom(
+            Arc::new(RpcPool::with_url(url).unwrap()),
+            Arc::new(AccountCache::new()),
+        );
+        let mut config = ClientConfig::default();
+        config.rpc.endpoints[0].role = EndpointRole::Submission;
+        config.datasources.clock_poll_interval_ms = 0;
+        config.processor.prefetch_lookahead_secs = 0;
+        // A never-executed thread keeps the worker waiting this long
+        let load_balancer = Arc::new(LoadBalancer::new(
+            Pubkey::new_unique(),
+            LoadBalancerConfig {
+                thread_process_delay: 1,
+                ..Default::default()
+            },
+        ));
+
+        let (_eviction_tx, eviction_rx) = mpsc::unbounded_channel();
+        let (staging, _) = Actor::spawn(
+            None,
+            StagingActor,
+            (
+                config.clone(),
+                resources.clone(),
+                load_balancer.clone(),
+                eviction_rx,
+            ),
+        )
+        .await
+        .unwrap();
+        let executor = ExecutorLogic::new(Arc::new(Keypair::new()), resources.clone(), false);
+        let (processor, _) = Actor::spawn(
+            None,
+            ProcessorFactory,
+            (
+                config.clone(),
+                resources.clone(),
+                staging.clone(),
+                executor.clone(),
+                load_balancer.clone(),
+            ),
+        )
+        .await
+        .unwrap();
+        let (datasource, _) = Actor::spawn(
+            None,
+            DatasourceSupervisor,
+            (config, resources.clone(), staging.clone(), None),
+        )
+        .await
+        .unwrap();
+
+        let (results_tx, _results) = mpsc::unbounded_channel();
+        let (collector, _) = Actor::spawn(None, Collector, results_tx).await.unwrap();
+        let (_clock_tx, clock_rx) = broadcast::channel(1);
+        let (worker, _) = Actor::spawn(
+            None,
+            WorkerActor,
+            WorkerArgs {
+                thread_pubkey: Pubkey::new_unique(),
+                thread: new_thread(Pubkey::default()),
+                is_overdue: false,
+                overdue_seconds: 0,
+                permit: Arc::new(Semaphore::new(1)).acquire_owned().await.unwrap(),
+                processor_ref: collector.clone(),
+                clock_rx,
+                resources,
+                executor,
+                load_balancer: load_balancer.clone(),
+                fiber_execution_timeout: Duration::from_secs(1),
+                simulate_before_send: false,
+                submission_mode: SubmissionMode::default(),
+                fee_ceiling: FeeCeilingConfig::default(),
+                broadcast: false,
+                batch_exec: false,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Handled messages are counted, the one being answered is not yet
+        let (tx, rx) = oneshot::channel();
+        staging
+            .send_message(StagingMessage::QueryStatus(tx))
+            .unwrap();
+        rx.await.unwrap();
+
+        let targets = CollectTargets {
+            staging: staging.clone(),
+            processor: processor.clone(),
+            datasource: datasource.clone(),
+            load_balancer,
+        };
+        let stats = collect_system_stats(&targets).await;
+        assert_eq!(stats.actors.len(), 4);
+
+        let staging_stats = stats.get(STAGING_ACTOR_NAME).unwrap();
+        assert_eq!(staging_stats.messages_processed, 1);
+        assert_eq!(staging_stats.errors, 0);
+        assert_eq!(staging_stats.custom["tracked_threads"], 0.0);
+
+        let processor_stats = stats.get(PROCESSOR_ACTOR_NAME).unwrap();
+        assert_eq!(processor_stats.messages_processed, 0);
+        assert!(processor_stats.custom["available_permits"] > 0.0);
+
+        let datasource_stats = stats.get(DATASOURCE_ACTOR_NAME).unwrap();
+        assert_eq!(datasource_stats.custom["rpc_sources"], 0.0);
+
+        let worker_stats = stats.get(WORKER_ACTOR_NAME).unwrap();
+        assert_eq!(worker_stats.custom["active"], 1.0);
+        assert_eq!(worker_stats.custom["cancelled"], 0.0);
+
+        // The collection round itself is counted by the next one
+        let stats = collect_system_stats(&targets).await;
+        assert_eq!(stats.get(STAGING_ACTOR_NAME).unwrap().messages_processed, 2);
+        assert_eq!(stats.messages_processed(), 2 + 1 + 1 + 1);
+
+        for actor in [
+            worker.get_cell(),
+            datasource.get_cell(),
+            processor.get_cell(),
+        ] {
+            actor.stop(None);
+        }
+        staging.stop(None);
+        collector.stop(None);
+    }
+}
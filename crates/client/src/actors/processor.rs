@@ -1,44 +1,62 @@
 //! Processor Factory Actor
 //!
-//! The ProcessorFactory maintains a FIFO queue of threads ready for execution.
-//! It receives ReadyThread messages from the StagingActor (pubkey + metadata only),
-//! fetches full Thread data from cache, and spawns WorkerActor instances up to
-//! the configured concurrency limit.
+//! The ProcessorFactory maintains FIFO queues of threads ready for execution,
+//! one per worker lane (see [`crate::lanes`]). It receives ReadyThread messages
+//! from the StagingActor (pubkey + metadata only), fetches full Thread data from
+//! cache, and spawns WorkerActor instances up to each lane's concurrency limit.
 //!
 //! Key design: ProcessorFactory fetches Thread data from cache on-demand, not upfront.
 //! The cache is the single source of truth for account data.
 
 use crate::actors::messages::{
-    CompletionReason, ExecutionResult, ProcessorMessage, ProcessorStatus, ReadyThread,
-    StagingMessage,
+    ActorCounters, CompletionReason, ExecutionResult, ProcessorMessage, ProcessorStatus,
+    ReadyThread, StagingMessage,
 };
+use crate::actors::worker::{is_chain_limit_error, DUPLICATE_EXECUTION_ERROR, UNDERFUNDED_ERROR};
 use crate::actors::WorkerActor;
-use crate::config::ClientConfig;
+use crate::cadence::SubmissionTimeout;
+use crate::concurrency::{AimdController, ExecutionSignal};
+use crate::config::{
+    ClientConfig, FeeCeilingConfig, LeaderAlignmentConfig, SubmissionConfig, SubmissionMode,
+};
 use crate::executor::ExecutorLogic;
+use crate::fees::FEE_CEILING_DEFERRED_ERROR;
+use crate::lanes::WorkerLanes;
 use crate::load_balancer::LoadBalancer;
-use crate::resources::SharedResources;
+use crate::metrics;
+use crate::resources::{AccountCache, SharedResources};
+use crate::types::AccountUpdate;
 use log::warn;
 use ractor::{Actor, ActorProcessingErr, ActorRef};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+/// Name the ProcessorFactory reports its stats under
+pub const PROCESSOR_ACTOR_NAME: &str = "processor";
 
 #[derive(Default)]
 pub struct ProcessorFactory;
 
 pub struct ProcessorState {
-    // FIFO queue of ready threads (pubkey + metadata only)
-    // Full Thread data is fetched from cache when spawning worker
-    pending_queue: VecDeque<ReadyThread>,
+    // Per-lane FIFO queues of ready threads (pubkey + metadata only) and
+    // worker accounting. Full Thread data is fetched from cache when spawning
+    lanes: WorkerLanes,
 
     // Worker tracking
     active_workers: HashMap<Pubkey, ActorRef<crate::actors::messages::WorkerMessage>>,
 
-    // Concurrency control
+    // Concurrency control, sized to the total of all lanes
     task_semaphore: Arc<Semaphore>,
-    available_permits: usize,
+
+    // Adaptive concurrency (`processor.adaptive_concurrency`) and when each
+    // running worker was spawned
+    concurrency: Option<AimdController>,
+    worker_started: HashMap<Pubkey, Instant>,
 
     // Communication
     staging_ref: ActorRef<StagingMessage>,
@@ -50,6 +68,41 @@ pub struct ProcessorState {
     // Executor and load balancer
     executor: ExecutorLogic,
     load_balancer: Arc<LoadBalancer>,
+
+    // Per-attempt bound on fiber simulation/submission
+    fiber_execution_timeout: Duration,
+
+    // Longest confirmation wait, and the share of a thread's trigger period
+    // waited instead when shorter
+    submission_timeout: Duration,
+    submission_timeout_factor: f64,
+
+    // Preflight-simulate transactions before TPU submission
+    simulate_before_send: bool,
+
+    // TPU-first or concurrent TPU + RPC submission
+    submission_mode: SubmissionMode,
+
+    // How long TPU sends may wait for a warm leader, per trigger kind
+    leader_alignment: Option<LeaderAlignmentConfig>,
+
+    // Priority fee cap per execution
+    fee_ceiling: FeeCeilingConfig,
+
+    // Which threads broadcast their transactions to every submission path
+    submission: SubmissionConfig,
+
+    // Threads skipped for being unable to reimburse fees
+    underfunded_skips: u64,
+
+    // Executions aborted by the on-chain fiber chain depth or cycle check
+    chain_limit_failures: u64,
+
+    // Stops the fiber prefetch task
+    prefetch_cancel: CancellationToken,
+
+    // Message counts reported to CollectMetrics
+    counters: ActorCounters,
 }
 
 impl Actor for ProcessorFactory {
@@ -68,28 +121,72 @@ impl Actor for ProcessorFactory {
         _myself: ActorRef<Self::Msg>,
         (config, resources, staging_ref, executor, load_balancer): Self::Arguments,
     ) -> Result<Self::State, Box<dyn Error + Send + Sync>> {
-        let max_concurrent_threads = config.processor.max_concurrent_threads;
-        log::debug!(
-            "ProcessorFactory starting with max {} concurrent threads",
-            max_concurrent_threads
-        );
+        let lanes = WorkerLanes::from_config(&config.processor);
+        match &config.processor.lanes {
+            Some(lanes) => log::debug!(
+                "ProcessorFactory starting with {} time and {} account workers (borrow_idle={})",
+                lanes.time,
+                lanes.account,
+                lanes.borrow_idle
+            ),
+            None => log::debug!(
+                "ProcessorFactory starting with max {} concurrent threads",
+                config.processor.max_concurrent_threads
+            ),
+        }
 
         // Create semaphore for concurrency control
-        let task_semaphore = Arc::new(Semaphore::new(max_concurrent_threads));
+        let task_semaphore = Arc::new(Semaphore::new(lanes.capacity()));
+        let concurrency = config
+            .processor
+            .adaptive_concurrency
+            .as_ref()
+            .map(|adaptive| AimdController::new(adaptive, lanes.capacity()));
+        if let Some(concurrency) = &concurrency {
+            log::debug!(
+                "Adaptive concurrency between {} and {} workers",
+                concurrency.status().min,
+                concurrency.status().max
+            );
+        }
 
         // Create broadcast channel for clock distribution
         let (clock_tx, _clock_rx) = broadcast::channel(10);
 
+        let prefetch_cancel = CancellationToken::new();
+        if config.processor.prefetch_lookahead_secs > 0 {
+            spawn_prefetch(
+                resources.clone(),
+                config.processor.prefetch_lookahead_secs,
+                prefetch_cancel.clone(),
+            );
+        }
+
         Ok(ProcessorState {
-            pending_queue: VecDeque::new(),
+            lanes,
             active_workers: HashMap::new(),
             task_semaphore,
-            available_permits: max_concurrent_threads,
+            concurrency,
+            worker_started: HashMap::new(),
             staging_ref,
             clock_tx,
             resources,
             executor,
             load_balancer,
+            fiber_execution_timeout: Duration::from_millis(
+                config.processor.fiber_execution_timeout_ms,
+            ),
+            submission_timeout: Duration::from_millis(config.processor.submission_timeout_ms),
+            submission_timeout_factor: config.processor.submission_timeout_factor,
+            simulate_before_send: config.processor.simulate_before_send,
+            submission_mode: config.tpu.submission_mode,
+            leader_alignment: config.tpu.leader_alignment.clone(),
+            fee_ceiling: config.processor.fee_ceiling,
+            submission: config.submission.clone(),
+            underfunded_skips: 0,
+            chain_limit_failures: 0,
+            prefetch_cancel,
+            counters: ActorCounters::new(),
         })
     }
 
@@ -98,18 +195,60 @@ impl Actor for ProcessorFactory {
         myself: ActorRef<Self::Msg>,
         message: Self::Msg,
         state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        let result = self.handle_message(myself, message, state).await;
+        state.counters.record(result.is_err());
+        result
+    }
+
+    async fn post_stop(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        state: &mut Self::State,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        state.prefetch_cancel.cancel();
+        log::info!(
+            "ProcessorFactory stopped. {} items in queue, {} active workers",
+            state.lanes.queued(),
+            state.active_workers.len()
+        );
+        Ok(())
+    }
+}
+
+impl ProcessorFactory {
+    /// Dispatch one message; `handle` counts it for `CollectMetrics`
+    async fn handle_message(
+        &self,
+        myself: ActorRef<ProcessorMessage>,
+        message: ProcessorMessage,
+        state: &mut ProcessorState,
     ) -> Result<(), ActorProcessingErr> {
         match message {
             ProcessorMessage::ProcessReady(ready_thread) => {
                 log::debug!(
                     "Received thread {} for processing (queue_size={})",
                     ready_thread.thread_pubkey,
-                    state.pending_queue.len()
+                    state.lanes.queued()
                 );
 
-                // Add to FIFO queue (pubkey + metadata only)
+                // Add to its lane's FIFO queue (pubkey + metadata only)
                 // Full Thread data will be fetched from cache when spawning worker
-                state.pending_queue.push_back(ready_thread);
+                if let Some(shed) = state.lanes.push(ready_thread) {
+                    // Staging re-queues it and offers it again on a later tick
+                    warn!(
+                        "Processor queue full ({} threads), shedding thread {} ({}s overdue)",
+                        state.lanes.queued(),
+                        shed.thread_pubkey,
+                        shed.overdue_seconds
+                    );
+                    let _ = state
+                        .staging_ref
+                        .send_message(StagingMessage::ThreadCompleted {
+                            thread_pubkey: shed.thread_pubkey,
+                            reason: CompletionReason::Skipped,
+                        });
+                }
 
                 // Try to spawn worker if capacity available
                 self.try_spawn_next_worker(myself, state).await?;
@@ -117,10 +256,8 @@ impl Actor for ProcessorFactory {
                 Ok(())
             }
             ProcessorMessage::CancelThread(thread_pubkey) => {
-                // Remove from pending queue if present
-                state
-                    .pending_queue
-                    .retain(|t| t.thread_pubkey != thread_pubkey);
+                // Remove from pending queues if present
+                state.lanes.remove(&thread_pubkey);
 
                 // Cancel active worker if exists
                 if let Some(worker_ref) = state.active_workers.get(&thread_pubkey) {
@@ -144,11 +281,15 @@ impl Actor for ProcessorFactory {
                     worker_ref.stop(None);
                 }
 
-                // Increment available permits
-                state.available_permits += 1;
+                // Release the worker in its lane
+                state.lanes.finish(&result.thread_pubkey);
+                let elapsed = state
+                    .worker_started
+                    .remove(&result.thread_pubkey)
+                    .map(|started| started.elapsed());
 
                 // Handle result
-                self.handle_execution_result(state, result).await?;
+                self.handle_execution_result(state, result, elapsed).await?;
 
                 // Try to spawn next worker from queue
                 self.try_spawn_next_worker(myself, state).await?;
@@ -157,13 +298,55 @@ impl Actor for ProcessorFactory {
             }
             ProcessorMessage::QueryStatus(tx) => {
                 let status = ProcessorStatus {
-                    pending_queue_size: state.pending_queue.len(),
+                    pending_queue_size: state.lanes.queued(),
                     active_workers: state.active_workers.len(),
-                    available_permits: state.available_permits,
+                    available_permits: state.lanes.available(),
+                    underfunded_skips: state.underfunded_skips,
+                    chain_limit_failures: state.chain_limit_failures,
+                    both_mode_tpu_wins: state.resources.submission_metrics.tpu_wins(),
+                    both_mode_rpc_wins: state.resources.submission_metrics.rpc_wins(),
+                    fee_clamped: state.resources.fee_metrics.clamped(),
+                    fee_deferred: state.resources.fee_metrics.deferred(),
+                    dedup_hits: state.resources.submission_dedup.hits(),
+                    replay_guard_hits: state.resources.confirmed_executions.hits(),
+                    batched_transactions: state.resources.exec_batcher.metrics().transactions(),
+                    threads_per_batch: state
+                        .resources
+                        .exec_batcher
+                        .metrics()
+                        .threads_per_transaction(),
+                    lanes: state.lanes.status(),
+                    queue_high_water: state.lanes.high_water(),
+                    queue_shed: state.lanes.shed(),
+                    concurrency: state.concurrency.as_ref().map(|c| c.status()),
                 };
                 let _ = tx.send(status);
                 Ok(())
             }
+            ProcessorMessage::CollectMetrics(tx) => {
+                let stats = state.counters.stats(
+                    PROCESSOR_ACTOR_NAME,
+                    [
+                        ("pending_queue_size", state.lanes.queued() as f64),
+                        ("active_workers", state.active_workers.len() as f64),
+                        ("available_permits", state.lanes.available() as f64),
+                        ("underfunded_skips", state.underfunded_skips as f64),
+                        ("chain_limit_failures", state.chain_limit_failures as f64),
+                        ("queue_high_water", state.lanes.high_water() as f64),
+                        ("queue_shed", state.lanes.shed() as f64),
+                        (
+                            "concurrency_limit",
+                            state
+                                .concurrency
+                                .as_ref()
+                                .map_or(state.lanes.capacity(), |c| c.limit())
+                                as f64,
+                        ),
+                    ],
+                );
+                let _ = tx.send(stats);
+                Ok(())
+            }
             ProcessorMessage::Shutdown => {
                 log::info!("ProcessorFactory shutting down...");
                 Err(From::from("Shutdown signal received"))
@@ -171,61 +354,87 @@ impl Actor for ProcessorFactory {
         }
     }
 
-    async fn post_stop(
-        &self,
-        _myself: ActorRef<Self::Msg>,
-        state: &mut Self::State,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        log::info!(
-            "ProcessorFactory stopped. {} items in queue, {} active workers",
-            state.pending_queue.len(),
-            state.active_workers.len()
-        );
-        Ok(())
+    /// Fetch `pubkeys` into the account cache ahead of execution
+    ///
+    /// Accounts already cached are skipped, and fetch failures are left for
+    /// execution to retry. Returns how many accounts were fetched.
+    pub async fn prefetch_accounts(resources: &SharedResources, pubkeys: &[Pubkey]) -> usize {
+        let mut fetched = 0;
+        for pubkey in pubkeys {
+            if resources.cache.get(pubkey).await.is_some() {
+                continue;
+            }
+            match resources.rpc_client.get_account_with_slot(pubkey).await {
+                Ok((account, slot)) => {
+                    resources
+                        .cache
+                        .put_if_newer(*pubkey, account.data, slot)
+                        .await;
+                    fetched += 1;
+                }
+                Err(e) => log::debug!("Prefetch of {} failed: {}", pubkey, e),
+            }
+        }
+        fetched
     }
-}
 
-impl ProcessorFactory {
-    /// Try to spawn next worker from queue if capacity available
+    /// Spawn workers for queued threads while their lanes have capacity
     ///
-    /// Fetches Thread data from cache before spawning worker.
-    /// If cache miss, skips the thread (will be re-queued on next update).
+    /// Threads that already have an active worker stay queued until it
+    /// finishes, and none start while the adaptive concurrency limit is
+    /// reached. Nothing is spawned once shutdown has started draining.
     async fn try_spawn_next_worker(
         &self,
         myself: ActorRef<ProcessorMessage>,
         state: &mut ProcessorState,
     ) -> Result<(), ActorProcessingErr> {
-        use anchor_lang::AccountDeserialize;
-        use antegen_thread_program::state::Thread;
-
-        // Check if we have capacity
-        if state.available_permits == 0 {
-            log::debug!("No available permits, cannot spawn worker");
+        // Shutdown is waiting for in-flight workers; start no new ones
+        if state.load_balancer.is_draining() {
+            log::debug!(
+                "Draining for shutdown, leaving {} threads queued",
+                state.lanes.queued()
+            );
             return Ok(());
         }
 
-        // Check if queue has work
-        let Some(ready_thread) = state.pending_queue.pop_front() else {
-            return Ok(());
-        };
-
-        // Guard against duplicate active workers
-        if state
-            .active_workers
-            .contains_key(&ready_thread.thread_pubkey)
+        while state
+            .concurrency
+            .as_ref()
+            .is_none_or(|c| state.active_workers.len() < c.limit())
         {
-            log::debug!(
-                "Thread {} already has active worker, re-queuing",
-                ready_thread.thread_pubkey
-            );
-            state.pending_queue.push_back(ready_thread);
-            return Ok(());
+            let Some(ready_thread) = state.lanes.next_ready() else {
+                break;
+            };
+            let thread_pubkey = ready_thread.thread_pubkey;
+            if !self
+                .spawn_worker(myself.clone(), state, ready_thread)
+                .await?
+            {
+                state.lanes.finish(&thread_pubkey);
+            }
         }
+        Ok(())
+    }
+
+    /// Spawn a worker for a thread whose lane has claimed a worker for it
+    ///
+    /// Fetches Thread data from cache before spawning worker.
+    /// If cache miss, skips the thread (will be re-queued on next update).
+    /// Returns false if no worker was spawned.
+    async fn spawn_worker(
+        &self,
+        myself: ActorRef<ProcessorMessage>,
+        state: &mut ProcessorState,
+        ready_thread: ReadyThread,
+    ) -> Result<bool, ActorProcessingErr> {
+        use anchor_lang::AccountDeserialize;
+        use antegen_thread_program::state::Thread;
 
         log::debug!(
-            "Spawning worker for thread {} (queue_size={}, active={})",
+            "Spawning worker for thread {} in {} lane (queue_size={}, active={})",
             ready_thread.thread_pubkey,
-            state.pending_queue.len(),
+            ready_thread.lane,
+            state.lanes.queued(),
             state.active_workers.len()
         );
 
@@ -251,7 +460,35 @@ impl ProcessorFactory {
                                     reason: CompletionReason::Executed,
                                 })
                                 .ok();
-                            return Ok(());
+                            return Ok(false);
+                        }
+
+                        // Already executed, but the update carrying it hasn't
+                        // arrived and this copy was read before it landed
+                        if let Some(confirmed) = state.resources.confirmed_executions.predates(
+                            &ready_thread.thread_pubkey,
+                            cached.slot,
+                            thread.exec_count,
+                        ) {
+                            log::info!(
+                                "Thread {} cached at slot {} predates its execution confirmed at slot {}, refreshing",
+                                ready_thread.thread_pubkey,
+                                cached.slot,
+                                confirmed.slot
+                            );
+                            state
+                                .staging_ref
+                                .send_message(StagingMessage::ThreadCompleted {
+                                    thread_pubkey: ready_thread.thread_pubkey,
+                                    reason: CompletionReason::Executed,
+                                })
+                                .ok();
+                            tokio::spawn(refresh_thread(
+                                state.resources.clone(),
+                                state.staging_ref.clone(),
+                                ready_thread.thread_pubkey,
+                            ));
+                            return Ok(false);
                         }
                         thread
                     }
@@ -268,7 +505,7 @@ impl ProcessorFactory {
                                 reason: CompletionReason::Executed,
                             })
                             .ok();
-                        return Ok(());
+                        return Ok(false);
                     }
                 }
             }
@@ -301,7 +538,7 @@ impl ProcessorFactory {
                                     reason: CompletionReason::Executed,
                                 })
                                 .ok();
-                            return Ok(());
+                            return Ok(false);
                         }
                         thread
                     }
@@ -318,7 +555,7 @@ impl ProcessorFactory {
                                 reason: CompletionReason::Executed,
                             })
                             .ok();
-                        return Ok(());
+                        return Ok(false);
                     }
                 }
             }
@@ -332,7 +569,20 @@ impl ProcessorFactory {
             .await
             .map_err(|e| format!("Semaphore error: {}", e))?;
 
-        state.available_permits -= 1;
+        let submission_timeout = SubmissionTimeout::for_trigger(
+            &thread.trigger,
+            state.submission_timeout,
+            state.submission_timeout_factor,
+            chrono::Utc::now().timestamp(),
+        )
+        .with_leader_hold(
+            state
+                .leader_alignment
+                .as_ref()
+                .map_or(Duration::ZERO, |alignment| {
+                    alignment.max_hold(&thread.trigger)
+                }),
+        );
 
         // Spawn WorkerActor with Thread data from cache
         let worker_args = crate::actors::worker::WorkerArgs {
@@ -346,6 +596,13 @@ impl ProcessorFactory {
             resources: state.resources.clone(),
             executor: state.executor.clone(),
             load_balancer: state.load_balancer.clone(),
+            fiber_execution_timeout: state.fiber_execution_timeout,
+            submission_timeout,
+            simulate_before_send: state.simulate_before_send,
+            submission_mode: state.submission_mode,
+            fee_ceiling: state.fee_ceiling,
+            broadcast: state.submission.broadcasts(&thread.trigger),
+            batch_exec: state.submission.batch_exec,
         };
 
         let (worker_ref, _handle) = Actor::spawn(
@@ -360,15 +617,22 @@ impl ProcessorFactory {
         state
             .active_workers
             .insert(ready_thread.thread_pubkey, worker_ref);
+        state
+            .worker_started
+            .insert(ready_thread.thread_pubkey, Instant::now());
 
-        Ok(())
+        Ok(true)
     }
 
     /// Handle execution result from worker
+    ///
+    /// `elapsed` is the time since the worker was spawned, for adaptive
+    /// concurrency.
     async fn handle_execution_result(
         &self,
         state: &mut ProcessorState,
         result: ExecutionResult,
+        elapsed: Option<Duration>,
     ) -> Result<(), ActorProcessingErr> {
         // Check if this was a load balancer skip
         let is_lb_skip = result
@@ -377,17 +641,66 @@ impl ProcessorFactory {
             .map(|e| e.contains("load balancer") || e.contains("At capacity"))
             .unwrap_or(false);
 
+        let is_underfunded = result.error.as_deref() == Some(UNDERFUNDED_ERROR);
+        if is_underfunded {
+            state.underfunded_skips += 1;
+        }
+
+        // Deferred by the fee ceiling - retry on a later evaluation
+        let is_fee_deferred = result.error.as_deref() == Some(FEE_CEILING_DEFERRED_ERROR);
+        let is_duplicate = result.error.as_deref() == Some(DUPLICATE_EXECUTION_ERROR);
+
+        let is_chain_limit =
+            !result.success && result.error.as_deref().is_some_and(is_chain_limit_error);
+        if is_chain_limit {
+            state.chain_limit_failures += 1;
+        }
+
+        let outcome = if result.skipped
+            || (!result.success
+                && (is_lb_skip || is_underfunded || is_fee_deferred || is_duplicate))
+        {
+            metrics::OUTCOME_SKIPPED
+        } else if result.success {
+            metrics::OUTCOME_SUCCESS
+        } else if is_chain_limit {
+            metrics::OUTCOME_CHAIN_LIMIT
+        } else {
+            metrics::OUTCOME_FAILED
+        };
+        metrics::record_thread_execution(outcome);
+        let signal = match outcome {
+            metrics::OUTCOME_SUCCESS => elapsed.map(ExecutionSignal::Success),
+            metrics::OUTCOME_FAILED => Some(ExecutionSignal::Failure),
+            _ => None,
+        };
+        if let Some(signal) = signal {
+            adjust_concurrency(state, signal);
+        }
+        if outcome != metrics::OUTCOME_SKIPPED {
+            state
+                .resources
+                .alert_metrics
+                .record_execution(result.thread_pubkey, result.success);
+        }
+
         // Log the result
         if result.skipped {
             log::debug!("Thread {} skipped: empty fiber", result.thread_pubkey);
         } else if result.success {
             log::info!("Thread {} execution succeeded", result.thread_pubkey);
-        } else if is_lb_skip {
+        } else if is_lb_skip || is_underfunded || is_fee_deferred || is_duplicate {
             log::debug!(
                 "Thread {} skipped: {:?}",
                 result.thread_pubkey,
                 result.error
             );
+        } else if is_chain_limit {
+            log::error!(
+                "Thread {} fiber chain aborted (depth limit or cycle), check its fiber signals: {:?}",
+                result.thread_pubkey,
+                result.error
+            );
         } else {
             log::warn!(
                 "Thread {} execution failed after {} attempts: {:?}",
@@ -397,8 +710,8 @@ impl ProcessorFactory {
             );
         }
 
-        // Determine completion reason based on whether load balancer skipped
-        let reason = if is_lb_skip {
+        // Load balancer skips and fee deferrals are re-queued for evaluation
+        let reason = if is_lb_skip || is_fee_deferred {
             CompletionReason::Skipped
         } else {
             CompletionReason::Executed
@@ -416,3 +729,408 @@ impl ProcessorFactory {
         Ok(())
     }
 }
+
+/// Feed an execution to the adaptive concurrency controller
+///
+/// Lowering the limit stops no running worker; fewer are spawned as they
+/// finish.
+fn adjust_concurrency(state: &mut ProcessorState, signal: ExecutionSignal) {
+    let Some(concurrency) = state.concurrency.as_mut() else {
+        return;
+    };
+    let before = concurrency.limit();
+    if concurrency.record(signal, Instant::now()) {
+        let limit = concurrency.limit();
+        let direction = if limit > before {
+            "increase"
+        } else {
+            "decrease"
+        };
+        log::info!(
+            "Adaptive concurrency {} from {} to {} workers",
+            direction,
+            before,
+            limit
+        );
+        metrics::record_concurrency_limit(limit, direction);
+    }
+}
+
+/// Re-read a thread whose cached state is stale and hand newer state to staging
+///
+/// If the endpoint is still behind, the read is dropped and the thread waits
+/// for its next datasource update.
+async fn refresh_thread(
+    resources: SharedResources,
+    staging_ref: ActorRef<StagingMessage>,
+    thread_pubkey: Pubkey,
+) {
+    let (account, slot) = match resources
+        .rpc_client
+        .get_account_with_slot(&thread_pubkey)
+        .await
+    {
+        Ok(fetched) => fetched,
+        Err(e) => {
+            log::debug!("Failed to refresh thread {}: {}", thread_pubkey, e);
+            return;
+        }
+    };
+
+    if resources
+        .cache
+        .put_if_newer(thread_pubkey, account.data.clone(), slot)
+        .await
+    {
+        staging_ref
+            .send_message(StagingMessage::AccountUpdate(AccountUpdate::new(
+                thread_pubkey,
+                account.data,
+                slot,
+            )))
+            .ok();
+    }
+}
+
+/// Fiber accounts of cached threads due within `lookahead_secs` of `now`
+///
+/// Candidates come from the cache's time index plus the slot-relative
+/// threads, rather than a scan of every cached account.
+async fn due_fiber_accounts(
+    cache: &AccountCache,
+    now: i64,
+    slot: u64,
+    lookahead_secs: u64,
+) -> Vec<Pubkey> {
+    use anchor_lang::AccountDeserialize;
+    use antegen_thread_program::state::Thread;
+
+    let horizon = now.saturating_add(lookahead_secs as i64);
+    let mut fibers = Vec::new();
+    for pubkey in cache
+        .due_before(horizon)
+        .into_iter()
+        .chain(cache.floating())
+    {
+        let Some(cached) = cache.get(&pubkey).await else {
+            continue;
+        };
+        let Ok(thread) = Thread::try_deserialize(&mut cached.data.as_slice()) else {
+            continue;
+        };
+        if thread
+            .next_trigger_at(now, slot)
+            .is_some_and(|at| at <= horizon)
+        {
+            fibers.extend(
+                thread
+                    .fiber_ids
+                    .iter()
+                    .map(|&idx| thread.fiber_at_index(&pubkey, idx)),
+            );
+        }
+    }
+    fibers
+}
+
+/// Every `lookahead_secs`, prefetch the fibers of threads due within that window
+fn spawn_prefetch(resources: SharedResources, lookahead_secs: u64, cancel: CancellationToken) {
+    tokio::spawn(async move {
+        let slot_rx = resources.slot_receiver();
+        let mut ticker = tokio::time::interval(Duration::from_secs(lookahead_secs));
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = cancel.cancelled() => break,
+            }
+            let slot = slot_rx.as_ref().map_or(0, |rx| *rx.borrow());
+            let now = chrono::Utc::now().timestamp();
+            let fibers = due_fiber_accounts(&resources.cache, now, slot, lookahead_secs).await;
+            if fibers.is_empty() {
+                continue;
+            }
+            let fetched = ProcessorFactory::prefetch_accounts(&resources, &fibers).await;
+            log::debug!(
+                "Prefetched {} of {} fiber accounts due within {}s",
+                fetched,
+                fibers.len(),
+                lookahead_secs
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::messages::ReadyThread;
+    use crate::lanes::WorkerLane;
+    use crate::load_balancer::LoadBalancerConfig;
+    use crate::resources::ConfirmedExecutions;
+    use crate::rpc::mock::spawn_mock_rpc;
+    use crate::rpc::RpcPool;
+    use anchor_lang::AccountSerialize;
+    use antegen_thread_program::state::{
+        Schedule, Signal, Thread, Trigger, CURRENT_THREAD_VERSION,
+    };
+    use base64::prelude::*;
+    use solana_sdk::signature::Keypair;
+    use tokio::sync::{mpsc, oneshot};
+
+    /// Forwards staging messages to a channel
+    struct StagingCollector;
+
+    impl Actor for StagingCollector {
+        type Msg = StagingMessage;
+        type State = mpsc::UnboundedSender<StagingMessage>;
+        type Arguments = mpsc::UnboundedSender<StagingMessage>;
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            tx: Self::Arguments,
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(tx)
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            tx: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            let _ = tx.send(message);
+            Ok(())
+        }
+    }
+
+    const FIBER_ACCOUNT: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":500},"value":{"data":["AQID","base64"],"executable":false,"lamports":7,"owner":"11111111111111111111111111111111","rentEpoch":0,"space":3}}}"#;
+
+    /// Cache an interval thread with `fibers` fibers due at `next`
+    async fn cache_thread(cache: &AccountCache, next: i64, fibers: u8) -> (Pubkey, Thread) {
+        let pubkey = Pubkey::new_unique();
+        let thread = Thread {
+            version: CURRENT_THREAD_VERSION,
+            bump: 0,
+            authority: Pubkey::new_unique(),
+            id: b"prefetch".to_vec(),
+            name: "prefetch".to_string(),
+            created_at: 0,
+            trigger: Trigger::Interval {
+                seconds: 60,
+                skippable: false,
+                jitter: 0,
+            },
+            schedule: Schedule::Timed { prev: 0, next },
+            fiber_ids: (0..fibers).collect(),
+            fiber_cursor: 0,
+            fiber_next_id: fibers,
+            fiber_signal: Signal::None,
+            paused: false,
+            exec_count: 0,
+            last_executor: Pubkey::default(),
+            nonce_account: antegen_thread_program::ID,
+            last_nonce: String::new(),
+            close_fiber: Vec::new(),
+            fee_payer_mode: None,
+            consecutive_execs: 0,
+            chain_slot: 0,
+            chain_depth: 0,
+            chain_visited: [0; 4],
+            failing_fiber: 0,
+            fiber_failures: 0,
+            last_failure_slot: 0,
+            disabled_fibers: [0; 4],
+            resume_at: None,
+        };
+        let mut data = Vec::new();
+        thread.try_serialize(&mut data).unwrap();
+        cache.put_if_newer(pubkey, data, 1).await;
+        (pubkey, thread)
+    }
+
+    async fn resources() -> SharedResources {
+        let url = spawn_mock_rpc(FIBER_ACCOUNT).await;
+        SharedResources::with_custom(
+            Arc::new(RpcPool::with_url(url).unwrap()),
+            Arc::new(AccountCache::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_populates_cache_before_fire() {
+        let resources = resources().await;
+        let now = chrono::Utc::now().timestamp();
+        let (due, due_thread) = cache_thread(&resources.cache, now + 30, 2).await;
+        let (later, later_thread) = cache_thread(&resources.cache, now + 600, 1).await;
+
+        let fibers = due_fiber_accounts(&resources.cache, now, 0, 60).await;
+        assert_eq!(
+            fibers,
+            vec![
+                due_thread.fiber_at_index(&due, 0),
+                due_thread.fiber_at_index(&due, 1)
+            ]
+        );
+
+        assert_eq!(
+            ProcessorFactory::prefetch_accounts(&resources, &fibers).await,
+            2
+        );
+        for fiber in &fibers {
+            let cached = resources.cache.get(fiber).await.unwrap();
+            assert_eq!(cached.data, vec![1, 2, 3]);
+            assert_eq!(cached.slot, 500);
+        }
+        // Outside the lookahead window
+        assert!(resources
+            .cache
+            .get(&later_thread.fiber_at_index(&later, 0))
+            .await
+            .is_none());
+
+        // Already cached accounts are not fetched again
+        assert_eq!(
+            ProcessorFactory::prefetch_accounts(&resources, &fibers).await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_burst_hit_rate() {
+        // A burst of threads coming due within the same minute
+        let resources = resources().await;
+        let now = chrono::Utc::now().timestamp();
+        let mut fibers = Vec::new();
+        for i in 0..50 {
+            let (pubkey, thread) = cache_thread(&resources.cache, now + 10 + i % 40, 1).await;
+            fibers.push(thread.fiber_at_index(&pubkey, 0));
+        }
+
+        let hit_rate = |hits: usize| hits as f64 / fibers.len() as f64;
+        let mut hits = 0;
+        for fiber in &fibers {
+            hits += resources.cache.get(fiber).await.is_some() as usize;
+        }
+        assert_eq!(hit_rate(hits), 0.0);
+
+        let due = due_fiber_accounts(&resources.cache, now, 0, 60).await;
+        ProcessorFactory::prefetch_accounts(&resources, &due).await;
+
+        // Each execution takes its prefetched fiber from the cache
+        let mut hits = 0;
+        for fiber in &fibers {
+            hits += resources.cache.take(fiber).await.is_some() as usize;
+        }
+        assert_eq!(hit_rate(hits), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_stale_state_after_confirmation_not_executed_again() {
+        let cache = Arc::new(AccountCache::new());
+        let now = chrono::Utc::now().timestamp();
+        let (pubkey, thread) = cache_thread(&cache, now - 5, 1).await;
+
+        // The account update carrying the execution, as served by RPC
+        let mut executed = thread.clone();
+        executed.exec_count = 1;
+        let mut data = Vec::new();
+        executed.try_serialize(&mut data).unwrap();
+        let body: &'static str = Box::leak(
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1001}},"value":{{"data":["{}","base64"],"executable":false,"lamports":1000000,"owner":"{}","rentEpoch":0,"space":{}}}}}}}"#,
+                BASE64_STANDARD.encode(&data),
+                antegen_thread_program::ID,
+                data.len()
+            )
+            .into_boxed_str(),
+        );
+
+        let url = spawn_mock_rpc(body).await;
+        let mut resources =
+            SharedResources::with_custom(Arc::new(RpcPool::with_url(url).unwrap()), cache);
+        resources.confirmed_executions = Arc::new(ConfirmedExecutions::new(150));
+
+        // The thread executed at exec count 0 and landed in slot 1_000, but
+        // its account update is still on the way
+        resources.confirmed_executions.record(pubkey, 1_000, 0);
+
+        // A lagging endpoint then delivers the pre-execution state
+        let mut stale = Vec::new();
+        thread.try_serialize(&mut stale).unwrap();
+        assert!(resources.cache.put_if_newer(pubkey, stale, 995).await);
+
+        let (staging_tx, mut staging_rx) = mpsc::unbounded_channel();
+        let (staging, _) = Actor::spawn(None, StagingCollector, staging_tx)
+            .await
+            .unwrap();
+        let mut config = ClientConfig::default();
+        config.processor.prefetch_lookahead_secs = 0;
+        let executor = ExecutorLogic::new(Arc::new(Keypair::new()), resources.clone(), false);
+        let load_balancer = Arc::new(LoadBalancer::new(
+            Pubkey::new_unique(),
+            LoadBalancerConfig::default(),
+        ));
+        let (processor, handle) = Actor::spawn(
+            None,
+            ProcessorFactory,
+            (config, resources.clone(), staging, executor, load_balancer),
+        )
+        .await
+        .unwrap();
+
+        // Staging re-queues the thread from the stale copy
+        processor
+            .send_message(ProcessorMessage::ProcessReady(ReadyThread {
+                thread_pubkey: pubkey,
+                exec_count: 0,
+                is_overdue: false,
+                overdue_seconds: 0,
+                lane: WorkerLane::Time,
+            }))
+            .unwrap();
+
+        let recv = |rx: &mut mpsc::UnboundedReceiver<StagingMessage>| {
+            tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        };
+        match recv(&mut staging_rx).await.unwrap().unwrap() {
+            StagingMessage::ThreadCompleted {
+                thread_pubkey,
+                reason,
+            } => {
+                assert_eq!(thread_pubkey, pubkey);
+                assert_eq!(reason, CompletionReason::Executed);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // The refreshed state is cached and handed back to staging
+        match recv(&mut staging_rx).await.unwrap().unwrap() {
+            StagingMessage::AccountUpdate(update) => {
+                assert_eq!(update.pubkey, pubkey);
+                assert_eq!(update.slot, 1_001);
+                assert_eq!(update.data, data);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert_eq!(resources.cache.get(&pubkey).await.unwrap().slot, 1_001);
+
+        let (tx, rx) = oneshot::channel();
+        processor
+            .send_message(ProcessorMessage::QueryStatus(tx))
+            .unwrap();
+        let status = rx.await.unwrap();
+        assert_eq!(status.active_workers, 0);
+        assert_eq!(status.replay_guard_hits, 1);
+
+        // With the new state the record is cleared and the next run proceeds
+        assert!(resources
+            .confirmed_executions
+            .predates(&pubkey, 1_001, 1)
+            .is_none());
+        assert!(resources.confirmed_executions.is_empty());
+
+        processor.stop(None);
+        handle.await.unwrap();
+    }
+}
@@ -4,13 +4,18 @@
 //! via SIGINT (Ctrl+C) and SIGTERM signals.
 
 use crate::actors::messages::{RootMessage, StagingMessage};
-use crate::actors::observability::{ObservabilityActor, ObservabilityMessage};
+use crate::actors::observability::{CollectTargets, ObservabilityActor, ObservabilityMessage};
 use crate::actors::{DatasourceSupervisor, ProcessorFactory, StagingActor};
+use crate::alerts::run_alert_service;
 use crate::config::ClientConfig;
+use crate::control::{run_control_server, ControlContext};
+use crate::datasources::reconcile::reconcile_threads;
+use crate::datasources::PriorityReceiver;
 use crate::executor::ExecutorLogic;
+use crate::fee_payers::FeePayers;
+use crate::lanes::WorkerLanes;
 use crate::load_balancer::{LoadBalancer, LoadBalancerConfig};
 use crate::resources::SharedResources;
-use crate::types::AccountUpdate;
 use ractor::{Actor, ActorProcessingErr, ActorRef, SupervisionEvent};
 use signal_hook::consts::signal::{SIGINT, SIGTERM};
 use signal_hook_tokio::Signals;
@@ -19,7 +24,12 @@ use solana_sdk::signature::read_keypair_file;
 use solana_sdk::signer::Signer;
 use std::error::Error;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Upper bound on waiting for in-flight executions at shutdown
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Default)]
 pub struct RootSupervisor;
@@ -27,6 +37,12 @@ pub struct RootSupervisor;
 pub struct RootState {
     #[allow(dead_code)] // Kept for future observability control
     observability_ref: Option<ActorRef<ObservabilityMessage>>,
+    /// Tracks in-flight workers, drained before shutdown
+    load_balancer: Arc<LoadBalancer>,
+    /// Stops the alert service, if `[alerts]` is enabled
+    alerts_cancel: CancellationToken,
+    /// Stops the control API server, if `[control]` is enabled
+    control_cancel: CancellationToken,
 }
 
 impl Actor for RootSupervisor {
@@ -35,7 +51,7 @@ impl Actor for RootSupervisor {
     type Arguments = (
         ClientConfig,
         SharedResources,
-        Option<mpsc::Receiver<AccountUpdate>>,
+        Option<PriorityReceiver>,
         mpsc::UnboundedReceiver<Pubkey>, // Cache eviction receiver for StagingActor
     );
 
@@ -61,12 +77,30 @@ impl Actor for RootSupervisor {
         let executor_pubkey = keypair.pubkey();
         log::info!("Executor pubkey: {}", executor_pubkey);
 
+        // Additional fee payers, rotated with the primary
+        let mut others = Vec::with_capacity(config.executor.keypairs.len());
+        for path in &config.executor.keypairs {
+            let path = shellexpand::tilde(path).to_string();
+            let keypair = read_keypair_file(&path)
+                .map_err(|e| format!("Failed to load fee payer keypair from {}: {}", path, e))?;
+            log::info!("Fee payer pubkey: {}", keypair.pubkey());
+            others.push(Arc::new(keypair));
+        }
+        let fee_payers = Arc::new(FeePayers::new(Arc::new(keypair), others));
+        if fee_payers
+            .spawn_balance_monitor(resources.rpc_client.clone())
+            .is_some()
+        {
+            log::info!("Rotating executions across {} fee payers", fee_payers.len());
+        }
+
         // Create ExecutorLogic
         let executor = ExecutorLogic::new(
-            Arc::new(keypair),
+            fee_payers.primary().clone(),
             resources.clone(),
             config.executor.forgo_commission,
-        );
+        )
+        .with_fee_payers(fee_payers.clone());
 
         // Create LoadBalancer with config values
         let load_balancer_config = LoadBalancerConfig {
@@ -74,8 +108,14 @@ impl Actor for RootSupervisor {
             capacity_threshold: 5,
             thread_takeover_delay: config.load_balancer.grace_period as i64,
             thread_process_delay: config.load_balancer.thread_process_delay,
+            backpressure_threshold: config.load_balancer.backpressure_threshold,
+            backpressure_release_threshold: config.load_balancer.backpressure_release_threshold,
         };
-        let load_balancer = Arc::new(LoadBalancer::new(executor_pubkey, load_balancer_config));
+        let load_balancer = Arc::new(
+            LoadBalancer::new(executor_pubkey, load_balancer_config)
+                .with_fee_payers(fee_payers.pubkeys())
+                .with_worker_capacity(WorkerLanes::from_config(&config.processor).capacity()),
+        );
 
         let supervisor = myself.get_cell();
 
@@ -94,6 +134,7 @@ impl Actor for RootSupervisor {
         )
         .await
         .map_err(|e| format!("Failed to spawn StagingActor: {}", e))?;
+        load_balancer.set_backpressure_target(staging_ref.clone());
 
         // Spawn ProcessorFactory (depends on staging)
         log::debug!("Spawning ProcessorFactory...");
@@ -117,10 +158,21 @@ impl Actor for RootSupervisor {
             .send_message(StagingMessage::SetProcessorRef(processor_ref.clone()))
             .map_err(|e| format!("Failed to set processor ref in staging: {}", e))?;
 
+        // Seed staging with every existing thread so overdue ones run immediately
+        if config.processor.reconcile_on_start {
+            let resources = resources.clone();
+            let staging_ref = staging_ref.clone();
+            tokio::spawn(async move {
+                if let Err(e) = reconcile_threads(&resources, &staging_ref).await {
+                    log::error!("Startup reconciliation sweep failed: {}", e);
+                }
+            });
+        }
+
         // Spawn DatasourceSupervisor (depends on staging)
         // Pass optional geyser receiver for plugin mode
         log::debug!("Spawning DatasourceSupervisor...");
-        let (_datasource_ref, _datasource_handle) = Actor::spawn_linked(
+        let (datasource_ref, _datasource_handle) = Actor::spawn_linked(
             Some("datasource-supervisor".to_string()),
             DatasourceSupervisor,
             (
@@ -142,7 +194,17 @@ impl Actor for RootSupervisor {
             let (obs_ref, _obs_handle) = Actor::spawn_linked(
                 Some("observability".to_string()),
                 ObservabilityActor,
-                config.observability.clone(),
+                (
+                    config.observability.clone(),
+                    resources.tpu_client.clone(),
+                    resources.submission_metrics.clone(),
+                    CollectTargets {
+                        staging: staging_ref.clone(),
+                        processor: processor_ref.clone(),
+                        datasource: datasource_ref,
+                        load_balancer: load_balancer.clone(),
+                    },
+                ),
                 supervisor.clone(),
             )
             .await
@@ -153,20 +215,64 @@ impl Actor for RootSupervisor {
             None
         };
 
+        // Spawn the alert service if any rule has a sink
+        let alerts_cancel = CancellationToken::new();
+        if config.alerts.is_enabled() {
+            tokio::spawn(run_alert_service(
+                config.alerts.clone(),
+                resources.clone(),
+                fee_payers.pubkeys(),
+                alerts_cancel.clone(),
+            ));
+        }
+
+        // Serve the control API, answering from the actors spawned above
+        let control_cancel = CancellationToken::new();
+        if config.control.enabled {
+            let context = ControlContext {
+                staging: staging_ref.clone(),
+                processor: processor_ref.clone(),
+                resources: resources.clone(),
+                config_path: config.source_path.clone(),
+                started_at: Instant::now(),
+            };
+            tokio::spawn(run_control_server(
+                config.control.clone(),
+                context,
+                control_cancel.clone(),
+            ));
+        }
+
         log::info!("System ready. Press Ctrl+C to shutdown.");
 
-        Ok(RootState { observability_ref })
+        Ok(RootState {
+            observability_ref,
+            load_balancer,
+            alerts_cancel,
+            control_cancel,
+        })
     }
 
     async fn handle(
         &self,
         _myself: ActorRef<Self::Msg>,
         message: Self::Msg,
-        _state: &mut Self::State,
+        state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
         match message {
             RootMessage::Shutdown => {
                 log::info!("RootSupervisor received shutdown signal");
+
+                // Let in-flight executions finish so none is left in an unknown state
+                if tokio::time::timeout(SHUTDOWN_DRAIN_TIMEOUT, state.load_balancer.drain())
+                    .await
+                    .is_err()
+                {
+                    log::warn!(
+                        "Workers still running after {:?}, stopping anyway",
+                        SHUTDOWN_DRAIN_TIMEOUT
+                    );
+                }
                 log::info!("Shutting down...");
 
                 // Stop this actor (triggers post_stop)
@@ -207,8 +313,10 @@ impl Actor for RootSupervisor {
     async fn post_stop(
         &self,
         _myself: ActorRef<Self::Msg>,
-        _state: &mut Self::State,
+        state: &mut Self::State,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        state.alerts_cancel.cancel();
+        state.control_cancel.cancel();
         log::info!("RootSupervisor stopped. Graceful shutdown complete.");
         Ok(())
     }
@@ -8,13 +8,16 @@
 //! The cache is the single source of truth for account data.
 
 use crate::actors::messages::{
-    CompletionReason, ProcessorMessage, ReadyThread, ScheduledThread, StagingMessage, StagingStatus,
+    ActorCounters, CompletionReason, ProcessorMessage, ReadyThread, ScheduledThread,
+    StagingMessage, StagingStatus, TrackedThreadInfo,
 };
-use crate::config::ClientConfig;
+use crate::config::{ClientConfig, GapBackfillConfig, GapPolicy};
+use crate::lanes::WorkerLane;
 use crate::load_balancer::LoadBalancer;
 use crate::resources::SharedResources;
+use crate::thread_filter::ThreadFilter;
 use anchor_lang::AccountDeserialize;
-use antegen_thread_program::state::{Schedule, Thread, Trigger};
+use antegen_thread_program::state::{Schedule, Thread, ThreadConfig, Trigger};
 use anyhow::Result;
 use dashmap::DashSet;
 use log::{debug, info, trace, warn};
@@ -26,6 +29,9 @@ use std::error::Error;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 
+/// Name the StagingActor reports its stats under
+pub const STAGING_ACTOR_NAME: &str = "staging";
+
 #[derive(Default)]
 pub struct StagingActor;
 
@@ -35,6 +41,28 @@ struct TrackedThread {
     exec_count: u64,
     schedule: Schedule,
     paused: bool,
+    resume_at: Option<i64>,
+}
+
+impl TrackedThread {
+    fn new(thread: &Thread) -> Self {
+        Self {
+            exec_count: thread.exec_count,
+            schedule: thread.schedule.clone(),
+            paused: thread.paused,
+            resume_at: thread.resume_at,
+        }
+    }
+
+    /// Paused with no resume time: nothing to schedule until an unpause
+    fn is_paused_indefinitely(&self) -> bool {
+        self.paused && self.resume_at.is_none()
+    }
+
+    /// Mirrors `Thread::is_paused_at`
+    fn is_paused_at(&self, unix_timestamp: i64) -> bool {
+        self.paused && !self.resume_at.is_some_and(|r| unix_timestamp >= r)
+    }
 }
 
 pub struct StagingState {
@@ -54,6 +82,22 @@ pub struct StagingState {
     // Only track slot since slots are monotonically increasing
     last_processed_slot: u64,
 
+    // Threads this client follows, and updates dropped because of it
+    thread_filter: ThreadFilter,
+    filtered_updates: u64,
+
+    // Ready threads stay queued while set (control API pause)
+    processing_paused: bool,
+
+    // Account updates held while the worker pool is saturated, newest per
+    // account (see StagingMessage::BackpressureStart)
+    backpressure: bool,
+    deferred_updates: HashMap<Pubkey, crate::types::AccountUpdate>,
+
+    // Previous clock and gap policy, for catching up after a datasource gap
+    last_clock: Option<Clock>,
+    gap_config: GapBackfillConfig,
+
     // Communication
     processor_ref: Option<ActorRef<ProcessorMessage>>,
 
@@ -65,6 +109,9 @@ pub struct StagingState {
 
     // Cache eviction receiver - threads to refetch after TTL expiry
     eviction_rx: mpsc::UnboundedReceiver<Pubkey>,
+
+    // Message counts reported to CollectMetrics
+    counters: ActorCounters,
 }
 
 impl Actor for StagingActor {
@@ -80,11 +127,19 @@ impl Actor for StagingActor {
     async fn pre_start(
         &self,
         _myself: ActorRef<Self::Msg>,
-        (_config, resources, load_balancer, eviction_rx): Self::Arguments,
+        (config, resources, load_balancer, eviction_rx): Self::Arguments,
     ) -> Result<Self::State, Box<dyn Error + Send + Sync>> {
         log::debug!("StagingActor starting...");
         log::debug!("Thread program ID: {}", resources.program_id);
 
+        let thread_filter = ThreadFilter::new(&config.processor.thread_filter)?;
+        if !thread_filter.matches_all() {
+            log::info!(
+                "Following only threads matching {:?}",
+                config.processor.thread_filter
+            );
+        }
+
         Ok(StagingState {
             tracked_threads: HashMap::new(),
             time_queue: Arc::new(Mutex::new(BinaryHeap::new())),
@@ -92,10 +147,18 @@ impl Actor for StagingActor {
             epoch_queue: Arc::new(Mutex::new(BinaryHeap::new())),
             queued_threads: DashSet::new(),
             last_processed_slot: 0,
+            thread_filter,
+            filtered_updates: 0,
+            processing_paused: false,
+            backpressure: false,
+            deferred_updates: HashMap::new(),
+            last_clock: None,
+            gap_config: config.processor.gap_backfill,
             processor_ref: None, // Will be set by RootSupervisor after processor spawns
             resources,
             load_balancer,
             eviction_rx,
+            counters: ActorCounters::new(),
         })
     }
 
@@ -104,9 +167,39 @@ impl Actor for StagingActor {
         _myself: ActorRef<Self::Msg>,
         message: Self::Msg,
         state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        let result = self.handle_message(message, state).await;
+        state.counters.record(result.is_err());
+        result
+    }
+
+    async fn post_stop(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        state: &mut Self::State,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        log::info!(
+            "StagingActor stopped. {} threads tracked, {} queued",
+            state.tracked_threads.len(),
+            state.queued_threads.len()
+        );
+        Ok(())
+    }
+}
+
+impl StagingActor {
+    /// Dispatch one message; `handle` counts it for `CollectMetrics`
+    async fn handle_message(
+        &self,
+        message: StagingMessage,
+        state: &mut StagingState,
     ) -> Result<(), ActorProcessingErr> {
         match message {
             StagingMessage::AccountUpdate(update) => {
+                if state.backpressure {
+                    Self::defer_update(state, update);
+                    return Ok(());
+                }
                 self.handle_account_update(state, update).await?;
                 Ok(())
             }
@@ -114,6 +207,10 @@ impl Actor for StagingActor {
                 self.handle_clock_tick(state, clock).await?;
                 Ok(())
             }
+            StagingMessage::InitialLoadComplete { count } => {
+                self.handle_initial_load(state, count).await;
+                Ok(())
+            }
             StagingMessage::ThreadCompleted {
                 thread_pubkey,
                 reason,
@@ -149,6 +246,10 @@ impl Actor for StagingActor {
                 state.processor_ref = Some(processor_ref);
                 Ok(())
             }
+            StagingMessage::SetThreadFilter(filter) => {
+                self.handle_set_thread_filter(state, filter).await;
+                Ok(())
+            }
             StagingMessage::QueryStatus(tx) => {
                 let status = StagingStatus {
                     total_threads: state.tracked_threads.len(),
@@ -156,10 +257,78 @@ impl Actor for StagingActor {
                     time_queue_size: state.time_queue.lock().await.len(),
                     slot_queue_size: state.slot_queue.lock().await.len(),
                     epoch_queue_size: state.epoch_queue.lock().await.len(),
+                    filtered_updates: state.filtered_updates,
+                    processing_paused: state.processing_paused,
                 };
                 let _ = tx.send(status);
                 Ok(())
             }
+            StagingMessage::SetProcessingPaused(paused, tx) => {
+                if paused != state.processing_paused {
+                    info!("Processing {}", if paused { "paused" } else { "resumed" });
+                }
+                let _ = tx.send(std::mem::replace(&mut state.processing_paused, paused));
+                Ok(())
+            }
+            StagingMessage::BackpressureStart => {
+                state.backpressure = true;
+                Ok(())
+            }
+            StagingMessage::BackpressureStop => {
+                state.backpressure = false;
+                let mut deferred: Vec<_> = state.deferred_updates.drain().map(|(_, u)| u).collect();
+                if !deferred.is_empty() {
+                    debug!(
+                        "Processing {} account updates held by back-pressure",
+                        deferred.len()
+                    );
+                }
+                deferred.sort_by_key(|update| update.slot);
+                for update in deferred {
+                    self.handle_account_update(state, update).await?;
+                }
+                Ok(())
+            }
+            StagingMessage::ListThreads(tx) => {
+                let threads = state
+                    .tracked_threads
+                    .iter()
+                    .map(|(pubkey, tracked)| TrackedThreadInfo {
+                        pubkey: *pubkey,
+                        exec_count: tracked.exec_count,
+                        paused: tracked.paused,
+                        resume_at: tracked.resume_at,
+                        queued: state.queued_threads.contains(pubkey),
+                    })
+                    .collect();
+                let _ = tx.send(threads);
+                Ok(())
+            }
+            StagingMessage::CollectMetrics(tx) => {
+                let stats = state.counters.stats(
+                    STAGING_ACTOR_NAME,
+                    [
+                        ("tracked_threads", state.tracked_threads.len() as f64),
+                        ("queued_threads", state.queued_threads.len() as f64),
+                        (
+                            "time_queue_size",
+                            state.time_queue.lock().await.len() as f64,
+                        ),
+                        (
+                            "slot_queue_size",
+                            state.slot_queue.lock().await.len() as f64,
+                        ),
+                        (
+                            "epoch_queue_size",
+                            state.epoch_queue.lock().await.len() as f64,
+                        ),
+                        ("filtered_updates", state.filtered_updates as f64),
+                        ("deferred_updates", state.deferred_updates.len() as f64),
+                    ],
+                );
+                let _ = tx.send(stats);
+                Ok(())
+            }
             StagingMessage::Shutdown => {
                 log::info!("StagingActor shutting down...");
                 Err(From::from("Shutdown signal received"))
@@ -167,21 +336,19 @@ impl Actor for StagingActor {
         }
     }
 
-    async fn post_stop(
-        &self,
-        _myself: ActorRef<Self::Msg>,
-        state: &mut Self::State,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        log::info!(
-            "StagingActor stopped. {} threads tracked, {} queued",
-            state.tracked_threads.len(),
-            state.queued_threads.len()
-        );
-        Ok(())
+    /// Hold an update while back-pressure is engaged
+    ///
+    /// Only the newest update per account is kept, so the backlog is bounded
+    /// by the number of accounts rather than the update rate.
+    fn defer_update(state: &mut StagingState, update: crate::types::AccountUpdate) {
+        match state.deferred_updates.get(&update.pubkey) {
+            Some(held) if held.slot > update.slot => {}
+            _ => {
+                state.deferred_updates.insert(update.pubkey, update);
+            }
+        }
     }
-}
 
-impl StagingActor {
     /// Handle incoming account update
     ///
     /// NOTE: Data has already been stored in cache by datasource.
@@ -191,9 +358,17 @@ impl StagingActor {
         state: &mut StagingState,
         update: crate::types::AccountUpdate,
     ) -> Result<(), ActorProcessingErr> {
+        state.resources.alert_metrics.record_account_update();
+
         // Classify the account type and extract trigger info
         match self.classify_account(&update.data, &update.pubkey) {
             AccountType::Thread(thread) => {
+                if !state.thread_filter.matches(&update.pubkey, &thread.id) {
+                    state.filtered_updates += 1;
+                    trace!("Thread {} excluded by thread filter", update.pubkey);
+                    return Ok(());
+                }
+
                 // Check if we already have a newer or same version
                 if let Some(existing) = state.tracked_threads.get(&update.pubkey) {
                     // Skip if nothing changed (same exec_count AND same schedule)
@@ -250,17 +425,14 @@ impl StagingActor {
                 }
 
                 // Track exec_count, schedule, and paused state (cache has full data)
-                state.tracked_threads.insert(
-                    update.pubkey,
-                    TrackedThread {
-                        exec_count: thread.exec_count,
-                        schedule: thread.schedule.clone(),
-                        paused: thread.paused,
-                    },
-                );
-
-                // Skip scheduling paused threads — they'll be scheduled when unpaused
-                if thread.paused {
+                let tracked = TrackedThread::new(&thread);
+                let paused_indefinitely = tracked.is_paused_indefinitely();
+                state.tracked_threads.insert(update.pubkey, tracked);
+
+                // Skip scheduling paused threads — they'll be scheduled when
+                // unpaused. Threads paused until resume_at stay queued and are
+                // held back until the clock passes it.
+                if paused_indefinitely {
                     debug!("Thread {} is paused, skipping scheduling", update.pubkey);
                     return Ok(());
                 }
@@ -279,6 +451,14 @@ impl StagingActor {
             }
             AccountType::Other => {
                 // Not a thread account (could be Fiber, ThreadConfig, etc.)
+                let dependents = state.resources.cache.dependents_of(&update.pubkey);
+                if !dependents.is_empty() {
+                    debug!(
+                        "Account {} changed, watched by {} thread(s) (account triggers not yet supported)",
+                        update.pubkey,
+                        dependents.len()
+                    );
+                }
             }
         }
 
@@ -300,9 +480,33 @@ impl StagingActor {
             return Ok(());
         }
 
+        let prev_clock = state.last_clock.replace(clock.clone());
+
+        // Recurring epoch threads come due when the epoch rolls over
+        if let Some(prev) = prev_clock.as_ref().filter(|prev| clock.epoch > prev.epoch) {
+            info!(
+                "Epoch boundary: {} -> {} at slot {}, epoch_queue={}",
+                prev.epoch,
+                clock.epoch,
+                clock.slot,
+                state.epoch_queue.lock().await.len()
+            );
+        }
+
+        // Detect a datasource gap (clock jumped further than expected)
+        let gap_start =
+            prev_clock.filter(|prev| clock.slot - prev.slot > state.gap_config.max_slot_gap);
+
         // Update last processed slot
         state.last_processed_slot = clock.slot;
 
+        // Age out replay guard records
+        let confirmed = &state.resources.confirmed_executions;
+        confirmed.observe_slot(clock.slot);
+        if clock.slot.is_multiple_of(100) {
+            confirmed.cleanup();
+        }
+
         // Periodic heartbeat at INFO level every 100 slots
         if clock.slot.is_multiple_of(100) {
             info!(
@@ -353,16 +557,11 @@ impl StagingActor {
             {
                 Ok(thread) => {
                     // Update tracked thread with fresh data
-                    state.tracked_threads.insert(
-                        pubkey,
-                        TrackedThread {
-                            exec_count: thread.exec_count,
-                            schedule: thread.schedule.clone(),
-                            paused: thread.paused,
-                        },
-                    );
+                    let tracked = TrackedThread::new(&thread);
+                    let paused_indefinitely = tracked.is_paused_indefinitely();
+                    state.tracked_threads.insert(pubkey, tracked);
                     // Skip re-scheduling paused threads
-                    if thread.paused {
+                    if paused_indefinitely {
                         debug!("Refetched thread {} is paused, skipping reschedule", pubkey);
                     } else if let Err(e) = self.schedule_thread(state, pubkey, &thread).await {
                         warn!(
@@ -385,6 +584,99 @@ impl StagingActor {
             }
         }
 
+        // Apply the gap policy to threads that came due while we were behind
+        // (while processing is paused they simply run on resume)
+        if let Some(prev) = gap_start.filter(|_| !state.processing_paused) {
+            self.backfill_gap(state, &prev, &clock).await;
+        }
+
+        self.push_ready_threads(state, &clock).await;
+
+        Ok(())
+    }
+
+    /// Schedule every thread bulk-loaded into the cache during startup, then
+    /// evaluate the queues once against the latest clock
+    async fn handle_initial_load(&self, state: &mut StagingState, count: usize) {
+        let staged = self.stage_cached_accounts(state).await;
+
+        info!(
+            "Initial load complete: {} accounts loaded, {} staged, {} threads tracked",
+            count,
+            staged,
+            state.tracked_threads.len()
+        );
+
+        if let Some(clock) = state.last_clock.clone() {
+            self.push_ready_threads(state, &clock).await;
+        }
+    }
+
+    /// Run every cached account through `handle_account_update`, returning
+    /// how many were staged
+    async fn stage_cached_accounts(&self, state: &mut StagingState) -> usize {
+        let cache = state.resources.cache.clone();
+        let mut staged = 0;
+
+        for pubkey in cache.keys() {
+            let Some(cached) = cache.get(&pubkey).await else {
+                continue; // Evicted since the key snapshot
+            };
+            let update = crate::types::AccountUpdate::new(pubkey, cached.data, cached.slot);
+            match self.handle_account_update(state, update).await {
+                Ok(()) => staged += 1,
+                Err(e) => warn!("Failed to stage cached account {}: {:?}", pubkey, e),
+            }
+        }
+
+        staged
+    }
+
+    /// Swap the thread filter at runtime
+    ///
+    /// Threads the new filter excludes stop being tracked (their queue entries
+    /// go stale and are skipped); threads it newly includes are picked up from
+    /// the cache without waiting for their next account update.
+    async fn handle_set_thread_filter(&self, state: &mut StagingState, filter: ThreadFilter) {
+        info!("Thread filter set to {:?}", filter.patterns());
+        state.thread_filter = filter;
+
+        let mut excluded = Vec::new();
+        for pubkey in state.tracked_threads.keys() {
+            let Some(cached) = state.resources.cache.get(pubkey).await else {
+                continue;
+            };
+            let Ok(thread) = Thread::try_deserialize(&mut cached.data.as_slice()) else {
+                continue;
+            };
+            if !state.thread_filter.matches(pubkey, &thread.id) {
+                excluded.push(*pubkey);
+            }
+        }
+        for pubkey in &excluded {
+            state.tracked_threads.remove(pubkey);
+        }
+
+        self.stage_cached_accounts(state).await;
+        info!(
+            "Thread filter applied: {} threads dropped, {} tracked",
+            excluded.len(),
+            state.tracked_threads.len()
+        );
+
+        if let Some(clock) = state.last_clock.clone() {
+            self.push_ready_threads(state, &clock).await;
+        }
+    }
+
+    /// Pop ready threads from the priority queues and push them to the processor
+    async fn push_ready_threads(&self, state: &mut StagingState, clock: &Clock) {
+        // While paused, due threads wait in their queues and run on resume
+        if state.processing_paused {
+            trace!("Processing paused, holding ready threads");
+            return;
+        }
+
         // Get ready threads from all priority queues
         let ready_threads = self
             .get_ready_threads(state, clock.unix_timestamp, clock.slot, clock.epoch)
@@ -394,8 +686,21 @@ impl StagingActor {
             info!("Found {} ready threads", ready_threads.len());
         }
 
+        // Thread config from the cache, if it has been loaded; without it the
+        // pre-filter is skipped and workers find out on-chain as before
+        let thread_config = if ready_threads.is_empty() {
+            None
+        } else {
+            state
+                .resources
+                .cache
+                .get(&ThreadConfig::pubkey())
+                .await
+                .and_then(|cached| ThreadConfig::try_deserialize(&mut cached.data.as_slice()).ok())
+        };
+
         // Push each ready thread to ProcessorFactory
-        for ready_thread in ready_threads {
+        for mut ready_thread in ready_threads {
             // Check if already queued (additional dedup safety)
             if state.queued_threads.contains(&ready_thread.thread_pubkey) {
                 debug!(
@@ -405,6 +710,35 @@ impl StagingActor {
                 continue;
             }
 
+            // Drop threads thread_exec would reject at this clock (paused,
+            // trigger not yet due after jitter, ...) and requeue them for the
+            // next tick instead of spending a worker on a failing transaction
+            if let Some(config) = &thread_config {
+                if let Some(thread) = self.cached_thread(state, &ready_thread.thread_pubkey).await {
+                    if !thread.is_executable(clock, config) {
+                        debug!(
+                            "Thread {} not executable at slot {}, requeueing",
+                            ready_thread.thread_pubkey, clock.slot
+                        );
+                        if let Err(e) = self
+                            .schedule_thread(state, ready_thread.thread_pubkey, &thread)
+                            .await
+                        {
+                            warn!(
+                                "Failed to requeue thread {}: {:?}",
+                                ready_thread.thread_pubkey, e
+                            );
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            // Route to a worker lane by the trigger type recorded in the cache
+            if let Some(cached) = state.resources.cache.get(&ready_thread.thread_pubkey).await {
+                ready_thread.lane = WorkerLane::of(cached.trigger_type);
+            }
+
             // Mark as queued
             state.queued_threads.insert(ready_thread.thread_pubkey);
 
@@ -434,8 +768,90 @@ impl StagingActor {
                 state.queued_threads.remove(&ready_thread.thread_pubkey);
             }
         }
+    }
 
-        Ok(())
+    /// Thread account from the cache, if present and decodable
+    async fn cached_thread(&self, state: &StagingState, thread_pubkey: &Pubkey) -> Option<Thread> {
+        let cached = state.resources.cache.get(thread_pubkey).await?;
+        Thread::try_deserialize(&mut cached.data.as_slice()).ok()
+    }
+
+    /// Handle threads whose trigger fell inside a datasource gap
+    ///
+    /// With `GapPolicy::CatchUp` the entries stay queued, so the ready pass
+    /// executes each thread once (its most recent missed occurrence, since a
+    /// thread only has one live queue entry). With `GapPolicy::Skip` they are
+    /// dropped and the thread is rescheduled on its next account update.
+    async fn backfill_gap(&self, state: &StagingState, prev: &Clock, clock: &Clock) {
+        let windows = [
+            (
+                &state.time_queue,
+                state.gap_config.time,
+                prev.unix_timestamp.max(0) as u64,
+                clock.unix_timestamp.max(0) as u64,
+            ),
+            (
+                &state.slot_queue,
+                state.gap_config.slot,
+                prev.slot,
+                clock.slot,
+            ),
+            (
+                &state.epoch_queue,
+                state.gap_config.epoch,
+                prev.epoch,
+                clock.epoch,
+            ),
+        ];
+
+        let mut caught_up: HashSet<Pubkey> = HashSet::new();
+        let mut skipped: HashSet<Pubkey> = HashSet::new();
+
+        for (queue, policy, from, to) in windows {
+            // Live entries that came due strictly after the previous clock
+            let missed = |scheduled: &ScheduledThread| {
+                scheduled.trigger_value > from
+                    && scheduled.trigger_value <= to
+                    && !state.queued_threads.contains(&scheduled.thread_pubkey)
+                    && state
+                        .tracked_threads
+                        .get(&scheduled.thread_pubkey)
+                        .is_some_and(|t| {
+                            !t.is_paused_at(clock.unix_timestamp)
+                                && t.exec_count == scheduled.exec_count
+                        })
+            };
+
+            let mut lock = queue.lock().await;
+            match policy {
+                GapPolicy::CatchUp => {
+                    caught_up.extend(
+                        lock.iter()
+                            .filter(|Reverse(s)| missed(s))
+                            .map(|Reverse(s)| s.thread_pubkey),
+                    );
+                }
+                GapPolicy::Skip => {
+                    lock.retain(|Reverse(s)| {
+                        if missed(s) {
+                            skipped.insert(s.thread_pubkey);
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                }
+            }
+        }
+
+        info!(
+            "Datasource gap of {} slots (slot {} -> {}): {} threads caught up, {} skipped",
+            clock.slot - prev.slot,
+            prev.slot,
+            clock.slot,
+            caught_up.len(),
+            skipped.len()
+        );
     }
 
     /// Schedule a thread in the appropriate priority queue
@@ -452,6 +868,11 @@ impl StagingActor {
             | Trigger::Interval { .. }
             | Trigger::Cron { .. } => {
                 if let Schedule::Timed { next, .. } = thread.schedule {
+                    // A thread paused until resume_at can't run before it
+                    let next = match thread.resume_at.filter(|_| thread.paused) {
+                        Some(resume_at) => next.max(resume_at),
+                        None => next,
+                    };
                     ("timestamp", next.max(0) as u64)
                 } else {
                     warn!(
@@ -472,7 +893,7 @@ impl StagingActor {
                     return Ok(());
                 }
             }
-            Trigger::Epoch { .. } => {
+            Trigger::Epoch { .. } | Trigger::EpochInterval { .. } => {
                 if let Schedule::Block { next, .. } = thread.schedule {
                     ("epoch", next)
                 } else {
@@ -587,6 +1008,7 @@ impl StagingActor {
     ) {
         let mut queue_lock = queue.lock().await;
         let mut latest_exec_count: HashMap<Pubkey, u64> = HashMap::new();
+        let mut deferred = Vec::new();
 
         while let Some(Reverse(scheduled)) = queue_lock.peek() {
             if scheduled.trigger_value <= current_value {
@@ -609,9 +1031,13 @@ impl StagingActor {
                     }
                 };
 
-                // Skip paused threads
-                if tracked.paused {
+                // Skip paused threads, holding back those paused until a
+                // resume_at still ahead of the cached clock
+                if tracked.is_paused_at(timestamp) {
                     trace!("Thread {} is paused, skipping", scheduled.thread_pubkey);
+                    if tracked.resume_at.is_some() {
+                        deferred.push(Reverse(scheduled));
+                    }
                     continue;
                 }
 
@@ -655,6 +1081,7 @@ impl StagingActor {
                     exec_count: tracked.exec_count,
                     is_overdue: overdue_seconds > 0,
                     overdue_seconds,
+                    lane: WorkerLane::default(),
                 };
 
                 ready.push(ready_thread);
@@ -664,6 +1091,7 @@ impl StagingActor {
                 break;
             }
         }
+        queue_lock.extend(deferred);
     }
 
     /// Compact a priority queue by removing stale entries.
@@ -9,39 +9,52 @@
 //!
 //! Includes deadman's switch to prevent runaway workers.
 
-use crate::actors::messages::{ExecutionResult, ProcessorMessage, WorkerMessage};
-use crate::executor::ExecutorLogic;
+use crate::actors::messages::{
+    ActorCounters, Drained, ExecutionResult, ProcessorMessage, WorkerMessage,
+};
+use crate::batch::{
+    failed_instruction, pack, responsible_member, BatchEntry, BatchOutcome, PendingExec,
+};
+use crate::cadence::SubmissionTimeout;
+use crate::config::{FeeCeilingConfig, SubmissionMode};
+use crate::costs::{CostLedger, ThreadCosts};
+use crate::executor::{stored_compute_limit, ExecutorLogic};
+use crate::fees::{apply_fee_ceiling, FeeDecision, FEE_CEILING_DEFERRED_ERROR};
 use crate::load_balancer::{LoadBalancer, ProcessDecision};
+use crate::metrics;
 use crate::resources::SharedResources;
-use antegen_thread_program::state::Thread;
+use crate::results;
+use crate::tpu::{send_both, send_broadcast, TpuError};
+use crate::tx_builder::{TransactionBuilder, TxStrategy};
+use crate::tx_log::{TxPhase, TxSubmission};
+use antegen_thread_program::state::{FiberState, Thread};
 use ractor::{Actor, ActorProcessingErr, ActorRef};
+use solana_commitment_config::CommitmentConfig;
 use solana_compute_budget_interface::ComputeBudgetInstruction;
-use solana_sdk::{
-    clock::Clock, instruction::Instruction, message::Message, pubkey::Pubkey, signature::Signature,
-    transaction::Transaction,
-};
+use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Signature};
 use std::error::Error;
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{broadcast, OwnedSemaphorePermit};
+use tokio::sync::{broadcast, watch, OwnedSemaphorePermit};
 
 /// Maximum number of submission attempts
 const MAX_ATTEMPTS: u32 = 5;
 
-/// Timeout for waiting for confirmation (seconds)
-const CONFIRMATION_TIMEOUT_SECS: u64 = 30;
-
 /// Base delay between retries (milliseconds)
 const BASE_RETRY_DELAY_MS: u64 = 500;
 
-/// Interval for re-sending via TPU during confirmation polling (milliseconds)
-const TPU_RETRY_INTERVAL_MS: u64 = 2000;
-
 /// Retry deadline for trigger-not-ready errors (seconds)
 /// This bounds how long we'll retry before giving up
 const TRIGGER_RETRY_DEADLINE_SECS: u64 = 10;
 
+/// Failure reason for threads skipped because they cannot reimburse fees
+pub(crate) const UNDERFUNDED_ERROR: &str = "Thread cannot reimburse execution fees";
+
+/// Failure reason for executions dropped by the submission dedup window
+pub(crate) const DUPLICATE_EXECUTION_ERROR: &str = "Duplicate execution within dedup window";
+
 /// Check if an error indicates the trigger condition is not yet met (error 6004)
 fn is_trigger_not_ready_error(error: &str) -> bool {
     error.contains("Custom(6004)") || error.contains("6004")
@@ -52,6 +65,26 @@ fn is_thread_paused_error(error: &str) -> bool {
     error.contains("Custom(6006)") || error.contains("6006")
 }
 
+/// Check if an error is a fiber chain limit: depth exceeded (6049) or a
+/// cycle (6050). Retrying cannot help until the fiber signals are fixed.
+pub(crate) fn is_chain_limit_error(error: &str) -> bool {
+    error.contains("Custom(6049)") || error.contains("Custom(6050)")
+}
+
+/// Check if a simulation failed inside the first fiber's CPI rather than in
+/// the thread program's own checks. Only non-custom errors (compute budget
+/// exhausted, program failed to complete, ...) count: custom codes may come
+/// from the thread program itself.
+fn is_fiber_failure_error(error: &str) -> bool {
+    error
+        .split("InstructionError(1, ")
+        .nth(1)
+        .is_some_and(|rest| !rest.starts_with("Custom("))
+}
+
+/// Name every WorkerActor reports its stats under
+pub const WORKER_ACTOR_NAME: &str = "worker";
+
 pub struct WorkerActor;
 
 pub struct WorkerArgs {
@@ -65,6 +98,16 @@ pub struct WorkerArgs {
     pub resources: SharedResources,
     pub executor: ExecutorLogic,
     pub load_balancer: Arc<LoadBalancer>,
+    pub fiber_execution_timeout: Duration,
+    /// Confirmation wait derived from the thread's trigger period
+    pub submission_timeout: SubmissionTimeout,
+    pub simulate_before_send: bool,
+    pub submission_mode: SubmissionMode,
+    pub fee_ceiling: FeeCeilingConfig,
+    /// Send to every submission path at once (`submission.broadcast`)
+    pub broadcast: bool,
+    /// Pack with other threads' executions when possible (`submission.batch_exec`)
+    pub batch_exec: bool,
 }
 
 pub struct WorkerState {
@@ -75,6 +118,11 @@ pub struct WorkerState {
     #[allow(dead_code)] // Kept for future cancellation completion signaling
     processor_ref: ActorRef<ProcessorMessage>,
     cancelled: Arc<AtomicBool>, // Flag for cancellation
+    // Set once the execution result has been reported
+    finished: watch::Receiver<bool>,
+    load_balancer: Arc<LoadBalancer>,
+    // Message counts reported to CollectMetrics
+    counters: ActorCounters,
 }
 
 impl Actor for WorkerActor {
@@ -90,6 +138,12 @@ impl Actor for WorkerActor {
         log::debug!("WorkerActor started for thread: {}", args.thread_pubkey);
 
         let cancelled = Arc::new(AtomicBool::new(false));
+        let (finished_tx, finished) = watch::channel(false);
+
+        // Let shutdown wait for this execution
+        args.load_balancer
+            .register_worker(args.thread_pubkey, myself.clone())
+            .await;
 
         let state = WorkerState {
             thread_pubkey: args.thread_pubkey,
@@ -97,6 +151,9 @@ impl Actor for WorkerActor {
             _permit: args.permit,
             processor_ref: args.processor_ref.clone(),
             cancelled: cancelled.clone(),
+            finished,
+            load_balancer: args.load_balancer.clone(),
+            counters: ActorCounters::new(),
         };
 
         // Spawn background task for execution
@@ -108,6 +165,13 @@ impl Actor for WorkerActor {
         let resources = args.resources;
         let executor = args.executor;
         let load_balancer = args.load_balancer;
+        let fiber_execution_timeout = args.fiber_execution_timeout;
+        let submission_timeout = args.submission_timeout;
+        let simulate_before_send = args.simulate_before_send;
+        let submission_mode = args.submission_mode;
+        let fee_ceiling = args.fee_ceiling;
+        let broadcast = args.broadcast;
+        let batch_exec = args.batch_exec;
         let cancelled_flag = cancelled;
         let myself_ref = myself.clone();
 
@@ -121,6 +185,13 @@ impl Actor for WorkerActor {
                 &executor,
                 &load_balancer,
                 &cancelled_flag,
+                fiber_execution_timeout,
+                submission_timeout,
+                simulate_before_send,
+                submission_mode,
+                fee_ceiling,
+                broadcast,
+                batch_exec,
             )
             .await;
 
@@ -132,6 +203,7 @@ impl Actor for WorkerActor {
                     e
                 );
             }
+            let _ = finished_tx.send(true);
 
             // Always stop ourselves so the semaphore permit held in WorkerState is
             // released via drop, even if the completion message failed to deliver.
@@ -147,6 +219,7 @@ impl Actor for WorkerActor {
         message: Self::Msg,
         state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
+        state.counters.record(false);
         match message {
             WorkerMessage::Cancel => {
                 // Set cancellation flag so background task can check it
@@ -155,19 +228,74 @@ impl Actor for WorkerActor {
                 // detect the flag and send completion message
                 Ok(())
             }
+            WorkerMessage::Drain(reply) => {
+                // Reply once the in-flight execution has reported its result,
+                // without blocking the mailbox meanwhile
+                let mut finished = state.finished.clone();
+                tokio::spawn(async move {
+                    // Errors only if the execution task is gone, which means it's done too
+                    let _ = finished.wait_for(|done| *done).await;
+                    let _ = reply.send(Drained);
+                });
+                Ok(())
+            }
+            WorkerMessage::CollectMetrics(tx) => {
+                let stats = state.counters.stats(
+                    WORKER_ACTOR_NAME,
+                    [(
+                        "cancelled",
+                        state.cancelled.load(Ordering::Relaxed) as u8 as f64,
+                    )],
+                );
+                let _ = tx.send(stats);
+                Ok(())
+            }
         }
     }
 
     async fn post_stop(
         &self,
-        _myself: ActorRef<Self::Msg>,
+        myself: ActorRef<Self::Msg>,
         state: &mut Self::State,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        state
+            .load_balancer
+            .unregister_worker(&state.thread_pubkey, &myself)
+            .await;
         log::debug!("WorkerActor for {} stopped", state.thread_pubkey);
         Ok(())
     }
 }
 
+/// Run one fiber simulation or submission step, bounded by `timeout`.
+///
+/// A slow endpoint must not pin the worker, so on timeout the step is
+/// abandoned and the caller reports the failure and moves on.
+async fn with_fiber_timeout<T>(
+    timeout: Duration,
+    fiber_pubkey: &Pubkey,
+    step: &str,
+    fut: impl Future<Output = T>,
+) -> Result<T, String> {
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(output) => Ok(output),
+        Err(_) => {
+            log::warn!(
+                "Fiber {} {} timed out after {:?}",
+                fiber_pubkey,
+                step,
+                start.elapsed()
+            );
+            Err(format!(
+                "Fiber {} timed out after {}ms",
+                step,
+                timeout.as_millis()
+            ))
+        }
+    }
+}
+
 /// Execute a thread with retries and proper error handling
 async fn execute_thread(
     thread_pubkey: Pubkey,
@@ -178,6 +306,13 @@ async fn execute_thread(
     executor: &ExecutorLogic,
     load_balancer: &LoadBalancer,
     cancelled: &AtomicBool,
+    fiber_timeout: Duration,
+    submission_timeout: SubmissionTimeout,
+    simulate_before_send: bool,
+    submission_mode: SubmissionMode,
+    fee_ceiling: FeeCeilingConfig,
+    broadcast: bool,
+    batch_exec: bool,
 ) -> ExecutionResult {
     // Check cancellation before starting
     if cancelled.load(Ordering::Relaxed) {
@@ -217,6 +352,25 @@ async fn execute_thread(
         None => thread.last_executor, // Fall back to original if not in cache
     };
 
+    // Reimbursing threads that can't pay would only fail on-chain
+    match executor.can_reimburse(&thread_pubkey, &thread).await {
+        Ok(true) => {}
+        Ok(false) => {
+            log::warn!(
+                "Thread {} cannot reimburse execution fees, skipping",
+                thread_pubkey
+            );
+            return ExecutionResult::failed(thread_pubkey, UNDERFUNDED_ERROR.to_string(), 0);
+        }
+        Err(e) => {
+            log::debug!(
+                "Funding check failed for thread {}: {}, proceeding",
+                thread_pubkey,
+                e
+            );
+        }
+    }
+
     // Check load balancer decision with fresh last_executor
     let decision = match load_balancer
         .should_process(
@@ -295,6 +449,16 @@ async fn execute_thread(
         }
     }
 
+    log::debug!(
+        "{}: submission timeout {:?} (trigger period {:?})",
+        thread_pubkey,
+        submission_timeout.confirmation,
+        submission_timeout.period
+    );
+
+    // One fee payer signs the whole execution, continuations included
+    let executor = &executor.next_fee_payer();
+
     // Build and submit loop.
     // Each iteration builds one transaction batch, submits it, and confirms it.
     // If the executor signals continuation (instructions didn't fit in one tx),
@@ -304,6 +468,7 @@ async fn execute_thread(
     let mut batch_num = 0u32;
     let mut max_priority_fee: u64 = 0;
     let mut pending_fiber_cursor: Option<u8> = None;
+    let mut last_signature = None;
 
     loop {
         batch_num += 1;
@@ -317,8 +482,11 @@ async fn execute_thread(
             break;
         }
 
+        let fiber_index = pending_fiber_cursor.unwrap_or(thread.fiber_cursor);
+        let fiber_pubkey = FiberState::pubkey(thread_pubkey, fiber_index);
+
         // Build batch — first iteration uses trigger retry, subsequent don't need it
-        let (ixs, priority_fee, needs_continuation, next_cursor) = if batch_num == 1 {
+        let (ixs, priority_fee, needs_continuation, next_cursor, stored_units) = if batch_num == 1 {
             let trigger_retry_deadline =
                 Instant::now() + Duration::from_secs(TRIGGER_RETRY_DEADLINE_SECS);
             loop {
@@ -336,12 +504,21 @@ async fn execute_thread(
                         0,
                     );
                 }
-                match executor
-                    .build_execute_transaction(&thread_pubkey, &thread, pending_fiber_cursor)
-                    .await
+                match with_fiber_timeout(
+                    fiber_timeout,
+                    &fiber_pubkey,
+                    "simulation",
+                    executor.build_execute_transaction(
+                        &thread_pubkey,
+                        &thread,
+                        pending_fiber_cursor,
+                    ),
+                )
+                .await
                 {
-                    Ok(result) => break result,
-                    Err(e) => {
+                    Err(e) => return ExecutionResult::failed(thread_pubkey, e, 0),
+                    Ok(Ok(result)) => break result,
+                    Ok(Err(e)) => {
                         let error_str = e.to_string();
                         if is_trigger_not_ready_error(&error_str) {
                             log::debug!(
@@ -366,6 +543,28 @@ async fn execute_thread(
                                 thread_pubkey,
                                 e
                             );
+                            if is_fiber_failure_error(&error_str) {
+                                match executor
+                                    .report_fiber_failure(&thread_pubkey, &thread, fiber_index)
+                                    .await
+                                {
+                                    Ok(Some(signature)) => log::info!(
+                                        "{}: reported fiber {} failure: {}",
+                                        thread_pubkey,
+                                        fiber_index,
+                                        signature
+                                    ),
+                                    Ok(None) => {}
+                                    // Rejected until the thread has stalled for
+                                    // a whole commission window
+                                    Err(e) => log::debug!(
+                                        "{}: fiber {} failure not reported: {}",
+                                        thread_pubkey,
+                                        fiber_index,
+                                        e
+                                    ),
+                                }
+                            }
                             return ExecutionResult::failed(
                                 thread_pubkey,
                                 format!("Transaction build failed: {}", e),
@@ -377,12 +576,17 @@ async fn execute_thread(
             }
         } else {
             // Continuation batch — build against fresh on-chain state
-            match executor
-                .build_execute_transaction(&thread_pubkey, &thread, pending_fiber_cursor)
-                .await
+            match with_fiber_timeout(
+                fiber_timeout,
+                &fiber_pubkey,
+                "simulation",
+                executor.build_execute_transaction(&thread_pubkey, &thread, pending_fiber_cursor),
+            )
+            .await
             {
-                Ok(result) => result,
-                Err(e) => {
+                Err(e) => return ExecutionResult::failed(thread_pubkey, e, 0),
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) => {
                     log::error!(
                         "{}: continuation batch {} build failed: {:?}",
                         thread_pubkey,
@@ -419,33 +623,141 @@ async fn execute_thread(
             needs_continuation
         );
 
-        // Simulate for accurate CU estimate
-        let cu_estimate = match executor.estimate_compute_units(&ixs, &thread_pubkey).await {
-            Ok(units) => units,
-            Err(e) => {
-                log::error!(
-                    "{}: batch {} CU estimation failed: {:?}",
+        // Size the compute budget from the fibers' recorded estimates, and
+        // simulate only when a fiber in the batch has none
+        let compute_units = match stored_units {
+            Some(units) => {
+                log::debug!(
+                    "{}: batch {} uses recorded compute estimates ({} units)",
                     thread_pubkey,
                     batch_num,
-                    e
-                );
-                return ExecutionResult::failed(
-                    thread_pubkey,
-                    format!("Batch {} CU estimation failed: {}", batch_num, e),
-                    0,
+                    units
                 );
+                stored_compute_limit(units, ixs.len())
             }
+            None => match with_fiber_timeout(
+                fiber_timeout,
+                &fiber_pubkey,
+                "simulation",
+                executor.estimate_compute_units(&ixs, &thread_pubkey),
+            )
+            .await
+            {
+                Err(e) => return ExecutionResult::failed(thread_pubkey, e, 0),
+                Ok(Ok(units)) => ((units as f64) * 1.1) as u32,
+                Ok(Err(e)) => {
+                    log::error!(
+                        "{}: batch {} CU estimation failed: {:?}",
+                        thread_pubkey,
+                        batch_num,
+                        e
+                    );
+                    return ExecutionResult::failed(
+                        thread_pubkey,
+                        format!("Batch {} CU estimation failed: {}", batch_num, e),
+                        0,
+                    );
+                }
+            },
         };
 
         // Prepend compute budget instructions
-        let compute_units = ((cu_estimate as f64) * 1.1) as u32;
         let mut final_ixs = vec![ComputeBudgetInstruction::set_compute_unit_limit(
             compute_units,
         )];
-        if max_priority_fee > 0 {
-            final_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
-                max_priority_fee,
-            ));
+        let cu_price = match cap_priority_fee(
+            executor,
+            resources,
+            &fee_ceiling,
+            &thread_pubkey,
+            max_priority_fee,
+            compute_units,
+        )
+        .await
+        {
+            Some(price) => price,
+            None => {
+                return ExecutionResult::failed(
+                    thread_pubkey,
+                    FEE_CEILING_DEFERRED_ERROR.to_string(),
+                    0,
+                )
+            }
+        };
+        if cu_price > 0 {
+            final_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(cu_price));
+        }
+
+        // The same thread state may arrive from more than one datasource.
+        // Claimed only now, so an execution that bailed out before submitting
+        // does not block the next attempt at the same state.
+        if batch_num == 1
+            && !resources
+                .submission_dedup
+                .check_and_insert(&thread_pubkey, &thread)
+        {
+            log::debug!(
+                "Thread {} already submitting this state, dropping duplicate",
+                thread_pubkey
+            );
+            return ExecutionResult::failed(
+                thread_pubkey,
+                DUPLICATE_EXECUTION_ERROR.to_string(),
+                0,
+            );
+        }
+
+        // A whole execution in one transaction can be packed with other threads'
+        if batch_exec && batch_num == 1 && !needs_continuation {
+            let entry = BatchEntry {
+                thread_pubkey,
+                fiber_pubkey,
+                fiber_cursor: fiber_index,
+                submission_timeout,
+                payer: executor.pubkey(),
+                instructions: ixs.clone(),
+                compute_units,
+                cu_price,
+                broadcast,
+            };
+            match execute_batched(
+                entry,
+                executor,
+                resources,
+                load_balancer,
+                fiber_timeout,
+                simulate_before_send,
+                submission_mode,
+            )
+            .await
+            {
+                BatchOutcome::Confirmed(sig) => {
+                    log::info!(
+                        "{}: confirmed in packed transaction ({})",
+                        thread_pubkey,
+                        sig
+                    );
+                    let _ = load_balancer
+                        .record_execution_result(
+                            &thread_pubkey,
+                            true,
+                            chrono::Utc::now().timestamp(),
+                        )
+                        .await;
+                    record_confirmed_costs(resources, thread_pubkey, sig);
+                    last_signature = Some(sig);
+                    break;
+                }
+                BatchOutcome::Failed(error) => {
+                    record_failed_costs(resources, &thread_pubkey);
+                    return ExecutionResult::failed(
+                        thread_pubkey,
+                        format!("Packed transaction failed: {}", error),
+                        1,
+                    );
+                }
+                BatchOutcome::Unbatched => {}
+            }
         }
         final_ixs.extend_from_slice(&ixs);
 
@@ -457,13 +769,24 @@ async fn execute_thread(
             cancelled,
             &thread_pubkey,
             load_balancer,
+            fiber_timeout,
+            &fiber_pubkey,
+            fiber_index,
+            submission_timeout,
+            simulate_before_send,
+            submission_mode,
+            broadcast,
         )
         .await
         {
             Ok(sig) => {
                 log::info!("{}: batch {} confirmed ({})", thread_pubkey, batch_num, sig);
+                record_confirmed_costs(resources, thread_pubkey, sig);
+                results::publish_confirmed_result(resources, thread_pubkey, fiber_index, sig);
+                last_signature = Some(sig);
             }
             Err((error, attempts)) => {
+                record_failed_costs(resources, &thread_pubkey);
                 return ExecutionResult::failed(
                     thread_pubkey,
                     format!("Batch {} failed: {}", batch_num, error),
@@ -499,13 +822,317 @@ async fn execute_thread(
         };
     }
 
+    if let Some(signature) = last_signature {
+        record_confirmed_execution(resources, thread_pubkey, &thread, &signature).await;
+    }
     ExecutionResult::success(thread_pubkey)
 }
 
+/// Apply the priority fee ceiling to a batch's compute unit price
+///
+/// Returns the price to send with, or `None` if the execution should be
+/// deferred because even the ceiling would not plausibly land it.
+async fn cap_priority_fee(
+    executor: &ExecutorLogic,
+    resources: &SharedResources,
+    fee_ceiling: &FeeCeilingConfig,
+    thread_pubkey: &Pubkey,
+    cu_price: u64,
+    cu_limit: u32,
+) -> Option<u64> {
+    if cu_price == 0 {
+        return Some(0);
+    }
+
+    // Only the balance-relative cap needs the thread's balance
+    let balance = if fee_ceiling.max_balance_fraction.is_some() {
+        match executor.thread_balance(thread_pubkey).await {
+            Ok(lamports) => Some(lamports),
+            Err(e) => {
+                log::debug!(
+                    "{}: balance unavailable for fee ceiling: {}",
+                    thread_pubkey,
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let decision = apply_fee_ceiling(fee_ceiling, cu_price, cu_limit, balance);
+    resources.fee_metrics.record(&decision);
+
+    match decision {
+        FeeDecision::Unchanged => Some(cu_price),
+        FeeDecision::Clamped {
+            cu_price: clamped,
+            ceiling_lamports,
+        } => {
+            log::warn!(
+                "{}: priority fee clamped to ceiling of {} lamports (CU price {} -> {})",
+                thread_pubkey,
+                ceiling_lamports,
+                cu_price,
+                clamped
+            );
+            Some(clamped)
+        }
+        FeeDecision::Defer { ceiling_lamports } => {
+            log::warn!(
+                "{}: fee ceiling of {} lamports is below the viable CU price, deferring",
+                thread_pubkey,
+                ceiling_lamports
+            );
+            None
+        }
+    }
+}
+
+/// Hand an execution to the shared batcher and wait for its outcome
+///
+/// The worker that opens a batch packs and submits everything collected in
+/// the window; the others wait for it to report back.
+async fn execute_batched(
+    entry: BatchEntry,
+    executor: &ExecutorLogic,
+    resources: &SharedResources,
+    load_balancer: &LoadBalancer,
+    fiber_timeout: Duration,
+    simulate_before_send: bool,
+    submission_mode: SubmissionMode,
+) -> BatchOutcome {
+    let (outcome, opened) = resources.exec_batcher.join(entry);
+    if opened {
+        let pending = resources.exec_batcher.collect().await;
+        submit_packed(
+            pending,
+            executor,
+            resources,
+            load_balancer,
+            fiber_timeout,
+            simulate_before_send,
+            submission_mode,
+        )
+        .await;
+    }
+    // A dropped reply means the batch was abandoned; go it alone
+    outcome.await.unwrap_or(BatchOutcome::Unbatched)
+}
+
+/// Pack collected executions into transactions, submit them, and resolve
+/// every execution
+///
+/// Only executions built for the same fee payer share a transaction, each
+/// group signed by its own payer. Executions that end up alone are handed
+/// back unbatched. When a packed transaction fails, the thread owning the
+/// failing instruction is failed and the rest are handed back to submit on
+/// their own.
+async fn submit_packed(
+    pending: Vec<PendingExec>,
+    executor: &ExecutorLogic,
+    resources: &SharedResources,
+    load_balancer: &LoadBalancer,
+    fiber_timeout: Duration,
+    simulate_before_send: bool,
+    submission_mode: SubmissionMode,
+) {
+    let mut by_payer: Vec<(Pubkey, Vec<PendingExec>)> = Vec::new();
+    for exec in pending {
+        match by_payer
+            .iter_mut()
+            .find(|(payer, _)| *payer == exec.entry.payer)
+        {
+            Some((_, execs)) => execs.push(exec),
+            None => by_payer.push((exec.entry.payer, vec![exec])),
+        }
+    }
+
+    let submissions = by_payer.into_iter().map(|(payer, execs)| async move {
+        match executor.signed_by(&payer) {
+            Some(executor) => {
+                submit_packed_for_payer(
+                    execs,
+                    &executor,
+                    resources,
+                    load_balancer,
+                    fiber_timeout,
+                    simulate_before_send,
+                    submission_mode,
+                )
+                .await
+            }
+            None => {
+                for exec in execs {
+                    exec.resolve(BatchOutcome::Unbatched);
+                }
+            }
+        }
+    });
+    futures::future::join_all(submissions).await;
+}
+
+/// [`submit_packed`] for executions that all pay with `executor`'s keypair
+async fn submit_packed_for_payer(
+    pending: Vec<PendingExec>,
+    executor: &ExecutorLogic,
+    resources: &SharedResources,
+    load_balancer: &LoadBalancer,
+    fiber_timeout: Duration,
+    simulate_before_send: bool,
+    submission_mode: SubmissionMode,
+) {
+    let metrics = resources.exec_batcher.metrics();
+    let unbatched = move |execs: Vec<PendingExec>| {
+        metrics
+            .unbatched
+            .fetch_add(execs.len() as u64, Ordering::Relaxed);
+        for exec in execs {
+            exec.resolve(BatchOutcome::Unbatched);
+        }
+    };
+
+    if pending.len() < 2 {
+        return unbatched(pending);
+    }
+    let shared = match executor.shared_exec_accounts().await {
+        Ok(accounts) => accounts.into_iter().collect(),
+        Err(e) => {
+            log::debug!("Cannot pack executions without thread config: {}", e);
+            return unbatched(pending);
+        }
+    };
+
+    let entries: Vec<BatchEntry> = pending.iter().map(|exec| exec.entry.clone()).collect();
+    let groups = pack(&entries, &executor.pubkey(), &shared);
+    let mut slots: Vec<Option<PendingExec>> = pending.into_iter().map(Some).collect();
+    let groups: Vec<Vec<PendingExec>> = groups
+        .into_iter()
+        .map(|group| group.into_iter().filter_map(|i| slots[i].take()).collect())
+        .collect();
+
+    // Packed transactions don't depend on each other, so send them together
+    let sends = groups.into_iter().map(move |group| async move {
+        if group.len() < 2 {
+            return unbatched(group);
+        }
+
+        let compute_units = group
+            .iter()
+            .map(|exec| exec.entry.compute_units)
+            .fold(0u32, u32::saturating_add);
+        let cu_price = group
+            .iter()
+            .map(|exec| exec.entry.cu_price)
+            .max()
+            .unwrap_or(0);
+        let mut ixs = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            compute_units,
+        )];
+        if cu_price > 0 {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_price(cu_price));
+        }
+        let prefix = ixs.len();
+        for exec in &group {
+            ixs.extend_from_slice(&exec.entry.instructions);
+        }
+
+        let lead = &group[0].entry;
+        // Packed threads all wait as long as the most impatient of them
+        let submission_timeout = group
+            .iter()
+            .map(|exec| exec.entry.submission_timeout)
+            .min_by_key(|timeout| timeout.confirmation)
+            .unwrap_or(lead.submission_timeout)
+            .with_leader_hold(
+                group
+                    .iter()
+                    .map(|exec| exec.entry.submission_timeout.leader_hold)
+                    .min()
+                    .unwrap_or_default(),
+            );
+        log::info!(
+            "{}: submitting packed transaction with {} threads",
+            lead.thread_pubkey,
+            group.len()
+        );
+        // One worker's cancellation must not abort the others' executions
+        let not_cancelled = AtomicBool::new(false);
+        let result = submit_and_confirm_batch(
+            &ixs,
+            executor,
+            resources,
+            &not_cancelled,
+            &lead.thread_pubkey,
+            load_balancer,
+            fiber_timeout,
+            &lead.fiber_pubkey,
+            lead.fiber_cursor,
+            submission_timeout,
+            simulate_before_send,
+            submission_mode,
+            group.iter().any(|exec| exec.entry.broadcast),
+        )
+        .await;
+
+        match result {
+            Ok(sig) => {
+                metrics.record_transaction(group.len());
+                for exec in group {
+                    results::publish_confirmed_result(
+                        resources,
+                        exec.entry.thread_pubkey,
+                        exec.entry.fiber_cursor,
+                        sig,
+                    );
+                    exec.resolve(BatchOutcome::Confirmed(sig));
+                }
+            }
+            Err((error, _)) => {
+                let lengths: Vec<usize> = group
+                    .iter()
+                    .map(|exec| exec.entry.instructions.len())
+                    .collect();
+                let culprit = failed_instruction(&error)
+                    .and_then(|index| responsible_member(index, prefix, &lengths));
+                log::warn!(
+                    "Packed transaction of {} threads failed ({}): {}",
+                    group.len(),
+                    culprit
+                        .map(|i| group[i].entry.thread_pubkey.to_string())
+                        .unwrap_or_else(|| "unattributed".to_string()),
+                    error
+                );
+
+                let mut rest = Vec::with_capacity(group.len());
+                for (i, exec) in group.into_iter().enumerate() {
+                    if Some(i) == culprit {
+                        exec.resolve(BatchOutcome::Failed(error.clone()));
+                    } else {
+                        rest.push(exec);
+                    }
+                }
+                unbatched(rest);
+            }
+        }
+    });
+    futures::future::join_all(sends).await;
+}
+
 /// Submit a batch of instructions as a transaction, with retries and confirmation.
 ///
-/// Handles: get blockhash, build+sign transaction, TPU send + confirmation polling,
-/// RPC fallback, retry up to MAX_ATTEMPTS.
+/// Handles: get blockhash, build+sign transaction, TPU send + confirmation polling
+/// (`TpuClient::send_transaction_with_confirmation`),
+/// RPC fallback, retry up to MAX_ATTEMPTS. An RPC send that exceeds `fiber_timeout`
+/// aborts the batch. With `simulate_before_send`, the signed transaction is
+/// simulated before TPU submission (TPU has no preflight) and not sent if it fails.
+/// With `SubmissionMode::Both`, the initial send goes out via TPU and RPC concurrently.
+/// With `broadcast`, every send goes out via TPU and all healthy submission endpoints.
+/// Each signed transaction and its outcome go to the transaction log, if enabled.
+/// Each attempt waits up to `submission_timeout.confirmation` to confirm, and no
+/// attempt starts that couldn't confirm before the thread's next scheduled fire.
+/// A TPU send may first wait up to `submission_timeout.leader_hold` for a warm leader.
 ///
 /// Returns Ok(signature) on success, Err((error_msg, attempts)) on failure.
 async fn submit_and_confirm_batch(
@@ -515,11 +1142,35 @@ async fn submit_and_confirm_batch(
     cancelled: &AtomicBool,
     thread_pubkey: &Pubkey,
     load_balancer: &LoadBalancer,
+    fiber_timeout: Duration,
+    fiber_pubkey: &Pubkey,
+    fiber_cursor: u8,
+    submission_timeout: SubmissionTimeout,
+    simulate_before_send: bool,
+    submission_mode: SubmissionMode,
+    broadcast: bool,
 ) -> Result<Signature, (String, u32)> {
     let mut attempt = 0u32;
     let mut last_error = String::new();
+    let submit_start = Instant::now();
+    let (tpu_path, rpc_path) = match submission_mode {
+        _ if broadcast => (metrics::PATH_BROADCAST, metrics::PATH_BROADCAST),
+        SubmissionMode::Both => (metrics::PATH_BOTH, metrics::PATH_RPC),
+        SubmissionMode::TpuFirst => (metrics::PATH_TPU, metrics::PATH_RPC),
+    };
 
     while attempt < MAX_ATTEMPTS {
+        // A retry that runs into the next fire would race that execution
+        if attempt > 0 && !submission_timeout.allows_retry(submit_start.elapsed()) {
+            log::debug!(
+                "{}: not retrying, the next scheduled fire is due before another attempt could confirm",
+                thread_pubkey
+            );
+            return Err((
+                format!("Not retrying past the next scheduled fire: {}", last_error),
+                attempt,
+            ));
+        }
         attempt += 1;
 
         // Check cancellation
@@ -557,73 +1208,184 @@ async fn submit_and_confirm_batch(
             }
         };
 
-        // Build and sign transaction
-        let message = Message::new(instructions, Some(&executor.pubkey()));
-        let tx = Transaction::new(&[executor.keypair().as_ref()], message, blockhash);
+        // Build and sign transaction; TPU and RPC submission carry legacy
+        // transactions only
+        let strategy = TxStrategy::LegacyBlockhash;
+        let tx = TransactionBuilder::new(instructions, executor.pubkey())
+            .with_strategy(strategy)
+            .build_legacy(executor.keypair(), blockhash);
+        metrics::record_submission_strategy(strategy.as_str());
 
         // Compute signature before sending (needed for confirmation polling)
         // TPU submission is fire-and-forget so we need the signature upfront
         let signature = tx.signatures[0];
+        let tpu_enabled = resources
+            .tpu_client
+            .as_ref()
+            .is_some_and(|tpu| tpu.is_enabled());
+        let submission = TxSubmission::new(
+            signature,
+            *thread_pubkey,
+            fiber_cursor,
+            if tpu_enabled { tpu_path } else { rpc_path },
+            instructions,
+        );
+        log_tx(resources, &submission, TxPhase::Submitted, None);
 
         log::info!("{}: sent", thread_pubkey);
         log::debug!("  txn: {}", signature);
 
         // TPU retry loop: send via TPU and poll for confirmation, re-sending every 2s
         // This handles the case where TPU send appears to succeed but transaction doesn't land
+        // TPU is skipped while auto-disable has it cooling down
         let mut tpu_confirmed = false;
-        if let Some(tpu_client) = &resources.tpu_client {
-            let start = Instant::now();
-            let timeout = Duration::from_secs(CONFIRMATION_TIMEOUT_SECS);
-            let mut last_tpu_send = Instant::now();
-
-            // Initial TPU send
-            if let Err(e) = tpu_client.send_transaction(&tx).await {
-                log::debug!("Initial TPU send failed: {}", e);
-            }
-
-            // Combined send + confirmation polling loop
-            loop {
-                // Check timeout
-                if start.elapsed() > timeout {
-                    log::debug!("TPU confirmation timeout, falling back to RPC");
-                    break;
-                }
-
-                // Re-send via TPU every 2 seconds (may hit different leader)
-                if last_tpu_send.elapsed() > Duration::from_millis(TPU_RETRY_INTERVAL_MS) {
-                    if let Err(e) = tpu_client.send_transaction(&tx).await {
-                        log::debug!("TPU re-send failed: {}", e);
-                    }
-                    last_tpu_send = Instant::now();
-                }
-
-                // Check confirmation
-                match resources.rpc_client.get_signature_status(&signature).await {
-                    Ok(Some(Ok(()))) => {
-                        // Confirmed!
-                        tpu_confirmed = true;
-                        break;
+        if let Some(tpu_client) = resources.tpu_client.as_ref().filter(|tpu| tpu.is_enabled()) {
+            if simulate_before_send {
+                let preflight = match with_fiber_timeout(
+                    fiber_timeout,
+                    fiber_pubkey,
+                    "preflight",
+                    resources.rpc_client.simulate_transaction(&tx),
+                )
+                .await
+                {
+                    Ok(preflight) => preflight,
+                    Err(e) => {
+                        log_tx(resources, &submission, TxPhase::Failed, Some(&e));
+                        return Err((e, attempt));
                     }
-                    Ok(Some(Err(e))) => {
-                        let error_str = format!("{:?}", e);
-                        if is_trigger_not_ready_error(&error_str) {
-                            log::debug!(
-                                "{}: 6004 on-chain (trigger not ready), will retry",
-                                thread_pubkey
-                            );
-                            break;
+                };
+                match preflight {
+                    Ok(result) if !result.success => {
+                        let error = result.error.unwrap_or_default();
+                        for log in &result.logs {
+                            log::debug!("  PREFLIGHT LOG: {}", log);
                         }
-
-                        if is_thread_paused_error(&error_str) {
+                        log_tx(resources, &submission, TxPhase::Failed, Some(&error));
+                        if is_trigger_not_ready_error(&error) {
                             log::debug!(
-                                "{}: 6006 on-chain (thread paused), skipping",
+                                "{}: preflight 6004 (trigger not ready), will retry",
                                 thread_pubkey
                             );
-                            return Err(("Thread is paused".to_string(), attempt));
+                            last_error = format!("Preflight simulation failed: {}", error);
+                            tokio::time::sleep(Duration::from_millis(
+                                BASE_RETRY_DELAY_MS * (1 << attempt.min(4)),
+                            ))
+                            .await;
+                            continue;
                         }
+                        log::warn!(
+                            "{}: preflight simulation failed, not submitting: {}",
+                            thread_pubkey,
+                            error
+                        );
+                        return Err((format!("Preflight simulation failed: {}", error), attempt));
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        // RPC trouble shouldn't block submission; TPU send proceeds
+                        log::debug!("{}: preflight simulation unavailable: {}", thread_pubkey, e);
+                    }
+                }
+            }
+
+            // Wait for a leader that has been landing our transactions, if one
+            // is due within the hold
+            let window = tpu_client.next_good_send_window(submission_timeout.leader_hold);
+            if !window.hold.is_zero() {
+                log::debug!(
+                    "{}: holding send {}ms for a warm leader",
+                    thread_pubkey,
+                    window.hold.as_millis()
+                );
+                tokio::time::sleep(window.hold).await;
+            }
 
+            // Send and poll for confirmation, re-sending via TPU every 2s since
+            // a send can appear to succeed without the transaction landing.
+            // In Both mode the initial send also goes out via RPC; in broadcast mode
+            // it goes to every healthy submission endpoint.
+            let commitment = CommitmentConfig::confirmed();
+            let timeout = tpu_client
+                .confirmation_timeout()
+                .min(submission_timeout.confirmation);
+            let confirmation = match submission_mode {
+                _ if broadcast => {
+                    if let Err(e) = send_broadcast(
+                        Some(tpu_client.as_ref()),
+                        &resources.rpc_client,
+                        &tx,
+                        &resources.submission_metrics,
+                    )
+                    .await
+                    {
+                        log::debug!("Initial broadcast failed: {}", e);
+                    }
+                    tpu_client
+                        .confirm_transaction(&tx, commitment, timeout)
+                        .await
+                }
+                SubmissionMode::TpuFirst => {
+                    tpu_client
+                        .send_transaction_with_confirmation(&tx, commitment, timeout)
+                        .await
+                }
+                SubmissionMode::Both => {
+                    // Two attempts of one signature: confirm once, then
+                    // credit the path that accepted it first
+                    let winner = match send_both(
+                        tpu_client.send_transaction(&tx),
+                        resources.rpc_client.send_transaction(&tx),
+                    )
+                    .await
+                    {
+                        Ok(winner) => Some(winner),
+                        Err(e) => {
+                            log::debug!("Initial both-mode send failed: {}", e);
+                            None
+                        }
+                    };
+                    let confirmation = tpu_client
+                        .confirm_transaction(&tx, commitment, timeout)
+                        .await;
+                    if let (Ok(_), Some(winner)) = (&confirmation, winner) {
+                        resources
+                            .submission_metrics
+                            .record_both_mode_confirmed(winner);
+                    }
+                    confirmation
+                }
+            };
+
+            metrics::record_alignment_send(
+                window.aligned,
+                matches!(
+                    confirmation,
+                    Ok(_) | Err(TpuError::TransactionFailed { .. })
+                ),
+            );
+            match confirmation {
+                Ok(_) => {
+                    tpu_confirmed = true;
+                }
+                Err(TpuError::TransactionFailed { error, .. }) => {
+                    let error_str = format!("{:?}", error);
+                    log_tx(resources, &submission, TxPhase::Failed, Some(&error_str));
+                    if is_trigger_not_ready_error(&error_str) {
+                        log::debug!(
+                            "{}: 6004 on-chain (trigger not ready), will retry",
+                            thread_pubkey
+                        );
+                    } else if is_thread_paused_error(&error_str) {
+                        log::debug!("{}: 6006 on-chain (thread paused), skipping", thread_pubkey);
+                        return Err(("Thread is paused".to_string(), attempt));
+                    } else {
                         // Other on-chain error - don't retry, return failure
-                        log::warn!("{}: transaction failed on-chain: {:?}", thread_pubkey, e);
+                        log::warn!(
+                            "{}: transaction failed on-chain: {:?}",
+                            thread_pubkey,
+                            error
+                        );
 
                         let _ = load_balancer
                             .record_execution_result(
@@ -633,24 +1395,26 @@ async fn submit_and_confirm_batch(
                             )
                             .await;
 
-                        return Err((format!("Transaction failed on-chain: {:?}", e), attempt));
-                    }
-                    Ok(None) => {
-                        // Not yet confirmed, continue polling
-                    }
-                    Err(e) => {
-                        // RPC error, continue polling
-                        log::debug!("Error checking signature status: {:?}", e);
+                        return Err((format!("Transaction failed on-chain: {:?}", error), attempt));
                     }
                 }
-
-                tokio::time::sleep(Duration::from_millis(500)).await;
+                Err(e) => {
+                    log::debug!("{}, falling back to RPC", e);
+                }
             }
         }
 
         if tpu_confirmed {
+            log_tx(resources, &submission, TxPhase::Confirmed, None);
             log::info!("{}: confirmed", thread_pubkey);
             log::debug!("  txn: {}", signature);
+            metrics::record_submission_latency(tpu_path, submit_start.elapsed());
+            resources
+                .submission_metrics
+                .record_submission_latency(submit_start.elapsed().as_micros() as u64);
+            resources
+                .alert_metrics
+                .record_confirmation(submit_start.elapsed());
 
             // Record success in load balancer
             let _ = load_balancer
@@ -661,12 +1425,42 @@ async fn submit_and_confirm_batch(
         }
 
         // Fall back to RPC if TPU not available or TPU loop timed out
-        match resources.rpc_client.send_transaction(&tx).await {
+        let rpc_send = async {
+            if broadcast {
+                send_broadcast(
+                    None,
+                    &resources.rpc_client,
+                    &tx,
+                    &resources.submission_metrics,
+                )
+                .await
+                .map(|()| signature)
+                .map_err(|e| anyhow::anyhow!(e))
+            } else {
+                resources.rpc_client.send_transaction(&tx).await
+            }
+        };
+        let send_result =
+            match with_fiber_timeout(fiber_timeout, fiber_pubkey, "submission", rpc_send).await {
+                Ok(result) => result,
+                Err(e) => {
+                    log_tx(resources, &submission, TxPhase::Unconfirmed, Some(&e));
+                    return Err((e, attempt));
+                }
+            };
+        match send_result {
             Ok(sig) => {
                 log::debug!("Transaction sent via RPC: {}", sig);
             }
             Err(e) => {
                 last_error = format!("Transaction send failed: {}", e);
+                // An earlier TPU send of this transaction may still land
+                log_tx(
+                    resources,
+                    &submission,
+                    TxPhase::Unconfirmed,
+                    Some(&last_error),
+                );
                 log::warn!(
                     "Failed to send transaction for thread {} (attempt {}): {:?}",
                     thread_pubkey,
@@ -688,12 +1482,24 @@ async fn submit_and_confirm_batch(
         }
 
         // Wait for RPC confirmation
-        match wait_for_confirmation(&resources.rpc_client, &signature, CONFIRMATION_TIMEOUT_SECS)
-            .await
+        match wait_for_confirmation(
+            &resources.rpc_client,
+            &signature,
+            submission_timeout.confirmation,
+        )
+        .await
         {
             Ok(()) => {
+                log_tx(resources, &submission, TxPhase::Confirmed, None);
                 log::info!("{}: confirmed", thread_pubkey);
                 log::debug!("  txn: {}", signature);
+                metrics::record_submission_latency(rpc_path, submit_start.elapsed());
+                resources
+                    .submission_metrics
+                    .record_submission_latency(submit_start.elapsed().as_micros() as u64);
+                resources
+                    .alert_metrics
+                    .record_confirmation(submit_start.elapsed());
 
                 // Record success in load balancer
                 let _ = load_balancer
@@ -704,6 +1510,12 @@ async fn submit_and_confirm_batch(
             }
             Err(e) => {
                 last_error = format!("Confirmation failed: {}", e);
+                let phase = if e.starts_with(CONFIRMATION_TIMEOUT_ERROR) {
+                    TxPhase::Unconfirmed
+                } else {
+                    TxPhase::Failed
+                };
+                log_tx(resources, &submission, phase, Some(&e));
 
                 // 6004/6006 errors are transient or expected — log as DEBUG, not WARN
                 if is_trigger_not_ready_error(&e) {
@@ -717,6 +1529,12 @@ async fn submit_and_confirm_batch(
                         thread_pubkey
                     );
                     return Err(("Thread is paused".to_string(), attempt));
+                } else if is_chain_limit_error(&e) {
+                    log::debug!(
+                        "{}: fiber chain limit on RPC confirmation, stopping",
+                        thread_pubkey
+                    );
+                    return Err((last_error, attempt));
                 } else {
                     log::warn!(
                         "Transaction confirmation failed for thread {} (attempt {}): {:?}",
@@ -757,18 +1575,148 @@ async fn submit_and_confirm_batch(
     Err((last_error, attempt))
 }
 
+/// Append a line for `submission` to the transaction log, if enabled
+fn log_tx(
+    resources: &SharedResources,
+    submission: &TxSubmission,
+    phase: TxPhase,
+    error: Option<&str>,
+) {
+    let Some(tx_log) = &resources.tx_log else {
+        return;
+    };
+    let slot = resources
+        .slot_receiver()
+        .map(|slot| *slot.borrow())
+        .filter(|&slot| slot > 0);
+    if let Err(e) = tx_log.append(&submission.entry(phase, slot, error)) {
+        log::warn!(
+            "{}: failed to log transaction {}: {}",
+            submission.thread,
+            submission.signature,
+            e
+        );
+    }
+}
+
+/// Record a confirmed batch in the cost ledger, if enabled.
+///
+/// Fees and thread payouts are read back from the confirmed transaction in
+/// the background so the worker isn't held up.
+fn record_confirmed_costs(
+    resources: &SharedResources,
+    thread_pubkey: Pubkey,
+    signature: Signature,
+) {
+    let Some(ledger) = resources.cost_ledger.clone() else {
+        return;
+    };
+    let rpc_client = resources.rpc_client.clone();
+
+    tokio::spawn(async move {
+        let now = chrono::Utc::now().timestamp();
+        let (costs, timestamp) = match rpc_client.get_transaction(&signature).await {
+            Ok(Some(tx)) => (
+                ThreadCosts::from_transaction(&tx, &thread_pubkey),
+                tx.block_time.unwrap_or(now),
+            ),
+            result => {
+                if let Err(e) = result {
+                    log::debug!("{}: failed to fetch tx for costs: {}", thread_pubkey, e);
+                }
+                let costs = ThreadCosts {
+                    executions: 1,
+                    ..ThreadCosts::default()
+                };
+                (costs, now)
+            }
+        };
+
+        record_costs(ledger, thread_pubkey, timestamp, costs).await;
+    });
+}
+
+/// Record a confirmed execution in the replay guard, if enabled
+///
+/// The landing slot is read from the signature status. If that fails, the
+/// latest slot the guard has seen stands in; it may be earlier than the
+/// landing slot, which only narrows what the guard refuses.
+async fn record_confirmed_execution(
+    resources: &SharedResources,
+    thread_pubkey: Pubkey,
+    thread: &Thread,
+    signature: &Signature,
+) {
+    let guard = &resources.confirmed_executions;
+    if !guard.is_enabled() {
+        return;
+    }
+
+    let slot = match resources
+        .rpc_client
+        .get_signature_statuses(std::slice::from_ref(signature))
+        .await
+    {
+        Ok(statuses) => statuses.into_iter().flatten().next().map(|s| s.slot),
+        Err(e) => {
+            log::debug!("{}: failed to read landing slot: {}", thread_pubkey, e);
+            None
+        }
+    }
+    .unwrap_or_else(|| guard.latest_slot());
+
+    guard.record(thread_pubkey, slot, thread.exec_count);
+}
+
+/// Record a failed batch in the cost ledger, if enabled.
+fn record_failed_costs(resources: &SharedResources, thread_pubkey: &Pubkey) {
+    let Some(ledger) = resources.cost_ledger.clone() else {
+        return;
+    };
+
+    let thread_pubkey = *thread_pubkey;
+    let now = chrono::Utc::now().timestamp();
+    tokio::spawn(record_costs(
+        ledger,
+        thread_pubkey,
+        now,
+        ThreadCosts::failure(),
+    ));
+}
+
+/// Write to the cost ledger off the async runtime, since it does file I/O
+async fn record_costs(
+    ledger: Arc<CostLedger>,
+    thread_pubkey: Pubkey,
+    timestamp: i64,
+    costs: ThreadCosts,
+) {
+    let result =
+        tokio::task::spawn_blocking(move || ledger.record(&thread_pubkey, timestamp, costs)).await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => log::warn!("{}: failed to record execution costs: {}", thread_pubkey, e),
+        Err(e) => log::warn!("{}: cost ledger task failed: {}", thread_pubkey, e),
+    }
+}
+
+/// Prefix of the error `wait_for_confirmation` returns when it gives up
+const CONFIRMATION_TIMEOUT_ERROR: &str = "Confirmation timeout";
+
 /// Wait for transaction confirmation with timeout
 async fn wait_for_confirmation(
     rpc_client: &crate::rpc::RpcPool,
     signature: &solana_sdk::signature::Signature,
-    timeout_secs: u64,
+    timeout: Duration,
 ) -> Result<(), String> {
     let start = std::time::Instant::now();
-    let timeout = Duration::from_secs(timeout_secs);
 
     loop {
         if start.elapsed() > timeout {
-            return Err(format!("Confirmation timeout after {}s", timeout_secs));
+            return Err(format!(
+                "{} after {:?}",
+                CONFIRMATION_TIMEOUT_ERROR, timeout
+            ));
         }
 
         match rpc_client.get_signature_status(signature).await {
@@ -788,3 +1736,537 @@ async fn wait_for_confirmation(
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::batch::ExecBatcher;
+    use crate::fee_payers::FeePayers;
+    use crate::load_balancer::LoadBalancerConfig;
+    use crate::rpc::mock::spawn_mock_rpc_sequence;
+    use crate::rpc::RpcPool;
+    use crate::tx_log::{TxLog, TxLogEntry};
+    use anchor_lang::AccountSerialize;
+    use antegen_thread_program::state::{
+        Schedule, Signal, ThreadConfig, Trigger, CURRENT_CONFIG_VERSION, CURRENT_THREAD_VERSION,
+    };
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::message::Message;
+    use solana_sdk::signature::{Keypair, Signer};
+    use tokio::sync::{mpsc, Semaphore};
+
+    const RPC_ERROR: &str =
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#;
+    const BLOCKHASH: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"blockhash":"11111111111111111111111111111111","lastValidBlockHeight":100}}}"#;
+    const CONFIRMED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":3},"value":[{"slot":2,"confirmations":1,"err":null,"status":{"Ok":null},"confirmationStatus":"confirmed"}]}}"#;
+    const SEND_OK: &str = r#"{"jsonrpc":"2.0","id":1,"result":"1111111111111111111111111111111111111111111111111111111111111111"}"#;
+    const FAILED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":3},"value":[{"slot":2,"confirmations":0,"err":{"InstructionError":[2,{"Custom":6001}]},"status":{"Err":{}},"confirmationStatus":"processed"}]}}"#;
+    const SIMULATION_FAILED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":{"InstructionError":[2,{"Custom":6001}]},"logs":[],"unitsConsumed":0}}}"#;
+    const TRANSACTION: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"slot":7,"blockTime":null,"meta":{"err":null,"fee":5000,"preBalances":[],"postBalances":[],"logMessages":[],"returnData":null},"transaction":{"message":{"accountKeys":[]}}}}"#;
+
+    /// Compute budget instructions followed by a thread program instruction
+    fn exec_instructions(executor: &Pubkey) -> Vec<Instruction> {
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            Instruction::new_with_bytes(
+                antegen_thread_program::ID,
+                &[1, 2, 3],
+                vec![
+                    AccountMeta::new(*executor, true),
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                ],
+            ),
+        ]
+    }
+
+    async fn submit_with_mock_tpu(
+        bodies: Vec<&'static str>,
+        simulate_before_send: bool,
+        submission_mode: SubmissionMode,
+    ) -> (
+        Result<Signature, (String, u32)>,
+        Vec<Instruction>,
+        Pubkey,
+        SharedResources,
+        Arc<crate::tpu::MockTpuSink>,
+    ) {
+        let (url, _) = spawn_mock_rpc_sequence(bodies).await;
+        let (resources, sink) =
+            SharedResources::with_mock_tpu(Arc::new(RpcPool::with_url(url).unwrap()));
+        let keypair = Arc::new(Keypair::new());
+        let executor_pubkey = keypair.pubkey();
+        let executor = ExecutorLogic::new(keypair, resources.clone(), false);
+        let load_balancer = LoadBalancer::new(executor_pubkey, LoadBalancerConfig::default());
+        let instructions = exec_instructions(&executor_pubkey);
+
+        let result = submit_and_confirm_batch(
+            &instructions,
+            &executor,
+            &resources,
+            &AtomicBool::new(false),
+            &Pubkey::new_unique(),
+            &load_balancer,
+            Duration::from_secs(5),
+            &Pubkey::new_unique(),
+            0,
+            SubmissionTimeout::fixed(Duration::from_secs(30)),
+            simulate_before_send,
+            submission_mode,
+            false,
+        )
+        .await;
+        (result, instructions, executor_pubkey, resources, sink)
+    }
+
+    /// Forwards worker results to a channel
+    pub(crate) struct Collector;
+
+    impl Actor for Collector {
+        type Msg = ProcessorMessage;
+        type State = mpsc::UnboundedSender<ExecutionResult>;
+        type Arguments = mpsc::UnboundedSender<ExecutionResult>;
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            tx: Self::Arguments,
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(tx)
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            tx: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            if let ProcessorMessage::WorkerCompleted(result) = message {
+                let _ = tx.send(result);
+            }
+            Ok(())
+        }
+    }
+
+    pub(crate) fn new_thread(last_executor: Pubkey) -> Thread {
+        Thread {
+            version: CURRENT_THREAD_VERSION,
+            bump: 0,
+            authority: Pubkey::new_unique(),
+            id: b"drain".to_vec(),
+            name: "drain".to_string(),
+            created_at: 0,
+            trigger: Trigger::Interval {
+                seconds: 60,
+                skippable: false,
+                jitter: 0,
+            },
+            schedule: Schedule::Timed { prev: 0, next: 0 },
+            fiber_ids: vec![0],
+            fiber_cursor: 0,
+            fiber_next_id: 1,
+            fiber_signal: Signal::None,
+            paused: false,
+            exec_count: 0,
+            last_executor,
+            nonce_account: antegen_thread_program::ID,
+            last_nonce: String::new(),
+            close_fiber: Vec::new(),
+            fee_payer_mode: None,
+            consecutive_execs: 0,
+            chain_slot: 0,
+            chain_depth: 0,
+            chain_visited: [0; 4],
+            failing_fiber: 0,
+            fiber_failures: 0,
+            last_failure_slot: 0,
+            disabled_fibers: [0; 4],
+            resume_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_execution() {
+        let (url, _) = spawn_mock_rpc_sequence(vec![RPC_ERROR]).await;
+        let (resources, sink) =
+            SharedResources::with_mock_tpu(Arc::new(RpcPool::with_url(url).unwrap()));
+        // A never-executed thread makes the worker wait this long mid-execution
+        let load_balancer = Arc::new(LoadBalancer::new(
+            Pubkey::new_unique(),
+            LoadBalancerConfig {
+                thread_process_delay: 1,
+                ..Default::default()
+            },
+        ));
+
+        let (results_tx, mut results) = mpsc::unbounded_channel();
+        let (collector, _) = Actor::spawn(None, Collector, results_tx).await.unwrap();
+        let (_clock_tx, clock_rx) = broadcast::channel(1);
+        let permit = Arc::new(Semaphore::new(1)).acquire_owned().await.unwrap();
+
+        let thread_pubkey = Pubkey::new_unique();
+        let args = WorkerArgs {
+            thread_pubkey,
+            thread: new_thread(Pubkey::default()),
+            is_overdue: false,
+            overdue_seconds: 0,
+            permit,
+            processor_ref: collector.clone(),
+            clock_rx,
+            resources: resources.clone(),
+            executor: ExecutorLogic::new(Arc::new(Keypair::new()), resources.clone(), false),
+            load_balancer: load_balancer.clone(),
+            fiber_execution_timeout: Duration::from_secs(1),
+            submission_timeout: SubmissionTimeout::fixed(Duration::from_secs(30)),
+            simulate_before_send: false,
+            submission_mode: SubmissionMode::default(),
+            fee_ceiling: FeeCeilingConfig::default(),
+            broadcast: false,
+            batch_exec: false,
+        };
+        let (_worker, handle) = Actor::spawn(None, WorkerActor, args).await.unwrap();
+
+        // Another executor claims the thread while the worker is waiting
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let mut data = Vec::new();
+        new_thread(Pubkey::new_unique())
+            .try_serialize(&mut data)
+            .unwrap();
+        resources.cache.put_if_newer(thread_pubkey, data, 1).await;
+
+        // Shutdown is signaled mid-execution
+        let start = Instant::now();
+        load_balancer.drain().await;
+        assert!(load_balancer.is_draining());
+        assert!(start.elapsed() >= Duration::from_millis(500));
+
+        // The execution ran to completion before drain returned
+        let result = results.try_recv().unwrap();
+        assert_eq!(result.thread_pubkey, thread_pubkey);
+        assert_eq!(result.error.as_deref(), Some("Claimed during delay"));
+        assert!(sink.transactions().is_empty());
+
+        handle.await.unwrap();
+        collector.stop(None);
+    }
+
+    #[tokio::test]
+    async fn test_submit_sends_signed_transaction_via_tpu() {
+        let (result, instructions, executor, _, sink) =
+            submit_with_mock_tpu(vec![BLOCKHASH, CONFIRMED], false, SubmissionMode::TpuFirst).await;
+
+        let sent = sink.transactions();
+        assert_eq!(sent.len(), 1);
+        let tx = &sent[0];
+        assert_eq!(result.unwrap(), tx.signatures[0]);
+
+        // Signed by the executor alone, as fee payer
+        assert_eq!(tx.message.header.num_required_signatures, 1);
+        assert_eq!(tx.message.account_keys[0], executor);
+        assert!(tx.verify().is_ok());
+
+        // The instructions go out as given, priority fee included
+        assert_eq!(tx.message, Message::new(&instructions, Some(&executor)));
+        let expected = ComputeBudgetInstruction::set_compute_unit_price(5_000);
+        let cu_price = &tx.message.instructions[1];
+        assert_eq!(
+            tx.message.account_keys[cu_price.program_id_index as usize],
+            expected.program_id
+        );
+        assert_eq!(cu_price.data, expected.data);
+    }
+
+    #[tokio::test]
+    async fn test_submit_logs_transaction_and_outcome() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let tx_log = Arc::new(TxLog::open(dir.path().join("tx-log.jsonl"), 1 << 20).unwrap());
+        let (url, _) = spawn_mock_rpc_sequence(vec![BLOCKHASH, CONFIRMED]).await;
+        let (mut resources, _) =
+            SharedResources::with_mock_tpu(Arc::new(RpcPool::with_url(url).unwrap()));
+        resources.tx_log = Some(tx_log.clone());
+        let keypair = Arc::new(Keypair::new());
+        let executor_pubkey = keypair.pubkey();
+        let executor = ExecutorLogic::new(keypair, resources.clone(), false);
+        let load_balancer = LoadBalancer::new(executor_pubkey, LoadBalancerConfig::default());
+        let thread_pubkey = Pubkey::new_unique();
+
+        let signature = submit_and_confirm_batch(
+            &exec_instructions(&executor_pubkey),
+            &executor,
+            &resources,
+            &AtomicBool::new(false),
+            &thread_pubkey,
+            &load_balancer,
+            Duration::from_secs(5),
+            &Pubkey::new_unique(),
+            3,
+            SubmissionTimeout::fixed(Duration::from_secs(30)),
+            false,
+            SubmissionMode::TpuFirst,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let log = std::fs::read_to_string(tx_log.path()).unwrap();
+        let entries: Vec<TxLogEntry> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].phase, TxPhase::Submitted);
+        assert_eq!(entries[1].phase, TxPhase::Confirmed);
+        for entry in &entries {
+            assert_eq!(entry.signature, signature.to_string());
+            assert_eq!(entry.thread, thread_pubkey.to_string());
+            assert_eq!(entry.fiber_cursor, 3);
+            assert_eq!(entry.path, metrics::PATH_TPU);
+        }
+        assert!(tx_log.unresolved().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_signs_with_rotated_fee_payer() {
+        let (url, _) =
+            spawn_mock_rpc_sequence(vec![BLOCKHASH, CONFIRMED, BLOCKHASH, CONFIRMED]).await;
+        let (resources, sink) =
+            SharedResources::with_mock_tpu(Arc::new(RpcPool::with_url(url).unwrap()));
+        let keypairs: Vec<Arc<Keypair>> = (0..3).map(|_| Arc::new(Keypair::new())).collect();
+        let fee_payers = Arc::new(FeePayers::new(keypairs[0].clone(), keypairs[1..].to_vec()));
+        // The second fee payer is drained, so rotation goes primary, third
+        fee_payers.record_balance(&keypairs[1].pubkey(), 0);
+        let executor = ExecutorLogic::new(keypairs[0].clone(), resources.clone(), false)
+            .with_fee_payers(fee_payers.clone());
+        let load_balancer = LoadBalancer::new(keypairs[0].pubkey(), LoadBalancerConfig::default())
+            .with_fee_payers(fee_payers.pubkeys());
+
+        for expected in [&keypairs[0], &keypairs[2]] {
+            let executor = executor.next_fee_payer();
+            assert_eq!(executor.pubkey(), expected.pubkey());
+            let instructions = exec_instructions(&executor.pubkey());
+            submit_and_confirm_batch(
+                &instructions,
+                &executor,
+                &resources,
+                &AtomicBool::new(false),
+                &Pubkey::new_unique(),
+                &load_balancer,
+                Duration::from_secs(5),
+                &Pubkey::new_unique(),
+                0,
+                SubmissionTimeout::fixed(Duration::from_secs(30)),
+                false,
+                SubmissionMode::TpuFirst,
+                false,
+            )
+            .await
+            .unwrap();
+        }
+
+        let sent = sink.transactions();
+        assert_eq!(sent.len(), 2);
+        for (tx, expected) in sent.iter().zip([&keypairs[0], &keypairs[2]]) {
+            // Signed by the rotated fee payer alone, which is also the executor
+            assert_eq!(tx.message.header.num_required_signatures, 1);
+            assert_eq!(tx.message.account_keys[0], expected.pubkey());
+            assert!(tx.verify().is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_skips_tpu_when_preflight_fails() {
+        let (result, _, _, _, sink) = submit_with_mock_tpu(
+            vec![BLOCKHASH, SIMULATION_FAILED],
+            true,
+            SubmissionMode::TpuFirst,
+        )
+        .await;
+
+        let (error, attempts) = result.unwrap_err();
+        assert!(error.starts_with("Preflight simulation failed"));
+        assert_eq!(attempts, 1);
+        assert!(sink.transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_both_mode_credits_one_confirmed_submission() {
+        let (result, _, _, resources, sink) = submit_with_mock_tpu(
+            vec![BLOCKHASH, SEND_OK, CONFIRMED],
+            false,
+            SubmissionMode::Both,
+        )
+        .await;
+
+        // One signature over both paths, confirmed once
+        let signature = result.unwrap();
+        assert_eq!(sink.transactions().len(), 1);
+        assert_eq!(sink.transactions()[0].signatures[0], signature);
+
+        // The in-memory TPU accepts before the RPC round trip completes
+        let metrics = &resources.submission_metrics;
+        assert_eq!(metrics.tpu_wins(), 1);
+        assert_eq!(metrics.rpc_wins(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_both_mode_unconfirmed_credits_no_path() {
+        let (result, _, _, resources, _) = submit_with_mock_tpu(
+            vec![BLOCKHASH, SEND_OK, FAILED],
+            false,
+            SubmissionMode::Both,
+        )
+        .await;
+
+        assert!(result.is_err());
+        let metrics = &resources.submission_metrics;
+        assert_eq!(metrics.tpu_wins() + metrics.rpc_wins(), 0);
+    }
+
+    /// Collects published fiber results
+    struct CollectingSink(Arc<parking_lot::Mutex<Vec<results::FiberResult>>>);
+
+    #[async_trait::async_trait]
+    impl results::ResultSink for CollectingSink {
+        async fn publish(&self, result: &results::FiberResult) -> anyhow::Result<()> {
+            self.0.lock().push(result.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_packed_execution_publishes_results() {
+        let (url, _) = spawn_mock_rpc_sequence(vec![BLOCKHASH, CONFIRMED, TRANSACTION]).await;
+        let (mut resources, sink) =
+            SharedResources::with_mock_tpu(Arc::new(RpcPool::with_url(url).unwrap()));
+        let published = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        resources.result_publisher = Some(Arc::new(results::ResultPublisher::new(vec![Box::new(
+            CollectingSink(published.clone()),
+        )])));
+        resources.exec_batcher = Arc::new(ExecBatcher::new(Duration::from_millis(50)));
+        let keypair = Arc::new(Keypair::new());
+        let executor_pubkey = keypair.pubkey();
+        let executor = ExecutorLogic::new(keypair, resources.clone(), false);
+        let load_balancer = LoadBalancer::new(executor_pubkey, LoadBalancerConfig::default());
+
+        // Packing shares the executor and admin accounts named by the config
+        let mut data = Vec::new();
+        ThreadConfig {
+            version: CURRENT_CONFIG_VERSION,
+            bump: 0,
+            admin: Pubkey::new_unique(),
+            paused: false,
+            commission_fee: 1000,
+            executor_fee_bps: 9000,
+            core_team_bps: 1000,
+            grace_period_seconds: 5,
+            fee_decay_seconds: 295,
+            waive_core_team_on_forgo: false,
+            fee_payer_mode: Default::default(),
+            base_fee_lamports: 5000,
+            pending_admin: None,
+            max_chain_depth: 16,
+            max_fiber_failures: 0,
+            streak_half_life_execs: 1_000,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        resources
+            .cache
+            .put_if_newer(ThreadConfig::pubkey(), data, 1)
+            .await;
+
+        let entry = |fiber_cursor: u8| BatchEntry {
+            thread_pubkey: Pubkey::new_unique(),
+            fiber_pubkey: Pubkey::new_unique(),
+            fiber_cursor,
+            submission_timeout: SubmissionTimeout::fixed(Duration::from_secs(30)),
+            payer: executor_pubkey,
+            instructions: exec_instructions(&executor_pubkey).split_off(2),
+            compute_units: 200_000,
+            cu_price: 5_000,
+            broadcast: false,
+        };
+        let (first, second) = (entry(0), entry(3));
+        let execute = |entry: BatchEntry| {
+            execute_batched(
+                entry,
+                &executor,
+                &resources,
+                &load_balancer,
+                Duration::from_secs(5),
+                false,
+                SubmissionMode::TpuFirst,
+            )
+        };
+        let outcomes = tokio::join!(execute(first.clone()), execute(second.clone()));
+
+        // Both threads went out in one transaction
+        assert_eq!(sink.transactions().len(), 1);
+        let signature = sink.transactions()[0].signatures[0];
+        assert_eq!(outcomes.0, BatchOutcome::Confirmed(signature));
+        assert_eq!(outcomes.1, BatchOutcome::Confirmed(signature));
+
+        // Each thread in the batch gets its own result
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while published.lock().len() < 2 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut published = published.lock().clone();
+        published.sort_by_key(|result| result.fiber_index);
+        assert_eq!(published.len(), 2);
+        for (result, entry) in published.iter().zip([&first, &second]) {
+            assert_eq!(result.thread, entry.thread_pubkey.to_string());
+            assert_eq!(result.fiber_index, entry.fiber_cursor);
+            assert_eq!(result.signature, signature.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fiber_timeout_moves_on() {
+        let fiber_pubkey = Pubkey::new_unique();
+        let start = Instant::now();
+
+        let result = with_fiber_timeout(
+            Duration::from_millis(20),
+            &fiber_pubkey,
+            "simulation",
+            tokio::time::sleep(Duration::from_secs(5)),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "Fiber simulation timed out after 20ms");
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_fiber_timeout_passes_through_result() {
+        let fiber_pubkey = Pubkey::new_unique();
+
+        let result =
+            with_fiber_timeout(Duration::from_secs(1), &fiber_pubkey, "submission", async {
+                42
+            })
+            .await;
+
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn test_is_fiber_failure_error() {
+        assert!(is_fiber_failure_error(
+            "Simulation error: InstructionError(1, ComputationalBudgetExceeded)"
+        ));
+        assert!(is_fiber_failure_error(
+            "Simulation error: InstructionError(1, ProgramFailedToComplete)"
+        ));
+        // Custom codes may be the thread program's own checks
+        assert!(!is_fiber_failure_error(
+            "Simulation error: InstructionError(1, Custom(6004))"
+        ));
+        // Errors in a chained exec or the compute budget instruction
+        assert!(!is_fiber_failure_error(
+            "Simulation error: InstructionError(2, ComputationalBudgetExceeded)"
+        ));
+        assert!(!is_fiber_failure_error("RPC request failed"));
+    }
+}
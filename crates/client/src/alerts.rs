@@ -0,0 +1,734 @@
+//! Alerting on confirmation lag, failing threads, executor balance and
+//! stalled datasources
+//!
+//! Workers, processors and staging feed [`AlertMetrics`] as they go. Every
+//! `alerts.check_interval_secs` the alert service takes an [`AlertSnapshot`]
+//! of it (plus the executor balance) and runs it through the [`AlertEngine`].
+//!
+//! The engine debounces: a rule that starts failing fires one alert, stays
+//! quiet while the condition holds, and sends one recovery notice once it
+//! clears. Alerts go to every configured [`AlertSink`].
+
+use crate::config::{AlertSinkConfig, AlertsConfig};
+use crate::resources::SharedResources;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Confirmation latencies older than this don't count towards the p95
+pub const CONFIRMATION_WINDOW: Duration = Duration::from_secs(300);
+
+/// Most confirmation latencies kept in the window
+const MAX_CONFIRMATION_SAMPLES: usize = 10_000;
+
+/// Timeout for posting an alert to a webhook
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How far above the threshold a low balance must climb to resolve, in
+/// basis points of the threshold
+const BALANCE_RECOVERY_MARGIN_BPS: u64 = 1_000;
+
+/// Condition an alert is raised for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertRule {
+    /// p95 submission-to-confirmation latency above `confirmation_p95_ms`
+    ConfirmationLatency,
+    /// A thread failed `max_consecutive_failures` executions in a row
+    ConsecutiveFailures,
+    /// Executor balance below `min_executor_balance`
+    ExecutorBalance,
+    /// No account update for `max_update_silence_secs`
+    UpdateSilence,
+}
+
+impl AlertRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertRule::ConfirmationLatency => "confirmation_latency",
+            AlertRule::ConsecutiveFailures => "consecutive_failures",
+            AlertRule::ExecutorBalance => "executor_balance",
+            AlertRule::UpdateSilence => "update_silence",
+        }
+    }
+}
+
+/// Whether an alert starts or ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// One alert or recovery notice
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub rule: AlertRule,
+    pub status: AlertStatus,
+    /// Thread the alert is about, for per-thread rules
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            AlertStatus::Firing => "FIRING",
+            AlertStatus::Resolved => "RESOLVED",
+        };
+        write!(f, "[{}] {}: {}", status, self.rule.as_str(), self.message)
+    }
+}
+
+/// Metrics the alert rules are evaluated against
+pub struct AlertMetrics {
+    /// Recent (confirmed at, latency), oldest first
+    confirmations: Mutex<VecDeque<(Instant, Duration)>>,
+    /// Threads whose last executions failed, with the failure streak
+    failures: DashMap<Pubkey, u32>,
+    last_update: Mutex<Option<Instant>>,
+    started: Instant,
+}
+
+impl Default for AlertMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlertMetrics {
+    pub fn new() -> Self {
+        Self {
+            confirmations: Mutex::new(VecDeque::new()),
+            failures: DashMap::new(),
+            last_update: Mutex::new(None),
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the time from submission to confirmation of a transaction
+    pub fn record_confirmation(&self, latency: Duration) {
+        let mut confirmations = self.confirmations.lock();
+        if confirmations.len() >= MAX_CONFIRMATION_SAMPLES {
+            confirmations.pop_front();
+        }
+        confirmations.push_back((Instant::now(), latency));
+    }
+
+    /// Record the outcome of a thread execution
+    pub fn record_execution(&self, thread: Pubkey, success: bool) {
+        if success {
+            self.failures.remove(&thread);
+        } else {
+            *self.failures.entry(thread).or_insert(0) += 1;
+        }
+    }
+
+    /// Note that an account update arrived
+    pub fn record_account_update(&self) {
+        *self.last_update.lock() = Some(Instant::now());
+    }
+
+    /// p95 confirmation latency over the last [`CONFIRMATION_WINDOW`]
+    pub fn confirmation_p95(&self) -> Option<Duration> {
+        let mut confirmations = self.confirmations.lock();
+        while let Some((at, _)) = confirmations.front() {
+            if at.elapsed() <= CONFIRMATION_WINDOW {
+                break;
+            }
+            confirmations.pop_front();
+        }
+        let latencies: Vec<Duration> = confirmations.iter().map(|(_, l)| *l).collect();
+        drop(confirmations);
+        percentile(latencies, 95)
+    }
+
+    /// Threads with a current failure streak
+    pub fn failure_streaks(&self) -> Vec<(Pubkey, u32)> {
+        self.failures
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect()
+    }
+
+    /// Time since the last account update, or since startup if none arrived
+    pub fn update_silence(&self) -> Duration {
+        self.last_update.lock().unwrap_or(self.started).elapsed()
+    }
+}
+
+/// `pct`th percentile (nearest rank) of `values`
+fn percentile(mut values: Vec<Duration>, pct: usize) -> Option<Duration> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let rank = (values.len() * pct).div_ceil(100).max(1);
+    Some(values[rank - 1])
+}
+
+/// Metric values at one evaluation
+#[derive(Debug, Clone, Default)]
+pub struct AlertSnapshot {
+    pub confirmation_p95: Option<Duration>,
+    pub failure_streaks: Vec<(Pubkey, u32)>,
+    /// `None` when the balance could not be fetched
+    pub executor_balance: Option<u64>,
+    pub update_silence: Option<Duration>,
+}
+
+impl AlertSnapshot {
+    pub fn from_metrics(metrics: &AlertMetrics, executor_balance: Option<u64>) -> Self {
+        Self {
+            confirmation_p95: metrics.confirmation_p95(),
+            failure_streaks: metrics.failure_streaks(),
+            executor_balance,
+            update_silence: Some(metrics.update_silence()),
+        }
+    }
+}
+
+/// Evaluates the configured rules and debounces their alerts
+pub struct AlertEngine {
+    config: AlertsConfig,
+    /// Rules (and threads, for per-thread rules) currently firing
+    active: HashSet<(AlertRule, Option<Pubkey>)>,
+}
+
+impl AlertEngine {
+    pub fn new(config: AlertsConfig) -> Self {
+        Self {
+            config,
+            active: HashSet::new(),
+        }
+    }
+
+    /// Evaluate `snapshot`, returning alerts for conditions that started or
+    /// cleared since the previous evaluation
+    ///
+    /// A metric that is missing from the snapshot leaves its rule as it was.
+    pub fn evaluate(&mut self, snapshot: &AlertSnapshot) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        if let (Some(max_ms), Some(p95)) =
+            (self.config.confirmation_p95_ms, snapshot.confirmation_p95)
+        {
+            let max = Duration::from_millis(max_ms);
+            let message = format!("p95 confirmation latency {:?} (threshold {:?})", p95, max);
+            self.transition(
+                AlertRule::ConfirmationLatency,
+                None,
+                p95 > max,
+                message,
+                &mut alerts,
+            );
+        }
+
+        if let Some(max_failures) = self.config.max_consecutive_failures {
+            let failing: Vec<(Pubkey, u32)> = snapshot
+                .failure_streaks
+                .iter()
+                .filter(|(_, streak)| *streak >= max_failures)
+                .copied()
+                .collect();
+            for (thread, streak) in &failing {
+                let message = format!("{} consecutive failed executions", streak);
+                self.transition(
+                    AlertRule::ConsecutiveFailures,
+                    Some(*thread),
+                    true,
+                    message,
+                    &mut alerts,
+                );
+            }
+            let recovered: Vec<Pubkey> = self
+                .active
+                .iter()
+                .filter_map(|(rule, thread)| match (rule, thread) {
+                    (AlertRule::ConsecutiveFailures, Some(thread))
+                        if !failing.iter().any(|(t, _)| t == thread) =>
+                    {
+                        Some(*thread)
+                    }
+                    _ => None,
+                })
+                .collect();
+            for thread in recovered {
+                self.transition(
+                    AlertRule::ConsecutiveFailures,
+                    Some(thread),
+                    false,
+                    "executions succeeding again".to_string(),
+                    &mut alerts,
+                );
+            }
+        }
+
+        if let (Some(floor), Some(balance)) =
+            (self.config.min_executor_balance, snapshot.executor_balance)
+        {
+            let message = format!("executor balance {} lamports (floor {})", balance, floor);
+            self.transition(
+                AlertRule::ExecutorBalance,
+                None,
+                balance < floor,
+                message,
+                &mut alerts,
+            );
+        }
+
+        if let (Some(max_secs), Some(silence)) =
+            (self.config.max_update_silence_secs, snapshot.update_silence)
+        {
+            let message = format!(
+                "no account update for {}s (threshold {}s)",
+                silence.as_secs(),
+                max_secs
+            );
+            self.transition(
+                AlertRule::UpdateSilence,
+                None,
+                silence > Duration::from_secs(max_secs),
+                message,
+                &mut alerts,
+            );
+        }
+
+        alerts
+    }
+
+    /// Emit an alert if `rule` (for `thread`) changed state
+    fn transition(
+        &mut self,
+        rule: AlertRule,
+        thread: Option<Pubkey>,
+        violated: bool,
+        message: String,
+        alerts: &mut Vec<Alert>,
+    ) {
+        let key = (rule, thread);
+        let status = match (violated, self.active.contains(&key)) {
+            (true, false) => {
+                self.active.insert(key);
+                AlertStatus::Firing
+            }
+            (false, true) => {
+                self.active.remove(&key);
+                AlertStatus::Resolved
+            }
+            _ => return,
+        };
+        alerts.push(Alert {
+            rule,
+            status,
+            thread: thread.map(|t| t.to_string()),
+            message,
+        });
+    }
+}
+
+/// Low-balance alerts for a set of signers, as `antegen client balance
+/// --watch` raises them
+///
+/// A signer fires once when its balance drops below the threshold and
+/// resolves once it climbs [`BALANCE_RECOVERY_MARGIN_BPS`] above it, so a
+/// balance hovering around the threshold doesn't alert on every change.
+pub struct BalanceThreshold {
+    threshold: u64,
+    recovery: u64,
+    low: HashSet<Pubkey>,
+}
+
+impl BalanceThreshold {
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            threshold,
+            recovery: threshold
+                .saturating_add(threshold.saturating_mul(BALANCE_RECOVERY_MARGIN_BPS) / 10_000),
+            low: HashSet::new(),
+        }
+    }
+
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    /// Whether `signer` is currently alerting
+    pub fn is_low(&self, signer: &Pubkey) -> bool {
+        self.low.contains(signer)
+    }
+
+    /// Record `signer`'s balance, returning an alert if it crossed the
+    /// threshold or recovered
+    pub fn observe(&mut self, signer: Pubkey, lamports: u64) -> Option<Alert> {
+        let (status, message) = if lamports < self.threshold && self.low.insert(signer) {
+            (
+                AlertStatus::Firing,
+                format!(
+                    "{} balance {} lamports below threshold {}",
+                    signer, lamports, self.threshold
+                ),
+            )
+        } else if lamports >= self.recovery && self.low.remove(&signer) {
+            (
+                AlertStatus::Resolved,
+                format!(
+                    "{} balance {} lamports back above threshold {}",
+                    signer, lamports, self.threshold
+                ),
+            )
+        } else {
+            return None;
+        };
+        Some(Alert {
+            rule: AlertRule::ExecutorBalance,
+            status,
+            thread: None,
+            message,
+        })
+    }
+}
+
+/// Destination for alerts
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    async fn send(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Writes alerts to the log
+pub struct LogSink;
+
+#[async_trait]
+impl AlertSink for LogSink {
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        let thread = alert
+            .thread
+            .as_ref()
+            .map(|t| format!(" thread={}", t))
+            .unwrap_or_default();
+        match alert.status {
+            AlertStatus::Firing => log::warn!("ALERT {}{}", alert, thread),
+            AlertStatus::Resolved => log::info!("ALERT {}{}", alert, thread),
+        }
+        Ok(())
+    }
+}
+
+/// POSTs alerts as JSON
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    /// Send Slack's `{"text": ...}` payload instead of the alert itself
+    slack: bool,
+}
+
+impl WebhookSink {
+    /// Post the alert as JSON to `url`
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        Self::build(url.into(), false)
+    }
+
+    /// Post to a Slack incoming webhook
+    pub fn slack(url: impl Into<String>) -> Result<Self> {
+        Self::build(url.into(), true)
+    }
+
+    fn build(url: String, slack: bool) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+        Ok(Self { client, url, slack })
+    }
+
+    fn payload(&self, alert: &Alert) -> serde_json::Value {
+        if self.slack {
+            let thread = alert
+                .thread
+                .as_ref()
+                .map(|t| format!(" (thread `{}`)", t))
+                .unwrap_or_default();
+            serde_json::json!({ "text": format!("{}{}", alert, thread) })
+        } else {
+            serde_json::json!(alert)
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&self.payload(alert))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Alert webhook returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Build the sinks listed in the config
+pub fn build_sinks(configs: &[AlertSinkConfig]) -> Result<Vec<Box<dyn AlertSink>>> {
+    configs
+        .iter()
+        .map(|config| -> Result<Box<dyn AlertSink>> {
+            Ok(match config {
+                AlertSinkConfig::Log => Box::new(LogSink),
+                AlertSinkConfig::Webhook { url } => Box::new(WebhookSink::new(url)?),
+                AlertSinkConfig::Slack { webhook_url } => {
+                    Box::new(WebhookSink::slack(webhook_url)?)
+                }
+            })
+        })
+        .collect()
+}
+
+/// Evaluate the alert rules until `cancel` fires
+///
+/// The balance rule watches the lowest balance among `executors`, the
+/// primary and any additional fee payers.
+pub async fn run_alert_service(
+    config: AlertsConfig,
+    resources: SharedResources,
+    executors: Vec<Pubkey>,
+    cancel: CancellationToken,
+) {
+    let sinks = match build_sinks(&config.sinks) {
+        Ok(sinks) => sinks,
+        Err(e) => {
+            log::error!("Alerts disabled, failed to build sinks: {}", e);
+            return;
+        }
+    };
+    let check_balance = config.min_executor_balance.is_some();
+    let interval = Duration::from_secs(config.check_interval_secs);
+    let mut engine = AlertEngine::new(config);
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    log::info!("Alert service started with {} sinks", sinks.len());
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = cancel.cancelled() => {
+                log::debug!("Alert service cancelled");
+                return;
+            }
+        }
+
+        let mut balance = None;
+        if check_balance {
+            for executor in &executors {
+                match resources.rpc_client.get_balance(executor).await {
+                    Ok(lamports) => {
+                        balance = Some(balance.map_or(lamports, |b: u64| b.min(lamports)))
+                    }
+                    Err(e) => {
+                        log::debug!("Alerts: failed to fetch {} balance: {}", executor, e);
+                    }
+                }
+            }
+        }
+
+        let snapshot = AlertSnapshot::from_metrics(&resources.alert_metrics, balance);
+        for alert in engine.evaluate(&snapshot) {
+            for sink in &sinks {
+                if let Err(e) = sink.send(&alert).await {
+                    log::warn!("Failed to send alert {}: {}", alert, e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> AlertEngine {
+        AlertEngine::new(AlertsConfig {
+            confirmation_p95_ms: Some(2_000),
+            max_consecutive_failures: Some(3),
+            min_executor_balance: Some(10_000_000),
+            max_update_silence_secs: Some(60),
+            sinks: vec![AlertSinkConfig::Log],
+            ..AlertsConfig::default()
+        })
+    }
+
+    fn statuses(alerts: &[Alert]) -> Vec<(AlertRule, AlertStatus)> {
+        alerts.iter().map(|a| (a.rule, a.status)).collect()
+    }
+
+    #[test]
+    fn test_sustained_condition_alerts_once_then_recovers() {
+        let mut engine = engine();
+        let latency = |ms| AlertSnapshot {
+            confirmation_p95: Some(Duration::from_millis(ms)),
+            ..AlertSnapshot::default()
+        };
+
+        assert!(engine.evaluate(&latency(800)).is_empty());
+        assert_eq!(
+            statuses(&engine.evaluate(&latency(2_500))),
+            vec![(AlertRule::ConfirmationLatency, AlertStatus::Firing)]
+        );
+        // Still lagging: no repeat
+        for ms in [3_000, 4_000, 2_100] {
+            assert!(engine.evaluate(&latency(ms)).is_empty());
+        }
+        // No samples this round: state unchanged
+        assert!(engine.evaluate(&AlertSnapshot::default()).is_empty());
+        assert_eq!(
+            statuses(&engine.evaluate(&latency(900))),
+            vec![(AlertRule::ConfirmationLatency, AlertStatus::Resolved)]
+        );
+        assert!(engine.evaluate(&latency(900)).is_empty());
+    }
+
+    #[test]
+    fn test_failure_streaks_alert_per_thread() {
+        let mut engine = engine();
+        let metrics = AlertMetrics::new();
+        let flaky = Pubkey::new_unique();
+        let broken = Pubkey::new_unique();
+
+        let mut fired = Vec::new();
+        for _ in 0..5 {
+            metrics.record_execution(flaky, false);
+            metrics.record_execution(broken, false);
+            metrics.record_execution(flaky, true);
+            let snapshot = AlertSnapshot::from_metrics(&metrics, None);
+            fired.extend(engine.evaluate(&snapshot));
+        }
+        // Only the thread that never succeeds, and only once
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].rule, AlertRule::ConsecutiveFailures);
+        assert_eq!(fired[0].thread, Some(broken.to_string()));
+        assert!(fired[0].message.starts_with("3 consecutive"));
+
+        metrics.record_execution(broken, true);
+        let resolved = engine.evaluate(&AlertSnapshot::from_metrics(&metrics, None));
+        assert_eq!(
+            statuses(&resolved),
+            vec![(AlertRule::ConsecutiveFailures, AlertStatus::Resolved)]
+        );
+        assert_eq!(resolved[0].thread, Some(broken.to_string()));
+    }
+
+    #[test]
+    fn test_balance_and_silence_rules() {
+        let mut engine = engine();
+        let snapshot = |balance, silence_secs| AlertSnapshot {
+            executor_balance: balance,
+            update_silence: Some(Duration::from_secs(silence_secs)),
+            ..AlertSnapshot::default()
+        };
+
+        assert_eq!(
+            statuses(&engine.evaluate(&snapshot(Some(5_000_000), 90))),
+            vec![
+                (AlertRule::ExecutorBalance, AlertStatus::Firing),
+                (AlertRule::UpdateSilence, AlertStatus::Firing),
+            ]
+        );
+        // Balance fetch failed: the balance alert neither repeats nor resolves
+        assert_eq!(
+            statuses(&engine.evaluate(&snapshot(None, 5))),
+            vec![(AlertRule::UpdateSilence, AlertStatus::Resolved)]
+        );
+        assert_eq!(
+            statuses(&engine.evaluate(&snapshot(Some(50_000_000), 5))),
+            vec![(AlertRule::ExecutorBalance, AlertStatus::Resolved)]
+        );
+    }
+
+    #[test]
+    fn test_balance_threshold_debounces_around_boundary() {
+        let signer = Pubkey::new_unique();
+        let mut threshold = BalanceThreshold::new(500_000_000);
+
+        assert!(threshold.observe(signer, 600_000_000).is_none());
+        let alert = threshold.observe(signer, 499_000_000).unwrap();
+        assert_eq!(alert.rule, AlertRule::ExecutorBalance);
+        assert_eq!(alert.status, AlertStatus::Firing);
+        assert!(threshold.is_low(&signer));
+
+        // Hovering around the threshold stays quiet
+        for lamports in [501_000_000, 498_000_000, 530_000_000, 499_999_999] {
+            assert!(threshold.observe(signer, lamports).is_none());
+        }
+
+        // Recovery needs the margin above the threshold
+        let alert = threshold.observe(signer, 550_000_000).unwrap();
+        assert_eq!(alert.status, AlertStatus::Resolved);
+        assert!(!threshold.is_low(&signer));
+    }
+
+    #[test]
+    fn test_balance_threshold_tracks_signers_separately() {
+        let executor = Pubkey::new_unique();
+        let fee_payer = Pubkey::new_unique();
+        let mut threshold = BalanceThreshold::new(1_000);
+
+        assert!(threshold.observe(executor, 10).is_some());
+        assert!(threshold.observe(fee_payer, 5_000).is_none());
+        assert!(threshold.observe(fee_payer, 10).is_some());
+        assert!(threshold.observe(executor, 20).is_none());
+    }
+
+    #[test]
+    fn test_unconfigured_rules_never_fire() {
+        let mut engine = AlertEngine::new(AlertsConfig::default());
+        let snapshot = AlertSnapshot {
+            confirmation_p95: Some(Duration::from_secs(60)),
+            failure_streaks: vec![(Pubkey::new_unique(), 100)],
+            executor_balance: Some(0),
+            update_silence: Some(Duration::from_secs(3_600)),
+        };
+        assert!(engine.evaluate(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_confirmation_p95() {
+        let metrics = AlertMetrics::new();
+        assert!(metrics.confirmation_p95().is_none());
+        for ms in 1..=100 {
+            metrics.record_confirmation(Duration::from_millis(ms));
+        }
+        assert_eq!(metrics.confirmation_p95(), Some(Duration::from_millis(95)));
+    }
+
+    #[test]
+    fn test_slack_payload() {
+        let sink = WebhookSink::slack("https://hooks.slack.com/services/x").unwrap();
+        let alert = Alert {
+            rule: AlertRule::UpdateSilence,
+            status: AlertStatus::Firing,
+            thread: None,
+            message: "no account update for 90s (threshold 60s)".to_string(),
+        };
+        assert_eq!(
+            sink.payload(&alert),
+            serde_json::json!({
+                "text": "[FIRING] update_silence: no account update for 90s (threshold 60s)"
+            })
+        );
+
+        let generic = WebhookSink::new("https://example.com/alerts").unwrap();
+        assert_eq!(generic.payload(&alert)["rule"], "update_silence");
+        assert_eq!(generic.payload(&alert)["status"], "firing");
+    }
+}
@@ -0,0 +1,417 @@
+//! Cross-thread exec batching
+//!
+//! Many cheap single-fiber threads come due in the same slot, and each would
+//! otherwise pay for its own transaction, blockhash fetch and confirmation
+//! poll. With `submission.batch_exec`, a worker whose execution fits in one
+//! transaction hands it to the [`ExecBatcher`] instead of submitting it. The
+//! first worker to arrive waits `submission.batch_window_ms` for others, then
+//! packs what was collected into as few transactions as the size and compute
+//! limits allow.
+//!
+//! Executions are only packed together if their accounts don't conflict. Each
+//! was simulated alone, so an account one of them writes must not appear in
+//! another: running them in sequence could change what the later one sees.
+//! The writable accounts every exec shares (executor and config admin) are
+//! exempt, and read-only accounts never conflict.
+//!
+//! A packed transaction that fails on-chain names the failing instruction.
+//! Only the thread owning it is failed; the others fall back to submitting on
+//! their own.
+
+use crate::cadence::SubmissionTimeout;
+use crate::executor::{estimate_transaction_size_with_budget, MAX_TRANSACTION_SIZE};
+use parking_lot::Mutex;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signature};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Compute unit limit of a single transaction
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u64 = 1_400_000;
+
+/// One thread's execution, ready to be packed
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    pub thread_pubkey: Pubkey,
+    /// Fiber being executed, for timeout logging
+    pub fiber_pubkey: Pubkey,
+    /// Cursor of the fiber being executed, for the transaction log
+    pub fiber_cursor: u8,
+    /// Confirmation wait derived from the thread's trigger period
+    pub submission_timeout: SubmissionTimeout,
+    /// Fee payer the instructions name as executor; only executions with
+    /// the same payer are packed together
+    pub payer: Pubkey,
+    /// Exec instructions, without compute budget instructions
+    pub instructions: Vec<Instruction>,
+    /// Compute unit limit the execution would have been sent with
+    pub compute_units: u32,
+    /// Compute unit price the execution would have been sent with
+    pub cu_price: u64,
+    /// Whether this thread broadcasts its transactions
+    pub broadcast: bool,
+}
+
+/// What became of a thread's execution handed to the batcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOutcome {
+    /// Landed in a packed transaction
+    Confirmed(Signature),
+    /// The packed transaction failed in this thread's instruction
+    Failed(String),
+    /// Not sent in a packed transaction; the worker submits it on its own
+    Unbatched,
+}
+
+/// An execution waiting in the batcher, with its worker's reply channel
+pub struct PendingExec {
+    pub entry: BatchEntry,
+    reply: oneshot::Sender<BatchOutcome>,
+}
+
+impl PendingExec {
+    /// Report the outcome to the waiting worker
+    pub fn resolve(self, outcome: BatchOutcome) {
+        // The worker may have given up waiting; nothing to do then
+        let _ = self.reply.send(outcome);
+    }
+}
+
+/// Packing statistics, shared by all workers
+#[derive(Debug, Default)]
+pub struct BatchMetrics {
+    /// Packed transactions confirmed
+    pub transactions: AtomicU64,
+    /// Thread executions confirmed in packed transactions
+    pub threads: AtomicU64,
+    /// Executions handed back to their worker to submit alone
+    pub unbatched: AtomicU64,
+}
+
+impl BatchMetrics {
+    pub fn transactions(&self) -> u64 {
+        self.transactions.load(Ordering::Relaxed)
+    }
+
+    pub fn threads(&self) -> u64 {
+        self.threads.load(Ordering::Relaxed)
+    }
+
+    pub fn unbatched(&self) -> u64 {
+        self.unbatched.load(Ordering::Relaxed)
+    }
+
+    /// Average number of threads per packed transaction
+    pub fn threads_per_transaction(&self) -> f64 {
+        match self.transactions() {
+            0 => 0.0,
+            transactions => self.threads() as f64 / transactions as f64,
+        }
+    }
+
+    /// Count a confirmed packed transaction of `threads` executions
+    pub fn record_transaction(&self, threads: usize) {
+        self.transactions.fetch_add(1, Ordering::Relaxed);
+        self.threads.fetch_add(threads as u64, Ordering::Relaxed);
+    }
+}
+
+/// Collects executions that come due together so they can be packed
+pub struct ExecBatcher {
+    window: Duration,
+    pending: Mutex<Vec<PendingExec>>,
+    metrics: BatchMetrics,
+}
+
+impl ExecBatcher {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Mutex::new(Vec::new()),
+            metrics: BatchMetrics::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> &BatchMetrics {
+        &self.metrics
+    }
+
+    /// Add an execution to the current batch
+    ///
+    /// Returns the channel its outcome arrives on, and whether the caller
+    /// opened the batch. The caller that opened it must call [`Self::collect`]
+    /// and resolve every returned execution, its own included.
+    pub fn join(&self, entry: BatchEntry) -> (oneshot::Receiver<BatchOutcome>, bool) {
+        let (reply, outcome) = oneshot::channel();
+        let mut pending = self.pending.lock();
+        let opened = pending.is_empty();
+        pending.push(PendingExec { entry, reply });
+        (outcome, opened)
+    }
+
+    /// Wait out the batch window, then take everything that joined
+    pub async fn collect(&self) -> Vec<PendingExec> {
+        tokio::time::sleep(self.window).await;
+        std::mem::take(&mut *self.pending.lock())
+    }
+}
+
+/// Accounts of one execution, split by how it uses them
+struct AccountUse {
+    writable: HashSet<Pubkey>,
+    all: HashSet<Pubkey>,
+}
+
+impl AccountUse {
+    fn of(instructions: &[Instruction], shared: &HashSet<Pubkey>) -> Self {
+        let mut writable = HashSet::new();
+        let mut all = HashSet::new();
+        for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+            if shared.contains(&meta.pubkey) {
+                continue;
+            }
+            if meta.is_writable {
+                writable.insert(meta.pubkey);
+            }
+            all.insert(meta.pubkey);
+        }
+        Self { writable, all }
+    }
+
+    /// Whether either side writes an account the other uses
+    fn conflicts(&self, other: &AccountUse) -> bool {
+        !self.writable.is_disjoint(&other.all) || !other.writable.is_disjoint(&self.all)
+    }
+
+    fn absorb(&mut self, other: AccountUse) {
+        self.writable.extend(other.writable);
+        self.all.extend(other.all);
+    }
+}
+
+/// Whether two executions' instructions can share a transaction
+pub fn conflicts(a: &[Instruction], b: &[Instruction], shared: &HashSet<Pubkey>) -> bool {
+    AccountUse::of(a, shared).conflicts(&AccountUse::of(b, shared))
+}
+
+/// Group executions into transactions, first fit in arrival order
+///
+/// Returns indices into `entries`. A group never holds two executions with
+/// conflicting accounts, and stays within the transaction size and compute
+/// unit limits. An execution that fits nowhere else gets a group of its own.
+pub fn pack(entries: &[BatchEntry], payer: &Pubkey, shared: &HashSet<Pubkey>) -> Vec<Vec<usize>> {
+    struct Group {
+        members: Vec<usize>,
+        instructions: Vec<Instruction>,
+        compute_units: u64,
+        accounts: AccountUse,
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let accounts = AccountUse::of(&entry.instructions, shared);
+        let compute_units = entry.compute_units as u64;
+
+        let fits = groups.iter().position(|group| {
+            if group.compute_units + compute_units > MAX_TRANSACTION_COMPUTE_UNITS
+                || group.accounts.conflicts(&accounts)
+            {
+                return false;
+            }
+            let mut trial = group.instructions.clone();
+            trial.extend_from_slice(&entry.instructions);
+            estimate_transaction_size_with_budget(payer, &trial) <= MAX_TRANSACTION_SIZE
+        });
+
+        match fits {
+            Some(position) => {
+                let group = &mut groups[position];
+                group.members.push(index);
+                group.instructions.extend_from_slice(&entry.instructions);
+                group.compute_units += compute_units;
+                group.accounts.absorb(accounts);
+            }
+            None => groups.push(Group {
+                members: vec![index],
+                instructions: entry.instructions.clone(),
+                compute_units,
+                accounts,
+            }),
+        }
+    }
+
+    groups.into_iter().map(|group| group.members).collect()
+}
+
+/// Index of the failing instruction in a transaction error message
+///
+/// Errors reach the worker as `Debug`-formatted `TransactionError`s, e.g.
+/// `Transaction failed on-chain: InstructionError(3, Custom(6004))`.
+pub fn failed_instruction(error: &str) -> Option<usize> {
+    let (_, rest) = error.split_once("InstructionError(")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Member of a packed transaction that owns instruction `index`
+///
+/// `prefix` is the number of compute budget instructions ahead of the execs;
+/// `lengths` are the members' instruction counts, in transaction order.
+pub fn responsible_member(index: usize, prefix: usize, lengths: &[usize]) -> Option<usize> {
+    let mut offset = index.checked_sub(prefix)?;
+    for (member, &len) in lengths.iter().enumerate() {
+        if offset < len {
+            return Some(member);
+        }
+        offset -= len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn entry(accounts: Vec<AccountMeta>, compute_units: u32) -> BatchEntry {
+        BatchEntry {
+            thread_pubkey: Pubkey::new_unique(),
+            fiber_pubkey: Pubkey::new_unique(),
+            fiber_cursor: 0,
+            submission_timeout: SubmissionTimeout::fixed(Duration::from_secs(30)),
+            payer: Pubkey::default(),
+            instructions: vec![Instruction {
+                program_id: antegen_thread_program::ID,
+                accounts,
+                data: vec![0; 16],
+            }],
+            compute_units,
+            cu_price: 0,
+            broadcast: false,
+        }
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let executor = Pubkey::new_unique();
+        let shared = HashSet::from([executor]);
+        let (x, y) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let writes_x = [AccountMeta::new(executor, true), AccountMeta::new(x, false)];
+        let reads_x = [
+            AccountMeta::new(executor, true),
+            AccountMeta::new_readonly(x, false),
+        ];
+        let reads_x_y = [
+            AccountMeta::new_readonly(x, false),
+            AccountMeta::new_readonly(y, false),
+        ];
+        fn ix(accounts: &[AccountMeta]) -> Vec<Instruction> {
+            vec![Instruction {
+                program_id: antegen_thread_program::ID,
+                accounts: accounts.to_vec(),
+                data: vec![],
+            }]
+        }
+
+        // Write/read and write/write on the same account conflict, in either order
+        assert!(conflicts(&ix(&writes_x), &ix(&reads_x), &shared));
+        assert!(conflicts(&ix(&reads_x), &ix(&writes_x), &shared));
+        assert!(conflicts(&ix(&writes_x), &ix(&writes_x), &shared));
+        // Shared writable executor and read-only overlap don't
+        assert!(!conflicts(&ix(&reads_x), &ix(&reads_x_y), &shared));
+        assert!(!conflicts(&ix(&reads_x), &ix(&reads_x), &shared));
+    }
+
+    #[test]
+    fn test_pack_separates_conflicts_and_respects_limits() {
+        let payer = Pubkey::new_unique();
+        let shared = HashSet::from([payer]);
+        let contended = Pubkey::new_unique();
+        let own = || AccountMeta::new(Pubkey::new_unique(), false);
+
+        let entries = vec![
+            entry(vec![AccountMeta::new(contended, false), own()], 50_000),
+            entry(vec![own()], 50_000),
+            entry(
+                vec![AccountMeta::new_readonly(contended, false), own()],
+                50_000,
+            ),
+            entry(vec![own()], 1_350_000),
+        ];
+        let groups = pack(&entries, &payer, &shared);
+
+        // 2 reads what 0 writes; 3 would exceed the CU limit next to 0 and 1
+        assert_eq!(groups, vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_pack_respects_transaction_size() {
+        let payer = Pubkey::new_unique();
+        let shared = HashSet::from([payer]);
+        // 12 unique accounts each: three of them can't share 1232 bytes
+        let entries: Vec<_> = (0..3)
+            .map(|_| {
+                entry(
+                    (0..12)
+                        .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                        .collect(),
+                    10_000,
+                )
+            })
+            .collect();
+        let groups = pack(&entries, &payer, &shared);
+
+        assert!(groups.len() > 1);
+        for group in &groups {
+            let instructions: Vec<_> = group
+                .iter()
+                .flat_map(|&i| entries[i].instructions.clone())
+                .collect();
+            assert!(
+                estimate_transaction_size_with_budget(&payer, &instructions)
+                    <= MAX_TRANSACTION_SIZE
+            );
+        }
+        let mut packed: Vec<usize> = groups.concat();
+        packed.sort();
+        assert_eq!(packed, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_failure_attribution() {
+        let error = "Transaction failed on-chain: InstructionError(4, Custom(6004))";
+        assert_eq!(failed_instruction(error), Some(4));
+        assert_eq!(failed_instruction("Confirmation timeout after 30s"), None);
+
+        // Two budget instructions, then members with 1, 2 and 1 instructions
+        let lengths = [1, 2, 1];
+        assert_eq!(responsible_member(2, 2, &lengths), Some(0));
+        assert_eq!(responsible_member(4, 2, &lengths), Some(1));
+        assert_eq!(responsible_member(5, 2, &lengths), Some(2));
+        assert_eq!(responsible_member(1, 2, &lengths), None);
+        assert_eq!(responsible_member(6, 2, &lengths), None);
+    }
+
+    #[tokio::test]
+    async fn test_first_to_join_opens_batch() {
+        let batcher = ExecBatcher::new(Duration::from_millis(10));
+        let (first, opened) = batcher.join(entry(vec![], 1));
+        let (second, joined_open) = batcher.join(entry(vec![], 1));
+        assert!(opened);
+        assert!(!joined_open);
+
+        let pending = batcher.collect().await;
+        assert_eq!(pending.len(), 2);
+        for exec in pending {
+            exec.resolve(BatchOutcome::Unbatched);
+        }
+        assert_eq!(first.await.unwrap(), BatchOutcome::Unbatched);
+        assert_eq!(second.await.unwrap(), BatchOutcome::Unbatched);
+
+        // The next execution opens a new batch
+        let (_, opened) = batcher.join(entry(vec![], 1));
+        assert!(opened);
+    }
+}
@@ -0,0 +1,179 @@
+//! Per-execution submission timeouts
+//!
+//! A thread firing every 5 seconds should give up on an unconfirmed
+//! submission much sooner than a daily cron thread. The processor derives
+//! each execution's confirmation timeout from the thread's trigger period:
+//!
+//! `min(submission_timeout_ms, period × submission_timeout_factor)`
+//!
+//! Interval and cron triggers have a period. Timestamp, slot, epoch,
+//! immediate and account triggers use `submission_timeout_ms` as is.
+//!
+//! For triggers with a period the worker also stops retrying once another
+//! attempt could not confirm before the thread fires again.
+//!
+//! With `tpu.leader_alignment` set, the execution also carries how long a TPU
+//! send may be held for a warm leader (see [`crate::tpu::SendWindow`]).
+
+use antegen_thread_program::state::Trigger;
+use chrono::DateTime;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How long an execution waits on its submissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmissionTimeout {
+    /// Longest wait for one attempt to confirm
+    pub confirmation: Duration,
+    /// Time between the thread's fires, if it recurs on a schedule
+    pub period: Option<Duration>,
+    /// Longest a TPU send may wait for a warm leader
+    pub leader_hold: Duration,
+}
+
+impl SubmissionTimeout {
+    /// The configured timeout, for threads without a period
+    pub fn fixed(confirmation: Duration) -> Self {
+        Self {
+            confirmation,
+            period: None,
+            leader_hold: Duration::ZERO,
+        }
+    }
+
+    /// Timeout for an execution of a thread with `trigger`, at unix time `now`
+    pub fn for_trigger(trigger: &Trigger, max: Duration, factor: f64, now: i64) -> Self {
+        match trigger_period(trigger, now) {
+            Some(period) => Self {
+                confirmation: period.mul_f64(factor).min(max),
+                period: Some(period),
+                leader_hold: Duration::ZERO,
+            },
+            None => Self::fixed(max),
+        }
+    }
+
+    /// Allow TPU sends to be held up to `leader_hold`
+    pub fn with_leader_hold(self, leader_hold: Duration) -> Self {
+        Self {
+            leader_hold,
+            ..self
+        }
+    }
+
+    /// Whether an attempt started `elapsed` after the first could still
+    /// confirm before the thread's next fire
+    pub fn allows_retry(&self, elapsed: Duration) -> bool {
+        self.period
+            .is_none_or(|period| elapsed + self.confirmation <= period)
+    }
+}
+
+/// Time between fires of a recurring trigger
+///
+/// For cron schedules this is the gap between the next two fires after
+/// `now`, so irregular schedules get the period they're about to run at.
+pub fn trigger_period(trigger: &Trigger, now: i64) -> Option<Duration> {
+    match trigger {
+        Trigger::Interval { seconds, .. } if *seconds > 0 => {
+            Some(Duration::from_secs(*seconds as u64))
+        }
+        Trigger::Cron { schedule, .. } => {
+            let schedule = antegen_cron::Schedule::from_str(schedule).ok()?;
+            let mut fires = schedule.after(&DateTime::from_timestamp(now, 0)?);
+            let next = fires.next()?;
+            let following = fires.next()?;
+            u64::try_from((following - next).num_seconds())
+                .ok()
+                .map(Duration::from_secs)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    const MAX: Duration = Duration::from_secs(30);
+
+    fn interval(seconds: i64) -> Trigger {
+        Trigger::Interval {
+            seconds,
+            skippable: false,
+            jitter: 0,
+        }
+    }
+
+    fn cron(schedule: &str) -> Trigger {
+        Trigger::Cron {
+            schedule: schedule.to_string(),
+            skippable: false,
+            jitter: 0,
+        }
+    }
+
+    #[test]
+    fn test_interval_timeout() {
+        let fast = SubmissionTimeout::for_trigger(&interval(5), MAX, 0.5, 0);
+        assert_eq!(fast.confirmation, Duration::from_millis(2500));
+        assert_eq!(fast.period, Some(Duration::from_secs(5)));
+
+        // Slow threads are capped at the configured maximum
+        let slow = SubmissionTimeout::for_trigger(&interval(3600), MAX, 0.5, 0);
+        assert_eq!(slow.confirmation, MAX);
+    }
+
+    #[test]
+    fn test_cron_timeout() {
+        // Every 10 seconds
+        let fast = SubmissionTimeout::for_trigger(&cron("*/10 * * * * * *"), MAX, 0.5, 0);
+        assert_eq!(fast.period, Some(Duration::from_secs(10)));
+        assert_eq!(fast.confirmation, Duration::from_secs(5));
+
+        // Daily
+        let daily = SubmissionTimeout::for_trigger(&cron("0 0 0 * * * *"), MAX, 0.5, 0);
+        assert_eq!(daily.period, Some(Duration::from_secs(86_400)));
+        assert_eq!(daily.confirmation, MAX);
+
+        // Unparseable schedules fall back to the maximum
+        assert_eq!(
+            SubmissionTimeout::for_trigger(&cron("not a schedule"), MAX, 0.5, 0),
+            SubmissionTimeout::fixed(MAX)
+        );
+    }
+
+    #[test]
+    fn test_one_shot_and_event_triggers_use_max() {
+        for trigger in [
+            Trigger::Immediate { jitter: 0 },
+            Trigger::Timestamp {
+                unix_ts: 100,
+                jitter: 0,
+            },
+            Trigger::Slot { slot: 100 },
+            Trigger::Epoch { epoch: 10 },
+            Trigger::Account {
+                address: Pubkey::new_unique(),
+                offset: 0,
+                size: 8,
+            },
+        ] {
+            assert_eq!(
+                SubmissionTimeout::for_trigger(&trigger, MAX, 0.5, 0),
+                SubmissionTimeout::fixed(MAX)
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_stops_before_next_fire() {
+        let timeout = SubmissionTimeout::for_trigger(&interval(10), MAX, 0.5, 0);
+        assert!(timeout.allows_retry(Duration::from_secs(5)));
+        assert!(!timeout.allows_retry(Duration::from_secs(6)));
+
+        // Without a period retries are never cut short
+        assert!(SubmissionTimeout::fixed(MAX).allows_retry(Duration::from_secs(3600)));
+    }
+}
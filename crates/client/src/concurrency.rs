@@ -0,0 +1,199 @@
+//! Adaptive worker concurrency
+//!
+//! `max_concurrent_threads` (or the lane sizes) is a fixed cap, but how many
+//! executions the network absorbs at once changes with RPC latency and
+//! congestion. With `[processor.adaptive_concurrency]` set, [`AimdController`]
+//! moves the number of workers allowed to run between `min_workers` and that
+//! cap, additive-increase/multiplicative-decrease:
+//!
+//! - an execution that succeeds within `target_latency_ms` raises the limit
+//!   by `1 / limit`, about one worker per limit's worth of fast executions
+//! - a failed execution, or a success slower than the target, multiplies the
+//!   limit by `backoff`
+//!
+//! After a decrease, further decreases wait `target_latency_ms`, so a burst of
+//! failures from one congested moment backs off once. Skipped executions say
+//! nothing about the network and are ignored.
+//!
+//! The processor applies the limit when spawning workers: no new worker
+//! starts while as many as the limit are running.
+
+use crate::config::AdaptiveConcurrencyConfig;
+use std::time::{Duration, Instant};
+
+/// How an execution went, as far as concurrency is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionSignal {
+    /// Confirmed after `latency`
+    Success(Duration),
+    /// Failed or timed out
+    Failure,
+}
+
+/// Snapshot of the controller, for `ProcessorStatus`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcurrencyStatus {
+    /// Workers currently allowed to run
+    pub limit: usize,
+    /// Configured cap
+    pub max: usize,
+    pub min: usize,
+    /// Times the limit went up by a whole worker
+    pub increases: u64,
+    /// Times the limit was cut
+    pub decreases: u64,
+}
+
+/// AIMD controller for the number of running workers
+#[derive(Debug)]
+pub struct AimdController {
+    min: usize,
+    max: usize,
+    limit: f64,
+    target_latency: Duration,
+    backoff: f64,
+    last_decrease: Option<Instant>,
+    increases: u64,
+    decreases: u64,
+}
+
+impl AimdController {
+    /// Controller bounded by `max` workers, starting there
+    pub fn new(config: &AdaptiveConcurrencyConfig, max: usize) -> Self {
+        let min = config.min_workers.clamp(1, max.max(1));
+        Self {
+            min,
+            max,
+            limit: max as f64,
+            target_latency: Duration::from_millis(config.target_latency_ms),
+            backoff: config.backoff,
+            last_decrease: None,
+            increases: 0,
+            decreases: 0,
+        }
+    }
+
+    /// Workers currently allowed to run
+    pub fn limit(&self) -> usize {
+        (self.limit as usize).clamp(self.min, self.max)
+    }
+
+    /// Adjust the limit for a finished execution; returns whether the
+    /// whole-worker limit changed
+    pub fn record(&mut self, signal: ExecutionSignal, now: Instant) -> bool {
+        let before = self.limit();
+        match signal {
+            ExecutionSignal::Success(latency) if latency <= self.target_latency => {
+                self.limit = (self.limit + 1.0 / self.limit.max(1.0)).min(self.max as f64);
+            }
+            ExecutionSignal::Success(_) | ExecutionSignal::Failure => {
+                let cooling = self
+                    .last_decrease
+                    .is_some_and(|at| now.duration_since(at) < self.target_latency);
+                if cooling {
+                    return false;
+                }
+                self.limit = (self.limit * self.backoff).max(self.min as f64);
+                self.last_decrease = Some(now);
+            }
+        }
+
+        let after = self.limit();
+        match after.cmp(&before) {
+            std::cmp::Ordering::Greater => self.increases += 1,
+            std::cmp::Ordering::Less => self.decreases += 1,
+            std::cmp::Ordering::Equal => return false,
+        }
+        true
+    }
+
+    pub fn status(&self) -> ConcurrencyStatus {
+        ConcurrencyStatus {
+            limit: self.limit(),
+            max: self.max,
+            min: self.min,
+            increases: self.increases,
+            decreases: self.decreases,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: ExecutionSignal = ExecutionSignal::Success(Duration::from_millis(800));
+    const SLOW: ExecutionSignal = ExecutionSignal::Success(Duration::from_secs(20));
+
+    fn controller(max: usize) -> AimdController {
+        AimdController::new(
+            &AdaptiveConcurrencyConfig {
+                min_workers: 2,
+                target_latency_ms: 5_000,
+                backoff: 0.5,
+            },
+            max,
+        )
+    }
+
+    #[test]
+    fn test_backs_off_on_failure_and_recovers() {
+        let mut aimd = controller(16);
+        let start = Instant::now();
+        assert_eq!(aimd.limit(), 16);
+
+        assert!(aimd.record(ExecutionSignal::Failure, start));
+        assert_eq!(aimd.limit(), 8);
+
+        // Fast successes add roughly one worker per limit's worth
+        for _ in 0..9 {
+            aimd.record(FAST, start);
+        }
+        assert_eq!(aimd.limit(), 9);
+
+        // Never above the configured cap
+        for _ in 0..1_000 {
+            aimd.record(FAST, start);
+        }
+        assert_eq!(aimd.limit(), 16);
+        assert_eq!(aimd.status().decreases, 1);
+        assert_eq!(aimd.status().increases, 8);
+    }
+
+    #[test]
+    fn test_burst_of_failures_backs_off_once() {
+        let mut aimd = controller(16);
+        let start = Instant::now();
+
+        aimd.record(ExecutionSignal::Failure, start);
+        for _ in 0..10 {
+            assert!(!aimd.record(ExecutionSignal::Failure, start + Duration::from_secs(1)));
+        }
+        assert_eq!(aimd.limit(), 8);
+
+        // Congestion that persists past the cooldown cuts again
+        aimd.record(SLOW, start + Duration::from_secs(6));
+        assert_eq!(aimd.limit(), 4);
+    }
+
+    #[test]
+    fn test_limit_stays_above_min() {
+        let mut aimd = controller(16);
+        let mut now = Instant::now();
+        for _ in 0..10 {
+            aimd.record(ExecutionSignal::Failure, now);
+            now += Duration::from_secs(10);
+        }
+        assert_eq!(aimd.limit(), 2);
+
+        // A minimum above the cap is clamped to it
+        let aimd = AimdController::new(
+            &AdaptiveConcurrencyConfig {
+                min_workers: 50,
+                ..AdaptiveConcurrencyConfig::default()
+            },
+            4,
+        );
+        assert_eq!(aimd.status().min, 4);
+    }
+}
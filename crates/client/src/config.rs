@@ -3,15 +3,25 @@
 //! This module contains the unified ClientConfig used by both
 //! standalone and plugin deployment modes.
 
+use crate::config_migrate::{migrate_str, CURRENT_CONFIG_VERSION, LEGACY_CONFIG_VERSION};
+use crate::rpc::RequestKind;
+use crate::thread_filter::ThreadFilter;
+use crate::{logging, trigger_str};
+use antegen_thread_program::state::Trigger;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Main configuration for the Antegen client
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ClientConfig {
+    /// Layout of the file, see [`crate::config_migrate`]
+    #[serde(default = "default_config_version")]
+    pub config_version: u32,
     #[serde(default)]
     pub executor: ExecutorConfig,
     pub rpc: RpcConfig,
@@ -25,6 +35,23 @@ pub struct ClientConfig {
     pub observability: ObservabilityConfig,
     #[serde(default)]
     pub tpu: TpuConfig,
+    #[serde(default)]
+    pub submission: SubmissionConfig,
+    #[serde(default)]
+    pub costs: CostsConfig,
+    #[serde(default)]
+    pub tx_log: TxLogConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+    /// File this config was loaded from, re-read by a control API reload
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
+}
+
+fn default_config_version() -> u32 {
+    LEGACY_CONFIG_VERSION
 }
 
 /// Executor configuration
@@ -32,27 +59,143 @@ pub struct ClientConfig {
 pub struct ExecutorConfig {
     #[serde(default = "default_keypair_path")]
     pub keypair_path: String,
+    /// Additional fee payer keypairs, rotated round-robin with
+    /// `keypair_path`, which stays the primary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keypairs: Vec<String>,
     #[serde(default)]
     pub forgo_commission: bool,
+    /// Where fiber execution results are published
+    #[serde(default)]
+    pub results: ResultsConfig,
 }
 
 fn default_keypair_path() -> String {
     "~/.antegen/executor-keypair.json".to_string()
 }
 
+impl ExecutorConfig {
+    /// Every fee payer keypair path, the primary first
+    pub fn keypair_paths(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.keypair_path.as_str()).chain(self.keypairs.iter().map(String::as_str))
+    }
+}
+
 impl Default for ExecutorConfig {
     fn default() -> Self {
         Self {
             keypair_path: default_keypair_path(),
+            keypairs: Vec::new(),
             forgo_commission: false,
+            results: ResultsConfig::default(),
         }
     }
 }
 
+/// Fiber execution result publishing, see [`crate::results`]
+///
+/// Nothing is fetched or published without at least one sink.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ResultsConfig {
+    /// Where each confirmed execution's result is sent
+    #[serde(default)]
+    pub sinks: Vec<ResultSinkConfig>,
+}
+
+impl ResultsConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+}
+
+/// Fiber result destination
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResultSinkConfig {
+    /// Write results to the client log
+    Log,
+    /// Publish each result as JSON on a NATS subject
+    Nats { url: String, subject: String },
+    /// POST each result as JSON
+    Webhook { url: String },
+}
+
 /// RPC endpoint configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RpcConfig {
     pub endpoints: Vec<RpcEndpoint>,
+    /// Quarantine an endpoint after it has been unhealthy this long (0 = never)
+    ///
+    /// A quarantined endpoint gets no traffic and is probed out-of-band until
+    /// it passes `readmit_after_checks` consecutive health checks.
+    #[serde(default)]
+    pub quarantine_after_secs: u64,
+    /// Consecutive passing probes before a quarantined endpoint is re-admitted
+    #[serde(default = "default_readmit_after_checks")]
+    pub readmit_after_checks: u32,
+    /// Seconds between probes of quarantined endpoints
+    #[serde(default = "default_probe_interval_secs")]
+    pub probe_interval_secs: u64,
+    /// Cap on the total time one request may spend failing over across
+    /// endpoints, in milliseconds (0 = no cap)
+    #[serde(default = "default_request_budget_ms")]
+    pub request_budget_ms: u64,
+    /// Idle keep-alive connections kept open per endpoint
+    #[serde(default = "default_http_pool_max_idle")]
+    pub http_pool_max_idle: usize,
+    /// Seconds an idle pooled connection is kept before it is closed
+    #[serde(default = "default_http_pool_idle_timeout_secs")]
+    pub http_pool_idle_timeout_secs: u64,
+    /// Longest a transaction send waits on an endpoint's `rate_limits`
+    /// before failing over to the next endpoint, in milliseconds
+    #[serde(default = "default_send_rate_limit_deadline_ms")]
+    pub send_rate_limit_deadline_ms: u64,
+    /// Check every endpoint at startup and start the failing ones unhealthy
+    ///
+    /// Startup fails if no endpoint passes for submission or for data.
+    #[serde(default)]
+    pub preflight: bool,
+    /// How requests pick among the healthy endpoints for their role
+    #[serde(default)]
+    pub selection: EndpointSelection,
+}
+
+fn default_readmit_after_checks() -> u32 {
+    3
+}
+
+fn default_probe_interval_secs() -> u64 {
+    15
+}
+
+fn default_request_budget_ms() -> u64 {
+    10_000
+}
+
+fn default_http_pool_max_idle() -> usize {
+    10
+}
+
+fn default_http_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_send_rate_limit_deadline_ms() -> u64 {
+    50
+}
+
+/// Order in which a request tries the healthy endpoints for its role
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EndpointSelection {
+    /// Rotate the starting endpoint on each request
+    #[default]
+    RoundRobin,
+    /// Lowest `priority` number first
+    Weighted,
+    /// Lowest measured latency first, discounting failing endpoints, with
+    /// occasional requests to slower ones to keep their latency current
+    Latency,
 }
 
 /// Individual RPC endpoint
@@ -67,6 +210,9 @@ pub struct RpcEndpoint {
     /// Ignored for Datasource-only endpoints (all datasources listen concurrently)
     #[serde(default = "default_priority")]
     pub priority: u8,
+    /// Requests per second this endpoint accepts, by method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<EndpointRateLimits>,
 }
 
 impl RpcEndpoint {
@@ -90,6 +236,28 @@ fn default_priority() -> u8 {
     1
 }
 
+/// Per-method request limits for one endpoint, in requests per second
+///
+/// Any key besides `default` and `burst` names a JSON-RPC method in
+/// snake_case (`get_program_accounts` for `getProgramAccounts`) and gives it
+/// its own bucket. All other methods share the `default` bucket.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct EndpointRateLimits {
+    /// Limit shared by methods without their own entry
+    #[serde(default = "default_rate_limit")]
+    pub default: f64,
+    /// Requests each bucket can take at once (0 = one second's worth)
+    #[serde(default)]
+    pub burst: u64,
+    /// Limits for individual methods
+    #[serde(flatten)]
+    pub methods: BTreeMap<String, f64>,
+}
+
+fn default_rate_limit() -> f64 {
+    50.0
+}
+
 /// Role of an RPC endpoint
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -100,6 +268,10 @@ pub enum EndpointRole {
     Submission,
     /// Used for both datasources and submission
     Both,
+    /// Preferred for transaction simulation
+    Simulation,
+    /// Preferred for heavy reads (`getProgramAccounts`, `getMultipleAccounts`)
+    Read,
 }
 
 /// Datasource configuration
@@ -109,6 +281,67 @@ pub struct DatasourceConfig {
     pub commitment: String,
     #[serde(default = "default_program_id", with = "pubkey_string")]
     pub program_id: Pubkey,
+    /// Unix socket for receiving the Geyser stream from a separately running
+    /// validator plugin. The plugin forwards to it instead of running the
+    /// client in-process, and the standalone client listens on it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geyser_socket: Option<String>,
+    /// How often (milliseconds) to poll the clock sysvar over RPC when no
+    /// datasource has delivered a clock update (0 = disabled)
+    #[serde(default = "default_clock_poll_interval_ms")]
+    pub clock_poll_interval_ms: u64,
+    /// Watch the clock sysvar over a WebSocket subscription instead of
+    /// polling it, under the same `clock_poll_interval_ms` back-off
+    #[serde(default)]
+    pub clock_use_websocket: bool,
+    /// Stream from Helius websockets in addition to the RPC endpoints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helius: Option<HeliusConfig>,
+}
+
+fn default_clock_poll_interval_ms() -> u64 {
+    2000
+}
+
+/// Helius websocket datasource
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HeliusConfig {
+    /// API key; `$VAR` or `${VAR}` reads it from the environment
+    pub api_key: String,
+    /// Subscribe on the enhanced endpoint first, and fall back to the
+    /// standard one if it rejects the subscription
+    #[serde(default = "default_helius_enhanced")]
+    pub enhanced: bool,
+    /// Enhanced websocket endpoint
+    #[serde(default = "default_helius_enhanced_ws_url")]
+    pub enhanced_ws_url: String,
+    /// Standard websocket endpoint
+    #[serde(default = "default_helius_ws_url")]
+    pub ws_url: String,
+}
+
+impl HeliusConfig {
+    /// The API key with environment references expanded
+    pub fn resolve_api_key(&self) -> Result<String> {
+        let key = shellexpand::env(&self.api_key)
+            .map_err(|e| anyhow::anyhow!("datasources.helius.api_key: {}", e))?;
+        if key.trim().is_empty() {
+            anyhow::bail!("datasources.helius.api_key is empty");
+        }
+        Ok(key.into_owned())
+    }
+}
+
+fn default_helius_enhanced() -> bool {
+    true
+}
+
+fn default_helius_enhanced_ws_url() -> String {
+    "wss://atlas-mainnet.helius-rpc.com".to_string()
+}
+
+fn default_helius_ws_url() -> String {
+    "wss://mainnet.helius-rpc.com".to_string()
 }
 
 fn default_program_id() -> Pubkey {
@@ -145,12 +378,213 @@ fn default_commitment() -> String {
 pub struct ProcessorConfig {
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent_threads: usize,
+    /// Maximum duration of a single fiber simulation or submission attempt (milliseconds)
+    #[serde(default = "default_fiber_execution_timeout_ms")]
+    pub fiber_execution_timeout_ms: u64,
+    /// Longest wait (milliseconds) for one submission to confirm; also the
+    /// timeout of threads without a trigger period, see [`crate::cadence`]
+    #[serde(default = "default_submission_timeout_ms")]
+    pub submission_timeout_ms: u64,
+    /// Fraction of an interval or cron thread's period to wait for a
+    /// submission to confirm, up to `submission_timeout_ms`
+    #[serde(default = "default_submission_timeout_factor")]
+    pub submission_timeout_factor: f64,
+    /// Simulate each signed transaction before sending it via TPU, which skips preflight
+    #[serde(default)]
+    pub simulate_before_send: bool,
+    /// Load all existing threads via getProgramAccounts at startup
+    #[serde(default)]
+    pub reconcile_on_start: bool,
+    /// Handling of threads that came due while the datasource was behind
+    #[serde(default)]
+    pub gap_backfill: GapBackfillConfig,
+    /// Upper bound on the priority fee paid for a single execution
+    #[serde(default)]
+    pub fee_ceiling: FeeCeilingConfig,
+    /// Window (milliseconds) in which a repeated execution of the same thread
+    /// state is dropped as a duplicate (0 = disabled)
+    #[serde(default = "default_dedup_window_ms")]
+    pub dedup_window_ms: u64,
+    /// Slots after a confirmed execution during which thread state read
+    /// before it is refused instead of executed again (0 = disabled)
+    #[serde(default = "default_replay_guard_slots")]
+    pub replay_guard_slots: u64,
+    /// Threads to follow: `*`, thread pubkeys, or globs on the thread id
+    #[serde(default = "default_thread_filter")]
+    pub thread_filter: Vec<String>,
+    /// How far ahead (seconds) to fetch the fiber accounts of threads about
+    /// to fire into the cache; also the prefetch interval (0 = disabled)
+    #[serde(default = "default_prefetch_lookahead_secs")]
+    pub prefetch_lookahead_secs: u64,
+    /// Separate worker lanes for time-based and account-based threads; when
+    /// set, replaces `max_concurrent_threads`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lanes: Option<LanesConfig>,
+    /// Most ready threads queued for a worker at once; past it the least
+    /// overdue are handed back to staging (0 = unbounded)
+    #[serde(default = "default_max_queued_threads")]
+    pub max_queued_threads: usize,
+    /// Vary the running workers with execution latency and failures, up to
+    /// the configured cap, see [`crate::concurrency`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
 }
 
 fn default_max_concurrent() -> usize {
     10
 }
 
+fn default_fiber_execution_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_submission_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_submission_timeout_factor() -> f64 {
+    0.5
+}
+
+fn default_dedup_window_ms() -> u64 {
+    500
+}
+
+fn default_replay_guard_slots() -> u64 {
+    150
+}
+
+fn default_thread_filter() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+fn default_prefetch_lookahead_secs() -> u64 {
+    60
+}
+
+fn default_max_queued_threads() -> usize {
+    10_000
+}
+
+/// Shortest possible gap between two executions of a time-based trigger
+/// (interval and cron triggers have one-second resolution)
+const MIN_TRIGGER_INTERVAL_MS: u64 = 1000;
+
+/// What to do with triggers that fired during a datasource gap
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GapPolicy {
+    /// Execute the most recent missed occurrence once the clock catches up
+    #[default]
+    CatchUp,
+    /// Drop missed occurrences; the thread is rescheduled on its next account update
+    Skip,
+}
+
+/// Slot gap detection and per-trigger-type backfill policy
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GapBackfillConfig {
+    /// A clock jump of more than this many slots is treated as a gap
+    #[serde(default = "default_max_slot_gap")]
+    pub max_slot_gap: u64,
+    /// Policy for time-based triggers (immediate, timestamp, interval, cron)
+    #[serde(default)]
+    pub time: GapPolicy,
+    /// Policy for slot triggers
+    #[serde(default)]
+    pub slot: GapPolicy,
+    /// Policy for epoch triggers
+    #[serde(default)]
+    pub epoch: GapPolicy,
+}
+
+fn default_max_slot_gap() -> u64 {
+    20
+}
+
+impl Default for GapBackfillConfig {
+    fn default() -> Self {
+        Self {
+            max_slot_gap: default_max_slot_gap(),
+            time: GapPolicy::default(),
+            slot: GapPolicy::default(),
+            epoch: GapPolicy::default(),
+        }
+    }
+}
+
+/// Worker lanes, so slow account-triggered threads can't starve cheap
+/// time-based ones
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LanesConfig {
+    /// Concurrent workers for time, slot and epoch triggers
+    pub time: usize,
+    /// Concurrent workers for account triggers
+    pub account: usize,
+    /// Let a lane with work waiting use the other lane's idle workers,
+    /// always leaving the other lane one free worker
+    #[serde(default = "default_borrow_idle")]
+    pub borrow_idle: bool,
+}
+
+fn default_borrow_idle() -> bool {
+    true
+}
+
+/// AIMD control of the running workers
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct AdaptiveConcurrencyConfig {
+    /// Fewest workers allowed to run
+    #[serde(default = "default_min_workers")]
+    pub min_workers: usize,
+    /// Executions confirming within this (milliseconds) raise the limit,
+    /// slower ones lower it; also the cooldown between decreases
+    #[serde(default = "default_target_latency_ms")]
+    pub target_latency_ms: u64,
+    /// Factor (0.0-1.0) the limit is multiplied by on a failure or slow execution
+    #[serde(default = "default_backoff")]
+    pub backoff: f64,
+}
+
+fn default_min_workers() -> usize {
+    1
+}
+
+fn default_target_latency_ms() -> u64 {
+    5_000
+}
+
+fn default_backoff() -> f64 {
+    0.5
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            min_workers: default_min_workers(),
+            target_latency_ms: default_target_latency_ms(),
+            backoff: default_backoff(),
+        }
+    }
+}
+
+/// Priority fee ceiling, guarding thread balances against fee runaway
+///
+/// Both caps are optional; when both are set the lower one applies.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+pub struct FeeCeilingConfig {
+    /// Absolute cap on the priority fee of one transaction (lamports)
+    #[serde(default)]
+    pub max_priority_fee_lamports: Option<u64>,
+    /// Cap as a fraction (0.0-1.0] of the thread's remaining balance
+    #[serde(default)]
+    pub max_balance_fraction: Option<f64>,
+    /// Compute unit price (micro-lamports) below which a clamped transaction
+    /// is not expected to land; such executions are deferred instead of sent
+    #[serde(default)]
+    pub min_viable_cu_price: u64,
+}
+
 /// Cache configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CacheConfig {
@@ -188,12 +622,29 @@ pub struct LoadBalancerConfigFile {
     /// Slower clients can set higher values to avoid wasting fees on races
     #[serde(default)]
     pub thread_process_delay: u64,
+
+    /// Worker saturation (busy / total workers) above which staging stops
+    /// processing account updates, holding them until workers free up
+    #[serde(default = "default_backpressure_threshold")]
+    pub backpressure_threshold: f32,
+
+    /// Worker saturation below which staging resumes processing updates
+    #[serde(default = "default_backpressure_release_threshold")]
+    pub backpressure_release_threshold: f32,
 }
 
 fn default_grace_period() -> u64 {
     10
 }
 
+fn default_backpressure_threshold() -> f32 {
+    0.9
+}
+
+fn default_backpressure_release_threshold() -> f32 {
+    0.5
+}
+
 fn default_eviction_buffer() -> u64 {
     20
 }
@@ -204,6 +655,8 @@ impl Default for LoadBalancerConfigFile {
             grace_period: default_grace_period(),
             eviction_buffer: default_eviction_buffer(),
             thread_process_delay: 0,
+            backpressure_threshold: default_backpressure_threshold(),
+            backpressure_release_threshold: default_backpressure_release_threshold(),
         }
     }
 }
@@ -259,6 +712,46 @@ pub struct ObservabilityConfig {
     /// Storage path for loa-core data (metrics, identity)
     #[serde(default = "default_observability_storage_path")]
     pub storage_path: String,
+    /// Log level per actor (e.g. `staging_actor = "warn"`), overriding the
+    /// global level. Keys are names from `logging::ACTOR_MODULES` or module paths
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub actor_log_levels: HashMap<String, log::LevelFilter>,
+    /// Log line format
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Push metrics to an OpenTelemetry collector (needs the `metrics` feature)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp: Option<OtlpConfig>,
+}
+
+/// OTLP metrics exporter configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtlpConfig {
+    /// Collector metrics endpoint (OTLP over HTTP)
+    #[serde(default = "default_otlp_endpoint")]
+    pub endpoint: String,
+    /// Extra request headers, e.g. collector authentication
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Seconds between exports
+    #[serde(default = "default_otlp_export_interval_secs")]
+    pub export_interval_secs: u64,
+    /// `service.name` resource attribute
+    #[serde(default = "default_otlp_service_name")]
+    pub service_name: String,
+}
+
+/// Log line format
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Default env_logger output
+    #[default]
+    Plain,
+    /// One JSON object per line, for log aggregators such as Loki
+    Json,
+    /// Time, level and message only
+    Compact,
 }
 
 /// TPU client configuration for direct validator transaction submission
@@ -276,6 +769,125 @@ pub struct TpuConfig {
     /// Channel buffer size for transaction batches
     #[serde(default = "default_tpu_worker_channel_size")]
     pub worker_channel_size: usize,
+    /// How transactions are sent when the TPU client is available
+    #[serde(default)]
+    pub submission_mode: SubmissionMode,
+    /// Delay between signature status polls after a TPU send (milliseconds)
+    #[serde(default = "default_tpu_confirmation_poll_interval_ms")]
+    pub confirmation_poll_interval_ms: u64,
+    /// How long to wait for a TPU send to confirm before falling back to RPC
+    /// (milliseconds)
+    #[serde(default = "default_tpu_confirmation_timeout_ms")]
+    pub confirmation_timeout_ms: u64,
+    /// Switch to RPC-only submission for a while when too many TPU sends
+    /// fail to land
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disable: Option<TpuAutoDisableConfig>,
+    /// Hold TPU sends for up to a few slots so they reach a leader that has
+    /// been landing our transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader_alignment: Option<LeaderAlignmentConfig>,
+}
+
+/// Leader-aligned TPU submission: when no leader in the send window has
+/// landed our transactions yet, wait up to `max_hold_ms` for one that has
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct LeaderAlignmentConfig {
+    /// Longest a send is held back (milliseconds); 0 disables the hold
+    #[serde(default = "default_leader_alignment_max_hold_ms")]
+    pub max_hold_ms: u64,
+    /// Trigger kinds that never hold (e.g. `["account"]`)
+    #[serde(default = "default_leader_alignment_latency_sensitive_triggers")]
+    pub latency_sensitive_triggers: Vec<String>,
+}
+
+fn default_leader_alignment_max_hold_ms() -> u64 {
+    1_600
+}
+
+fn default_leader_alignment_latency_sensitive_triggers() -> Vec<String> {
+    vec!["account".to_string()]
+}
+
+impl Default for LeaderAlignmentConfig {
+    fn default() -> Self {
+        Self {
+            max_hold_ms: default_leader_alignment_max_hold_ms(),
+            latency_sensitive_triggers: default_leader_alignment_latency_sensitive_triggers(),
+        }
+    }
+}
+
+impl LeaderAlignmentConfig {
+    /// Longest a send for a thread with this trigger may be held
+    pub fn max_hold(&self, trigger: &Trigger) -> Duration {
+        if self
+            .latency_sensitive_triggers
+            .iter()
+            .any(|kind| kind == trigger_str::kind(trigger))
+        {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(self.max_hold_ms)
+    }
+}
+
+/// TPU auto-disable: when the share of TPU sends that don't land within
+/// `window_secs` exceeds `max_error_rate`, submit via RPC only for
+/// `cooldown_secs`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct TpuAutoDisableConfig {
+    /// Sliding window the error rate is measured over (seconds)
+    #[serde(default = "default_tpu_auto_disable_window_secs")]
+    pub window_secs: u64,
+    /// Error rate (0.0-1.0] above which TPU is disabled
+    #[serde(default = "default_tpu_auto_disable_max_error_rate")]
+    pub max_error_rate: f64,
+    /// Sends needed in the window before the rate is acted on
+    #[serde(default = "default_tpu_auto_disable_min_samples")]
+    pub min_samples: usize,
+    /// How long TPU stays disabled (seconds)
+    #[serde(default = "default_tpu_auto_disable_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_tpu_auto_disable_window_secs() -> u64 {
+    60
+}
+
+fn default_tpu_auto_disable_max_error_rate() -> f64 {
+    0.5
+}
+
+fn default_tpu_auto_disable_min_samples() -> usize {
+    10
+}
+
+fn default_tpu_auto_disable_cooldown_secs() -> u64 {
+    120
+}
+
+impl Default for TpuAutoDisableConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: default_tpu_auto_disable_window_secs(),
+            max_error_rate: default_tpu_auto_disable_max_error_rate(),
+            min_samples: default_tpu_auto_disable_min_samples(),
+            cooldown_secs: default_tpu_auto_disable_cooldown_secs(),
+        }
+    }
+}
+
+/// Transaction submission strategy
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionMode {
+    /// Send via TPU, falling back to RPC if it doesn't confirm
+    #[default]
+    TpuFirst,
+    /// Send via TPU and RPC concurrently for the best landing odds.
+    /// Roughly doubles submission bandwidth
+    Both,
 }
 
 fn default_tpu_enabled() -> bool {
@@ -294,6 +906,14 @@ fn default_tpu_worker_channel_size() -> usize {
     256
 }
 
+fn default_tpu_confirmation_poll_interval_ms() -> u64 {
+    500
+}
+
+fn default_tpu_confirmation_timeout_ms() -> u64 {
+    30_000
+}
+
 impl Default for TpuConfig {
     fn default() -> Self {
         Self {
@@ -301,10 +921,62 @@ impl Default for TpuConfig {
             num_connections: default_tpu_num_connections(),
             leaders_fanout: default_tpu_leaders_fanout(),
             worker_channel_size: default_tpu_worker_channel_size(),
+            submission_mode: SubmissionMode::default(),
+            confirmation_poll_interval_ms: default_tpu_confirmation_poll_interval_ms(),
+            confirmation_timeout_ms: default_tpu_confirmation_timeout_ms(),
+            auto_disable: None,
+            leader_alignment: None,
+        }
+    }
+}
+
+/// Transaction submission configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubmissionConfig {
+    /// Send each transaction via TPU and to every healthy submission RPC
+    /// endpoint at once. Improves landing odds at the cost of bandwidth and
+    /// RPC rate limit budget
+    #[serde(default)]
+    pub broadcast: bool,
+    /// Trigger kinds that broadcast (e.g. `["timestamp"]`); empty means all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub broadcast_triggers: Vec<String>,
+    /// Pack executions of different threads that come due together into
+    /// shared transactions, when their accounts don't conflict
+    #[serde(default)]
+    pub batch_exec: bool,
+    /// How long the first execution of a batch waits for others (milliseconds)
+    #[serde(default = "default_batch_window_ms")]
+    pub batch_window_ms: u64,
+}
+
+fn default_batch_window_ms() -> u64 {
+    50
+}
+
+impl Default for SubmissionConfig {
+    fn default() -> Self {
+        Self {
+            broadcast: false,
+            broadcast_triggers: Vec::new(),
+            batch_exec: false,
+            batch_window_ms: default_batch_window_ms(),
         }
     }
 }
 
+impl SubmissionConfig {
+    /// Whether executions of a thread with this trigger are broadcast
+    pub fn broadcasts(&self, trigger: &Trigger) -> bool {
+        self.broadcast
+            && (self.broadcast_triggers.is_empty()
+                || self
+                    .broadcast_triggers
+                    .iter()
+                    .any(|kind| kind == trigger_str::kind(trigger)))
+    }
+}
+
 fn default_observability_enabled() -> bool {
     true
 }
@@ -318,61 +990,279 @@ impl Default for ObservabilityConfig {
         Self {
             enabled: default_observability_enabled(),
             storage_path: default_observability_storage_path(),
+            actor_log_levels: HashMap::new(),
+            log_format: LogFormat::default(),
+            otlp: None,
         }
     }
 }
 
-impl ClientConfig {
-    /// Load configuration from a TOML file
-    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref();
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+fn default_otlp_endpoint() -> String {
+    "http://localhost:4318/v1/metrics".to_string()
+}
 
-        let config: ClientConfig = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+fn default_otlp_export_interval_secs() -> u64 {
+    10
+}
 
-        config.validate()?;
-        Ok(config)
+fn default_otlp_service_name() -> String {
+    "antegen-client".to_string()
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_otlp_endpoint(),
+            headers: HashMap::new(),
+            export_interval_secs: default_otlp_export_interval_secs(),
+            service_name: default_otlp_service_name(),
+        }
     }
+}
 
-    /// Save configuration to a TOML file
-    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
+/// Per-thread execution cost ledger configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CostsConfig {
+    /// Record fees, executions, failures and commission per thread
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the cost ledger file
+    #[serde(default = "default_costs_storage_path")]
+    pub storage_path: String,
+    /// Days of history to keep; older daily buckets are compacted away
+    #[serde(default = "default_costs_retention_days")]
+    pub retention_days: u64,
+}
 
-        fs::write(path.as_ref(), content)
-            .with_context(|| format!("Failed to write config file: {}", path.as_ref().display()))?;
+fn default_costs_storage_path() -> String {
+    "~/.antegen/costs.jsonl".to_string()
+}
 
-        Ok(())
-    }
+fn default_costs_retention_days() -> u64 {
+    90
+}
 
-    /// Validate the configuration
-    pub fn validate(&self) -> Result<()> {
-        // Validate keypair path
-        if self.executor.keypair_path.is_empty() {
-            anyhow::bail!("Executor keypair_path cannot be empty");
+impl Default for CostsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            storage_path: default_costs_storage_path(),
+            retention_days: default_costs_retention_days(),
         }
+    }
+}
 
-        // Validate RPC endpoints
-        if self.rpc.endpoints.is_empty() {
-            anyhow::bail!("At least one RPC endpoint must be configured");
-        }
+/// Submitted transaction log configuration, see [`crate::tx_log`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxLogConfig {
+    /// Append every submitted transaction and its outcome to `path`
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the JSON lines log file
+    #[serde(default = "default_tx_log_path")]
+    pub path: String,
+    /// Size in bytes past which the log is rotated to `<path>.1`
+    #[serde(default = "default_tx_log_max_bytes")]
+    pub max_bytes: u64,
+}
 
-        // Ensure at least one RPC datasource endpoint for standalone mode
-        // (Plugin mode will use Geyser instead, but config should be valid for standalone)
-        let has_rpc_datasource = self
-            .rpc
-            .endpoints
-            .iter()
-            .any(|e| matches!(e.role, EndpointRole::Datasource | EndpointRole::Both));
+fn default_tx_log_path() -> String {
+    "~/.antegen/tx-log.jsonl".to_string()
+}
 
-        if !has_rpc_datasource {
-            anyhow::bail!(
-                "At least one RPC datasource endpoint must be configured for standalone mode"
-            );
-        }
+fn default_tx_log_max_bytes() -> u64 {
+    64 * 1024 * 1024
+}
 
-        // Ensure at least one submission endpoint (required for both modes)
+impl Default for TxLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_tx_log_path(),
+            max_bytes: default_tx_log_max_bytes(),
+        }
+    }
+}
+
+/// Alerting configuration, see [`crate::alerts`]
+///
+/// Each rule is off unless its threshold is set. Nothing is evaluated
+/// without at least one sink.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlertsConfig {
+    /// Seconds between rule evaluations
+    #[serde(default = "default_alerts_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Alert when p95 confirmation latency over the last 5 minutes exceeds this
+    #[serde(default)]
+    pub confirmation_p95_ms: Option<u64>,
+    /// Alert when a thread fails this many executions in a row
+    #[serde(default)]
+    pub max_consecutive_failures: Option<u32>,
+    /// Alert when the executor balance drops below this many lamports
+    #[serde(default)]
+    pub min_executor_balance: Option<u64>,
+    /// Alert when no account update arrives for this many seconds
+    #[serde(default)]
+    pub max_update_silence_secs: Option<u64>,
+    /// Where alerts are sent
+    #[serde(default)]
+    pub sinks: Vec<AlertSinkConfig>,
+}
+
+fn default_alerts_check_interval_secs() -> u64 {
+    30
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: default_alerts_check_interval_secs(),
+            confirmation_p95_ms: None,
+            max_consecutive_failures: None,
+            min_executor_balance: None,
+            max_update_silence_secs: None,
+            sinks: Vec::new(),
+        }
+    }
+}
+
+impl AlertsConfig {
+    /// Whether any rule is set and there is somewhere to send its alerts
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+            && (self.confirmation_p95_ms.is_some()
+                || self.max_consecutive_failures.is_some()
+                || self.min_executor_balance.is_some()
+                || self.max_update_silence_secs.is_some())
+    }
+}
+
+/// Alert destination
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertSinkConfig {
+    /// Write alerts to the client log
+    Log,
+    /// POST each alert as JSON
+    Webhook { url: String },
+    /// Post to a Slack incoming webhook
+    Slack { webhook_url: String },
+}
+
+/// gRPC control API configuration, see [`crate::control`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ControlConfig {
+    /// Serve the control API (needs the `control` feature)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the gRPC server listens on
+    #[serde(default = "default_control_bind_address")]
+    pub bind_address: String,
+    /// Require `authorization: Bearer <token>` on every call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+}
+
+fn default_control_bind_address() -> String {
+    "127.0.0.1:50051".to_string()
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_control_bind_address(),
+            auth_token: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Load configuration from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        // Older layouts are upgraded in memory; the file itself is left alone
+        let content = match migrate_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?
+        {
+            Some(migrated) => {
+                log::info!(
+                    "{} uses an older config layout, upgrade it with: antegen config migrate --config {} --write",
+                    path.display(),
+                    path.display()
+                );
+                migrated
+            }
+            None => content,
+        };
+
+        let mut config: ClientConfig = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        config.source_path = Some(path.to_path_buf());
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Save configuration to a TOML file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
+
+        fs::write(path.as_ref(), content)
+            .with_context(|| format!("Failed to write config file: {}", path.as_ref().display()))?;
+
+        Ok(())
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.config_version > CURRENT_CONFIG_VERSION {
+            anyhow::bail!(
+                "config_version {} is newer than this client supports ({})",
+                self.config_version,
+                CURRENT_CONFIG_VERSION
+            );
+        }
+
+        // Validate keypair path
+        if self.executor.keypair_path.is_empty() {
+            anyhow::bail!("Executor keypair_path cannot be empty");
+        }
+        let mut keypair_paths = HashSet::new();
+        for path in self.executor.keypair_paths() {
+            if path.is_empty() {
+                anyhow::bail!("Executor keypairs cannot contain an empty path");
+            }
+            if !keypair_paths.insert(path) {
+                anyhow::bail!("Executor keypair {} is listed more than once", path);
+            }
+        }
+
+        // Validate RPC endpoints
+        if self.rpc.endpoints.is_empty() {
+            anyhow::bail!("At least one RPC endpoint must be configured");
+        }
+
+        // Ensure at least one RPC datasource endpoint for standalone mode
+        // (Plugin mode will use Geyser instead, but config should be valid for standalone)
+        let has_rpc_datasource = self
+            .rpc
+            .endpoints
+            .iter()
+            .any(|e| matches!(e.role, EndpointRole::Datasource | EndpointRole::Both))
+            || self.datasources.helius.is_some();
+
+        if !has_rpc_datasource {
+            anyhow::bail!(
+                "At least one RPC datasource endpoint must be configured for standalone mode"
+            );
+        }
+
+        // Ensure at least one submission endpoint (required for both modes)
         let has_submission_endpoint = self
             .rpc
             .endpoints
@@ -383,6 +1273,35 @@ impl ClientConfig {
             anyhow::bail!("At least one submission endpoint must be configured");
         }
 
+        for endpoint in &self.rpc.endpoints {
+            let Some(limits) = &endpoint.rate_limits else {
+                continue;
+            };
+            let rates = std::iter::once(("default", limits.default))
+                .chain(limits.methods.iter().map(|(k, v)| (k.as_str(), *v)));
+            for (method, rate) in rates {
+                if !(rate.is_finite() && rate > 0.0) {
+                    anyhow::bail!(
+                        "rate_limits.{} for {} must be a positive number",
+                        method,
+                        endpoint.url
+                    );
+                }
+            }
+        }
+
+        // Every kind of request must have an endpoint able to serve it
+        for kind in RequestKind::ALL {
+            let served = self
+                .rpc
+                .endpoints
+                .iter()
+                .any(|e| kind.rank(e.role.clone().into()).is_some());
+            if !served {
+                anyhow::bail!("No RPC endpoint can serve {} requests", kind);
+            }
+        }
+
         // Validate endpoint URLs
         for endpoint in &self.rpc.endpoints {
             if endpoint.url.is_empty() {
@@ -398,6 +1317,15 @@ impl ClientConfig {
             }
         }
 
+        if self.rpc.quarantine_after_secs > 0 {
+            if self.rpc.readmit_after_checks == 0 {
+                anyhow::bail!("rpc.readmit_after_checks must be at least 1");
+            }
+            if self.rpc.probe_interval_secs == 0 {
+                anyhow::bail!("rpc.probe_interval_secs must be greater than 0");
+            }
+        }
+
         // Validate commitment level
         let valid_commitments = ["processed", "confirmed", "finalized"];
         if !valid_commitments.contains(&self.datasources.commitment.as_str()) {
@@ -408,10 +1336,216 @@ impl ClientConfig {
             );
         }
 
+        if let Some(helius) = &self.datasources.helius {
+            helius.resolve_api_key()?;
+        }
+
         // Validate processor config
         if self.processor.max_concurrent_threads == 0 {
             anyhow::bail!("max_concurrent_threads must be greater than 0");
         }
+        if self.processor.fiber_execution_timeout_ms == 0 {
+            anyhow::bail!("fiber_execution_timeout_ms must be greater than 0");
+        }
+        if self.processor.submission_timeout_ms == 0 {
+            anyhow::bail!("submission_timeout_ms must be greater than 0");
+        }
+        if !self.processor.submission_timeout_factor.is_finite()
+            || self.processor.submission_timeout_factor <= 0.0
+        {
+            anyhow::bail!("submission_timeout_factor must be greater than 0");
+        }
+        if self.processor.dedup_window_ms >= MIN_TRIGGER_INTERVAL_MS {
+            anyhow::bail!(
+                "dedup_window_ms must be shorter than the minimum trigger interval ({}ms)",
+                MIN_TRIGGER_INTERVAL_MS
+            );
+        }
+        ThreadFilter::new(&self.processor.thread_filter)?;
+        if let Some(lanes) = &self.processor.lanes {
+            if lanes.time == 0 || lanes.account == 0 {
+                anyhow::bail!(
+                    "processor.lanes.time and processor.lanes.account must be greater than 0"
+                );
+            }
+        }
+        if let Some(adaptive) = &self.processor.adaptive_concurrency {
+            if adaptive.min_workers == 0 {
+                anyhow::bail!("processor.adaptive_concurrency.min_workers must be greater than 0");
+            }
+            if adaptive.target_latency_ms == 0 {
+                anyhow::bail!(
+                    "processor.adaptive_concurrency.target_latency_ms must be greater than 0"
+                );
+            }
+            if !(adaptive.backoff > 0.0 && adaptive.backoff < 1.0) {
+                anyhow::bail!("processor.adaptive_concurrency.backoff must be in (0.0, 1.0)");
+            }
+        }
+
+        if let Some(fraction) = self.processor.fee_ceiling.max_balance_fraction {
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                anyhow::bail!("fee_ceiling.max_balance_fraction must be in (0.0, 1.0]");
+            }
+        }
+
+        // Validate load balancer config
+        let lb = &self.load_balancer;
+        if !(lb.backpressure_threshold > 0.0 && lb.backpressure_threshold <= 1.0) {
+            anyhow::bail!("load_balancer.backpressure_threshold must be in (0.0, 1.0]");
+        }
+        if !(lb.backpressure_release_threshold >= 0.0
+            && lb.backpressure_release_threshold < lb.backpressure_threshold)
+        {
+            anyhow::bail!(
+                "load_balancer.backpressure_release_threshold must be in [0.0, backpressure_threshold)"
+            );
+        }
+
+        // Validate TPU config
+        if self.tpu.confirmation_poll_interval_ms == 0 {
+            anyhow::bail!("tpu.confirmation_poll_interval_ms must be greater than 0");
+        }
+        if self.tpu.confirmation_timeout_ms == 0 {
+            anyhow::bail!("tpu.confirmation_timeout_ms must be greater than 0");
+        }
+        if let Some(auto_disable) = &self.tpu.auto_disable {
+            if auto_disable.window_secs == 0 {
+                anyhow::bail!("tpu.auto_disable.window_secs must be greater than 0");
+            }
+            let rate = auto_disable.max_error_rate;
+            if !(rate > 0.0 && rate <= 1.0) {
+                anyhow::bail!("tpu.auto_disable.max_error_rate must be in (0.0, 1.0]");
+            }
+        }
+        if let Some(alignment) = &self.tpu.leader_alignment {
+            if alignment.max_hold_ms >= self.tpu.confirmation_timeout_ms {
+                anyhow::bail!(
+                    "tpu.leader_alignment.max_hold_ms must be less than tpu.confirmation_timeout_ms"
+                );
+            }
+            for kind in &alignment.latency_sensitive_triggers {
+                if !trigger_str::KINDS.contains(&kind.as_str()) {
+                    anyhow::bail!(
+                        "Unknown trigger kind in tpu.leader_alignment.latency_sensitive_triggers: {}. Must be one of: {}",
+                        kind,
+                        trigger_str::KINDS.join(", ")
+                    );
+                }
+            }
+        }
+
+        // Validate submission config
+        for kind in &self.submission.broadcast_triggers {
+            if !trigger_str::KINDS.contains(&kind.as_str()) {
+                anyhow::bail!(
+                    "Unknown trigger kind in submission.broadcast_triggers: {}. Must be one of: {}",
+                    kind,
+                    trigger_str::KINDS.join(", ")
+                );
+            }
+        }
+        if self.submission.batch_exec && self.submission.batch_window_ms == 0 {
+            anyhow::bail!("submission.batch_window_ms must be greater than 0 with batch_exec");
+        }
+
+        // Validate observability config
+        for actor in self.observability.actor_log_levels.keys() {
+            if logging::actor_module(actor).is_none() {
+                anyhow::bail!(
+                    "Unknown actor in observability.actor_log_levels: {}. Must be a module path or one of: {}",
+                    actor,
+                    logging::ACTOR_MODULES
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        if let Some(otlp) = &self.observability.otlp {
+            if otlp.endpoint.is_empty() {
+                anyhow::bail!("observability.otlp.endpoint cannot be empty");
+            }
+            if otlp.export_interval_secs == 0 {
+                anyhow::bail!("observability.otlp.export_interval_secs must be greater than 0");
+            }
+        }
+
+        // Validate cost ledger config
+        if self.costs.enabled && self.costs.retention_days == 0 {
+            anyhow::bail!("costs.retention_days must be greater than 0");
+        }
+
+        // Validate transaction log config
+        if self.tx_log.enabled {
+            if self.tx_log.path.is_empty() {
+                anyhow::bail!("tx_log.path cannot be empty");
+            }
+            if self.tx_log.max_bytes == 0 {
+                anyhow::bail!("tx_log.max_bytes must be greater than 0");
+            }
+        }
+
+        // Validate fiber result sinks
+        for sink in &self.executor.results.sinks {
+            match sink {
+                ResultSinkConfig::Log => {}
+                ResultSinkConfig::Nats { url, subject } => {
+                    if !url.starts_with("nats://") {
+                        anyhow::bail!("Invalid NATS URL: {}. Must start with nats://", url);
+                    }
+                    if subject.is_empty() || subject.contains(char::is_whitespace) {
+                        anyhow::bail!("Invalid NATS subject: {:?}", subject);
+                    }
+                }
+                ResultSinkConfig::Webhook { url } => {
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        anyhow::bail!(
+                            "Invalid result webhook URL: {}. Must start with http:// or https://",
+                            url
+                        );
+                    }
+                }
+            }
+        }
+
+        // Validate alerts config
+        if self.alerts.is_enabled() && self.alerts.check_interval_secs == 0 {
+            anyhow::bail!("alerts.check_interval_secs must be greater than 0");
+        }
+        if self.alerts.max_consecutive_failures == Some(0) {
+            anyhow::bail!("alerts.max_consecutive_failures must be greater than 0");
+        }
+        for sink in &self.alerts.sinks {
+            let url = match sink {
+                AlertSinkConfig::Log => continue,
+                AlertSinkConfig::Webhook { url } => url,
+                AlertSinkConfig::Slack { webhook_url } => webhook_url,
+            };
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                anyhow::bail!(
+                    "Invalid alert webhook URL: {}. Must start with http:// or https://",
+                    url
+                );
+            }
+        }
+
+        // Validate control API config
+        if self.control.enabled {
+            self.control
+                .bind_address
+                .parse::<std::net::SocketAddr>()
+                .with_context(|| {
+                    format!(
+                        "Invalid control.bind_address: {}",
+                        self.control.bind_address
+                    )
+                })?;
+            if self.control.auth_token.as_deref() == Some("") {
+                anyhow::bail!("control.auth_token cannot be empty");
+            }
+        }
 
         Ok(())
     }
@@ -420,9 +1554,12 @@ impl ClientConfig {
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
             executor: ExecutorConfig {
                 keypair_path: "~/.antegen/executor-keypair.json".to_string(),
+                keypairs: Vec::new(),
                 forgo_commission: false,
+                results: ResultsConfig::default(),
             },
             rpc: RpcConfig {
                 endpoints: vec![RpcEndpoint {
@@ -430,19 +1567,53 @@ impl Default for ClientConfig {
                     ws_url: None,
                     role: EndpointRole::Both,
                     priority: 1,
+                    rate_limits: None,
                 }],
+                quarantine_after_secs: 0,
+                readmit_after_checks: default_readmit_after_checks(),
+                probe_interval_secs: default_probe_interval_secs(),
+                request_budget_ms: default_request_budget_ms(),
+                http_pool_max_idle: default_http_pool_max_idle(),
+                http_pool_idle_timeout_secs: default_http_pool_idle_timeout_secs(),
+                send_rate_limit_deadline_ms: default_send_rate_limit_deadline_ms(),
+                preflight: false,
+                selection: EndpointSelection::default(),
             },
             datasources: DatasourceConfig {
                 commitment: "confirmed".to_string(),
                 program_id: default_program_id(),
+                geyser_socket: None,
+                clock_poll_interval_ms: default_clock_poll_interval_ms(),
+                clock_use_websocket: false,
+                helius: None,
             },
             processor: ProcessorConfig {
                 max_concurrent_threads: 10,
+                fiber_execution_timeout_ms: default_fiber_execution_timeout_ms(),
+                submission_timeout_ms: default_submission_timeout_ms(),
+                submission_timeout_factor: default_submission_timeout_factor(),
+                simulate_before_send: false,
+                reconcile_on_start: false,
+                gap_backfill: GapBackfillConfig::default(),
+                fee_ceiling: FeeCeilingConfig::default(),
+                dedup_window_ms: default_dedup_window_ms(),
+                replay_guard_slots: default_replay_guard_slots(),
+                thread_filter: default_thread_filter(),
+                prefetch_lookahead_secs: default_prefetch_lookahead_secs(),
+                lanes: None,
+                max_queued_threads: default_max_queued_threads(),
+                adaptive_concurrency: None,
             },
             cache: CacheConfig::default(),
             load_balancer: LoadBalancerConfigFile::default(),
             observability: ObservabilityConfig::default(),
             tpu: TpuConfig::default(),
+            submission: SubmissionConfig::default(),
+            costs: CostsConfig::default(),
+            tx_log: TxLogConfig::default(),
+            alerts: AlertsConfig::default(),
+            control: ControlConfig::default(),
+            source_path: None,
         }
     }
 }
@@ -516,6 +1687,7 @@ mod tests {
             ws_url: None,
             role: EndpointRole::Both,
             priority: 1,
+            rate_limits: None,
         };
         assert_eq!(endpoint.get_ws_url(), "ws://localhost:8899");
 
@@ -525,6 +1697,7 @@ mod tests {
             ws_url: None,
             role: EndpointRole::Both,
             priority: 1,
+            rate_limits: None,
         };
         assert_eq!(endpoint.get_ws_url(), "wss://api.mainnet-beta.solana.com");
 
@@ -534,7 +1707,553 @@ mod tests {
             ws_url: Some("wss://custom-ws-url.com".to_string()),
             role: EndpointRole::Both,
             priority: 1,
+            rate_limits: None,
         };
         assert_eq!(endpoint.get_ws_url(), "wss://custom-ws-url.com");
     }
+
+    #[test]
+    fn test_gap_backfill_policy_parsing() {
+        let mut config = ClientConfig::default();
+        config.processor.gap_backfill.slot = GapPolicy::Skip;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        config.save(temp_file.path()).unwrap();
+        let saved = std::fs::read_to_string(temp_file.path()).unwrap();
+        assert!(saved.contains("slot = \"skip\""));
+        assert!(saved.contains("time = \"catch_up\""));
+
+        let loaded = ClientConfig::load(temp_file.path()).unwrap();
+        let gap = loaded.processor.gap_backfill;
+        assert_eq!(gap.max_slot_gap, default_max_slot_gap());
+        assert_eq!(gap.time, GapPolicy::CatchUp);
+        assert_eq!(gap.slot, GapPolicy::Skip);
+        assert_eq!(gap.epoch, GapPolicy::CatchUp);
+    }
+
+    #[test]
+    fn test_dedup_window_validation() {
+        let mut config = ClientConfig::default();
+        config.processor.dedup_window_ms = 0;
+        assert!(config.validate().is_ok());
+
+        config.processor.dedup_window_ms = MIN_TRIGGER_INTERVAL_MS;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_fee_ceiling_fraction_validation() {
+        let mut config = ClientConfig::default();
+        config.processor.fee_ceiling.max_balance_fraction = Some(0.05);
+        assert!(config.validate().is_ok());
+
+        config.processor.fee_ceiling.max_balance_fraction = Some(1.5);
+        assert!(config.validate().is_err());
+
+        config.processor.fee_ceiling.max_balance_fraction = Some(0.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tpu_confirmation_validation() {
+        let mut config = ClientConfig::default();
+        assert_eq!(config.tpu.confirmation_poll_interval_ms, 500);
+        assert_eq!(config.tpu.confirmation_timeout_ms, 30_000);
+
+        config.tpu.confirmation_poll_interval_ms = 0;
+        assert!(config.validate().is_err());
+
+        config.tpu.confirmation_poll_interval_ms = 500;
+        config.tpu.confirmation_timeout_ms = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tpu_auto_disable_config() {
+        let mut config = ClientConfig::default();
+        assert!(config.tpu.auto_disable.is_none());
+
+        let auto_disable: TpuAutoDisableConfig = toml::from_str("max_error_rate = 0.25").unwrap();
+        assert_eq!(
+            auto_disable,
+            TpuAutoDisableConfig {
+                max_error_rate: 0.25,
+                ..TpuAutoDisableConfig::default()
+            }
+        );
+        config.tpu.auto_disable = Some(auto_disable);
+        assert!(config.validate().is_ok());
+
+        config.tpu.auto_disable = Some(TpuAutoDisableConfig {
+            max_error_rate: 0.0,
+            ..auto_disable
+        });
+        assert!(config.validate().is_err());
+
+        config.tpu.auto_disable = Some(TpuAutoDisableConfig {
+            window_secs: 0,
+            ..auto_disable
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tpu_leader_alignment_config() {
+        let mut config = ClientConfig::default();
+        assert!(config.tpu.leader_alignment.is_none());
+
+        let alignment: LeaderAlignmentConfig = toml::from_str("max_hold_ms = 800").unwrap();
+        assert_eq!(alignment.latency_sensitive_triggers, vec!["account"]);
+        let interval = Trigger::Interval {
+            seconds: 60,
+            skippable: false,
+            jitter: 0,
+        };
+        let account = Trigger::Account {
+            address: Pubkey::new_unique(),
+            offset: 0,
+            size: 8,
+        };
+        assert_eq!(alignment.max_hold(&interval), Duration::from_millis(800));
+        assert_eq!(alignment.max_hold(&account), Duration::ZERO);
+
+        config.tpu.leader_alignment = Some(alignment.clone());
+        assert!(config.validate().is_ok());
+
+        config.tpu.leader_alignment = Some(LeaderAlignmentConfig {
+            max_hold_ms: config.tpu.confirmation_timeout_ms,
+            ..alignment.clone()
+        });
+        assert!(config.validate().is_err());
+
+        config.tpu.leader_alignment = Some(LeaderAlignmentConfig {
+            latency_sensitive_triggers: vec!["sometimes".to_string()],
+            ..alignment
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_lanes_config() {
+        let mut config = ClientConfig::default();
+        assert!(config.processor.lanes.is_none());
+
+        let lanes: LanesConfig = toml::from_str(
+            r#"
+            time = 4
+            account = 8
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            lanes,
+            LanesConfig {
+                time: 4,
+                account: 8,
+                borrow_idle: true,
+            }
+        );
+
+        config.processor.lanes = Some(lanes);
+        assert!(config.validate().is_ok());
+
+        config.processor.lanes = Some(LanesConfig {
+            account: 0,
+            ..lanes
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_config() {
+        let mut config = ClientConfig::default();
+        assert!(config.processor.adaptive_concurrency.is_none());
+
+        let adaptive: AdaptiveConcurrencyConfig = toml::from_str("min_workers = 2").unwrap();
+        assert_eq!(
+            adaptive,
+            AdaptiveConcurrencyConfig {
+                min_workers: 2,
+                ..AdaptiveConcurrencyConfig::default()
+            }
+        );
+        config.processor.adaptive_concurrency = Some(adaptive);
+        assert!(config.validate().is_ok());
+
+        config.processor.adaptive_concurrency = Some(AdaptiveConcurrencyConfig {
+            backoff: 1.0,
+            ..adaptive
+        });
+        assert!(config.validate().is_err());
+
+        config.processor.adaptive_concurrency = Some(AdaptiveConcurrencyConfig {
+            min_workers: 0,
+            ..adaptive
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_quarantine_validation() {
+        let mut config = ClientConfig::default();
+        config.rpc.readmit_after_checks = 0;
+        // Ignored while quarantine is disabled
+        assert!(config.validate().is_ok());
+
+        config.rpc.quarantine_after_secs = 300;
+        assert!(config.validate().is_err());
+
+        config.rpc.readmit_after_checks = 3;
+        assert!(config.validate().is_ok());
+
+        let pool_config = crate::rpc::RpcPoolConfig::from_rpc_config(&config.rpc);
+        assert_eq!(
+            pool_config.quarantine.quarantine_after,
+            Some(std::time::Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn test_broadcast_trigger_override() {
+        let mut config = ClientConfig::default();
+        let timestamp = Trigger::Timestamp {
+            unix_ts: 0,
+            jitter: 0,
+        };
+        let slot = Trigger::Slot { slot: 0 };
+        assert!(!config.submission.broadcasts(&timestamp));
+
+        config.submission.broadcast = true;
+        assert!(config.submission.broadcasts(&timestamp));
+        assert!(config.submission.broadcasts(&slot));
+
+        config.submission.broadcast_triggers = vec!["timestamp".to_string()];
+        assert!(config.validate().is_ok());
+        assert!(config.submission.broadcasts(&timestamp));
+        assert!(!config.submission.broadcasts(&slot));
+
+        config.submission.broadcast_triggers = vec!["hourly".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_observability_logging_config() {
+        let observability: ObservabilityConfig = toml::from_str(
+            r#"
+            log_format = "json"
+
+            [actor_log_levels]
+            staging_actor = "warn"
+            worker_actor = "debug"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(observability.log_format, LogFormat::Json);
+        assert_eq!(
+            observability.actor_log_levels["staging_actor"],
+            log::LevelFilter::Warn
+        );
+
+        let mut config = ClientConfig {
+            observability,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config
+            .observability
+            .actor_log_levels
+            .insert("mystery_actor".to_string(), log::LevelFilter::Off);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_observability_otlp_config() {
+        let observability: ObservabilityConfig = toml::from_str(
+            r#"
+            [otlp]
+            endpoint = "https://otel.example.com/v1/metrics"
+
+            [otlp.headers]
+            authorization = "Bearer token"
+            "#,
+        )
+        .unwrap();
+        let otlp = observability.otlp.clone().unwrap();
+        assert_eq!(otlp.endpoint, "https://otel.example.com/v1/metrics");
+        assert_eq!(otlp.headers["authorization"], "Bearer token");
+        assert_eq!(otlp.export_interval_secs, 10);
+        assert_eq!(otlp.service_name, "antegen-client");
+        assert!(ObservabilityConfig::default().otlp.is_none());
+
+        let mut config = ClientConfig {
+            observability,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config
+            .observability
+            .otlp
+            .as_mut()
+            .unwrap()
+            .export_interval_secs = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_alerts_config() {
+        let alerts: AlertsConfig = toml::from_str(
+            r#"
+            confirmation_p95_ms = 2000
+            max_consecutive_failures = 3
+
+            [[sinks]]
+            type = "log"
+
+            [[sinks]]
+            type = "slack"
+            webhook_url = "https://hooks.slack.com/services/T/B/x"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(alerts.check_interval_secs, 30);
+        assert_eq!(alerts.confirmation_p95_ms, Some(2000));
+        assert!(alerts.min_executor_balance.is_none());
+        assert_eq!(
+            alerts.sinks,
+            vec![
+                AlertSinkConfig::Log,
+                AlertSinkConfig::Slack {
+                    webhook_url: "https://hooks.slack.com/services/T/B/x".to_string()
+                },
+            ]
+        );
+        assert!(alerts.is_enabled());
+        assert!(!AlertsConfig::default().is_enabled());
+
+        let mut config = ClientConfig {
+            alerts,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.alerts.sinks.push(AlertSinkConfig::Webhook {
+            url: "alerts.example.com".to_string(),
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_control_config() {
+        let control: ControlConfig = toml::from_str(
+            r#"
+            enabled = true
+            auth_token = "secret"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(control.bind_address, "127.0.0.1:50051");
+        assert_eq!(control.auth_token.as_deref(), Some("secret"));
+        assert!(!ControlConfig::default().enabled);
+
+        let mut config = ClientConfig {
+            control,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.control.bind_address = "localhost".to_string();
+        assert!(config.validate().is_err());
+    }
+    #[test]
+    fn test_results_config() {
+        let executor: ExecutorConfig = toml::from_str(
+            r#"
+            keypair_path = "~/.antegen/executor-keypair.json"
+
+            [[results.sinks]]
+            type = "nats"
+            url = "nats://localhost:4222"
+            subject = "antegen.results"
+
+            [[results.sinks]]
+            type = "webhook"
+            url = "https://example.com/results"
+            "#,
+        )
+        .unwrap();
+        assert!(executor.results.is_enabled());
+        assert_eq!(
+            executor.results.sinks[0],
+            ResultSinkConfig::Nats {
+                url: "nats://localhost:4222".to_string(),
+                subject: "antegen.results".to_string(),
+            }
+        );
+        assert!(!ClientConfig::default().executor.results.is_enabled());
+
+        let mut config = ClientConfig {
+            executor,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.executor.results.sinks.push(ResultSinkConfig::Nats {
+            url: "localhost:4222".to_string(),
+            subject: "antegen.results".to_string(),
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_executor_keypairs() {
+        let executor: ExecutorConfig = toml::from_str(
+            r#"
+            keypair_path = "~/.antegen/executor-keypair.json"
+            keypairs = ["~/.antegen/payer-1.json", "~/.antegen/payer-2.json"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            executor.keypair_paths().collect::<Vec<_>>(),
+            vec![
+                "~/.antegen/executor-keypair.json",
+                "~/.antegen/payer-1.json",
+                "~/.antegen/payer-2.json"
+            ]
+        );
+        assert_eq!(ClientConfig::default().executor.keypair_paths().count(), 1);
+
+        let mut config = ClientConfig {
+            executor,
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config
+            .executor
+            .keypairs
+            .push("~/.antegen/executor-keypair.json".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_endpoint_rate_limits() {
+        let rpc: RpcConfig = toml::from_str(
+            r#"
+            [[endpoints]]
+            url = "http://localhost:8899"
+            role = "both"
+
+            [endpoints.rate_limits]
+            default = 50
+            get_program_accounts = 2
+            send_transaction = 20.5
+            burst = 4
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rpc.send_rate_limit_deadline_ms, 50);
+        assert!(!rpc.preflight);
+        assert_eq!(rpc.selection, EndpointSelection::RoundRobin);
+        let limits = rpc.endpoints[0].rate_limits.clone().unwrap();
+        assert_eq!(limits.default, 50.0);
+        assert_eq!(limits.burst, 4);
+        assert_eq!(limits.methods.len(), 2);
+
+        let buckets = crate::rpc::MethodRateLimitConfig::from(&limits);
+        assert_eq!(buckets.default.requests_per_second, 50.0);
+        assert_eq!(
+            buckets.methods["getProgramAccounts"].requests_per_second,
+            2.0
+        );
+        assert_eq!(buckets.methods["sendTransaction"].requests_per_second, 20.5);
+        assert_eq!(buckets.methods["sendTransaction"].burst_capacity, 4);
+
+        // Without a burst, buckets hold one second's worth
+        let buckets = crate::rpc::MethodRateLimitConfig::from(&EndpointRateLimits {
+            burst: 0,
+            ..limits.clone()
+        });
+        assert_eq!(buckets.methods["getProgramAccounts"].burst_capacity, 2);
+        assert_eq!(buckets.methods["sendTransaction"].burst_capacity, 21);
+
+        let mut config = ClientConfig::default();
+        config.rpc.endpoints[0].rate_limits = Some(limits.clone());
+        assert!(config.validate().is_ok());
+        config.rpc.endpoints[0].rate_limits = Some(EndpointRateLimits {
+            methods: BTreeMap::from([("get_program_accounts".to_string(), 0.0)]),
+            ..limits
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_backpressure_thresholds() {
+        let mut config = ClientConfig::default();
+        assert_eq!(config.load_balancer.backpressure_threshold, 0.9);
+        assert_eq!(config.load_balancer.backpressure_release_threshold, 0.5);
+        assert!(config.validate().is_ok());
+
+        // Release must sit below the engage threshold
+        config.load_balancer.backpressure_release_threshold = 0.9;
+        assert!(config.validate().is_err());
+        config.load_balancer.backpressure_release_threshold = 0.5;
+        config.load_balancer.backpressure_threshold = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_helius_api_key_from_env() {
+        let config: HeliusConfig =
+            toml::from_str(r#"api_key = "${ANTEGEN_TEST_HELIUS_KEY}""#).unwrap();
+        assert!(config.enhanced);
+        assert_eq!(config.ws_url, "wss://mainnet.helius-rpc.com");
+        assert!(config.resolve_api_key().is_err());
+
+        std::env::set_var("ANTEGEN_TEST_HELIUS_KEY", "abc123");
+        assert_eq!(config.resolve_api_key().unwrap(), "abc123");
+
+        // A Helius stream is enough of a datasource on its own
+        let mut client = ClientConfig::default();
+        client.rpc.endpoints[0].role = EndpointRole::Submission;
+        for role in [EndpointRole::Read, EndpointRole::Simulation] {
+            let mut endpoint = client.rpc.endpoints[0].clone();
+            endpoint.role = role;
+            client.rpc.endpoints.push(endpoint);
+        }
+        assert!(client.validate().is_err());
+        client.datasources.helius = Some(config);
+        assert!(client.validate().is_ok());
+    }
+
+    #[test]
+    fn test_tx_log_config() {
+        let tx_log: TxLogConfig = toml::from_str("enabled = true").unwrap();
+        assert_eq!(tx_log.path, "~/.antegen/tx-log.jsonl");
+        assert_eq!(tx_log.max_bytes, 64 * 1024 * 1024);
+
+        let mut config = ClientConfig::default();
+        config.tx_log = tx_log;
+        assert!(config.validate().is_ok());
+        config.tx_log.max_bytes = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_submission_timeout_config() {
+        let processor: ProcessorConfig = toml::from_str("").unwrap();
+        assert_eq!(processor.submission_timeout_ms, 30_000);
+        assert_eq!(processor.submission_timeout_factor, 0.5);
+
+        let mut config = ClientConfig::default();
+        config.processor.submission_timeout_factor = 0.0;
+        assert!(config.validate().is_err());
+        config.processor.submission_timeout_factor = f64::NAN;
+        assert!(config.validate().is_err());
+        config.processor.submission_timeout_factor = 2.0;
+        config.processor.submission_timeout_ms = 0;
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,245 @@
+//! Upgrades older antegen.toml layouts to the current one
+//!
+//! A config file records its layout in a top-level `config_version`; files
+//! without it are layout 1. Each migration moves or renames the fields of one
+//! layout step by step, editing the document in place so comments and
+//! formatting survive. [`ClientConfig::load`](crate::config::ClientConfig::load)
+//! applies them in memory; `antegen config migrate --write` persists them.
+
+use anyhow::{bail, Result};
+use toml_edit::{DocumentMut, Item, Key, Table};
+
+/// Layout written by this version of the client
+pub const CURRENT_CONFIG_VERSION: u32 = 3;
+
+/// Layout of a file without `config_version`
+pub const LEGACY_CONFIG_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades layout `n + 1` to `n + 2`
+const MIGRATIONS: [fn(&mut DocumentMut); (CURRENT_CONFIG_VERSION - 1) as usize] =
+    [migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// The layout a config document declares
+pub fn config_version(doc: &DocumentMut) -> Result<u32> {
+    match doc.get("config_version") {
+        None => Ok(LEGACY_CONFIG_VERSION),
+        Some(item) => match item.as_integer() {
+            Some(version) if version >= 1 => Ok(version as u32),
+            _ => bail!("config_version must be a positive integer"),
+        },
+    }
+}
+
+/// Upgrade a config document in place to [`CURRENT_CONFIG_VERSION`]
+///
+/// Returns the layout the document was in.
+pub fn migrate(doc: &mut DocumentMut) -> Result<u32> {
+    let from = config_version(doc)?;
+    if from > CURRENT_CONFIG_VERSION {
+        bail!(
+            "config_version {} is newer than this client supports ({})",
+            from,
+            CURRENT_CONFIG_VERSION
+        );
+    }
+
+    for migration in &MIGRATIONS[(from - 1) as usize..] {
+        migration(doc);
+    }
+    if from < CURRENT_CONFIG_VERSION {
+        doc.insert(
+            "config_version",
+            toml_edit::value(CURRENT_CONFIG_VERSION as i64),
+        );
+    }
+    Ok(from)
+}
+
+/// Upgrade config file contents, or `None` if already current
+pub fn migrate_str(content: &str) -> Result<Option<String>> {
+    let mut doc: DocumentMut = content.parse()?;
+    if migrate(&mut doc)? == CURRENT_CONFIG_VERSION {
+        return Ok(None);
+    }
+    Ok(Some(doc.to_string()))
+}
+
+/// Layout 1 used short field names
+fn migrate_v1_to_v2(doc: &mut DocumentMut) {
+    rename(doc, "processor", "max_threads", "max_concurrent_threads");
+    rename(doc, "cache", "capacity", "max_capacity");
+    rename(doc, "tpu", "connections", "num_connections");
+    rename(doc, "tpu", "fanout", "leaders_fanout");
+}
+
+/// Layout 2 kept the eviction buffer with the cache and named the new-thread
+/// delay `process_delay`
+fn migrate_v2_to_v3(doc: &mut DocumentMut) {
+    relocate(doc, "cache", "load_balancer", "eviction_buffer");
+    rename(
+        doc,
+        "load_balancer",
+        "process_delay",
+        "thread_process_delay",
+    );
+}
+
+/// Rename a key within a table, keeping its value and comments
+///
+/// If the new key is already set, it wins and the old one is dropped.
+fn rename(doc: &mut DocumentMut, table: &str, from: &str, to: &str) {
+    let Some(table) = doc.get_mut(table).and_then(Item::as_table_mut) else {
+        return;
+    };
+    let Some((key, item)) = table.remove_entry(from) else {
+        return;
+    };
+    if table.contains_key(to) {
+        log::warn!("Config has both {} and {}, keeping {}", from, to, to);
+        return;
+    }
+    insert_as(table, &key, to, item);
+}
+
+/// Move a key to another table, creating it if needed
+fn relocate(doc: &mut DocumentMut, from: &str, to: &str, name: &str) {
+    let Some((key, item)) = doc
+        .get_mut(from)
+        .and_then(Item::as_table_mut)
+        .and_then(|table| table.remove_entry(name))
+    else {
+        return;
+    };
+
+    let target = doc
+        .entry(to)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut();
+    match target {
+        Some(target) if !target.contains_key(name) => insert_as(target, &key, name, item),
+        _ => log::warn!(
+            "Config has both {}.{} and {}.{}, keeping {}.{}",
+            from,
+            name,
+            to,
+            name,
+            to,
+            name
+        ),
+    }
+}
+
+fn insert_as(table: &mut Table, old_key: &Key, name: &str, item: Item) {
+    let mut key = Key::new(name);
+    *key.leaf_decor_mut() = old_key.leaf_decor().clone();
+    table.insert_formatted(&key, item);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ClientConfig;
+
+    const LAYOUT_1: &str = r#"[executor]
+keypair_path = "~/.antegen/executor-keypair.json"
+
+[rpc]
+[[rpc.endpoints]]
+url = "http://localhost:8899"
+role = "both"
+priority = 1
+
+[datasources]
+commitment = "confirmed"
+
+[processor]
+# Keep this low on small machines
+max_threads = 4
+
+[cache]
+capacity = 5000
+
+[load_balancer]
+grace_period = 15
+
+[tpu]
+connections = 2
+fanout = 8
+"#;
+
+    const LAYOUT_2: &str = r#"config_version = 2
+
+[rpc]
+[[rpc.endpoints]]
+url = "http://localhost:8899"
+role = "both"
+priority = 1
+
+[datasources]
+commitment = "finalized"
+
+[processor]
+max_concurrent_threads = 6
+
+[cache]
+max_capacity = 2000
+# Seconds to keep overdue threads around
+eviction_buffer = 45
+
+[load_balancer]
+process_delay = 2
+"#;
+
+    #[test]
+    fn test_migrate_layout_1() {
+        let migrated = migrate_str(LAYOUT_1).unwrap().unwrap();
+        assert!(migrated.contains("# Keep this low on small machines\nmax_concurrent_threads = 4"));
+
+        let config: ClientConfig = toml::from_str(&migrated).unwrap();
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.processor.max_concurrent_threads, 4);
+        assert_eq!(config.cache.max_capacity, 5000);
+        assert_eq!(config.load_balancer.grace_period, 15);
+        assert_eq!(config.tpu.num_connections, 2);
+        assert_eq!(config.tpu.leaders_fanout, 8);
+        assert!(config.validate().is_ok());
+
+        // Already current
+        assert!(migrate_str(&migrated).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_migrate_layout_2() {
+        let migrated = migrate_str(LAYOUT_2).unwrap().unwrap();
+        assert!(migrated.contains("# Seconds to keep overdue threads around\neviction_buffer = 45"));
+
+        let config: ClientConfig = toml::from_str(&migrated).unwrap();
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.datasources.commitment, "finalized");
+        assert_eq!(config.processor.max_concurrent_threads, 6);
+        assert_eq!(config.cache.max_capacity, 2000);
+        assert_eq!(config.load_balancer.eviction_buffer, 45);
+        assert_eq!(config.load_balancer.thread_process_delay, 2);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_load_applies_migrations() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), LAYOUT_1).unwrap();
+
+        let config = ClientConfig::load(temp_file.path()).unwrap();
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.processor.max_concurrent_threads, 4);
+
+        // The file is only rewritten by `antegen config migrate --write`
+        assert_eq!(std::fs::read_to_string(temp_file.path()).unwrap(), LAYOUT_1);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_layout() {
+        let content = format!("config_version = {}\n", CURRENT_CONFIG_VERSION + 1);
+        assert!(migrate_str(&content).is_err());
+        assert!(migrate_str("config_version = 0\n").is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! gRPC control API of a running client
+//!
+//! With the `control` feature and `control.enabled`, [`run_control_server`]
+//! serves `proto/control.proto` on `control.bind_address`: status, pausing
+//! and resuming dispatch, config reload and the tracked thread list. Calls
+//! are answered by the live actors through their regular messages, and the
+//! server stops with the actor tree.
+//!
+//! With `control.auth_token` set, every call must carry
+//! `authorization: Bearer <token>` metadata.
+//!
+//! Without the feature, [`run_control_server`] only warns that the API is
+//! unavailable.
+
+use crate::actors::messages::{ProcessorMessage, StagingMessage};
+use crate::resources::SharedResources;
+use ractor::ActorRef;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[cfg(feature = "control")]
+pub use server::*;
+
+#[cfg(not(feature = "control"))]
+pub use noop::*;
+
+/// What the control API answers from
+#[derive(Clone)]
+pub struct ControlContext {
+    pub staging: ActorRef<StagingMessage>,
+    pub processor: ActorRef<ProcessorMessage>,
+    pub resources: SharedResources,
+    /// Config file re-read by `ReloadConfig`
+    pub config_path: Option<PathBuf>,
+    pub started_at: Instant,
+}
+
+#[cfg(feature = "control")]
+mod server {
+    use super::ControlContext;
+    use crate::actors::messages::{ProcessorMessage, StagingMessage};
+    use crate::config::ControlConfig;
+    use crate::rpc::{EndpointHealth, EndpointStatus};
+    use ractor::ActorRef;
+    use std::net::SocketAddr;
+    use tokio::sync::oneshot;
+    use tokio_util::sync::CancellationToken;
+    use tonic::transport::Server;
+    use tonic::{Request, Response, Status};
+
+    /// Types and services generated from `proto/control.proto`
+    pub mod proto {
+        tonic::include_proto!("antegen.control.v1");
+    }
+
+    use proto::control_server::{Control, ControlServer};
+
+    /// Serve the control API until `cancel` fires
+    pub async fn run_control_server(
+        config: ControlConfig,
+        context: ControlContext,
+        cancel: CancellationToken,
+    ) {
+        let addr: SocketAddr = match config.bind_address.parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                log::error!(
+                    "Control API disabled, invalid bind address {}: {}",
+                    config.bind_address,
+                    e
+                );
+                return;
+            }
+        };
+
+        let expected = config.auth_token.map(|token| format!("Bearer {}", token));
+        let service = ControlServer::with_interceptor(ControlService { context }, move |request| {
+            check_auth(expected.as_deref(), request)
+        });
+
+        log::info!("Control API listening on {}", addr);
+        let result = Server::builder()
+            .add_service(service)
+            .serve_with_shutdown(addr, cancel.cancelled())
+            .await;
+        match result {
+            Ok(()) => log::debug!("Control API stopped"),
+            Err(e) => log::error!("Control API server failed: {}", e),
+        }
+    }
+
+    /// Accept `request` if it carries the expected `authorization` value
+    fn check_auth(expected: Option<&str>, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = expected else {
+            return Ok(request);
+        };
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        if authorization == Some(expected) {
+            Ok(request)
+        } else {
+            Err(Status::unauthenticated(
+                "Missing or invalid control API token",
+            ))
+        }
+    }
+
+    /// Send `message` built around a reply channel and wait for the reply
+    async fn ask<M, T>(
+        actor: &ActorRef<M>,
+        message: impl FnOnce(oneshot::Sender<T>) -> M,
+    ) -> Result<T, Status>
+    where
+        M: ractor::Message,
+    {
+        let (tx, rx) = oneshot::channel();
+        actor
+            .send_message(message(tx))
+            .map_err(|e| Status::unavailable(format!("Client is shutting down: {}", e)))?;
+        rx.await
+            .map_err(|_| Status::unavailable("Client is shutting down"))
+    }
+
+    fn endpoint_status(status: EndpointStatus) -> proto::EndpointStatus {
+        let health = match status.health {
+            EndpointHealth::Healthy => "healthy",
+            EndpointHealth::Degraded => "degraded",
+            EndpointHealth::Unhealthy => "unhealthy",
+        };
+        proto::EndpointStatus {
+            url: status.url,
+            role: format!("{:?}", status.role).to_lowercase(),
+            health: health.to_string(),
+            quarantined: status.quarantine.is_some(),
+            latency_ms: status
+                .latency
+                .map_or(0.0, |latency| latency.as_secs_f64() * 1000.0),
+        }
+    }
+
+    struct ControlService {
+        context: ControlContext,
+    }
+
+    impl ControlService {
+        async fn set_processing_paused(&self, paused: bool) -> Result<bool, Status> {
+            ask(&self.context.staging, |tx| {
+                StagingMessage::SetProcessingPaused(paused, tx)
+            })
+            .await
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Control for ControlService {
+        async fn get_status(
+            &self,
+            _request: Request<proto::GetStatusRequest>,
+        ) -> Result<Response<proto::GetStatusResponse>, Status> {
+            let staging = ask(&self.context.staging, StagingMessage::QueryStatus).await?;
+            let processor = ask(&self.context.processor, ProcessorMessage::QueryStatus).await?;
+            let endpoints = self
+                .context
+                .resources
+                .rpc_client
+                .endpoint_states()
+                .into_iter()
+                .map(endpoint_status)
+                .collect();
+
+            Ok(Response::new(proto::GetStatusResponse {
+                uptime_secs: self.context.started_at.elapsed().as_secs(),
+                processing_paused: staging.processing_paused,
+                tracked_threads: staging.total_threads as u64,
+                queue_depth: (staging.time_queue_size
+                    + staging.slot_queue_size
+                    + staging.epoch_queue_size) as u64,
+                pending: processor.pending_queue_size as u64,
+                in_flight: processor.active_workers as u64,
+                endpoints,
+            }))
+        }
+
+        async fn pause_processing(
+            &self,
+            _request: Request<proto::PauseProcessingRequest>,
+        ) -> Result<Response<proto::PauseProcessingResponse>, Status> {
+            let was_paused = self.set_processing_paused(true).await?;
+            Ok(Response::new(proto::PauseProcessingResponse { was_paused }))
+        }
+
+        async fn resume_processing(
+            &self,
+            _request: Request<proto::ResumeProcessingRequest>,
+        ) -> Result<Response<proto::ResumeProcessingResponse>, Status> {
+            let was_paused = self.set_processing_paused(false).await?;
+            Ok(Response::new(proto::ResumeProcessingResponse {
+                was_paused,
+            }))
+        }
+
+        async fn reload_config(
+            &self,
+            _request: Request<proto::ReloadConfigRequest>,
+        ) -> Result<Response<proto::ReloadConfigResponse>, Status> {
+            let path = self.context.config_path.as_deref().ok_or_else(|| {
+                Status::failed_precondition("Client was not started from a config file")
+            })?;
+            let applied = crate::reload_config(path)
+                .map_err(|e| Status::invalid_argument(format!("{:#}", e)))?;
+            log::info!("Reloaded config from {} via control API", path.display());
+
+            Ok(Response::new(proto::ReloadConfigResponse {
+                applied: applied.into_iter().map(String::from).collect(),
+            }))
+        }
+
+        async fn list_threads(
+            &self,
+            _request: Request<proto::ListThreadsRequest>,
+        ) -> Result<Response<proto::ListThreadsResponse>, Status> {
+            let mut threads = ask(&self.context.staging, StagingMessage::ListThreads).await?;
+            threads.sort_by_key(|thread| thread.pubkey);
+
+            Ok(Response::new(proto::ListThreadsResponse {
+                threads: threads
+                    .into_iter()
+                    .map(|thread| proto::ThreadInfo {
+                        pubkey: thread.pubkey.to_string(),
+                        exec_count: thread.exec_count,
+                        paused: thread.paused,
+                        resume_at: thread.resume_at,
+                        in_flight: thread.queued,
+                    })
+                    .collect(),
+            }))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn request_with(authorization: Option<&str>) -> Request<()> {
+            let mut request = Request::new(());
+            if let Some(value) = authorization {
+                request
+                    .metadata_mut()
+                    .insert("authorization", value.parse().unwrap());
+            }
+            request
+        }
+
+        #[test]
+        fn test_check_auth() {
+            assert!(check_auth(None, request_with(None)).is_ok());
+
+            let expected = Some("Bearer secret");
+            assert!(check_auth(expected, request_with(Some("Bearer secret"))).is_ok());
+            let err = check_auth(expected, request_with(Some("Bearer wrong"))).unwrap_err();
+            assert_eq!(err.code(), tonic::Code::Unauthenticated);
+            assert!(check_auth(expected, request_with(None)).is_err());
+        }
+    }
+}
+
+#[cfg(not(feature = "control"))]
+mod noop {
+    use super::ControlContext;
+    use crate::config::ControlConfig;
+    use tokio_util::sync::CancellationToken;
+
+    /// Without the `control` feature there is no server to run
+    pub async fn run_control_server(
+        config: ControlConfig,
+        _context: ControlContext,
+        _cancel: CancellationToken,
+    ) {
+        log::warn!(
+            "control.enabled is set ({}) but this build lacks the `control` feature",
+            config.bind_address
+        );
+    }
+}
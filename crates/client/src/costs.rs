@@ -0,0 +1,466 @@
+//! Per-thread execution cost accounting
+//!
+//! Tracks, per thread, how many executions landed or failed, the network fees
+//! paid for them (base + priority) and the lamports paid out of the thread
+//! (executor commission, core team fee and fee-payer reimbursement).
+//!
+//! Totals are kept in daily buckets. Each recorded execution is appended to a
+//! JSONL log, which is loaded back into buckets on open. Buckets older than the
+//! retention window are dropped, and the log is rewritten from the buckets once
+//! it holds more lines than they need, so it never grows far beyond
+//! `threads × retention_days` entries.
+
+use crate::config::CostsConfig;
+use crate::rpc::{RpcPool, SafeTransaction};
+use anyhow::{anyhow, Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Page size used when walking a thread's signature history
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+
+/// Appended lines allowed beyond the bucket count before the log is rewritten
+const COMPACT_SLACK: usize = 1024;
+
+/// Log line Anchor emits for the thread program's `exec_thread` instruction
+const EXEC_THREAD_LOG: &str = "Program log: Instruction: ExecThread";
+
+/// Accumulated execution costs for a thread
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadCosts {
+    /// Executions that landed successfully
+    pub executions: u64,
+    /// Executions that failed (on-chain or after exhausting retries)
+    pub failures: u64,
+    /// Network fees paid by the executor (base + priority), in lamports
+    pub fees_paid: u64,
+    /// Lamports debited from the thread (commission, core team fee, reimbursement)
+    pub thread_payout: u64,
+}
+
+impl ThreadCosts {
+    /// A single failed execution with no known fee
+    pub fn failure() -> Self {
+        Self {
+            failures: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Costs attributable to `thread` in a single confirmed transaction
+    pub fn from_transaction(tx: &SafeTransaction, thread: &Pubkey) -> Self {
+        let Some(meta) = &tx.meta else {
+            return Self::default();
+        };
+
+        if meta.err.is_some() {
+            return Self {
+                failures: 1,
+                fees_paid: meta.fee,
+                ..Self::default()
+            };
+        }
+
+        // Deposits show up as positive changes; only count lamports leaving the thread
+        let debited = tx
+            .balance_change(thread)
+            .map(|change| change.min(0).unsigned_abs())
+            .unwrap_or(0);
+
+        Self {
+            executions: 1,
+            failures: 0,
+            fees_paid: meta.fee,
+            thread_payout: debited,
+        }
+    }
+
+    /// Add another set of costs into this one
+    pub fn add(&mut self, other: &ThreadCosts) {
+        self.executions += other.executions;
+        self.failures += other.failures;
+        self.fees_paid += other.fees_paid;
+        self.thread_payout += other.thread_payout;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DayBucket {
+    /// Days since the unix epoch
+    day: i64,
+    costs: ThreadCosts,
+}
+
+/// One line of the ledger log
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerEntry {
+    thread: String,
+    day: i64,
+    costs: ThreadCosts,
+}
+
+struct LedgerState {
+    threads: HashMap<String, Vec<DayBucket>>,
+    /// Lines in the log file
+    lines: usize,
+    /// The log ends in a partial line, which the next append must not extend
+    partial_tail: bool,
+}
+
+/// Persistent per-thread cost ledger
+///
+/// `record` does blocking file I/O; async callers run it on
+/// `tokio::task::spawn_blocking`.
+pub struct CostLedger {
+    path: PathBuf,
+    retention_days: u64,
+    state: Mutex<LedgerState>,
+}
+
+impl CostLedger {
+    /// Open the ledger at `path`, loading existing history if present
+    pub fn open(path: impl Into<PathBuf>, retention_days: u64) -> Result<Self> {
+        let path = path.into();
+        let mut threads = HashMap::new();
+        let mut lines = 0;
+        let mut partial_tail = false;
+        if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read cost ledger: {}", path.display()))?;
+            partial_tail = !content.is_empty() && !content.ends_with('\n');
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                // A crash mid-append can leave a partial last line
+                let Ok(entry) = serde_json::from_str::<LedgerEntry>(line) else {
+                    log::warn!("Skipping malformed cost ledger line in {}", path.display());
+                    continue;
+                };
+                add_to_bucket(&mut threads, entry.thread, entry.day, &entry.costs);
+                lines += 1;
+            }
+        }
+
+        Ok(Self {
+            path,
+            retention_days,
+            state: Mutex::new(LedgerState {
+                threads,
+                lines,
+                partial_tail,
+            }),
+        })
+    }
+
+    /// Open the ledger described by the client config
+    pub fn from_config(config: &CostsConfig) -> Result<Self> {
+        let path = shellexpand::tilde(&config.storage_path).to_string();
+        Self::open(path, config.retention_days)
+    }
+
+    /// Add `costs` to the thread's bucket for the day containing `timestamp`
+    /// and append it to the log
+    pub fn record(&self, thread: &Pubkey, timestamp: i64, costs: ThreadCosts) -> Result<()> {
+        let day = timestamp.div_euclid(SECONDS_PER_DAY);
+        let mut state = self.state.lock();
+
+        add_to_bucket(&mut state.threads, thread.to_string(), day, &costs);
+        compact(&mut state.threads, day, self.retention_days);
+
+        let buckets: usize = state.threads.values().map(Vec::len).sum();
+        if state.lines + 1 > buckets + COMPACT_SLACK {
+            self.rewrite(&state.threads)?;
+            state.lines = buckets;
+        } else {
+            let entry = LedgerEntry {
+                thread: thread.to_string(),
+                day,
+                costs,
+            };
+            self.append(&entry, state.partial_tail)?;
+            state.lines += 1;
+        }
+        state.partial_tail = false;
+        Ok(())
+    }
+
+    /// Totals for `thread` since `since` (unix seconds), or `None` if the
+    /// ledger has no history for it
+    pub fn summary(&self, thread: &Pubkey, since: i64) -> Option<ThreadCosts> {
+        let since_day = since.div_euclid(SECONDS_PER_DAY);
+        let state = self.state.lock();
+        let buckets = state.threads.get(&thread.to_string())?;
+
+        let mut totals = ThreadCosts::default();
+        for bucket in buckets.iter().filter(|b| b.day >= since_day) {
+            totals.add(&bucket.costs);
+        }
+        Some(totals)
+    }
+
+    fn append(&self, entry: &LedgerEntry, partial_tail: bool) -> Result<()> {
+        self.create_parent()?;
+        let mut line = if partial_tail {
+            "\n".to_string()
+        } else {
+            String::new()
+        };
+        line.push_str(&serde_json::to_string(entry)?);
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Failed to write cost ledger: {}", self.path.display()))
+    }
+
+    /// Replace the log with one line per bucket
+    fn rewrite(&self, threads: &HashMap<String, Vec<DayBucket>>) -> Result<()> {
+        self.create_parent()?;
+        let mut content = String::new();
+        for (thread, buckets) in threads {
+            for bucket in buckets {
+                let entry = LedgerEntry {
+                    thread: thread.clone(),
+                    day: bucket.day,
+                    costs: bucket.costs,
+                };
+                content.push_str(&serde_json::to_string(&entry)?);
+                content.push('\n');
+            }
+        }
+
+        // Write-then-rename so a crash never leaves a truncated ledger
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content)
+            .with_context(|| format!("Failed to write cost ledger: {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write cost ledger: {}", self.path.display()))?;
+        Ok(())
+    }
+
+    fn create_parent(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn add_to_bucket(
+    threads: &mut HashMap<String, Vec<DayBucket>>,
+    thread: String,
+    day: i64,
+    costs: &ThreadCosts,
+) {
+    let buckets = threads.entry(thread).or_default();
+    match buckets.iter_mut().find(|b| b.day == day) {
+        Some(bucket) => bucket.costs.add(costs),
+        None => buckets.push(DayBucket { day, costs: *costs }),
+    }
+}
+
+/// Drop buckets older than the retention window and threads left empty
+fn compact(threads: &mut HashMap<String, Vec<DayBucket>>, today: i64, retention_days: u64) {
+    let cutoff = today - retention_days as i64;
+    threads.retain(|_, buckets| {
+        buckets.retain(|b| b.day > cutoff);
+        !buckets.is_empty()
+    });
+}
+
+/// Rebuild a thread's costs since `since` (unix seconds) from chain history
+///
+/// Walks the thread's signatures newest-first and only counts `exec_thread`
+/// transactions. Used when no local ledger history exists.
+pub async fn fetch_thread_costs(
+    rpc_client: &RpcPool,
+    thread: &Pubkey,
+    since: i64,
+) -> Result<ThreadCosts> {
+    let mut totals = ThreadCosts::default();
+    let mut before: Option<Signature> = None;
+
+    loop {
+        let page = rpc_client
+            .get_signatures_for_address(thread, before.as_ref(), SIGNATURE_PAGE_SIZE)
+            .await?;
+
+        for info in &page {
+            if info.block_time.is_some_and(|t| t < since) {
+                return Ok(totals);
+            }
+
+            let signature: Signature = info
+                .signature
+                .parse()
+                .map_err(|e| anyhow!("Invalid signature {}: {}", info.signature, e))?;
+            before = Some(signature);
+
+            let Some(tx) = rpc_client.get_transaction(&signature).await? else {
+                continue;
+            };
+            if is_exec_thread(&tx) {
+                totals.add(&ThreadCosts::from_transaction(&tx, thread));
+            }
+        }
+
+        if page.len() < SIGNATURE_PAGE_SIZE {
+            return Ok(totals);
+        }
+    }
+}
+
+fn is_exec_thread(tx: &SafeTransaction) -> bool {
+    tx.meta
+        .as_ref()
+        .and_then(|m| m.log_messages.as_ref())
+        .is_some_and(|logs| logs.iter().any(|l| l == EXEC_THREAD_LOG))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn costs(executions: u64, fees_paid: u64) -> ThreadCosts {
+        ThreadCosts {
+            executions,
+            failures: 0,
+            fees_paid,
+            thread_payout: 0,
+        }
+    }
+
+    #[test]
+    fn test_record_and_summary() {
+        let dir = TempDir::new().unwrap();
+        let ledger = CostLedger::open(dir.path().join("costs.jsonl"), 30).unwrap();
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+
+        ledger.record(&thread, now, costs(1, 5000)).unwrap();
+        ledger.record(&thread, now + 60, costs(1, 7000)).unwrap();
+        ledger.record(&thread, now, ThreadCosts::failure()).unwrap();
+
+        let summary = ledger.summary(&thread, now - SECONDS_PER_DAY).unwrap();
+        assert_eq!(summary.executions, 2);
+        assert_eq!(summary.failures, 1);
+        assert_eq!(summary.fees_paid, 12000);
+
+        assert!(ledger.summary(&Pubkey::new_unique(), 0).is_none());
+    }
+
+    #[test]
+    fn test_summary_since() {
+        let dir = TempDir::new().unwrap();
+        let ledger = CostLedger::open(dir.path().join("costs.jsonl"), 30).unwrap();
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+
+        ledger
+            .record(&thread, now - 10 * SECONDS_PER_DAY, costs(1, 5000))
+            .unwrap();
+        ledger.record(&thread, now, costs(1, 5000)).unwrap();
+
+        assert_eq!(
+            ledger
+                .summary(&thread, now - SECONDS_PER_DAY)
+                .unwrap()
+                .executions,
+            1
+        );
+        assert_eq!(ledger.summary(&thread, 0).unwrap().executions, 2);
+    }
+
+    #[test]
+    fn test_retention_compaction() {
+        let dir = TempDir::new().unwrap();
+        let ledger = CostLedger::open(dir.path().join("costs.jsonl"), 7).unwrap();
+        let old_thread = Pubkey::new_unique();
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+
+        ledger
+            .record(&old_thread, now - 30 * SECONDS_PER_DAY, costs(1, 5000))
+            .unwrap();
+        ledger.record(&thread, now, costs(1, 5000)).unwrap();
+
+        // Old thread's only bucket is past retention and removed entirely
+        assert!(ledger.summary(&old_thread, 0).is_none());
+        assert!(ledger.summary(&thread, 0).is_some());
+    }
+
+    #[test]
+    fn test_ledger_persists() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("costs.jsonl");
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+
+        CostLedger::open(&path, 30)
+            .unwrap()
+            .record(&thread, now, costs(3, 15000))
+            .unwrap();
+
+        let reopened = CostLedger::open(&path, 30).unwrap();
+        assert_eq!(reopened.summary(&thread, 0), Some(costs(3, 15000)));
+    }
+
+    #[test]
+    fn test_ledger_appends_and_compacts_log() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("costs.jsonl");
+        let ledger = CostLedger::open(&path, 30).unwrap();
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+        let line_count = || fs::read_to_string(&path).unwrap().lines().count();
+
+        ledger.record(&thread, now, costs(1, 5000)).unwrap();
+        ledger.record(&thread, now, costs(1, 5000)).unwrap();
+        assert_eq!(line_count(), 2);
+
+        // Past the slack the log is rewritten to one line per bucket
+        for _ in 0..COMPACT_SLACK {
+            ledger.record(&thread, now, costs(1, 5000)).unwrap();
+        }
+        assert!(line_count() < COMPACT_SLACK);
+
+        let reopened = CostLedger::open(&path, 30).unwrap();
+        let total = COMPACT_SLACK as u64 + 2;
+        assert_eq!(
+            reopened.summary(&thread, 0),
+            Some(costs(total, total * 5000))
+        );
+    }
+
+    #[test]
+    fn test_ledger_skips_partial_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("costs.jsonl");
+        let thread = Pubkey::new_unique();
+        let now = 100 * SECONDS_PER_DAY;
+
+        CostLedger::open(&path, 30)
+            .unwrap()
+            .record(&thread, now, costs(1, 5000))
+            .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"thread\":").unwrap();
+
+        let reopened = CostLedger::open(&path, 30).unwrap();
+        assert_eq!(reopened.summary(&thread, 0), Some(costs(1, 5000)));
+
+        // The next append starts a fresh line
+        reopened.record(&thread, now, costs(1, 5000)).unwrap();
+        let reopened = CostLedger::open(&path, 30).unwrap();
+        assert_eq!(reopened.summary(&thread, 0), Some(costs(2, 10000)));
+    }
+}
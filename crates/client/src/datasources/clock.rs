@@ -0,0 +1,371 @@
+//! Clock sysvar poller
+//!
+//! Time-based triggers only fire on clock updates. Datasources normally
+//! deliver them with every slot, but a subscription filtered too narrowly or a
+//! dead clock stream would silently stop every time trigger.
+//!
+//! The poller fetches the clock sysvar over RPC every `clock_poll_interval_ms`
+//! and hands it to staging as a `ClockTick`. While a datasource has delivered
+//! a clock within the last interval it backs off and fetches nothing. Staging
+//! drops clocks that don't advance the slot, so both sources can run at once.
+//!
+//! With `clock_use_websocket` the fallback watches the clock sysvar over an
+//! `accountSubscribe` WebSocket instead, forwarding clocks under the same
+//! back-off rule, and drops to polling if the watch can't be kept up.
+
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use ractor::ActorRef;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{clock::Clock, sysvar};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::actors::messages::StagingMessage;
+use crate::metrics;
+use crate::resources::SharedResources;
+
+/// Where a clock update came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    /// A datasource clock subscription
+    Datasource,
+    /// The RPC clock poller
+    Poller,
+    /// The clock sysvar watch
+    Watcher,
+}
+
+impl ClockSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClockSource::Datasource => "datasource",
+            ClockSource::Poller => "poller",
+            ClockSource::Watcher => "watcher",
+        }
+    }
+}
+
+#[derive(Default)]
+struct ClockUpdates {
+    last_datasource: Option<Instant>,
+    last: Option<(Instant, ClockSource)>,
+}
+
+/// When clock updates last arrived, and from where
+#[derive(Default)]
+pub struct ClockMonitor {
+    updates: Mutex<ClockUpdates>,
+}
+
+impl ClockMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note a clock update from `source`
+    pub fn record(&self, source: ClockSource) {
+        let now = Instant::now();
+        let mut updates = self.updates.lock();
+        if source == ClockSource::Datasource {
+            updates.last_datasource = Some(now);
+        }
+        updates.last = Some((now, source));
+        drop(updates);
+        metrics::record_clock_update(source.as_str());
+    }
+
+    /// Source of the most recent clock update
+    pub fn source(&self) -> Option<ClockSource> {
+        self.updates.lock().last.map(|(_, source)| source)
+    }
+
+    /// Time since the most recent clock update from any source
+    pub fn age(&self) -> Option<Duration> {
+        self.updates.lock().last.map(|(at, _)| at.elapsed())
+    }
+
+    /// Time since a datasource last delivered a clock update
+    pub fn datasource_age(&self) -> Option<Duration> {
+        self.updates.lock().last_datasource.map(|at| at.elapsed())
+    }
+}
+
+/// Poll the clock sysvar until `cancel` fires
+pub async fn run_clock_poller(
+    resources: SharedResources,
+    staging_ref: ActorRef<StagingMessage>,
+    interval: Duration,
+    cancel: CancellationToken,
+) {
+    let monitor = resources.clock_monitor.clone();
+    // Give the datasources one interval to deliver a clock first
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut polling = false;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = cancel.cancelled() => {
+                log::debug!("Clock poller cancelled");
+                return;
+            }
+        }
+
+        if let Some(age) = monitor.age() {
+            metrics::record_clock_age(age);
+        }
+
+        // The datasource is keeping up; back off
+        if monitor.datasource_age().is_some_and(|age| age < interval) {
+            if polling {
+                log::info!("Datasource clock updates resumed, clock poller backing off");
+                polling = false;
+            }
+            continue;
+        }
+
+        let clock = match fetch_clock(&resources).await {
+            Ok(clock) => clock,
+            Err(e) => {
+                log::warn!("Clock poller: {}", e);
+                continue;
+            }
+        };
+        if !polling {
+            log::warn!(
+                "No clock update from any datasource in the last {:?}, polling the clock sysvar",
+                interval
+            );
+            polling = true;
+        }
+
+        monitor.record(ClockSource::Poller);
+        if let Err(e) = staging_ref.send_message(StagingMessage::ClockTick(clock)) {
+            log::error!("Clock poller: failed to send clock to staging: {}", e);
+            return;
+        }
+    }
+}
+
+/// Watch the clock sysvar until `cancel` fires, polling instead once the
+/// watch ends
+pub async fn run_clock_watcher(
+    resources: SharedResources,
+    staging_ref: ActorRef<StagingMessage>,
+    interval: Duration,
+    cancel: CancellationToken,
+) {
+    let monitor = resources.clock_monitor.clone();
+    let mut updates = Box::pin(
+        resources
+            .rpc_client
+            .watch_account(sysvar::clock::ID, CommitmentConfig::confirmed()),
+    );
+    let mut forwarding = false;
+
+    loop {
+        let update = tokio::select! {
+            update = updates.next() => update,
+            _ = cancel.cancelled() => {
+                log::debug!("Clock watcher cancelled");
+                return;
+            }
+        };
+        let Some(update) = update else {
+            break;
+        };
+
+        // The datasource is keeping up; back off
+        if monitor.datasource_age().is_some_and(|age| age < interval) {
+            if forwarding {
+                log::info!("Datasource clock updates resumed, clock watcher backing off");
+                forwarding = false;
+            }
+            continue;
+        }
+
+        let clock: Clock = match bincode::deserialize(&update.data) {
+            Ok(clock) => clock,
+            Err(e) => {
+                log::warn!("Clock watcher: failed to deserialize clock sysvar: {}", e);
+                continue;
+            }
+        };
+        if !forwarding {
+            log::warn!(
+                "No clock update from any datasource in the last {:?}, forwarding the clock watch",
+                interval
+            );
+            forwarding = true;
+        }
+
+        monitor.record(ClockSource::Watcher);
+        if let Err(e) = staging_ref.send_message(StagingMessage::ClockTick(clock)) {
+            log::error!("Clock watcher: failed to send clock to staging: {}", e);
+            return;
+        }
+    }
+
+    log::warn!("Clock watch closed, polling the clock sysvar instead");
+    run_clock_poller(resources, staging_ref, interval, cancel).await;
+}
+
+/// Fetch the current clock sysvar over RPC
+pub(crate) async fn fetch_clock(resources: &SharedResources) -> Result<Clock> {
+    let account = resources
+        .rpc_client
+        .get_account(&sysvar::clock::ID)
+        .await?
+        .ok_or_else(|| anyhow!("Clock sysvar not found"))?;
+    let data = account
+        .decode_data()
+        .map_err(|e| anyhow!("Failed to decode clock sysvar: {}", e))?;
+    bincode::deserialize(&data).map_err(|e| anyhow!("Failed to deserialize clock sysvar: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::AccountCache;
+    use crate::rpc::mock::spawn_mock_rpc;
+    use crate::rpc::RpcPool;
+    use crate::types::AccountUpdate;
+    use base64::prelude::*;
+    use ractor::{Actor, ActorProcessingErr};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    /// Forwards clock ticks to a channel
+    struct ClockCollector;
+
+    impl Actor for ClockCollector {
+        type Msg = StagingMessage;
+        type State = mpsc::UnboundedSender<Clock>;
+        type Arguments = mpsc::UnboundedSender<Clock>;
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            tx: Self::Arguments,
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(tx)
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            tx: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            if let StagingMessage::ClockTick(clock) = message {
+                let _ = tx.send(clock);
+            }
+            Ok(())
+        }
+    }
+
+    async fn resources() -> SharedResources {
+        let data = AccountUpdate::new_clock(4_200, 1_700_000_000).data;
+        let body: &'static str = Box::leak(
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":4200}},"value":{{"data":["{}","base64"],"executable":false,"lamports":1169280,"owner":"Sysvar1111111111111111111111111111111111111","rentEpoch":0,"space":{}}}}}}}"#,
+                BASE64_STANDARD.encode(&data),
+                data.len()
+            )
+            .into_boxed_str(),
+        );
+        let url = spawn_mock_rpc(body).await;
+        SharedResources::with_custom(
+            Arc::new(RpcPool::with_url(url).unwrap()),
+            Arc::new(AccountCache::new()),
+        )
+    }
+
+    #[test]
+    fn test_new_clock_update_deserializes() {
+        let update = AccountUpdate::new_clock(4_200, 1_700_000_000);
+        assert_eq!(update.pubkey, sysvar::clock::ID);
+        assert_eq!(update.slot, 4_200);
+
+        let clock: Clock = bincode::deserialize(&update.data).unwrap();
+        assert_eq!(clock.slot, 4_200);
+        assert_eq!(clock.unix_timestamp, 1_700_000_000);
+        assert_eq!(clock.epoch_start_timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_monitor_tracks_source_and_age() {
+        let monitor = ClockMonitor::new();
+        assert!(monitor.source().is_none());
+        assert!(monitor.age().is_none());
+
+        monitor.record(ClockSource::Poller);
+        assert_eq!(monitor.source(), Some(ClockSource::Poller));
+        assert!(monitor.datasource_age().is_none());
+
+        monitor.record(ClockSource::Datasource);
+        assert_eq!(monitor.source(), Some(ClockSource::Datasource));
+        assert!(monitor.datasource_age().unwrap() < Duration::from_secs(1));
+        assert!(monitor.age().unwrap() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_poller_fills_in_without_datasource_clock() {
+        let resources = resources().await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (staging, _) = Actor::spawn(None, ClockCollector, tx).await.unwrap();
+        let cancel = CancellationToken::new();
+        tokio::spawn(run_clock_poller(
+            resources.clone(),
+            staging.clone(),
+            Duration::from_millis(50),
+            cancel.clone(),
+        ));
+
+        let clock = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(clock.slot, 4_200);
+        assert_eq!(clock.unix_timestamp, 1_700_000_000);
+        assert_eq!(resources.clock_monitor.source(), Some(ClockSource::Poller));
+
+        cancel.cancel();
+        staging.stop(None);
+    }
+
+    #[tokio::test]
+    async fn test_poller_backs_off_while_datasource_delivers() {
+        let resources = resources().await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (staging, _) = Actor::spawn(None, ClockCollector, tx).await.unwrap();
+        let cancel = CancellationToken::new();
+        let interval = Duration::from_millis(200);
+        tokio::spawn(run_clock_poller(
+            resources.clone(),
+            staging.clone(),
+            interval,
+            cancel.clone(),
+        ));
+
+        // A datasource delivering clocks every slot
+        for _ in 0..10 {
+            resources.clock_monitor.record(ClockSource::Datasource);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(rx.try_recv().is_err());
+
+        // The datasource goes quiet and the poller takes over
+        let clock = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(clock.slot, 4_200);
+
+        cancel.cancel();
+        staging.stop(None);
+    }
+}
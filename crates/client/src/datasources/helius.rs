@@ -0,0 +1,369 @@
+//! Helius websocket datasource.
+//!
+//! Streams thread accounts and the clock sysvar over a single Helius
+//! connection. It subscribes on the enhanced endpoint first and drops to the
+//! standard endpoint when the enhanced one refuses the connection or the
+//! subscription (e.g. on plans without enhanced websockets).
+//!
+//! Besides plain JSON-RPC text frames it accepts:
+//! - zstd-compressed binary frames
+//! - several notifications batched into one frame as a JSON array
+//! - error replies for exhausted credits or rate limits, which are logged
+//!   while the subscription stays up
+
+use anchor_lang::Discriminator;
+use antegen_thread_program::state::Thread;
+use antegen_ws::Message as WsMessage;
+use anyhow::Result;
+use log::{debug, error, info, trace, warn};
+use ractor::ActorRef;
+use serde::Deserialize;
+use solana_sdk::{clock::Clock, pubkey::Pubkey, sysvar};
+use std::time::Duration;
+
+use super::rpc::{parse_clock_notification, parse_program_notification};
+use crate::actors::messages::RpcSourceMessage;
+use crate::config::HeliusConfig;
+use crate::rpc::websocket::{build_account_subscribe_request, build_program_subscribe_request};
+use crate::types::AccountUpdate;
+
+const KEEPALIVE: Duration = Duration::from_secs(10);
+
+/// Helius subscription for thread accounts and the clock
+pub struct HeliusDatasource {
+    api_key: String,
+    enhanced: bool,
+    enhanced_ws_url: String,
+    ws_url: String,
+    program_id: Pubkey,
+    commitment: String,
+}
+
+/// What one message from Helius carried
+#[derive(Debug)]
+enum HeliusEvent {
+    Update(AccountUpdate),
+    Clock(Clock),
+    /// A request was refused
+    Rejected {
+        id: u64,
+        code: i64,
+        message: String,
+    },
+    /// The API key ran out of credits or hit its rate limit
+    Throttled {
+        code: i64,
+        message: String,
+    },
+}
+
+/// Why a connection stopped streaming
+#[derive(Debug, PartialEq, Eq)]
+enum StreamEnd {
+    /// The endpoint refused the connection or the program subscription
+    Rejected,
+    /// The connection or the actor went away
+    Closed,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorReply {
+    id: Option<u64>,
+    error: Option<ErrorBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    code: i64,
+    message: String,
+}
+
+impl HeliusDatasource {
+    /// Resolve the API key and endpoints from `config`
+    pub fn new(config: &HeliusConfig, program_id: Pubkey, commitment: &str) -> Result<Self> {
+        Ok(Self {
+            api_key: config.resolve_api_key()?,
+            enhanced: config.enhanced,
+            enhanced_ws_url: config.enhanced_ws_url.clone(),
+            ws_url: config.ws_url.clone(),
+            program_id,
+            commitment: commitment.to_string(),
+        })
+    }
+
+    /// Endpoint to report in logs, without the API key
+    pub fn label(&self) -> &str {
+        if self.enhanced {
+            &self.enhanced_ws_url
+        } else {
+            &self.ws_url
+        }
+    }
+
+    /// Stream until the connection can't be kept up. Reconnects are handled
+    /// by antegen-ws; every connect re-subscribes and sends
+    /// `RpcSourceMessage::Reconnected` so the actor backfills.
+    pub async fn run(&self, actor_ref: ActorRef<RpcSourceMessage>) {
+        let mut enhanced = self.enhanced;
+        loop {
+            let endpoint = if enhanced {
+                &self.enhanced_ws_url
+            } else {
+                &self.ws_url
+            };
+            let end = self.stream(endpoint, &actor_ref).await;
+            if end == StreamEnd::Rejected && enhanced {
+                warn!(
+                    "[{}] Enhanced websocket unavailable, falling back to {}",
+                    self.enhanced_ws_url, self.ws_url
+                );
+                enhanced = false;
+                continue;
+            }
+            return;
+        }
+    }
+
+    async fn stream(&self, endpoint: &str, actor_ref: &ActorRef<RpcSourceMessage>) -> StreamEnd {
+        let url = format!(
+            "{}/?api-key={}",
+            endpoint.trim_end_matches('/'),
+            self.api_key
+        );
+
+        let filters = vec![serde_json::json!({
+            "memcmp": {
+                "offset": 0,
+                "bytes": bs58::encode(Thread::DISCRIMINATOR).into_string(),
+            }
+        })];
+        let (program_request, program_msg) =
+            build_program_subscribe_request(&self.program_id, &self.commitment, Some(filters));
+        let (_, clock_msg) = build_account_subscribe_request(&sysvar::clock::ID, &self.commitment);
+
+        let builder = match antegen_ws::WsClient::builder(&url) {
+            Ok(b) => b,
+            Err(e) => {
+                error!("[{}] Invalid WebSocket URL: {e}", endpoint);
+                return StreamEnd::Closed;
+            }
+        };
+
+        let actor_on_connect = actor_ref.clone();
+        let label = endpoint.to_string();
+        let mut handle = match builder
+            .keepalive(KEEPALIVE)
+            .on_connect(move |tx| {
+                let messages = [program_msg.clone(), clock_msg.clone()];
+                let actor = actor_on_connect.clone();
+                let label = label.clone();
+                async move {
+                    debug!("[{}] Helius connected, subscribing...", label);
+                    for msg in messages {
+                        if let Err(e) = tx.send_text(msg).await {
+                            error!("[{}] Failed to send Helius subscription: {e}", label);
+                            return Ok(());
+                        }
+                    }
+                    let _ = actor.send_message(RpcSourceMessage::Reconnected);
+                    Ok(())
+                }
+            })
+            .build()
+            .await
+        {
+            Ok(h) => h,
+            Err(e) => {
+                error!("[{}] Failed to connect to Helius: {e}", endpoint);
+                return StreamEnd::Rejected;
+            }
+        };
+        info!("[{}] Streaming thread accounts from Helius", endpoint);
+
+        while let Some(msg) = handle.recv().await {
+            let text = match msg {
+                WsMessage::Text(text) => text,
+                WsMessage::Binary(bytes) => match decompress_frame(&bytes) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        debug!("[{}] Dropping undecodable binary frame: {}", endpoint, e);
+                        continue;
+                    }
+                },
+                WsMessage::Close(_) => continue,
+            };
+
+            for event in parse_frame(&text) {
+                let sent = match event {
+                    HeliusEvent::Update(update) => {
+                        actor_ref.send_message(RpcSourceMessage::UpdateReceived(update))
+                    }
+                    HeliusEvent::Clock(clock) => {
+                        actor_ref.send_message(RpcSourceMessage::ClockReceived(clock))
+                    }
+                    HeliusEvent::Throttled { code, message } => {
+                        warn!(
+                            "[{}] Helius is throttling this API key ({}): {}",
+                            endpoint, code, message
+                        );
+                        Ok(())
+                    }
+                    HeliusEvent::Rejected { id, code, message } if id == program_request => {
+                        warn!(
+                            "[{}] Program subscription rejected ({}): {}",
+                            endpoint, code, message
+                        );
+                        handle.abort();
+                        return StreamEnd::Rejected;
+                    }
+                    HeliusEvent::Rejected { id, code, message } => {
+                        warn!(
+                            "[{}] Request {} rejected ({}): {}",
+                            endpoint, id, code, message
+                        );
+                        Ok(())
+                    }
+                };
+                if let Err(e) = sent {
+                    error!("[{}] Failed to forward Helius update: {:?}", endpoint, e);
+                    handle.abort();
+                    return StreamEnd::Closed;
+                }
+            }
+        }
+        StreamEnd::Closed
+    }
+}
+
+/// Decompress a zstd binary frame into its JSON text
+fn decompress_frame(bytes: &[u8]) -> Result<String> {
+    let decompressed = zstd::decode_all(bytes)?;
+    Ok(String::from_utf8(decompressed)?)
+}
+
+/// Events in one text frame, which may hold a single message or a batch
+fn parse_frame(text: &str) -> Vec<HeliusEvent> {
+    if !text.trim_start().starts_with('[') {
+        return parse_message(text).into_iter().collect();
+    }
+    match serde_json::from_str::<Vec<serde_json::Value>>(text) {
+        Ok(batch) => batch
+            .iter()
+            .filter_map(|message| parse_message(&message.to_string()))
+            .collect(),
+        Err(e) => {
+            trace!("Dropping malformed Helius batch: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn parse_message(text: &str) -> Option<HeliusEvent> {
+    if let Some(update) = parse_program_notification(text) {
+        return Some(HeliusEvent::Update(update));
+    }
+    if let Some(clock) = parse_clock_notification(text) {
+        return Some(HeliusEvent::Clock(clock));
+    }
+
+    let reply: ErrorReply = serde_json::from_str(text).ok()?;
+    let ErrorBody { code, message } = reply.error?;
+    if is_throttled(code, &message) {
+        return Some(HeliusEvent::Throttled { code, message });
+    }
+    Some(HeliusEvent::Rejected {
+        id: reply.id?,
+        code,
+        message,
+    })
+}
+
+/// Whether an error reply means the key is out of credits or rate limited
+/// rather than the request being unsupported
+fn is_throttled(code: i64, message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    code == 429 || code == -32429 || message.contains("credit") || message.contains("rate limit")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+
+    /// Thread discriminator plus a few bytes, as Helius sends it
+    fn compressed_account_data() -> (Vec<u8>, String) {
+        let mut data = Thread::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1, 2, 3, 4]);
+        let compressed = zstd::encode_all(data.as_slice(), 0).unwrap();
+        (data, BASE64_STANDARD.encode(compressed))
+    }
+
+    fn program_frame(slot: u64, pubkey: &Pubkey, encoded: &str) -> String {
+        format!(
+            r#"{{"jsonrpc":"2.0","method":"programNotification","params":{{"result":{{"context":{{"slot":{slot}}},"value":{{"pubkey":"{pubkey}","account":{{"data":["{encoded}","base64+zstd"],"lamports":1000,"owner":"{owner}","executable":false,"rentEpoch":0,"space":12}}}}}},"subscription":24011}}}}"#,
+            owner = antegen_thread_program::ID,
+        )
+    }
+
+    #[test]
+    fn test_program_notification_decompresses_account_data() {
+        let (data, encoded) = compressed_account_data();
+        let pubkey = Pubkey::new_unique();
+        let events = parse_frame(&program_frame(330_000_123, &pubkey, &encoded));
+        match events.as_slice() {
+            [HeliusEvent::Update(update)] => {
+                assert_eq!(update.pubkey, pubkey);
+                assert_eq!(update.slot, 330_000_123);
+                assert_eq!(update.data, data);
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_batched_and_compressed_frames() {
+        let (_, encoded) = compressed_account_data();
+        let clock = r#"{"jsonrpc":"2.0","method":"accountNotification","params":{"result":{"context":{"slot":136071883},"value":{"lamports":1169280,"data":["y0ocCAAAAABJVDlpAAAAAEcBAAAAAAAASAEAAAAAAADGGztpAAAAAA==","base64"],"owner":"Sysvar1111111111111111111111111111111111111","executable":false,"rentEpoch":18446744073709551615,"space":40}},"subscription":24012}}"#;
+        let batch = format!(
+            "[{},{},{}]",
+            program_frame(10, &Pubkey::new_unique(), &encoded),
+            clock,
+            program_frame(11, &Pubkey::new_unique(), &encoded),
+        );
+
+        let events = parse_frame(&batch);
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], HeliusEvent::Update(ref u) if u.slot == 10));
+        assert!(matches!(events[1], HeliusEvent::Clock(_)));
+        assert!(matches!(events[2], HeliusEvent::Update(ref u) if u.slot == 11));
+
+        // The same batch delivered as a zstd binary frame
+        let compressed = zstd::encode_all(batch.as_bytes(), 0).unwrap();
+        let text = decompress_frame(&compressed).unwrap();
+        assert_eq!(parse_frame(&text).len(), 3);
+    }
+
+    #[test]
+    fn test_error_replies() {
+        let rejected =
+            r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":7}"#;
+        assert!(matches!(
+            parse_frame(rejected).as_slice(),
+            [HeliusEvent::Rejected {
+                id: 7,
+                code: -32601,
+                ..
+            }]
+        ));
+
+        let throttled = r#"{"jsonrpc":"2.0","error":{"code":-32429,"message":"max usage reached, upgrade your plan for more credits"},"id":8}"#;
+        assert!(matches!(
+            parse_frame(throttled).as_slice(),
+            [HeliusEvent::Throttled { code: -32429, .. }]
+        ));
+
+        // Subscription confirmations carry nothing to forward
+        let confirmed = r#"{"jsonrpc":"2.0","result":24011,"id":7}"#;
+        assert!(parse_frame(confirmed).is_empty());
+    }
+}
@@ -1,4 +1,14 @@
 // Datasource modules
+pub mod clock;
+pub mod helius;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod priority;
+pub mod reconcile;
 pub mod rpc;
+#[cfg(unix)]
+pub mod socket;
 
+pub use helius::HeliusDatasource;
+pub use priority::{priority_channel, PriorityLevel, PriorityReceiver, PrioritySender};
 pub use rpc::RpcSubscription;
@@ -0,0 +1,221 @@
+//! Two-tier channel between the Geyser plugin and `GeyserSourceActor`
+//!
+//! A clock update and a burst of thread account updates can arrive in the
+//! same slot. Time-based triggers are evaluated against the latest clock, so
+//! the clock must not wait behind the account updates queued before it.
+//!
+//! Events are sent with a [`PriorityLevel`] into one of two bounded `mpsc`
+//! channels. [`PriorityReceiver::recv`] always drains the critical channel
+//! before taking the next normal event. Ordering is only kept within a tier.
+
+use crate::types::{AccountUpdate, PluginEvent};
+use solana_sdk::sysvar;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{SendError, TrySendError};
+
+/// Which channel an event is sent on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityLevel {
+    /// Received before any queued normal event (clock updates)
+    Critical,
+    /// Everything else, in arrival order
+    #[default]
+    Normal,
+}
+
+impl PriorityLevel {
+    /// Clock sysvar updates are critical, all other accounts normal
+    pub fn for_update(update: &AccountUpdate) -> Self {
+        if update.pubkey == sysvar::clock::ID {
+            PriorityLevel::Critical
+        } else {
+            PriorityLevel::Normal
+        }
+    }
+
+    /// Priority of an event relayed from the plugin
+    ///
+    /// Startup snapshot updates stay normal so none of them is received
+    /// after the `EndOfStartup` marker that follows them.
+    pub fn for_event(event: &PluginEvent) -> Self {
+        match event {
+            PluginEvent::AccountUpdate {
+                update,
+                is_startup: false,
+            } => Self::for_update(update),
+            _ => PriorityLevel::Normal,
+        }
+    }
+}
+
+/// Create a priority channel; each tier buffers up to `capacity` events
+pub fn priority_channel(capacity: usize) -> (PrioritySender, PriorityReceiver) {
+    let (critical, critical_rx) = mpsc::channel(capacity);
+    let (normal, normal_rx) = mpsc::channel(capacity);
+    (
+        PrioritySender { critical, normal },
+        PriorityReceiver {
+            critical: critical_rx,
+            normal: normal_rx,
+        },
+    )
+}
+
+/// Sending half of [`priority_channel`]
+#[derive(Clone)]
+pub struct PrioritySender {
+    critical: mpsc::Sender<PluginEvent>,
+    normal: mpsc::Sender<PluginEvent>,
+}
+
+impl PrioritySender {
+    fn tier(&self, priority: PriorityLevel) -> &mpsc::Sender<PluginEvent> {
+        match priority {
+            PriorityLevel::Critical => &self.critical,
+            PriorityLevel::Normal => &self.normal,
+        }
+    }
+
+    /// Send without waiting; fails if that tier is full or the receiver closed
+    pub fn try_send(
+        &self,
+        event: PluginEvent,
+        priority: PriorityLevel,
+    ) -> Result<(), TrySendError<PluginEvent>> {
+        self.tier(priority).try_send(event)
+    }
+
+    /// Send, waiting for room in that tier
+    pub async fn send(
+        &self,
+        event: PluginEvent,
+        priority: PriorityLevel,
+    ) -> Result<(), SendError<PluginEvent>> {
+        self.tier(priority).send(event).await
+    }
+
+    /// Send, blocking the current thread for room in that tier
+    ///
+    /// Must not be called from within an async context.
+    pub fn blocking_send(
+        &self,
+        event: PluginEvent,
+        priority: PriorityLevel,
+    ) -> Result<(), SendError<PluginEvent>> {
+        self.tier(priority).blocking_send(event)
+    }
+}
+
+/// Receiving half of [`priority_channel`]
+pub struct PriorityReceiver {
+    critical: mpsc::Receiver<PluginEvent>,
+    normal: mpsc::Receiver<PluginEvent>,
+}
+
+impl PriorityReceiver {
+    /// Receive the next event, critical first
+    ///
+    /// Returns `None` once all senders are dropped and both tiers are drained.
+    pub async fn recv(&mut self) -> Option<PluginEvent> {
+        tokio::select! {
+            biased;
+            Some(event) = self.critical.recv() => Some(event),
+            Some(event) = self.normal.recv() => Some(event),
+            else => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn event(pubkey: Pubkey, slot: u64) -> PluginEvent {
+        PluginEvent::AccountUpdate {
+            update: AccountUpdate::new(pubkey, vec![], slot),
+            is_startup: false,
+        }
+    }
+
+    fn slot_of(event: PluginEvent) -> u64 {
+        match event {
+            PluginEvent::AccountUpdate { update, .. } => update.slot,
+            PluginEvent::EndOfStartup => panic!("Unexpected EndOfStartup"),
+        }
+    }
+
+    #[test]
+    fn test_clock_updates_are_critical() {
+        let clock = AccountUpdate::new_clock(1, 0);
+        let thread = AccountUpdate::new(Pubkey::new_unique(), vec![], 1);
+        assert_eq!(PriorityLevel::for_update(&clock), PriorityLevel::Critical);
+        assert_eq!(PriorityLevel::for_update(&thread), PriorityLevel::Normal);
+
+        let startup_clock = PluginEvent::AccountUpdate {
+            update: clock,
+            is_startup: true,
+        };
+        assert_eq!(
+            PriorityLevel::for_event(&startup_clock),
+            PriorityLevel::Normal
+        );
+        assert_eq!(
+            PriorityLevel::for_event(&event(sysvar::clock::ID, 2)),
+            PriorityLevel::Critical
+        );
+    }
+
+    #[tokio::test]
+    async fn test_critical_received_first() {
+        let (tx, mut rx) = priority_channel(16);
+        let thread = Pubkey::new_unique();
+
+        tx.try_send(event(thread, 1), PriorityLevel::Normal)
+            .unwrap();
+        tx.try_send(event(thread, 2), PriorityLevel::Normal)
+            .unwrap();
+        tx.try_send(event(sysvar::clock::ID, 3), PriorityLevel::Critical)
+            .unwrap();
+        tx.try_send(event(sysvar::clock::ID, 4), PriorityLevel::Critical)
+            .unwrap();
+
+        let mut slots = Vec::new();
+        for _ in 0..4 {
+            slots.push(slot_of(rx.recv().await.unwrap()));
+        }
+        // Critical in order, then normal in order
+        assert_eq!(slots, vec![3, 4, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_tiers_fill_independently() {
+        let (tx, mut rx) = priority_channel(1);
+        let thread = Pubkey::new_unique();
+
+        tx.try_send(event(thread, 1), PriorityLevel::Normal)
+            .unwrap();
+        assert!(matches!(
+            tx.try_send(event(thread, 2), PriorityLevel::Normal),
+            Err(TrySendError::Full(_))
+        ));
+        // A full normal tier doesn't hold back the clock
+        tx.try_send(event(sysvar::clock::ID, 3), PriorityLevel::Critical)
+            .unwrap();
+
+        assert_eq!(slot_of(rx.recv().await.unwrap()), 3);
+        assert_eq!(slot_of(rx.recv().await.unwrap()), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recv_drains_before_closing() {
+        let (tx, mut rx) = priority_channel(16);
+        tx.send(event(Pubkey::new_unique(), 1), PriorityLevel::Normal)
+            .await
+            .unwrap();
+        drop(tx);
+
+        assert_eq!(slot_of(rx.recv().await.unwrap()), 1);
+        assert!(rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Startup reconciliation sweep
+//!
+//! Loads every existing Thread account once at startup so threads that were
+//! already due before the client started execute immediately, instead of
+//! waiting for their next account update.
+//!
+//! The sweep lists Thread pubkeys with a data-less `getProgramAccounts`
+//! (discriminator memcmp filter) and fetches their data in batches with
+//! `getMultipleAccounts`.
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use antegen_thread_program::state::Thread;
+use anyhow::{anyhow, Result};
+use log::{debug, info, warn};
+use ractor::ActorRef;
+use solana_sdk::clock::Clock;
+
+use crate::actors::messages::StagingMessage;
+use crate::datasources::clock::fetch_clock;
+use crate::resources::SharedResources;
+use crate::rpc::response::decode_account_data;
+use crate::types::AccountUpdate;
+
+/// Maximum accounts per `getMultipleAccounts` request (RPC limit)
+const MULTIPLE_ACCOUNTS_BATCH_SIZE: usize = 100;
+
+/// Result of a reconciliation sweep
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileSummary {
+    /// Thread accounts loaded into the cache
+    pub loaded: usize,
+    /// Loaded threads that were already due at the current clock
+    pub due: usize,
+}
+
+/// Load all Thread accounts into the cache and hand them to staging
+///
+/// A clock tick is sent to staging afterwards so overdue threads are picked
+/// up right away.
+pub async fn reconcile_threads(
+    resources: &SharedResources,
+    staging_ref: &ActorRef<StagingMessage>,
+) -> Result<ReconcileSummary> {
+    let clock = fetch_clock(resources).await?;
+
+    let filters = vec![serde_json::json!({
+        "memcmp": {
+            "offset": 0,
+            "bytes": bs58::encode(Thread::DISCRIMINATOR).into_string()
+        }
+    })];
+    let pubkeys = resources
+        .rpc_client
+        .get_program_account_keys(&resources.program_id, Some(filters))
+        .await?;
+    debug!("Reconciliation: found {} Thread accounts", pubkeys.len());
+
+    let mut summary = ReconcileSummary::default();
+
+    for chunk in pubkeys.chunks(MULTIPLE_ACCOUNTS_BATCH_SIZE) {
+        let accounts = resources.rpc_client.get_multiple_accounts(chunk).await?;
+
+        for (pubkey, account) in chunk.iter().zip(accounts) {
+            // Closed between the two calls
+            let Some(account) = account else {
+                continue;
+            };
+
+            let data = match decode_account_data(&account.data.0, &account.data.1) {
+                Ok(d) => d,
+                Err(e) => {
+                    warn!("Reconciliation: failed to decode account {}: {}", pubkey, e);
+                    continue;
+                }
+            };
+
+            let Ok(thread) = Thread::try_deserialize(&mut data.as_slice()) else {
+                warn!("Reconciliation: failed to deserialize thread {}", pubkey);
+                continue;
+            };
+
+            summary.loaded += 1;
+            if is_due(&thread, &clock) {
+                summary.due += 1;
+            }
+
+            // Slot 0 like the datasource backfill; live updates supersede it
+            if resources.cache.put_if_newer(*pubkey, data.clone(), 0).await {
+                staging_ref
+                    .send_message(StagingMessage::AccountUpdate(AccountUpdate::new(
+                        *pubkey, data, 0,
+                    )))
+                    .map_err(|e| anyhow!("Failed to send to staging: {}", e))?;
+            }
+        }
+    }
+
+    // Evaluate the freshly seeded queues now rather than on the next clock update
+    staging_ref
+        .send_message(StagingMessage::ClockTick(clock))
+        .map_err(|e| anyhow!("Failed to send clock to staging: {}", e))?;
+
+    info!(
+        "Reconciliation sweep: loaded {} threads, {} immediately due",
+        summary.loaded, summary.due
+    );
+    Ok(summary)
+}
+
+/// Whether an unpaused thread (or one whose resume_at has passed) is ready
+/// to execute at `clock`
+fn is_due(thread: &Thread, clock: &Clock) -> bool {
+    !thread.is_paused_at(clock.unix_timestamp) && thread.is_ready(clock.slot, clock.unix_timestamp)
+}
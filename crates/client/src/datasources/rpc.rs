@@ -305,7 +305,7 @@ struct AccountNotificationValue {
 }
 
 /// Parse a program notification message
-fn parse_program_notification(text: &str) -> Option<AccountUpdate> {
+pub(super) fn parse_program_notification(text: &str) -> Option<AccountUpdate> {
     let notification: ProgramNotification = serde_json::from_str(text).ok()?;
 
     if notification.method.as_deref() != Some("programNotification") {
@@ -325,7 +325,7 @@ fn parse_program_notification(text: &str) -> Option<AccountUpdate> {
 }
 
 /// Parse a clock account notification message
-fn parse_clock_notification(text: &str) -> Option<Clock> {
+pub(super) fn parse_clock_notification(text: &str) -> Option<Clock> {
     let notification: AccountNotification = serde_json::from_str(text).ok()?;
 
     if notification.method.as_deref() != Some("accountNotification") {
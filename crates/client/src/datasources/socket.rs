@@ -0,0 +1,286 @@
+//! Unix socket transport between the Geyser plugin and a standalone client
+//!
+//! Lets the client run as its own process while still receiving the
+//! validator's Geyser stream. The plugin forwards [`PluginEvent`]s as
+//! length-prefixed bincode frames (u32 little-endian length, then payload)
+//! over a local Unix domain socket, and the client feeds them into the same
+//! priority channel `GeyserSourceActor` consumes in plugin mode.
+//!
+//! Both sides read the socket path from `datasources.geyser_socket`.
+//!
+//! Back-pressure: the plugin never blocks the validator. If the client is
+//! slow or disconnected, live updates are dropped (they are
+//! superseded by later writes and recovered by the client's RPC backfill).
+//! Startup snapshot accounts and the end-of-startup marker are critical and
+//! are always queued.
+
+use crate::datasources::priority::{PriorityLevel, PrioritySender};
+use crate::types::PluginEvent;
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+/// Largest accepted frame (accounts are at most 10 MiB)
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Events buffered by the forwarder before live updates are dropped
+const FORWARD_QUEUE_SIZE: usize = 10_000;
+
+/// Delay between reconnect attempts when the client is not listening
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Encode an event as a length-prefixed frame
+pub fn encode_frame(event: &PluginEvent) -> Result<Vec<u8>> {
+    let payload = bincode::serialize(event)?;
+    if payload.len() > MAX_FRAME_LEN {
+        return Err(anyhow!("Frame too large: {} bytes", payload.len()));
+    }
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Read one frame; returns `Ok(None)` on a clean end of stream
+pub async fn read_frame(stream: &mut UnixStream) -> Result<Option<PluginEvent>> {
+    let mut len_bytes = [0u8; 4];
+    match stream.read_exact(&mut len_bytes).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(anyhow!("Frame too large: {} bytes", len));
+    }
+
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await?;
+    let event = bincode::deserialize(&payload).context("Failed to decode frame")?;
+    Ok(Some(event))
+}
+
+/// Plugin side: forwards events to a client listening on the socket
+///
+/// Writing happens on a background task, so `forward` never blocks the
+/// validator's thread.
+pub struct SocketForwarder {
+    /// Startup snapshot and end-of-startup events, never dropped
+    critical: mpsc::UnboundedSender<PluginEvent>,
+    /// Live account updates, dropped when full
+    live: mpsc::Sender<PluginEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SocketForwarder {
+    /// Spawn the writer task on the current tokio runtime
+    pub fn spawn(path: impl Into<PathBuf>) -> Self {
+        let (critical, critical_rx) = mpsc::unbounded_channel();
+        let (live, live_rx) = mpsc::channel(FORWARD_QUEUE_SIZE);
+        tokio::spawn(write_loop(path.into(), critical_rx, live_rx));
+        Self {
+            critical,
+            live,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Forward an event without blocking
+    ///
+    /// Live account updates are dropped when the client falls behind; startup
+    /// updates and the end-of-startup marker are always queued (the snapshot
+    /// only holds thread program accounts, so this stays bounded).
+    pub fn forward(&self, event: PluginEvent) -> Result<()> {
+        let is_live = matches!(
+            event,
+            PluginEvent::AccountUpdate {
+                is_startup: false,
+                ..
+            }
+        );
+
+        if !is_live {
+            return self
+                .critical
+                .send(event)
+                .map_err(|e| anyhow!("Socket forwarder closed: {}", e));
+        }
+
+        match self.live.try_send(event) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                if dropped.is_power_of_two() {
+                    log::warn!("Socket client is slow, {} live updates dropped", dropped);
+                }
+                Ok(())
+            }
+            Err(e) => Err(anyhow!("Socket forwarder closed: {}", e)),
+        }
+    }
+
+    /// Live updates dropped because the client could not keep up
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Connect (and reconnect) to the client and write queued events,
+/// critical events first
+async fn write_loop(
+    path: PathBuf,
+    mut critical: mpsc::UnboundedReceiver<PluginEvent>,
+    mut live: mpsc::Receiver<PluginEvent>,
+) {
+    loop {
+        let mut stream = match UnixStream::connect(&path).await {
+            Ok(stream) => {
+                log::info!("Connected to client socket {}", path.display());
+                stream
+            }
+            Err(e) => {
+                if critical.is_closed() && live.is_closed() {
+                    return;
+                }
+                log::debug!("Client socket {} unavailable: {}", path.display(), e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        loop {
+            let event = tokio::select! {
+                biased;
+                Some(event) = critical.recv() => event,
+                Some(event) = live.recv() => event,
+                else => return, // Forwarder dropped and queues drained
+            };
+
+            let frame = match encode_frame(&event) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    log::warn!("Dropping event that cannot be framed: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = stream.write_all(&frame).await {
+                log::warn!("Client socket write failed, reconnecting: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Client side: accept plugin connections and feed their events into `sender`
+///
+/// Live clock updates are sent as critical. Removes a stale socket file left
+/// over from a previous run before binding.
+pub async fn listen(path: &Path, sender: PrioritySender) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind socket {}", path.display()))?;
+    log::info!("Listening for Geyser plugin on {}", path.display());
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        log::info!("Geyser plugin connected on {}", path.display());
+
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            loop {
+                match read_frame(&mut stream).await {
+                    Ok(Some(event)) => {
+                        let priority = PriorityLevel::for_event(&event);
+                        if sender.send(event, priority).await.is_err() {
+                            return; // Client shutting down
+                        }
+                    }
+                    Ok(None) => {
+                        log::info!("Geyser plugin disconnected");
+                        return;
+                    }
+                    Err(e) => {
+                        log::warn!("Geyser socket read failed: {}", e);
+                        return;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AccountUpdate;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[tokio::test]
+    async fn test_events_cross_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("geyser.sock");
+
+        let (tx, mut rx) = crate::datasources::priority_channel(16);
+        let listen_path = path.clone();
+        tokio::spawn(async move { listen(&listen_path, tx).await });
+
+        let forwarder = SocketForwarder::spawn(&path);
+        let pubkey = Pubkey::new_unique();
+        forwarder
+            .forward(PluginEvent::AccountUpdate {
+                update: AccountUpdate::new(pubkey, vec![7; 64], 42),
+                is_startup: false,
+            })
+            .unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            PluginEvent::AccountUpdate { update, is_startup } => {
+                assert_eq!(update.pubkey, pubkey);
+                assert_eq!(update.data, vec![7; 64]);
+                assert_eq!(update.slot, 42);
+                assert!(!is_startup);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert_eq!(forwarder.dropped(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_rejected() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        client
+            .write_all(&((MAX_FRAME_LEN as u32) + 1).to_le_bytes())
+            .await
+            .unwrap();
+        assert!(read_frame(&mut server).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_frame_round_trip() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        client
+            .write_all(&encode_frame(&PluginEvent::EndOfStartup).unwrap())
+            .await
+            .unwrap();
+        drop(client);
+
+        assert!(matches!(
+            read_frame(&mut server).await.unwrap(),
+            Some(PluginEvent::EndOfStartup)
+        ));
+        assert!(read_frame(&mut server).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,496 @@
+//! Account data decoders
+//!
+//! `antegen thread get --decode-target` and `antegen thread debug` show the
+//! account an account trigger watches. [`DecoderRegistry`] picks a decoder by
+//! the account's owner program and the leading bytes of its data (the
+//! discriminator), so the thread program's own accounts and SPL token
+//! accounts read as named fields. Accounts no decoder claims, or that a
+//! decoder fails to read, are shown as hex and base64.
+//!
+//! Integrators register decoders for their own programs:
+//!
+//! ```ignore
+//! let mut registry = DecoderRegistry::with_builtin();
+//! registry.register(my_program::ID, Oracle::DISCRIMINATOR, |data: &[u8]| {
+//!     let oracle = Oracle::try_deserialize(&mut &data[..])?;
+//!     Ok(DecodedAccount::new("Oracle").field("price", oracle.price))
+//! });
+//! let view = registry.view(&owner, &data);
+//! ```
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use antegen_thread_program::fiber::{decompile_instruction, CompiledInstructionV0, Fiber};
+use antegen_thread_program::fiber::{FiberState, FiberVersionedState};
+use antegen_thread_program::state::{Thread, ThreadConfig};
+use anyhow::{anyhow, Result};
+use base64::prelude::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+use crate::trigger_str;
+
+/// SPL Token program
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token-2022 program
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PSnBZHkz1Pqkdt");
+
+/// Size of an SPL token account without extensions
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Size of an SPL mint without extensions
+const MINT_LEN: usize = 82;
+
+/// Reads the data of accounts one program owns
+///
+/// Implemented for closures taking the raw account data, so simple decoders
+/// don't need a type of their own.
+pub trait AccountDecoder: Send + Sync {
+    /// Decode `data`, the whole account including its discriminator
+    fn decode(&self, data: &[u8]) -> Result<DecodedAccount>;
+}
+
+impl<F> AccountDecoder for F
+where
+    F: Fn(&[u8]) -> Result<DecodedAccount> + Send + Sync,
+{
+    fn decode(&self, data: &[u8]) -> Result<DecodedAccount> {
+        self(data)
+    }
+}
+
+/// An account read by a decoder, fields in layout order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedAccount {
+    /// Account type, e.g. `Thread` or `TokenAccount`
+    pub kind: String,
+    pub fields: Vec<DecodedField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedField {
+    pub name: String,
+    pub value: String,
+}
+
+impl DecodedAccount {
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Append a field
+    pub fn field(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.fields.push(DecodedField {
+            name: name.into(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Value of the field called `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| field.value.as_str())
+    }
+}
+
+/// Account data as it is displayed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "encoding", rename_all = "snake_case")]
+pub enum AccountView {
+    Decoded(DecodedAccount),
+    /// No decoder claimed the account, or the one that did failed
+    Raw {
+        hex: String,
+        base64: String,
+    },
+}
+
+impl AccountView {
+    pub fn raw(data: &[u8]) -> Self {
+        Self::Raw {
+            hex: to_hex(data),
+            base64: BASE64_STANDARD.encode(data),
+        }
+    }
+}
+
+struct Registration {
+    owner: Pubkey,
+    discriminator: Vec<u8>,
+    decoder: Arc<dyn AccountDecoder>,
+}
+
+/// Decoders by owner program and discriminator
+#[derive(Clone, Default)]
+pub struct DecoderRegistry {
+    registrations: Vec<Arc<Registration>>,
+}
+
+impl DecoderRegistry {
+    /// Registry without any decoders
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the thread program, fiber program and SPL token decoders
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register(
+                antegen_thread_program::ID,
+                Thread::DISCRIMINATOR,
+                decode_thread,
+            )
+            .register(
+                antegen_thread_program::ID,
+                ThreadConfig::DISCRIMINATOR,
+                decode_thread_config,
+            )
+            .register(
+                antegen_thread_program::fiber::ID,
+                FiberState::DISCRIMINATOR,
+                decode_fiber,
+            )
+            .register(
+                antegen_thread_program::fiber::ID,
+                FiberVersionedState::DISCRIMINATOR,
+                decode_fiber,
+            )
+            .register(TOKEN_PROGRAM_ID, &[], decode_spl_token)
+            .register(TOKEN_2022_PROGRAM_ID, &[], decode_spl_token);
+        registry
+    }
+
+    /// Decode accounts owned by `owner` whose data starts with `discriminator`
+    ///
+    /// An empty discriminator claims every account of the owner. When several
+    /// registrations match, the longest discriminator wins, and among equally
+    /// long ones the latest registered.
+    pub fn register(
+        &mut self,
+        owner: Pubkey,
+        discriminator: &[u8],
+        decoder: impl AccountDecoder + 'static,
+    ) -> &mut Self {
+        self.registrations.push(Arc::new(Registration {
+            owner,
+            discriminator: discriminator.to_vec(),
+            decoder: Arc::new(decoder),
+        }));
+        self
+    }
+
+    /// The decoder for an account owned by `owner` holding `data`
+    pub fn decoder_for(&self, owner: &Pubkey, data: &[u8]) -> Option<&dyn AccountDecoder> {
+        self.registrations
+            .iter()
+            .filter(|r| r.owner == *owner && data.starts_with(&r.discriminator))
+            .max_by_key(|r| r.discriminator.len())
+            .map(|r| r.decoder.as_ref())
+    }
+
+    /// Decode an account, falling back to its raw bytes
+    pub fn view(&self, owner: &Pubkey, data: &[u8]) -> AccountView {
+        let Some(decoder) = self.decoder_for(owner, data) else {
+            return AccountView::raw(data);
+        };
+        match decoder.decode(data) {
+            Ok(decoded) => AccountView::Decoded(decoded),
+            Err(e) => {
+                log::debug!("Failed to decode account owned by {}: {}", owner, e);
+                AccountView::raw(data)
+            }
+        }
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_thread(data: &[u8]) -> Result<DecodedAccount> {
+    let thread = Thread::try_deserialize(&mut &data[..]).map_err(|e| anyhow!("{}", e))?;
+    let mut decoded = DecodedAccount::new("Thread")
+        .field("version", thread.version)
+        .field("authority", thread.authority)
+        .field("id", String::from_utf8_lossy(&thread.id))
+        .field("name", &thread.name)
+        .field("trigger", trigger_str::format(&thread.trigger))
+        .field("schedule", format!("{:?}", thread.schedule))
+        .field("fiber_ids", format!("{:?}", thread.fiber_ids))
+        .field("fiber_cursor", thread.fiber_cursor)
+        .field("paused", thread.paused);
+    if let Some(resume_at) = thread.resume_at {
+        decoded = decoded.field("resume_at", resume_at);
+    }
+    Ok(decoded
+        .field("exec_count", thread.exec_count)
+        .field("last_executor", thread.last_executor))
+}
+
+fn decode_thread_config(data: &[u8]) -> Result<DecodedAccount> {
+    let config = ThreadConfig::try_deserialize(&mut &data[..]).map_err(|e| anyhow!("{}", e))?;
+    Ok(DecodedAccount::new("ThreadConfig")
+        .field("version", config.version)
+        .field("admin", config.admin)
+        .field("paused", config.paused)
+        .field("commission_fee", config.commission_fee)
+        .field("executor_fee_bps", config.executor_fee_bps)
+        .field("core_team_bps", config.core_team_bps)
+        .field("fee_payer_mode", format!("{:?}", config.fee_payer_mode))
+        .field("max_fiber_failures", config.max_fiber_failures)
+        .field("streak_half_life_execs", config.streak_half_life_execs))
+}
+
+fn decode_fiber(data: &[u8]) -> Result<DecodedAccount> {
+    let fiber = Fiber::try_deserialize(&mut &data[..]).map_err(|e| anyhow!("{}", e))?;
+    let (kind, exec_count, last_executed) = match &fiber {
+        Fiber::Legacy(state) => ("FiberState", state.exec_count, state.last_executed),
+        Fiber::V1(state) => ("FiberVersionedState", state.exec_count, state.last_executed),
+    };
+    let mut decoded = DecodedAccount::new(kind)
+        .field("thread", fiber.thread())
+        .field("exec_count", exec_count)
+        .field("last_executed", last_executed)
+        .field("priority_fee", fiber.priority_fee());
+    let instruction = CompiledInstructionV0::deserialize(&mut fiber.compiled_instruction())
+        .map_err(|e| anyhow!("{}", e))
+        .and_then(|compiled| decompile_instruction(&compiled).map_err(|e| anyhow!("{}", e)));
+    if let Ok(instruction) = instruction {
+        decoded = decoded
+            .field("program", instruction.program_id)
+            .field("accounts", instruction.accounts.len())
+            .field("data", to_hex(&instruction.data));
+    }
+    if !fiber.lookup_tables().is_empty() {
+        decoded = decoded.field("lookup_tables", fiber.lookup_tables().len());
+    }
+    if let Some(units) = fiber.estimated_compute_units() {
+        decoded = decoded.field("estimated_compute_units", units);
+    }
+    Ok(decoded)
+}
+
+/// Token accounts and mints, with or without Token-2022 extensions
+///
+/// Both programs share the base layouts. Accounts with extensions are
+/// padded to the token account size and tagged with the account type.
+fn decode_spl_token(data: &[u8]) -> Result<DecodedAccount> {
+    match data.len() {
+        TOKEN_ACCOUNT_LEN => decode_token_account(data),
+        MINT_LEN => decode_mint(data),
+        len if len > TOKEN_ACCOUNT_LEN => match data[TOKEN_ACCOUNT_LEN] {
+            1 => decode_mint(data),
+            2 => decode_token_account(data),
+            other => Err(anyhow!("Unknown token account type {}", other)),
+        },
+        len => Err(anyhow!("Unexpected token account size {}", len)),
+    }
+}
+
+fn decode_token_account(data: &[u8]) -> Result<DecodedAccount> {
+    let mut decoded = DecodedAccount::new("TokenAccount")
+        .field("mint", read_pubkey(data, 0))
+        .field("owner", read_pubkey(data, 32))
+        .field("amount", read_u64(data, 64));
+    if let Some(delegate) = read_optional_pubkey(data, 72) {
+        decoded = decoded
+            .field("delegate", delegate)
+            .field("delegated_amount", read_u64(data, 121));
+    }
+    let state = match data[108] {
+        0 => "uninitialized",
+        1 => "initialized",
+        2 => "frozen",
+        _ => "unknown",
+    };
+    decoded = decoded.field("state", state);
+    if read_u32(data, 109) == 1 {
+        decoded = decoded.field("rent_exempt_reserve", read_u64(data, 113));
+    }
+    if let Some(close_authority) = read_optional_pubkey(data, 129) {
+        decoded = decoded.field("close_authority", close_authority);
+    }
+    Ok(decoded)
+}
+
+fn decode_mint(data: &[u8]) -> Result<DecodedAccount> {
+    let authority = |offset| {
+        read_optional_pubkey(data, offset)
+            .map(|pubkey| pubkey.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    };
+    Ok(DecodedAccount::new("Mint")
+        .field("mint_authority", authority(0))
+        .field("supply", read_u64(data, 36))
+        .field("decimals", data[44])
+        .field("is_initialized", data[45] != 0)
+        .field("freeze_authority", authority(46)))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// A `COption<Pubkey>`: a u32 tag followed by the key
+fn read_optional_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    (read_u32(data, offset) == 1).then(|| read_pubkey(data, offset + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::worker::tests::new_thread;
+    use anchor_lang::AccountSerialize;
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[0..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data[108] = 1;
+        data
+    }
+
+    #[test]
+    fn test_decodes_thread() {
+        let executor = Pubkey::new_unique();
+        let mut data = Vec::new();
+        new_thread(executor).try_serialize(&mut data).unwrap();
+
+        let registry = DecoderRegistry::with_builtin();
+        let AccountView::Decoded(decoded) = registry.view(&antegen_thread_program::ID, &data)
+        else {
+            panic!("thread was not decoded");
+        };
+        assert_eq!(decoded.kind, "Thread");
+        assert_eq!(decoded.get("name"), Some("drain"));
+        assert_eq!(decoded.get("trigger"), Some("interval:1m"));
+        assert_eq!(
+            decoded.get("last_executor"),
+            Some(executor.to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn test_decodes_spl_token_account() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut data = token_account(mint, owner, 1_500);
+        let registry = DecoderRegistry::with_builtin();
+
+        let AccountView::Decoded(decoded) = registry.view(&TOKEN_PROGRAM_ID, &data) else {
+            panic!("token account was not decoded");
+        };
+        assert_eq!(decoded.kind, "TokenAccount");
+        assert_eq!(decoded.get("mint"), Some(mint.to_string().as_str()));
+        assert_eq!(decoded.get("owner"), Some(owner.to_string().as_str()));
+        assert_eq!(decoded.get("amount"), Some("1500"));
+        assert_eq!(decoded.get("state"), Some("initialized"));
+        assert_eq!(decoded.get("delegate"), None);
+
+        // Token-2022 accounts with extensions carry the account type after the base layout
+        data.extend_from_slice(&[2, 0, 0, 0, 0]);
+        let AccountView::Decoded(decoded) = registry.view(&TOKEN_2022_PROGRAM_ID, &data) else {
+            panic!("token-2022 account was not decoded");
+        };
+        assert_eq!(decoded.get("amount"), Some("1500"));
+    }
+
+    #[test]
+    fn test_decodes_spl_mint() {
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0u8; MINT_LEN];
+        data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        data[4..36].copy_from_slice(authority.as_ref());
+        data[36..44].copy_from_slice(&1_000_000u64.to_le_bytes());
+        data[44] = 6;
+        data[45] = 1;
+
+        let registry = DecoderRegistry::with_builtin();
+        let AccountView::Decoded(decoded) = registry.view(&TOKEN_PROGRAM_ID, &data) else {
+            panic!("mint was not decoded");
+        };
+        assert_eq!(decoded.kind, "Mint");
+        assert_eq!(
+            decoded.get("mint_authority"),
+            Some(authority.to_string().as_str())
+        );
+        assert_eq!(decoded.get("supply"), Some("1000000"));
+        assert_eq!(decoded.get("decimals"), Some("6"));
+        assert_eq!(decoded.get("freeze_authority"), Some("none"));
+    }
+
+    #[test]
+    fn test_unknown_accounts_fall_back_to_raw() {
+        let registry = DecoderRegistry::with_builtin();
+        let data = [0xde, 0xad, 0xbe, 0xef];
+
+        // Nothing registered for the owner
+        assert_eq!(
+            registry.view(&Pubkey::new_unique(), &data),
+            AccountView::Raw {
+                hex: "deadbeef".to_string(),
+                base64: "3q2+7w==".to_string(),
+            }
+        );
+        // Unknown discriminator for a known owner
+        assert_eq!(
+            registry.view(&antegen_thread_program::ID, &[0; 16]),
+            AccountView::raw(&[0; 16])
+        );
+        // A decoder that fails on truncated data
+        assert_eq!(
+            registry.view(&TOKEN_PROGRAM_ID, &data),
+            AccountView::raw(&data)
+        );
+    }
+
+    #[test]
+    fn test_integrator_decoders() {
+        let program = Pubkey::new_unique();
+        let mut registry = DecoderRegistry::with_builtin();
+        registry
+            .register(program, &[], |_: &[u8]| Ok(DecodedAccount::new("Any")))
+            .register(program, &[7, 7], |data: &[u8]| {
+                Ok(DecodedAccount::new("Oracle").field("price", data[2]))
+            });
+
+        // The longest matching discriminator wins
+        assert_eq!(
+            registry.view(&program, &[7, 7, 42]),
+            AccountView::Decoded(DecodedAccount::new("Oracle").field("price", 42))
+        );
+        assert_eq!(
+            registry.view(&program, &[1, 2, 3]),
+            AccountView::Decoded(DecodedAccount::new("Any"))
+        );
+
+        // Later registrations override earlier ones of the same discriminator
+        registry.register(TOKEN_PROGRAM_ID, &[], |_: &[u8]| {
+            Ok(DecodedAccount::new("Custom"))
+        });
+        let data = token_account(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+        assert_eq!(
+            registry.view(&TOKEN_PROGRAM_ID, &data),
+            AccountView::Decoded(DecodedAccount::new("Custom"))
+        );
+    }
+}
@@ -5,18 +5,21 @@
 //!
 //! Batching is determined by the fiber's Signal return value:
 //! - Signal::Chain → batch another exec for next fiber in sequence
+//! - Signal::Goto → batch another exec for the targeted fiber
 //! - Signal::Close → batch a delete instruction
 //! - Other signals → no batching needed
 
+use crate::fee_payers::FeePayers;
+use crate::helpers::{exec_base_accounts, ExecThreadBuilder};
 use crate::resources::SharedResources;
 use crate::rpc::response::decode_account_data;
 use anchor_lang::{AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas};
 use antegen_thread_program::fiber::{decompile_instruction, CompiledInstructionV0, Fiber};
 use antegen_thread_program::state::PAYER_PUBKEY;
 use antegen_thread_program::{
-    accounts::ThreadExec,
-    instruction::ExecThread,
-    state::{Signal, Thread, ThreadConfig},
+    accounts::FiberReportFailure,
+    instruction::{ExecThread, ReportFiberFailure},
+    state::{FeePayerMode, Signal, Thread, ThreadConfig, Trigger},
 };
 use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_sdk::{
@@ -24,9 +27,9 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     message::Message,
     pubkey::Pubkey,
-    signature::Keypair,
+    rent::Rent,
+    signature::{Keypair, Signature},
     signer::Signer,
-    sysvar,
     transaction::Transaction,
 };
 use std::collections::HashSet;
@@ -36,13 +39,42 @@ use log::{debug, info, warn};
 use std::sync::Arc;
 
 /// Maximum serialized transaction size in bytes (Solana's PACKET_DATA_SIZE)
-const MAX_TRANSACTION_SIZE: usize = 1232;
+pub(crate) const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Estimate serialized size of a transaction paid by `payer`, including the
+/// compute budget instructions the worker prepends.
+/// Uses Message::new for accurate account deduplication + bincode size.
+pub(crate) fn estimate_transaction_size_with_budget(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+) -> usize {
+    let mut trial = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+        ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+    ];
+    trial.extend_from_slice(instructions);
+    let message = Message::new(&trial, Some(payer));
+    bincode::serialized_size(&message).unwrap_or(0) as usize + 65 // +64 sig +1 compact-u16
+}
+
+/// Compute units a `thread_exec` spends before its recorded estimate starts
+/// counting, loading and checking its accounts
+pub(crate) const EXEC_SETUP_UNITS: u64 = 10_000;
+
+/// Compute unit limit for `exec_count` thread_execs whose fibers recorded
+/// `stored_units` between them, with the same 10% margin as a simulation
+pub(crate) fn stored_compute_limit(stored_units: u64, exec_count: usize) -> u32 {
+    let units = stored_units + EXEC_SETUP_UNITS * exec_count as u64;
+    ((units as f64) * 1.1).min(1_400_000.0) as u32
+}
 
 /// Executor logic for building thread execution transactions
 #[derive(Clone)]
 pub struct ExecutorLogic {
-    /// Executor keypair for signing transactions
+    /// Keypair this instance signs with, as executor and fee payer
     keypair: Arc<Keypair>,
+    /// Every executor keypair, see [`Self::next_fee_payer`]
+    fee_payers: Arc<FeePayers>,
     /// Shared resources (RPC pool, cache)
     resources: SharedResources,
     /// Whether to forgo executor commission
@@ -60,6 +92,7 @@ impl ExecutorLogic {
     ) -> Self {
         let program_id = resources.program_id;
         Self {
+            fee_payers: Arc::new(FeePayers::single(keypair.clone())),
             keypair,
             resources,
             forgo_executor_commission,
@@ -67,6 +100,14 @@ impl ExecutorLogic {
         }
     }
 
+    /// Rotate executions across `fee_payers`, signing with the primary until
+    /// [`Self::next_fee_payer`] picks another
+    pub fn with_fee_payers(mut self, fee_payers: Arc<FeePayers>) -> Self {
+        self.keypair = fee_payers.primary().clone();
+        self.fee_payers = fee_payers;
+        self
+    }
+
     /// Get executor pubkey
     pub fn pubkey(&self) -> Pubkey {
         self.keypair.pubkey()
@@ -77,11 +118,34 @@ impl ExecutorLogic {
         &self.keypair
     }
 
+    pub fn fee_payers(&self) -> &Arc<FeePayers> {
+        &self.fee_payers
+    }
+
+    /// This executor signing with the next fee payer in rotation
+    ///
+    /// Taken once per execution, so every transaction of the execution
+    /// names and is signed by the same executor.
+    pub fn next_fee_payer(&self) -> Self {
+        let mut executor = self.clone();
+        executor.keypair = self.fee_payers.next();
+        executor
+    }
+
+    /// This executor signing with `pubkey`, if it is one of its fee payers
+    pub fn signed_by(&self, pubkey: &Pubkey) -> Option<Self> {
+        let keypair = self.fee_payers.get(pubkey)?.clone();
+        let mut executor = self.clone();
+        executor.keypair = keypair;
+        Some(executor)
+    }
+
     /// Build a single transaction batch to execute a thread with automatic batching.
     ///
     /// Simulates to detect chaining signals and estimate CU consumption.
     /// Batching is determined by the Signal returned from the fiber:
     /// - Signal::Chain → batch another exec for next fiber in sequence
+    /// - Signal::Goto → batch another exec for the targeted fiber
     /// - Signal::Close → batch a delete instruction
     ///
     /// If chained instructions would exceed Solana's max transaction size, only
@@ -92,27 +156,31 @@ impl ExecutorLogic {
     /// Returns (instructions, priority_fee, needs_continuation)
     /// Build a single transaction batch to execute a thread.
     ///
-    /// Returns (instructions, priority_fee, needs_continuation, next_fiber_cursor).
+    /// Returns (instructions, priority_fee, needs_continuation, next_fiber_cursor,
+    /// stored_compute_units).
     /// When `needs_continuation` is true, `next_fiber_cursor` holds the cursor
     /// that the next batch should start from (needed because on-chain Chain
-    /// signal doesn't advance `fiber_cursor`).
+    /// signal doesn't advance `fiber_cursor`). `stored_compute_units` sums the
+    /// compute estimates recorded on the batch's fibers, and is `None` when any
+    /// instruction in the batch has none.
     pub async fn build_execute_transaction(
         &self,
         thread_pubkey: &Pubkey,
         thread: &Thread,
         override_fiber_cursor: Option<u8>,
-    ) -> Result<(Vec<Instruction>, u64, bool, Option<u8>)> {
+    ) -> Result<(Vec<Instruction>, u64, bool, Option<u8>, Option<u64>)> {
         // Log thread state for debugging
         self.log_thread_debug(thread, thread_pubkey);
 
         const MAX_BATCHED_EXECS: usize = 5;
         let mut priority_fee: u64 = 0;
+        let mut stored_units: Option<u64> = Some(0);
         let mut ixs: Vec<Instruction> = Vec::new();
         let mut needs_continuation = false;
         let mut next_fiber_cursor: Option<u8> = None;
 
         // Track fiber_cursor through the chaining loop
-        // Signal::Chain tells us to execute next fiber in sequence
+        // Signal::Chain tells us to execute next fiber in sequence, Signal::Goto a specific one
         let mut current_fiber_cursor = override_fiber_cursor.unwrap_or(thread.fiber_cursor);
 
         // Build first instruction
@@ -123,6 +191,7 @@ impl ExecutorLogic {
         let first_ix = self
             .build_thread_exec_ix(
                 &mut priority_fee,
+                &mut stored_units,
                 thread_pubkey,
                 thread,
                 current_fiber_cursor,
@@ -132,7 +201,7 @@ impl ExecutorLogic {
         // Empty fiber — nothing to submit
         let Some(first_ix) = first_ix else {
             info!("{}: first fiber is empty, nothing to submit", thread_pubkey);
-            return Ok((vec![], 0, false, None));
+            return Ok((vec![], 0, false, None, None));
         };
 
         debug!(
@@ -169,19 +238,22 @@ impl ExecutorLogic {
                 thread_pubkey, current_fiber_cursor, signal
             );
 
-            // Handle signal - only Chain and Close trigger batching
+            // Handle signal - only Chain, Goto and Close trigger batching
             match signal {
-                Signal::Chain => {
-                    // Calculate next fiber in sequence
-                    current_fiber_cursor =
-                        Self::next_fiber_in_sequence(&thread.fiber_ids, current_fiber_cursor);
+                Signal::Chain | Signal::Goto { .. } => {
+                    // Chain continues with the next fiber in sequence, Goto with its target
+                    current_fiber_cursor = match signal {
+                        Signal::Goto { index } => index,
+                        _ => thread.next_enabled_fiber(current_fiber_cursor),
+                    };
                     info!(
-                        "Batching: Signal::Chain, adding thread_exec for fiber {}",
-                        current_fiber_cursor
+                        "Batching: Signal::{:?}, adding thread_exec for fiber {}",
+                        signal, current_fiber_cursor
                     );
                     let next_ix = self
                         .build_thread_exec_ix(
                             &mut priority_fee,
+                            &mut stored_units,
                             thread_pubkey,
                             thread,
                             current_fiber_cursor,
@@ -232,6 +304,8 @@ impl ExecutorLogic {
                     trial.push(close_ix.clone());
                     if self.would_fit_in_transaction(&trial) {
                         ixs.push(close_ix);
+                        // The close fiber records no estimate
+                        stored_units = None;
                     } else {
                         info!(
                             "{}: transaction full ({} ix), close deferred to continuation",
@@ -287,7 +361,100 @@ impl ExecutorLogic {
             needs_continuation
         );
 
-        Ok((ixs, priority_fee, needs_continuation, next_fiber_cursor))
+        Ok((
+            ixs,
+            priority_fee,
+            needs_continuation,
+            next_fiber_cursor,
+            stored_units,
+        ))
+    }
+
+    /// Check whether a thread can pay for its next execution.
+    ///
+    /// Only threads in `ThreadReimburses` mode are checked: their balance must
+    /// cover the base fee and full commission while staying rent-exempt, or the
+    /// exec fails on-chain. Executor-paid threads always pass.
+    pub async fn can_reimburse(&self, thread_pubkey: &Pubkey, thread: &Thread) -> Result<bool> {
+        let config = self.fetch_thread_config(&ThreadConfig::pubkey()).await?;
+        if thread.effective_fee_payer_mode(&config) == FeePayerMode::ExecutorPays {
+            return Ok(true);
+        }
+
+        // The cache holds account data only, so read the balance from RPC
+        let ui_account = self
+            .resources
+            .rpc_client
+            .get_account(thread_pubkey)
+            .await?
+            .ok_or_else(|| anyhow!("Thread {} not found", thread_pubkey))?;
+        let data_len = ui_account
+            .decode_data()
+            .map_err(|e| anyhow!("Failed to decode thread account data: {}", e))?
+            .len();
+
+        Ok(ui_account.lamports >= reimbursement_floor(&config, data_len))
+    }
+
+    /// Current lamport balance of a thread account, read from RPC
+    pub async fn thread_balance(&self, thread_pubkey: &Pubkey) -> Result<u64> {
+        let ui_account = self
+            .resources
+            .rpc_client
+            .get_account(thread_pubkey)
+            .await?
+            .ok_or_else(|| anyhow!("Thread {} not found", thread_pubkey))?;
+        Ok(ui_account.lamports)
+    }
+
+    /// Writable accounts every thread_exec shares: the executor and the
+    /// config admin. Execs of different threads touching only these in
+    /// common can be packed into one transaction.
+    pub async fn shared_exec_accounts(&self) -> Result<Vec<Pubkey>> {
+        let config = self.fetch_thread_config(&ThreadConfig::pubkey()).await?;
+        Ok(vec![self.keypair.pubkey(), config.admin])
+    }
+
+    /// Report a failed execution of `fiber_cursor` to the thread program,
+    /// which disables the fiber once the config's `max_fiber_failures` is
+    /// reached. Returns `None` without sending when that is disabled.
+    pub async fn report_fiber_failure(
+        &self,
+        thread_pubkey: &Pubkey,
+        thread: &Thread,
+        fiber_cursor: u8,
+    ) -> Result<Option<Signature>> {
+        let config_pubkey = ThreadConfig::pubkey();
+        let config = self.fetch_thread_config(&config_pubkey).await?;
+        if config.max_fiber_failures == 0 {
+            return Ok(None);
+        }
+
+        let mut accounts = FiberReportFailure {
+            executor: self.keypair.pubkey(),
+            thread: *thread_pubkey,
+            config: config_pubkey,
+        }
+        .to_account_metas(None);
+        // Account triggers are validated against the watched account
+        if let Trigger::Account { address, .. } = &thread.trigger {
+            accounts.push(AccountMeta::new_readonly(*address, false));
+        }
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: ReportFiberFailure { fiber_cursor }.data(),
+        };
+
+        let (blockhash, _) = self.resources.rpc_client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.keypair.pubkey()),
+            &[self.keypair.as_ref()],
+            blockhash,
+        );
+        let signature = self.resources.rpc_client.send_transaction(&tx).await?;
+        Ok(Some(signature))
     }
 
     /// Fetch thread account from RPC and deserialize.
@@ -309,21 +476,9 @@ impl ExecutorLogic {
             .map_err(|e| anyhow!("Failed to deserialize thread {}: {}", thread_pubkey, e))
     }
 
-    /// Estimate serialized transaction size for a set of instructions.
-    /// Uses Message::new for accurate account deduplication + bincode size.
-    fn estimate_transaction_size(&self, instructions: &[Instruction]) -> usize {
-        let message = Message::new(instructions, Some(&self.keypair.pubkey()));
-        bincode::serialized_size(&message).unwrap_or(0) as usize + 65 // +64 sig +1 compact-u16
-    }
-
     /// Estimate transaction size including compute budget instructions.
     fn estimate_transaction_size_with_budget(&self, instructions: &[Instruction]) -> usize {
-        let mut trial = vec![
-            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
-            ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
-        ];
-        trial.extend_from_slice(instructions);
-        self.estimate_transaction_size(&trial)
+        estimate_transaction_size_with_budget(&self.keypair.pubkey(), instructions)
     }
 
     /// Check if instructions (plus compute budget overhead) would fit in one transaction.
@@ -355,26 +510,13 @@ impl ExecutorLogic {
         debug!("  exec_count: {}", thread.exec_count);
     }
 
-    /// Calculate the next fiber index in sequence
-    /// Used for Signal::Chain which always chains to the next consecutive fiber
-    fn next_fiber_in_sequence(fiber_ids: &[u8], current_cursor: u8) -> u8 {
-        if fiber_ids.is_empty() {
-            return 0;
-        }
-        if let Some(current_pos) = fiber_ids.iter().position(|&x| x == current_cursor) {
-            let next_pos = (current_pos + 1) % fiber_ids.len();
-            fiber_ids[next_pos]
-        } else {
-            fiber_ids.first().copied().unwrap_or(0)
-        }
-    }
-
     /// Build thread_exec instruction for a specific fiber, returning the instruction.
     ///
     /// Fetches the external fiber account to get compiled instruction and priority fee.
     async fn build_thread_exec_ix(
         &self,
         priority_fee: &mut u64,
+        stored_units: &mut Option<u64>,
         thread_pubkey: &Pubkey,
         thread: &Thread,
         fiber_cursor: u8,
@@ -389,8 +531,13 @@ impl ExecutorLogic {
             fiber_pubkey, fiber_cursor
         );
 
-        let account = self.fetch_fiber_account(&fiber_pubkey).await?;
-        let fiber_read = Fiber::try_deserialize(&mut account.data.as_slice())
+        // A fiber prefetched ahead of the trigger is used once; later
+        // executions fetch it fresh again
+        let data = match self.resources.cache.take(&fiber_pubkey).await {
+            Some(prefetched) => prefetched.data,
+            None => self.fetch_fiber_account(&fiber_pubkey).await?.data,
+        };
+        let fiber_read = Fiber::try_deserialize(&mut data.as_slice())
             .map_err(|e| anyhow!("Failed to deserialize fiber {}: {}", fiber_pubkey, e))?;
 
         // Empty compiled_instruction = cleared fiber (e.g. after close). Skip.
@@ -404,22 +551,40 @@ impl ExecutorLogic {
 
         debug!("Fiber fetched, priority_fee={}", fiber_read.priority_fee());
 
+        self.audit_compiled_instruction(fiber_cursor, fiber_read.compiled_instruction());
+
         // Build execute instruction
-        let ix = self
-            .build_execute_instruction(
-                thread_pubkey,
-                thread,
-                fiber_cursor,
-                fiber_read.compiled_instruction(),
-            )
-            .await?;
+        let config = self.fetch_thread_config(&ThreadConfig::pubkey()).await?;
+        let ix = ExecThreadBuilder::new(
+            *thread_pubkey,
+            thread,
+            fiber_cursor,
+            &fiber_read,
+            self.keypair.pubkey(),
+        )
+        .forgo_commission(self.forgo_executor_commission)
+        .program_id(self.program_id)
+        .build(&config)?
+        .ok_or_else(|| anyhow!("fiber_{} has no compiled instruction", fiber_cursor))?;
+
+        info!(
+            "fiber_{} instruction: program={}, accounts={}, data_len={}",
+            fiber_cursor,
+            ix.program_id,
+            ix.accounts.len(),
+            ix.data.len()
+        );
 
         *priority_fee = (*priority_fee).max(fiber_read.priority_fee());
+        // A batch has a stored estimate only if every fiber in it has one
+        *stored_units = stored_units
+            .zip(fiber_read.estimated_compute_units())
+            .map(|(total, units)| total + u64::from(units));
 
         Ok(Some(ix))
     }
 
-    /// Build base ThreadExec accounts (shared by build_execute_instruction and build_close_thread_exec)
+    /// Build base ThreadExec accounts for build_close_thread_exec
     async fn build_thread_exec_base_accounts(
         &self,
         thread_pubkey: &Pubkey,
@@ -438,90 +603,27 @@ impl ExecutorLogic {
             has_nonce
         );
 
-        let accounts = ThreadExec {
-            executor: self.keypair.pubkey(),
-            thread: *thread_pubkey,
-            fiber: fiber_pubkey,
-            config: config_pubkey,
-            admin: config.admin,
-            nonce_account: if has_nonce {
-                Some(thread.nonce_account)
-            } else {
-                None
-            },
-            recent_blockhashes: if has_nonce {
-                Some(sysvar::recent_blockhashes::ID)
-            } else {
-                None
-            },
-            system_program: solana_system_interface::program::ID,
-        }
-        .to_account_metas(Some(false));
-
-        Ok((accounts, config))
-    }
-
-    /// Add compiled instruction accounts to the account list
-    fn add_compiled_accounts(
-        &self,
-        accounts: &mut Vec<AccountMeta>,
-        compiled: &CompiledInstructionV0,
-    ) {
-        debug!(
-            "Adding remaining accounts: {} accounts from compiled.accounts",
-            compiled.accounts.len()
+        let accounts = exec_base_accounts(
+            self.keypair.pubkey(),
+            thread_pubkey,
+            thread,
+            fiber_pubkey,
+            config.admin,
         );
 
-        for (account_index, pubkey) in compiled.accounts.iter().enumerate() {
-            // Replace PAYER_PUBKEY with executor
-            let actual_pubkey = if pubkey.eq(&PAYER_PUBKEY) {
-                self.keypair.pubkey()
-            } else {
-                *pubkey
-            };
-
-            // Determine writability based on position in sorted accounts
-            let account_idx = account_index as u8;
-            let is_writable = if account_idx < compiled.num_rw_signers {
-                true // Read-write signer
-            } else if account_idx < compiled.num_rw_signers + compiled.num_ro_signers {
-                false // Read-only signer
-            } else if account_idx
-                < compiled.num_rw_signers + compiled.num_ro_signers + compiled.num_rw
-            {
-                true // Read-write non-signer
-            } else {
-                false // Read-only non-signer
-            };
-
-            debug!(
-                "  remaining[{}]: {} (is_writable={})",
-                account_index, actual_pubkey, is_writable
-            );
-            accounts.push(AccountMeta {
-                pubkey: actual_pubkey,
-                is_signer: false, // CPI accounts don't need to be signers at transaction level
-                is_writable,
-            });
-        }
+        Ok((accounts, config))
     }
 
-    /// Build exec_thread instruction
-    async fn build_execute_instruction(
-        &self,
-        thread_pubkey: &Pubkey,
-        thread: &Thread,
-        fiber_cursor: u8,
-        compiled_instruction: &[u8],
-    ) -> Result<Instruction> {
-        debug!(
-            "Building exec_thread instruction: thread={}, fiber_cursor={}",
-            thread_pubkey, fiber_cursor,
-        );
-
-        // Get compiled instruction from fiber account
-        let fiber_pubkey = thread.fiber_at_index(thread_pubkey, fiber_cursor);
-        let compiled = CompiledInstructionV0::deserialize(&mut &compiled_instruction[..])?;
+    /// Log whether every account the fiber's instruction uses is in its
+    /// compiled account table
+    fn audit_compiled_instruction(&self, fiber_cursor: u8, compiled_instruction: &[u8]) {
+        let compiled = match CompiledInstructionV0::deserialize(&mut &compiled_instruction[..]) {
+            Ok(compiled) => compiled,
+            Err(e) => {
+                warn!("Failed to deserialize instruction for audit: {}", e);
+                return;
+            }
+        };
 
         // Diagnostic: decompile and verify all instruction accounts are in compiled.accounts
         let remaining_pubkeys: HashSet<Pubkey> = compiled
@@ -584,37 +686,6 @@ impl ExecutorLogic {
                 warn!("Failed to decompile instruction for audit: {}", e);
             }
         }
-
-        // Build base accounts
-        let (mut accounts, _config) = self
-            .build_thread_exec_base_accounts(thread_pubkey, thread, fiber_pubkey)
-            .await?;
-
-        // Add compiled instruction accounts as remaining accounts
-        self.add_compiled_accounts(&mut accounts, &compiled);
-
-        // Build instruction data using Anchor-generated type
-        let data = ExecThread {
-            forgo_commission: self.forgo_executor_commission,
-            fiber_cursor,
-        }
-        .data();
-
-        info!(
-            "fiber_{} instruction: program={}, base_accounts={}, remaining={}, total={}, data_len={}",
-            fiber_cursor,
-            self.program_id,
-            accounts.len() - compiled.accounts.len(),
-            compiled.accounts.len(),
-            accounts.len(),
-            data.len()
-        );
-
-        Ok(Instruction {
-            program_id: self.program_id,
-            accounts,
-            data,
-        })
     }
 
     /// Build thread_exec instruction that executes close_fiber to delete the thread
@@ -764,7 +835,7 @@ impl ExecutorLogic {
         let result = match self
             .resources
             .rpc_client
-            .simulate_transaction(&tx, &[*thread_pubkey])
+            .simulate_transaction_with_accounts(&tx, &[*thread_pubkey])
             .await
         {
             Ok(r) => r,
@@ -909,8 +980,18 @@ impl ExecutorLogic {
     }
 }
 
+/// Minimum balance a reimbursing thread needs to pay for one execution
+pub fn reimbursement_floor(config: &ThreadConfig, data_len: usize) -> u64 {
+    Rent::default()
+        .minimum_balance(data_len)
+        .saturating_add(config.base_fee_lamports)
+        .saturating_add(config.commission_fee)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     // Integration tests would require RPC connection
     // Unit tests for the module structure
     #[test]
@@ -918,4 +999,37 @@ mod tests {
         // Just verify the struct can be created
         // Full tests require RPC mocking
     }
+
+    #[test]
+    fn test_stored_compute_limit() {
+        // Setup overhead per exec, plus a 10% margin
+        assert_eq!(stored_compute_limit(50_000, 1), 66_000);
+        assert_eq!(stored_compute_limit(50_000, 2), 77_000);
+        // Never above the per-transaction maximum
+        assert_eq!(stored_compute_limit(u32::MAX as u64, 1), 1_400_000);
+    }
+
+    #[test]
+    fn test_reimbursement_floor() {
+        let config = ThreadConfig {
+            version: 1,
+            bump: 0,
+            admin: Pubkey::default(),
+            paused: false,
+            commission_fee: 1000,
+            executor_fee_bps: 9000,
+            core_team_bps: 1000,
+            grace_period_seconds: 5,
+            fee_decay_seconds: 295,
+            waive_core_team_on_forgo: false,
+            fee_payer_mode: FeePayerMode::ThreadReimburses,
+            base_fee_lamports: 5000,
+            pending_admin: None,
+            max_chain_depth: 16,
+            max_fiber_failures: 0,
+            streak_half_life_execs: 1_000,
+        };
+        let rent = Rent::default().minimum_balance(100);
+        assert_eq!(reimbursement_floor(&config, 100), rent + 6000);
+    }
 }
@@ -0,0 +1,240 @@
+//! Fee payer rotation across the executor's keypairs
+//!
+//! Every transaction write-locks its fee payer, so a single executor keypair
+//! becomes a hotspot once dozens of executions land per slot. With
+//! `[executor] keypairs` configured, each execution takes the next funded
+//! keypair in round-robin order. The thread program accepts any signer as
+//! executor, so the fee payer also signs as executor and earns the
+//! commission for its executions. The primary keypair (`keypair_path`) comes
+//! first and is used wherever a single identity is needed.
+
+use crate::rpc::RpcPool;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Fee payers below this balance are skipped, the same floor the node waits
+/// for at startup (0.001 SOL)
+pub const MIN_FEE_PAYER_BALANCE: u64 = 1_000_000;
+
+/// How often the balance monitor refreshes every fee payer's balance
+pub const BALANCE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The executor's keypairs, rotated round-robin for each execution
+pub struct FeePayers {
+    /// Primary first
+    keypairs: Vec<Arc<Keypair>>,
+    /// Last known balance of each keypair; `u64::MAX` until measured
+    balances: Vec<AtomicU64>,
+    next: AtomicUsize,
+}
+
+impl FeePayers {
+    /// Rotate across `primary` and `others`, in that order
+    pub fn new(primary: Arc<Keypair>, others: Vec<Arc<Keypair>>) -> Self {
+        let mut keypairs = vec![primary];
+        for keypair in others {
+            if !keypairs.iter().any(|k| k.pubkey() == keypair.pubkey()) {
+                keypairs.push(keypair);
+            }
+        }
+        let balances = keypairs.iter().map(|_| AtomicU64::new(u64::MAX)).collect();
+        Self {
+            keypairs,
+            balances,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// A single keypair, which pays for everything
+    pub fn single(keypair: Arc<Keypair>) -> Self {
+        Self::new(keypair, Vec::new())
+    }
+
+    pub fn primary(&self) -> &Arc<Keypair> {
+        &self.keypairs[0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.keypairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keypairs.is_empty()
+    }
+
+    pub fn pubkeys(&self) -> Vec<Pubkey> {
+        self.keypairs.iter().map(|k| k.pubkey()).collect()
+    }
+
+    /// Whether `pubkey` is one of the executor's keypairs
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.keypairs.iter().any(|k| k.pubkey() == *pubkey)
+    }
+
+    /// The keypair for `pubkey`, if it is one of the executor's
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&Arc<Keypair>> {
+        self.keypairs.iter().find(|k| k.pubkey() == *pubkey)
+    }
+
+    /// The next funded keypair in rotation
+    ///
+    /// Keypairs last seen below [`MIN_FEE_PAYER_BALANCE`] are skipped. When
+    /// every one is drained the primary pays, so executions fail on the
+    /// executor that operators watch rather than stopping silently.
+    pub fn next(&self) -> Arc<Keypair> {
+        let len = self.keypairs.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&index| self.balances[index].load(Ordering::Relaxed) >= MIN_FEE_PAYER_BALANCE)
+            .map(|index| self.keypairs[index].clone())
+            .unwrap_or_else(|| self.primary().clone())
+    }
+
+    /// Record a balance read for `pubkey`
+    pub fn record_balance(&self, pubkey: &Pubkey, lamports: u64) {
+        if let Some(index) = self.keypairs.iter().position(|k| k.pubkey() == *pubkey) {
+            self.balances[index].store(lamports, Ordering::Relaxed);
+        }
+    }
+
+    /// Last known balance of each keypair, primary first; `None` until measured
+    pub fn balances(&self) -> Vec<(Pubkey, Option<u64>)> {
+        self.keypairs
+            .iter()
+            .zip(&self.balances)
+            .map(|(keypair, balance)| {
+                let lamports = balance.load(Ordering::Relaxed);
+                (keypair.pubkey(), (lamports != u64::MAX).then_some(lamports))
+            })
+            .collect()
+    }
+
+    /// Read every keypair's balance over RPC
+    ///
+    /// A failed read keeps the previous balance.
+    pub async fn refresh_balances(&self, rpc: &RpcPool) {
+        for keypair in &self.keypairs {
+            let pubkey = keypair.pubkey();
+            match rpc.get_balance(&pubkey).await {
+                Ok(lamports) => {
+                    if lamports < MIN_FEE_PAYER_BALANCE {
+                        log::warn!(
+                            "Fee payer {} drained ({} lamports), skipping it until funded",
+                            pubkey,
+                            lamports
+                        );
+                    }
+                    self.record_balance(&pubkey, lamports);
+                }
+                Err(e) => log::debug!("Failed to read fee payer {} balance: {}", pubkey, e),
+            }
+        }
+    }
+
+    /// Spawn a background loop refreshing balances, if there is more than
+    /// one keypair to choose from
+    ///
+    /// The task holds a weak reference and exits once the fee payers are
+    /// dropped.
+    pub fn spawn_balance_monitor(
+        self: &Arc<Self>,
+        rpc: Arc<RpcPool>,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let fee_payers = Arc::downgrade(self);
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(BALANCE_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(fee_payers) = fee_payers.upgrade() else {
+                    break;
+                };
+                fee_payers.refresh_balances(&rpc).await;
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_rpc;
+
+    fn fee_payers(count: usize) -> FeePayers {
+        let keypairs: Vec<Arc<Keypair>> = (0..count).map(|_| Arc::new(Keypair::new())).collect();
+        FeePayers::new(keypairs[0].clone(), keypairs[1..].to_vec())
+    }
+
+    #[test]
+    fn test_rotation_is_round_robin() {
+        let payers = fee_payers(3);
+        let pubkeys = payers.pubkeys();
+        let picked: Vec<Pubkey> = (0..6).map(|_| payers.next().pubkey()).collect();
+        assert_eq!(picked[..3], pubkeys[..]);
+        assert_eq!(picked[3..], pubkeys[..]);
+    }
+
+    #[test]
+    fn test_rotation_skips_drained_keypairs() {
+        let payers = fee_payers(3);
+        let pubkeys = payers.pubkeys();
+        payers.record_balance(&pubkeys[1], MIN_FEE_PAYER_BALANCE - 1);
+
+        let picked: Vec<Pubkey> = (0..4).map(|_| payers.next().pubkey()).collect();
+        assert!(!picked.contains(&pubkeys[1]));
+        assert_eq!(picked, vec![pubkeys[0], pubkeys[2], pubkeys[2], pubkeys[0]]);
+
+        // Funded again, it rejoins the rotation
+        payers.record_balance(&pubkeys[1], MIN_FEE_PAYER_BALANCE);
+        let picked: Vec<Pubkey> = (0..3).map(|_| payers.next().pubkey()).collect();
+        assert!(picked.contains(&pubkeys[1]));
+    }
+
+    #[test]
+    fn test_all_drained_falls_back_to_primary() {
+        let payers = fee_payers(2);
+        for pubkey in payers.pubkeys() {
+            payers.record_balance(&pubkey, 0);
+        }
+        assert_eq!(payers.next().pubkey(), payers.primary().pubkey());
+        assert_eq!(payers.next().pubkey(), payers.primary().pubkey());
+    }
+
+    #[test]
+    fn test_duplicate_keypairs_are_dropped() {
+        let primary = Arc::new(Keypair::new());
+        let payers = FeePayers::new(primary.clone(), vec![primary.clone()]);
+        assert_eq!(payers.len(), 1);
+        assert!(payers.contains(&primary.pubkey()));
+        assert!(payers.get(&Pubkey::new_unique()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_balances() {
+        let url =
+            spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":5}}"#)
+                .await;
+        let rpc = RpcPool::with_url(url).unwrap();
+        let payers = fee_payers(2);
+        assert!(payers
+            .balances()
+            .iter()
+            .all(|(_, lamports)| lamports.is_none()));
+
+        payers.refresh_balances(&rpc).await;
+        assert!(payers
+            .balances()
+            .iter()
+            .all(|(_, lamports)| *lamports == Some(5)));
+        // Below the floor everywhere, so the primary pays
+        assert_eq!(payers.next().pubkey(), payers.primary().pubkey());
+    }
+}
@@ -0,0 +1,198 @@
+//! Priority fee ceiling
+//!
+//! A thread's fibers set the compute unit price the executor bids. During a
+//! congestion spike that price, times the compute unit limit, can drain a
+//! thread's balance within a few executions. The ceiling caps the priority
+//! fee of a single transaction, either absolutely or as a fraction of the
+//! thread's remaining balance:
+//!
+//! - fee within the ceiling: sent unchanged
+//! - fee above the ceiling: the price is clamped down to the ceiling
+//! - clamped price below `min_viable_cu_price`: the transaction is unlikely
+//!   to land, so the execution is deferred instead of paying for a failure
+
+use crate::config::FeeCeilingConfig;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Micro-lamports per lamport
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Failure reason for executions deferred because the capped fee would not land
+pub(crate) const FEE_CEILING_DEFERRED_ERROR: &str = "Priority fee ceiling too low to land";
+
+/// Outcome of applying the fee ceiling to a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeDecision {
+    /// Priority fee is within the ceiling
+    Unchanged,
+    /// Priority fee exceeded the ceiling; send with this compute unit price
+    Clamped {
+        cu_price: u64,
+        ceiling_lamports: u64,
+    },
+    /// Even the ceiling would not plausibly land the transaction
+    Defer { ceiling_lamports: u64 },
+}
+
+/// Counters for fee ceiling enforcement, shared by all workers
+#[derive(Debug, Default)]
+pub struct FeeCeilingMetrics {
+    /// Submissions sent with a clamped compute unit price
+    pub clamped: AtomicU64,
+    /// Executions deferred because the ceiling was below the viable price
+    pub deferred: AtomicU64,
+}
+
+impl FeeCeilingMetrics {
+    pub fn clamped(&self) -> u64 {
+        self.clamped.load(Ordering::Relaxed)
+    }
+
+    pub fn deferred(&self) -> u64 {
+        self.deferred.load(Ordering::Relaxed)
+    }
+
+    /// Count a decision
+    pub fn record(&self, decision: &FeeDecision) {
+        match decision {
+            FeeDecision::Unchanged => {}
+            FeeDecision::Clamped { .. } => {
+                self.clamped.fetch_add(1, Ordering::Relaxed);
+            }
+            FeeDecision::Defer { .. } => {
+                self.deferred.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Priority fee in lamports for a compute unit price and limit
+pub fn priority_fee_lamports(cu_price: u64, cu_limit: u32) -> u64 {
+    let micro_lamports = cu_price as u128 * cu_limit as u128;
+    micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64
+}
+
+/// Effective ceiling in lamports, or `None` when no cap applies
+///
+/// The balance cap is ignored if the thread balance is unknown.
+pub fn ceiling_lamports(config: &FeeCeilingConfig, thread_balance: Option<u64>) -> Option<u64> {
+    let balance_cap = config
+        .max_balance_fraction
+        .zip(thread_balance)
+        .map(|(fraction, balance)| (balance as f64 * fraction) as u64);
+
+    match (config.max_priority_fee_lamports, balance_cap) {
+        (Some(absolute), Some(relative)) => Some(absolute.min(relative)),
+        (absolute, relative) => absolute.or(relative),
+    }
+}
+
+/// Apply the ceiling to a transaction's compute unit price and limit
+pub fn apply_fee_ceiling(
+    config: &FeeCeilingConfig,
+    cu_price: u64,
+    cu_limit: u32,
+    thread_balance: Option<u64>,
+) -> FeeDecision {
+    let Some(ceiling) = ceiling_lamports(config, thread_balance) else {
+        return FeeDecision::Unchanged;
+    };
+    if cu_limit == 0 || priority_fee_lamports(cu_price, cu_limit) <= ceiling {
+        return FeeDecision::Unchanged;
+    }
+
+    let clamped = (ceiling as u128 * MICRO_LAMPORTS_PER_LAMPORT / cu_limit as u128) as u64;
+    if clamped < config.min_viable_cu_price {
+        return FeeDecision::Defer {
+            ceiling_lamports: ceiling,
+        };
+    }
+
+    FeeDecision::Clamped {
+        cu_price: clamped,
+        ceiling_lamports: ceiling,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_fee_lamports_rounds_up() {
+        assert_eq!(priority_fee_lamports(1_000_000, 200_000), 200_000);
+        assert_eq!(priority_fee_lamports(1, 1), 1);
+        assert_eq!(priority_fee_lamports(0, 200_000), 0);
+    }
+
+    #[test]
+    fn test_no_ceiling_configured() {
+        let config = FeeCeilingConfig::default();
+        assert_eq!(
+            apply_fee_ceiling(&config, u64::MAX / 2, 1_400_000, Some(1)),
+            FeeDecision::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_absolute_ceiling_clamps() {
+        let config = FeeCeilingConfig {
+            max_priority_fee_lamports: Some(10_000),
+            ..Default::default()
+        };
+
+        // 50_000 micro-lamports/CU * 200_000 CU = 10_000 lamports, at the cap
+        assert_eq!(
+            apply_fee_ceiling(&config, 50_000, 200_000, None),
+            FeeDecision::Unchanged
+        );
+        assert_eq!(
+            apply_fee_ceiling(&config, 500_000, 200_000, None),
+            FeeDecision::Clamped {
+                cu_price: 50_000,
+                ceiling_lamports: 10_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_balance_fraction_uses_lower_cap() {
+        let config = FeeCeilingConfig {
+            max_priority_fee_lamports: Some(1_000_000),
+            max_balance_fraction: Some(0.01),
+            ..Default::default()
+        };
+        assert_eq!(ceiling_lamports(&config, Some(100_000)), Some(1_000));
+        assert_eq!(ceiling_lamports(&config, None), Some(1_000_000));
+
+        assert_eq!(
+            apply_fee_ceiling(&config, 100_000, 100_000, Some(100_000)),
+            FeeDecision::Clamped {
+                cu_price: 10_000,
+                ceiling_lamports: 1_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_defer_below_viable_price() {
+        let config = FeeCeilingConfig {
+            max_priority_fee_lamports: Some(1_000),
+            min_viable_cu_price: 20_000,
+            ..Default::default()
+        };
+        let decision = apply_fee_ceiling(&config, 100_000, 100_000, None);
+        assert_eq!(
+            decision,
+            FeeDecision::Defer {
+                ceiling_lamports: 1_000
+            }
+        );
+
+        let metrics = FeeCeilingMetrics::default();
+        metrics.record(&decision);
+        metrics.record(&FeeDecision::Unchanged);
+        assert_eq!(metrics.deferred(), 1);
+        assert_eq!(metrics.clamped(), 0);
+    }
+}
@@ -0,0 +1,445 @@
+//! Helpers for building on the thread program off-chain
+//!
+//! Integrators scripting against Antegen need the same pieces the executor
+//! uses: finding an authority's threads, deriving fiber addresses, decoding
+//! thread accounts, and assembling an `exec_thread` instruction. The
+//! executor builds its instructions with [`ExecThreadBuilder`], so an
+//! instruction built here is the one a live client would submit.
+//!
+//! ```ignore
+//! let threads = find_threads_for_authority(&rpc, &authority).await?;
+//! for (thread_pubkey, thread) in threads {
+//!     let fibers = derive_fiber_pdas(&thread_pubkey, thread.fiber_next_id);
+//!     // fetch and deserialize the fiber at `thread.fiber_cursor`, then
+//!     let ix = ExecThreadBuilder::new(thread_pubkey, &thread, thread.fiber_cursor, &fiber, executor)
+//!         .build_from_chain(&rpc)
+//!         .await?;
+//! }
+//! ```
+
+use crate::rpc::RpcPool;
+use anchor_lang::{
+    AccountDeserialize, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use antegen_thread_program::fiber::{CompiledInstructionV0, Fiber};
+use antegen_thread_program::state::PAYER_PUBKEY;
+use antegen_thread_program::{
+    accounts::ThreadExec,
+    instruction::ExecThread,
+    state::{Thread, ThreadConfig},
+};
+use anyhow::{anyhow, Result};
+use log::{debug, warn};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar,
+};
+
+/// Offset of `Thread::authority`: discriminator, version, bump
+pub const THREAD_AUTHORITY_OFFSET: usize = 8 + 1 + 1;
+
+/// Decode a thread account, discriminator included
+pub fn decode_thread(account_data: &[u8]) -> Result<Thread> {
+    Thread::try_deserialize(&mut &account_data[..])
+        .map_err(|e| anyhow!("Failed to deserialize thread: {}", e))
+}
+
+/// Addresses of a thread's fibers at indices `0..count`
+pub fn derive_fiber_pdas(thread: &Pubkey, count: u8) -> Vec<Pubkey> {
+    (0..count)
+        .map(|index| antegen_thread_program::pda::fiber_pubkey(thread, index).0)
+        .collect()
+}
+
+/// Every thread `authority` owns, with its address
+///
+/// Accounts that fail to decode are skipped with a warning.
+pub async fn find_threads_for_authority(
+    rpc: &RpcPool,
+    authority: &Pubkey,
+) -> Result<Vec<(Pubkey, Thread)>> {
+    let filters = vec![
+        serde_json::json!({
+            "memcmp": { "offset": 0, "bytes": bs58::encode(Thread::DISCRIMINATOR).into_string() }
+        }),
+        serde_json::json!({
+            "memcmp": { "offset": THREAD_AUTHORITY_OFFSET, "bytes": authority.to_string() }
+        }),
+    ];
+    let accounts = rpc
+        .get_program_accounts(&antegen_thread_program::ID, Some(filters))
+        .await
+        .map_err(|e| anyhow!("Failed to fetch threads of {}: {}", authority, e))?;
+
+    let mut threads = Vec::with_capacity(accounts.len());
+    for (pubkey, account) in accounts {
+        let thread = account
+            .decode_data()
+            .map_err(|e| anyhow!("{}", e))
+            .and_then(|data| decode_thread(&data));
+        match thread {
+            Ok(thread) => threads.push((pubkey, thread)),
+            Err(e) => warn!("Skipping thread {}: {}", pubkey, e),
+        }
+    }
+    Ok(threads)
+}
+
+/// Builds the `exec_thread` instruction running one fiber of a thread
+///
+/// The fiber's `PAYER_PUBKEY` placeholder resolves to the executor, and the
+/// admin account comes from the thread config, passed to [`Self::build`] or
+/// fetched by [`Self::build_from_chain`]. Fibers without a compute estimate
+/// get the accounts the thread program needs to record one.
+#[derive(Debug, Clone)]
+pub struct ExecThreadBuilder<'a> {
+    thread_pubkey: Pubkey,
+    thread: &'a Thread,
+    fiber_cursor: u8,
+    fiber: &'a Fiber,
+    executor: Pubkey,
+    forgo_commission: bool,
+    program_id: Pubkey,
+}
+
+impl<'a> ExecThreadBuilder<'a> {
+    /// Run `fiber`, the fiber at `fiber_cursor` of the thread, signed by `executor`
+    pub fn new(
+        thread_pubkey: Pubkey,
+        thread: &'a Thread,
+        fiber_cursor: u8,
+        fiber: &'a Fiber,
+        executor: Pubkey,
+    ) -> Self {
+        Self {
+            thread_pubkey,
+            thread,
+            fiber_cursor,
+            fiber,
+            executor,
+            forgo_commission: false,
+            program_id: antegen_thread_program::ID,
+        }
+    }
+
+    /// Waive the executor's commission
+    pub fn forgo_commission(mut self, forgo_commission: bool) -> Self {
+        self.forgo_commission = forgo_commission;
+        self
+    }
+
+    /// Thread program to call, for deployments at another address
+    pub fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    /// Address of the fiber being run
+    pub fn fiber_pubkey(&self) -> Pubkey {
+        self.thread
+            .fiber_at_index(&self.thread_pubkey, self.fiber_cursor)
+    }
+
+    /// The instruction, with the admin account taken from `config`
+    ///
+    /// Returns `None` for a cleared fiber, which has nothing to run.
+    pub fn build(&self, config: &ThreadConfig) -> Result<Option<Instruction>> {
+        let compiled_instruction = self.fiber.compiled_instruction();
+        if compiled_instruction.is_empty() {
+            return Ok(None);
+        }
+        let compiled = CompiledInstructionV0::deserialize(&mut &compiled_instruction[..])?;
+
+        let fiber_pubkey = self.fiber_pubkey();
+        let mut accounts = exec_base_accounts(
+            self.executor,
+            &self.thread_pubkey,
+            self.thread,
+            fiber_pubkey,
+            config.admin,
+        );
+        accounts.extend(compiled_remaining_accounts(self.executor, &compiled));
+
+        let mut ix = Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: ExecThread {
+                forgo_commission: self.forgo_commission,
+                fiber_cursor: self.fiber_cursor,
+            }
+            .data(),
+        };
+        request_compute_estimate(&mut ix, fiber_pubkey, self.fiber);
+        Ok(Some(ix))
+    }
+
+    /// The instruction, fetching the thread config for the admin account
+    pub async fn build_from_chain(&self, rpc: &RpcPool) -> Result<Option<Instruction>> {
+        let config_pubkey = ThreadConfig::pubkey();
+        let account = rpc
+            .get_account(&config_pubkey)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch thread config {}: {}", config_pubkey, e))?
+            .ok_or_else(|| anyhow!("Thread config {} not found", config_pubkey))?;
+        let data = account
+            .decode_data()
+            .map_err(|e| anyhow!("Failed to decode thread config: {}", e))?;
+        let config = ThreadConfig::try_deserialize(&mut data.as_slice())
+            .map_err(|e| anyhow!("Failed to deserialize thread config: {}", e))?;
+        self.build(&config)
+    }
+}
+
+/// `exec_thread` accounts ahead of the fiber's remaining accounts
+pub(crate) fn exec_base_accounts(
+    executor: Pubkey,
+    thread_pubkey: &Pubkey,
+    thread: &Thread,
+    fiber_pubkey: Pubkey,
+    admin: Pubkey,
+) -> Vec<AccountMeta> {
+    let has_nonce = thread.has_nonce_account();
+    ThreadExec {
+        executor,
+        thread: *thread_pubkey,
+        fiber: fiber_pubkey,
+        config: ThreadConfig::pubkey(),
+        admin,
+        nonce_account: if has_nonce {
+            Some(thread.nonce_account)
+        } else {
+            None
+        },
+        recent_blockhashes: if has_nonce {
+            Some(sysvar::recent_blockhashes::ID)
+        } else {
+            None
+        },
+        system_program: solana_system_interface::program::ID,
+    }
+    .to_account_metas(Some(false))
+}
+
+/// The fiber's compiled accounts as `exec_thread` remaining accounts, with
+/// the payer placeholder resolved to `executor`
+fn compiled_remaining_accounts(
+    executor: Pubkey,
+    compiled: &CompiledInstructionV0,
+) -> Vec<AccountMeta> {
+    debug!(
+        "Adding remaining accounts: {} accounts from compiled.accounts",
+        compiled.accounts.len()
+    );
+
+    let mut accounts = Vec::with_capacity(compiled.accounts.len());
+    for (account_index, pubkey) in compiled.accounts.iter().enumerate() {
+        // Replace PAYER_PUBKEY with executor
+        let actual_pubkey = if pubkey.eq(&PAYER_PUBKEY) {
+            executor
+        } else {
+            *pubkey
+        };
+
+        // Determine writability based on position in sorted accounts
+        let account_idx = account_index as u8;
+        let is_writable = if account_idx < compiled.num_rw_signers {
+            true // Read-write signer
+        } else if account_idx < compiled.num_rw_signers + compiled.num_ro_signers {
+            false // Read-only signer
+        } else if account_idx < compiled.num_rw_signers + compiled.num_ro_signers + compiled.num_rw
+        {
+            true // Read-write non-signer
+        } else {
+            false // Read-only non-signer
+        };
+
+        debug!(
+            "  remaining[{}]: {} (is_writable={})",
+            account_index, actual_pubkey, is_writable
+        );
+        accounts.push(AccountMeta {
+            pubkey: actual_pubkey,
+            is_signer: false, // CPI accounts don't need to be signers at transaction level
+            is_writable,
+        });
+    }
+    accounts
+}
+
+/// Until the fiber has a compute estimate, pass it writable with the Fiber
+/// Program so the exec records one on-chain
+fn request_compute_estimate(ix: &mut Instruction, fiber_pubkey: Pubkey, fiber: &Fiber) {
+    if fiber.is_legacy() || fiber.estimated_compute_units().is_some() {
+        return;
+    }
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == fiber_pubkey {
+            meta.is_writable = true;
+        }
+    }
+    ix.accounts.push(AccountMeta::new_readonly(
+        antegen_thread_program::fiber::ID,
+        false,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::worker::tests::new_thread;
+    use anchor_lang::{AccountSerialize, AnchorSerialize};
+    use antegen_thread_program::fiber::FiberVersionedState;
+    use antegen_thread_program::state::{compile_instruction, FeePayerMode};
+
+    /// `sha256("global:exec_thread")[..8]`
+    const EXEC_THREAD_DISCRIMINATOR: [u8; 8] = [7, 62, 96, 222, 111, 42, 101, 124];
+
+    fn config(admin: Pubkey) -> ThreadConfig {
+        ThreadConfig {
+            version: 1,
+            bump: 0,
+            admin,
+            paused: false,
+            commission_fee: 1000,
+            executor_fee_bps: 9000,
+            core_team_bps: 1000,
+            grace_period_seconds: 5,
+            fee_decay_seconds: 295,
+            waive_core_team_on_forgo: false,
+            fee_payer_mode: FeePayerMode::ThreadReimburses,
+            base_fee_lamports: 5000,
+            pending_admin: None,
+            max_chain_depth: 16,
+            max_fiber_failures: 0,
+            streak_half_life_execs: 1_000,
+        }
+    }
+
+    /// A fiber paying `target` from the payer placeholder
+    fn fiber(thread: Pubkey, target: Pubkey, estimated_compute_units: Option<u32>) -> Fiber {
+        let compiled = compile_instruction(Instruction {
+            program_id: solana_system_interface::program::ID,
+            accounts: vec![
+                AccountMeta::new(PAYER_PUBKEY, true),
+                AccountMeta::new(target, false),
+            ],
+            data: vec![2, 0, 0, 0],
+        })
+        .unwrap();
+        let mut compiled_instruction = Vec::new();
+        compiled.serialize(&mut compiled_instruction).unwrap();
+        Fiber::V1(FiberVersionedState {
+            version: 1,
+            thread,
+            compiled_instruction,
+            last_executed: 0,
+            exec_count: 0,
+            priority_fee: 0,
+            lookup_tables: Vec::new(),
+            estimated_compute_units,
+        })
+    }
+
+    #[test]
+    fn test_exec_thread_golden() {
+        let executor = Pubkey::new_from_array([1; 32]);
+        let thread_pubkey = Pubkey::new_from_array([2; 32]);
+        let target = Pubkey::new_from_array([3; 32]);
+        let admin = Pubkey::new_from_array([4; 32]);
+        let thread = new_thread(Pubkey::default());
+        let fiber = fiber(thread_pubkey, target, Some(20_000));
+        let fiber_pubkey = derive_fiber_pdas(&thread_pubkey, 1)[0];
+
+        let ix = ExecThreadBuilder::new(thread_pubkey, &thread, 0, &fiber, executor)
+            .forgo_commission(true)
+            .build(&config(admin))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(ix.program_id, antegen_thread_program::ID);
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(executor, true),
+                AccountMeta::new(thread_pubkey, false),
+                AccountMeta::new_readonly(fiber_pubkey, false),
+                AccountMeta::new_readonly(ThreadConfig::pubkey(), false),
+                AccountMeta::new(admin, false),
+                // No nonce account: Anchor's placeholders for the optional accounts
+                AccountMeta::new_readonly(antegen_thread_program::ID, false),
+                AccountMeta::new_readonly(antegen_thread_program::ID, false),
+                AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+                // The fiber's accounts, payer resolved to the executor
+                AccountMeta::new(executor, false),
+                AccountMeta::new(target, false),
+                AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            ]
+        );
+        let mut data = EXEC_THREAD_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1, 0]);
+        assert_eq!(ix.data, data);
+    }
+
+    #[test]
+    fn test_exec_thread_requests_compute_estimate() {
+        let executor = Pubkey::new_unique();
+        let thread_pubkey = Pubkey::new_unique();
+        let thread = new_thread(Pubkey::default());
+        let fiber = fiber(thread_pubkey, Pubkey::new_unique(), None);
+
+        let builder = ExecThreadBuilder::new(thread_pubkey, &thread, 0, &fiber, executor);
+        let ix = builder
+            .build(&config(Pubkey::new_unique()))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            ix.accounts[2],
+            AccountMeta::new(builder.fiber_pubkey(), false)
+        );
+        assert_eq!(
+            ix.accounts.last(),
+            Some(&AccountMeta::new_readonly(
+                antegen_thread_program::fiber::ID,
+                false
+            ))
+        );
+        assert_eq!(&ix.data[8..], &[0, 0]);
+    }
+
+    #[test]
+    fn test_cleared_fiber_builds_nothing() {
+        let thread_pubkey = Pubkey::new_unique();
+        let thread = new_thread(Pubkey::default());
+        let Fiber::V1(mut state) = fiber(thread_pubkey, Pubkey::new_unique(), None) else {
+            unreachable!()
+        };
+        state.compiled_instruction.clear();
+        let fiber = Fiber::V1(state);
+
+        let ix = ExecThreadBuilder::new(thread_pubkey, &thread, 0, &fiber, Pubkey::new_unique())
+            .build(&config(Pubkey::new_unique()))
+            .unwrap();
+        assert!(ix.is_none());
+    }
+
+    #[test]
+    fn test_decode_thread_and_fiber_pdas() {
+        let executor = Pubkey::new_unique();
+        let mut data = Vec::new();
+        new_thread(executor).try_serialize(&mut data).unwrap();
+
+        let thread = decode_thread(&data).unwrap();
+        assert_eq!(thread.name, "drain");
+        assert_eq!(thread.last_executor, executor);
+        assert!(decode_thread(&data[..8]).is_err());
+
+        let thread_pubkey = Pubkey::new_unique();
+        let pdas = derive_fiber_pdas(&thread_pubkey, 3);
+        assert_eq!(pdas.len(), 3);
+        for (index, pda) in pdas.iter().enumerate() {
+            assert_eq!(*pda, thread.fiber_at_index(&thread_pubkey, index as u8));
+        }
+    }
+}
@@ -0,0 +1,549 @@
+//! Worker lanes for the processor
+//!
+//! Account-triggered threads need extra RPC reads and run slower than
+//! time-based ones, so a burst of them could hold every worker while cheap
+//! timestamp threads wait past their deadline. With `[processor.lanes]` set,
+//! [`WorkerLanes`] gives each kind of trigger its own queue and worker limit:
+//!
+//! - the time lane runs timestamp, cron, interval, immediate, slot and epoch
+//!   triggers
+//! - the account lane runs account triggers
+//!
+//! With `borrow_idle`, a lane with work waiting may use the other lane's idle
+//! workers, but always leaves that lane one free worker so its own next
+//! thread starts right away. Without `[processor.lanes]` there is a single
+//! queue of `max_concurrent_threads` workers, as before.
+//!
+//! The queues together hold at most `processor.max_queued_threads` threads.
+//! When a push goes over the limit, the least overdue queued thread is shed,
+//! among equals the one furthest back in its lane's queue, so threads that
+//! have waited longest past their trigger are kept. A shed thread is not dropped for
+//! good: the processor hands it back to staging, which offers it again on a
+//! later clock tick.
+
+use crate::actors::messages::ReadyThread;
+use crate::config::{LanesConfig, ProcessorConfig};
+use crate::resources::CacheTriggerType;
+use solana_sdk::pubkey::Pubkey;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+/// Lane a ready thread is routed to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WorkerLane {
+    /// Time, slot and epoch triggers
+    #[default]
+    Time,
+    /// Account triggers
+    Account,
+}
+
+impl WorkerLane {
+    pub const ALL: [WorkerLane; 2] = [WorkerLane::Time, WorkerLane::Account];
+
+    /// Lane for a trigger type recorded in the cache
+    pub fn of(trigger_type: CacheTriggerType) -> Self {
+        match trigger_type {
+            CacheTriggerType::Account => WorkerLane::Account,
+            CacheTriggerType::Time { .. } | CacheTriggerType::Block | CacheTriggerType::Unknown => {
+                WorkerLane::Time
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkerLane::Time => "time",
+            WorkerLane::Account => "account",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn other(self) -> Self {
+        match self {
+            WorkerLane::Time => WorkerLane::Account,
+            WorkerLane::Account => WorkerLane::Time,
+        }
+    }
+}
+
+impl std::fmt::Display for WorkerLane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Snapshot of one lane, for `ProcessorStatus`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaneStatus {
+    pub lane: WorkerLane,
+    /// Worker limit of the lane
+    pub capacity: usize,
+    /// Workers of the lane in use, including any lent to the other lane
+    pub active: usize,
+    /// Threads waiting in the lane's queue
+    pub queued: usize,
+    /// Workers of the lane currently running the other lane's threads
+    pub lent: usize,
+    /// Times the lane filled up with threads still waiting
+    pub saturations: u64,
+    /// Threads of the lane that ran on a borrowed worker
+    pub borrowed: u64,
+}
+
+#[derive(Default)]
+struct Lane {
+    capacity: usize,
+    active: usize,
+    queue: VecDeque<ReadyThread>,
+    /// Whether the lane is currently full with work waiting
+    saturated: bool,
+    saturations: u64,
+    borrowed: u64,
+}
+
+/// Running thread: the lane it belongs to and the lane whose worker it holds
+#[derive(Clone, Copy)]
+struct Running {
+    owner: WorkerLane,
+    charged: WorkerLane,
+}
+
+/// Per-lane queues and worker accounting for `ProcessorFactory`
+pub struct WorkerLanes {
+    lanes: [Lane; 2],
+    /// Whether threads are routed by lane; if not, everything uses the time lane
+    split: bool,
+    borrow_idle: bool,
+    running: HashMap<Pubkey, Running>,
+    /// Most threads queued at once (0 = unbounded)
+    max_queued: usize,
+    /// Most threads ever queued at once
+    high_water: usize,
+    /// Threads shed because the queues were full
+    shed: u64,
+}
+
+impl WorkerLanes {
+    /// One shared queue of `max_concurrent` workers
+    pub fn shared(max_concurrent: usize) -> Self {
+        let mut lanes: [Lane; 2] = Default::default();
+        lanes[WorkerLane::Time.index()].capacity = max_concurrent;
+        Self {
+            lanes,
+            split: false,
+            borrow_idle: false,
+            running: HashMap::new(),
+            max_queued: 0,
+            high_water: 0,
+            shed: 0,
+        }
+    }
+
+    /// Separate time and account lanes
+    pub fn new(config: &LanesConfig) -> Self {
+        let mut lanes: [Lane; 2] = Default::default();
+        lanes[WorkerLane::Time.index()].capacity = config.time;
+        lanes[WorkerLane::Account.index()].capacity = config.account;
+        Self {
+            lanes,
+            split: true,
+            borrow_idle: config.borrow_idle,
+            running: HashMap::new(),
+            max_queued: 0,
+            high_water: 0,
+            shed: 0,
+        }
+    }
+
+    pub fn from_config(config: &ProcessorConfig) -> Self {
+        let lanes = match &config.lanes {
+            Some(lanes) => Self::new(lanes),
+            None => Self::shared(config.max_concurrent_threads),
+        };
+        lanes.with_max_queued(config.max_queued_threads)
+    }
+
+    /// Bound the threads queued across lanes (0 = unbounded)
+    pub fn with_max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// Total workers across lanes
+    pub fn capacity(&self) -> usize {
+        self.lanes.iter().map(|l| l.capacity).sum()
+    }
+
+    /// Workers not in use
+    pub fn available(&self) -> usize {
+        self.capacity() - self.running.len()
+    }
+
+    /// Threads waiting across lanes
+    pub fn queued(&self) -> usize {
+        self.lanes.iter().map(|l| l.queue.len()).sum()
+    }
+
+    /// Most threads ever queued at once
+    pub fn high_water(&self) -> usize {
+        self.high_water
+    }
+
+    /// Threads shed because the queues were full
+    pub fn shed(&self) -> u64 {
+        self.shed
+    }
+
+    /// Queue a ready thread in its lane
+    ///
+    /// Over the queue limit, returns the thread shed to make room, which may
+    /// be `ready` itself (see the module docs for which one goes).
+    pub fn push(&mut self, ready: ReadyThread) -> Option<ReadyThread> {
+        let lane = self.route(ready.lane);
+        self.lanes[lane.index()].queue.push_back(ready);
+
+        let queued = self.queued();
+        if queued > self.high_water {
+            self.high_water = queued;
+            crate::metrics::record_queue_high_water(queued);
+        }
+        if self.max_queued == 0 || queued <= self.max_queued {
+            return None;
+        }
+
+        // Least overdue first, then furthest back in its queue
+        let (lane, pos) = WorkerLane::ALL
+            .into_iter()
+            .flat_map(|lane| {
+                self.lanes[lane.index()]
+                    .queue
+                    .iter()
+                    .enumerate()
+                    .map(move |(pos, t)| (lane, pos, t.overdue_seconds))
+            })
+            .min_by_key(|&(_, pos, overdue)| (overdue, Reverse(pos)))
+            .map(|(lane, pos, _)| (lane, pos))?;
+        self.shed += 1;
+        crate::metrics::record_queue_shed(lane.as_str());
+        self.lanes[lane.index()].queue.remove(pos)
+    }
+
+    /// Drop a thread from the queues
+    pub fn remove(&mut self, thread_pubkey: &Pubkey) {
+        for lane in &mut self.lanes {
+            lane.queue.retain(|t| t.thread_pubkey != *thread_pubkey);
+        }
+    }
+
+    /// Take the next thread that can start now and claim a worker for it
+    ///
+    /// The time lane is served first. Threads already running stay queued
+    /// until their worker finishes. Every thread returned must be released
+    /// with [`Self::finish`].
+    pub fn next_ready(&mut self) -> Option<ReadyThread> {
+        for lane in WorkerLane::ALL {
+            let Some(pos) = self.next_runnable(lane) else {
+                self.lanes[lane.index()].saturated = false;
+                continue;
+            };
+
+            let Some(charged) = self.worker_for(lane) else {
+                let state = &mut self.lanes[lane.index()];
+                if !state.saturated {
+                    state.saturated = true;
+                    state.saturations += 1;
+                    crate::metrics::record_lane_saturation(lane.as_str());
+                    log::debug!(
+                        "{} lane saturated ({} workers busy, {} waiting)",
+                        lane,
+                        state.active,
+                        state.queue.len()
+                    );
+                }
+                continue;
+            };
+
+            let state = &mut self.lanes[lane.index()];
+            state.saturated = false;
+            if charged != lane {
+                state.borrowed += 1;
+            }
+            let ready = state.queue.remove(pos)?;
+            self.lanes[charged.index()].active += 1;
+            self.running.insert(
+                ready.thread_pubkey,
+                Running {
+                    owner: lane,
+                    charged,
+                },
+            );
+            return Some(ready);
+        }
+        None
+    }
+
+    /// Release the worker held by a thread
+    pub fn finish(&mut self, thread_pubkey: &Pubkey) {
+        if let Some(running) = self.running.remove(thread_pubkey) {
+            self.lanes[running.charged.index()].active -= 1;
+        }
+    }
+
+    /// Per-lane status, empty without `[processor.lanes]`
+    pub fn status(&self) -> Vec<LaneStatus> {
+        if !self.split {
+            return vec![];
+        }
+        WorkerLane::ALL
+            .into_iter()
+            .map(|lane| {
+                let state = &self.lanes[lane.index()];
+                LaneStatus {
+                    lane,
+                    capacity: state.capacity,
+                    active: state.active,
+                    queued: state.queue.len(),
+                    lent: self
+                        .running
+                        .values()
+                        .filter(|r| r.charged == lane && r.owner != lane)
+                        .count(),
+                    saturations: state.saturations,
+                    borrowed: state.borrowed,
+                }
+            })
+            .collect()
+    }
+
+    fn route(&self, lane: WorkerLane) -> WorkerLane {
+        if self.split {
+            lane
+        } else {
+            WorkerLane::Time
+        }
+    }
+
+    /// Position of the first queued thread of `lane` that isn't running
+    fn next_runnable(&self, lane: WorkerLane) -> Option<usize> {
+        self.lanes[lane.index()]
+            .queue
+            .iter()
+            .position(|t| !self.running.contains_key(&t.thread_pubkey))
+    }
+
+    /// Lane whose worker a thread of `lane` would use, if any is free
+    fn worker_for(&self, lane: WorkerLane) -> Option<WorkerLane> {
+        let own = &self.lanes[lane.index()];
+        if own.active < own.capacity {
+            return Some(lane);
+        }
+        if !(self.split && self.borrow_idle) {
+            return None;
+        }
+
+        // Borrow only from an idle lane, and leave it a free worker
+        let other = lane.other();
+        let lender = &self.lanes[other.index()];
+        (self.next_runnable(other).is_none() && lender.active + 1 < lender.capacity)
+            .then_some(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(lane: WorkerLane) -> ReadyThread {
+        ReadyThread {
+            thread_pubkey: Pubkey::new_unique(),
+            exec_count: 0,
+            is_overdue: false,
+            overdue_seconds: 0,
+            lane,
+        }
+    }
+
+    fn overdue(lane: WorkerLane, overdue_seconds: i64) -> ReadyThread {
+        ReadyThread {
+            is_overdue: overdue_seconds > 0,
+            overdue_seconds,
+            ..ready(lane)
+        }
+    }
+
+    fn lanes(time: usize, account: usize) -> WorkerLanes {
+        WorkerLanes::new(&LanesConfig {
+            time,
+            account,
+            borrow_idle: true,
+        })
+    }
+
+    /// Seconds a timestamp thread, queued behind `flood` account threads,
+    /// waits to start when every execution takes `exec_secs`
+    fn timestamp_start_delay(mut lanes: WorkerLanes, flood: usize, exec_secs: u64) -> u64 {
+        let mut now = 0;
+        let mut running: Vec<(u64, Pubkey)> = Vec::new();
+
+        // The flood takes every worker it can before the timestamp comes due
+        for _ in 0..flood {
+            lanes.push(ready(WorkerLane::Account));
+        }
+        while let Some(started) = lanes.next_ready() {
+            running.push((now + exec_secs, started.thread_pubkey));
+        }
+
+        let timestamp = ready(WorkerLane::Time);
+        let target = timestamp.thread_pubkey;
+        lanes.push(timestamp);
+        loop {
+            while let Some(started) = lanes.next_ready() {
+                if started.thread_pubkey == target {
+                    return now;
+                }
+                running.push((now + exec_secs, started.thread_pubkey));
+            }
+            // Advance to the next completion
+            running.sort();
+            let (at, pubkey) = running.remove(0);
+            now = at;
+            lanes.finish(&pubkey);
+        }
+    }
+
+    #[test]
+    fn test_account_flood_does_not_delay_timestamp_trigger() {
+        // Timestamp threads should start within the 1s grace of their trigger
+        const DEADLINE_SECS: u64 = 1;
+
+        // One shared queue: the timestamp thread waits behind the whole flood
+        let shared = timestamp_start_delay(WorkerLanes::shared(6), 100, 10);
+        assert!(shared > DEADLINE_SECS);
+
+        // Separate lanes: it starts immediately, even with borrowing on
+        let split = timestamp_start_delay(lanes(2, 4), 100, 10);
+        assert!(split <= DEADLINE_SECS);
+    }
+
+    #[test]
+    fn test_borrow_idle_leaves_lender_a_worker() {
+        let mut lanes = lanes(3, 2);
+        for _ in 0..10 {
+            lanes.push(ready(WorkerLane::Account));
+        }
+
+        // 2 own workers plus 2 of the idle time lane's 3
+        let started: Vec<_> = std::iter::from_fn(|| lanes.next_ready()).collect();
+        assert_eq!(started.len(), 4);
+
+        let status = lanes.status();
+        assert_eq!(status[0].lent, 2);
+        assert_eq!(status[0].active, 2);
+        assert_eq!(status[1].borrowed, 2);
+        assert_eq!(status[1].saturations, 1);
+        assert_eq!(status[1].queued, 6);
+
+        // The spare time worker goes to a timestamp thread
+        lanes.push(ready(WorkerLane::Time));
+        assert!(lanes.next_ready().is_some());
+        assert_eq!(lanes.available(), 0);
+
+        // Releasing a borrowed worker hands it back to the time lane
+        lanes.finish(&started[3].thread_pubkey);
+        assert_eq!(lanes.status()[0].active, 2);
+    }
+
+    #[test]
+    fn test_no_borrowing_while_lender_has_work() {
+        let mut lanes = lanes(2, 1);
+        lanes.push(ready(WorkerLane::Account));
+        lanes.push(ready(WorkerLane::Account));
+        lanes.push(ready(WorkerLane::Time));
+        lanes.push(ready(WorkerLane::Time));
+        lanes.push(ready(WorkerLane::Time));
+
+        let started: Vec<_> = std::iter::from_fn(|| lanes.next_ready()).collect();
+        assert_eq!(started.len(), 3);
+        let status = lanes.status();
+        assert_eq!((status[0].queued, status[1].queued), (1, 1));
+        assert_eq!(status[1].borrowed, 0);
+    }
+
+    #[test]
+    fn test_shared_mode_ignores_lanes() {
+        let mut lanes = WorkerLanes::shared(2);
+        let first = ready(WorkerLane::Account);
+        let duplicate = ReadyThread {
+            lane: WorkerLane::Time,
+            ..first.clone()
+        };
+        lanes.push(first.clone());
+        lanes.push(duplicate);
+        lanes.push(ready(WorkerLane::Time));
+
+        // The second entry for a running thread waits for it to finish
+        assert_eq!(
+            lanes.next_ready().unwrap().thread_pubkey,
+            first.thread_pubkey
+        );
+        assert_ne!(
+            lanes.next_ready().unwrap().thread_pubkey,
+            first.thread_pubkey
+        );
+        assert!(lanes.next_ready().is_none());
+        assert!(lanes.status().is_empty());
+
+        lanes.finish(&first.thread_pubkey);
+        assert_eq!(
+            lanes.next_ready().unwrap().thread_pubkey,
+            first.thread_pubkey
+        );
+
+        lanes.remove(&first.thread_pubkey);
+        assert_eq!(lanes.queued(), 0);
+    }
+
+    #[test]
+    fn test_queue_limit_sheds_least_overdue() {
+        let mut lanes = lanes(1, 1).with_max_queued(3);
+        let oldest = overdue(WorkerLane::Time, 30);
+        let recent = overdue(WorkerLane::Account, 2);
+        let also_recent = overdue(WorkerLane::Time, 2);
+        assert!(lanes.push(oldest.clone()).is_none());
+        assert!(lanes.push(recent.clone()).is_none());
+        assert!(lanes.push(also_recent.clone()).is_none());
+
+        // Of the two least overdue, the one further back in its queue goes
+        let shed = lanes.push(overdue(WorkerLane::Account, 10)).unwrap();
+        assert_eq!(shed.thread_pubkey, also_recent.thread_pubkey);
+        assert_eq!(lanes.queued(), 3);
+
+        // A thread less overdue than everything queued is shed itself
+        let newest = ready(WorkerLane::Time);
+        let shed = lanes.push(newest.clone()).unwrap();
+        assert_eq!(shed.thread_pubkey, newest.thread_pubkey);
+
+        assert_eq!(lanes.shed(), 2);
+        assert_eq!(lanes.high_water(), 4);
+        let started: Vec<_> = std::iter::from_fn(|| lanes.next_ready())
+            .map(|t| t.thread_pubkey)
+            .collect();
+        assert_eq!(started.len(), 2);
+        assert_eq!(started, vec![oldest.thread_pubkey, recent.thread_pubkey]);
+    }
+
+    #[test]
+    fn test_unbounded_queue_never_sheds() {
+        let mut lanes = WorkerLanes::shared(1);
+        for _ in 0..1000 {
+            assert!(lanes.push(ready(WorkerLane::Time)).is_none());
+        }
+        assert_eq!(lanes.queued(), 1000);
+        assert_eq!(lanes.high_water(), 1000);
+        assert_eq!(lanes.shed(), 0);
+    }
+}
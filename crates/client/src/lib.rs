@@ -16,29 +16,54 @@
 //! let handle = PluginHandle::spawn(config).await?;
 //! // Later, in Geyser callbacks:
 //! handle.try_send_update(account_update)?;
+//! handle.try_send_update_with_priority(clock_update, PriorityLevel::Critical)?;
 //! ```
 
 pub mod actors;
+pub mod alerts;
+pub mod batch;
+pub mod cadence;
+pub mod concurrency;
 pub mod config;
+pub mod config_migrate;
+pub mod control;
+pub mod costs;
 pub mod datasources;
+pub mod decoders;
 pub mod executor;
+pub mod fee_payers;
+pub mod fees;
+pub mod helpers;
+pub mod lanes;
 pub mod load_balancer;
+pub mod logging;
+pub mod metrics;
 pub mod resources;
+pub mod results;
 pub mod rpc;
+pub mod thread_filter;
 pub mod tpu;
+pub mod trigger_str;
+pub mod tx_builder;
+pub mod tx_log;
 pub mod types;
 
 // Re-exports
 pub use config::ClientConfig;
+pub use datasources::PriorityLevel;
 pub use executor::ExecutorLogic;
 pub use load_balancer::{LoadBalancer, LoadBalancerConfig, LoadBalancerStats, ProcessDecision};
 pub use resources::{AccountCache, CachedAccount, SharedResources};
 pub use rpc::RpcPool;
+pub use thread_filter::ThreadFilter;
 pub use tpu::{TpuClient, TpuClientConfig};
-pub use types::{AccountUpdate, DurableTransactionMessage, ProcessorMessage, TransactionMessage};
+pub use tx_builder::{TransactionBuilder, TxStrategy};
+pub use types::{
+    AccountUpdate, DurableTransactionMessage, PluginEvent, ProcessorMessage, TransactionMessage,
+};
 
 use anyhow::Result;
-use tokio::sync::mpsc;
+use datasources::{priority_channel, PrioritySender};
 
 /// Run the client in standalone mode (blocking)
 ///
@@ -69,11 +94,29 @@ pub async fn run_standalone(config: ClientConfig) -> Result<()> {
     let (resources, eviction_rx) = SharedResources::new(&config).await?;
     log::debug!("Created shared resources (RPC pool, unified cache, TPU client)");
 
-    // Spawn RootSupervisor (no geyser channel in standalone mode)
+    // Receive the Geyser stream over a Unix socket if configured
+    let geyser_rx = match &config.datasources.geyser_socket {
+        #[cfg(unix)]
+        Some(path) => {
+            let (tx, rx) = priority_channel(1000);
+            let path = std::path::PathBuf::from(path);
+            tokio::spawn(async move {
+                if let Err(e) = datasources::socket::listen(&path, tx).await {
+                    log::error!("Geyser socket listener failed: {}", e);
+                }
+            });
+            Some(rx)
+        }
+        #[cfg(not(unix))]
+        Some(_) => anyhow::bail!("datasources.geyser_socket requires a Unix platform"),
+        None => None,
+    };
+
+    // Spawn RootSupervisor (geyser channel only when listening on a socket)
     let (_root_ref, root_handle) = ractor::Actor::spawn(
         Some("root-supervisor".to_string()),
         actors::RootSupervisor,
-        (config, resources, None, eviction_rx),
+        (config, resources, geyser_rx, eviction_rx),
     )
     .await
     .map_err(|e| anyhow::anyhow!("Failed to spawn RootSupervisor: {}", e))?;
@@ -91,12 +134,38 @@ pub async fn run_standalone(config: ClientConfig) -> Result<()> {
     }
 }
 
+/// Replace the thread filter of the running client
+///
+/// Takes effect without a restart: threads the new filter excludes are
+/// dropped from scheduling and newly included ones are picked up from the
+/// cache. Fails if no client is running in this process.
+pub fn set_thread_filter(filter: ThreadFilter) -> Result<()> {
+    let staging: ractor::ActorRef<actors::messages::StagingMessage> =
+        ractor::registry::where_is("staging-actor".to_string())
+            .ok_or_else(|| anyhow::anyhow!("Client is not running"))?
+            .into();
+    staging
+        .send_message(actors::messages::StagingMessage::SetThreadFilter(filter))
+        .map_err(|e| anyhow::anyhow!("Failed to send thread filter: {}", e))
+}
+
+/// Re-read the config file at `path` and apply the settings that take
+/// effect without a restart
+///
+/// Only `processor.thread_filter` is reloadable today. Returns the names of
+/// the applied settings. Fails if no client is running in this process.
+pub fn reload_config(path: &std::path::Path) -> Result<Vec<&'static str>> {
+    let config = ClientConfig::load(path)?;
+    set_thread_filter(ThreadFilter::new(&config.processor.thread_filter)?)?;
+    Ok(vec!["processor.thread_filter"])
+}
+
 /// Handle for plugin mode
 ///
 /// This provides a way for the Geyser plugin to send account updates
 /// to the client without blocking the validator.
 pub struct PluginHandle {
-    account_sender: mpsc::Sender<AccountUpdate>,
+    account_sender: PrioritySender,
     // Root supervisor runs in background, handle is not stored but actor tree remains alive
 }
 
@@ -122,7 +191,7 @@ impl PluginHandle {
         );
 
         // Create channel for plugin -> processor communication
-        let (tx, rx) = mpsc::channel(1000);
+        let (tx, rx) = priority_channel(1000);
 
         // Create shared resources (async for TPU client initialization)
         let (resources, eviction_rx) = SharedResources::new(&config).await?;
@@ -155,10 +224,53 @@ impl PluginHandle {
     /// Returns an error if the channel is full or closed.
     /// The Geyser plugin should call this from `update_account()` callbacks.
     pub fn try_send_update(&self, update: AccountUpdate) -> Result<()> {
+        self.try_send_update_with_priority(update, PriorityLevel::Normal)
+    }
+
+    /// Send an account update at `priority` (non-blocking)
+    ///
+    /// Critical updates (the clock) are received ahead of any queued normal
+    /// ones and don't compete with them for channel space.
+    pub fn try_send_update_with_priority(
+        &self,
+        update: AccountUpdate,
+        priority: PriorityLevel,
+    ) -> Result<()> {
         self.account_sender
-            .try_send(update)
+            .try_send(
+                PluginEvent::AccountUpdate {
+                    update,
+                    is_startup: false,
+                },
+                priority,
+            )
             .map_err(|e| anyhow::anyhow!("Failed to send account update: {}", e))
     }
+
+    /// Send a startup snapshot account update (blocking)
+    ///
+    /// Snapshot accounts are bulk-loaded into the cache and only evaluated
+    /// once `notify_end_of_startup()` is called. Blocks while the channel is
+    /// full so no snapshot account is dropped; must not be called from within
+    /// an async context.
+    pub fn send_startup_update(&self, update: AccountUpdate) -> Result<()> {
+        self.account_sender
+            .blocking_send(
+                PluginEvent::AccountUpdate {
+                    update,
+                    is_startup: true,
+                },
+                PriorityLevel::Normal,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to send startup account update: {}", e))
+    }
+
+    /// Signal that the validator finished streaming startup snapshot accounts (blocking)
+    pub fn notify_end_of_startup(&self) -> Result<()> {
+        self.account_sender
+            .blocking_send(PluginEvent::EndOfStartup, PriorityLevel::Normal)
+            .map_err(|e| anyhow::anyhow!("Failed to send end of startup: {}", e))
+    }
 }
 
 #[cfg(test)]
@@ -207,4 +319,16 @@ mod tests {
             assert!(handle.try_send_update(update).is_ok());
         }
     }
+
+    #[tokio::test]
+    async fn test_plugin_handle_send_update_with_priority() {
+        let config = ClientConfig::default();
+        // Spawn may fail if keypair file doesn't exist, which is OK for this test
+        if let Ok(handle) = PluginHandle::spawn(config).await {
+            let clock = AccountUpdate::new_clock(100, 0);
+            assert!(handle
+                .try_send_update_with_priority(clock, PriorityLevel::Critical)
+                .is_ok());
+        }
+    }
 }
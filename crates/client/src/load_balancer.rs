@@ -4,14 +4,21 @@
 //! Threads are "owned" when an executor successfully executes them.
 //! Ownership is released after consecutive losses to other executors.
 //! This prevents duplicate work while allowing takeover of abandoned threads.
+//!
+//! It also tracks in-flight workers, and signals back-pressure to staging
+//! while the worker pool is saturated.
 
+use crate::actors::messages::{StagingMessage, WorkerMessage};
 use anyhow::Result;
 use log::{debug, info};
+use ractor::ActorRef;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 
 /// Configuration for the load balancer
 #[derive(Clone, Debug)]
@@ -24,6 +31,10 @@ pub struct LoadBalancerConfig {
     pub thread_takeover_delay: i64,
     /// Delay before claiming new threads (seconds)
     pub thread_process_delay: u64,
+    /// Worker saturation above which staging stops processing account updates
+    pub backpressure_threshold: f32,
+    /// Worker saturation below which staging resumes
+    pub backpressure_release_threshold: f32,
 }
 
 impl Default for LoadBalancerConfig {
@@ -33,6 +44,8 @@ impl Default for LoadBalancerConfig {
             capacity_threshold: 5,
             thread_takeover_delay: 10,
             thread_process_delay: 0,
+            backpressure_threshold: 0.9,
+            backpressure_release_threshold: 0.5,
         }
     }
 }
@@ -63,12 +76,26 @@ pub enum ProcessDecision {
 pub struct LoadBalancer {
     /// Our executor's public key
     pub executor_pubkey: Pubkey,
+    /// Our other fee payers, which sign as executor in rotation
+    pub fee_payers: Vec<Pubkey>,
     /// Configuration
     pub config: LoadBalancerConfig,
     /// Tracking data for each thread
     tracking: Arc<RwLock<HashMap<Pubkey, ThreadTracking>>>,
     /// Whether we're currently at capacity
     at_capacity: Arc<RwLock<bool>>,
+    /// Workers with an execution in flight, by thread
+    workers: Arc<RwLock<HashMap<Pubkey, ActorRef<WorkerMessage>>>>,
+    /// Set once shutdown starts; no new workers are spawned after that
+    draining: Arc<AtomicBool>,
+    /// Total workers across all lanes; 0 disables back-pressure
+    worker_capacity: usize,
+    /// Whether staging has been told to hold account updates
+    backpressure: AtomicBool,
+    /// Times back-pressure has engaged
+    backpressure_events: AtomicU64,
+    /// Receives `BackpressureStart` / `BackpressureStop`
+    backpressure_target: OnceLock<ActorRef<StagingMessage>>,
 }
 
 impl LoadBalancer {
@@ -76,9 +103,148 @@ impl LoadBalancer {
     pub fn new(executor_pubkey: Pubkey, config: LoadBalancerConfig) -> Self {
         Self {
             executor_pubkey,
+            fee_payers: Vec::new(),
             config,
             tracking: Arc::new(RwLock::new(HashMap::new())),
             at_capacity: Arc::new(RwLock::new(false)),
+            workers: Arc::new(RwLock::new(HashMap::new())),
+            draining: Arc::new(AtomicBool::new(false)),
+            worker_capacity: 0,
+            backpressure: AtomicBool::new(false),
+            backpressure_events: AtomicU64::new(0),
+            backpressure_target: OnceLock::new(),
+        }
+    }
+
+    /// Size of the worker pool, for [`Self::worker_saturation`]
+    pub fn with_worker_capacity(mut self, capacity: usize) -> Self {
+        self.worker_capacity = capacity;
+        self
+    }
+
+    /// Send back-pressure transitions to `staging`
+    pub fn set_backpressure_target(&self, staging: ActorRef<StagingMessage>) {
+        let _ = self.backpressure_target.set(staging);
+    }
+
+    /// Count executions signed by any of `fee_payers` as ours
+    pub fn with_fee_payers(mut self, fee_payers: Vec<Pubkey>) -> Self {
+        self.fee_payers = fee_payers;
+        self
+    }
+
+    /// Whether `pubkey` is our executor or one of our fee payers
+    pub fn is_ours(&self, pubkey: &Pubkey) -> bool {
+        *pubkey == self.executor_pubkey || self.fee_payers.contains(pubkey)
+    }
+
+    /// Track a worker so [`Self::drain`] waits for its execution
+    pub async fn register_worker(&self, thread_pubkey: Pubkey, worker: ActorRef<WorkerMessage>) {
+        let mut workers = self.workers.write().await;
+        workers.insert(thread_pubkey, worker);
+        self.update_backpressure(workers.len());
+    }
+
+    /// Stop tracking a worker, unless a newer one has taken its place
+    pub async fn unregister_worker(
+        &self,
+        thread_pubkey: &Pubkey,
+        worker: &ActorRef<WorkerMessage>,
+    ) {
+        let mut workers = self.workers.write().await;
+        if workers
+            .get(thread_pubkey)
+            .is_some_and(|w| w.get_id() == worker.get_id())
+        {
+            workers.remove(thread_pubkey);
+            self.update_backpressure(workers.len());
+        }
+    }
+
+    /// Fraction of the worker pool busy executing, from 0.0 to 1.0
+    pub async fn worker_saturation(&self) -> f32 {
+        self.saturation(self.workers.read().await.len())
+    }
+
+    /// Whether staging is holding account updates for workers to free up
+    pub fn is_backpressured(&self) -> bool {
+        self.backpressure.load(Ordering::Relaxed)
+    }
+
+    fn saturation(&self, busy: usize) -> f32 {
+        if self.worker_capacity == 0 {
+            return 0.0;
+        }
+        (busy as f32 / self.worker_capacity as f32).min(1.0)
+    }
+
+    /// Engage or release back-pressure for `busy` workers
+    ///
+    /// Engages above `backpressure_threshold` and releases only below
+    /// `backpressure_release_threshold`, so staging doesn't flap while the
+    /// pool hovers near full. Callers hold the workers lock, which orders
+    /// the transitions.
+    fn update_backpressure(&self, busy: usize) {
+        let saturation = self.saturation(busy);
+        let engaged = self.backpressure.load(Ordering::Relaxed);
+        let message = if !engaged && saturation > self.config.backpressure_threshold {
+            self.backpressure_events.fetch_add(1, Ordering::Relaxed);
+            info!(
+                "Worker pool saturated ({}/{}), holding account updates",
+                busy, self.worker_capacity
+            );
+            StagingMessage::BackpressureStart
+        } else if engaged && saturation < self.config.backpressure_release_threshold {
+            info!(
+                "Worker pool below release threshold ({}/{}), resuming account updates",
+                busy, self.worker_capacity
+            );
+            StagingMessage::BackpressureStop
+        } else {
+            return;
+        };
+        self.backpressure.store(!engaged, Ordering::Relaxed);
+        if let Some(staging) = self.backpressure_target.get() {
+            let _ = staging.send_message(message);
+        }
+    }
+
+    /// Workers currently executing
+    pub async fn workers(&self) -> Vec<ActorRef<WorkerMessage>> {
+        self.workers.read().await.values().cloned().collect()
+    }
+
+    /// Whether shutdown has started draining workers
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// Wait for every in-flight worker to finish its current execution
+    ///
+    /// From the call on, [`Self::is_draining`] is true and no new workers are
+    /// spawned. Each tracked worker gets a `Drain` message and the future
+    /// resolves once all have replied or stopped.
+    pub fn drain(&self) -> impl Future<Output = ()> + '_ {
+        self.draining.store(true, Ordering::Relaxed);
+        async move {
+            let workers: Vec<_> = self
+                .workers
+                .read()
+                .await
+                .iter()
+                .map(|(thread, worker)| (*thread, worker.clone()))
+                .collect();
+            info!("Draining {} in-flight workers", workers.len());
+
+            futures::future::join_all(workers.into_iter().map(|(thread, worker)| async move {
+                let (tx, rx) = oneshot::channel();
+                // A worker that already stopped has nothing left to finish
+                if worker.send_message(WorkerMessage::Drain(tx)).is_ok() {
+                    let _ = rx.await;
+                }
+                debug!("Worker for thread {} drained", thread);
+            }))
+            .await;
         }
     }
 
@@ -104,7 +270,7 @@ impl LoadBalancer {
         let thread_track = tracking.entry(*thread_pubkey).or_default();
 
         // Check if we're the last executor
-        let we_executed_last = last_executor.eq(&self.executor_pubkey);
+        let we_executed_last = self.is_ours(last_executor);
 
         // Update ownership based on last executor
         if we_executed_last {
@@ -267,6 +433,7 @@ impl LoadBalancer {
                 .filter(|t| t.consecutive_losses > 0)
                 .count(),
             at_capacity,
+            backpressure_events: self.backpressure_events.load(Ordering::Relaxed),
         }
     }
 
@@ -305,6 +472,8 @@ pub struct LoadBalancerStats {
     pub owned_threads: usize,
     pub threads_with_losses: usize,
     pub at_capacity: bool,
+    /// Times staging was told to hold account updates
+    pub backpressure_events: u64,
 }
 
 #[cfg(test)]
@@ -317,6 +486,8 @@ mod tests {
             capacity_threshold: 3,
             thread_takeover_delay: 5,
             thread_process_delay: 0,
+            backpressure_threshold: 0.9,
+            backpressure_release_threshold: 0.5,
         }
     }
 
@@ -362,6 +533,8 @@ mod tests {
             capacity_threshold: 2,
             thread_takeover_delay: 5,
             thread_process_delay: 0,
+            backpressure_threshold: 0.9,
+            backpressure_release_threshold: 0.5,
         };
         let lb = LoadBalancer::new(executor, config);
         let thread = Pubkey::new_unique();
@@ -412,6 +585,23 @@ mod tests {
         assert_eq!(decision, ProcessDecision::Process);
     }
 
+    #[tokio::test]
+    async fn test_fee_payer_executions_count_as_ours() {
+        let executor = Pubkey::new_unique();
+        let fee_payer = Pubkey::new_unique();
+        let lb = LoadBalancer::new(executor, test_config()).with_fee_payers(vec![fee_payer]);
+        let thread = Pubkey::new_unique();
+
+        // Executed last by one of our fee payers, not overdue - still ours
+        let decision = lb
+            .should_process(&thread, &fee_payer, false, 0)
+            .await
+            .unwrap();
+        assert_eq!(decision, ProcessDecision::Process);
+        assert!(lb.is_ours(&executor));
+        assert!(!lb.is_ours(&Pubkey::new_unique()));
+    }
+
     #[tokio::test]
     async fn test_disabled_always_processes() {
         let executor = Pubkey::new_unique();
@@ -440,4 +630,122 @@ mod tests {
         let lb = LoadBalancer::new(Pubkey::new_unique(), config);
         assert_eq!(lb.thread_process_delay(), Duration::from_secs(2));
     }
+
+    /// Stand-in for a worker; only its ref is tracked
+    struct IdleWorker;
+
+    impl ractor::Actor for IdleWorker {
+        type Msg = WorkerMessage;
+        type State = ();
+        type Arguments = ();
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            _args: (),
+        ) -> std::result::Result<(), ractor::ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    /// Stand-in for StagingActor forwarding back-pressure transitions
+    struct RecordingStaging;
+
+    impl ractor::Actor for RecordingStaging {
+        type Msg = StagingMessage;
+        type State = tokio::sync::mpsc::UnboundedSender<bool>;
+        type Arguments = tokio::sync::mpsc::UnboundedSender<bool>;
+
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            tx: Self::Arguments,
+        ) -> std::result::Result<Self::State, ractor::ActorProcessingErr> {
+            Ok(tx)
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            tx: &mut Self::State,
+        ) -> std::result::Result<(), ractor::ActorProcessingErr> {
+            match message {
+                StagingMessage::BackpressureStart => {
+                    let _ = tx.send(true);
+                }
+                StagingMessage::BackpressureStop => {
+                    let _ = tx.send(false);
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    async fn workers(count: usize) -> Vec<(Pubkey, ActorRef<WorkerMessage>)> {
+        let mut workers = Vec::new();
+        for _ in 0..count {
+            let (worker, _) = ractor::Actor::spawn(None, IdleWorker, ()).await.unwrap();
+            workers.push((Pubkey::new_unique(), worker));
+        }
+        workers
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_follows_worker_saturation() {
+        let lb = LoadBalancer::new(Pubkey::new_unique(), test_config()).with_worker_capacity(10);
+        let (tx, mut transitions) = tokio::sync::mpsc::unbounded_channel();
+        let (staging, _) = ractor::Actor::spawn(None, RecordingStaging, tx)
+            .await
+            .unwrap();
+        lb.set_backpressure_target(staging.clone());
+
+        // Fill the pool: 90% is not above the threshold, 100% is
+        let pool = workers(10).await;
+        for (thread, worker) in &pool[..9] {
+            lb.register_worker(*thread, worker.clone()).await;
+        }
+        assert_eq!(lb.worker_saturation().await, 0.9);
+        assert!(!lb.is_backpressured());
+        let (thread, worker) = &pool[9];
+        lb.register_worker(*thread, worker.clone()).await;
+        assert_eq!(lb.worker_saturation().await, 1.0);
+        assert!(lb.is_backpressured());
+        assert_eq!(transitions.recv().await, Some(true));
+
+        // Draining to half full holds back-pressure; below it releases
+        for (thread, worker) in &pool[..5] {
+            lb.unregister_worker(thread, worker).await;
+        }
+        assert_eq!(lb.worker_saturation().await, 0.5);
+        assert!(lb.is_backpressured());
+        lb.unregister_worker(&pool[5].0, &pool[5].1).await;
+        assert!(!lb.is_backpressured());
+        assert_eq!(transitions.recv().await, Some(false));
+
+        // Saturating again is a second event
+        for (thread, worker) in &pool[..6] {
+            lb.register_worker(*thread, worker.clone()).await;
+        }
+        assert_eq!(transitions.recv().await, Some(true));
+        assert_eq!(lb.get_stats().await.backpressure_events, 2);
+
+        for (_, worker) in pool {
+            worker.stop(None);
+        }
+        staging.stop(None);
+    }
+
+    #[tokio::test]
+    async fn test_no_backpressure_without_worker_capacity() {
+        let lb = LoadBalancer::new(Pubkey::new_unique(), test_config());
+        for (thread, worker) in workers(4).await {
+            lb.register_worker(thread, worker.clone()).await;
+            worker.stop(None);
+        }
+        assert_eq!(lb.worker_saturation().await, 0.0);
+        assert!(!lb.is_backpressured());
+        assert_eq!(lb.get_stats().await.backpressure_events, 0);
+    }
 }
@@ -0,0 +1,235 @@
+//! Per-actor log filtering and log line formats
+//!
+//! Some actors log on every account update or slot, which drowns out the rest
+//! at `debug`. `observability.actor_log_levels` sets a level per actor
+//! (`staging_actor = "warn"`, `worker_actor = "debug"`), enforced by
+//! [`ActorLogFilter`] wrapping the node's logger. Actors not listed follow the
+//! global level.
+
+use crate::config::LogFormat;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::HashMap;
+
+/// Actor names accepted in `actor_log_levels`, with the module they log from
+pub const ACTOR_MODULES: &[(&str, &str)] = &[
+    ("root_actor", "antegen_client::actors::root"),
+    ("staging_actor", "antegen_client::actors::staging"),
+    ("processor_actor", "antegen_client::actors::processor"),
+    ("worker_actor", "antegen_client::actors::worker"),
+    ("datasource_actor", "antegen_client::actors::datasource"),
+    (
+        "observability_actor",
+        "antegen_client::actors::observability",
+    ),
+];
+
+/// Module path for an `actor_log_levels` key
+///
+/// Keys are actor names from [`ACTOR_MODULES`] or module paths
+/// (`antegen_client::rpc`), which are used as-is.
+pub fn actor_module(key: &str) -> Option<&str> {
+    if key.contains("::") {
+        return Some(key);
+    }
+    ACTOR_MODULES
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, module)| *module)
+}
+
+/// Logger wrapper applying per-actor levels
+///
+/// Records from a listed module are let through to the inner logger only at
+/// or above that module's level; everything else is up to the inner logger.
+/// The inner logger must itself allow whatever an actor level raises above
+/// the global level.
+pub struct ActorLogFilter {
+    inner: Box<dyn Log>,
+    /// (module prefix, level), longest prefix first
+    filters: Vec<(String, LevelFilter)>,
+}
+
+impl ActorLogFilter {
+    pub fn new(inner: Box<dyn Log>, actor_log_levels: &HashMap<String, LevelFilter>) -> Self {
+        let mut filters: Vec<(String, LevelFilter)> = actor_log_levels
+            .iter()
+            .filter_map(|(key, level)| actor_module(key).map(|m| (m.to_string(), *level)))
+            .collect();
+        filters.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        Self { inner, filters }
+    }
+
+    /// Module/level pairs, for configuring the inner logger
+    pub fn filters(&self) -> &[(String, LevelFilter)] {
+        &self.filters
+    }
+
+    /// Highest level any actor filter allows
+    pub fn max_level(&self) -> LevelFilter {
+        self.filters
+            .iter()
+            .map(|(_, level)| *level)
+            .max()
+            .unwrap_or(LevelFilter::Off)
+    }
+
+    /// Level for a target, if it falls under an actor filter
+    fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        self.filters
+            .iter()
+            .find(|(module, _)| {
+                target
+                    .strip_prefix(module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+    }
+}
+
+impl Log for ActorLogFilter {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match self.level_for(metadata.target()) {
+            Some(level) => metadata.level() <= level,
+            None => self.inner.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        let target = record.module_path().unwrap_or(record.target());
+        match self.level_for(target) {
+            Some(level) if record.level() > level => {}
+            _ => self.inner.log(record),
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush()
+    }
+}
+
+/// Render a record as a log line, or `None` for [`LogFormat::Plain`], which
+/// keeps the logger's default format
+pub fn format_record(format: LogFormat, record: &Record) -> Option<String> {
+    let now = chrono::Utc::now();
+    match format {
+        LogFormat::Plain => None,
+        LogFormat::Compact => Some(format!(
+            "{} {:<5} {}",
+            now.format("%H:%M:%S%.3f"),
+            record.level(),
+            record.args()
+        )),
+        LogFormat::Json => Some(
+            serde_json::json!({
+                "ts": now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                "level": record.level().as_str(),
+                "target": record.target(),
+                "msg": record.args().to_string(),
+            })
+            .to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    /// Inner logger that accepts everything and keeps the messages
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Log for Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.0.lock().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn emit(logger: &dyn Log, module: &str, level: Level, msg: &str) {
+        logger.log(
+            &Record::builder()
+                .args(format_args!("{}", msg))
+                .level(level)
+                .target(module)
+                .module_path(Some(module))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_actor_levels_suppress_messages() {
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let levels = HashMap::from([
+            ("staging_actor".to_string(), LevelFilter::Warn),
+            ("worker_actor".to_string(), LevelFilter::Debug),
+        ]);
+        let logger = ActorLogFilter::new(Box::new(Capture(captured.clone())), &levels);
+
+        let staging = "antegen_client::actors::staging";
+        let worker = "antegen_client::actors::worker";
+        emit(&logger, staging, Level::Info, "staging info");
+        emit(&logger, staging, Level::Warn, "staging warn");
+        emit(&logger, worker, Level::Debug, "worker debug");
+        emit(&logger, worker, Level::Trace, "worker trace");
+        emit(
+            &logger,
+            "antegen_client::rpc::pool",
+            Level::Trace,
+            "rpc trace",
+        );
+
+        assert_eq!(
+            *captured.lock(),
+            vec!["staging warn", "worker debug", "rpc trace"]
+        );
+        assert_eq!(logger.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_module_prefix_matching() {
+        let levels = HashMap::from([
+            ("antegen_client::rpc".to_string(), LevelFilter::Off),
+            ("antegen_client::rpc::pool".to_string(), LevelFilter::Info),
+        ]);
+        let logger = ActorLogFilter::new(Box::new(Capture(Default::default())), &levels);
+
+        // Longest prefix wins, and prefixes only match whole path segments
+        assert_eq!(
+            logger.level_for("antegen_client::rpc::pool"),
+            Some(LevelFilter::Info)
+        );
+        assert_eq!(
+            logger.level_for("antegen_client::rpc::health"),
+            Some(LevelFilter::Off)
+        );
+        assert_eq!(logger.level_for("antegen_client::rpcx"), None);
+        assert_eq!(actor_module("mystery_actor"), None);
+    }
+
+    #[test]
+    fn test_json_format() {
+        let line = format_record(
+            LogFormat::Json,
+            &Record::builder()
+                .args(format_args!("thread {} executed", 7))
+                .level(Level::Info)
+                .target("antegen_client::actors::worker")
+                .build(),
+        )
+        .unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "INFO");
+        assert_eq!(value["target"], "antegen_client::actors::worker");
+        assert_eq!(value["msg"], "thread 7 executed");
+        assert!(format_record(LogFormat::Plain, &Record::builder().build()).is_none());
+    }
+}
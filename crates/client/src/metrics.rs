@@ -0,0 +1,492 @@
+//! OpenTelemetry metrics
+//!
+//! With the `metrics` feature, [`init`] installs a global meter provider that
+//! pushes to an OTLP collector configured under `observability.otlp`, and the
+//! `record_*` functions feed these instruments:
+//!
+//! - `antegen.thread.executions`: counter by `outcome`
+//! - `antegen.submission.latency`: histogram in seconds by `path`
+//! - `antegen.submission.sends`: counter by `path`
+//! - `antegen.submission.strategy`: counter of transactions built, by
+//!   `strategy` (see [`crate::tx_builder::TxStrategy`])
+//! - `antegen.submission.both_mode_wins`: counter of confirmed both-mode
+//!   submissions by `won_by`, the path that accepted them first
+//! - `antegen.submission.latency.p50`, `.p95`, `.p99`: gauges in seconds over
+//!   recent submissions, see [`crate::tpu::LatencyHistogram`]
+//! - `antegen.tpu.alignment_sends`: counter of TPU sends by `aligned`
+//!   (whether a warm leader was in the send window, see
+//!   [`crate::tpu::SendWindow`]) and `outcome` (`success` if it landed)
+//! - `antegen.rpc.calls`: counter by `method` and `outcome`
+//! - `antegen.rpc.latency`: histogram in seconds by `method`
+//! - `antegen.rpc.rate_limit_wait`: histogram in seconds spent waiting on an
+//!   endpoint's rate limit, by `method`
+//! - `antegen.processor.lane_saturations`: counter by `lane`
+//! - `antegen.processor.queue_high_water`: gauge of the most threads ever
+//!   queued at once
+//! - `antegen.processor.queue_shed`: counter of threads shed from a full
+//!   queue, by `lane`
+//! - `antegen.processor.concurrency_limit`: gauge of the workers the adaptive
+//!   concurrency controller allows to run
+//! - `antegen.processor.concurrency_adjustments`: counter of controller limit
+//!   changes, by `direction` (`increase` or `decrease`)
+//! - `antegen.clock.updates`: counter by `source` (datasource or poller)
+//! - `antegen.clock.age`: gauge in seconds since the last clock update
+//! - `antegen.actor.messages`, `antegen.actor.errors`: gauges of messages
+//!   handled and failed, by `actor`
+//! - `antegen.actor.uptime`: gauge in seconds by `actor`
+//! - `antegen.actor.stat`: gauge of an actor's own values by `actor` and
+//!   `stat`, see [`crate::actors::ActorStats`]
+//!
+//! Without the feature, and before [`init`], every `record_*` call is a no-op,
+//! so callers never need a `cfg`.
+
+// Thread execution outcomes
+pub const OUTCOME_SUCCESS: &str = "success";
+pub const OUTCOME_FAILED: &str = "failed";
+pub const OUTCOME_SKIPPED: &str = "skipped";
+/// Aborted on-chain by the fiber chain depth limit or cycle check
+pub const OUTCOME_CHAIN_LIMIT: &str = "chain_limit";
+
+// Submission paths
+pub const PATH_TPU: &str = "tpu";
+pub const PATH_RPC: &str = "rpc";
+pub const PATH_BROADCAST: &str = "broadcast";
+/// TPU and RPC at once, counted as one submission
+pub const PATH_BOTH: &str = "both";
+
+#[cfg(feature = "metrics")]
+pub use otel::*;
+
+#[cfg(not(feature = "metrics"))]
+pub use noop::*;
+
+#[cfg(feature = "metrics")]
+mod otel {
+    use super::{OUTCOME_FAILED, OUTCOME_SUCCESS};
+    use crate::actors::ActorStats;
+    use crate::config::OtlpConfig;
+    use opentelemetry::metrics::{Counter, Gauge, Histogram};
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::Resource;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    struct Instruments {
+        thread_executions: Counter<u64>,
+        submission_latency: Histogram<f64>,
+        submission_sends: Counter<u64>,
+        submission_strategy: Counter<u64>,
+        both_mode_wins: Counter<u64>,
+        submission_latency_p50: Gauge<f64>,
+        submission_latency_p95: Gauge<f64>,
+        submission_latency_p99: Gauge<f64>,
+        alignment_sends: Counter<u64>,
+        rpc_calls: Counter<u64>,
+        rpc_latency: Histogram<f64>,
+        rate_limit_wait: Histogram<f64>,
+        lane_saturations: Counter<u64>,
+        queue_high_water: Gauge<u64>,
+        queue_shed: Counter<u64>,
+        concurrency_limit: Gauge<u64>,
+        concurrency_adjustments: Counter<u64>,
+        clock_updates: Counter<u64>,
+        clock_age: Gauge<f64>,
+        actor_messages: Gauge<u64>,
+        actor_errors: Gauge<u64>,
+        actor_uptime: Gauge<f64>,
+        actor_stat: Gauge<f64>,
+    }
+
+    static PROVIDER: OnceLock<SdkMeterProvider> = OnceLock::new();
+    static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+
+    /// Install the global meter provider and register the instruments
+    ///
+    /// Must run inside a tokio runtime, which drives the periodic export.
+    pub fn init(config: &OtlpConfig) -> anyhow::Result<()> {
+        let exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_http()
+            .with_endpoint(config.endpoint.clone())
+            .with_headers(config.headers.clone())
+            .build()?;
+        let reader = PeriodicReader::builder(exporter, opentelemetry_sdk::runtime::Tokio)
+            .with_interval(Duration::from_secs(config.export_interval_secs))
+            .build();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(reader)
+            .with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                config.service_name.clone(),
+            )]))
+            .build();
+
+        if PROVIDER.set(provider.clone()).is_err() {
+            anyhow::bail!("metrics already initialized");
+        }
+        opentelemetry::global::set_meter_provider(provider);
+
+        let meter = opentelemetry::global::meter("antegen-client");
+        let _ = INSTRUMENTS.set(Instruments {
+            thread_executions: meter
+                .u64_counter("antegen.thread.executions")
+                .with_description("Thread executions by outcome")
+                .build(),
+            submission_latency: meter
+                .f64_histogram("antegen.submission.latency")
+                .with_description("Time from first send to confirmation")
+                .with_unit("s")
+                .build(),
+            submission_sends: meter
+                .u64_counter("antegen.submission.sends")
+                .with_description("Transactions sent, by the path that accepted them")
+                .build(),
+            submission_strategy: meter
+                .u64_counter("antegen.submission.strategy")
+                .with_description("Transactions built, by construction strategy")
+                .build(),
+            both_mode_wins: meter
+                .u64_counter("antegen.submission.both_mode_wins")
+                .with_description(
+                    "Confirmed both-mode submissions, by the path that accepted first",
+                )
+                .build(),
+            submission_latency_p50: meter
+                .f64_gauge("antegen.submission.latency.p50")
+                .with_description("Median latency of recent submissions")
+                .with_unit("s")
+                .build(),
+            submission_latency_p95: meter
+                .f64_gauge("antegen.submission.latency.p95")
+                .with_description("95th percentile latency of recent submissions")
+                .with_unit("s")
+                .build(),
+            submission_latency_p99: meter
+                .f64_gauge("antegen.submission.latency.p99")
+                .with_description("99th percentile latency of recent submissions")
+                .with_unit("s")
+                .build(),
+            alignment_sends: meter
+                .u64_counter("antegen.tpu.alignment_sends")
+                .with_description("TPU sends by leader alignment and whether they landed")
+                .build(),
+            rpc_calls: meter
+                .u64_counter("antegen.rpc.calls")
+                .with_description("RPC requests by method and outcome")
+                .build(),
+            rpc_latency: meter
+                .f64_histogram("antegen.rpc.latency")
+                .with_description("RPC request latency")
+                .with_unit("s")
+                .build(),
+            rate_limit_wait: meter
+                .f64_histogram("antegen.rpc.rate_limit_wait")
+                .with_description("Time RPC requests waited on endpoint rate limits")
+                .with_unit("s")
+                .build(),
+            lane_saturations: meter
+                .u64_counter("antegen.processor.lane_saturations")
+                .with_description("Times a worker lane filled up with threads waiting")
+                .build(),
+            queue_high_water: meter
+                .u64_gauge("antegen.processor.queue_high_water")
+                .with_description("Most threads ever queued for execution at once")
+                .build(),
+            queue_shed: meter
+                .u64_counter("antegen.processor.queue_shed")
+                .with_description("Threads shed from a full processor queue, by lane")
+                .build(),
+            concurrency_limit: meter
+                .u64_gauge("antegen.processor.concurrency_limit")
+                .with_description("Workers the adaptive concurrency controller allows")
+                .build(),
+            concurrency_adjustments: meter
+                .u64_counter("antegen.processor.concurrency_adjustments")
+                .with_description("Adaptive concurrency limit changes, by direction")
+                .build(),
+            clock_updates: meter
+                .u64_counter("antegen.clock.updates")
+                .with_description("Clock updates by source")
+                .build(),
+            clock_age: meter
+                .f64_gauge("antegen.clock.age")
+                .with_description("Time since the last clock update")
+                .with_unit("s")
+                .build(),
+            actor_messages: meter
+                .u64_gauge("antegen.actor.messages")
+                .with_description("Messages handled, by actor")
+                .build(),
+            actor_errors: meter
+                .u64_gauge("antegen.actor.errors")
+                .with_description("Messages whose handling failed, by actor")
+                .build(),
+            actor_uptime: meter
+                .f64_gauge("antegen.actor.uptime")
+                .with_description("Time since the actor started")
+                .with_unit("s")
+                .build(),
+            actor_stat: meter
+                .f64_gauge("antegen.actor.stat")
+                .with_description("Actor-specific values, by actor and stat")
+                .build(),
+        });
+
+        log::info!("OTLP metrics exporting to {}", config.endpoint);
+        Ok(())
+    }
+
+    /// Flush pending metrics and stop exporting
+    pub fn shutdown() {
+        if let Some(provider) = PROVIDER.get() {
+            if let Err(e) = provider.shutdown() {
+                log::warn!("Failed to shut down metrics exporter: {}", e);
+            }
+        }
+    }
+
+    pub fn record_thread_execution(outcome: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .thread_executions
+                .add(1, &[KeyValue::new("outcome", outcome)]);
+        }
+    }
+
+    pub fn record_submission_latency(path: &'static str, latency: Duration) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .submission_latency
+                .record(latency.as_secs_f64(), &[KeyValue::new("path", path)]);
+        }
+    }
+
+    pub fn record_submission_send(path: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .submission_sends
+                .add(1, &[KeyValue::new("path", path)]);
+        }
+    }
+
+    pub fn record_submission_strategy(strategy: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .submission_strategy
+                .add(1, &[KeyValue::new("strategy", strategy)]);
+        }
+    }
+
+    pub fn record_both_mode_win(won_by: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .both_mode_wins
+                .add(1, &[KeyValue::new("won_by", won_by)]);
+        }
+    }
+
+    pub fn record_submission_latency_percentiles(p50: Duration, p95: Duration, p99: Duration) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .submission_latency_p50
+                .record(p50.as_secs_f64(), &[]);
+            instruments
+                .submission_latency_p95
+                .record(p95.as_secs_f64(), &[]);
+            instruments
+                .submission_latency_p99
+                .record(p99.as_secs_f64(), &[]);
+        }
+    }
+
+    pub fn record_alignment_send(aligned: bool, landed: bool) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            let outcome = if landed {
+                OUTCOME_SUCCESS
+            } else {
+                OUTCOME_FAILED
+            };
+            instruments.alignment_sends.add(
+                1,
+                &[
+                    KeyValue::new("aligned", aligned),
+                    KeyValue::new("outcome", outcome),
+                ],
+            );
+        }
+    }
+
+    pub fn record_rpc_call(method: &str, success: bool, latency: Duration) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            let method = KeyValue::new("method", method.to_string());
+            let outcome = if success {
+                OUTCOME_SUCCESS
+            } else {
+                OUTCOME_FAILED
+            };
+            instruments
+                .rpc_calls
+                .add(1, &[method.clone(), KeyValue::new("outcome", outcome)]);
+            instruments
+                .rpc_latency
+                .record(latency.as_secs_f64(), &[method]);
+        }
+    }
+
+    pub fn record_rate_limit_wait(method: &str, waited: Duration) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments.rate_limit_wait.record(
+                waited.as_secs_f64(),
+                &[KeyValue::new("method", method.to_string())],
+            );
+        }
+    }
+
+    pub fn record_lane_saturation(lane: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .lane_saturations
+                .add(1, &[KeyValue::new("lane", lane)]);
+        }
+    }
+
+    pub fn record_queue_high_water(queued: usize) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments.queue_high_water.record(queued as u64, &[]);
+        }
+    }
+
+    pub fn record_queue_shed(lane: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .queue_shed
+                .add(1, &[KeyValue::new("lane", lane)]);
+        }
+    }
+
+    pub fn record_concurrency_limit(limit: usize, direction: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments.concurrency_limit.record(limit as u64, &[]);
+            instruments
+                .concurrency_adjustments
+                .add(1, &[KeyValue::new("direction", direction)]);
+        }
+    }
+
+    pub fn record_clock_update(source: &'static str) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments
+                .clock_updates
+                .add(1, &[KeyValue::new("source", source)]);
+        }
+    }
+
+    pub fn record_clock_age(age: Duration) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            instruments.clock_age.record(age.as_secs_f64(), &[]);
+        }
+    }
+
+    pub fn record_actor_stats(stats: &ActorStats) {
+        if let Some(instruments) = INSTRUMENTS.get() {
+            let actor = KeyValue::new("actor", stats.actor_name.clone());
+            instruments
+                .actor_messages
+                .record(stats.messages_processed, &[actor.clone()]);
+            instruments
+                .actor_errors
+                .record(stats.errors, &[actor.clone()]);
+            instruments
+                .actor_uptime
+                .record(stats.uptime_secs as f64, &[actor.clone()]);
+            for (stat, value) in &stats.custom {
+                instruments.actor_stat.record(
+                    *value,
+                    &[actor.clone(), KeyValue::new("stat", stat.clone())],
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod noop {
+    use crate::actors::ActorStats;
+    use crate::config::OtlpConfig;
+    use std::time::Duration;
+
+    /// Without the `metrics` feature there is nothing to export to
+    pub fn init(config: &OtlpConfig) -> anyhow::Result<()> {
+        log::warn!(
+            "observability.otlp is set ({}) but this build lacks the `metrics` feature",
+            config.endpoint
+        );
+        Ok(())
+    }
+
+    pub fn shutdown() {}
+
+    pub fn record_thread_execution(_outcome: &'static str) {}
+
+    pub fn record_submission_latency(_path: &'static str, _latency: Duration) {}
+
+    pub fn record_submission_send(_path: &'static str) {}
+
+    pub fn record_submission_strategy(_strategy: &'static str) {}
+
+    pub fn record_both_mode_win(_won_by: &'static str) {}
+
+    pub fn record_submission_latency_percentiles(_p50: Duration, _p95: Duration, _p99: Duration) {}
+
+    pub fn record_alignment_send(_aligned: bool, _landed: bool) {}
+
+    pub fn record_rpc_call(_method: &str, _success: bool, _latency: Duration) {}
+
+    pub fn record_rate_limit_wait(_method: &str, _waited: Duration) {}
+
+    pub fn record_lane_saturation(_lane: &'static str) {}
+
+    pub fn record_queue_high_water(_queued: usize) {}
+
+    pub fn record_queue_shed(_lane: &'static str) {}
+
+    pub fn record_concurrency_limit(_limit: usize, _direction: &'static str) {}
+
+    pub fn record_clock_update(_source: &'static str) {}
+
+    pub fn record_clock_age(_age: Duration) {}
+
+    pub fn record_actor_stats(_stats: &ActorStats) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_record_before_init_is_noop() {
+        record_thread_execution(OUTCOME_SUCCESS);
+        record_submission_latency(PATH_TPU, Duration::from_millis(450));
+        record_submission_send(PATH_RPC);
+        record_submission_strategy("legacy");
+        record_both_mode_win(PATH_TPU);
+        record_submission_latency_percentiles(
+            Duration::from_millis(400),
+            Duration::from_millis(900),
+            Duration::from_millis(1500),
+        );
+        record_alignment_send(true, false);
+        record_rpc_call("getSlot", true, Duration::from_millis(20));
+        record_rate_limit_wait("getProgramAccounts", Duration::from_millis(500));
+        record_lane_saturation("account");
+        record_queue_high_water(12);
+        record_queue_shed("time");
+        record_concurrency_limit(4, "decrease");
+        record_clock_update("poller");
+        record_clock_age(Duration::from_millis(400));
+        record_actor_stats(&crate::actors::ActorStats::default());
+        shutdown();
+    }
+}
@@ -3,7 +3,8 @@
 //! This is the executor process that runs Solana threads.
 //! Managed by `anm` (Antegen Node Manager) via `anm use/update/install`.
 
-use antegen_client::config::{EndpointRole, RpcEndpoint};
+use antegen_client::config::{EndpointRole, LogFormat, ObservabilityConfig, RpcEndpoint};
+use antegen_client::logging::{self, ActorLogFilter};
 use antegen_client::rpc::websocket::WsClient;
 use antegen_client::rpc::RpcPool;
 use antegen_client::ClientConfig;
@@ -11,6 +12,7 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -100,31 +102,21 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
     let config_path = resolve_config_path(cli.config)?;
 
-    // Initialize logging
-    let mut builder = env_logger::Builder::new();
-
-    if let Some(level) = cli.log_level {
-        builder.filter_level(level.to_level_filter());
-    } else {
-        builder.parse_env(env_logger::Env::default().default_filter_or("info"));
+    // Auto-generate default config if it doesn't exist
+    let generated = !config_path.exists();
+    if generated {
+        ClientConfig::default().save(&config_path)?;
     }
 
-    builder.filter_module("ractor", log::LevelFilter::Warn);
-    builder.filter_module(
-        "solana_tpu_client_next::connection_worker",
-        log::LevelFilter::Error,
-    );
-    builder.filter_module("antegen_ws", log::LevelFilter::Off);
-    builder.format_timestamp_millis().init();
+    // Load configuration (before logging, which it configures)
+    let mut config = ClientConfig::load(&config_path)?;
+
+    init_logging(cli.log_level, &config.observability);
 
     log::info!("Antegen Node - Standalone Mode");
 
-    // Auto-generate default config if it doesn't exist
-    if !config_path.exists() {
+    if generated {
         log::warn!("Config file not found: {}", config_path.display());
-        log::info!("Generating default configuration...");
-
-        ClientConfig::default().save(&config_path)?;
 
         let abs_config_path = config_path.canonicalize().unwrap_or_else(|_| {
             std::env::current_dir()
@@ -145,9 +137,6 @@ async fn main() -> Result<()> {
         log::info!("Loading configuration from: {}", config_path.display());
     }
 
-    // Load configuration
-    let mut config = ClientConfig::load(&config_path)?;
-
     // Override RPC if provided via CLI
     if let Some(rpc_url) = cli.rpc {
         log::info!("Using RPC override: {}", rpc_url);
@@ -156,6 +145,7 @@ async fn main() -> Result<()> {
             ws_url: None,
             role: EndpointRole::Both,
             priority: 1,
+            rate_limits: None,
         }];
     }
 
@@ -173,10 +163,84 @@ async fn main() -> Result<()> {
 
     check_balance_or_wait(&rpc_endpoint.url, &rpc_endpoint.get_ws_url(), &keypair_path).await?;
 
+    // Re-read processor.thread_filter from the config file on SIGHUP
+    #[cfg(unix)]
+    spawn_thread_filter_reload(config_path.clone());
+
     // Run the client
     antegen_client::run_standalone(config).await
 }
 
+/// Apply `processor.thread_filter` from the config file whenever SIGHUP is
+/// received, so an operator can focus on specific threads without a restart
+#[cfg(unix)]
+fn spawn_thread_filter_reload(config_path: PathBuf) {
+    use futures::stream::StreamExt;
+    use signal_hook::consts::signal::SIGHUP;
+
+    tokio::spawn(async move {
+        let mut signals = match signal_hook_tokio::Signals::new([SIGHUP]) {
+            Ok(s) => s,
+            Err(e) => {
+                log::error!("Failed to create SIGHUP handler: {}", e);
+                return;
+            }
+        };
+
+        while signals.next().await.is_some() {
+            match antegen_client::reload_config(&config_path) {
+                Ok(_) => log::info!("Reloaded thread filter from {}", config_path.display()),
+                Err(e) => log::error!("Failed to reload thread filter: {}", e),
+            }
+        }
+    });
+}
+
+/// Install the logger: env_logger with the configured line format, wrapped
+/// in the per-actor level filter
+fn init_logging(log_level: Option<LogLevel>, observability: &ObservabilityConfig) {
+    let mut builder = env_logger::Builder::new();
+
+    if let Some(level) = log_level {
+        builder.filter_level(level.to_level_filter());
+    } else {
+        builder.parse_env(env_logger::Env::default().default_filter_or("info"));
+    }
+
+    builder.filter_module("ractor", log::LevelFilter::Warn);
+    builder.filter_module(
+        "solana_tpu_client_next::connection_worker",
+        log::LevelFilter::Error,
+    );
+    builder.filter_module("antegen_ws", log::LevelFilter::Off);
+
+    let format = observability.log_format;
+    if format == LogFormat::Plain {
+        builder.format_timestamp_millis();
+    } else {
+        builder.format(move |buf, record| {
+            let line = logging::format_record(format, record).unwrap_or_default();
+            writeln!(buf, "{}", line)
+        });
+    }
+
+    // The actor filter decides for listed modules, so env_logger must let
+    // through anything it allows
+    for (key, level) in &observability.actor_log_levels {
+        if let Some(module) = logging::actor_module(key) {
+            builder.filter_module(module, *level);
+        }
+    }
+
+    let logger = builder.build();
+    let inner_level = logger.filter();
+    let filter = ActorLogFilter::new(Box::new(logger), &observability.actor_log_levels);
+    let max_level = inner_level.max(filter.max_level());
+    if log::set_boxed_logger(Box::new(filter)).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
 /// Check if executor has sufficient balance, wait for funding if not
 async fn check_balance_or_wait(rpc_url: &str, ws_url: &str, keypair_path: &Path) -> Result<()> {
     let keypair = read_keypair_file(keypair_path)
@@ -4,17 +4,21 @@
 //! Uses per-entry variable expiration:
 //! - Time triggers: expire after trigger_time + grace_period
 //! - Slot/Epoch/Account triggers: no TTL (persist until capacity eviction)
+//!
+//! Cached threads are also indexed by next fire time and by watched account,
+//! so lookups like "due within the next minute" don't scan every entry.
 
 use crate::config::CacheConfig;
 use crate::rpc::RpcPool;
 use anchor_lang::AccountDeserialize;
 use antegen_thread_program::state::{Schedule, Thread, Trigger};
-use base64::prelude::*;
 use moka::future::Cache;
 use moka::notification::RemovalCause;
 use moka::policy::Expiry;
 use solana_sdk::pubkey::Pubkey;
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
@@ -48,7 +52,9 @@ impl CacheTriggerType {
                     CacheTriggerType::Unknown
                 }
             }
-            Trigger::Slot { .. } | Trigger::Epoch { .. } => CacheTriggerType::Block,
+            Trigger::Slot { .. } | Trigger::Epoch { .. } | Trigger::EpochInterval { .. } => {
+                CacheTriggerType::Block
+            }
             Trigger::Account { .. } => CacheTriggerType::Account,
         }
     }
@@ -58,10 +64,107 @@ impl CacheTriggerType {
 #[derive(Debug, Clone)]
 pub struct CachedAccount {
     pub data: Vec<u8>,
+    /// Slot the data was observed at: the update slot for datasource
+    /// updates, the response context slot for RPC fetches
     pub slot: u64,
     pub hash: u64,
     /// Trigger type for expiration calculation
     pub trigger_type: CacheTriggerType,
+    /// Write sequence, so index removals ignore entries already replaced
+    seq: u64,
+}
+
+/// Where a cached thread sits in the [`TriggerIndex`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexEntry {
+    /// Scheduled to fire at a unix timestamp
+    Time(i64),
+    /// Fires when the watched account changes
+    Account(Pubkey),
+    /// Due time depends on the current slot (slot and epoch triggers, cron
+    /// threads not yet scheduled); evaluated on every lookup
+    Floating,
+}
+
+impl IndexEntry {
+    fn from_thread(thread: &Thread) -> Option<Self> {
+        match (&thread.trigger, &thread.schedule) {
+            (Trigger::Account { address, .. }, _) => Some(Self::Account(*address)),
+            (Trigger::Slot { .. } | Trigger::Epoch { .. } | Trigger::EpochInterval { .. }, _) => {
+                Some(Self::Floating)
+            }
+            (_, Schedule::Timed { next, .. }) if *next != 0 && *next != i64::MAX => {
+                Some(Self::Time(*next))
+            }
+            (Trigger::Cron { .. }, _) => Some(Self::Floating),
+            // Immediate threads after their first execution never fire again
+            _ => None,
+        }
+    }
+}
+
+/// Secondary indexes over the cached threads
+#[derive(Default)]
+struct TriggerIndex {
+    entries: HashMap<Pubkey, (u64, IndexEntry)>,
+    by_time: BTreeSet<(i64, Pubkey)>,
+    dependents: HashMap<Pubkey, HashSet<Pubkey>>,
+    floating: HashSet<Pubkey>,
+}
+
+impl TriggerIndex {
+    /// Index `key` as written at `seq`, replacing its previous entry
+    fn insert(&mut self, key: Pubkey, seq: u64, entry: Option<IndexEntry>) {
+        self.unlink(&key);
+        let Some(entry) = entry else {
+            return;
+        };
+        match entry {
+            IndexEntry::Time(at) => {
+                self.by_time.insert((at, key));
+            }
+            IndexEntry::Account(address) => {
+                self.dependents.entry(address).or_default().insert(key);
+            }
+            IndexEntry::Floating => {
+                self.floating.insert(key);
+            }
+        }
+        self.entries.insert(key, (seq, entry));
+    }
+
+    /// Drop `key` if its indexed entry was written at `seq`
+    fn remove(&mut self, key: &Pubkey, seq: u64) {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|(indexed, _)| *indexed == seq)
+        {
+            self.unlink(key);
+        }
+    }
+
+    fn unlink(&mut self, key: &Pubkey) {
+        let Some((_, entry)) = self.entries.remove(key) else {
+            return;
+        };
+        match entry {
+            IndexEntry::Time(at) => {
+                self.by_time.remove(&(at, *key));
+            }
+            IndexEntry::Account(address) => {
+                if let Some(dependents) = self.dependents.get_mut(&address) {
+                    dependents.remove(key);
+                    if dependents.is_empty() {
+                        self.dependents.remove(&address);
+                    }
+                }
+            }
+            IndexEntry::Floating => {
+                self.floating.remove(key);
+            }
+        }
+    }
 }
 
 /// Per-entry expiration policy
@@ -150,6 +253,12 @@ pub struct AccountCache {
     /// Channel to notify when cache entries expire (for refetch)
     /// Note: Stored here for lifetime management; actual send happens in eviction_listener closure
     _eviction_tx: Option<mpsc::UnboundedSender<Pubkey>>,
+    /// Threads by next fire time and watched account, kept in step with the
+    /// cache by writes here and removals in the eviction listener
+    index: Arc<Mutex<TriggerIndex>>,
+    /// Serializes writes so the index sees them in cache order
+    write_lock: tokio::sync::Mutex<()>,
+    next_seq: AtomicU64,
 }
 
 impl AccountCache {
@@ -170,12 +279,15 @@ impl AccountCache {
             eviction_buffer,
         };
         let eviction_tx_clone = eviction_tx.clone();
+        let index = Arc::new(Mutex::new(TriggerIndex::default()));
+        let listener_index = index.clone();
 
         Self {
             cache: Cache::builder()
                 .max_capacity(config.max_capacity)
                 .expire_after(expiry)
-                .eviction_listener(move |key: Arc<Pubkey>, _value, cause| {
+                .eviction_listener(move |key: Arc<Pubkey>, value: CachedAccount, cause| {
+                    unindex(&listener_index, &key, &value, cause);
                     // Log evictions for debugging
                     match cause {
                         RemovalCause::Expired => {
@@ -199,27 +311,57 @@ impl AccountCache {
                 .build(),
             grace_period,
             _eviction_tx: eviction_tx,
+            index,
+            write_lock: tokio::sync::Mutex::new(()),
+            next_seq: AtomicU64::new(0),
         }
     }
 
     /// Create a new account cache with custom capacity (for testing)
     #[cfg(test)]
     pub fn with_capacity(max_capacity: u64) -> Self {
+        let index = Arc::new(Mutex::new(TriggerIndex::default()));
+        let listener_index = index.clone();
         Self {
-            cache: Cache::builder().max_capacity(max_capacity).build(),
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .eviction_listener(move |key: Arc<Pubkey>, value: CachedAccount, cause| {
+                    unindex(&listener_index, &key, &value, cause)
+                })
+                .build(),
             grace_period: 10,
             _eviction_tx: None,
+            index,
+            write_lock: tokio::sync::Mutex::new(()),
+            next_seq: AtomicU64::new(0),
         }
     }
 
-    /// Get a cached account
-    pub async fn get(&self, key: &Pubkey) -> Option<CachedAccount> {
-        self.cache.get(key).await
-    }
-
-    /// Put an account in the cache with trigger type for expiration
-    pub async fn put(&self, key: Pubkey, data: Vec<u8>, slot: u64, trigger_type: CacheTriggerType) {
-        let hash = seahash::hash(&data);
+    /// Index and insert an entry; callers hold the write lock
+    ///
+    /// Without an explicit `trigger_type` it is derived from the thread data.
+    /// The index is updated first, so an eviction racing the insert still
+    /// finds the entry to remove.
+    async fn insert(
+        &self,
+        key: Pubkey,
+        data: Vec<u8>,
+        slot: u64,
+        hash: u64,
+        trigger_type: Option<CacheTriggerType>,
+    ) {
+        let thread = Thread::try_deserialize(&mut data.as_slice()).ok();
+        let trigger_type = trigger_type.unwrap_or_else(|| {
+            thread
+                .as_ref()
+                .map_or(CacheTriggerType::Unknown, CacheTriggerType::from_thread)
+        });
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        self.index.lock().unwrap().insert(
+            key,
+            seq,
+            thread.as_ref().and_then(IndexEntry::from_thread),
+        );
         self.cache
             .insert(
                 key,
@@ -228,26 +370,99 @@ impl AccountCache {
                     slot,
                     hash,
                     trigger_type,
+                    seq,
                 },
             )
             .await;
     }
 
+    /// Get a cached account
+    pub async fn get(&self, key: &Pubkey) -> Option<CachedAccount> {
+        self.cache.get(key).await
+    }
+
+    /// Put an account in the cache with trigger type for expiration
+    pub async fn put(&self, key: Pubkey, data: Vec<u8>, slot: u64, trigger_type: CacheTriggerType) {
+        let hash = seahash::hash(&data);
+        let _write = self.write_lock.lock().await;
+        self.insert(key, data, slot, hash, Some(trigger_type)).await;
+    }
+
     /// Put an account in the cache (legacy, uses Unknown trigger type)
     pub async fn put_simple(&self, key: Pubkey, data: Vec<u8>, slot: u64) {
         self.put(key, data, slot, CacheTriggerType::Unknown).await;
     }
 
+    /// Keys of all cached accounts
+    pub fn keys(&self) -> Vec<Pubkey> {
+        self.cache.iter().map(|(key, _)| *key).collect()
+    }
+
     /// Invalidate a specific account
     pub async fn invalidate(&self, key: &Pubkey) {
         self.cache.invalidate(key).await;
     }
 
+    /// Remove and return a cached account, for entries meant to be used once
+    pub async fn take(&self, key: &Pubkey) -> Option<CachedAccount> {
+        self.cache.remove(key).await
+    }
+
+    /// All cached accounts that deserialize as threads
+    pub fn threads(&self) -> Vec<(Pubkey, Thread)> {
+        self.cache
+            .iter()
+            .filter_map(|(key, cached)| {
+                Thread::try_deserialize(&mut cached.data.as_slice())
+                    .ok()
+                    .map(|thread| (*key, thread))
+            })
+            .collect()
+    }
+
+    /// Cached threads scheduled to fire at or before `ts`, soonest first
+    ///
+    /// Overdue threads are included. Slot, epoch and unscheduled cron
+    /// threads are not, see [`Self::floating`].
+    pub fn due_before(&self, ts: i64) -> Vec<Pubkey> {
+        self.index
+            .lock()
+            .unwrap()
+            .by_time
+            .range(..=(ts, Pubkey::new_from_array([u8::MAX; 32])))
+            .map(|(_, key)| *key)
+            .collect()
+    }
+
+    /// Cached threads whose account trigger watches `address`
+    pub fn dependents_of(&self, address: &Pubkey) -> Vec<Pubkey> {
+        self.index
+            .lock()
+            .unwrap()
+            .dependents
+            .get(address)
+            .map(|dependents| dependents.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Cached threads whose due time depends on the current slot rather than
+    /// a stored timestamp
+    pub fn floating(&self) -> Vec<Pubkey> {
+        self.index
+            .lock()
+            .unwrap()
+            .floating
+            .iter()
+            .copied()
+            .collect()
+    }
+
     /// Put account data only if it's newer than cached version
     /// Returns true if data was actually updated (not a duplicate)
     /// This serves as both caching AND deduplication in one operation
     pub async fn put_if_newer(&self, key: Pubkey, data: Vec<u8>, slot: u64) -> bool {
         let new_hash = seahash::hash(&data);
+        let _write = self.write_lock.lock().await;
 
         if let Some(existing) = self.cache.get(&key).await {
             // Same hash = identical data (duplicate)
@@ -260,24 +475,8 @@ impl AccountCache {
             }
         }
 
-        // Try to deserialize to get trigger type
-        let trigger_type = if let Ok(thread) = Thread::try_deserialize(&mut data.as_slice()) {
-            CacheTriggerType::from_thread(&thread)
-        } else {
-            CacheTriggerType::Unknown
-        };
-
-        self.cache
-            .insert(
-                key,
-                CachedAccount {
-                    data,
-                    slot,
-                    hash: new_hash,
-                    trigger_type,
-                },
-            )
-            .await;
+        // Trigger type comes from the thread data, if it is one
+        self.insert(key, data, slot, new_hash, None).await;
         true
     }
 
@@ -298,26 +497,22 @@ impl AccountCache {
         // Cache miss - fetch from RPC
         log::debug!("Cache miss for thread {}, fetching from RPC", key);
 
-        let ui_account = rpc_client
-            .get_account(key)
+        let (account, slot) = rpc_client
+            .get_account_with_slot(key)
             .await
-            .map_err(|e| format!("Failed to fetch account {}: {}", key, e))?
-            .ok_or_else(|| format!("Account {} not found", key))?;
+            .map_err(|e| format!("Failed to fetch account {}: {}", key, e))?;
 
-        // Decode base64 account data
-        let account_data = BASE64_STANDARD
-            .decode(&ui_account.data.0)
-            .map_err(|e| format!("Failed to decode account data: {}", e))?;
-
-        // Deserialize to get trigger type
-        let thread = Thread::try_deserialize(&mut account_data.as_slice())
+        let thread = Thread::try_deserialize(&mut account.data.as_slice())
             .map_err(|e| format!("Failed to deserialize fetched thread: {}", e))?;
 
-        let trigger_type = CacheTriggerType::from_thread(&thread);
-
-        // Store in cache with trigger type
-        let slot = 0; // We don't have slot info from get_account, use 0
-        self.put(*key, account_data, slot, trigger_type).await;
+        // A datasource update may have landed while we were fetching; keep
+        // whichever is newer by context slot
+        if !self.put_if_newer(*key, account.data, slot).await {
+            if let Some(cached) = self.cache.get(key).await {
+                return Thread::try_deserialize(&mut cached.data.as_slice())
+                    .map_err(|e| format!("Failed to deserialize cached thread: {}", e));
+            }
+        }
 
         Ok(thread)
     }
@@ -344,6 +539,14 @@ impl AccountCache {
     }
 }
 
+/// Remove an evicted entry from the index, unless it was only replaced or a
+/// newer write has already been indexed
+fn unindex(index: &Mutex<TriggerIndex>, key: &Pubkey, value: &CachedAccount, cause: RemovalCause) {
+    if cause != RemovalCause::Replaced {
+        index.lock().unwrap().remove(key, value.seq);
+    }
+}
+
 impl Default for AccountCache {
     fn default() -> Self {
         Self::new()
@@ -540,4 +743,174 @@ mod tests {
         let trigger = CacheTriggerType::Account;
         assert_eq!(trigger, CacheTriggerType::Account);
     }
+
+    fn thread_data(trigger: Trigger, next: i64) -> Vec<u8> {
+        use anchor_lang::AccountSerialize;
+        let mut thread = crate::actors::worker::tests::new_thread(Pubkey::default());
+        thread.trigger = trigger;
+        thread.schedule = Schedule::Timed { prev: 0, next };
+        let mut data = Vec::new();
+        thread.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn interval() -> Trigger {
+        Trigger::Interval {
+            seconds: 60,
+            skippable: false,
+            jitter: 0,
+        }
+    }
+
+    fn account(address: Pubkey) -> Trigger {
+        Trigger::Account {
+            address,
+            offset: 0,
+            size: 8,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_follows_trigger_changes() {
+        let cache = AccountCache::new();
+        let watched = Pubkey::new_unique();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        cache.put_if_newer(a, thread_data(interval(), 100), 1).await;
+        cache.put_if_newer(b, thread_data(interval(), 300), 1).await;
+        assert_eq!(cache.due_before(99), Vec::<Pubkey>::new());
+        assert_eq!(cache.due_before(100), vec![a]);
+        assert_eq!(cache.due_before(300), vec![a, b]);
+
+        // Rescheduled: moves within the time index
+        cache.put_if_newer(a, thread_data(interval(), 400), 2).await;
+        assert_eq!(cache.due_before(300), vec![b]);
+        assert_eq!(cache.due_before(400), vec![b, a]);
+
+        // Trigger changed to an account trigger: leaves the time index
+        cache
+            .put_if_newer(a, thread_data(account(watched), 0), 3)
+            .await;
+        assert_eq!(cache.due_before(i64::MAX - 1), vec![b]);
+        assert_eq!(cache.dependents_of(&watched), vec![a]);
+
+        cache
+            .put_if_newer(b, thread_data(Trigger::Slot { slot: 10 }, 0), 2)
+            .await;
+        assert!(cache.due_before(i64::MAX - 1).is_empty());
+        assert_eq!(cache.floating(), vec![b]);
+
+        // Non-thread data drops the entry from every index
+        cache.put_simple(a, vec![1, 2, 3], 4).await;
+        assert!(cache.dependents_of(&watched).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_drops_removed_entries() {
+        let cache = AccountCache::new();
+        let watched = Pubkey::new_unique();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        cache.put_if_newer(a, thread_data(interval(), 100), 1).await;
+        cache
+            .put_if_newer(b, thread_data(account(watched), 0), 1)
+            .await;
+
+        cache.invalidate(&a).await;
+        assert!(cache.take(&b).await.is_some());
+        cache.run_pending_tasks().await;
+        assert!(cache.due_before(100).is_empty());
+        assert!(cache.dependents_of(&watched).is_empty());
+
+        // A stale removal does not drop the entry written after it
+        cache.put_if_newer(a, thread_data(interval(), 100), 2).await;
+        cache.invalidate(&a).await;
+        cache.put_if_newer(a, thread_data(interval(), 200), 3).await;
+        cache.run_pending_tasks().await;
+        assert_eq!(cache.due_before(200), vec![a]);
+    }
+
+    #[tokio::test]
+    async fn test_index_drops_capacity_evictions() {
+        let cache = AccountCache::with_capacity(8);
+        for i in 0..32 {
+            cache
+                .put_if_newer(Pubkey::new_unique(), thread_data(interval(), i + 1), 1)
+                .await;
+            cache.run_pending_tasks().await;
+        }
+        let mut cached = cache.keys();
+        let mut indexed = cache.due_before(i64::MAX - 1);
+        cached.sort();
+        indexed.sort();
+        assert_eq!(indexed, cached);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_index_consistent_under_concurrent_put_and_evict() {
+        let cache = Arc::new(AccountCache::new());
+        let watched = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+
+        let tasks: Vec<_> = (0..8u64)
+            .map(|task| {
+                let cache = cache.clone();
+                let keys = keys.clone();
+                tokio::spawn(async move {
+                    for round in 0..200u64 {
+                        let key = keys[((task + round) % keys.len() as u64) as usize];
+                        let slot = task * 1000 + round;
+                        match (task + round) % 4 {
+                            0 => cache.invalidate(&key).await,
+                            1 => {
+                                cache
+                                    .put_if_newer(key, thread_data(account(watched), 0), slot)
+                                    .await;
+                            }
+                            _ => {
+                                cache
+                                    .put_if_newer(
+                                        key,
+                                        thread_data(interval(), slot as i64 + 1),
+                                        slot,
+                                    )
+                                    .await;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        cache.run_pending_tasks().await;
+
+        let mut expected = HashMap::new();
+        for key in &keys {
+            let entry = cache.get(key).await.and_then(|cached| {
+                let thread = Thread::try_deserialize(&mut cached.data.as_slice()).unwrap();
+                IndexEntry::from_thread(&thread)
+            });
+            expected.insert(*key, entry);
+        }
+
+        let index = cache.index.lock().unwrap();
+        for key in &keys {
+            let expected = expected[key];
+            let indexed = index.entries.get(key).map(|(_, entry)| *entry);
+            assert_eq!(indexed, expected, "index out of step for {}", key);
+            match indexed {
+                Some(IndexEntry::Time(at)) => assert!(index.by_time.contains(&(at, *key))),
+                Some(IndexEntry::Account(address)) => {
+                    assert!(index.dependents[&address].contains(key))
+                }
+                _ => {}
+            }
+        }
+        // No leftovers from earlier writes
+        assert_eq!(
+            index.by_time.len() + index.dependents.values().map(HashSet::len).sum::<usize>(),
+            index.entries.len()
+        );
+    }
 }
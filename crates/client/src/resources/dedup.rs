@@ -0,0 +1,211 @@
+//! Submission deduplication window
+//!
+//! During datasource failover the same executable thread can arrive from both
+//! the Geyser stream and RPC reconciliation within milliseconds. Both copies
+//! carry identical on-chain state, so they would build transactions that
+//! differ only by blockhash, and the second one would fail on-chain.
+//!
+//! Each execution is keyed by a hash of (thread, fiber cursor, exec count,
+//! schedule), claimed just before the first transaction is submitted. The
+//! first execution with a given key to get there within the TTL proceeds.
+//! Later ones are dropped before a signature is spent; executions that give
+//! up before submitting never claim the key. Once the thread
+//! executes, its exec count and schedule change, so the next legitimate
+//! execution gets a new key regardless of the TTL.
+
+use antegen_thread_program::state::{Schedule, Thread};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often expired entries are swept
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Recently started executions, keyed by thread state snapshot
+pub struct SubmissionDedup {
+    ttl: Duration,
+    entries: DashMap<u64, Instant>,
+    last_cleanup: Mutex<Instant>,
+    hits: AtomicU64,
+}
+
+impl SubmissionDedup {
+    /// Create a window with the given TTL; a zero TTL disables deduplication
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: DashMap::new(),
+            last_cleanup: Mutex::new(Instant::now()),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Dedup key for an execution of `thread` in its current state
+    pub fn key(thread_pubkey: &Pubkey, thread: &Thread) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        thread_pubkey.hash(&mut hasher);
+        thread.fiber_cursor.hash(&mut hasher);
+        thread.exec_count.hash(&mut hasher);
+        match &thread.schedule {
+            Schedule::OnChange { prev } => (0u8, *prev).hash(&mut hasher),
+            Schedule::Timed { prev, next } => (1u8, *prev, *next).hash(&mut hasher),
+            Schedule::Block { prev, next } => (2u8, *prev, *next).hash(&mut hasher),
+        }
+        hasher.finish()
+    }
+
+    /// Record an execution attempt
+    ///
+    /// Returns `true` if this is the first attempt with this key within the
+    /// TTL, `false` if it is a duplicate that should be dropped.
+    pub fn check_and_insert(&self, thread_pubkey: &Pubkey, thread: &Thread) -> bool {
+        if self.ttl.is_zero() {
+            return true;
+        }
+        self.maybe_cleanup();
+
+        let now = Instant::now();
+        let mut first = true;
+        self.entries
+            .entry(Self::key(thread_pubkey, thread))
+            .and_modify(|seen| {
+                if now.duration_since(*seen) < self.ttl {
+                    first = false;
+                } else {
+                    *seen = now;
+                }
+            })
+            .or_insert(now);
+
+        if !first {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        first
+    }
+
+    /// Duplicate executions dropped so far
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of live entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the window holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove expired entries, returning how many were removed
+    pub fn cleanup(&self) -> usize {
+        let before = self.entries.len();
+        let ttl = self.ttl;
+        self.entries.retain(|_, seen| seen.elapsed() < ttl);
+        before.saturating_sub(self.entries.len())
+    }
+
+    /// Sweep at most once per `CLEANUP_INTERVAL`, skipping if another caller is
+    fn maybe_cleanup(&self) {
+        let Some(mut last) = self.last_cleanup.try_lock() else {
+            return;
+        };
+        if last.elapsed() >= CLEANUP_INTERVAL {
+            *last = Instant::now();
+            drop(last);
+            self.cleanup();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use antegen_thread_program::state::{Signal, Trigger, CURRENT_THREAD_VERSION};
+
+    fn thread(exec_count: u64, next: i64) -> Thread {
+        Thread {
+            version: CURRENT_THREAD_VERSION,
+            bump: 0,
+            authority: Pubkey::new_unique(),
+            id: b"dedup".to_vec(),
+            name: "dedup".to_string(),
+            created_at: 0,
+            trigger: Trigger::Interval {
+                seconds: 10,
+                skippable: false,
+                jitter: 0,
+            },
+            schedule: Schedule::Timed { prev: 0, next },
+            fiber_ids: vec![0],
+            fiber_cursor: 0,
+            fiber_next_id: 1,
+            fiber_signal: Signal::None,
+            paused: false,
+            exec_count,
+            last_executor: Pubkey::default(),
+            nonce_account: antegen_thread_program::ID,
+            last_nonce: String::new(),
+            close_fiber: Vec::new(),
+            fee_payer_mode: None,
+            consecutive_execs: 0,
+            chain_slot: 0,
+            chain_depth: 0,
+            chain_visited: [0; 4],
+            failing_fiber: 0,
+            fiber_failures: 0,
+            last_failure_slot: 0,
+            disabled_fibers: [0; 4],
+            resume_at: None,
+        }
+    }
+
+    #[test]
+    fn test_synthetic_duplicates_dropped() {
+        let dedup = SubmissionDedup::new(Duration::from_secs(10));
+        let pubkey = Pubkey::new_unique();
+        let snapshot = thread(3, 100);
+
+        // Same snapshot from geyser and RPC reconciliation
+        assert!(dedup.check_and_insert(&pubkey, &snapshot));
+        assert!(!dedup.check_and_insert(&pubkey, &snapshot.clone()));
+        assert!(!dedup.check_and_insert(&pubkey, &snapshot));
+        assert_eq!(dedup.hits(), 2);
+
+        // Next execution (new exec_count/schedule) and other threads pass
+        assert!(dedup.check_and_insert(&pubkey, &thread(4, 110)));
+        assert!(dedup.check_and_insert(&Pubkey::new_unique(), &snapshot));
+        assert_eq!(dedup.hits(), 2);
+        assert_eq!(dedup.len(), 3);
+    }
+
+    #[test]
+    fn test_expired_entries_pass_and_are_cleaned_up() {
+        let dedup = SubmissionDedup::new(Duration::from_millis(20));
+        let pubkey = Pubkey::new_unique();
+        let snapshot = thread(0, 100);
+
+        assert!(dedup.check_and_insert(&pubkey, &snapshot));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(dedup.check_and_insert(&pubkey, &snapshot));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(dedup.cleanup(), 1);
+        assert!(dedup.is_empty());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables() {
+        let dedup = SubmissionDedup::new(Duration::ZERO);
+        let pubkey = Pubkey::new_unique();
+        let snapshot = thread(0, 100);
+        assert!(dedup.check_and_insert(&pubkey, &snapshot));
+        assert!(dedup.check_and_insert(&pubkey, &snapshot));
+        assert_eq!(dedup.hits(), 0);
+    }
+}
@@ -1,6 +1,6 @@
 //! Shared resources module
 //!
-//! Contains all shared resources (RPC client, TPU client, unified cache) and a wrapper
+//! Contains all shared resources (RPC client, TPU client, unified cache, slot feed) and a wrapper
 //! for easy passing to actors and components.
 //!
 //! The AccountCache serves dual purposes:
@@ -8,16 +8,30 @@
 //! - Deduplication of account updates via `put_if_newer()`
 
 mod cache;
+mod dedup;
+mod replay;
+mod slot;
 
 pub use cache::{AccountCache, CacheTriggerType, CachedAccount};
+pub use dedup::SubmissionDedup;
+pub use replay::{ConfirmedExecution, ConfirmedExecutions};
+pub use slot::SlotSubscription;
 
+use crate::alerts::AlertMetrics;
+use crate::batch::ExecBatcher;
 use crate::config::{ClientConfig, EndpointRole};
-use crate::rpc::{EndpointConfig, RpcPool, RpcPoolConfig};
-use crate::tpu::{TpuClient, TpuClientConfig};
+use crate::costs::CostLedger;
+use crate::datasources::clock::ClockMonitor;
+use crate::fees::FeeCeilingMetrics;
+use crate::results::{self, ResultPublisher};
+use crate::rpc::{self, EndpointConfig, RpcPool, RpcPoolConfig};
+use crate::tpu::{SubmissionMetrics, TpuClient, TpuClientConfig};
+use crate::tx_log::TxLog;
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
 
 /// Shared resources used across all actors
 ///
@@ -36,6 +50,32 @@ pub struct SharedResources {
     /// All workers share this single instance via Arc for efficient QUIC
     /// connection management.
     pub tpu_client: Option<Arc<TpuClient>>,
+    /// Single shared `slotSubscribe` feed (optional)
+    ///
+    /// Started alongside the TPU client so leader tracking and any other slot
+    /// consumer share one WebSocket subscription. Use `slot_receiver()` to
+    /// obtain a receiver.
+    pub slot_subscription: Option<Arc<SlotSubscription>>,
+    /// Per-thread execution cost ledger (optional, see `[costs]` config)
+    pub cost_ledger: Option<Arc<CostLedger>>,
+    /// Log of submitted transactions and their outcomes (optional, see `[tx_log]` config)
+    pub tx_log: Option<Arc<TxLog>>,
+    /// Counters for `SubmissionMode::Both` sends
+    pub submission_metrics: Arc<SubmissionMetrics>,
+    /// Counters for clamped and deferred priority fees
+    pub fee_metrics: Arc<FeeCeilingMetrics>,
+    /// Recently started executions, to drop duplicates from overlapping datasources
+    pub submission_dedup: Arc<SubmissionDedup>,
+    /// Last confirmed execution per thread, to refuse state that predates it
+    pub confirmed_executions: Arc<ConfirmedExecutions>,
+    /// Executions waiting to be packed into shared transactions (`submission.batch_exec`)
+    pub exec_batcher: Arc<ExecBatcher>,
+    /// When clock updates last arrived, and from which source
+    pub clock_monitor: Arc<ClockMonitor>,
+    /// Confirmation latencies, failure streaks and update times for `[alerts]`
+    pub alert_metrics: Arc<AlertMetrics>,
+    /// Publishes fiber return data (optional, see `[executor.results]`)
+    pub result_publisher: Option<Arc<ResultPublisher>>,
     /// Thread program ID (configurable, defaults to compiled-in value)
     pub program_id: Pubkey,
 }
@@ -53,7 +93,17 @@ impl SharedResources {
 
         // Custom RPC client with safe deserialization
         let endpoint_configs = EndpointConfig::from_rpc_config(&config.rpc);
-        let rpc_client = Arc::new(RpcPool::new(endpoint_configs, RpcPoolConfig::default())?);
+        let rpc_client = Arc::new(RpcPool::new(
+            endpoint_configs,
+            RpcPoolConfig::from_rpc_config(&config.rpc),
+        )?);
+        if config.rpc.preflight {
+            let reports = rpc_client.preflight().await;
+            rpc::log_results(&reports);
+            rpc::require_passing(&reports)?;
+        }
+        rpc_client.spawn_quarantine_monitor();
+        rpc_client.spawn_latency_monitor();
 
         let cache = Arc::new(AccountCache::with_config(
             &config.cache,
@@ -62,7 +112,8 @@ impl SharedResources {
             Some(eviction_tx),
         ));
 
-        // Initialize TPU client if enabled
+        // Initialize TPU client (and the shared slot feed it follows) if enabled
+        let mut slot_subscription = None;
         let tpu_client = if config.tpu.enabled {
             // Use first submission endpoint for TPU leader updates
             // This shares the same endpoint URL as the RpcPool
@@ -73,12 +124,21 @@ impl SharedResources {
                 .find(|e| matches!(e.role, EndpointRole::Submission | EndpointRole::Both))
                 .expect("Config validation ensures submission endpoint exists");
 
+            let slots = Arc::new(SlotSubscription::spawn(submission_endpoint.get_ws_url()));
+            let slot_receiver = Some(slots.subscribe());
+            slot_subscription = Some(slots);
+
             let tpu_config = TpuClientConfig {
                 rpc_url: submission_endpoint.url.clone(),
                 websocket_url: submission_endpoint.get_ws_url(),
                 num_connections: config.tpu.num_connections,
                 leaders_fanout: config.tpu.leaders_fanout,
                 worker_channel_size: config.tpu.worker_channel_size,
+                slot_receiver,
+                confirmation_rpc: rpc_client.clone(),
+                confirmation_poll_interval_ms: config.tpu.confirmation_poll_interval_ms,
+                confirmation_timeout_ms: config.tpu.confirmation_timeout_ms,
+                auto_disable: config.tpu.auto_disable,
             };
 
             match TpuClient::new(tpu_config).await {
@@ -96,17 +156,102 @@ impl SharedResources {
             None
         };
 
+        let cost_ledger = if config.costs.enabled {
+            match CostLedger::from_config(&config.costs) {
+                Ok(ledger) => Some(Arc::new(ledger)),
+                Err(e) => {
+                    log::warn!("Failed to open cost ledger, cost tracking disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let tx_log = if config.tx_log.enabled {
+            match TxLog::from_config(&config.tx_log) {
+                Ok(tx_log) => {
+                    let tx_log = Arc::new(tx_log);
+                    // Resolve submissions a previous run never saw the outcome
+                    // of, read now before this run submits anything
+                    match tx_log.unresolved() {
+                        Ok(pending) if !pending.is_empty() => {
+                            let reconciling = tx_log.clone();
+                            let rpc = rpc_client.clone();
+                            tokio::spawn(async move {
+                                match reconciling.reconcile(&rpc, &pending).await {
+                                    Ok(n) => log::info!(
+                                        "Reconciled {} unresolved logged transactions",
+                                        n
+                                    ),
+                                    Err(e) => {
+                                        log::warn!("Failed to reconcile transaction log: {}", e)
+                                    }
+                                }
+                            });
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("Failed to read transaction log: {}", e),
+                    }
+                    Some(tx_log)
+                }
+                Err(e) => {
+                    log::warn!("Failed to open transaction log, logging disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let result_publisher = if config.executor.results.is_enabled() {
+            match results::build_sinks(&config.executor.results.sinks) {
+                Ok(sinks) => Some(Arc::new(ResultPublisher::new(sinks))),
+                Err(e) => {
+                    log::warn!("Failed to build result sinks, results disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Ok((
             Self {
                 rpc_client,
                 cache,
                 tpu_client,
+                slot_subscription,
+                cost_ledger,
+                tx_log,
+                submission_metrics: Arc::new(SubmissionMetrics::default()),
+                fee_metrics: Arc::new(FeeCeilingMetrics::default()),
+                submission_dedup: Arc::new(SubmissionDedup::new(Duration::from_millis(
+                    config.processor.dedup_window_ms,
+                ))),
+                confirmed_executions: Arc::new(ConfirmedExecutions::new(
+                    config.processor.replay_guard_slots,
+                )),
+                exec_batcher: Arc::new(ExecBatcher::new(Duration::from_millis(
+                    config.submission.batch_window_ms,
+                ))),
+                clock_monitor: Arc::new(ClockMonitor::new()),
+                alert_metrics: Arc::new(AlertMetrics::new()),
+                result_publisher,
                 program_id: config.datasources.program_id,
             },
             eviction_rx,
         ))
     }
 
+    /// Receiver for the shared slot feed, if one is running
+    ///
+    /// The receiver always observes the latest slot; slow consumers skip
+    /// intermediate values rather than blocking delivery.
+    pub fn slot_receiver(&self) -> Option<watch::Receiver<u64>> {
+        self.slot_subscription.as_ref().map(|s| s.subscribe())
+    }
+
     /// Create with custom settings (for testing)
     #[cfg(test)]
     pub fn with_custom(rpc_client: Arc<RpcPool>, cache: Arc<AccountCache>) -> Self {
@@ -114,9 +259,36 @@ impl SharedResources {
             rpc_client,
             cache,
             tpu_client: None,
+            slot_subscription: None,
+            cost_ledger: None,
+            tx_log: None,
+            submission_metrics: Arc::new(SubmissionMetrics::default()),
+            fee_metrics: Arc::new(FeeCeilingMetrics::default()),
+            submission_dedup: Arc::new(SubmissionDedup::new(Duration::ZERO)),
+            confirmed_executions: Arc::new(ConfirmedExecutions::new(0)),
+            exec_batcher: Arc::new(ExecBatcher::new(Duration::ZERO)),
+            clock_monitor: Arc::new(ClockMonitor::new()),
+            alert_metrics: Arc::new(AlertMetrics::new()),
+            result_publisher: None,
             program_id: antegen_thread_program::ID,
         }
     }
+
+    /// Create with a TPU client that records its sends instead of opening
+    /// QUIC connections (for testing)
+    ///
+    /// Returns the resources and the sink holding every transaction sent via
+    /// TPU. Confirmation polls `rpc_client`.
+    #[cfg(test)]
+    pub fn with_mock_tpu(rpc_client: Arc<RpcPool>) -> (Self, Arc<crate::tpu::MockTpuSink>) {
+        let sink = Arc::new(crate::tpu::MockTpuSink::default());
+        let tpu_client = TpuClient::new_mock(sink.clone(), rpc_client.clone());
+        let resources = Self {
+            tpu_client: Some(Arc::new(tpu_client)),
+            ..Self::with_custom(rpc_client, Arc::new(AccountCache::new()))
+        };
+        (resources, sink)
+    }
 }
 
 #[cfg(test)]
@@ -144,4 +316,19 @@ mod tests {
         assert_eq!(Arc::strong_count(&resources.rpc_client), 2);
         assert_eq!(Arc::strong_count(&resources.cache), 2);
     }
+
+    #[tokio::test]
+    async fn test_resources_preflight_fails_without_passing_endpoint() {
+        let url = crate::rpc::mock::spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+        )
+        .await;
+        let mut config = ClientConfig::default();
+        config.tpu.enabled = false;
+        config.rpc.endpoints[0].url = url;
+        config.rpc.preflight = true;
+
+        let err = SharedResources::new(&config).await.err().unwrap();
+        assert!(err.to_string().contains("RPC preflight failed"));
+    }
 }
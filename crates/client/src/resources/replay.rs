@@ -0,0 +1,191 @@
+//! Replay guard for confirmed executions
+//!
+//! Once an execution confirms, the cache still holds the thread state it was
+//! built from until the datasource delivers the account update carrying the
+//! new state. A stale copy arriving in that window (a lagging RPC endpoint,
+//! the reconciliation sweep, a load balancer re-queue) looks executable and
+//! would be submitted again, outside the dedup window's reach.
+//!
+//! Each confirmed execution records the slot it landed in and the exec count
+//! it ran at. Thread state read before that slot that still shows that exec
+//! count predates the execution and must not run. Records are dropped once
+//! newer state is seen or `expiry_slots` slots have passed, so a lost update
+//! can't block a thread forever.
+
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Last confirmed execution of a thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmedExecution {
+    /// Slot the execution landed in
+    pub slot: u64,
+    /// Thread exec count the execution ran at
+    pub exec_count: u64,
+}
+
+/// Last confirmed execution per thread
+pub struct ConfirmedExecutions {
+    expiry_slots: u64,
+    entries: DashMap<Pubkey, ConfirmedExecution>,
+    latest_slot: AtomicU64,
+    hits: AtomicU64,
+}
+
+impl ConfirmedExecutions {
+    /// Create a guard whose records expire after `expiry_slots`; zero disables it
+    pub fn new(expiry_slots: u64) -> Self {
+        Self {
+            expiry_slots,
+            entries: DashMap::new(),
+            latest_slot: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether records are kept at all
+    pub fn is_enabled(&self) -> bool {
+        self.expiry_slots > 0
+    }
+
+    /// Record a confirmed execution of `thread_pubkey`
+    pub fn record(&self, thread_pubkey: Pubkey, slot: u64, exec_count: u64) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.observe_slot(slot);
+        self.entries
+            .insert(thread_pubkey, ConfirmedExecution { slot, exec_count });
+    }
+
+    /// Advance the slot records expire against
+    pub fn observe_slot(&self, slot: u64) {
+        self.latest_slot.fetch_max(slot, Ordering::Relaxed);
+    }
+
+    /// Latest slot seen by [`record`](Self::record) or [`observe_slot`](Self::observe_slot)
+    pub fn latest_slot(&self) -> u64 {
+        self.latest_slot.load(Ordering::Relaxed)
+    }
+
+    /// Check thread state read at `slot` with `exec_count` against the last
+    /// confirmed execution
+    ///
+    /// Returns the execution the state predates, in which case it must not
+    /// run. Records that are expired or superseded by the state are removed.
+    pub fn predates(
+        &self,
+        thread_pubkey: &Pubkey,
+        slot: u64,
+        exec_count: u64,
+    ) -> Option<ConfirmedExecution> {
+        let confirmed = *self.entries.get(thread_pubkey)?;
+
+        let expired = self.latest_slot() >= confirmed.slot.saturating_add(self.expiry_slots);
+        // A thread closed and recreated since starts over at exec count 0,
+        // so newer state is recognised by its slot as well
+        let superseded = slot >= confirmed.slot || exec_count > confirmed.exec_count;
+        if expired || superseded {
+            self.entries
+                .remove_if(thread_pubkey, |_, entry| *entry == confirmed);
+            return None;
+        }
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(confirmed)
+    }
+
+    /// Stale executions refused so far
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of live records
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no records are held
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove expired records, returning how many were removed
+    pub fn cleanup(&self) -> usize {
+        let before = self.entries.len();
+        let latest = self.latest_slot();
+        let expiry = self.expiry_slots;
+        self.entries
+            .retain(|_, confirmed| latest < confirmed.slot.saturating_add(expiry));
+        before.saturating_sub(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_state_refused_until_newer_state_seen() {
+        let guard = ConfirmedExecutions::new(150);
+        let pubkey = Pubkey::new_unique();
+        guard.record(pubkey, 1_000, 7);
+
+        // Read before the execution landed, still at exec count 7
+        assert_eq!(
+            guard.predates(&pubkey, 990, 7),
+            Some(ConfirmedExecution {
+                slot: 1_000,
+                exec_count: 7
+            })
+        );
+        assert_eq!(guard.hits(), 1);
+        // Other threads are unaffected
+        assert!(guard.predates(&Pubkey::new_unique(), 990, 7).is_none());
+
+        // The update carrying the execution clears the record
+        assert!(guard.predates(&pubkey, 1_001, 8).is_none());
+        assert!(guard.is_empty());
+        assert!(guard.predates(&pubkey, 990, 7).is_none());
+    }
+
+    #[test]
+    fn test_recreated_thread_not_blocked() {
+        let guard = ConfirmedExecutions::new(150);
+        let pubkey = Pubkey::new_unique();
+        guard.record(pubkey, 1_000, 7);
+        assert!(guard.predates(&pubkey, 1_005, 0).is_none());
+    }
+
+    #[test]
+    fn test_records_expire() {
+        let guard = ConfirmedExecutions::new(150);
+        let stale = Pubkey::new_unique();
+        let fresh = Pubkey::new_unique();
+        guard.record(stale, 1_000, 7);
+        guard.record(fresh, 1_100, 2);
+
+        guard.observe_slot(1_149);
+        assert!(guard.predates(&stale, 990, 7).is_some());
+        assert_eq!(guard.cleanup(), 0);
+
+        guard.observe_slot(1_150);
+        assert_eq!(guard.cleanup(), 1);
+        assert!(guard.predates(&stale, 990, 7).is_none());
+        assert!(guard.predates(&fresh, 1_050, 2).is_some());
+
+        // Slots never move backwards
+        guard.observe_slot(10);
+        assert_eq!(guard.latest_slot(), 1_150);
+    }
+
+    #[test]
+    fn test_zero_expiry_disables() {
+        let guard = ConfirmedExecutions::new(0);
+        let pubkey = Pubkey::new_unique();
+        guard.record(pubkey, 1_000, 7);
+        assert!(guard.is_empty());
+        assert!(guard.predates(&pubkey, 990, 7).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+//! Shared slot subscription
+//!
+//! Maintains a single `slotSubscribe` WebSocket subscription and broadcasts the
+//! latest slot to every consumer (e.g. TPU leader tracking) through a `watch`
+//! channel. `watch` only retains the most recent value, so a slow consumer
+//! simply skips intermediate slots and never holds up delivery to the others.
+
+use crate::rpc::websocket::{build_slot_subscribe_request, parse_slot_notification};
+use antegen_ws::Message as WsMessage;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+const KEEPALIVE: Duration = Duration::from_secs(10);
+
+/// Single slot subscription shared by all components that need the current slot
+pub struct SlotSubscription {
+    slot_tx: watch::Sender<u64>,
+    cancel: CancellationToken,
+}
+
+impl SlotSubscription {
+    /// Spawn the background subscription against `ws_url`
+    ///
+    /// The subscription is re-sent on every (re)connect. Until the first
+    /// notification arrives, receivers observe slot `0`.
+    pub fn spawn(ws_url: String) -> Self {
+        let (slot_tx, _) = watch::channel(0u64);
+        let cancel = CancellationToken::new();
+
+        let task_tx = slot_tx.clone();
+        let task_cancel = cancel.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = run_subscription(&ws_url, &task_tx) => {
+                    log::warn!("[{}] Slot subscription ended", ws_url);
+                }
+                _ = task_cancel.cancelled() => {
+                    log::debug!("[{}] Slot subscription cancelled", ws_url);
+                }
+            }
+        });
+
+        Self { slot_tx, cancel }
+    }
+
+    /// Hand out a receiver that always observes the latest slot
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.slot_tx.subscribe()
+    }
+
+    /// Latest slot seen (0 if no notification has arrived yet)
+    pub fn current_slot(&self) -> u64 {
+        *self.slot_tx.borrow()
+    }
+
+    /// Stop the background subscription
+    pub fn shutdown(&self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Drop for SlotSubscription {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+async fn run_subscription(ws_url: &str, slot_tx: &watch::Sender<u64>) {
+    let (_, subscribe_msg) = build_slot_subscribe_request();
+
+    let builder = match antegen_ws::WsClient::builder(ws_url) {
+        Ok(b) => b,
+        Err(e) => {
+            log::error!("[{}] Invalid WebSocket URL: {e}", ws_url);
+            return;
+        }
+    };
+
+    let url_on_connect = ws_url.to_string();
+    let mut handle = match builder
+        .keepalive(KEEPALIVE)
+        .on_connect(move |tx| {
+            let msg = subscribe_msg.clone();
+            let url = url_on_connect.clone();
+            async move {
+                log::debug!("[{}] WS slot connected, subscribing...", url);
+                if let Err(e) = tx.send_text(msg).await {
+                    log::error!("[{}] Failed to send slot subscription: {e}", url);
+                }
+                Ok(())
+            }
+        })
+        .build()
+        .await
+    {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!("[{}] Failed to connect WebSocket: {e}", ws_url);
+            return;
+        }
+    };
+
+    while let Some(msg) = handle.recv().await {
+        if let WsMessage::Text(text) = msg {
+            if let Some(slot) = parse_slot_notification(&text) {
+                // Only publish forward progress; reconnects can replay older slots
+                slot_tx.send_if_modified(|current| {
+                    if slot > *current {
+                        *current = slot;
+                        true
+                    } else {
+                        false
+                    }
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Fiber execution results
+//!
+//! A fiber's program can hand data back with `set_return_data`; the thread
+//! program reads it as the fiber's [`Signal`]. Once a thread's transaction
+//! confirms, the worker fetches it, takes the return data and publishes a
+//! [`FiberResult`] to every sink under `[executor.results]`.
+//!
+//! Return data is limited by the runtime to [`MAX_RETURN_DATA`] bytes, and a
+//! transaction keeps only the last value set. A transaction that chains
+//! several fibers therefore reports the data of the last fiber that set any,
+//! under the index of the first fiber it executed. Packed transactions
+//! (`submission.batch_exec`) mix threads and are not reported.
+//!
+//! Each result carries the exec sequence of that first fiber, read from the
+//! thread program's `ThreadExecuted` event. A thread and sequence identify an
+//! exec, so consumers can drop results they already saw through a replay.
+
+use crate::config::ResultSinkConfig;
+use crate::resources::SharedResources;
+use crate::rpc::SafeTransaction;
+use anchor_lang::AnchorDeserialize;
+use anchor_lang::Discriminator;
+use antegen_thread_program::events::ThreadExecuted;
+use antegen_thread_program::state::Signal;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::prelude::*;
+use serde::Serialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// Most bytes a program can set as return data
+pub const MAX_RETURN_DATA: usize = 1024;
+
+/// Timeout for posting a result to a webhook or NATS
+const SINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Log line the runtime writes when a program returns data
+const PROGRAM_RETURN_LOG: &str = "Program return: ";
+
+/// Log line the runtime writes when a program emits an event
+const PROGRAM_DATA_LOG: &str = "Program data: ";
+
+/// What one confirmed fiber execution returned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FiberResult {
+    pub thread: String,
+    pub fiber_index: u8,
+    /// Exec sequence of `fiber_index`, for deduplication
+    pub exec_sequence: Option<u64>,
+    pub signature: String,
+    pub slot: u64,
+    /// Program that set the return data
+    pub program: Option<String>,
+    /// The return data decoded as a [`Signal`], if it is one
+    pub signal: Option<String>,
+    /// Raw return data, base64
+    pub return_data: Option<String>,
+}
+
+impl FiberResult {
+    /// Extract the return data of a confirmed transaction
+    ///
+    /// The runtime logs `Program return: <program> <base64>` each time a
+    /// program returns data; the last such line wins. The transaction's
+    /// `returnData` is the fallback when logs were truncated.
+    pub fn from_transaction(
+        tx: &SafeTransaction,
+        thread: &Pubkey,
+        fiber_index: u8,
+        signature: &Signature,
+    ) -> Self {
+        let meta = tx.meta.as_ref();
+        let from_logs = meta
+            .and_then(|meta| meta.log_messages.as_ref())
+            .and_then(|logs| {
+                logs.iter().rev().find_map(|line| {
+                    let (program, data) = line.strip_prefix(PROGRAM_RETURN_LOG)?.split_once(' ')?;
+                    Some((program.to_string(), data.to_string()))
+                })
+            });
+        let returned = from_logs.or_else(|| {
+            meta.and_then(|meta| meta.return_data.as_ref())
+                .map(|data| (data.program_id.clone(), data.data.0.clone()))
+        });
+
+        let (program, return_data) = match returned {
+            Some((program, data)) if !data.is_empty() => (Some(program), Some(data)),
+            _ => (None, None),
+        };
+        let signal = return_data
+            .as_ref()
+            .and_then(|data| BASE64_STANDARD.decode(data).ok())
+            .and_then(|bytes| Signal::try_from_slice(&bytes).ok())
+            .map(|signal| format!("{:?}", signal));
+
+        let exec_sequence = meta
+            .and_then(|meta| meta.log_messages.as_ref())
+            .and_then(|logs| {
+                logs.iter()
+                    .filter_map(|line| line.strip_prefix(PROGRAM_DATA_LOG))
+                    .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+                    .filter_map(|bytes| {
+                        let data = bytes.strip_prefix(ThreadExecuted::DISCRIMINATOR)?;
+                        ThreadExecuted::try_from_slice(data).ok()
+                    })
+                    .find(|event| event.thread == *thread && event.fiber_index == fiber_index)
+            })
+            .map(|event| event.exec_sequence);
+
+        Self {
+            thread: thread.to_string(),
+            fiber_index,
+            exec_sequence,
+            signature: signature.to_string(),
+            slot: tx.slot,
+            program,
+            signal,
+            return_data,
+        }
+    }
+}
+
+/// Destination for fiber results
+#[async_trait]
+pub trait ResultSink: Send + Sync {
+    async fn publish(&self, result: &FiberResult) -> Result<()>;
+}
+
+/// Writes results to the log
+pub struct LogSink;
+
+#[async_trait]
+impl ResultSink for LogSink {
+    async fn publish(&self, result: &FiberResult) -> Result<()> {
+        log::info!(
+            "{}: fiber {} result sequence={} signal={} return_data={} ({})",
+            result.thread,
+            result.fiber_index,
+            result
+                .exec_sequence
+                .map_or_else(|| "-".to_string(), |sequence| sequence.to_string()),
+            result.signal.as_deref().unwrap_or("-"),
+            result.return_data.as_deref().unwrap_or("-"),
+            result.signature
+        );
+        Ok(())
+    }
+}
+
+/// POSTs results as JSON
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(SINK_TIMEOUT).build()?;
+        Ok(Self {
+            client,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl ResultSink for WebhookSink {
+    async fn publish(&self, result: &FiberResult) -> Result<()> {
+        let response = self.client.post(&self.url).json(result).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Result webhook returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Publishes results as JSON on a NATS subject
+///
+/// Speaks the core NATS text protocol over one connection, reconnecting
+/// when it drops. Server `PING`s are answered before each publish.
+pub struct NatsSink {
+    address: String,
+    subject: String,
+    connection: tokio::sync::Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl NatsSink {
+    /// `url` is `nats://host[:port]`, the port defaulting to 4222
+    pub fn new(url: &str, subject: impl Into<String>) -> Result<Self> {
+        let host = url
+            .strip_prefix("nats://")
+            .ok_or_else(|| anyhow!("NATS URL must start with nats://: {}", url))?
+            .trim_end_matches('/');
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:4222", host)
+        };
+        Ok(Self {
+            address,
+            subject: subject.into(),
+            connection: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    async fn connect(&self) -> Result<BufReader<TcpStream>> {
+        let mut connection = BufReader::new(TcpStream::connect(&self.address).await?);
+        let mut info = String::new();
+        connection.read_line(&mut info).await?;
+        if !info.starts_with("INFO") {
+            return Err(anyhow!("Unexpected NATS greeting: {}", info.trim_end()));
+        }
+        connection
+            .get_mut()
+            .write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"antegen\"}\r\n")
+            .await?;
+        Ok(connection)
+    }
+
+    /// Answer pending `PING`s; false if the server closed the connection
+    fn keep_alive(connection: &mut BufReader<TcpStream>) -> Result<bool> {
+        let mut buf = [0u8; 512];
+        let mut pings = 0;
+        loop {
+            match connection.get_ref().try_read(&mut buf) {
+                Ok(0) => return Ok(false),
+                Ok(n) => {
+                    pings += String::from_utf8_lossy(&buf[..n]).matches("PING").count();
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        for _ in 0..pings {
+            connection.get_ref().try_write(b"PONG\r\n")?;
+        }
+        Ok(true)
+    }
+
+    async fn send(connection: &mut BufReader<TcpStream>, frame: &[u8]) -> Result<()> {
+        if !Self::keep_alive(connection)? {
+            return Err(anyhow!("NATS connection closed"));
+        }
+        connection.get_mut().write_all(frame).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResultSink for NatsSink {
+    async fn publish(&self, result: &FiberResult) -> Result<()> {
+        let payload = serde_json::to_vec(result)?;
+        let mut frame = format!("PUB {} {}\r\n", self.subject, payload.len()).into_bytes();
+        frame.extend_from_slice(&payload);
+        frame.extend_from_slice(b"\r\n");
+
+        let mut guard = self.connection.lock().await;
+        // One reconnect if the held connection went stale
+        for _ in 0..2 {
+            if guard.is_none() {
+                *guard = Some(tokio::time::timeout(SINK_TIMEOUT, self.connect()).await??);
+            }
+            if let Some(connection) = guard.as_mut() {
+                match tokio::time::timeout(SINK_TIMEOUT, Self::send(connection, &frame)).await {
+                    Ok(Ok(())) => return Ok(()),
+                    Ok(Err(e)) => log::debug!("NATS publish to {} failed: {}", self.address, e),
+                    Err(_) => log::debug!("NATS publish to {} timed out", self.address),
+                }
+            }
+            *guard = None;
+        }
+        Err(anyhow!("Failed to publish to NATS at {}", self.address))
+    }
+}
+
+/// Build the sinks listed in the config
+pub fn build_sinks(configs: &[ResultSinkConfig]) -> Result<Vec<Box<dyn ResultSink>>> {
+    configs
+        .iter()
+        .map(|config| -> Result<Box<dyn ResultSink>> {
+            Ok(match config {
+                ResultSinkConfig::Log => Box::new(LogSink),
+                ResultSinkConfig::Nats { url, subject } => Box::new(NatsSink::new(url, subject)?),
+                ResultSinkConfig::Webhook { url } => Box::new(WebhookSink::new(url)?),
+            })
+        })
+        .collect()
+}
+
+/// Sends each fiber result to every configured sink
+pub struct ResultPublisher {
+    sinks: Vec<Box<dyn ResultSink>>,
+}
+
+impl ResultPublisher {
+    pub fn new(sinks: Vec<Box<dyn ResultSink>>) -> Self {
+        Self { sinks }
+    }
+
+    pub async fn publish(&self, result: &FiberResult) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.publish(result).await {
+                log::warn!("{}: failed to publish fiber result: {}", result.thread, e);
+            }
+        }
+    }
+}
+
+/// Fetch a confirmed transaction and publish its fiber result, if enabled
+///
+/// Runs in the background so the worker isn't held up.
+pub fn publish_confirmed_result(
+    resources: &SharedResources,
+    thread_pubkey: Pubkey,
+    fiber_index: u8,
+    signature: Signature,
+) {
+    let Some(publisher) = resources.result_publisher.clone() else {
+        return;
+    };
+    let rpc_client = resources.rpc_client.clone();
+
+    tokio::spawn(async move {
+        match rpc_client.get_transaction(&signature).await {
+            Ok(Some(tx)) => {
+                let result =
+                    FiberResult::from_transaction(&tx, &thread_pubkey, fiber_index, &signature);
+                publisher.publish(&result).await;
+            }
+            Ok(None) => log::debug!("{}: confirmed tx {} not found", thread_pubkey, signature),
+            Err(e) => log::debug!("{}: failed to fetch tx for result: {}", thread_pubkey, e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_rpc;
+    use crate::rpc::RpcPool;
+
+    fn transaction(logs: &str, return_data: &str) -> SafeTransaction {
+        let json = format!(
+            r#"{{
+                "slot": 42,
+                "blockTime": 1700000000,
+                "meta": {{
+                    "err": null,
+                    "fee": 5000,
+                    "preBalances": [],
+                    "postBalances": [],
+                    "logMessages": [{}],
+                    "returnData": {}
+                }},
+                "transaction": {{ "message": {{ "accountKeys": [] }} }}
+            }}"#,
+            logs, return_data
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_result_from_logs() {
+        let program = antegen_thread_program::ID;
+        // Borsh-encoded Signal::Chain
+        let chain = BASE64_STANDARD.encode([1u8]);
+        let logs = format!(
+            r#""Program log: Thread memo: hi", "Program return: {} {}""#,
+            program, chain
+        );
+        let thread = Pubkey::new_unique();
+        let signature = Signature::from([1; 64]);
+        let result =
+            FiberResult::from_transaction(&transaction(&logs, "null"), &thread, 2, &signature);
+
+        assert_eq!(result.thread, thread.to_string());
+        assert_eq!(result.fiber_index, 2);
+        assert_eq!(result.slot, 42);
+        assert_eq!(result.program, Some(program.to_string()));
+        assert_eq!(result.signal.as_deref(), Some("Chain"));
+        assert_eq!(result.return_data, Some(chain));
+    }
+
+    #[test]
+    fn test_result_exec_sequence() {
+        let thread = Pubkey::new_unique();
+        let executed = |thread: Pubkey, fiber_index: u8, exec_sequence: u64| {
+            let event = ThreadExecuted {
+                thread,
+                executor: Pubkey::new_unique(),
+                fiber_index,
+                exec_sequence,
+                slot: 42,
+            };
+            format!(
+                r#""Program data: {}""#,
+                BASE64_STANDARD.encode(anchor_lang::Event::data(&event))
+            )
+        };
+        // Another thread's exec and the chained fiber's exec are skipped
+        let logs = [
+            executed(Pubkey::new_unique(), 0, 9),
+            executed(thread, 0, 7),
+            executed(thread, 1, 8),
+        ]
+        .join(", ");
+
+        let result = FiberResult::from_transaction(
+            &transaction(&logs, "null"),
+            &thread,
+            0,
+            &Signature::default(),
+        );
+        assert_eq!(result.exec_sequence, Some(7));
+
+        let result = FiberResult::from_transaction(
+            &transaction(r#""Log truncated""#, "null"),
+            &thread,
+            0,
+            &Signature::default(),
+        );
+        assert_eq!(result.exec_sequence, None);
+    }
+
+    #[test]
+    fn test_result_falls_back_to_return_data() {
+        let program = Pubkey::new_unique();
+        // Not a Signal: only the raw data is reported
+        let return_data = format!(r#"{{"programId":"{}","data":["AQID","base64"]}}"#, program);
+        let result = FiberResult::from_transaction(
+            &transaction(r#""Log truncated""#, &return_data),
+            &Pubkey::new_unique(),
+            0,
+            &Signature::default(),
+        );
+        assert_eq!(result.program, Some(program.to_string()));
+        assert_eq!(result.return_data.as_deref(), Some("AQID"));
+        assert_eq!(result.signal, None);
+
+        // Nothing returned
+        let result = FiberResult::from_transaction(
+            &transaction("", "null"),
+            &Pubkey::new_unique(),
+            0,
+            &Signature::default(),
+        );
+        assert_eq!(result.program, None);
+        assert_eq!(result.return_data, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_confirmed_result() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"result":{"slot":7,"blockTime":null,"meta":{"err":null,"fee":5000,"preBalances":[],"postBalances":[],"logMessages":["Program return: AgenHfmHkJQCpp9m7RzD8U4xsMuKMmUV2nvrvBkE9dnF AQ=="],"returnData":null},"transaction":{"message":{"accountKeys":[]}}}}"#;
+        let url = spawn_mock_rpc(response).await;
+        let rpc_client = RpcPool::with_url(url).unwrap();
+        let signature = Signature::from([3; 64]);
+
+        let tx = rpc_client
+            .get_transaction(&signature)
+            .await
+            .unwrap()
+            .unwrap();
+        let result = FiberResult::from_transaction(&tx, &Pubkey::new_unique(), 1, &signature);
+        assert_eq!(result.slot, 7);
+        assert_eq!(result.signal.as_deref(), Some("Chain"));
+    }
+
+    #[test]
+    fn test_nats_address() {
+        let sink = NatsSink::new("nats://localhost", "antegen.results").unwrap();
+        assert_eq!(sink.address, "localhost:4222");
+        let sink = NatsSink::new("nats://10.0.0.1:4333/", "antegen.results").unwrap();
+        assert_eq!(sink.address, "10.0.0.1:4333");
+        assert!(NatsSink::new("localhost:4222", "antegen.results").is_err());
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Configuration types for the custom RPC client pool.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Main configuration for the RPC pool
@@ -19,6 +20,14 @@ pub struct RpcPoolConfig {
     pub retry: RetryConfig,
     /// Load balancing strategy
     pub load_balance_strategy: LoadBalanceStrategy,
+    /// Removal of long-unhealthy endpoints from rotation
+    pub quarantine: QuarantineConfig,
+    /// Cap on the total time one request may spend across all endpoints it
+    /// fails over to (milliseconds, 0 = no cap)
+    pub request_budget_ms: u64,
+    /// Longest a `sendTransaction` waits on an endpoint's rate limit before
+    /// failing over to the next endpoint instead
+    pub send_wait_deadline: Duration,
 }
 
 impl Default for RpcPoolConfig {
@@ -30,11 +39,40 @@ impl Default for RpcPoolConfig {
             health_check: HealthCheckConfig::default(),
             retry: RetryConfig::default(),
             load_balance_strategy: LoadBalanceStrategy::RoundRobin,
+            quarantine: QuarantineConfig::default(),
+            request_budget_ms: 10_000,
+            send_wait_deadline: Duration::from_millis(50),
+        }
+    }
+}
+
+impl RpcPoolConfig {
+    /// Create a pool config from the existing RpcConfig
+    pub fn from_rpc_config(config: &crate::config::RpcConfig) -> Self {
+        Self {
+            quarantine: QuarantineConfig {
+                quarantine_after: (config.quarantine_after_secs > 0)
+                    .then(|| Duration::from_secs(config.quarantine_after_secs)),
+                readmit_after_checks: config.readmit_after_checks,
+                probe_interval: Duration::from_secs(config.probe_interval_secs),
+            },
+            request_budget_ms: config.request_budget_ms,
+            send_wait_deadline: Duration::from_millis(config.send_rate_limit_deadline_ms),
+            load_balance_strategy: config.selection.into(),
+            http: HttpConfig {
+                pool_idle_timeout: Duration::from_secs(config.http_pool_idle_timeout_secs),
+                pool_max_idle_per_host: config.http_pool_max_idle,
+                ..Default::default()
+            },
+            ..Default::default()
         }
     }
 }
 
 /// HTTP client configuration
+///
+/// Each endpoint gets its own long-lived client built from this, so its
+/// connections (and TLS sessions) are reused across requests.
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
     /// Connection timeout
@@ -45,6 +83,10 @@ pub struct HttpConfig {
     pub pool_idle_timeout: Duration,
     /// Max idle connections per host
     pub pool_max_idle_per_host: usize,
+    /// TCP keep-alive interval for pooled connections
+    pub tcp_keepalive: Duration,
+    /// Negotiate HTTP/2 over TLS where the endpoint supports it
+    pub http2: bool,
 }
 
 impl Default for HttpConfig {
@@ -54,6 +96,8 @@ impl Default for HttpConfig {
             request_timeout: Duration::from_secs(30),
             pool_idle_timeout: Duration::from_secs(90),
             pool_max_idle_per_host: 10,
+            tcp_keepalive: Duration::from_secs(60),
+            http2: true,
         }
     }
 }
@@ -97,6 +141,51 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Per-method rate limits for one endpoint
+#[derive(Debug, Clone, Default)]
+pub struct MethodRateLimitConfig {
+    /// Bucket shared by every method not listed in `methods`
+    pub default: RateLimitConfig,
+    /// Buckets by JSON-RPC method name
+    pub methods: HashMap<String, RateLimitConfig>,
+}
+
+impl From<&crate::config::EndpointRateLimits> for MethodRateLimitConfig {
+    fn from(limits: &crate::config::EndpointRateLimits) -> Self {
+        // Without an explicit burst, each bucket holds one second's worth
+        let bucket = |requests_per_second: f64| RateLimitConfig {
+            requests_per_second,
+            burst_capacity: match limits.burst {
+                0 => requests_per_second.ceil().max(1.0) as u64,
+                burst => burst,
+            },
+        };
+        Self {
+            default: bucket(limits.default),
+            methods: limits
+                .methods
+                .iter()
+                .map(|(key, rps)| (method_name(key), bucket(*rps)))
+                .collect(),
+        }
+    }
+}
+
+/// JSON-RPC method name for a snake_case config key
+/// (`get_program_accounts` -> `getProgramAccounts`)
+pub fn method_name(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
 /// Health check configuration
 #[derive(Debug, Clone)]
 pub struct HealthCheckConfig {
@@ -118,6 +207,32 @@ impl Default for HealthCheckConfig {
     }
 }
 
+/// Endpoint quarantine configuration
+///
+/// An endpoint unhealthy for longer than `quarantine_after` is taken out of
+/// rotation entirely, rather than being retried by live traffic. It is probed
+/// with `getHealth` every `probe_interval` and re-admitted after
+/// `readmit_after_checks` consecutive passes.
+#[derive(Debug, Clone)]
+pub struct QuarantineConfig {
+    /// How long an endpoint must be unhealthy before quarantine (`None` disables)
+    pub quarantine_after: Option<Duration>,
+    /// Consecutive passing probes needed to re-admit an endpoint
+    pub readmit_after_checks: u32,
+    /// Interval between quarantine checks and probes
+    pub probe_interval: Duration,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            quarantine_after: None,
+            readmit_after_checks: 3,
+            probe_interval: Duration::from_secs(15),
+        }
+    }
+}
+
 /// Retry configuration
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -158,6 +273,17 @@ pub enum LoadBalanceStrategy {
     Priority,
 }
 
+impl From<crate::config::EndpointSelection> for LoadBalanceStrategy {
+    fn from(selection: crate::config::EndpointSelection) -> Self {
+        use crate::config::EndpointSelection;
+        match selection {
+            EndpointSelection::RoundRobin => LoadBalanceStrategy::RoundRobin,
+            EndpointSelection::Weighted => LoadBalanceStrategy::WeightedRoundRobin,
+            EndpointSelection::Latency => LoadBalanceStrategy::LeastLatency,
+        }
+    }
+}
+
 /// Configuration for a single RPC endpoint
 #[derive(Debug, Clone)]
 pub struct EndpointConfig {
@@ -171,6 +297,9 @@ pub struct EndpointConfig {
     pub role: EndpointRole,
     /// Custom rate limit for this endpoint (overrides global)
     pub rate_limit: Option<RateLimitConfig>,
+    /// Per-method limits every request waits on before it is sent
+    /// (`None` = requests are sent without waiting)
+    pub method_rate_limits: Option<MethodRateLimitConfig>,
 }
 
 impl EndpointConfig {
@@ -182,6 +311,7 @@ impl EndpointConfig {
             priority: 100,
             role: EndpointRole::Both,
             rate_limit: None,
+            method_rate_limits: None,
         }
     }
 
@@ -197,6 +327,12 @@ impl EndpointConfig {
         self
     }
 
+    /// Set per-method rate limits
+    pub fn with_method_rate_limits(mut self, limits: MethodRateLimitConfig) -> Self {
+        self.method_rate_limits = Some(limits);
+        self
+    }
+
     /// Set custom WebSocket URL
     pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
         self.ws_url = Some(ws_url.into());
@@ -222,6 +358,10 @@ pub enum EndpointRole {
     Datasource,
     /// Used for both submission and data fetching
     Both,
+    /// Used for transaction simulation
+    Simulation,
+    /// Used for heavy reads
+    Read,
 }
 
 impl EndpointRole {
@@ -236,21 +376,91 @@ impl EndpointRole {
     }
 }
 
+/// Kind of RPC request, for routing to endpoint roles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// `sendTransaction`
+    Submit,
+    /// `simulateTransaction`
+    Simulate,
+    /// `getProgramAccounts` and `getMultipleAccounts`
+    HeavyRead,
+    /// Every other read
+    Read,
+}
+
+impl RequestKind {
+    pub const ALL: [RequestKind; 4] = [
+        RequestKind::Submit,
+        RequestKind::Simulate,
+        RequestKind::HeavyRead,
+        RequestKind::Read,
+    ];
+
+    /// Kind of a JSON-RPC method
+    pub fn of_method(method: &str) -> Self {
+        match method {
+            "sendTransaction" => RequestKind::Submit,
+            "simulateTransaction" => RequestKind::Simulate,
+            "getProgramAccounts" | "getMultipleAccounts" => RequestKind::HeavyRead,
+            _ => RequestKind::Read,
+        }
+    }
+
+    /// Preference of `role` for this kind of request, lower first, or `None`
+    /// if endpoints with that role don't serve it
+    ///
+    /// Simulations and heavy reads go to their dedicated role first and fall
+    /// back to the general fetch roles, so configs without the dedicated
+    /// roles route as before.
+    pub fn rank(self, role: EndpointRole) -> Option<u8> {
+        use EndpointRole::*;
+        match (self, role) {
+            (RequestKind::Submit, Submission | Both) => Some(0),
+            (RequestKind::Simulate, Simulation) => Some(0),
+            (RequestKind::Simulate, Both | Datasource) => Some(1),
+            (RequestKind::HeavyRead, Read) => Some(0),
+            (RequestKind::HeavyRead, Both | Datasource) => Some(1),
+            (RequestKind::Read, Datasource | Both) => Some(0),
+            (RequestKind::Read, Read) => Some(1),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RequestKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RequestKind::Submit => "submission",
+            RequestKind::Simulate => "simulation",
+            RequestKind::HeavyRead => "heavy read",
+            RequestKind::Read => "read",
+        })
+    }
+}
+
 // Conversion from existing config types
-impl From<&crate::config::RpcEndpoint> for EndpointConfig {
-    fn from(endpoint: &crate::config::RpcEndpoint) -> Self {
-        let role = match endpoint.role {
+impl From<crate::config::EndpointRole> for EndpointRole {
+    fn from(role: crate::config::EndpointRole) -> Self {
+        match role {
             crate::config::EndpointRole::Submission => EndpointRole::Submission,
             crate::config::EndpointRole::Datasource => EndpointRole::Datasource,
             crate::config::EndpointRole::Both => EndpointRole::Both,
-        };
+            crate::config::EndpointRole::Simulation => EndpointRole::Simulation,
+            crate::config::EndpointRole::Read => EndpointRole::Read,
+        }
+    }
+}
 
+impl From<&crate::config::RpcEndpoint> for EndpointConfig {
+    fn from(endpoint: &crate::config::RpcEndpoint) -> Self {
         Self {
             url: endpoint.url.clone(),
             ws_url: endpoint.ws_url.clone(),
             priority: endpoint.priority,
-            role,
+            role: endpoint.role.clone().into(),
             rate_limit: None,
+            method_rate_limits: endpoint.rate_limits.as_ref().map(Into::into),
         }
     }
 }
@@ -3,10 +3,12 @@
 //! Tracks health, latency, and status of individual RPC endpoints.
 
 use parking_lot::RwLock;
+use reqwest::Client;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use super::config::{EndpointConfig, EndpointRole};
+use super::rate_limiter::{MethodRateLimiter, RateLimiter};
 
 /// Health status of an endpoint
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +40,41 @@ pub struct EndpointStats {
     pub last_failure: Option<Duration>,
 }
 
+/// Quarantine and probe status of an endpoint
+#[derive(Debug, Clone)]
+pub struct QuarantineStatus {
+    /// Time since the endpoint was quarantined
+    pub quarantined_for: Duration,
+    /// Consecutive passing probes so far
+    pub consecutive_passes: u32,
+    /// Failed probes since quarantine
+    pub failed_probes: u64,
+    /// Time since the last probe
+    pub last_probe: Option<Duration>,
+}
+
+/// Point-in-time view of an endpoint, see [`super::RpcPool::endpoint_states`]
+#[derive(Debug, Clone)]
+pub struct EndpointStatus {
+    pub url: String,
+    pub role: EndpointRole,
+    pub health: EndpointHealth,
+    /// How long the endpoint has been unhealthy, if it is
+    pub unhealthy_for: Option<Duration>,
+    /// Set while the endpoint is out of rotation
+    pub quarantine: Option<QuarantineStatus>,
+    /// Rolling average latency of requests and health checks, once measured
+    pub latency: Option<Duration>,
+}
+
+/// Probe bookkeeping for a quarantined endpoint
+struct Quarantine {
+    since: Instant,
+    consecutive_passes: u32,
+    failed_probes: u64,
+    last_probe: Option<Instant>,
+}
+
 /// Tracks the state of a single RPC endpoint
 pub struct EndpointState {
     /// Configuration for this endpoint
@@ -62,13 +99,27 @@ pub struct EndpointState {
     last_failure: RwLock<Option<Instant>>,
     /// Latency sample count for averaging
     latency_samples: AtomicU64,
+    /// Request budget for this endpoint
+    rate_limiter: RateLimiter,
+    /// Per-method buckets requests wait on, if configured
+    method_rate_limiter: Option<MethodRateLimiter>,
+    /// When the endpoint last became unhealthy, cleared once it recovers
+    unhealthy_since: RwLock<Option<Instant>>,
+    /// Set while the endpoint is out of rotation
+    quarantine: RwLock<Option<Quarantine>>,
+    /// Long-lived HTTP client holding this endpoint's connection pool
+    http_client: Client,
 }
 
 impl EndpointState {
-    /// Create a new endpoint state from configuration
+    /// Create a new endpoint state from configuration, with a default HTTP client
     pub fn new(config: EndpointConfig) -> Self {
+        Self::with_http_client(config, Client::new())
+    }
+
+    /// Create endpoint state that sends its requests through `http_client`
+    pub fn with_http_client(config: EndpointConfig, http_client: Client) -> Self {
         Self {
-            config,
             health: RwLock::new(EndpointHealth::Healthy),
             consecutive_failures: AtomicU64::new(0),
             consecutive_successes: AtomicU64::new(0),
@@ -79,9 +130,23 @@ impl EndpointState {
             last_success: RwLock::new(None),
             last_failure: RwLock::new(None),
             latency_samples: AtomicU64::new(0),
+            rate_limiter: RateLimiter::new(config.rate_limit.clone().unwrap_or_default()),
+            method_rate_limiter: config
+                .method_rate_limits
+                .as_ref()
+                .map(MethodRateLimiter::new),
+            unhealthy_since: RwLock::new(None),
+            quarantine: RwLock::new(None),
+            http_client,
+            config,
         }
     }
 
+    /// HTTP client (and connection pool) for this endpoint
+    pub fn http_client(&self) -> &Client {
+        &self.http_client
+    }
+
     /// Get the HTTP URL for this endpoint
     pub fn url(&self) -> &str {
         &self.config.url
@@ -117,12 +182,96 @@ impl EndpointState {
         *self.health.read()
     }
 
+    /// Take a `method` request from this endpoint's rate limit budget
+    ///
+    /// Returns false if the endpoint is currently rate limited.
+    pub fn try_acquire(&self, method: &str) -> bool {
+        match self.rate_limiter(method) {
+            Some(bucket) => bucket.try_acquire(),
+            None => self.rate_limiter.try_acquire(),
+        }
+    }
+
+    /// Bucket that `method` requests wait on before they are sent, if this
+    /// endpoint has per-method rate limits
+    pub fn rate_limiter(&self, method: &str) -> Option<&RateLimiter> {
+        self.method_rate_limiter
+            .as_ref()
+            .map(|limiter| limiter.bucket(method))
+    }
+
     /// Check if endpoint is healthy enough to receive requests
     pub fn is_available(&self) -> bool {
-        matches!(
-            self.health(),
-            EndpointHealth::Healthy | EndpointHealth::Degraded
-        )
+        !self.is_quarantined()
+            && matches!(
+                self.health(),
+                EndpointHealth::Healthy | EndpointHealth::Degraded
+            )
+    }
+
+    /// How long the endpoint has been unhealthy, if it is
+    pub fn unhealthy_for(&self) -> Option<Duration> {
+        self.unhealthy_since.read().map(|t| t.elapsed())
+    }
+
+    /// Check if the endpoint is out of rotation
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantine.read().is_some()
+    }
+
+    /// Take the endpoint out of rotation until it passes enough probes
+    pub fn quarantine(&self) {
+        self.quarantine.write().get_or_insert_with(|| Quarantine {
+            since: Instant::now(),
+            consecutive_passes: 0,
+            failed_probes: 0,
+            last_probe: None,
+        });
+    }
+
+    /// Record an out-of-band probe of a quarantined endpoint
+    ///
+    /// After `readmit_after` consecutive passes the endpoint is re-admitted
+    /// as healthy and this returns true. Probes don't count towards request
+    /// stats.
+    pub fn record_probe(&self, passed: bool, readmit_after: u32) -> bool {
+        {
+            let mut quarantine = self.quarantine.write();
+            let Some(q) = quarantine.as_mut() else {
+                return false;
+            };
+            q.last_probe = Some(Instant::now());
+            if !passed {
+                q.consecutive_passes = 0;
+                q.failed_probes += 1;
+                return false;
+            }
+            q.consecutive_passes += 1;
+            if q.consecutive_passes < readmit_after {
+                return false;
+            }
+            *quarantine = None;
+        }
+        self.mark_healthy();
+        true
+    }
+
+    /// Current health and quarantine status
+    pub fn status(&self) -> EndpointStatus {
+        let now = Instant::now();
+        EndpointStatus {
+            url: self.config.url.clone(),
+            role: self.config.role,
+            health: self.health(),
+            unhealthy_for: self.unhealthy_for(),
+            quarantine: self.quarantine.read().as_ref().map(|q| QuarantineStatus {
+                quarantined_for: now.duration_since(q.since),
+                consecutive_passes: q.consecutive_passes,
+                failed_probes: q.failed_probes,
+                last_probe: q.last_probe.map(|t| now.duration_since(t)),
+            }),
+            latency: (self.latency_samples.load(Ordering::Relaxed) > 0).then(|| self.avg_latency()),
+        }
     }
 
     /// Record a successful request
@@ -161,6 +310,23 @@ impl EndpointState {
         Duration::from_micros(self.avg_latency_us.load(Ordering::Relaxed))
     }
 
+    /// Latency used to rank this endpoint for latency-based selection
+    ///
+    /// The rolling average, doubled while the endpoint is degraded and
+    /// multiplied by one plus its consecutive failures, so a fast but failing
+    /// endpoint drops behind slower reliable ones. Unmeasured endpoints rank
+    /// as zero and are tried first.
+    pub fn selection_latency(&self) -> Duration {
+        let failures = self.consecutive_failures.load(Ordering::Relaxed) as u32;
+        let degraded = match self.health() {
+            EndpointHealth::Healthy => 1,
+            EndpointHealth::Degraded | EndpointHealth::Unhealthy => 2,
+        };
+        self.avg_latency()
+            .saturating_mul(degraded)
+            .saturating_mul(failures.saturating_add(1))
+    }
+
     /// Get statistics for this endpoint
     pub fn stats(&self) -> EndpointStats {
         let now = Instant::now();
@@ -178,17 +344,30 @@ impl EndpointState {
     /// Manually mark endpoint as unhealthy
     pub fn mark_unhealthy(&self) {
         *self.health.write() = EndpointHealth::Unhealthy;
+        self.track_unhealthy(EndpointHealth::Unhealthy);
     }
 
     /// Manually mark endpoint as degraded
     pub fn mark_degraded(&self) {
         *self.health.write() = EndpointHealth::Degraded;
+        self.track_unhealthy(EndpointHealth::Degraded);
     }
 
     /// Manually mark endpoint as healthy
     pub fn mark_healthy(&self) {
         *self.health.write() = EndpointHealth::Healthy;
         self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.track_unhealthy(EndpointHealth::Healthy);
+    }
+
+    /// Start or clear the unhealthy clock after a health change
+    fn track_unhealthy(&self, health: EndpointHealth) {
+        let mut since = self.unhealthy_since.write();
+        if health != EndpointHealth::Unhealthy {
+            *since = None;
+        } else if since.is_none() {
+            *since = Some(Instant::now());
+        }
     }
 
     /// Reset statistics
@@ -231,6 +410,7 @@ impl EndpointState {
             }
             _ => {}
         }
+        self.track_unhealthy(*health);
     }
 
     /// Check if health should be downgraded based on consecutive failures
@@ -247,6 +427,7 @@ impl EndpointState {
             }
             _ => {}
         }
+        self.track_unhealthy(*health);
     }
 }
 
@@ -257,6 +438,7 @@ impl std::fmt::Debug for EndpointState {
             .field("health", &self.health())
             .field("priority", &self.config.priority)
             .field("role", &self.config.role)
+            .field("quarantined", &self.is_quarantined())
             .finish()
     }
 }
@@ -367,6 +549,72 @@ mod tests {
         assert!(updated < Duration::from_millis(200));
     }
 
+    #[test]
+    fn test_selection_latency_penalizes_failures() {
+        let endpoint = test_endpoint();
+        assert_eq!(endpoint.selection_latency(), Duration::ZERO);
+        assert!(endpoint.status().latency.is_none());
+
+        endpoint.record_success(Duration::from_millis(10));
+        assert_eq!(endpoint.selection_latency(), Duration::from_millis(10));
+        assert_eq!(endpoint.status().latency, Some(Duration::from_millis(10)));
+
+        // Two failures triple it; a third also degrades the endpoint
+        endpoint.record_failure();
+        endpoint.record_failure();
+        assert_eq!(endpoint.selection_latency(), Duration::from_millis(30));
+        endpoint.record_failure();
+        assert_eq!(endpoint.health(), EndpointHealth::Degraded);
+        assert_eq!(endpoint.selection_latency(), Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_unhealthy_clock() {
+        let endpoint = test_endpoint();
+        assert!(endpoint.unhealthy_for().is_none());
+
+        for _ in 0..8 {
+            endpoint.record_failure();
+        }
+        assert_eq!(endpoint.health(), EndpointHealth::Unhealthy);
+        assert!(endpoint.unhealthy_for().is_some());
+
+        // Degraded is no longer unhealthy
+        for _ in 0..3 {
+            endpoint.record_success(Duration::from_millis(50));
+        }
+        assert!(endpoint.unhealthy_for().is_none());
+    }
+
+    #[test]
+    fn test_quarantine_readmits_after_consecutive_probes() {
+        let endpoint = test_endpoint();
+        endpoint.mark_unhealthy();
+        endpoint.quarantine();
+        assert!(endpoint.is_quarantined());
+        assert!(!endpoint.is_available());
+
+        // A failed probe restarts the count
+        assert!(!endpoint.record_probe(true, 3));
+        assert!(!endpoint.record_probe(false, 3));
+        assert!(!endpoint.record_probe(true, 3));
+        assert!(!endpoint.record_probe(true, 3));
+
+        let quarantine = endpoint.status().quarantine.unwrap();
+        assert_eq!(quarantine.consecutive_passes, 2);
+        assert_eq!(quarantine.failed_probes, 1);
+        assert!(quarantine.last_probe.is_some());
+
+        assert!(endpoint.record_probe(true, 3));
+        assert!(!endpoint.is_quarantined());
+        assert!(endpoint.is_available());
+        assert_eq!(endpoint.health(), EndpointHealth::Healthy);
+        assert!(endpoint.status().quarantine.is_none());
+
+        // Probes don't count as requests
+        assert_eq!(endpoint.stats().total_requests, 0);
+    }
+
     #[test]
     fn test_ws_url_derivation() {
         let endpoint = EndpointState::new(EndpointConfig::new("https://api.devnet.solana.com"));
@@ -3,6 +3,7 @@
 //! Background task that periodically checks endpoint health.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::watch;
 
@@ -66,83 +67,68 @@ impl HealthChecker {
         }
     }
 
-    /// Check all endpoints
+    /// Check all endpoints, leaving quarantined ones to the pool's own probes
     async fn check_all_endpoints(&self) {
-        for endpoint in &self.endpoints {
+        for endpoint in self.endpoints.iter().filter(|e| !e.is_quarantined()) {
             self.check_endpoint(endpoint).await;
         }
     }
 
     /// Check a single endpoint's health
     async fn check_endpoint(&self, endpoint: &EndpointState) {
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getHealth"
-        });
-
         let start = std::time::Instant::now();
-        let result = self
-            .http_client
-            .post(endpoint.url())
-            .json(&body)
-            .send()
-            .await;
-
-        match result {
-            Ok(response) => {
-                if response.status().is_success() {
-                    // Parse response to check for RPC-level health
-                    match response.text().await {
-                        Ok(text) => {
-                            if text.contains("\"result\":\"ok\"")
-                                || text.contains("\"result\": \"ok\"")
-                            {
-                                endpoint.record_success(start.elapsed());
-                                log::trace!("Health check passed for {}", endpoint.url());
-                            } else if text.contains("\"error\"") {
-                                // RPC returned an error
-                                endpoint.record_failure();
-                                log::warn!(
-                                    "Health check failed for {} (RPC error): {}",
-                                    endpoint.url(),
-                                    &text[..text.len().min(200)]
-                                );
-                            } else {
-                                // Unknown response, treat as success
-                                endpoint.record_success(start.elapsed());
-                            }
-                        }
-                        Err(e) => {
-                            endpoint.record_failure();
-                            log::warn!(
-                                "Health check failed for {} (body read error): {}",
-                                endpoint.url(),
-                                e
-                            );
-                        }
-                    }
-                } else {
-                    endpoint.record_failure();
-                    log::warn!(
-                        "Health check failed for {} (HTTP {})",
-                        endpoint.url(),
-                        response.status()
-                    );
-                }
+        match check_health(&self.http_client, endpoint.url(), self.config.timeout).await {
+            Ok(()) => {
+                endpoint.record_success(start.elapsed());
+                log::trace!("Health check passed for {}", endpoint.url());
             }
-            Err(e) => {
+            Err(reason) => {
                 endpoint.record_failure();
-                log::warn!(
-                    "Health check failed for {} (request error): {}",
-                    endpoint.url(),
-                    e
-                );
+                log::warn!("Health check failed for {} ({})", endpoint.url(), reason);
             }
         }
     }
 }
 
+/// Send `getHealth` to `url`, returning why it failed if it did
+pub async fn check_health(
+    http_client: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getHealth"
+    });
+
+    let response = http_client
+        .post(url)
+        .timeout(timeout)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("request error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    // Parse response to check for RPC-level health
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("body read error: {}", e))?;
+    if text.contains("\"result\":\"ok\"") || text.contains("\"result\": \"ok\"") {
+        Ok(())
+    } else if text.contains("\"error\"") {
+        Err(format!("RPC error: {}", &text[..text.len().min(200)]))
+    } else {
+        // Unknown response, treat as success
+        Ok(())
+    }
+}
+
 /// Spawn the health checker as a background task
 pub fn spawn_health_checker(
     endpoints: Vec<Arc<EndpointState>>,
@@ -160,7 +146,7 @@ pub fn spawn_health_checker(
 mod tests {
     use super::*;
     use crate::rpc::config::EndpointConfig;
-    use std::time::Duration;
+    use crate::rpc::mock::spawn_mock_rpc;
 
     #[tokio::test]
     async fn test_health_checker_creation() {
@@ -203,4 +189,20 @@ mod tests {
             .expect("Health checker should shut down")
             .expect("Task should complete without panic");
     }
+
+    #[tokio::test]
+    async fn test_check_health() {
+        let client = reqwest::Client::new();
+        let timeout = Duration::from_secs(5);
+
+        let ok = spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#).await;
+        assert!(check_health(&client, &ok, timeout).await.is_ok());
+
+        let behind = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#,
+        )
+        .await;
+        let reason = check_health(&client, &behind, timeout).await.unwrap_err();
+        assert!(reason.contains("Node is behind"));
+    }
 }
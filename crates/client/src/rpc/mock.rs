@@ -0,0 +1,213 @@
+//! Minimal JSON-RPC server for tests
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Serve `body` as the JSON-RPC response to every request on a local port
+pub async fn spawn_mock_rpc(body: &'static str) -> String {
+    spawn_mock_rpc_sequence(vec![body]).await.0
+}
+
+/// Serve `body` to every request, each response delayed by `delay`
+pub async fn spawn_slow_mock_rpc(body: &'static str, delay: Duration) -> String {
+    serve(vec![body], delay).await.0
+}
+
+/// Serve `bodies` in order, one per request, repeating the last one
+///
+/// Returns the server URL and the bodies of the requests received so far.
+pub async fn spawn_mock_rpc_sequence(
+    bodies: Vec<&'static str>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    serve(bodies, Duration::ZERO).await
+}
+
+async fn serve(bodies: Vec<&'static str>, delay: Duration) -> (String, Arc<Mutex<Vec<String>>>) {
+    assert!(!bodies.is_empty());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let Some(request) = read_request(&mut socket).await else {
+                continue;
+            };
+
+            let index = {
+                let mut received = received.lock();
+                received.push(request);
+                received.len() - 1
+            };
+            let body = bodies[index.min(bodies.len() - 1)];
+            tokio::time::sleep(delay).await;
+            let _ = socket.write_all(response(body, false).as_bytes()).await;
+        }
+    });
+
+    (format!("http://{}", addr), requests)
+}
+
+/// Connections seen by [`spawn_keepalive_mock_rpc`]
+#[derive(Default)]
+pub struct MockConnections {
+    accepted: AtomicUsize,
+    open: AtomicUsize,
+}
+
+impl MockConnections {
+    /// Connections accepted so far
+    pub fn accepted(&self) -> usize {
+        self.accepted.load(Ordering::SeqCst)
+    }
+
+    /// Connections the client has not closed yet
+    pub fn open(&self) -> usize {
+        self.open.load(Ordering::SeqCst)
+    }
+}
+
+/// Serve `body` to every request, keeping connections open between requests
+pub async fn spawn_keepalive_mock_rpc(body: &'static str) -> (String, Arc<MockConnections>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(MockConnections::default());
+    let stats = connections.clone();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            stats.accepted.fetch_add(1, Ordering::SeqCst);
+            stats.open.fetch_add(1, Ordering::SeqCst);
+            let stats = stats.clone();
+            tokio::spawn(async move {
+                while read_request(&mut socket).await.is_some() {
+                    if socket
+                        .write_all(response(body, true).as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                stats.open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+
+    (format!("http://{}", addr), connections)
+}
+
+/// Connections seen by [`spawn_mock_ws`]
+#[derive(Default)]
+pub struct MockWsConnections {
+    accepted: AtomicUsize,
+    subscribes: AtomicUsize,
+}
+
+impl MockWsConnections {
+    /// WebSocket connections accepted so far
+    pub fn accepted(&self) -> usize {
+        self.accepted.load(Ordering::SeqCst)
+    }
+
+    /// Subscribe requests received across all connections
+    pub fn subscribes(&self) -> usize {
+        self.subscribes.load(Ordering::SeqCst)
+    }
+}
+
+/// Serve a WebSocket that answers each connection's first request with the
+/// next session's messages
+///
+/// Every session but the last is closed once its messages are sent, so the
+/// client has to reconnect to reach the next one. The last session stays
+/// open, and later connections get no messages.
+pub async fn spawn_mock_ws(sessions: Vec<Vec<String>>) -> (String, Arc<MockWsConnections>) {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(MockWsConnections::default());
+    let stats = connections.clone();
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let index = stats.accepted.fetch_add(1, Ordering::SeqCst);
+            let messages = sessions.get(index).cloned().unwrap_or_default();
+            let last = index + 1 >= sessions.len();
+            let stats = stats.clone();
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(socket).await else {
+                    return;
+                };
+                // Wait for the subscription before sending anything
+                while let Some(Ok(msg)) = ws.next().await {
+                    if msg.is_text() {
+                        stats.subscribes.fetch_add(1, Ordering::SeqCst);
+                        break;
+                    }
+                }
+                for message in messages {
+                    if ws.send(Message::Text(message.into())).await.is_err() {
+                        return;
+                    }
+                }
+                if last {
+                    while let Some(Ok(_)) = ws.next().await {}
+                } else {
+                    let _ = ws.close(None).await;
+                }
+            });
+        }
+    });
+
+    (format!("ws://{}", addr), connections)
+}
+
+/// Read one request's headers, then the body announced by Content-Length
+///
+/// Returns `None` once the client closes the connection.
+async fn read_request(socket: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    let body_start = loop {
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        if n == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buf[..n]);
+        if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+    let headers = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+    let content_length = headers
+        .lines()
+        .find_map(|l| l.strip_prefix("content-length:"))
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while request.len() < body_start + content_length {
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    Some(String::from_utf8_lossy(&request[body_start..]).into_owned())
+}
+
+fn response(body: &str, keep_alive: bool) -> String {
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: {}\r\n\r\n{}",
+        body.len(),
+        connection,
+        body
+    )
+}
@@ -11,15 +11,21 @@
 //! - `circuit_breaker` - Circuit breaker pattern for fault tolerance
 //! - `rate_limiter` - Token bucket rate limiting
 //! - `health` - Background health checking
+//! - `preflight` - One-off endpoint checks at startup
 //! - `websocket` - Persistent WebSocket subscriptions using pws
+//! - `watch` - Account watches sharing one connection per account
 
 pub mod circuit_breaker;
 pub mod config;
 pub mod endpoint;
 pub mod health;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod pool;
+pub mod preflight;
 pub mod rate_limiter;
 pub mod response;
+pub mod watch;
 pub mod websocket;
 
 pub use circuit_breaker::*;
@@ -27,6 +33,8 @@ pub use config::*;
 pub use endpoint::*;
 pub use health::*;
 pub use pool::*;
+pub use preflight::*;
 pub use rate_limiter::*;
 pub use response::*;
+pub use watch::*;
 pub use websocket::*;
@@ -5,17 +5,33 @@
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use base64::prelude::*;
 use reqwest::Client;
 use serde_json::json;
-use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction};
-
-use super::config::{EndpointConfig, LoadBalanceStrategy, RpcPoolConfig};
-use super::endpoint::{EndpointHealth, EndpointState};
-use super::response::{RpcResponse, SafeSimulationResult, SafeUiAccount};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    account::Account, hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction,
+};
+
+use crate::metrics;
+use crate::types::AccountUpdate;
+
+use super::config::{EndpointConfig, HttpConfig, LoadBalanceStrategy, RequestKind, RpcPoolConfig};
+use super::endpoint::{EndpointHealth, EndpointState, EndpointStatus};
+use super::health::check_health;
+use super::preflight::{preflight_endpoint, EndpointPreflight};
+use super::response::{
+    RpcResponse, SafeAccountResponse, SafeSignatureInfo, SafeSignatureStatus, SafeSimulationResult,
+    SafeTransaction, SafeUiAccount, SimulationResult,
+};
+use super::watch::AccountWatchers;
+
+/// With latency-based selection, one request in this many is sent to a
+/// slower endpoint first to refresh its latency
+const LATENCY_EXPLORE_EVERY: usize = 10;
 
 /// Error types for RPC operations
 #[derive(Debug, thiserror::Error)]
@@ -34,6 +50,56 @@ pub enum RpcError {
     SimulationError(String),
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+    #[error("Retry budget of {0:?} exhausted")]
+    BudgetExhausted(Duration),
+    #[error("Rate limited on {0}")]
+    RateLimited(String),
+}
+
+/// Time left for one logical request across all endpoints it tries
+///
+/// Started when the request starts; every attempt draws on it, and once it
+/// is spent no further endpoint is tried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryBudget {
+    /// Total time the request may take (`None` = unlimited)
+    total_duration: Option<Duration>,
+    started: Instant,
+}
+
+impl RetryBudget {
+    /// Start a budget of `total_duration`
+    pub fn new(total_duration: Duration) -> Self {
+        Self {
+            total_duration: Some(total_duration),
+            started: Instant::now(),
+        }
+    }
+
+    /// A budget that never runs out
+    pub fn unlimited() -> Self {
+        Self {
+            total_duration: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Total time the request may take
+    pub fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+
+    /// Time left, `None` if unlimited
+    pub fn remaining(&self) -> Option<Duration> {
+        self.total_duration
+            .map(|total| total.saturating_sub(self.started.elapsed()))
+    }
+
+    /// Whether no time is left
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining.is_zero())
+    }
 }
 
 /// RPC response wrapper for JSON-RPC
@@ -62,12 +128,6 @@ struct BlockhashValue {
     last_valid_block_height: u64,
 }
 
-/// Account response wrapper
-#[derive(Debug, serde::Deserialize)]
-struct AccountResponse {
-    value: Option<SafeUiAccount>,
-}
-
 /// Program accounts response
 #[derive(Debug, serde::Deserialize)]
 struct ProgramAccountsItem {
@@ -75,16 +135,82 @@ struct ProgramAccountsItem {
     account: SafeUiAccount,
 }
 
+/// Result of broadcasting a transaction to one endpoint
+#[derive(Debug)]
+pub enum BroadcastResult {
+    /// The endpoint accepted the transaction
+    Sent(Signature),
+    /// The endpoint rejected the transaction or could not be reached
+    Failed(String),
+    /// The endpoint's rate limit was exhausted, so nothing was sent
+    RateLimited,
+}
+
+/// Per-endpoint outcome of [`RpcPool::broadcast_transaction`]
+#[derive(Debug)]
+pub struct BroadcastOutcome {
+    pub url: String,
+    pub result: BroadcastResult,
+}
+
+/// Build a `sendTransaction` request
+fn send_transaction_body(transaction: &Transaction) -> Result<serde_json::Value> {
+    let tx_bytes = bincode::serialize(transaction)?;
+    let tx_base64 = BASE64_STANDARD.encode(&tx_bytes);
+
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendTransaction",
+        "params": [tx_base64, {
+            "encoding": "base64",
+            "skipPreflight": false,
+            "preflightCommitment": "confirmed",
+            "maxRetries": 3
+        }]
+    }))
+}
+
+/// Extract the signature from a `sendTransaction` response
+fn parse_signature_response(response: JsonRpcResponse<String>) -> Result<Signature> {
+    let signature_str = response
+        .result
+        .ok_or_else(|| anyhow!("No result in send transaction response"))?;
+
+    signature_str
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))
+}
+
+/// Build the long-lived, connection-pooling client for one endpoint
+fn build_http_client(config: &HttpConfig) -> Result<Client> {
+    let mut builder = Client::builder()
+        .connect_timeout(config.connect_timeout)
+        .timeout(config.request_timeout)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .tcp_keepalive(config.tcp_keepalive);
+    if config.http2 {
+        // Keep idle HTTP/2 connections alive through proxies and load balancers
+        builder = builder
+            .http2_keep_alive_interval(config.tcp_keepalive)
+            .http2_keep_alive_while_idle(true);
+    } else {
+        builder = builder.http1_only();
+    }
+    Ok(builder.build()?)
+}
+
 /// Core RPC client pool
 pub struct RpcPool {
-    /// HTTP client with connection pooling
-    http_client: Client,
     /// Configured endpoints with state tracking
     endpoints: Vec<Arc<EndpointState>>,
     /// Pool configuration
     config: RpcPoolConfig,
     /// Round-robin index for load balancing
     round_robin_idx: AtomicUsize,
+    /// Open `watch_account` connections
+    watchers: AccountWatchers,
 }
 
 impl RpcPool {
@@ -94,28 +220,48 @@ impl RpcPool {
             return Err(anyhow!("At least one endpoint is required"));
         }
 
-        // Build HTTP client with configuration
-        let http_client = Client::builder()
-            .connect_timeout(config.http.connect_timeout)
-            .timeout(config.http.request_timeout)
-            .pool_idle_timeout(config.http.pool_idle_timeout)
-            .pool_max_idle_per_host(config.http.pool_max_idle_per_host)
-            .build()?;
-
-        // Create endpoint states
+        // Create endpoint states, each with its own pooled HTTP client;
+        // endpoints without their own rate limit use the pool's
         let endpoints: Vec<Arc<EndpointState>> = endpoint_configs
             .into_iter()
-            .map(|cfg| Arc::new(EndpointState::new(cfg)))
-            .collect();
+            .map(|mut cfg| {
+                cfg.rate_limit
+                    .get_or_insert_with(|| config.rate_limit.clone());
+                let http_client = build_http_client(&config.http)?;
+                Ok(Arc::new(EndpointState::with_http_client(cfg, http_client)))
+            })
+            .collect::<Result<_>>()?;
+
+        for endpoint in &endpoints {
+            let serves: Vec<String> = RequestKind::ALL
+                .into_iter()
+                .filter(|kind| kind.rank(endpoint.role()).is_some())
+                .map(|kind| kind.to_string())
+                .collect();
+            log::info!(
+                "RPC endpoint {} ({:?}) serves: {}",
+                endpoint.url(),
+                endpoint.role(),
+                serves.join(", ")
+            );
+        }
 
         Ok(Self {
-            http_client,
             endpoints,
             config,
             round_robin_idx: AtomicUsize::new(0),
+            watchers: AccountWatchers::new(),
         })
     }
 
+    /// Start the retry budget for one request, from `request_budget_ms`
+    pub fn retry_budget(&self) -> RetryBudget {
+        match self.config.request_budget_ms {
+            0 => RetryBudget::unlimited(),
+            ms => RetryBudget::new(Duration::from_millis(ms)),
+        }
+    }
+
     /// Create a pool with a single endpoint URL
     pub fn with_url(url: impl Into<String>) -> Result<Self> {
         Self::new(vec![EndpointConfig::new(url)], RpcPoolConfig::default())
@@ -133,7 +279,7 @@ impl RpcPool {
         });
 
         let response: JsonRpcResponse<BlockhashResponse> =
-            self.execute_with_failover(&body, true).await?;
+            self.execute_with_failover(&body).await?;
 
         let result = response
             .result
@@ -150,30 +296,56 @@ impl RpcPool {
 
     /// Send a transaction
     pub async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
-        let tx_bytes = bincode::serialize(transaction)?;
-        let tx_base64 = BASE64_STANDARD.encode(&tx_bytes);
-
-        let body = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sendTransaction",
-            "params": [tx_base64, {
-                "encoding": "base64",
-                "skipPreflight": false,
-                "preflightCommitment": "confirmed",
-                "maxRetries": 3
-            }]
-        });
+        let body = send_transaction_body(transaction)?;
+        let response: JsonRpcResponse<String> = self.execute_with_failover(&body).await?;
+        parse_signature_response(response)
+    }
 
-        let response: JsonRpcResponse<String> = self.execute_with_failover(&body, false).await?;
+    /// Send a transaction to every available submission endpoint at once
+    ///
+    /// All copies carry the same signature, so the cluster processes the
+    /// transaction at most once. Each send is taken from that endpoint's own
+    /// rate limit and its result counts towards that endpoint's stats.
+    pub async fn broadcast_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Vec<BroadcastOutcome>> {
+        let body = send_transaction_body(transaction)?;
 
-        let signature_str = response
-            .result
-            .ok_or_else(|| anyhow!("No result in send transaction response"))?;
+        let sends = self
+            .endpoints
+            .iter()
+            .filter(|e| e.can_submit() && e.is_available())
+            .map(|endpoint| {
+                let body = &body;
+                async move {
+                    let result = if !endpoint.try_acquire("sendTransaction") {
+                        BroadcastResult::RateLimited
+                    } else {
+                        let start = Instant::now();
+                        match self
+                            .execute_request(endpoint, body)
+                            .await
+                            .and_then(parse_signature_response)
+                        {
+                            Ok(signature) => {
+                                endpoint.record_success(start.elapsed());
+                                BroadcastResult::Sent(signature)
+                            }
+                            Err(e) => {
+                                endpoint.record_failure();
+                                BroadcastResult::Failed(e.to_string())
+                            }
+                        }
+                    };
+                    BroadcastOutcome {
+                        url: endpoint.url().to_string(),
+                        result,
+                    }
+                }
+            });
 
-        signature_str
-            .parse()
-            .map_err(|e| anyhow!("Failed to parse signature: {}", e))
+        Ok(futures::future::join_all(sends).await)
     }
 
     /// Send a transaction and wait for confirmation
@@ -214,6 +386,29 @@ impl RpcPool {
 
     /// Get account info with safe deserialization
     pub async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<SafeUiAccount>> {
+        let response = self.get_account_info(pubkey).await?;
+        Ok(response.and_then(|r| r.value))
+    }
+
+    /// Get an account along with the slot the node read it at
+    ///
+    /// The slot is taken from the response context. Callers caching the
+    /// account use it to order this read against datasource updates, so an
+    /// on-demand fetch never overwrites newer state.
+    pub async fn get_account_with_slot(&self, pubkey: &Pubkey) -> Result<(Account, u64)> {
+        let response = self
+            .get_account_info(pubkey)
+            .await?
+            .ok_or_else(|| anyhow!("No result in getAccountInfo response"))?;
+        let account = response
+            .value
+            .ok_or_else(|| anyhow!("Account {} not found", pubkey))?
+            .to_account()
+            .map_err(|e| anyhow!("Failed to decode account {}: {}", pubkey, e))?;
+        Ok((account, response.context.slot))
+    }
+
+    async fn get_account_info(&self, pubkey: &Pubkey) -> Result<Option<SafeAccountResponse>> {
         let body = json!({
             "jsonrpc": "2.0",
             "id": 1,
@@ -224,10 +419,28 @@ impl RpcPool {
             }]
         });
 
-        let response: JsonRpcResponse<AccountResponse> =
-            self.execute_with_failover(&body, true).await?;
+        let response: JsonRpcResponse<SafeAccountResponse> =
+            self.execute_with_failover(&body).await?;
+        Ok(response.result)
+    }
 
-        Ok(response.result.and_then(|r| r.value))
+    /// Stream updates to an account over an `accountSubscribe` WebSocket
+    ///
+    /// Connects to the preferred healthy endpoint for reads. Watches on the
+    /// same account and commitment share one connection, which reconnects on
+    /// its own and closes once every stream is dropped. The stream ends if
+    /// the connection can't be established.
+    pub fn watch_account(
+        &self,
+        pubkey: Pubkey,
+        commitment: CommitmentConfig,
+    ) -> impl futures::Stream<Item = AccountUpdate> + Send + 'static {
+        let ws_url = self
+            .select_endpoints(RequestKind::Read)
+            .first()
+            .unwrap_or(&self.endpoints[0])
+            .ws_url();
+        self.watchers.watch(&ws_url, pubkey, commitment)
     }
 
     /// Get account balance in lamports
@@ -246,8 +459,7 @@ impl RpcPool {
             value: u64,
         }
 
-        let response: JsonRpcResponse<BalanceResponse> =
-            self.execute_with_failover(&body, true).await?;
+        let response: JsonRpcResponse<BalanceResponse> = self.execute_with_failover(&body).await?;
 
         response
             .result
@@ -255,6 +467,24 @@ impl RpcPool {
             .ok_or_else(|| anyhow!("No result in balance response"))
     }
 
+    /// Request an airdrop of `lamports` to `pubkey` from the node's faucet
+    ///
+    /// Only test validators and devnet serve this. Returns the airdrop
+    /// transaction's signature without waiting for it to confirm.
+    pub async fn request_airdrop(&self, pubkey: &Pubkey, lamports: u64) -> Result<Signature> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "requestAirdrop",
+            "params": [pubkey.to_string(), lamports, {
+                "commitment": "confirmed"
+            }]
+        });
+
+        let response: JsonRpcResponse<String> = self.execute_with_failover(&body).await?;
+        parse_signature_response(response)
+    }
+
     /// Get multiple accounts
     pub async fn get_multiple_accounts(
         &self,
@@ -278,7 +508,7 @@ impl RpcPool {
         }
 
         let response: JsonRpcResponse<MultipleAccountsResponse> =
-            self.execute_with_failover(&body, true).await?;
+            self.execute_with_failover(&body).await?;
 
         Ok(response.result.map(|r| r.value).unwrap_or_default())
     }
@@ -306,7 +536,7 @@ impl RpcPool {
         });
 
         let response: JsonRpcResponse<Vec<ProgramAccountsItem>> =
-            self.execute_with_failover(&body, true).await?;
+            self.execute_with_failover(&body).await?;
 
         let items = response.result.unwrap_or_default();
         let mut accounts = Vec::with_capacity(items.len());
@@ -322,119 +552,231 @@ impl RpcPool {
         Ok(accounts)
     }
 
-    /// Simulate a transaction and return accounts
+    /// Simulate a transaction and return its success, logs, compute units and error
+    ///
+    /// Unlike [`Self::simulate_transaction_with_accounts`], a failed simulation
+    /// is not an error: it is reported through `SimulationResult::success`.
     pub async fn simulate_transaction(
         &self,
         transaction: &Transaction,
+    ) -> Result<SimulationResult> {
+        let response = self.simulate(transaction, None).await?;
+        Ok(response.result.value.into())
+    }
+
+    /// Simulate a transaction and return accounts
+    pub async fn simulate_transaction_with_accounts(
+        &self,
+        transaction: &Transaction,
         account_addresses: &[Pubkey],
     ) -> Result<SafeSimulationResult> {
+        let response = self.simulate(transaction, Some(account_addresses)).await?;
+
+        // Check for simulation error — surface program logs before returning
+        if let Some(err) = &response.result.value.err {
+            if let Some(logs) = &response.result.value.logs {
+                for log in logs {
+                    log::warn!("  SIM LOG: {}", log);
+                }
+            }
+            return Err(anyhow!("Simulation error: {:?}", err));
+        }
+
+        Ok(response.result)
+    }
+
+    async fn simulate(
+        &self,
+        transaction: &Transaction,
+        account_addresses: Option<&[Pubkey]>,
+    ) -> Result<RpcResponse<SafeSimulationResult>> {
         let tx_bytes = bincode::serialize(transaction)?;
         let tx_base64 = BASE64_STANDARD.encode(&tx_bytes);
 
-        let addresses: Vec<String> = account_addresses.iter().map(|p| p.to_string()).collect();
+        let mut config = json!({
+            "sigVerify": false,
+            "replaceRecentBlockhash": true,
+            "commitment": "processed",
+            "encoding": "base64"
+        });
+        if let Some(account_addresses) = account_addresses {
+            let addresses: Vec<String> = account_addresses.iter().map(|p| p.to_string()).collect();
+            config["accounts"] = json!({
+                "encoding": "base64+zstd",
+                "addresses": addresses
+            });
+        }
 
         let body = json!({
             "jsonrpc": "2.0",
             "id": 1,
             "method": "simulateTransaction",
-            "params": [tx_base64, {
-                "sigVerify": false,
-                "replaceRecentBlockhash": true,
-                "commitment": "processed",
-                "encoding": "base64",
-                "accounts": {
-                    "encoding": "base64+zstd",
-                    "addresses": addresses
-                }
-            }]
+            "params": [tx_base64, config]
         });
 
-        let response: RpcResponse<SafeSimulationResult> =
-            self.execute_with_failover(&body, true).await?;
+        self.execute_with_failover(&body).await
+    }
 
-        // Check for simulation error — surface program logs before returning
-        if let Some(err) = &response.result.value.err {
-            if let Some(logs) = &response.result.value.logs {
-                for log in logs {
-                    log::warn!("  SIM LOG: {}", log);
-                }
-            }
-            return Err(anyhow!("Simulation error: {:?}", err));
+    /// Get the pubkeys of program accounts matching `filters`, without their data
+    ///
+    /// Uses a zero-length `dataSlice` so the response stays small; fetch the
+    /// data afterwards with [`Self::get_multiple_accounts`].
+    pub async fn get_program_account_keys(
+        &self,
+        program_id: &Pubkey,
+        filters: Option<Vec<serde_json::Value>>,
+    ) -> Result<Vec<Pubkey>> {
+        let mut params = json!({
+            "encoding": "base64",
+            "commitment": "confirmed",
+            "dataSlice": { "offset": 0, "length": 0 }
+        });
+
+        if let Some(f) = filters {
+            params["filters"] = json!(f);
         }
 
-        Ok(response.result)
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getProgramAccounts",
+            "params": [program_id.to_string(), params]
+        });
+
+        #[derive(serde::Deserialize)]
+        struct KeyItem {
+            pubkey: String,
+        }
+
+        let response: JsonRpcResponse<Vec<KeyItem>> = self.execute_with_failover(&body).await?;
+
+        response
+            .result
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| {
+                item.pubkey
+                    .parse()
+                    .map_err(|e| anyhow!("Failed to parse pubkey: {}", e))
+            })
+            .collect()
     }
 
     /// Get signature status for confirmation checking
+    ///
+    /// Returns `None` until the transaction is confirmed or finalized.
     pub async fn get_signature_status(
         &self,
         signature: &Signature,
     ) -> Result<Option<Result<(), solana_sdk::transaction::TransactionError>>> {
+        let status = self
+            .get_signature_statuses(std::slice::from_ref(signature))
+            .await?
+            .into_iter()
+            .next()
+            .flatten();
+
+        match status {
+            Some(status) if status.reached(CommitmentConfig::confirmed()) => {
+                Ok(Some(status.transaction_error().map_or(Ok(()), Err)))
+            }
+            _ => Ok(None), // Not found or not yet confirmed
+        }
+    }
+
+    /// Get the statuses of several signatures, in the same order
+    ///
+    /// Entries are `None` for signatures the node has not seen.
+    pub async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<SafeSignatureStatus>>> {
+        let signatures: Vec<String> = signatures.iter().map(|s| s.to_string()).collect();
         let body = json!({
             "jsonrpc": "2.0",
             "id": 1,
             "method": "getSignatureStatuses",
-            "params": [[signature.to_string()]]
+            "params": [signatures]
         });
 
         #[derive(serde::Deserialize)]
-        struct SignatureStatusResponse {
-            value: Vec<Option<SignatureStatus>>,
+        struct SignatureStatusesResponse {
+            value: Vec<Option<SafeSignatureStatus>>,
         }
 
-        #[derive(serde::Deserialize)]
-        struct SignatureStatus {
-            err: Option<serde_json::Value>,
-            #[serde(rename = "confirmationStatus")]
-            confirmation_status: Option<String>,
+        let response: JsonRpcResponse<SignatureStatusesResponse> =
+            self.execute_with_failover(&body).await?;
+
+        Ok(response.result.map(|r| r.value).unwrap_or_default())
+    }
+
+    /// Get confirmed signatures involving `address`, newest first
+    ///
+    /// Pass the oldest signature of the previous page as `before` to paginate.
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        limit: usize,
+    ) -> Result<Vec<SafeSignatureInfo>> {
+        let mut params = json!({
+            "commitment": "confirmed",
+            "limit": limit
+        });
+
+        if let Some(sig) = before {
+            params["before"] = json!(sig.to_string());
         }
 
-        let response: JsonRpcResponse<SignatureStatusResponse> =
-            self.execute_with_failover(&body, true).await?;
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getSignaturesForAddress",
+            "params": [address.to_string(), params]
+        });
 
-        let statuses = response.result.map(|r| r.value).unwrap_or_default();
+        let response: JsonRpcResponse<Vec<SafeSignatureInfo>> =
+            self.execute_with_failover(&body).await?;
 
-        if let Some(Some(status)) = statuses.into_iter().next() {
-            // Check confirmation status
-            let confirmed = status
-                .confirmation_status
-                .map(|s| s == "confirmed" || s == "finalized")
-                .unwrap_or(false);
+        Ok(response.result.unwrap_or_default())
+    }
 
-            if !confirmed {
-                return Ok(None); // Not yet confirmed
-            }
+    /// Get a confirmed transaction with its status metadata
+    pub async fn get_transaction(&self, signature: &Signature) -> Result<Option<SafeTransaction>> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTransaction",
+            "params": [signature.to_string(), {
+                "encoding": "json",
+                "commitment": "confirmed",
+                "maxSupportedTransactionVersion": 0
+            }]
+        });
 
-            // Check for error
-            if let Some(err) = status.err {
-                // Try to parse into TransactionError
-                // For now, return a generic error
-                return Ok(Some(Err(
-                    solana_sdk::transaction::TransactionError::InstructionError(
-                        0,
-                        solana_sdk::instruction::InstructionError::Custom(
-                            err.get("InstructionError")
-                                .and_then(|e| e.get(1))
-                                .and_then(|e| e.get("Custom"))
-                                .and_then(|e| e.as_u64())
-                                .unwrap_or(0) as u32,
-                        ),
-                    ),
-                )));
-            }
+        let response: JsonRpcResponse<SafeTransaction> = self.execute_with_failover(&body).await?;
 
-            Ok(Some(Ok(())))
-        } else {
-            Ok(None) // Signature not found
-        }
+        Ok(response.result)
     }
 
     /// Execute a request with failover across healthy endpoints
-    async fn execute_with_failover<T>(&self, body: &serde_json::Value, read_only: bool) -> Result<T>
+    ///
+    /// Endpoints are chosen by the request's method, see [`RequestKind`].
+    /// All attempts share one [`RetryBudget`]; once it is spent the last
+    /// error is returned without trying the remaining endpoints.
+    ///
+    /// On endpoints with per-method rate limits, each attempt first waits for
+    /// a token from its method's bucket. A `sendTransaction` that would wait
+    /// longer than `send_wait_deadline` moves on to the next endpoint, unless
+    /// it is the last one.
+    async fn execute_with_failover<T>(&self, body: &serde_json::Value) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
-        let endpoints = self.select_endpoints(read_only);
+        let budget = self.retry_budget();
+        let method = body["method"].as_str().unwrap_or_default();
+        let kind = RequestKind::of_method(method);
+        let endpoints = self.select_endpoints(kind);
 
         if endpoints.is_empty() {
             return Err(anyhow!(RpcError::NoHealthyEndpoints));
@@ -442,16 +784,70 @@ impl RpcPool {
 
         let mut last_error = None;
 
-        for endpoint in &endpoints {
-            let start = Instant::now();
+        for (tried, endpoint) in endpoints.iter().enumerate() {
+            if budget.is_exhausted() {
+                log::warn!(
+                    "RPC {} gave up after {:?}, {} endpoint(s) not tried",
+                    method,
+                    budget.total_duration().unwrap_or_default(),
+                    endpoints.len() - tried
+                );
+                break;
+            }
 
-            match self.execute_request(endpoint, body).await {
+            if let Some(bucket) = endpoint.rate_limiter(method) {
+                let is_last = tried + 1 == endpoints.len();
+                if kind == RequestKind::Submit
+                    && !is_last
+                    && bucket.time_until_available() > self.config.send_wait_deadline
+                {
+                    log::debug!(
+                        "RPC {} rate limited on {}, trying next endpoint",
+                        method,
+                        endpoint.url()
+                    );
+                    last_error = Some(anyhow!(RpcError::RateLimited(endpoint.url().to_string())));
+                    continue;
+                }
+
+                let acquire = bucket.acquire();
+                let waited = match budget.remaining() {
+                    Some(remaining) => match tokio::time::timeout(remaining, acquire).await {
+                        Ok(waited) => waited,
+                        Err(_) => {
+                            last_error = Some(anyhow!(RpcError::BudgetExhausted(
+                                budget.total_duration().unwrap_or_default()
+                            )));
+                            break;
+                        }
+                    },
+                    None => acquire.await,
+                };
+                metrics::record_rate_limit_wait(method, waited);
+            }
+
+            let start = Instant::now();
+            let request = self.execute_request(endpoint, body);
+            let result = match budget.remaining() {
+                Some(remaining) => tokio::time::timeout(remaining, request)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(anyhow!(RpcError::BudgetExhausted(
+                            budget.total_duration().unwrap_or_default()
+                        )))
+                    }),
+                None => request.await,
+            };
+
+            match result {
                 Ok(response) => {
                     endpoint.record_success(start.elapsed());
+                    metrics::record_rpc_call(method, true, start.elapsed());
                     return Ok(response);
                 }
                 Err(e) => {
                     endpoint.record_failure();
+                    metrics::record_rpc_call(method, false, start.elapsed());
                     log::warn!("RPC request failed for {}: {}", endpoint.url(), e);
                     last_error = Some(e);
                 }
@@ -470,8 +866,8 @@ impl RpcPool {
     where
         T: serde::de::DeserializeOwned,
     {
-        let response = self
-            .http_client
+        let response = endpoint
+            .http_client()
             .post(endpoint.url())
             .json(body)
             .send()
@@ -508,20 +904,14 @@ impl RpcPool {
         })
     }
 
-    /// Select endpoints for a request based on load balancing strategy
-    fn select_endpoints(&self, read_only: bool) -> Vec<Arc<EndpointState>> {
+    /// Select endpoints for a request, preferred roles first and ordered by
+    /// the load balancing strategy within each role preference
+    fn select_endpoints(&self, kind: RequestKind) -> Vec<Arc<EndpointState>> {
         // Filter by role and health
         let available: Vec<_> = self
             .endpoints
             .iter()
-            .filter(|e| {
-                let role_ok = if read_only {
-                    e.can_fetch()
-                } else {
-                    e.can_submit()
-                };
-                role_ok && e.is_available()
-            })
+            .filter(|e| kind.rank(e.role()).is_some() && e.is_available())
             .cloned()
             .collect();
 
@@ -529,6 +919,14 @@ impl RpcPool {
             return vec![];
         }
 
+        // Stable sort keeps the strategy's order within each preference
+        let mut ordered = self.order_by_strategy(available);
+        ordered.sort_by_key(|e| kind.rank(e.role()));
+        ordered
+    }
+
+    /// Order endpoints by the load balancing strategy
+    fn order_by_strategy(&self, available: Vec<Arc<EndpointState>>) -> Vec<Arc<EndpointState>> {
         match self.config.load_balance_strategy {
             LoadBalanceStrategy::RoundRobin => {
                 let idx = self.round_robin_idx.fetch_add(1, Ordering::Relaxed);
@@ -547,9 +945,16 @@ impl RpcPool {
                 sorted
             }
             LoadBalanceStrategy::LeastLatency => {
-                // Sort by average latency
                 let mut sorted = available;
-                sorted.sort_by_key(|e| e.avg_latency());
+                sorted.sort_by_key(|e| e.selection_latency());
+
+                // Every so often lead with a slower endpoint, in turn, so its
+                // latency keeps being measured
+                let idx = self.round_robin_idx.fetch_add(1, Ordering::Relaxed);
+                if sorted.len() > 1 && idx % LATENCY_EXPLORE_EVERY == LATENCY_EXPLORE_EVERY - 1 {
+                    let explored = 1 + (idx / LATENCY_EXPLORE_EVERY) % (sorted.len() - 1);
+                    sorted[..=explored].rotate_right(1);
+                }
                 sorted
             }
             LoadBalanceStrategy::WeightedRoundRobin => {
@@ -570,6 +975,183 @@ impl RpcPool {
             .collect()
     }
 
+    /// Health and quarantine status of every endpoint, including the probe
+    /// progress of quarantined ones
+    pub fn endpoint_states(&self) -> Vec<EndpointStatus> {
+        self.endpoints.iter().map(|e| e.status()).collect()
+    }
+
+    /// Quarantine endpoints unhealthy for longer than `quarantine_after`
+    ///
+    /// An endpoint stays in rotation while it is the last available one for
+    /// any kind of request it serves, so the pool never strands a request
+    /// kind. Returns the URLs quarantined by this call.
+    fn quarantine_unhealthy(&self) -> Vec<String> {
+        let Some(quarantine_after) = self.config.quarantine.quarantine_after else {
+            return vec![];
+        };
+
+        let mut quarantined = Vec::new();
+        for endpoint in &self.endpoints {
+            if endpoint.is_quarantined()
+                || !endpoint
+                    .unhealthy_for()
+                    .is_some_and(|d| d >= quarantine_after)
+            {
+                continue;
+            }
+
+            let covered = RequestKind::ALL
+                .into_iter()
+                .filter(|kind| kind.rank(endpoint.role()).is_some())
+                .all(|kind| {
+                    self.endpoints.iter().any(|other| {
+                        !Arc::ptr_eq(other, endpoint)
+                            && other.is_available()
+                            && kind.rank(other.role()).is_some()
+                    })
+                });
+            if !covered {
+                log::debug!(
+                    "Not quarantining {}: no other healthy endpoint serves its requests",
+                    endpoint.url()
+                );
+                continue;
+            }
+
+            endpoint.quarantine();
+            log::warn!(
+                "Quarantined RPC endpoint {} after {:?} unhealthy",
+                endpoint.url(),
+                quarantine_after
+            );
+            quarantined.push(endpoint.url().to_string());
+        }
+        quarantined
+    }
+
+    /// Probe each quarantined endpoint once, returning the URLs re-admitted
+    async fn probe_quarantined(&self) -> Vec<String> {
+        let quarantine = &self.config.quarantine;
+        let mut readmitted = Vec::new();
+        for endpoint in self.endpoints.iter().filter(|e| e.is_quarantined()) {
+            let result = check_health(
+                endpoint.http_client(),
+                endpoint.url(),
+                self.config.health_check.timeout,
+            )
+            .await;
+            if let Err(reason) = &result {
+                log::debug!(
+                    "Probe of quarantined {} failed ({})",
+                    endpoint.url(),
+                    reason
+                );
+            }
+            if endpoint.record_probe(result.is_ok(), quarantine.readmit_after_checks) {
+                log::info!(
+                    "Re-admitted RPC endpoint {} after {} passing probes",
+                    endpoint.url(),
+                    quarantine.readmit_after_checks
+                );
+                readmitted.push(endpoint.url().to_string());
+            }
+        }
+        readmitted
+    }
+
+    /// Run one round of quarantine: move long-unhealthy endpoints out of
+    /// rotation, then probe the quarantined ones
+    pub async fn quarantine_tick(&self) {
+        self.quarantine_unhealthy();
+        self.probe_quarantined().await;
+    }
+
+    /// Spawn the background quarantine loop, if quarantine is enabled
+    ///
+    /// The task holds a weak reference and exits once the pool is dropped.
+    pub fn spawn_quarantine_monitor(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        if self.config.quarantine.quarantine_after.is_none() {
+            return None;
+        }
+
+        let pool = Arc::downgrade(self);
+        let probe_interval = self.config.quarantine.probe_interval;
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(probe_interval);
+            loop {
+                interval.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.quarantine_tick().await;
+            }
+        }))
+    }
+
+    /// Send `getHealth` to every endpoint in rotation, feeding the result
+    /// into its health and rolling latency
+    pub async fn measure_latency(&self) {
+        let timeout = self.config.health_check.timeout;
+        futures::future::join_all(self.endpoints.iter().filter(|e| !e.is_quarantined()).map(
+            |endpoint| async move {
+                let start = Instant::now();
+                match check_health(endpoint.http_client(), endpoint.url(), timeout).await {
+                    Ok(()) => endpoint.record_success(start.elapsed()),
+                    Err(reason) => {
+                        endpoint.record_failure();
+                        log::debug!("Health check failed for {} ({})", endpoint.url(), reason);
+                    }
+                }
+            },
+        ))
+        .await;
+    }
+
+    /// Spawn a background health check loop, if endpoints are selected by
+    /// latency, so idle endpoints keep a current measurement
+    ///
+    /// The task holds a weak reference and exits once the pool is dropped.
+    pub fn spawn_latency_monitor(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        if self.config.load_balance_strategy != LoadBalanceStrategy::LeastLatency {
+            return None;
+        }
+
+        let pool = Arc::downgrade(self);
+        let check_interval = self.config.health_check.interval;
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(check_interval);
+            loop {
+                interval.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.measure_latency().await;
+            }
+        }))
+    }
+
+    /// Run the startup preflight against every endpoint at once
+    ///
+    /// Endpoints failing any check are marked unhealthy, so they get no
+    /// traffic until they recover.
+    pub async fn preflight(&self) -> Vec<EndpointPreflight> {
+        let timeout = self.config.health_check.timeout;
+        let reports = futures::future::join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| preflight_endpoint(endpoint, timeout)),
+        )
+        .await;
+
+        for (endpoint, report) in self.endpoints.iter().zip(&reports) {
+            if !report.passed() {
+                endpoint.mark_unhealthy();
+            }
+        }
+        reports
+    }
+
     /// Get number of healthy endpoints
     pub fn healthy_count(&self) -> usize {
         self.endpoints
@@ -611,6 +1193,11 @@ impl std::fmt::Debug for RpcPool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc::config::{MethodRateLimitConfig, QuarantineConfig, RateLimitConfig};
+    use crate::rpc::mock::{
+        spawn_keepalive_mock_rpc, spawn_mock_rpc, spawn_mock_rpc_sequence, spawn_slow_mock_rpc,
+    };
+    use std::collections::HashMap;
 
     #[test]
     fn test_pool_creation() {
@@ -640,6 +1227,109 @@ mod tests {
         assert_eq!(pool.healthy_count(), 2);
     }
 
+    #[test]
+    fn test_routing_by_method() {
+        use crate::rpc::EndpointRole;
+
+        let pool = RpcPool::new(
+            vec![
+                EndpointConfig::new("https://submit.example.com")
+                    .with_role(EndpointRole::Submission),
+                EndpointConfig::new("https://both.example.com").with_role(EndpointRole::Both),
+                EndpointConfig::new("https://sim.example.com").with_role(EndpointRole::Simulation),
+                EndpointConfig::new("https://read.example.com").with_role(EndpointRole::Read),
+            ],
+            RpcPoolConfig {
+                load_balance_strategy: LoadBalanceStrategy::Priority,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let route = |method: &str| -> Vec<String> {
+            pool.select_endpoints(RequestKind::of_method(method))
+                .iter()
+                .map(|e| e.url().replace("https://", "").replace(".example.com", ""))
+                .collect()
+        };
+
+        assert_eq!(route("sendTransaction"), vec!["submit", "both"]);
+        assert_eq!(route("simulateTransaction"), vec!["sim", "both"]);
+        assert_eq!(route("getProgramAccounts"), vec!["read", "both"]);
+        assert_eq!(route("getMultipleAccounts"), vec!["read", "both"]);
+        assert_eq!(route("getAccountInfo"), vec!["both", "read"]);
+        assert_eq!(route("getLatestBlockhash"), vec!["both", "read"]);
+
+        // Without a healthy dedicated endpoint, simulations fall back to Both
+        pool.mark_unhealthy("https://sim.example.com");
+        assert_eq!(route("simulateTransaction"), vec!["both"]);
+    }
+
+    #[test]
+    fn test_latency_selection_prefers_fast_reliable_endpoints() {
+        let pool = RpcPool::new(
+            vec![
+                EndpointConfig::new("https://slow.example.com"),
+                EndpointConfig::new("https://fast.example.com"),
+                EndpointConfig::new("https://mid.example.com"),
+            ],
+            RpcPoolConfig {
+                load_balance_strategy: LoadBalanceStrategy::LeastLatency,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for (endpoint, ms) in pool.endpoints.iter().zip([300, 20, 80]) {
+            endpoint.record_success(Duration::from_millis(ms));
+        }
+        let firsts = |pool: &RpcPool| -> Vec<String> {
+            (0..LATENCY_EXPLORE_EVERY * 2)
+                .map(|_| {
+                    pool.select_endpoints(RequestKind::Read)[0]
+                        .url()
+                        .replace("https://", "")
+                        .replace(".example.com", "")
+                })
+                .collect()
+        };
+
+        // The fastest leads, apart from one exploring request in ten that
+        // goes to each slower endpoint in turn
+        let leads = firsts(&pool);
+        assert_eq!(leads.iter().filter(|url| *url == "fast").count(), 18);
+        assert!(leads.contains(&"mid".to_string()));
+        assert!(leads.contains(&"slow".to_string()));
+
+        // Failures push the fast endpoint behind a slower reliable one
+        pool.endpoints[1].record_failure();
+        pool.endpoints[1].record_failure();
+        pool.endpoints[1].record_failure();
+        let leads = firsts(&pool);
+        assert_eq!(leads.iter().filter(|url| *url == "mid").count(), 18);
+    }
+
+    #[tokio::test]
+    async fn test_measure_latency() {
+        let ok = spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#).await;
+        let behind = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#,
+        )
+        .await;
+        let pool = Arc::new(
+            RpcPool::new(
+                vec![EndpointConfig::new(ok), EndpointConfig::new(behind)],
+                RpcPoolConfig::default(),
+            )
+            .unwrap(),
+        );
+        assert!(pool.spawn_latency_monitor().is_none());
+
+        pool.measure_latency().await;
+        let states = pool.endpoint_states();
+        assert!(states[0].latency.is_some());
+        assert!(states[1].latency.is_none());
+        assert_eq!(pool.stats()[1].1.failed_requests, 1);
+    }
+
     #[test]
     fn test_mark_unhealthy() {
         let pool = RpcPool::new(
@@ -657,4 +1347,407 @@ mod tests {
         pool.mark_healthy("https://api.devnet.solana.com");
         assert_eq!(pool.healthy_count(), 2);
     }
+
+    #[tokio::test]
+    async fn test_preflight_marks_failing_endpoints() {
+        use crate::rpc::EndpointRole;
+
+        let (good, _) = spawn_mock_rpc_sequence(vec![
+            r#"{"jsonrpc":"2.0","id":1,"result":{"solana-core":"2.3.0"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"blockhash":"11111111111111111111111111111111","lastValidBlockHeight":100}}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":null,"logs":[]}}}"#,
+        ])
+        .await;
+        let bad = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+        )
+        .await;
+        let pool = RpcPool::new(
+            vec![
+                EndpointConfig::new(&good).with_role(EndpointRole::Submission),
+                EndpointConfig::new(&bad).with_role(EndpointRole::Submission),
+            ],
+            RpcPoolConfig::default(),
+        )
+        .unwrap();
+
+        let reports = pool.preflight().await;
+        assert!(reports[0].passed());
+        assert!(!reports[1].passed());
+        assert_eq!(pool.healthy_count(), 1);
+        let states = pool.endpoint_states();
+        assert_eq!(states[1].url, bad);
+        assert_eq!(states[1].health, EndpointHealth::Unhealthy);
+    }
+
+    fn quarantine_pool(urls: Vec<String>) -> RpcPool {
+        RpcPool::new(
+            urls.into_iter().map(EndpointConfig::new).collect(),
+            RpcPoolConfig {
+                quarantine: QuarantineConfig {
+                    quarantine_after: Some(std::time::Duration::ZERO),
+                    readmit_after_checks: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_and_readmit() {
+        let (dead, _) = spawn_mock_rpc_sequence(vec![
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#,
+        ])
+        .await;
+        let live = spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#).await;
+        let pool = quarantine_pool(vec![dead.clone(), live.clone()]);
+
+        pool.mark_unhealthy(&dead);
+        assert_eq!(pool.quarantine_unhealthy(), vec![dead.clone()]);
+        assert_eq!(pool.select_endpoints(RequestKind::Read).len(), 1);
+
+        // First probe fails, then two passes re-admit it
+        assert!(pool.probe_quarantined().await.is_empty());
+        assert!(pool.probe_quarantined().await.is_empty());
+        let states = pool.endpoint_states();
+        let quarantine = states[0].quarantine.as_ref().unwrap();
+        assert_eq!(quarantine.failed_probes, 1);
+        assert_eq!(quarantine.consecutive_passes, 1);
+        assert!(states[1].quarantine.is_none());
+
+        assert_eq!(pool.probe_quarantined().await, vec![dead.clone()]);
+        assert!(pool.endpoint_states()[0].quarantine.is_none());
+        assert_eq!(pool.select_endpoints(RequestKind::Read).len(), 2);
+    }
+
+    #[test]
+    fn test_never_quarantines_last_endpoint() {
+        let pool = quarantine_pool(vec![
+            "https://a.example.com".to_string(),
+            "https://b.example.com".to_string(),
+        ]);
+
+        pool.mark_unhealthy("https://a.example.com");
+        pool.mark_unhealthy("https://b.example.com");
+        assert!(pool.quarantine_unhealthy().is_empty());
+
+        // Once the other endpoint recovers, the dead one can leave rotation
+        pool.mark_healthy("https://b.example.com");
+        assert_eq!(pool.quarantine_unhealthy(), vec!["https://a.example.com"]);
+
+        // ...but the remaining one never follows it
+        pool.mark_unhealthy("https://b.example.com");
+        assert!(pool.quarantine_unhealthy().is_empty());
+        assert!(pool.endpoint_states()[1].quarantine.is_none());
+    }
+
+    #[test]
+    fn test_quarantine_disabled_by_default() {
+        let pool = RpcPool::with_url("https://api.devnet.solana.com").unwrap();
+        pool.mark_unhealthy("https://api.devnet.solana.com");
+        assert!(pool.quarantine_unhealthy().is_empty());
+    }
+
+    fn test_transaction() -> Transaction {
+        use solana_sdk::{
+            instruction::Instruction, message::Message, signature::Keypair, signer::Signer,
+        };
+
+        let payer = Keypair::new();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        Transaction::new(&[&payer], message, Hash::default())
+    }
+
+    #[tokio::test]
+    async fn test_simulate_transaction_success() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":null,"logs":["Program 11111111111111111111111111111111 success"],"unitsConsumed":150,"accounts":null,"returnData":null}}}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let result = pool
+            .simulate_transaction(&test_transaction())
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.compute_units_consumed, 150);
+        assert_eq!(result.logs.len(), 1);
+        assert!(result.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_transaction_failure() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":{"InstructionError":[0,{"Custom":1}]},"logs":["Program log: insufficient funds"],"unitsConsumed":300,"accounts":null,"returnData":null}}}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        // A failed simulation is a result, not an error
+        let result = pool
+            .simulate_transaction(&test_transaction())
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.compute_units_consumed, 300);
+        assert_eq!(result.logs, vec!["Program log: insufficient funds"]);
+        assert_eq!(
+            result.error.as_deref(),
+            Some(r#"{"InstructionError":[0,{"Custom":1}]}"#)
+        );
+
+        // The account-returning variant still treats it as an error
+        assert!(pool
+            .simulate_transaction_with_accounts(&test_transaction(), &[])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_program_account_keys() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":[{"pubkey":"11111111111111111111111111111111","account":{"data":["","base64"],"executable":false,"lamports":1,"owner":"11111111111111111111111111111111","rentEpoch":0,"space":0}}]}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let keys = pool
+            .get_program_account_keys(&Pubkey::new_unique(), None)
+            .await
+            .unwrap();
+        assert_eq!(keys, vec![Pubkey::default()]);
+    }
+
+    #[tokio::test]
+    async fn test_get_account_with_slot() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"apiVersion":"2.2.0","slot":4242},"value":{"data":["AQID","base64"],"executable":false,"lamports":7,"owner":"11111111111111111111111111111111","rentEpoch":0,"space":3}}}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let (account, slot) = pool
+            .get_account_with_slot(&Pubkey::new_unique())
+            .await
+            .unwrap();
+        assert_eq!(slot, 4242);
+        assert_eq!(account.data, vec![1, 2, 3]);
+        assert_eq!(account.lamports, 7);
+    }
+
+    #[tokio::test]
+    async fn test_get_account_with_slot_not_found() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":null}}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let err = pool
+            .get_account_with_slot(&Pubkey::new_unique())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_get_signature_statuses() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":5},"value":[{"slot":4,"confirmations":null,"err":{"InstructionError":[0,{"Custom":6004}]},"status":{"Err":{}},"confirmationStatus":"finalized"},null]}}"#,
+        )
+        .await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let statuses = pool
+            .get_signature_statuses(&[Signature::default(), Signature::default()])
+            .await
+            .unwrap();
+        assert_eq!(statuses.len(), 2);
+        let status = statuses[0].as_ref().unwrap();
+        assert_eq!(status.slot, 4);
+        assert!(status.reached(CommitmentConfig::confirmed()));
+        assert!(status.reached(CommitmentConfig::finalized()));
+        assert!(statuses[1].is_none());
+
+        // The single-signature helper surfaces the custom error code
+        let result = pool
+            .get_signature_status(&Signature::default())
+            .await
+            .unwrap();
+        assert!(format!("{:?}", result).contains("6004"));
+    }
+
+    #[tokio::test]
+    async fn test_request_airdrop() {
+        const AIRDROP: &str = r#"{"jsonrpc":"2.0","id":1,"result":"5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"}"#;
+        let (url, requests) = spawn_mock_rpc_sequence(vec![AIRDROP]).await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        let pubkey = Pubkey::new_unique();
+        let signature = pool.request_airdrop(&pubkey, 1_000_000_000).await.unwrap();
+        assert_eq!(
+            signature.to_string(),
+            "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+        );
+
+        let requests = requests.lock();
+        assert!(requests[0].contains("requestAirdrop"));
+        assert!(requests[0].contains(&pubkey.to_string()));
+        assert!(requests[0].contains("1000000000"));
+    }
+
+    fn method_limits(method: &str, requests_per_second: f64) -> MethodRateLimitConfig {
+        MethodRateLimitConfig {
+            default: RateLimitConfig::default(),
+            methods: HashMap::from([(
+                method.to_string(),
+                RateLimitConfig {
+                    requests_per_second,
+                    burst_capacity: 1,
+                },
+            )]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_method_rate_limit_holds_concurrent_requests() {
+        const BALANCE: &str =
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":5000}}"#;
+        let (url, requests) = spawn_mock_rpc_sequence(vec![BALANCE]).await;
+        let pool = RpcPool::new(
+            vec![
+                EndpointConfig::new(url).with_method_rate_limits(method_limits("getBalance", 20.0))
+            ],
+            RpcPoolConfig::default(),
+        )
+        .unwrap();
+
+        // One from the burst, then 20 rps for the other three
+        let start = Instant::now();
+        let calls = (0..4).map(|_| pool.get_balance(&Pubkey::new_unique()));
+        for result in futures::future::join_all(calls).await {
+            assert_eq!(result.unwrap(), 5000);
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(140), "{:?}", elapsed);
+        assert_eq!(requests.lock().len(), 4);
+
+        // Other methods draw from the fallback bucket and aren't held
+        let start = Instant::now();
+        let _ = pool.get_latest_blockhash().await;
+        assert!(start.elapsed() < Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_over_when_rate_limited() {
+        const SEND_OK: &str = r#"{"jsonrpc":"2.0","id":1,"result":"5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"}"#;
+        let (limited_url, limited) = spawn_mock_rpc_sequence(vec![SEND_OK]).await;
+        let (open_url, open) = spawn_mock_rpc_sequence(vec![SEND_OK]).await;
+        let pool = RpcPool::new(
+            vec![
+                EndpointConfig::new(limited_url)
+                    .with_priority(1)
+                    .with_method_rate_limits(method_limits("sendTransaction", 0.5)),
+                EndpointConfig::new(open_url).with_priority(2),
+            ],
+            RpcPoolConfig {
+                load_balance_strategy: LoadBalanceStrategy::Priority,
+                ..RpcPoolConfig::default()
+            },
+        )
+        .unwrap();
+        let transaction = Transaction::default();
+
+        // The first send takes the limited endpoint's only token; the second
+        // would wait 2s for the next one, so it goes to the other endpoint
+        let start = Instant::now();
+        pool.send_transaction(&transaction).await.unwrap();
+        pool.send_transaction(&transaction).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(limited.lock().len(), 1);
+        assert_eq!(open.lock().len(), 1);
+
+        // With no other endpoint left, the send waits for its token
+        let pool = RpcPool::new(
+            vec![EndpointConfig::new(spawn_mock_rpc(SEND_OK).await)
+                .with_method_rate_limits(method_limits("sendTransaction", 10.0))],
+            RpcPoolConfig::default(),
+        )
+        .unwrap();
+        pool.send_transaction(&transaction).await.unwrap();
+        let start = Instant::now();
+        pool.send_transaction(&transaction).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_caps_failover() {
+        const BEHIND: &str =
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#;
+        let delay = Duration::from_millis(200);
+        let mut endpoints = Vec::new();
+        for _ in 0..3 {
+            endpoints.push(EndpointConfig::new(
+                spawn_slow_mock_rpc(BEHIND, delay).await,
+            ));
+        }
+        let pool = RpcPool::new(
+            endpoints,
+            RpcPoolConfig {
+                request_budget_ms: 300,
+                ..RpcPoolConfig::default()
+            },
+        )
+        .unwrap();
+
+        // Trying all three endpoints would take 600ms
+        let start = Instant::now();
+        let result = pool.get_balance(&Pubkey::new_unique()).await;
+        let elapsed = start.elapsed();
+        assert!(result.is_err());
+        assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(450), "{:?}", elapsed);
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("Retry budget"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_connections_reused_and_closed_on_drop() {
+        const BALANCE: &str =
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":5000}}"#;
+        let (url, connections) = spawn_keepalive_mock_rpc(BALANCE).await;
+        let pool = RpcPool::with_url(url).unwrap();
+
+        for _ in 0..5 {
+            assert_eq!(pool.get_balance(&Pubkey::new_unique()).await.unwrap(), 5000);
+        }
+        assert_eq!(connections.accepted(), 1);
+        assert_eq!(connections.open(), 1);
+
+        // Dropping the pool closes its pooled connections
+        drop(pool);
+        for _ in 0..100 {
+            if connections.open() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(connections.open(), 0);
+    }
+
+    #[test]
+    fn test_retry_budget() {
+        let budget = RetryBudget::new(Duration::from_secs(60));
+        assert!(!budget.is_exhausted());
+        assert!(budget.remaining().unwrap() <= Duration::from_secs(60));
+
+        assert!(RetryBudget::new(Duration::ZERO).is_exhausted());
+        assert!(RetryBudget::unlimited().remaining().is_none());
+        assert!(!RetryBudget::unlimited().is_exhausted());
+    }
 }
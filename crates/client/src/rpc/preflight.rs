@@ -0,0 +1,373 @@
+//! Startup Preflight
+//!
+//! Checks every endpoint once before the client starts (`rpc.preflight`), so
+//! a misconfigured or unsupported endpoint is found at startup rather than on
+//! the first request routed to it.
+
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use base64::prelude::*;
+use serde_json::{json, Value};
+use solana_sdk::{message::Message, pubkey::Pubkey, transaction::Transaction};
+
+use super::config::EndpointRole;
+use super::endpoint::EndpointState;
+
+/// JSON-RPC error code for an unknown method
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// One check run against an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreflightCheck {
+    /// `getVersion` answers
+    Version,
+    /// `getLatestBlockhash` answers
+    LatestBlockhash,
+    /// The WebSocket URL accepts a connection (datasource roles)
+    WebSocket,
+    /// `simulateTransaction` is available (submission roles)
+    Simulate,
+}
+
+impl PreflightCheck {
+    /// Checks that apply to an endpoint with `role`
+    pub fn for_role(role: EndpointRole) -> Vec<PreflightCheck> {
+        let mut checks = vec![PreflightCheck::Version, PreflightCheck::LatestBlockhash];
+        if role.can_fetch() {
+            checks.push(PreflightCheck::WebSocket);
+        }
+        if role.can_submit() {
+            checks.push(PreflightCheck::Simulate);
+        }
+        checks
+    }
+}
+
+impl fmt::Display for PreflightCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PreflightCheck::Version => "getVersion",
+            PreflightCheck::LatestBlockhash => "getLatestBlockhash",
+            PreflightCheck::WebSocket => "websocket",
+            PreflightCheck::Simulate => "simulateTransaction",
+        })
+    }
+}
+
+/// Preflight results of one endpoint
+#[derive(Debug, Clone)]
+pub struct EndpointPreflight {
+    pub url: String,
+    pub role: EndpointRole,
+    /// Each check run, with why it failed if it did
+    pub results: Vec<(PreflightCheck, Result<(), String>)>,
+}
+
+impl EndpointPreflight {
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+}
+
+/// Run the checks for `endpoint`'s role, in order
+pub async fn preflight_endpoint(endpoint: &EndpointState, timeout: Duration) -> EndpointPreflight {
+    let mut results = Vec::new();
+    for check in PreflightCheck::for_role(endpoint.role()) {
+        let result = match check {
+            PreflightCheck::Version => rpc_call(endpoint, "getVersion", json!([]), timeout).await,
+            PreflightCheck::LatestBlockhash => {
+                let params = json!([{ "commitment": "confirmed" }]);
+                rpc_call(endpoint, "getLatestBlockhash", params, timeout).await
+            }
+            PreflightCheck::WebSocket => ws_connect(&endpoint.ws_url(), timeout).await,
+            PreflightCheck::Simulate => simulate_available(endpoint, timeout).await,
+        };
+        results.push((check, result));
+    }
+
+    EndpointPreflight {
+        url: endpoint.url().to_string(),
+        role: endpoint.role(),
+        results,
+    }
+}
+
+/// Fail unless some endpoint passed for submission and some for data, where
+/// endpoints of that kind are configured
+pub fn require_passing(reports: &[EndpointPreflight]) -> Result<()> {
+    let required: [(&str, fn(&EndpointRole) -> bool); 2] = [
+        ("submission", EndpointRole::can_submit),
+        ("datasource", EndpointRole::can_fetch),
+    ];
+    for (name, serves) in required {
+        let mut endpoints = reports.iter().filter(|r| serves(&r.role)).peekable();
+        if endpoints.peek().is_some() && !endpoints.any(EndpointPreflight::passed) {
+            return Err(anyhow!("RPC preflight failed: no {} endpoint passed", name));
+        }
+    }
+    Ok(())
+}
+
+/// Log the results as a table, as a warning if any check failed
+pub fn log_results(reports: &[EndpointPreflight]) {
+    let url_width = reports.iter().map(|r| r.url.len()).max().unwrap_or(0);
+    let mut table = format!(
+        "{:<url_width$}  {:<10}  {:<6}  FAILURES",
+        "ENDPOINT", "ROLE", "RESULT"
+    );
+    for report in reports {
+        let failures: Vec<String> = report
+            .results
+            .iter()
+            .filter_map(|(check, result)| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|reason| format!("{} ({})", check, reason))
+            })
+            .collect();
+        table.push_str(&format!(
+            "\n{:<url_width$}  {:<10}  {:<6}  {}",
+            report.url,
+            format!("{:?}", report.role).to_lowercase(),
+            if report.passed() { "ok" } else { "failed" },
+            if failures.is_empty() {
+                "-".to_string()
+            } else {
+                failures.join(", ")
+            }
+        ));
+    }
+
+    if reports.iter().all(EndpointPreflight::passed) {
+        log::info!("RPC preflight passed:\n{}", table);
+    } else {
+        log::warn!(
+            "RPC preflight failed for some endpoints, starting them unhealthy:\n{}",
+            table
+        );
+    }
+}
+
+/// Send `method` and fail on transport errors and any JSON-RPC error
+async fn rpc_call(
+    endpoint: &EndpointState,
+    method: &str,
+    params: Value,
+    timeout: Duration,
+) -> Result<(), String> {
+    match send(endpoint, method, params, timeout).await? {
+        Some((code, message)) => Err(format!("RPC error {}: {}", code, message)),
+        None => Ok(()),
+    }
+}
+
+/// Simulate an empty transaction; only an unknown method or a transport
+/// error fails, since the simulation itself is expected to error
+async fn simulate_available(endpoint: &EndpointState, timeout: Duration) -> Result<(), String> {
+    let payer = Pubkey::new_unique();
+    let transaction = Transaction::new_unsigned(Message::new(&[], Some(&payer)));
+    let tx_bytes = bincode::serialize(&transaction).map_err(|e| e.to_string())?;
+    let params = json!([
+        BASE64_STANDARD.encode(tx_bytes),
+        {
+            "sigVerify": false,
+            "replaceRecentBlockhash": true,
+            "commitment": "processed",
+            "encoding": "base64"
+        }
+    ]);
+
+    match send(endpoint, "simulateTransaction", params, timeout).await? {
+        Some((METHOD_NOT_FOUND, message)) => Err(format!("method not found: {}", message)),
+        _ => Ok(()),
+    }
+}
+
+/// Send one JSON-RPC request, returning its error code and message if the
+/// response carries one
+async fn send(
+    endpoint: &EndpointState,
+    method: &str,
+    params: Value,
+    timeout: Duration,
+) -> Result<Option<(i64, String)>, String> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params
+    });
+
+    let response = endpoint
+        .http_client()
+        .post(endpoint.url())
+        .timeout(timeout)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("request error: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    let response: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("invalid response: {}", e))?;
+    if let Some(error) = response.get("error") {
+        let code = error.get("code").and_then(Value::as_i64).unwrap_or(0);
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        return Ok(Some((code, message)));
+    }
+    if response.get("result").is_none() {
+        return Err("response has no result".to_string());
+    }
+    Ok(None)
+}
+
+/// Open a WebSocket connection to `ws_url` and close it again
+async fn ws_connect(ws_url: &str, timeout: Duration) -> Result<(), String> {
+    let builder = antegen_ws::WsClient::builder(ws_url).map_err(|e| e.to_string())?;
+    match tokio::time::timeout(timeout, builder.build()).await {
+        Ok(Ok(handle)) => {
+            handle.abort();
+            Ok(())
+        }
+        Ok(Err(e)) => Err(format!("connect failed: {}", e)),
+        Err(_) => Err(format!("connect timed out after {:?}", timeout)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::config::EndpointConfig;
+    use crate::rpc::mock::{spawn_mock_rpc, spawn_mock_rpc_sequence};
+
+    const METHOD_NOT_FOUND_BODY: &str =
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#;
+    const VERSION: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"solana-core":"2.3.0"}}"#;
+    const BLOCKHASH: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"blockhash":"11111111111111111111111111111111","lastValidBlockHeight":100}}}"#;
+    const SIMULATION_ERROR: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":"AccountNotFound","logs":[]}}}"#;
+
+    fn endpoint(url: &str, role: EndpointRole) -> EndpointState {
+        EndpointState::new(EndpointConfig::new(url).with_role(role))
+    }
+
+    fn report(role: EndpointRole, passed: bool) -> EndpointPreflight {
+        let result = if passed { Ok(()) } else { Err("down".into()) };
+        EndpointPreflight {
+            url: "http://localhost:8899".into(),
+            role,
+            results: vec![(PreflightCheck::Version, result)],
+        }
+    }
+
+    #[test]
+    fn test_checks_for_role() {
+        use PreflightCheck::*;
+        assert_eq!(
+            PreflightCheck::for_role(EndpointRole::Submission),
+            vec![Version, LatestBlockhash, Simulate]
+        );
+        assert_eq!(
+            PreflightCheck::for_role(EndpointRole::Datasource),
+            vec![Version, LatestBlockhash, WebSocket]
+        );
+        assert_eq!(
+            PreflightCheck::for_role(EndpointRole::Read),
+            vec![Version, LatestBlockhash]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submission_endpoint_passes() {
+        let (url, requests) =
+            spawn_mock_rpc_sequence(vec![VERSION, BLOCKHASH, SIMULATION_ERROR]).await;
+        let endpoint = endpoint(&url, EndpointRole::Submission);
+
+        let report = preflight_endpoint(&endpoint, Duration::from_secs(5)).await;
+        assert!(report.passed(), "{:?}", report.results);
+        let methods: Vec<String> = requests
+            .lock()
+            .iter()
+            .map(|body| serde_json::from_str::<Value>(body).unwrap()["method"].to_string())
+            .collect();
+        assert_eq!(
+            methods,
+            vec![
+                r#""getVersion""#,
+                r#""getLatestBlockhash""#,
+                r#""simulateTransaction""#
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_method_not_found_fails() {
+        let url = spawn_mock_rpc(METHOD_NOT_FOUND_BODY).await;
+        let endpoint = endpoint(&url, EndpointRole::Submission);
+
+        let report = preflight_endpoint(&endpoint, Duration::from_secs(5)).await;
+        assert!(!report.passed());
+        assert!(report.results.iter().all(|(_, result)| result.is_err()));
+        let (check, reason) = &report.results[2];
+        assert_eq!(*check, PreflightCheck::Simulate);
+        assert!(reason.as_ref().unwrap_err().contains("method not found"));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_not_found_fails() {
+        let (url, _) =
+            spawn_mock_rpc_sequence(vec![VERSION, BLOCKHASH, METHOD_NOT_FOUND_BODY]).await;
+        let endpoint = endpoint(&url, EndpointRole::Submission);
+
+        let report = preflight_endpoint(&endpoint, Duration::from_secs(5)).await;
+        assert!(!report.passed());
+        assert!(report.results[0].1.is_ok());
+        assert!(report.results[1].1.is_ok());
+        assert!(report.results[2].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_websocket_failure() {
+        // The mock only speaks plain HTTP, so the upgrade is refused
+        let (url, _) = spawn_mock_rpc_sequence(vec![VERSION, BLOCKHASH, VERSION]).await;
+        let endpoint = endpoint(&url, EndpointRole::Datasource);
+
+        let report = preflight_endpoint(&endpoint, Duration::from_secs(5)).await;
+        assert!(!report.passed());
+        assert_eq!(report.results[2].0, PreflightCheck::WebSocket);
+        assert!(report.results[2].1.is_err());
+    }
+
+    #[test]
+    fn test_require_passing() {
+        use EndpointRole::*;
+
+        // One passing endpoint per required role is enough
+        assert!(require_passing(&[report(Both, true), report(Both, false)]).is_ok());
+        assert!(require_passing(&[report(Submission, true), report(Datasource, true)]).is_ok());
+
+        // No passing submission endpoint
+        let err =
+            require_passing(&[report(Submission, false), report(Datasource, true)]).unwrap_err();
+        assert!(err.to_string().contains("submission"));
+
+        // No passing datasource endpoint
+        let err =
+            require_passing(&[report(Submission, true), report(Datasource, false)]).unwrap_err();
+        assert!(err.to_string().contains("datasource"));
+
+        // Optional roles never block startup
+        assert!(require_passing(&[report(Both, true), report(Simulation, false)]).is_ok());
+    }
+}
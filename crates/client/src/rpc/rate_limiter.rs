@@ -1,11 +1,14 @@
 //! Token Bucket Rate Limiter
 //!
 //! Limits request rate per endpoint to prevent overwhelming RPCs.
+//! [`MethodRateLimiter`] keeps separate buckets for methods that providers
+//! limit on their own, such as `getProgramAccounts`.
 
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-use super::config::RateLimitConfig;
+use super::config::{MethodRateLimitConfig, RateLimitConfig};
 
 /// Token bucket rate limiter
 pub struct RateLimiter {
@@ -70,6 +73,16 @@ impl RateLimiter {
         }
     }
 
+    /// Time until a token is available, zero if one is now
+    pub fn time_until_available(&self) -> Duration {
+        let tokens = self.available_tokens();
+        if tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - tokens) / self.config.requests_per_second)
+        }
+    }
+
     /// Get current available tokens
     pub fn available_tokens(&self) -> f64 {
         self.refill();
@@ -112,9 +125,44 @@ impl std::fmt::Debug for RateLimiter {
     }
 }
 
+/// Token buckets for one endpoint, one per configured method plus a shared
+/// fallback bucket for every other method
+pub struct MethodRateLimiter {
+    fallback: RateLimiter,
+    methods: HashMap<String, RateLimiter>,
+}
+
+impl MethodRateLimiter {
+    pub fn new(config: &MethodRateLimitConfig) -> Self {
+        Self {
+            fallback: RateLimiter::new(config.default.clone()),
+            methods: config
+                .methods
+                .iter()
+                .map(|(method, limit)| (method.clone(), RateLimiter::new(limit.clone())))
+                .collect(),
+        }
+    }
+
+    /// Bucket that requests for `method` draw from
+    pub fn bucket(&self, method: &str) -> &RateLimiter {
+        self.methods.get(method).unwrap_or(&self.fallback)
+    }
+}
+
+impl std::fmt::Debug for MethodRateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MethodRateLimiter")
+            .field("fallback", &self.fallback)
+            .field("methods", &self.methods)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
 
     fn fast_limiter() -> RateLimiter {
         RateLimiter::new(RateLimitConfig {
@@ -191,4 +239,74 @@ mod tests {
         // Should have waited some time (but not too long due to high rps)
         assert!(elapsed >= Duration::from_millis(5));
     }
+
+    #[test]
+    fn test_time_until_available() {
+        let limiter = fast_limiter();
+        assert_eq!(limiter.time_until_available(), Duration::ZERO);
+
+        for _ in 0..5 {
+            limiter.try_acquire();
+        }
+        // At 10 rps the next token is up to 100ms away
+        let wait = limiter.time_until_available();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_method_buckets() {
+        let limiter = MethodRateLimiter::new(&MethodRateLimitConfig {
+            default: RateLimitConfig {
+                requests_per_second: 10.0,
+                burst_capacity: 3,
+            },
+            methods: HashMap::from([(
+                "getProgramAccounts".to_string(),
+                RateLimitConfig {
+                    requests_per_second: 1.0,
+                    burst_capacity: 1,
+                },
+            )]),
+        });
+
+        // The listed method has its own bucket
+        assert!(limiter.bucket("getProgramAccounts").try_acquire());
+        assert!(!limiter.bucket("getProgramAccounts").try_acquire());
+
+        // Unlisted methods share the fallback bucket
+        assert!(limiter.bucket("getAccountInfo").try_acquire());
+        assert!(limiter.bucket("getBalance").try_acquire());
+        assert!(limiter.bucket("getSlot").try_acquire());
+        assert!(!limiter.bucket("getAccountInfo").try_acquire());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_bucket() {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            requests_per_second: 20.0,
+            burst_capacity: 2,
+        }));
+
+        let start = Instant::now();
+        let callers: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        let mut waited = Vec::new();
+        for caller in callers {
+            waited.push(caller.await.unwrap());
+        }
+        let elapsed = start.elapsed();
+
+        // 2 from the burst, then 6 more at 20 rps take at least 300ms
+        assert!(elapsed >= Duration::from_millis(280), "{:?}", elapsed);
+        let immediate = waited
+            .iter()
+            .filter(|w| **w < Duration::from_millis(20))
+            .count();
+        assert_eq!(immediate, 2);
+        assert!(limiter.available_tokens() < 1.0);
+    }
 }
@@ -6,6 +6,8 @@
 
 use base64::prelude::*;
 use serde::{Deserialize, Deserializer};
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::{account::Account, instruction::InstructionError, transaction::TransactionError};
 use std::io::Read;
 use thiserror::Error;
 
@@ -139,6 +141,34 @@ impl SafeUiAccount {
             .parse()
             .map_err(|e| format!("Invalid owner pubkey: {}", e))
     }
+
+    /// Convert to an [`Account`], decoding the data and owner
+    pub fn to_account(&self) -> Result<Account, String> {
+        Ok(Account {
+            lamports: self.lamports,
+            data: self.decode_data().map_err(|e| e.to_string())?,
+            owner: self.owner_pubkey()?,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        })
+    }
+}
+
+/// Response context carried by `getAccountInfo` and similar methods
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcContext {
+    /// Slot the node evaluated the request at
+    pub slot: u64,
+}
+
+/// `getAccountInfo` result with its context
+///
+/// `context.slot` orders this read against datasource updates: the account
+/// state returned is at least as new as that slot.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SafeAccountResponse {
+    pub context: RpcContext,
+    pub value: Option<SafeUiAccount>,
 }
 
 /// Safe simulation result value
@@ -159,6 +189,154 @@ pub struct SafeSimulationResult {
     pub value: SafeSimulationValue,
 }
 
+/// Outcome of a `simulateTransaction` call, success or failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub logs: Vec<String>,
+    pub compute_units_consumed: u64,
+    /// Transaction error as reported by the RPC, if the simulation failed
+    pub error: Option<String>,
+}
+
+impl From<SafeSimulationValue> for SimulationResult {
+    fn from(value: SafeSimulationValue) -> Self {
+        let error = value.err.map(|err| match err {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        });
+
+        Self {
+            success: error.is_none(),
+            logs: value.logs.unwrap_or_default(),
+            compute_units_consumed: value.units_consumed.unwrap_or(0),
+            error,
+        }
+    }
+}
+
+/// Entry returned by `getSignaturesForAddress`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeSignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<serde_json::Value>,
+    pub block_time: Option<i64>,
+}
+
+/// Entry returned by `getSignatureStatuses`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeSignatureStatus {
+    pub slot: u64,
+    pub err: Option<serde_json::Value>,
+    pub confirmation_status: Option<String>,
+}
+
+impl SafeSignatureStatus {
+    /// Check whether the transaction has reached `commitment`
+    pub fn reached(&self, commitment: CommitmentConfig) -> bool {
+        let rank = |level: &str| match level {
+            "processed" => Some(0),
+            "confirmed" => Some(1),
+            "finalized" => Some(2),
+            _ => None,
+        };
+        let required = match commitment.commitment {
+            CommitmentLevel::Processed => 0,
+            CommitmentLevel::Confirmed => 1,
+            CommitmentLevel::Finalized => 2,
+        };
+        self.confirmation_status
+            .as_deref()
+            .and_then(rank)
+            .is_some_and(|level| level >= required)
+    }
+
+    /// The transaction's error, if it failed
+    ///
+    /// Only the custom program error code is recovered; other errors map to
+    /// `Custom(0)`.
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        let err = self.err.as_ref()?;
+        let code = err
+            .get("InstructionError")
+            .and_then(|e| e.get(1))
+            .and_then(|e| e.get("Custom"))
+            .and_then(|e| e.as_u64())
+            .unwrap_or(0) as u32;
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ))
+    }
+}
+
+/// Transaction status metadata (fee and lamport balances)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransactionMeta {
+    pub err: Option<serde_json::Value>,
+    pub fee: u64,
+    pub pre_balances: Vec<u64>,
+    pub post_balances: Vec<u64>,
+    #[serde(default)]
+    pub log_messages: Option<Vec<String>>,
+    #[serde(default)]
+    pub return_data: Option<SafeReturnData>,
+}
+
+/// Return data left by the transaction's last `set_return_data`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeReturnData {
+    pub program_id: String,
+    /// `[data, "base64"]`
+    pub data: (String, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransactionMessage {
+    pub account_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SafeTransactionBody {
+    pub message: SafeTransactionMessage,
+}
+
+/// Confirmed transaction returned by `getTransaction` (json encoding)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub meta: Option<SafeTransactionMeta>,
+    pub transaction: SafeTransactionBody,
+}
+
+impl SafeTransaction {
+    /// Lamport change of `pubkey` in this transaction (post - pre)
+    ///
+    /// Only static account keys are considered, which covers all accounts
+    /// passed to `exec_thread` directly.
+    pub fn balance_change(&self, pubkey: &solana_sdk::pubkey::Pubkey) -> Option<i64> {
+        let meta = self.meta.as_ref()?;
+        let key = pubkey.to_string();
+        let index = self
+            .transaction
+            .message
+            .account_keys
+            .iter()
+            .position(|k| *k == key)?;
+        let pre = *meta.pre_balances.get(index)? as i64;
+        let post = *meta.post_balances.get(index)? as i64;
+        Some(post - pre)
+    }
+}
+
 /// Generic RPC response wrapper
 #[derive(Debug, Clone, Deserialize)]
 pub struct RpcResponse<T> {
@@ -228,4 +406,76 @@ mod tests {
         let account = accounts[0].as_ref().unwrap();
         assert_eq!(account.rent_epoch, u64::MAX);
     }
+
+    #[test]
+    fn test_simulation_result_failure() {
+        let json = r#"{
+            "value": {
+                "err": {"InstructionError": [0, {"Custom": 6004}]},
+                "logs": ["Program log: AnchorError occurred"],
+                "unitsConsumed": 1.8446744073709552e19,
+                "accounts": null,
+                "returnData": null
+            }
+        }"#;
+        let result: SafeSimulationResult = serde_json::from_str(json).unwrap();
+        let result = SimulationResult::from(result.value);
+        assert!(!result.success);
+        assert_eq!(result.compute_units_consumed, u64::MAX);
+        assert_eq!(result.logs.len(), 1);
+        assert_eq!(
+            result.error.as_deref(),
+            Some(r#"{"InstructionError":[0,{"Custom":6004}]}"#)
+        );
+    }
+
+    #[test]
+    fn test_transaction_balance_change() {
+        let json = r#"{
+            "slot": 42,
+            "blockTime": 1700000000,
+            "meta": {
+                "err": null,
+                "fee": 5000,
+                "preBalances": [1000000, 2000000],
+                "postBalances": [1004000, 1990000]
+            },
+            "transaction": {
+                "message": {
+                    "accountKeys": [
+                        "11111111111111111111111111111111",
+                        "SysvarC1ock11111111111111111111111111111111"
+                    ]
+                }
+            }
+        }"#;
+        let tx: SafeTransaction = serde_json::from_str(json).unwrap();
+        let clock = solana_sdk::sysvar::clock::ID;
+        assert_eq!(tx.balance_change(&clock), Some(-10000));
+        assert_eq!(
+            tx.balance_change(&solana_sdk::pubkey::Pubkey::new_unique()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_account_response_with_context() {
+        let json = r#"{"context":{"apiVersion":"2.2.0","slot":341197053},"value":{"data":["AQID","base64"],"executable":false,"lamports":1000000,"owner":"11111111111111111111111111111111","rentEpoch":18446744073709551615,"space":3}}"#;
+        let response: SafeAccountResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.context.slot, 341197053);
+
+        let account = response.value.unwrap().to_account().unwrap();
+        assert_eq!(account.data, vec![1, 2, 3]);
+        assert_eq!(account.lamports, 1000000);
+        assert_eq!(account.owner, solana_sdk::pubkey::Pubkey::default());
+        assert_eq!(account.rent_epoch, u64::MAX);
+    }
+
+    #[test]
+    fn test_account_response_missing_account() {
+        let json = r#"{"context":{"slot":12},"value":null}"#;
+        let response: SafeAccountResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.context.slot, 12);
+        assert!(response.value.is_none());
+    }
 }
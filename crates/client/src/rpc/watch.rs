@@ -0,0 +1,281 @@
+//! Shared `accountSubscribe` watches.
+//!
+//! [`AccountWatchers`] backs [`RpcPool::watch_account`](super::RpcPool::watch_account).
+//! Each watched account and commitment gets one WebSocket connection, shared
+//! by every stream watching it. antegen-ws reconnects with backoff and the
+//! subscription is re-sent on every connect. The connection is closed once
+//! the last stream is dropped.
+
+use antegen_ws::Message as WsMessage;
+use futures::Stream;
+use parking_lot::Mutex;
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use super::websocket::{build_account_subscribe_request, parse_notification};
+use crate::types::AccountUpdate;
+
+/// Updates buffered per watch before a slow stream starts skipping ahead
+const WATCH_CAPACITY: usize = 64;
+
+/// How often an idle watch checks whether anyone still listens
+const WATCH_IDLE_CHECK: Duration = Duration::from_secs(30);
+
+const KEEPALIVE: Duration = Duration::from_secs(10);
+
+/// Watched account and commitment level
+type WatchKey = (Pubkey, &'static str);
+type Watches = Arc<Mutex<HashMap<WatchKey, broadcast::Sender<AccountUpdate>>>>;
+
+/// Open account watches, one connection per account and commitment
+#[derive(Default)]
+pub struct AccountWatchers {
+    watches: Watches,
+}
+
+impl AccountWatchers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stream updates to `pubkey` from `ws_url`, joining an open watch on the
+    /// same account and commitment if there is one
+    ///
+    /// The stream ends if the connection can't be established.
+    pub fn watch(
+        &self,
+        ws_url: &str,
+        pubkey: Pubkey,
+        commitment: CommitmentConfig,
+    ) -> impl Stream<Item = AccountUpdate> + Send + 'static {
+        let key = (pubkey, commitment_str(commitment.commitment));
+        let receiver = {
+            let mut watches = self.watches.lock();
+            match watches.get(&key) {
+                Some(sender) => sender.subscribe(),
+                None => {
+                    let (sender, receiver) = broadcast::channel(WATCH_CAPACITY);
+                    watches.insert(key, sender.clone());
+                    tokio::spawn(run_watch(
+                        ws_url.to_string(),
+                        key,
+                        sender,
+                        self.watches.clone(),
+                    ));
+                    receiver
+                }
+            }
+        };
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(update) => return Some((update, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::debug!("Account watch skipped {} stale updates", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Number of open watch connections
+    pub fn len(&self) -> usize {
+        self.watches.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.lock().is_empty()
+    }
+}
+
+fn commitment_str(level: CommitmentLevel) -> &'static str {
+    match level {
+        CommitmentLevel::Processed => "processed",
+        CommitmentLevel::Confirmed => "confirmed",
+        CommitmentLevel::Finalized => "finalized",
+    }
+}
+
+/// Remove `key`'s watch if no stream listens anymore
+///
+/// Checked under the registry lock so a concurrent `watch` either joins
+/// before the watch closes or opens a new one after.
+fn release_if_unwatched(
+    watches: &Watches,
+    key: &WatchKey,
+    sender: &broadcast::Sender<AccountUpdate>,
+) -> bool {
+    let mut watches = watches.lock();
+    if sender.receiver_count() > 0 {
+        return false;
+    }
+    if watches.get(key).is_some_and(|s| s.same_channel(sender)) {
+        watches.remove(key);
+    }
+    true
+}
+
+async fn run_watch(
+    ws_url: String,
+    key: WatchKey,
+    sender: broadcast::Sender<AccountUpdate>,
+    watches: Watches,
+) {
+    let (pubkey, commitment) = key;
+    let (_, subscribe_msg) = build_account_subscribe_request(&pubkey, commitment);
+
+    let connected = match antegen_ws::WsClient::builder(&ws_url) {
+        Ok(builder) => {
+            builder
+                .keepalive(KEEPALIVE)
+                .on_connect(move |tx| {
+                    let msg = subscribe_msg.clone();
+                    async move {
+                        let _ = tx.send_text(msg).await;
+                        Ok(())
+                    }
+                })
+                .build()
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let mut handle = match connected {
+        Ok(handle) => handle,
+        Err(e) => {
+            log::warn!("[{}] Failed to watch account {}: {}", ws_url, pubkey, e);
+            release(&watches, &key, &sender);
+            return;
+        }
+    };
+    log::debug!("[{}] Watching account {}", ws_url, pubkey);
+
+    let mut idle_check = tokio::time::interval(WATCH_IDLE_CHECK);
+    loop {
+        tokio::select! {
+            msg = handle.recv() => {
+                let Some(msg) = msg else {
+                    break;
+                };
+                let WsMessage::Text(text) = msg else {
+                    continue;
+                };
+                match parse_notification(&text, Some(pubkey)) {
+                    Ok(Some(update)) => match update.account.decode_data() {
+                        Ok(data) => {
+                            let _ = sender.send(AccountUpdate {
+                                pubkey,
+                                data,
+                                slot: update.slot,
+                            });
+                        }
+                        Err(e) => {
+                            log::debug!("[{}] Failed to decode {}: {}", ws_url, pubkey, e);
+                        }
+                    },
+                    Ok(None) => {}
+                    Err(e) => log::trace!("[{}] Ignoring message: {}", ws_url, e),
+                }
+            }
+            _ = idle_check.tick() => {}
+        }
+
+        if release_if_unwatched(&watches, &key, &sender) {
+            log::debug!("[{}] No longer watching account {}", ws_url, pubkey);
+            handle.abort();
+            return;
+        }
+    }
+
+    log::warn!("[{}] Watch on account {} closed", ws_url, pubkey);
+    release(&watches, &key, &sender);
+}
+
+/// Drop `key`'s watch so its streams end and the next `watch` reconnects
+fn release(watches: &Watches, key: &WatchKey, sender: &broadcast::Sender<AccountUpdate>) {
+    let mut watches = watches.lock();
+    if watches.get(key).is_some_and(|s| s.same_channel(sender)) {
+        watches.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_ws;
+    use futures::StreamExt;
+
+    fn notification(slot: u64, data: &str) -> String {
+        format!(
+            r#"{{"jsonrpc":"2.0","method":"accountNotification","params":{{"result":{{"context":{{"slot":{slot}}},"value":{{"lamports":1,"data":["{data}","base64"],"owner":"11111111111111111111111111111111","executable":false,"rentEpoch":0}}}},"subscription":1}}}}"#
+        )
+    }
+
+    async fn next(stream: &mut (impl Stream<Item = AccountUpdate> + Unpin)) -> AccountUpdate {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for an update")
+            .expect("watch ended")
+    }
+
+    #[tokio::test]
+    async fn test_watches_share_one_connection() {
+        let (url, connections) = spawn_mock_ws(vec![vec![notification(7, "AQID")]]).await;
+        let watchers = AccountWatchers::new();
+        let pubkey = Pubkey::new_unique();
+
+        let mut first = Box::pin(watchers.watch(&url, pubkey, CommitmentConfig::confirmed()));
+        let mut second = Box::pin(watchers.watch(&url, pubkey, CommitmentConfig::confirmed()));
+        assert_eq!(watchers.len(), 1);
+
+        for stream in [&mut first, &mut second] {
+            let update = next(stream).await;
+            assert_eq!(update.pubkey, pubkey);
+            assert_eq!(update.slot, 7);
+            assert_eq!(update.data, vec![1, 2, 3]);
+        }
+        assert_eq!(connections.accepted(), 1);
+
+        // Another commitment is a separate subscription
+        let _finalized = watchers.watch(&url, pubkey, CommitmentConfig::finalized());
+        assert_eq!(watchers.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_resubscribes_after_reconnect() {
+        // The first connection sends one update and drops
+        let (url, connections) = spawn_mock_ws(vec![
+            vec![notification(1, "AQ==")],
+            vec![notification(2, "Ag==")],
+        ])
+        .await;
+        let watchers = AccountWatchers::new();
+
+        let mut stream =
+            Box::pin(watchers.watch(&url, Pubkey::new_unique(), CommitmentConfig::confirmed()));
+        assert_eq!(next(&mut stream).await.slot, 1);
+        assert_eq!(next(&mut stream).await.slot, 2);
+        assert_eq!(connections.accepted(), 2);
+        // Both connections were sent the subscription
+        assert_eq!(connections.subscribes(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_watch_ends() {
+        let watchers = AccountWatchers::new();
+        let mut stream = Box::pin(watchers.watch(
+            "ws://127.0.0.1:1",
+            Pubkey::new_unique(),
+            CommitmentConfig::confirmed(),
+        ));
+        let ended = tokio::time::timeout(Duration::from_secs(5), stream.next()).await;
+        assert!(matches!(ended, Ok(None)));
+        assert!(watchers.is_empty());
+    }
+}
@@ -63,6 +63,19 @@ pub fn build_account_subscribe_request(pubkey: &Pubkey, commitment: &str) -> (u6
     (subscription_id, request.to_string())
 }
 
+/// Build a `slotSubscribe` request and return `(id, json)`.
+pub fn build_slot_subscribe_request() -> (u64, String) {
+    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": subscription_id,
+        "method": "slotSubscribe",
+    });
+
+    (subscription_id, request.to_string())
+}
+
 /// High-level helpers around `antegen-ws`.
 pub struct WsClient;
 
@@ -195,6 +208,33 @@ pub fn parse_notification(
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct SlotNotification {
+    method: Option<String>,
+    params: Option<SlotNotificationParams>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlotNotificationParams {
+    result: SlotNotificationResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlotNotificationResult {
+    slot: u64,
+}
+
+/// Parse a `slotNotification` message, returning the notified slot.
+pub fn parse_slot_notification(text: &str) -> Option<u64> {
+    let notification: SlotNotification = serde_json::from_str(text).ok()?;
+
+    if notification.method.as_deref() != Some("slotNotification") {
+        return None;
+    }
+
+    Some(notification.params?.result.slot)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,4 +339,22 @@ mod tests {
         let update = result.unwrap();
         assert_eq!(update.account.rent_epoch, u64::MAX);
     }
+
+    #[test]
+    fn test_parse_slot_notification() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "method": "slotNotification",
+            "params": {
+                "result": {"parent": 75, "root": 44, "slot": 76},
+                "subscription": 0
+            }
+        }"#;
+
+        assert_eq!(parse_slot_notification(json), Some(76));
+
+        // Subscription confirmations are not slot notifications
+        let json = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+        assert_eq!(parse_slot_notification(json), None);
+    }
 }
@@ -0,0 +1,164 @@
+//! Thread selection by id pattern or pubkey
+//!
+//! In a busy deployment an operator may want the client to follow only a few
+//! threads, e.g. to focus on one that misbehaves. `processor.thread_filter`
+//! lists the threads to follow:
+//!
+//! - `*` matches every thread (the default)
+//! - a base58 pubkey matches that thread account
+//! - anything else is a glob on the thread id, where `*` matches any run of
+//!   characters and `?` a single character (`payroll-*`, `job-??`)
+//!
+//! The filter can be swapped at runtime with [`crate::set_thread_filter`].
+
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// One entry of a [`ThreadFilter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadPattern {
+    /// Matches every thread
+    Any,
+    /// Matches the thread with this address
+    Pubkey(Pubkey),
+    /// Glob matched against the thread id
+    Id(String),
+}
+
+impl ThreadPattern {
+    pub fn parse(pattern: &str) -> anyhow::Result<Self> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            anyhow::bail!("thread filter pattern cannot be empty");
+        }
+        if pattern == "*" {
+            return Ok(Self::Any);
+        }
+        if let Ok(pubkey) = Pubkey::from_str(pattern) {
+            return Ok(Self::Pubkey(pubkey));
+        }
+        Ok(Self::Id(pattern.to_string()))
+    }
+
+    fn matches(&self, pubkey: &Pubkey, id: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Pubkey(p) => p == pubkey,
+            Self::Id(glob) => glob_match(glob.as_bytes(), id.as_bytes()),
+        }
+    }
+}
+
+/// Set of threads the client follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadFilter {
+    patterns: Vec<ThreadPattern>,
+}
+
+impl Default for ThreadFilter {
+    fn default() -> Self {
+        Self {
+            patterns: vec![ThreadPattern::Any],
+        }
+    }
+}
+
+impl ThreadFilter {
+    /// Build a filter from config patterns; an empty list matches everything
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> anyhow::Result<Self> {
+        if patterns.is_empty() {
+            return Ok(Self::default());
+        }
+        let patterns = patterns
+            .iter()
+            .map(|p| ThreadPattern::parse(p.as_ref()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Whether this filter lets every thread through
+    pub fn matches_all(&self) -> bool {
+        self.patterns.contains(&ThreadPattern::Any)
+    }
+
+    /// Whether the thread at `pubkey` with id `thread_id` is followed
+    pub fn matches(&self, pubkey: &Pubkey, thread_id: &[u8]) -> bool {
+        if self.matches_all() {
+            return true;
+        }
+        let id = String::from_utf8_lossy(thread_id);
+        self.patterns.iter().any(|p| p.matches(pubkey, &id))
+    }
+
+    pub fn patterns(&self) -> &[ThreadPattern] {
+        &self.patterns
+    }
+}
+
+/// Glob match with `*` (any run, including empty) and `?` (one character)
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text index it is currently covering
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"payroll-*", b"payroll-2024"));
+        assert!(glob_match(b"payroll-*", b"payroll-"));
+        assert!(glob_match(b"*-daily", b"report-daily"));
+        assert!(glob_match(b"job-??", b"job-42"));
+        assert!(glob_match(b"a*b*c", b"axxbyyc"));
+        assert!(!glob_match(b"job-??", b"job-420"));
+        assert!(!glob_match(b"payroll-*", b"billing-1"));
+        assert!(!glob_match(b"a*b*c", b"axxbyy"));
+    }
+
+    #[test]
+    fn test_default_matches_everything() {
+        let filter = ThreadFilter::default();
+        assert!(filter.matches_all());
+        assert!(filter.matches(&Pubkey::new_unique(), b"anything"));
+        assert_eq!(ThreadFilter::new::<&str>(&[]).unwrap(), filter);
+    }
+
+    #[test]
+    fn test_pubkey_and_id_patterns() {
+        let focus = Pubkey::new_unique();
+        let filter = ThreadFilter::new(&[focus.to_string(), "payroll-*".to_string()]).unwrap();
+        assert!(!filter.matches_all());
+
+        assert!(filter.matches(&focus, b"unrelated"));
+        assert!(filter.matches(&Pubkey::new_unique(), b"payroll-march"));
+        assert!(!filter.matches(&Pubkey::new_unique(), b"billing"));
+
+        assert!(ThreadFilter::new(&["payroll-*", " "]).is_err());
+    }
+}
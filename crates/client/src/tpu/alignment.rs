@@ -0,0 +1,177 @@
+//! Aligning TPU sends with healthy leaders
+//!
+//! The connection scheduler sends every transaction to the next
+//! `leaders_fanout` leaders and keeps connections open to a couple more
+//! beyond them. When none of the leaders in the send window has landed one of
+//! our transactions yet, but one a little further ahead has, waiting for it
+//! to rotate into the send window usually beats sending straight into the
+//! unknown.
+//!
+//! [`SendScheduler::next_good_send_window`] makes that call from the
+//! upcoming leaders the [`LeaderTracker`](super::LeaderTracker) saw last. A
+//! leader is warm once it has landed a transaction and lands at least as
+//! many as it loses. Upcoming addresses are distinct leaders, so each
+//! position is taken as one rotation of `NUM_CONSECUTIVE_LEADER_SLOTS`;
+//! leaders with back-to-back rotations make the estimate err long.
+//!
+//! The hold never exceeds the caller's `max_hold`. With a zero `max_hold`
+//! the window is only classified, for the alignment metrics.
+
+use solana_sdk::clock::{DEFAULT_MS_PER_SLOT, NUM_CONSECUTIVE_LEADER_SLOTS};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// Time one leader rotation lasts
+const ROTATION: Duration =
+    Duration::from_millis(DEFAULT_MS_PER_SLOT * NUM_CONSECUTIVE_LEADER_SLOTS);
+
+/// When the next send should go out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendWindow {
+    /// How long to wait before sending
+    pub hold: Duration,
+    /// Whether a warm leader is in the send window once the hold is over
+    pub aligned: bool,
+}
+
+impl SendWindow {
+    /// Send now, without a warm leader to send to
+    pub const UNALIGNED: Self = Self {
+        hold: Duration::ZERO,
+        aligned: false,
+    };
+}
+
+/// Picks send windows from the upcoming leader schedule
+#[derive(Debug, Clone, Copy)]
+pub struct SendScheduler {
+    send_fanout: usize,
+}
+
+impl SendScheduler {
+    pub fn new(send_fanout: usize) -> Self {
+        Self {
+            send_fanout: send_fanout.max(1),
+        }
+    }
+
+    /// Window for the next send to `upcoming` leaders, in schedule order
+    ///
+    /// Sends now when a warm leader is in the send window. Otherwise holds
+    /// until the nearest warm leader beyond it rotates in, if that is within
+    /// `max_hold`; failing that, sends now unaligned.
+    pub fn next_good_send_window(
+        &self,
+        upcoming: &[SocketAddr],
+        is_warm: impl Fn(&SocketAddr) -> bool,
+        max_hold: Duration,
+    ) -> SendWindow {
+        let Some(position) = upcoming.iter().position(is_warm) else {
+            return SendWindow::UNALIGNED;
+        };
+        if position < self.send_fanout {
+            return SendWindow {
+                hold: Duration::ZERO,
+                aligned: true,
+            };
+        }
+        let rotations = (position + 1 - self.send_fanout) as u32;
+        let hold = ROTATION * rotations;
+        if hold > max_hold {
+            return SendWindow::UNALIGNED;
+        }
+        SendWindow {
+            hold,
+            aligned: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tpu::{LeaderTracker, TrackingLeaderUpdater};
+    use async_trait::async_trait;
+    use solana_tpu_client_next::leader_updater::LeaderUpdater;
+    use std::sync::Arc;
+
+    /// Hands out the same upcoming leaders on every lookup
+    struct FixedLeaderUpdater(Vec<SocketAddr>);
+
+    #[async_trait]
+    impl LeaderUpdater for FixedLeaderUpdater {
+        fn next_leaders(&mut self, lookahead_leaders: usize) -> Vec<SocketAddr> {
+            self.0.iter().take(lookahead_leaders).copied().collect()
+        }
+
+        async fn stop(&mut self) {}
+    }
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    /// Tracker that has seen leaders 1..=4 come up, with a send fanout of 2
+    fn tracker() -> Arc<LeaderTracker> {
+        let tracker = Arc::new(LeaderTracker::new(2));
+        let leaders = (1..=4).map(addr).collect();
+        let mut updater =
+            TrackingLeaderUpdater::new(Box::new(FixedLeaderUpdater(leaders)), tracker.clone());
+        updater.next_leaders(4);
+        tracker
+    }
+
+    fn window(tracker: &LeaderTracker, max_hold: Duration) -> SendWindow {
+        SendScheduler::new(2).next_good_send_window(
+            &tracker.upcoming(),
+            |leader| tracker.is_warm(leader),
+            max_hold,
+        )
+    }
+
+    #[test]
+    fn test_sends_now_when_a_warm_leader_is_in_the_fanout() {
+        let tracker = tracker();
+        assert_eq!(tracker.targets(), vec![addr(1), addr(2)]);
+        tracker.record_outcome(&[addr(2)], true);
+
+        let window = window(&tracker, Duration::from_secs(2));
+        assert_eq!(window.hold, Duration::ZERO);
+        assert!(window.aligned);
+    }
+
+    #[test]
+    fn test_holds_for_a_warm_leader_beyond_the_fanout() {
+        let tracker = tracker();
+        tracker.record_outcome(&[addr(4)], true);
+        // A leader that loses more than it lands is not warm
+        tracker.record_outcome(&[addr(1)], true);
+        tracker.record_outcome(&[addr(1)], false);
+        tracker.record_outcome(&[addr(1)], false);
+
+        // Two rotations until leader 4 is in the send window
+        let window = window(&tracker, Duration::from_secs(5));
+        assert_eq!(window.hold, Duration::from_millis(3_200));
+        assert!(window.aligned);
+    }
+
+    #[test]
+    fn test_does_not_hold_past_max_hold() {
+        let tracker = tracker();
+        tracker.record_outcome(&[addr(4)], true);
+
+        assert_eq!(
+            window(&tracker, Duration::from_millis(1_600)),
+            SendWindow::UNALIGNED
+        );
+        // Latency-sensitive triggers never hold
+        assert_eq!(window(&tracker, Duration::ZERO), SendWindow::UNALIGNED);
+
+        // Without any warm leader there is nothing to wait for
+        let tracker = self::tracker();
+        assert_eq!(
+            window(&tracker, Duration::from_secs(60)),
+            SendWindow::UNALIGNED
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Confirmation polling for TPU submissions
+//!
+//! TPU sends are fire-and-forget: the leader gives no answer, so whether a
+//! transaction landed is learned by polling `getSignatureStatuses` until the
+//! signature reaches the requested commitment or the timeout elapses. While
+//! polling, the transaction is periodically re-sent, since the leader it first
+//! went to may have dropped it.
+
+use crate::rpc::RpcPool;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{signature::Signature, transaction::TransactionError};
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How often the transaction is re-sent while waiting for confirmation
+pub const RESEND_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Error from a confirmed TPU submission
+#[derive(Debug, thiserror::Error)]
+pub enum TpuError {
+    #[error("TPU send failed: {0}")]
+    Send(String),
+    #[error("Transaction {signature} not confirmed within {timeout:?}")]
+    ConfirmationTimeout {
+        signature: Signature,
+        timeout: Duration,
+    },
+    #[error("Transaction {signature} failed: {error:?}")]
+    TransactionFailed {
+        signature: Signature,
+        error: TransactionError,
+    },
+}
+
+/// Poll until `signature` reaches `commitment`, calling `resend` every
+/// [`RESEND_INTERVAL`]
+///
+/// RPC errors while polling are treated as transient. A transaction that
+/// lands with an error is reported as soon as it is seen, regardless of
+/// commitment, since it cannot succeed later.
+pub async fn await_confirmation<F, Fut>(
+    rpc_client: &RpcPool,
+    signature: Signature,
+    commitment: CommitmentConfig,
+    poll_interval: Duration,
+    timeout: Duration,
+    mut resend: F,
+) -> Result<Signature, TpuError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let start = Instant::now();
+    let mut last_send = start;
+
+    loop {
+        match rpc_client
+            .get_signature_statuses(std::slice::from_ref(&signature))
+            .await
+        {
+            Ok(statuses) => {
+                if let Some(Some(status)) = statuses.into_iter().next() {
+                    if let Some(error) = status.transaction_error() {
+                        return Err(TpuError::TransactionFailed { signature, error });
+                    }
+                    if status.reached(commitment) {
+                        return Ok(signature);
+                    }
+                }
+            }
+            Err(e) => {
+                log::debug!("Error checking signature status: {:?}", e);
+            }
+        }
+
+        if start.elapsed() >= timeout {
+            return Err(TpuError::ConfirmationTimeout { signature, timeout });
+        }
+
+        // Re-send periodically (may hit a different leader)
+        if last_send.elapsed() >= RESEND_INTERVAL {
+            if let Err(e) = resend().await {
+                log::debug!("TPU re-send failed: {}", e);
+            }
+            last_send = Instant::now();
+        }
+
+        tokio::time::sleep(poll_interval.min(timeout.saturating_sub(start.elapsed()))).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_rpc_sequence;
+
+    const NOT_FOUND: &str =
+        r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[null]}}"#;
+    const PROCESSED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":2},"value":[{"slot":2,"confirmations":0,"err":null,"status":{"Ok":null},"confirmationStatus":"processed"}]}}"#;
+    const CONFIRMED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":3},"value":[{"slot":2,"confirmations":1,"err":null,"status":{"Ok":null},"confirmationStatus":"confirmed"}]}}"#;
+    const FAILED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":3},"value":[{"slot":2,"confirmations":0,"err":{"InstructionError":[0,{"Custom":6006}]},"status":{"Err":{}},"confirmationStatus":"processed"}]}}"#;
+
+    #[tokio::test]
+    async fn test_polls_until_commitment_reached() {
+        let (url, served) = spawn_mock_rpc_sequence(vec![NOT_FOUND, PROCESSED, CONFIRMED]).await;
+        let rpc_client = RpcPool::with_url(url).unwrap();
+        let signature = Signature::from([7; 64]);
+
+        let result = await_confirmation(
+            &rpc_client,
+            signature,
+            CommitmentConfig::confirmed(),
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+            || async { Ok::<_, anyhow::Error>(()) },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), signature);
+        assert_eq!(served.lock().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_processed_satisfies_processed_commitment() {
+        let (url, served) = spawn_mock_rpc_sequence(vec![PROCESSED, CONFIRMED]).await;
+        let rpc_client = RpcPool::with_url(url).unwrap();
+
+        await_confirmation(
+            &rpc_client,
+            Signature::from([7; 64]),
+            CommitmentConfig::processed(),
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+            || async { Ok::<_, anyhow::Error>(()) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(served.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_typed() {
+        let (url, _) = spawn_mock_rpc_sequence(vec![PROCESSED]).await;
+        let rpc_client = RpcPool::with_url(url).unwrap();
+        let signature = Signature::from([7; 64]);
+
+        let result = await_confirmation(
+            &rpc_client,
+            signature,
+            CommitmentConfig::finalized(),
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+            || async { Ok::<_, anyhow::Error>(()) },
+        )
+        .await;
+
+        match result {
+            Err(TpuError::ConfirmationTimeout {
+                signature: timed_out,
+                timeout,
+            }) => {
+                assert_eq!(timed_out, signature);
+                assert_eq!(timeout, Duration::from_millis(100));
+            }
+            other => panic!("expected confirmation timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_transaction_reported_immediately() {
+        let (url, served) = spawn_mock_rpc_sequence(vec![FAILED]).await;
+        let rpc_client = RpcPool::with_url(url).unwrap();
+
+        let result = await_confirmation(
+            &rpc_client,
+            Signature::from([7; 64]),
+            CommitmentConfig::finalized(),
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+            || async { Ok::<_, anyhow::Error>(()) },
+        )
+        .await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(error, TpuError::TransactionFailed { .. }));
+        assert!(error.to_string().contains("6006"));
+        assert_eq!(served.lock().len(), 1);
+    }
+}
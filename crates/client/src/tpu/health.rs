@@ -0,0 +1,386 @@
+//! TPU leader health and automatic disabling
+//!
+//! [`TrackingLeaderUpdater`] wraps the leader updater handed to the
+//! connection scheduler and records which leaders each send was fanned out
+//! to. When the confirmation of a TPU send resolves, its leaders are credited
+//! with a landed or lost transaction. Sends made while no upcoming leader
+//! was known are counted separately.
+//!
+//! [`TpuBreaker`] turns TPU off for a cooldown when too many sends in a
+//! sliding window fail to land, so workers go straight to RPC instead of
+//! waiting out a confirmation timeout on every attempt.
+
+use crate::config::TpuAutoDisableConfig;
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use solana_tpu_client_next::leader_updater::LeaderUpdater;
+use solana_tpu_client_next::send_transaction_stats::SendTransactionStats;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Send outcomes of one leader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderHealth {
+    /// Leader TPU QUIC address
+    pub address: SocketAddr,
+    /// Sends (including re-sends) fanned out to this leader
+    pub attempted: u64,
+    /// Transactions sent to this leader that landed
+    pub succeeded: u64,
+    /// Transactions sent to this leader that timed out without landing
+    pub failed: u64,
+}
+
+#[derive(Default, Clone, Copy)]
+struct LeaderCounts {
+    attempted: u64,
+    succeeded: u64,
+    failed: u64,
+}
+
+/// Per-leader send outcomes, fed by [`TrackingLeaderUpdater`]
+#[derive(Default)]
+pub struct LeaderTracker {
+    send_fanout: usize,
+    /// Leaders the scheduler is currently sending to
+    targets: RwLock<Vec<SocketAddr>>,
+    /// Every leader of the scheduler's last lookup, in schedule order
+    upcoming: RwLock<Vec<SocketAddr>>,
+    leaders: Mutex<HashMap<SocketAddr, LeaderCounts>>,
+    no_leader: AtomicU64,
+}
+
+impl LeaderTracker {
+    pub fn new(send_fanout: usize) -> Self {
+        Self {
+            send_fanout,
+            ..Self::default()
+        }
+    }
+
+    /// Note the leaders the scheduler looked up for its next send
+    fn observe(&self, leaders: &[SocketAddr]) {
+        let send = &leaders[..leaders.len().min(self.send_fanout)];
+        *self.targets.write() = send.to_vec();
+        *self.upcoming.write() = leaders.to_vec();
+    }
+
+    /// Leaders the next send goes to
+    pub fn targets(&self) -> Vec<SocketAddr> {
+        self.targets.read().clone()
+    }
+
+    /// Leaders the scheduler looked up last, send targets first
+    pub fn upcoming(&self) -> Vec<SocketAddr> {
+        self.upcoming.read().clone()
+    }
+
+    /// Whether `leader` has landed our transactions at least as often as it
+    /// lost them, and landed at least one
+    pub fn is_warm(&self, leader: &SocketAddr) -> bool {
+        self.leaders
+            .lock()
+            .get(leader)
+            .is_some_and(|counts| counts.succeeded > 0 && counts.succeeded >= counts.failed)
+    }
+
+    /// Count a send to `targets`
+    pub fn record_send(&self, targets: &[SocketAddr]) {
+        if targets.is_empty() {
+            self.no_leader.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        let mut leaders = self.leaders.lock();
+        for target in targets {
+            leaders.entry(*target).or_default().attempted += 1;
+        }
+    }
+
+    /// Credit `targets` with a transaction that landed or was lost
+    pub fn record_outcome(&self, targets: &[SocketAddr], landed: bool) {
+        let mut leaders = self.leaders.lock();
+        for target in targets {
+            let counts = leaders.entry(*target).or_default();
+            if landed {
+                counts.succeeded += 1;
+            } else {
+                counts.failed += 1;
+            }
+        }
+    }
+
+    /// Sends made while no upcoming leader was known
+    pub fn no_leader_sends(&self) -> u64 {
+        self.no_leader.load(Ordering::Relaxed)
+    }
+
+    /// Outcomes per leader, most attempted first
+    pub fn leader_health(&self) -> Vec<LeaderHealth> {
+        let mut health: Vec<_> = self
+            .leaders
+            .lock()
+            .iter()
+            .map(|(address, counts)| LeaderHealth {
+                address: *address,
+                attempted: counts.attempted,
+                succeeded: counts.succeeded,
+                failed: counts.failed,
+            })
+            .collect();
+        health.sort_by(|a, b| {
+            b.attempted
+                .cmp(&a.attempted)
+                .then(a.address.cmp(&b.address))
+        });
+        health
+    }
+}
+
+/// `LeaderUpdater` that records the leaders it hands out
+pub struct TrackingLeaderUpdater {
+    inner: Box<dyn LeaderUpdater>,
+    tracker: Arc<LeaderTracker>,
+}
+
+impl TrackingLeaderUpdater {
+    pub fn new(inner: Box<dyn LeaderUpdater>, tracker: Arc<LeaderTracker>) -> Self {
+        Self { inner, tracker }
+    }
+}
+
+#[async_trait]
+impl LeaderUpdater for TrackingLeaderUpdater {
+    fn next_leaders(&mut self, lookahead_leaders: usize) -> Vec<SocketAddr> {
+        let leaders = self.inner.next_leaders(lookahead_leaders);
+        self.tracker.observe(&leaders);
+        leaders
+    }
+
+    async fn stop(&mut self) {
+        self.inner.stop().await;
+    }
+}
+
+/// Connection-level totals from the scheduler's `SendTransactionStats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TpuSendSummary {
+    /// Transactions written to a leader connection
+    pub sent: u64,
+    /// Connections that could not be established
+    pub connect_errors: u64,
+    /// Established connections that failed
+    pub connection_errors: u64,
+}
+
+impl TpuSendSummary {
+    pub fn from_stats(stats: &SendTransactionStats) -> Self {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        Self {
+            sent: load(&stats.successfully_sent),
+            connect_errors: load(&stats.connect_error_cannot_start)
+                + load(&stats.connect_error_invalid_remote_address)
+                + load(&stats.connect_error_other),
+            connection_errors: load(&stats.connection_error_connection_closed)
+                + load(&stats.connection_error_reset)
+                + load(&stats.connection_error_timed_out),
+        }
+    }
+}
+
+#[derive(Default)]
+struct BreakerState {
+    /// Recent outcomes (time, landed), oldest first
+    outcomes: VecDeque<(Instant, bool)>,
+    disabled_until: Option<Instant>,
+}
+
+/// Disables TPU for a cooldown when its error rate gets too high
+pub struct TpuBreaker {
+    window: Duration,
+    max_error_rate: f64,
+    min_samples: usize,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl TpuBreaker {
+    pub fn new(config: &TpuAutoDisableConfig) -> Self {
+        Self {
+            window: Duration::from_secs(config.window_secs),
+            max_error_rate: config.max_error_rate,
+            min_samples: config.min_samples,
+            cooldown: Duration::from_secs(config.cooldown_secs),
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Record whether a TPU send landed
+    ///
+    /// Outcomes arriving while disabled (sends started before) are ignored.
+    pub fn record(&self, landed: bool, now: Instant) {
+        let mut state = self.state.lock();
+        if state.disabled_until.is_some() {
+            return;
+        }
+
+        state.outcomes.push_back((now, landed));
+        while let Some((at, _)) = state.outcomes.front() {
+            if now.duration_since(*at) <= self.window {
+                break;
+            }
+            state.outcomes.pop_front();
+        }
+
+        let samples = state.outcomes.len();
+        if samples < self.min_samples {
+            return;
+        }
+        let errors = state.outcomes.iter().filter(|(_, landed)| !landed).count();
+        let error_rate = errors as f64 / samples as f64;
+        if error_rate > self.max_error_rate {
+            log::warn!(
+                "TPU error rate {:.0}% over the last {:?} ({} of {} sends lost), using RPC only for {:?}",
+                error_rate * 100.0,
+                self.window,
+                errors,
+                samples,
+                self.cooldown
+            );
+            state.outcomes.clear();
+            state.disabled_until = Some(now + self.cooldown);
+        }
+    }
+
+    /// Whether TPU may be used at `now`; re-enables it once the cooldown ends
+    pub fn is_enabled(&self, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        match state.disabled_until {
+            Some(until) if now < until => false,
+            Some(_) => {
+                log::info!("TPU cooldown over, re-enabling TPU submission");
+                state.disabled_until = None;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> TpuBreaker {
+        TpuBreaker::new(&TpuAutoDisableConfig {
+            window_secs: 60,
+            max_error_rate: 0.5,
+            min_samples: 4,
+            cooldown_secs: 120,
+        })
+    }
+
+    #[test]
+    fn test_breaker_trips_on_error_rate() {
+        let breaker = breaker();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // Below min_samples nothing trips
+        breaker.record(true, at(0));
+        breaker.record(false, at(1));
+        breaker.record(false, at(2));
+        assert!(breaker.is_enabled(at(2)));
+
+        // 3 of 4 lost
+        breaker.record(false, at(3));
+        assert!(!breaker.is_enabled(at(3)));
+
+        // Late outcomes while disabled don't extend the cooldown
+        breaker.record(false, at(60));
+        assert!(!breaker.is_enabled(at(122)));
+        assert!(breaker.is_enabled(at(123)));
+    }
+
+    #[test]
+    fn test_breaker_restarts_clean_after_cooldown() {
+        let breaker = breaker();
+        let start = Instant::now();
+        for i in 0..4 {
+            breaker.record(false, start + Duration::from_secs(i));
+        }
+        assert!(!breaker.is_enabled(start + Duration::from_secs(10)));
+
+        // Re-enabled with an empty window: one more loss doesn't trip it
+        let resumed = start + Duration::from_secs(200);
+        assert!(breaker.is_enabled(resumed));
+        breaker.record(false, resumed);
+        assert!(breaker.is_enabled(resumed));
+    }
+
+    #[test]
+    fn test_breaker_window_slides() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        // Old losses age out of the window before the rate is computed
+        for i in 0..3 {
+            breaker.record(false, start + Duration::from_secs(i));
+        }
+        for i in 0..3 {
+            breaker.record(true, start + Duration::from_secs(100 + i));
+        }
+        breaker.record(false, start + Duration::from_secs(103));
+        assert!(breaker.is_enabled(start + Duration::from_secs(103)));
+
+        // Exactly at the threshold (3 of 6) stays enabled
+        breaker.record(false, start + Duration::from_secs(105));
+        breaker.record(false, start + Duration::from_secs(106));
+        assert!(breaker.is_enabled(start + Duration::from_secs(106)));
+    }
+
+    #[test]
+    fn test_leader_tracker() {
+        let tracker = LeaderTracker::new(2);
+        let a: SocketAddr = "10.0.0.1:8009".parse().unwrap();
+        let b: SocketAddr = "10.0.0.2:8009".parse().unwrap();
+        let c: SocketAddr = "10.0.0.3:8009".parse().unwrap();
+
+        // No leader known yet
+        tracker.record_send(&tracker.targets());
+        assert_eq!(tracker.no_leader_sends(), 1);
+
+        // Only the send fanout is targeted; the rest are connect-ahead
+        tracker.observe(&[a, b, c]);
+        let targets = tracker.targets();
+        assert_eq!(targets, vec![a, b]);
+        tracker.record_send(&targets);
+        tracker.record_send(&targets);
+        tracker.record_outcome(&targets, true);
+
+        tracker.observe(&[b]);
+        tracker.record_send(&tracker.targets());
+        tracker.record_outcome(&[b], false);
+
+        assert_eq!(
+            tracker.leader_health(),
+            vec![
+                LeaderHealth {
+                    address: b,
+                    attempted: 3,
+                    succeeded: 1,
+                    failed: 1,
+                },
+                LeaderHealth {
+                    address: a,
+                    attempted: 2,
+                    succeeded: 1,
+                    failed: 0,
+                },
+            ]
+        );
+        assert_eq!(tracker.no_leader_sends(), 1);
+    }
+}
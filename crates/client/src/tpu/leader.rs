@@ -0,0 +1,234 @@
+//! Leader updater driven by the shared slot subscription
+//!
+//! `create_leader_updater` from solana-tpu-client-next opens its own
+//! `slotSubscribe` WebSocket. This updater instead reads the current slot
+//! from the client's shared `SlotSubscription`, and only uses RPC to refresh
+//! the leader schedule and cluster TPU addresses.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::clock::NUM_CONSECUTIVE_LEADER_SLOTS;
+use solana_sdk::pubkey::Pubkey;
+use solana_tpu_client_next::leader_updater::LeaderUpdater;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// Number of slot leaders fetched per schedule refresh
+const LEADER_SCHEDULE_WINDOW: u64 = 1000;
+
+/// Refresh the schedule once fewer than this many slots remain in the window
+const LEADER_SCHEDULE_REFRESH_MARGIN: u64 = 200;
+
+/// How often cluster TPU addresses are refreshed
+const CLUSTER_NODES_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Poll interval of the background refresh task
+const REFRESH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct LeaderSchedule {
+    /// First slot covered by `leaders`
+    start_slot: u64,
+    /// Leader for each slot starting at `start_slot`
+    leaders: Vec<Pubkey>,
+    /// Validator identity -> TPU QUIC address
+    tpu_addrs: HashMap<Pubkey, SocketAddr>,
+}
+
+impl LeaderSchedule {
+    fn leader_at(&self, slot: u64) -> Option<&Pubkey> {
+        let offset = slot.checked_sub(self.start_slot)?;
+        self.leaders.get(offset as usize)
+    }
+
+    fn needs_refresh(&self, slot: u64) -> bool {
+        let end_slot = self.start_slot + self.leaders.len() as u64;
+        slot < self.start_slot || slot + LEADER_SCHEDULE_REFRESH_MARGIN >= end_slot
+    }
+}
+
+/// `LeaderUpdater` that follows the shared slot subscription
+pub struct SharedSlotLeaderUpdater {
+    slot_rx: watch::Receiver<u64>,
+    schedule: Arc<RwLock<LeaderSchedule>>,
+    cancel: CancellationToken,
+}
+
+impl SharedSlotLeaderUpdater {
+    /// Load the initial leader schedule and spawn the background refresh task
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial slot, schedule, or cluster node fetch fails.
+    pub async fn new(rpc_client: Arc<RpcClient>, slot_rx: watch::Receiver<u64>) -> Result<Self> {
+        let schedule = Arc::new(RwLock::new(LeaderSchedule::default()));
+
+        // Seed from RPC in case the shared subscription hasn't delivered a slot yet
+        let slot = match *slot_rx.borrow() {
+            0 => rpc_client
+                .get_slot()
+                .await
+                .map_err(|e| anyhow!("Failed to fetch current slot: {}", e))?,
+            slot => slot,
+        };
+        refresh_cluster_nodes(&rpc_client, &schedule).await?;
+        refresh_leaders(&rpc_client, &schedule, slot).await?;
+
+        let cancel = CancellationToken::new();
+        tokio::spawn(run_refresh(
+            rpc_client,
+            slot_rx.clone(),
+            schedule.clone(),
+            cancel.clone(),
+        ));
+
+        Ok(Self {
+            slot_rx,
+            schedule,
+            cancel,
+        })
+    }
+}
+
+#[async_trait]
+impl LeaderUpdater for SharedSlotLeaderUpdater {
+    fn next_leaders(&mut self, lookahead_leaders: usize) -> Vec<SocketAddr> {
+        let current_slot = *self.slot_rx.borrow();
+        let schedule = self.schedule.read();
+
+        let mut addrs = Vec::with_capacity(lookahead_leaders);
+        for i in 0..lookahead_leaders as u64 {
+            let slot = current_slot + i * NUM_CONSECUTIVE_LEADER_SLOTS;
+            let Some(addr) = schedule
+                .leader_at(slot)
+                .and_then(|leader| schedule.tpu_addrs.get(leader))
+            else {
+                continue;
+            };
+            if !addrs.contains(addr) {
+                addrs.push(*addr);
+            }
+        }
+        addrs
+    }
+
+    async fn stop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Drop for SharedSlotLeaderUpdater {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+async fn run_refresh(
+    rpc_client: Arc<RpcClient>,
+    slot_rx: watch::Receiver<u64>,
+    schedule: Arc<RwLock<LeaderSchedule>>,
+    cancel: CancellationToken,
+) {
+    let mut last_nodes_refresh = Instant::now();
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(REFRESH_POLL_INTERVAL) => {}
+            _ = cancel.cancelled() => break,
+        }
+
+        if last_nodes_refresh.elapsed() >= CLUSTER_NODES_REFRESH_INTERVAL {
+            match refresh_cluster_nodes(&rpc_client, &schedule).await {
+                Ok(()) => last_nodes_refresh = Instant::now(),
+                Err(e) => log::warn!("TPU leader updater: {}", e),
+            }
+        }
+
+        let slot = *slot_rx.borrow();
+        if slot > 0 && schedule.read().needs_refresh(slot) {
+            if let Err(e) = refresh_leaders(&rpc_client, &schedule, slot).await {
+                log::warn!("TPU leader updater: {}", e);
+            }
+        }
+    }
+
+    log::debug!("TPU leader updater stopped");
+}
+
+async fn refresh_leaders(
+    rpc_client: &RpcClient,
+    schedule: &RwLock<LeaderSchedule>,
+    slot: u64,
+) -> Result<()> {
+    let leaders = rpc_client
+        .get_slot_leaders(slot, LEADER_SCHEDULE_WINDOW)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch slot leaders: {}", e))?;
+
+    let mut schedule = schedule.write();
+    schedule.start_slot = slot;
+    schedule.leaders = leaders;
+    Ok(())
+}
+
+async fn refresh_cluster_nodes(
+    rpc_client: &RpcClient,
+    schedule: &RwLock<LeaderSchedule>,
+) -> Result<()> {
+    let nodes = rpc_client
+        .get_cluster_nodes()
+        .await
+        .map_err(|e| anyhow!("Failed to fetch cluster nodes: {}", e))?;
+
+    let tpu_addrs = nodes
+        .into_iter()
+        .filter_map(|node| {
+            let pubkey = node.pubkey.parse::<Pubkey>().ok()?;
+            Some((pubkey, node.tpu_quic?))
+        })
+        .collect();
+
+    schedule.write().tpu_addrs = tpu_addrs;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leader_at() {
+        let leader = Pubkey::new_unique();
+        let schedule = LeaderSchedule {
+            start_slot: 100,
+            leaders: vec![leader; 4],
+            tpu_addrs: HashMap::new(),
+        };
+
+        assert_eq!(schedule.leader_at(100), Some(&leader));
+        assert_eq!(schedule.leader_at(103), Some(&leader));
+        assert_eq!(schedule.leader_at(104), None);
+        assert_eq!(schedule.leader_at(99), None);
+    }
+
+    #[test]
+    fn test_needs_refresh() {
+        let schedule = LeaderSchedule {
+            start_slot: 1000,
+            leaders: vec![Pubkey::default(); LEADER_SCHEDULE_WINDOW as usize],
+            tpu_addrs: HashMap::new(),
+        };
+
+        assert!(!schedule.needs_refresh(1000));
+        assert!(schedule.needs_refresh(999));
+        assert!(
+            schedule.needs_refresh(1000 + LEADER_SCHEDULE_WINDOW - LEADER_SCHEDULE_REFRESH_MARGIN)
+        );
+    }
+}
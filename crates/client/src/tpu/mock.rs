@@ -0,0 +1,18 @@
+//! In-memory TPU for tests
+
+use parking_lot::Mutex;
+use solana_sdk::transaction::Transaction;
+use std::sync::Arc;
+
+/// Collects what a [`super::TpuClient::new_mock`] client sends, in order
+#[derive(Default)]
+pub struct MockTpuSink {
+    pub received: Arc<Mutex<Vec<Transaction>>>,
+}
+
+impl MockTpuSink {
+    /// Transactions sent so far, including re-sends
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.received.lock().clone()
+    }
+}
@@ -31,20 +31,56 @@
 //! │  └───────────────────────────────────────────┘  │
 //! └─────────────────────────────────────────────────┘
 //! ```
+//!
+//! [`TpuClient::send_transaction_with_confirmation`] adds confirmation
+//! polling through the `RpcPool` on top of the fire-and-forget send.
+//!
+//! When a shared slot receiver is supplied, leader tracking follows the
+//! client's single `SlotSubscription` (see [`SharedSlotLeaderUpdater`])
+//! instead of opening a second `slotSubscribe` WebSocket.
+//!
+//! Every send is attributed to the leaders it was fanned out to (see
+//! [`TpuClient::leader_health`]). With `tpu.auto_disable` configured, a
+//! [`TpuBreaker`] turns TPU off while too many sends fail to land.
+//!
+//! [`TpuClient::next_good_send_window`] tells a worker how long to hold a send
+//! so it reaches a leader that has been landing our transactions.
+
+mod alignment;
+mod confirmation;
+mod health;
+mod leader;
+#[cfg(test)]
+mod mock;
+mod submission;
 
+pub use alignment::{SendScheduler, SendWindow};
+pub use confirmation::{await_confirmation, TpuError, RESEND_INTERVAL};
+pub use health::{LeaderHealth, LeaderTracker, TpuBreaker, TpuSendSummary, TrackingLeaderUpdater};
+pub use leader::SharedSlotLeaderUpdater;
+#[cfg(test)]
+pub use mock::MockTpuSink;
+pub use submission::{
+    send_both, send_broadcast, BothModeWinner, LatencyHistogram, SubmissionMetrics, LATENCY_WINDOW,
+};
+
+use crate::config::TpuAutoDisableConfig;
+use crate::rpc::RpcPool;
 use anyhow::{anyhow, Result};
+use solana_commitment_config::CommitmentConfig;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::transaction::Transaction;
+use solana_sdk::{signature::Signature, transaction::Transaction};
 use solana_tpu_client_next::{
     connection_workers_scheduler::{
         BindTarget, ConnectionWorkersScheduler, ConnectionWorkersSchedulerConfig, Fanout,
     },
-    leader_updater::create_leader_updater,
+    leader_updater::{create_leader_updater, LeaderUpdater},
     send_transaction_stats::SendTransactionStats,
     transaction_batch::TransactionBatch,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use tokio_util::sync::CancellationToken;
 
@@ -62,6 +98,15 @@ pub struct TpuClient {
     tx_sender: mpsc::Sender<TransactionBatch>,
     stats: Arc<SendTransactionStats>,
     cancel: CancellationToken,
+    confirmation_rpc: Arc<RpcPool>,
+    confirmation_poll_interval: Duration,
+    confirmation_timeout: Duration,
+    tracker: Arc<LeaderTracker>,
+    scheduler: SendScheduler,
+    breaker: Option<TpuBreaker>,
+    /// Takes every send instead of the scheduler (see [`Self::new_mock`])
+    #[cfg(test)]
+    mock_sink: Option<Arc<MockTpuSink>>,
 }
 
 /// Configuration for the TPU client
@@ -80,6 +125,16 @@ pub struct TpuClientConfig {
     pub leaders_fanout: usize,
     /// Channel buffer size for transaction batches
     pub worker_channel_size: usize,
+    /// Shared slot feed; when set, no dedicated slot WebSocket is opened
+    pub slot_receiver: Option<watch::Receiver<u64>>,
+    /// RPC pool polled for signature statuses after a send
+    pub confirmation_rpc: Arc<RpcPool>,
+    /// Delay between signature status polls (milliseconds)
+    pub confirmation_poll_interval_ms: u64,
+    /// Default time to wait for confirmation (milliseconds)
+    pub confirmation_timeout_ms: u64,
+    /// Disable TPU for a cooldown when its error rate gets too high
+    pub auto_disable: Option<TpuAutoDisableConfig>,
 }
 
 impl TpuClient {
@@ -100,9 +155,14 @@ impl TpuClient {
         // Uses same endpoint URL as our custom RpcPool
         let rpc_client = Arc::new(RpcClient::new(config.rpc_url.clone()));
 
-        let leader_updater = create_leader_updater(rpc_client, config.websocket_url, None)
-            .await
-            .map_err(|e| anyhow!("Failed to create leader updater: {:?}", e))?;
+        let leader_updater: Box<dyn LeaderUpdater> = match config.slot_receiver {
+            Some(slot_rx) => Box::new(SharedSlotLeaderUpdater::new(rpc_client, slot_rx).await?),
+            None => create_leader_updater(rpc_client, config.websocket_url, None)
+                .await
+                .map_err(|e| anyhow!("Failed to create leader updater: {:?}", e))?,
+        };
+        let tracker = Arc::new(LeaderTracker::new(config.leaders_fanout));
+        let leader_updater = Box::new(TrackingLeaderUpdater::new(leader_updater, tracker.clone()));
 
         let (tx_sender, tx_receiver) =
             mpsc::channel::<TransactionBatch>(config.worker_channel_size);
@@ -154,9 +214,38 @@ impl TpuClient {
             tx_sender,
             stats,
             cancel,
+            confirmation_rpc: config.confirmation_rpc,
+            confirmation_poll_interval: Duration::from_millis(config.confirmation_poll_interval_ms),
+            confirmation_timeout: Duration::from_millis(config.confirmation_timeout_ms),
+            tracker,
+            scheduler: SendScheduler::new(config.leaders_fanout),
+            breaker: config.auto_disable.as_ref().map(TpuBreaker::new),
+            #[cfg(test)]
+            mock_sink: None,
         })
     }
 
+    /// Create a client that records sends in `sink` instead of opening QUIC
+    /// connections
+    ///
+    /// Confirmation still polls `confirmation_rpc`.
+    #[cfg(test)]
+    pub fn new_mock(sink: Arc<MockTpuSink>, confirmation_rpc: Arc<RpcPool>) -> Self {
+        let (tx_sender, _) = mpsc::channel(1);
+        Self {
+            tx_sender,
+            stats: Arc::new(SendTransactionStats::default()),
+            cancel: CancellationToken::new(),
+            confirmation_rpc,
+            confirmation_poll_interval: Duration::from_millis(10),
+            confirmation_timeout: Duration::from_secs(5),
+            tracker: Arc::new(LeaderTracker::new(1)),
+            scheduler: SendScheduler::new(1),
+            breaker: None,
+            mock_sink: Some(sink),
+        }
+    }
+
     /// Send a single transaction via TPU (fire-and-forget)
     ///
     /// This method queues the transaction for submission to upcoming slot leaders.
@@ -174,6 +263,12 @@ impl TpuClient {
     /// - Transaction serialization fails
     /// - The internal channel is closed (scheduler has stopped)
     pub async fn send_transaction(&self, transaction: &Transaction) -> Result<()> {
+        #[cfg(test)]
+        if let Some(sink) = &self.mock_sink {
+            sink.received.lock().push(transaction.clone());
+            return Ok(());
+        }
+
         let wire_tx = bincode::serialize(transaction)?;
         let batch = TransactionBatch::new(vec![wire_tx]);
 
@@ -181,10 +276,79 @@ impl TpuClient {
             .send(batch)
             .await
             .map_err(|_| anyhow!("TPU channel closed"))?;
+        self.tracker.record_send(&self.tracker.targets());
 
         Ok(())
     }
 
+    /// Send a transaction via TPU and wait until it reaches `commitment`
+    ///
+    /// Polls signature statuses every `confirmation_poll_interval_ms`,
+    /// re-sending via TPU every [`RESEND_INTERVAL`] in case the leader dropped
+    /// it. Fails with [`TpuError::ConfirmationTimeout`] if the transaction
+    /// has not reached `commitment` within `timeout`, or
+    /// [`TpuError::TransactionFailed`] if it landed with an error.
+    pub async fn send_transaction_with_confirmation(
+        &self,
+        transaction: &Transaction,
+        commitment: CommitmentConfig,
+        timeout: Duration,
+    ) -> Result<Signature, TpuError> {
+        self.send_transaction(transaction)
+            .await
+            .map_err(|e| TpuError::Send(e.to_string()))?;
+        self.confirm_transaction(transaction, commitment, timeout)
+            .await
+    }
+
+    /// Wait for an already sent transaction to reach `commitment`
+    ///
+    /// Same as [`Self::send_transaction_with_confirmation`] without the
+    /// initial send, for callers that sent it some other way.
+    ///
+    /// The outcome is credited to the leaders the transaction was sent to
+    /// and fed to the auto-disable breaker.
+    pub async fn confirm_transaction(
+        &self,
+        transaction: &Transaction,
+        commitment: CommitmentConfig,
+        timeout: Duration,
+    ) -> Result<Signature, TpuError> {
+        // The initial send just went to the current targets
+        let mut sent_to = self.tracker.targets();
+        let result = await_confirmation(
+            &self.confirmation_rpc,
+            transaction.signatures[0],
+            commitment,
+            self.confirmation_poll_interval,
+            timeout,
+            || {
+                for target in self.tracker.targets() {
+                    if !sent_to.contains(&target) {
+                        sent_to.push(target);
+                    }
+                }
+                self.send_transaction(transaction)
+            },
+        )
+        .await;
+
+        // A transaction that landed with an error still reached the leader
+        let landed = matches!(result, Ok(_) | Err(TpuError::TransactionFailed { .. }));
+        if !matches!(result, Err(TpuError::Send(_))) {
+            self.tracker.record_outcome(&sent_to, landed);
+        }
+        if let Some(breaker) = &self.breaker {
+            breaker.record(landed, Instant::now());
+        }
+        result
+    }
+
+    /// Default confirmation timeout from the client configuration
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.confirmation_timeout
+    }
+
     /// Get current send statistics
     ///
     /// Returns statistics about transaction sending including success/failure counts
@@ -193,6 +357,41 @@ impl TpuClient {
         &self.stats
     }
 
+    /// Send outcomes per leader, most attempted first
+    pub fn leader_health(&self) -> Vec<LeaderHealth> {
+        self.tracker.leader_health()
+    }
+
+    /// Sends made while no upcoming leader was known
+    pub fn no_leader_sends(&self) -> u64 {
+        self.tracker.no_leader_sends()
+    }
+
+    /// When to send next so a warm leader receives it, holding at most
+    /// `max_hold`
+    pub fn next_good_send_window(&self, max_hold: Duration) -> SendWindow {
+        self.scheduler.next_good_send_window(
+            &self.tracker.upcoming(),
+            |leader| self.tracker.is_warm(leader),
+            max_hold,
+        )
+    }
+
+    /// Connection-level send totals from the scheduler
+    pub fn send_summary(&self) -> TpuSendSummary {
+        TpuSendSummary::from_stats(&self.stats)
+    }
+
+    /// Whether TPU should be used for the next submission
+    ///
+    /// False while the auto-disable breaker is cooling down; callers submit
+    /// via RPC only in the meantime.
+    pub fn is_enabled(&self) -> bool {
+        self.breaker
+            .as_ref()
+            .is_none_or(|breaker| breaker.is_enabled(Instant::now()))
+    }
+
     /// Check if the TPU client is still running
     pub fn is_running(&self) -> bool {
         !self.cancel.is_cancelled()
@@ -0,0 +1,484 @@
+//! Dual-path (TPU + RPC) submission
+//!
+//! With `SubmissionMode::Both` each transaction is sent through TPU and RPC
+//! at the same time, as two attempts of one submission: the send counts as
+//! successful if either path accepts it, and the signature is confirmed once.
+//! Once it confirms, the path that accepted it first is credited in
+//! [`SubmissionMetrics`] (see [`BothModeWinner`]), so operators can tell
+//! whether the extra RPC send is worth its bandwidth. Attribution is
+//! best-effort: both copies carry the same signature, so which one landed
+//! can't be told apart.
+//!
+//! Broadcast mode goes further: the transaction is sent via TPU and to every
+//! healthy submission RPC endpoint at once (see [`send_broadcast`]).
+//!
+//! [`SubmissionMetrics`] also keeps the latencies of recent submissions in a
+//! [`LatencyHistogram`] for P50/P95/P99 reporting.
+
+use super::TpuClient;
+use crate::metrics;
+use crate::rpc::{BroadcastResult, RpcPool};
+use parking_lot::Mutex;
+use solana_sdk::transaction::Transaction;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::time::Instant;
+
+/// Path credited with a confirmed both-mode submission: the one that
+/// accepted the transaction first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BothModeWinner {
+    Tpu,
+    Rpc,
+}
+
+impl BothModeWinner {
+    /// The `won_by` metric label
+    pub fn label(self) -> &'static str {
+        match self {
+            BothModeWinner::Tpu => metrics::PATH_TPU,
+            BothModeWinner::Rpc => metrics::PATH_RPC,
+        }
+    }
+}
+
+/// Counters for dual-path submission, shared by all workers
+#[derive(Debug, Default)]
+pub struct SubmissionMetrics {
+    /// Confirmed both-mode submissions where TPU accepted the transaction first
+    pub both_mode_tpu_wins: AtomicU64,
+    /// Confirmed both-mode submissions where RPC accepted the transaction first
+    pub both_mode_rpc_wins: AtomicU64,
+    /// Transactions sent in broadcast mode
+    pub broadcasts: AtomicU64,
+    /// Broadcast endpoint sends skipped by that endpoint's rate limit
+    pub broadcast_rate_limited: AtomicU64,
+    /// Send-to-confirmation latencies of recent submissions
+    latency: Mutex<LatencyHistogram>,
+}
+
+impl SubmissionMetrics {
+    /// Record the send-to-confirmation latency of a submission
+    pub fn record_submission_latency(&self, micros: u64) {
+        self.latency.lock().record(micros);
+    }
+
+    /// `p`th percentile of recent submission latencies in microseconds
+    /// (0 before any submission is recorded)
+    pub fn percentile(&self, p: f64) -> u64 {
+        self.latency.lock().percentile(p)
+    }
+
+    /// Submission latencies currently in the window
+    pub fn latency_samples(&self) -> usize {
+        self.latency.lock().len()
+    }
+
+    pub fn tpu_wins(&self) -> u64 {
+        self.both_mode_tpu_wins.load(Ordering::Relaxed)
+    }
+
+    pub fn rpc_wins(&self) -> u64 {
+        self.both_mode_rpc_wins.load(Ordering::Relaxed)
+    }
+
+    pub fn broadcasts(&self) -> u64 {
+        self.broadcasts.load(Ordering::Relaxed)
+    }
+
+    pub fn broadcast_rate_limited(&self) -> u64 {
+        self.broadcast_rate_limited.load(Ordering::Relaxed)
+    }
+
+    /// Credit a confirmed both-mode submission to `winner`, once per
+    /// signature
+    pub fn record_both_mode_confirmed(&self, winner: BothModeWinner) {
+        let counter = match winner {
+            BothModeWinner::Tpu => &self.both_mode_tpu_wins,
+            BothModeWinner::Rpc => &self.both_mode_rpc_wins,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        metrics::record_both_mode_win(winner.label());
+    }
+
+    fn record_broadcast(&self) {
+        self.broadcasts.fetch_add(1, Ordering::Relaxed);
+        metrics::record_submission_send(metrics::PATH_BROADCAST);
+    }
+}
+
+/// Submission latencies kept for percentiles
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// Latencies of the most recent [`LATENCY_WINDOW`] submissions, in
+/// microseconds
+///
+/// A fixed-size ring: once full, each new sample replaces the oldest, so
+/// percentiles follow recent conditions rather than the whole run.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    ring: VecDeque<u64>,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, micros: u64) {
+        if self.ring.len() >= LATENCY_WINDOW {
+            self.ring.pop_front();
+        }
+        self.ring.push_back(micros);
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Nearest-rank `p`th percentile (0-100) of the window, 0 if empty
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.ring.is_empty() {
+            return 0;
+        }
+        let mut sorted: Vec<u64> = self.ring.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (p.clamp(0.0, 100.0) * sorted.len() as f64 / 100.0).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+/// Run a TPU send and an RPC send concurrently
+///
+/// Both sends always run to completion, even if one fails early; the other
+/// path's outcome is only logged. Returns the path that succeeded first, to
+/// pass to [`SubmissionMetrics::record_both_mode_confirmed`] once the
+/// transaction confirms. Counts as a single send either way.
+pub async fn send_both<T, R, A, B, TE, RE>(
+    tpu_send: T,
+    rpc_send: R,
+) -> Result<BothModeWinner, String>
+where
+    T: Future<Output = Result<A, TE>>,
+    R: Future<Output = Result<B, RE>>,
+    TE: Display,
+    RE: Display,
+{
+    let timed_tpu = async {
+        let result = tpu_send.await;
+        (result, Instant::now())
+    };
+    let timed_rpc = async {
+        let result = rpc_send.await;
+        (result, Instant::now())
+    };
+
+    let ((tpu_result, tpu_done), (rpc_result, rpc_done)) = tokio::join!(timed_tpu, timed_rpc);
+
+    let winner = match (&tpu_result, &rpc_result) {
+        (Ok(_), Ok(_)) if tpu_done <= rpc_done => BothModeWinner::Tpu,
+        (Ok(_), Ok(_)) | (Err(_), Ok(_)) => BothModeWinner::Rpc,
+        (Ok(_), Err(_)) => BothModeWinner::Tpu,
+        (Err(tpu_error), Err(rpc_error)) => {
+            return Err(format!(
+                "TPU send failed: {}; RPC send failed: {}",
+                tpu_error, rpc_error
+            ));
+        }
+    };
+    metrics::record_submission_send(metrics::PATH_BOTH);
+
+    if let Err(e) = &tpu_result {
+        log::debug!("Both-mode TPU send failed (RPC succeeded): {}", e);
+    }
+    if let Err(e) = &rpc_result {
+        log::debug!("Both-mode RPC send failed (TPU succeeded): {}", e);
+    }
+
+    Ok(winner)
+}
+
+/// Send a transaction via TPU (if available) and every healthy submission
+/// RPC endpoint concurrently
+///
+/// Every copy has the same signature, so the cluster executes it at most once
+/// and the duplicates are dropped. Returns `Ok(())` if any path accepted it.
+pub async fn send_broadcast(
+    tpu_client: Option<&TpuClient>,
+    rpc_client: &RpcPool,
+    transaction: &Transaction,
+    metrics: &SubmissionMetrics,
+) -> Result<(), String> {
+    let tpu_send = async {
+        match tpu_client {
+            Some(tpu_client) => Some(tpu_client.send_transaction(transaction).await),
+            None => None,
+        }
+    };
+    let (tpu_result, rpc_result) =
+        tokio::join!(tpu_send, rpc_client.broadcast_transaction(transaction));
+    metrics.record_broadcast();
+
+    let mut accepted = 0;
+    let mut errors = Vec::new();
+    match tpu_result {
+        Some(Ok(())) => accepted += 1,
+        Some(Err(e)) => errors.push(format!("TPU: {}", e)),
+        None => {}
+    }
+    match rpc_result {
+        Ok(outcomes) => {
+            for outcome in outcomes {
+                match outcome.result {
+                    BroadcastResult::Sent(_) => accepted += 1,
+                    BroadcastResult::Failed(e) => errors.push(format!("{}: {}", outcome.url, e)),
+                    BroadcastResult::RateLimited => {
+                        metrics
+                            .broadcast_rate_limited
+                            .fetch_add(1, Ordering::Relaxed);
+                        errors.push(format!("{}: rate limited", outcome.url));
+                    }
+                }
+            }
+        }
+        Err(e) => errors.push(format!("RPC: {}", e)),
+    }
+
+    for error in &errors {
+        log::debug!("Broadcast send failed via {}", error);
+    }
+    if accepted == 0 {
+        return Err(format!(
+            "No path accepted the broadcast: {}",
+            errors.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_rpc_sequence;
+    use crate::rpc::{EndpointConfig, RateLimitConfig, RpcPoolConfig};
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    #[test]
+    fn test_latency_percentiles_uniform() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.percentile(50.0), 0);
+
+        // 1..=100ms, shuffled so order doesn't matter
+        for i in (1..=100u64).rev() {
+            histogram.record(i * 1000);
+        }
+        assert_eq!(histogram.percentile(50.0), 50_000);
+        assert_eq!(histogram.percentile(95.0), 95_000);
+        assert_eq!(histogram.percentile(99.0), 99_000);
+        assert_eq!(histogram.percentile(100.0), 100_000);
+        assert_eq!(histogram.percentile(0.0), 1_000);
+    }
+
+    #[test]
+    fn test_latency_percentiles_skewed() {
+        let metrics = SubmissionMetrics::default();
+
+        // 90 fast submissions and 10 slow ones
+        for _ in 0..90 {
+            metrics.record_submission_latency(400_000);
+        }
+        for _ in 0..10 {
+            metrics.record_submission_latency(5_000_000);
+        }
+        assert_eq!(metrics.percentile(50.0), 400_000);
+        assert_eq!(metrics.percentile(90.0), 400_000);
+        assert_eq!(metrics.percentile(95.0), 5_000_000);
+        assert_eq!(metrics.percentile(99.0), 5_000_000);
+    }
+
+    #[test]
+    fn test_latency_window_drops_oldest() {
+        let mut histogram = LatencyHistogram::default();
+        for _ in 0..LATENCY_WINDOW {
+            histogram.record(10_000_000);
+        }
+        // A full window of fast samples pushes out every slow one
+        for _ in 0..LATENCY_WINDOW {
+            histogram.record(1_000);
+        }
+        assert_eq!(histogram.len(), LATENCY_WINDOW);
+        assert_eq!(histogram.percentile(99.0), 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_both_paths_attempted_when_one_fails() {
+        let tpu_attempted = AtomicBool::new(false);
+        let rpc_attempted = AtomicBool::new(false);
+
+        // TPU fails immediately, RPC succeeds later
+        let result = send_both(
+            async {
+                tpu_attempted.store(true, Ordering::SeqCst);
+                Err::<(), _>("no leader connection")
+            },
+            async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                rpc_attempted.store(true, Ordering::SeqCst);
+                Ok::<_, String>("sig")
+            },
+        )
+        .await;
+
+        assert_eq!(result, Ok(BothModeWinner::Rpc));
+        assert!(tpu_attempted.load(Ordering::SeqCst));
+        assert!(rpc_attempted.load(Ordering::SeqCst));
+
+        // RPC fails, TPU succeeds
+        let rpc_attempted = AtomicBool::new(false);
+        let result = send_both(async { Ok::<_, String>(()) }, async {
+            rpc_attempted.store(true, Ordering::SeqCst);
+            Err::<(), _>("rate limited")
+        })
+        .await;
+
+        assert_eq!(result, Ok(BothModeWinner::Tpu));
+        assert!(rpc_attempted.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_both_paths_failing_reports_both_errors() {
+        let result = send_both(async { Err::<(), _>("tpu down") }, async {
+            Err::<(), _>("rpc down")
+        })
+        .await;
+
+        let error = result.unwrap_err();
+        assert!(error.contains("tpu down"));
+        assert!(error.contains("rpc down"));
+    }
+
+    #[tokio::test]
+    async fn test_faster_path_wins() {
+        let winner = send_both(
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok::<_, String>(())
+            },
+            async { Ok::<_, String>(()) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(winner, BothModeWinner::Rpc);
+    }
+
+    #[test]
+    fn test_both_mode_wins_counted_on_confirmation() {
+        let metrics = SubmissionMetrics::default();
+        assert_eq!(metrics.tpu_wins() + metrics.rpc_wins(), 0);
+
+        metrics.record_both_mode_confirmed(BothModeWinner::Tpu);
+        metrics.record_both_mode_confirmed(BothModeWinner::Rpc);
+        metrics.record_both_mode_confirmed(BothModeWinner::Tpu);
+        assert_eq!(metrics.tpu_wins(), 2);
+        assert_eq!(metrics.rpc_wins(), 1);
+        assert_eq!(BothModeWinner::Rpc.label(), "rpc");
+    }
+
+    const SEND_OK: &str = r#"{"jsonrpc":"2.0","id":1,"result":"1111111111111111111111111111111111111111111111111111111111111111"}"#;
+    const SEND_ERR: &str = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32002,"message":"Transaction simulation failed"}}"#;
+
+    fn test_transaction() -> Transaction {
+        use solana_sdk::{
+            hash::Hash, instruction::Instruction, message::Message, pubkey::Pubkey,
+            signature::Keypair, signer::Signer,
+        };
+
+        let payer = Keypair::new();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        Transaction::new(&[&payer], message, Hash::default())
+    }
+
+    fn wire_base64(transaction: &Transaction) -> String {
+        use base64::prelude::*;
+        BASE64_STANDARD.encode(bincode::serialize(transaction).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_reaches_every_endpoint() {
+        let mut endpoints = Vec::new();
+        let mut received = Vec::new();
+        for body in [SEND_OK, SEND_OK, SEND_ERR] {
+            let (url, requests) = spawn_mock_rpc_sequence(vec![body]).await;
+            endpoints.push(EndpointConfig::new(url));
+            received.push(requests);
+        }
+        let pool = RpcPool::new(endpoints, RpcPoolConfig::default()).unwrap();
+        let metrics = SubmissionMetrics::default();
+        let transaction = test_transaction();
+
+        // One endpoint rejecting the transaction doesn't fail the broadcast
+        send_broadcast(None, &pool, &transaction, &metrics)
+            .await
+            .unwrap();
+
+        let wire = wire_base64(&transaction);
+        for requests in &received {
+            let requests = requests.lock();
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].contains("sendTransaction"));
+            assert!(requests[0].contains(&wire));
+        }
+        assert_eq!(metrics.broadcasts(), 1);
+
+        // The rejection counts against that endpoint only
+        let stats = pool.stats();
+        assert_eq!(stats[0].1.successful_requests, 1);
+        assert_eq!(stats[1].1.successful_requests, 1);
+        assert_eq!(stats[2].1.failed_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_rate_limited_per_endpoint() {
+        let (limited_url, limited) = spawn_mock_rpc_sequence(vec![SEND_OK]).await;
+        let (open_url, open) = spawn_mock_rpc_sequence(vec![SEND_OK]).await;
+        let mut limited_endpoint = EndpointConfig::new(limited_url);
+        limited_endpoint.rate_limit = Some(RateLimitConfig {
+            requests_per_second: 0.001,
+            burst_capacity: 1,
+        });
+        let pool = RpcPool::new(
+            vec![limited_endpoint, EndpointConfig::new(open_url)],
+            RpcPoolConfig::default(),
+        )
+        .unwrap();
+        let metrics = SubmissionMetrics::default();
+
+        for _ in 0..2 {
+            send_broadcast(None, &pool, &test_transaction(), &metrics)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(limited.lock().len(), 1);
+        assert_eq!(open.lock().len(), 2);
+        assert_eq!(metrics.broadcasts(), 2);
+        assert_eq!(metrics.broadcast_rate_limited(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_fails_when_nothing_accepts() {
+        let (url, _) = spawn_mock_rpc_sequence(vec![SEND_ERR]).await;
+        let pool = RpcPool::with_url(url).unwrap();
+        let metrics = SubmissionMetrics::default();
+
+        let error = send_broadcast(None, &pool, &test_transaction(), &metrics)
+            .await
+            .unwrap_err();
+        assert!(error.contains("simulation failed"));
+    }
+}
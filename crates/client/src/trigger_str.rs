@@ -0,0 +1,743 @@
+//! Trigger string parsing and formatting
+//!
+//! A compact text form for [`Trigger`], shared by the CLIs for input and
+//! display. The form is `<kind>[:<value>]` followed by optional `;key=value`
+//! options:
+//!
+//! | Trigger     | Example                                   | Options                  |
+//! |-------------|-------------------------------------------|--------------------------|
+//! | `Immediate` | `immediate`                               | `jitter`                 |
+//! | `Interval`  | `interval:30`, `interval:1h30m`           | `skippable`, `jitter`    |
+//! | `Cron`      | `cron:0 * * * * *`                        | `skippable`, `jitter`    |
+//! | `Timestamp` | `timestamp:1700000000`, `timestamp:2024-01-01T00:00:00Z` | `jitter`  |
+//! | `Slot`      | `slot:123456`                             |                          |
+//! | `Epoch`     | `epoch:42`                                |                          |
+//! | `EpochInterval` | `epoch:every:1`                       |                          |
+//! | `Account`   | `account:<pubkey>`                        | `offset`, `size`         |
+//!
+//! Intervals accept plain seconds or `d`/`h`/`m`/`s` components. Options left
+//! out take the defaults below, and [`format`] omits options equal to their
+//! default, so `parse(&format(t)) == t` for every trigger.
+
+use antegen_thread_program::state::Trigger;
+use chrono::{DateTime, SecondsFormat};
+use solana_sdk::pubkey::Pubkey;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Default `skippable` for interval and cron triggers
+pub const DEFAULT_SKIPPABLE: bool = true;
+
+/// Default `jitter` in seconds
+pub const DEFAULT_JITTER: u64 = 0;
+
+/// Default byte offset watched by account triggers
+pub const DEFAULT_ACCOUNT_OFFSET: u64 = 0;
+
+/// Default number of bytes watched by account triggers
+pub const DEFAULT_ACCOUNT_SIZE: u64 = 100;
+
+/// Trigger kind names, as written before the `:`
+pub const KINDS: &[&str] = &[
+    "immediate",
+    "interval",
+    "cron",
+    "timestamp",
+    "slot",
+    "epoch",
+    "account",
+];
+
+/// Error from [`parse`], with the byte range of the offending input
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} (at {}..{})", .span.start, .span.end)]
+pub struct TriggerParseError {
+    pub message: String,
+    pub span: Range<usize>,
+}
+
+impl TriggerParseError {
+    fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+/// A piece of the input together with its byte offset
+#[derive(Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+}
+
+impl<'a> Token<'a> {
+    fn span(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+
+    fn split_once(&self, delimiter: char) -> Option<(Token<'a>, Token<'a>)> {
+        let (left, right) = self.text.split_once(delimiter)?;
+        Some((
+            Token {
+                text: left,
+                start: self.start,
+            },
+            Token {
+                text: right,
+                start: self.start + left.len() + delimiter.len_utf8(),
+            },
+        ))
+    }
+
+    fn error(&self, message: impl Into<String>) -> TriggerParseError {
+        TriggerParseError::new(message, self.span())
+    }
+}
+
+/// Options after the trigger value, validated against what the kind accepts
+struct Options<'a> {
+    entries: Vec<(Token<'a>, Token<'a>)>,
+}
+
+impl<'a> Options<'a> {
+    fn parse(
+        tokens: &[Token<'a>],
+        allowed: &[&str],
+        kind: &str,
+    ) -> Result<Self, TriggerParseError> {
+        let mut entries: Vec<(Token<'a>, Token<'a>)> = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| token.error("expected option in the form key=value"))?;
+            if !allowed.contains(&key.text) {
+                return Err(key.error(if allowed.is_empty() {
+                    format!("{} triggers take no options", kind)
+                } else {
+                    format!(
+                        "unknown option '{}' for {} trigger (expected one of: {})",
+                        key.text,
+                        kind,
+                        allowed.join(", ")
+                    )
+                }));
+            }
+            if entries.iter().any(|(k, _)| k.text == key.text) {
+                return Err(key.error(format!("duplicate option '{}'", key.text)));
+            }
+            entries.push((key, value));
+        }
+        Ok(Self { entries })
+    }
+
+    fn get(&self, key: &str) -> Option<Token<'a>> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.text == key)
+            .map(|(_, v)| *v)
+    }
+
+    fn u64_or(&self, key: &str, default: u64) -> Result<u64, TriggerParseError> {
+        match self.get(key) {
+            Some(value) => parse_u64(value, key),
+            None => Ok(default),
+        }
+    }
+
+    fn bool_or(&self, key: &str, default: bool) -> Result<bool, TriggerParseError> {
+        match self.get(key) {
+            Some(value) => match value.text {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(value.error(format!("invalid {}: expected true or false", key))),
+            },
+            None => Ok(default),
+        }
+    }
+}
+
+/// Parse a trigger string
+pub fn parse(input: &str) -> Result<Trigger, TriggerParseError> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for text in input.split(';') {
+        segments.push(Token { text, start });
+        start += text.len() + 1;
+    }
+    let head = segments[0];
+    let option_tokens = &segments[1..];
+
+    let (kind, value) = match head.split_once(':') {
+        Some((kind, value)) => (kind, Some(value)),
+        None => (head, None),
+    };
+
+    let require_value = |what: &str| {
+        value.filter(|v| !v.text.is_empty()).ok_or_else(|| {
+            TriggerParseError::new(
+                format!("missing {} after '{}:'", what, kind.text),
+                head.start + head.text.len()..head.start + head.text.len(),
+            )
+        })
+    };
+
+    match kind.text {
+        "immediate" => {
+            if let Some(value) = value {
+                return Err(value.error("immediate triggers take no value"));
+            }
+            let options = Options::parse(option_tokens, &["jitter"], kind.text)?;
+            Ok(Trigger::Immediate {
+                jitter: options.u64_or("jitter", DEFAULT_JITTER)?,
+            })
+        }
+        "interval" => {
+            let value = require_value("interval")?;
+            let options = Options::parse(option_tokens, &["skippable", "jitter"], kind.text)?;
+            Ok(Trigger::Interval {
+                seconds: parse_duration(value)?,
+                skippable: options.bool_or("skippable", DEFAULT_SKIPPABLE)?,
+                jitter: options.u64_or("jitter", DEFAULT_JITTER)?,
+            })
+        }
+        "cron" => {
+            let value = require_value("cron schedule")?;
+            let options = Options::parse(option_tokens, &["skippable", "jitter"], kind.text)?;
+            let schedule = antegen_cron::Schedule::from_str(value.text)
+                .map_err(|e| value.error(format!("invalid cron schedule: {}", e)))?;
+            if !schedule.is_satisfiable() {
+                return Err(value.error("cron schedule never fires"));
+            }
+            Ok(Trigger::Cron {
+                schedule: value.text.to_string(),
+                skippable: options.bool_or("skippable", DEFAULT_SKIPPABLE)?,
+                jitter: options.u64_or("jitter", DEFAULT_JITTER)?,
+            })
+        }
+        "timestamp" => {
+            let value = require_value("timestamp")?;
+            let options = Options::parse(option_tokens, &["jitter"], kind.text)?;
+            Ok(Trigger::Timestamp {
+                unix_ts: parse_timestamp(value)?,
+                jitter: options.u64_or("jitter", DEFAULT_JITTER)?,
+            })
+        }
+        "slot" => {
+            let value = require_value("slot")?;
+            Options::parse(option_tokens, &[], kind.text)?;
+            Ok(Trigger::Slot {
+                slot: parse_u64(value, "slot")?,
+            })
+        }
+        "epoch" => {
+            let value = require_value("epoch")?;
+            Options::parse(option_tokens, &[], kind.text)?;
+            match value.split_once(':') {
+                Some((every_kw, every)) => {
+                    if every_kw.text != "every" {
+                        return Err(every_kw.error(format!(
+                            "unknown epoch schedule '{}' (expected epoch:<n> or epoch:every:<n>)",
+                            every_kw.text
+                        )));
+                    }
+                    let every = parse_u64(every, "epoch interval").and_then(|n| {
+                        (n > 0)
+                            .then_some(n)
+                            .ok_or_else(|| every.error("epoch interval must be at least 1"))
+                    })?;
+                    Ok(Trigger::EpochInterval { every })
+                }
+                None => Ok(Trigger::Epoch {
+                    epoch: parse_u64(value, "epoch")?,
+                }),
+            }
+        }
+        "account" => {
+            let value = require_value("account pubkey")?;
+            let options = Options::parse(option_tokens, &["offset", "size"], kind.text)?;
+            let address = Pubkey::from_str(value.text)
+                .map_err(|e| value.error(format!("invalid account pubkey: {}", e)))?;
+            Ok(Trigger::Account {
+                address,
+                offset: options.u64_or("offset", DEFAULT_ACCOUNT_OFFSET)?,
+                size: options.u64_or("size", DEFAULT_ACCOUNT_SIZE)?,
+            })
+        }
+        _ => Err(kind.error(format!(
+            "unknown trigger '{}' (expected one of: immediate, interval, cron, timestamp, \
+             slot, epoch, account)",
+            kind.text
+        ))),
+    }
+}
+
+/// Kind name of a trigger, one of [`KINDS`]
+pub fn kind(trigger: &Trigger) -> &'static str {
+    match trigger {
+        Trigger::Immediate { .. } => "immediate",
+        Trigger::Interval { .. } => "interval",
+        Trigger::Cron { .. } => "cron",
+        Trigger::Timestamp { .. } => "timestamp",
+        Trigger::Slot { .. } => "slot",
+        Trigger::Epoch { .. } | Trigger::EpochInterval { .. } => "epoch",
+        Trigger::Account { .. } => "account",
+    }
+}
+
+/// Format a trigger in the form accepted by [`parse`]
+pub fn format(trigger: &Trigger) -> String {
+    let mut out = match trigger {
+        Trigger::Immediate { .. } => "immediate".to_string(),
+        Trigger::Interval { seconds, .. } => format!("interval:{}", format_duration(*seconds)),
+        Trigger::Cron { schedule, .. } => format!("cron:{}", schedule),
+        Trigger::Timestamp { unix_ts, .. } => {
+            format!("timestamp:{}", format_timestamp(*unix_ts))
+        }
+        Trigger::Slot { slot } => format!("slot:{}", slot),
+        Trigger::Epoch { epoch } => format!("epoch:{}", epoch),
+        Trigger::EpochInterval { every } => format!("epoch:every:{}", every),
+        Trigger::Account { address, .. } => format!("account:{}", address),
+    };
+
+    let mut push = |key: &str, value: String| {
+        out.push(';');
+        out.push_str(key);
+        out.push('=');
+        out.push_str(&value);
+    };
+
+    match trigger {
+        Trigger::Immediate { jitter } | Trigger::Timestamp { jitter, .. } => {
+            if *jitter != DEFAULT_JITTER {
+                push("jitter", jitter.to_string());
+            }
+        }
+        Trigger::Interval {
+            skippable, jitter, ..
+        }
+        | Trigger::Cron {
+            skippable, jitter, ..
+        } => {
+            if *skippable != DEFAULT_SKIPPABLE {
+                push("skippable", skippable.to_string());
+            }
+            if *jitter != DEFAULT_JITTER {
+                push("jitter", jitter.to_string());
+            }
+        }
+        Trigger::Account { offset, size, .. } => {
+            if *offset != DEFAULT_ACCOUNT_OFFSET {
+                push("offset", offset.to_string());
+            }
+            if *size != DEFAULT_ACCOUNT_SIZE {
+                push("size", size.to_string());
+            }
+        }
+        Trigger::Slot { .. } | Trigger::Epoch { .. } | Trigger::EpochInterval { .. } => {}
+    }
+
+    out
+}
+
+/// Describe a trigger in plain English, for display only
+///
+/// Cron schedules are spelled out field by field (`0 */5 * * * *` reads
+/// "at second 0, every 5 minutes").
+pub fn describe(trigger: &Trigger) -> String {
+    let mut out = match trigger {
+        Trigger::Immediate { .. } => "once, as soon as possible".to_string(),
+        Trigger::Interval { seconds, .. } => format!("every {}", format_duration(*seconds)),
+        Trigger::Cron { schedule, .. } => describe_cron(schedule),
+        Trigger::Timestamp { unix_ts, .. } => format!("once at {}", format_timestamp(*unix_ts)),
+        Trigger::Slot { slot } => format!("once at slot {}", slot),
+        Trigger::Epoch { epoch } => format!("once at epoch {}", epoch),
+        Trigger::EpochInterval { every: 1 } => "every epoch".to_string(),
+        Trigger::EpochInterval { every } => format!("every {} epochs", every),
+        Trigger::Account {
+            address,
+            offset,
+            size,
+        } => format!(
+            "whenever bytes {}..{} of account {} change",
+            offset,
+            offset.saturating_add(*size),
+            address
+        ),
+    };
+
+    match trigger {
+        Trigger::Interval {
+            skippable, jitter, ..
+        }
+        | Trigger::Cron {
+            skippable, jitter, ..
+        } => {
+            if !skippable {
+                out.push_str(", catching up on missed runs");
+            }
+            if *jitter > 0 {
+                out.push_str(&format!(", with up to {}s jitter", jitter));
+            }
+        }
+        Trigger::Immediate { jitter } | Trigger::Timestamp { jitter, .. } if *jitter > 0 => {
+            out.push_str(&format!(", with up to {}s jitter", jitter));
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Spell out a cron schedule, one clause per restricted field
+fn describe_cron(schedule: &str) -> String {
+    const SINGULAR: [&str; 7] = [
+        "second",
+        "minute",
+        "hour",
+        "day of month",
+        "month",
+        "day of week",
+        "year",
+    ];
+    const PLURAL: [&str; 7] = [
+        "seconds", "minutes", "hours", "days", "months", "days", "years",
+    ];
+
+    let clauses: Vec<String> = schedule
+        .split_whitespace()
+        .zip(SINGULAR.iter().zip(PLURAL))
+        .filter(|(field, _)| *field != "*" && *field != "?")
+        .map(
+            |(field, (singular, plural))| match field.strip_prefix("*/") {
+                Some(step) => format!("every {} {}", step, plural),
+                None => format!("{} {}", singular, field),
+            },
+        )
+        .collect();
+
+    if clauses.is_empty() {
+        format!("every second (cron {})", schedule)
+    } else {
+        format!("at {} (cron {})", clauses.join(", "), schedule)
+    }
+}
+
+fn parse_u64(value: Token, what: &str) -> Result<u64, TriggerParseError> {
+    value
+        .text
+        .parse()
+        .map_err(|_| value.error(format!("invalid {}: expected a non-negative integer", what)))
+}
+
+/// Parse plain seconds (`90`, `-5`) or unit components (`1h30m`, `2d`)
+fn parse_duration(value: Token) -> Result<i64, TriggerParseError> {
+    if let Ok(seconds) = value.text.parse::<i64>() {
+        return Ok(seconds);
+    }
+
+    let mut total: i64 = 0;
+    let mut digits_start = 0;
+    for (i, c) in value.text.char_indices() {
+        if c.is_ascii_digit() {
+            continue;
+        }
+        let span = value.start + digits_start..value.start + i + c.len_utf8();
+        let multiplier = match c {
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => {
+                return Err(TriggerParseError::new(
+                    format!("invalid duration unit '{}' (expected d, h, m or s)", c),
+                    value.start + i..value.start + i + c.len_utf8(),
+                ))
+            }
+        };
+        let amount: i64 = value.text[digits_start..i].parse().map_err(|_| {
+            TriggerParseError::new("expected a number before the unit", span.clone())
+        })?;
+        total = amount
+            .checked_mul(multiplier)
+            .and_then(|s| total.checked_add(s))
+            .ok_or_else(|| TriggerParseError::new("duration is too large", span))?;
+        digits_start = i + c.len_utf8();
+    }
+
+    if digits_start != value.text.len() {
+        return Err(TriggerParseError::new(
+            "missing duration unit (expected d, h, m or s)",
+            value.start + digits_start..value.start + value.text.len(),
+        ));
+    }
+    Ok(total)
+}
+
+/// Format seconds as unit components, falling back to plain seconds for non-positive values
+fn format_duration(seconds: i64) -> String {
+    if seconds <= 0 {
+        return seconds.to_string();
+    }
+
+    let mut out = String::new();
+    let mut remaining = seconds;
+    for (unit, size) in [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)] {
+        let amount = remaining / size;
+        if amount > 0 {
+            out.push_str(&amount.to_string());
+            out.push(unit);
+            remaining %= size;
+        }
+    }
+    out
+}
+
+/// Parse unix seconds or an RFC3339 timestamp
+fn parse_timestamp(value: Token) -> Result<i64, TriggerParseError> {
+    if let Ok(unix_ts) = value.text.parse::<i64>() {
+        return Ok(unix_ts);
+    }
+    DateTime::parse_from_rfc3339(value.text)
+        .map(|dt| dt.timestamp())
+        .map_err(|e| {
+            value.error(format!(
+                "invalid timestamp: expected unix seconds or RFC3339 ({})",
+                e
+            ))
+        })
+}
+
+/// Format as RFC3339 (UTC) when representable, otherwise unix seconds
+fn format_timestamp(unix_ts: i64) -> String {
+    match DateTime::from_timestamp(unix_ts, 0) {
+        Some(dt) => dt.to_rfc3339_opts(SecondsFormat::Secs, true),
+        None => unix_ts.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_variants() -> Vec<Trigger> {
+        let address = Pubkey::new_unique();
+        vec![
+            Trigger::Immediate { jitter: 0 },
+            Trigger::Immediate { jitter: 15 },
+            Trigger::Interval {
+                seconds: 30,
+                skippable: true,
+                jitter: 0,
+            },
+            Trigger::Interval {
+                seconds: 5_400,
+                skippable: false,
+                jitter: 7,
+            },
+            Trigger::Interval {
+                seconds: 0,
+                skippable: true,
+                jitter: 0,
+            },
+            Trigger::Interval {
+                seconds: -5,
+                skippable: true,
+                jitter: 0,
+            },
+            Trigger::Cron {
+                schedule: "0 * * * * *".to_string(),
+                skippable: true,
+                jitter: 0,
+            },
+            Trigger::Cron {
+                schedule: "0 0,30 9-17 ? * MON-FRI".to_string(),
+                skippable: false,
+                jitter: 60,
+            },
+            Trigger::Timestamp {
+                unix_ts: 1_700_000_000,
+                jitter: 0,
+            },
+            Trigger::Timestamp {
+                unix_ts: -1,
+                jitter: 3,
+            },
+            Trigger::Timestamp {
+                unix_ts: i64::MAX,
+                jitter: 0,
+            },
+            Trigger::Slot { slot: 0 },
+            Trigger::Slot { slot: u64::MAX },
+            Trigger::Epoch { epoch: 42 },
+            Trigger::EpochInterval { every: 1 },
+            Trigger::EpochInterval { every: 10 },
+            Trigger::Account {
+                address,
+                offset: 0,
+                size: 100,
+            },
+            Trigger::Account {
+                address,
+                offset: 8,
+                size: 32,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_all_variants() {
+        for trigger in all_variants() {
+            let text = format(&trigger);
+            assert_eq!(parse(&text), Ok(trigger.clone()), "round trip of {}", text);
+        }
+    }
+
+    #[test]
+    fn test_format_omits_defaults() {
+        assert_eq!(format(&Trigger::Immediate { jitter: 0 }), "immediate");
+        assert_eq!(
+            format(&Trigger::Interval {
+                seconds: 5_400,
+                skippable: true,
+                jitter: 0,
+            }),
+            "interval:1h30m"
+        );
+        assert_eq!(
+            format(&Trigger::Cron {
+                schedule: "0 * * * * *".to_string(),
+                skippable: false,
+                jitter: 10,
+            }),
+            "cron:0 * * * * *;skippable=false;jitter=10"
+        );
+        assert_eq!(
+            format(&Trigger::Timestamp {
+                unix_ts: 1_704_067_200,
+                jitter: 0,
+            }),
+            "timestamp:2024-01-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_parse_human_durations() {
+        let seconds = |s: &str| match parse(s).unwrap() {
+            Trigger::Interval { seconds, .. } => seconds,
+            other => panic!("unexpected trigger {:?}", other),
+        };
+        assert_eq!(seconds("interval:30"), 30);
+        assert_eq!(seconds("interval:45s"), 45);
+        assert_eq!(seconds("interval:5m"), 300);
+        assert_eq!(seconds("interval:1h30m"), 5_400);
+        assert_eq!(seconds("interval:1d2h3m4s"), 93_784);
+    }
+
+    #[test]
+    fn test_parse_rfc3339_timestamp() {
+        assert_eq!(
+            parse("timestamp:2024-01-01T01:00:00+01:00"),
+            Ok(Trigger::Timestamp {
+                unix_ts: 1_704_067_200,
+                jitter: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_defaults_match_cli() {
+        let address = Pubkey::new_unique();
+        assert_eq!(
+            parse(&format!("account:{}", address)),
+            Ok(Trigger::Account {
+                address,
+                offset: DEFAULT_ACCOUNT_OFFSET,
+                size: DEFAULT_ACCOUNT_SIZE,
+            })
+        );
+        assert_eq!(
+            parse("cron:0 * * * * *"),
+            Ok(Trigger::Cron {
+                schedule: "0 * * * * *".to_string(),
+                skippable: DEFAULT_SKIPPABLE,
+                jitter: DEFAULT_JITTER,
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_spans() {
+        let span = |s: &str| parse(s).unwrap_err().span;
+
+        // Unknown kind covers the kind only
+        assert_eq!(span("hourly:5"), 0..6);
+        // Bad unit points at the unit character
+        assert_eq!(span("interval:5x"), 10..11);
+        // Trailing digits without a unit
+        assert_eq!(span("interval:1h30"), 11..13);
+        // Bad value covers the value
+        assert_eq!(span("slot:abc"), 5..8);
+        // Missing value points just past the colon
+        assert_eq!(span("epoch:"), 6..6);
+        // Unknown option covers the key
+        assert_eq!(span("interval:30;offset=1"), 12..18);
+        // Bad option value covers the value
+        assert_eq!(span("cron:* * * * * *;skippable=maybe"), 27..32);
+        // Malformed option covers the whole option
+        assert_eq!(span("immediate;jitter"), 10..16);
+        // Duplicate option covers the repeated key
+        assert_eq!(span("immediate;jitter=1;jitter=2"), 19..25);
+        // Impossible cron schedules cover the schedule
+        assert_eq!(span("cron:0 0 0 31 2 *"), 5..17);
+        assert_eq!(span("cron:0 0 0 29 2 * 2025;jitter=5"), 5..22);
+        // Options on kinds that take none
+        assert_eq!(span("slot:5;jitter=1"), 7..13);
+        // Value on immediate
+        assert_eq!(span("immediate:5"), 10..11);
+        // Epoch intervals need a known keyword and a positive count
+        assert_eq!(span("epoch:each:2"), 6..10);
+        assert_eq!(span("epoch:every:0"), 12..13);
+    }
+
+    #[test]
+    fn test_describe() {
+        let cron = |schedule: &str, skippable| Trigger::Cron {
+            schedule: schedule.to_string(),
+            skippable,
+            jitter: 0,
+        };
+        assert_eq!(
+            describe(&cron("0 */5 * * * *", true)),
+            "at second 0, every 5 minutes (cron 0 */5 * * * *)"
+        );
+        assert_eq!(
+            describe(&cron("0 30 9 * * Mon-Fri", false)),
+            "at second 0, minute 30, hour 9, day of week Mon-Fri (cron 0 30 9 * * Mon-Fri), \
+             catching up on missed runs"
+        );
+        assert_eq!(
+            describe(&Trigger::Interval {
+                seconds: 5400,
+                skippable: true,
+                jitter: 10,
+            }),
+            "every 1h30m, with up to 10s jitter"
+        );
+        assert_eq!(
+            describe(&Trigger::Timestamp {
+                unix_ts: 1_700_000_000,
+                jitter: 0,
+            }),
+            "once at 2023-11-14T22:13:20Z"
+        );
+        assert_eq!(
+            describe(&Trigger::EpochInterval { every: 1 }),
+            "every epoch"
+        );
+        assert_eq!(
+            describe(&Trigger::EpochInterval { every: 3 }),
+            "every 3 epochs"
+        );
+    }
+}
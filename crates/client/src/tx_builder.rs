@@ -0,0 +1,362 @@
+//! Transaction construction strategies
+//!
+//! [`TransactionBuilder`] turns a thread's instructions into a signed
+//! transaction in one of three ways, see [`TxStrategy`].
+//! [`TxStrategy::select`] picks one from what is known about a thread's
+//! execution, and the submitter reports the strategy it used as
+//! `antegen.submission.strategy`.
+//!
+//! TPU and RPC submission, simulation and confirmation take legacy
+//! transactions, so worker executions are built with
+//! [`TxStrategy::LegacyBlockhash`].
+
+use anyhow::{anyhow, bail, Result};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, AddressLookupTableAccount, Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use std::time::Duration;
+
+/// Accounts a legacy message can carry and still fit in a packet
+pub const LEGACY_ACCOUNT_LIMIT: usize = 32;
+
+/// How long a recent blockhash stays valid (150 slots)
+pub const BLOCKHASH_LIFETIME: Duration = Duration::from_secs(60);
+
+/// How a transaction is built
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TxStrategy {
+    /// Legacy message with a recent blockhash
+    #[default]
+    LegacyBlockhash,
+    /// Version 0 message resolving accounts through address lookup tables
+    V0WithAlt,
+    /// Legacy message whose lifetime is a durable nonce, advanced by its
+    /// first instruction
+    DurableNonce,
+}
+
+impl TxStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxStrategy::LegacyBlockhash => "legacy",
+            TxStrategy::V0WithAlt => "v0",
+            TxStrategy::DurableNonce => "durable_nonce",
+        }
+    }
+
+    /// Strategy for a thread, among those the executor is set up for
+    ///
+    /// A thread expected to wait longer than a blockhash lives gets a
+    /// durable nonce; one with more accounts than a legacy message holds
+    /// gets lookup tables. Everything else is built legacy.
+    pub fn select(profile: &ThreadTxProfile, available: &AvailableStrategies) -> Self {
+        if available.durable_nonce && profile.expected_queue_delay >= BLOCKHASH_LIFETIME {
+            TxStrategy::DurableNonce
+        } else if available.lookup_tables && profile.account_count > LEGACY_ACCOUNT_LIMIT {
+            TxStrategy::V0WithAlt
+        } else {
+            TxStrategy::LegacyBlockhash
+        }
+    }
+}
+
+impl std::fmt::Display for TxStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What the processor knows about a thread's next execution
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadTxProfile {
+    /// Distinct accounts referenced by the execution's instructions
+    pub account_count: usize,
+    /// Expected wait between building the transaction and it landing
+    pub expected_queue_delay: Duration,
+}
+
+impl ThreadTxProfile {
+    pub fn new(instructions: &[Instruction], expected_queue_delay: Duration) -> Self {
+        let mut accounts: Vec<&Pubkey> = instructions
+            .iter()
+            .flat_map(|ix| {
+                std::iter::once(&ix.program_id).chain(ix.accounts.iter().map(|m| &m.pubkey))
+            })
+            .collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        Self {
+            account_count: accounts.len(),
+            expected_queue_delay,
+        }
+    }
+}
+
+/// Strategies beyond legacy that the executor can build
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AvailableStrategies {
+    /// Lookup tables are known for the thread's accounts
+    pub lookup_tables: bool,
+    /// The executor has a nonce account it is the authority of
+    pub durable_nonce: bool,
+}
+
+/// Durable nonce a transaction is built against
+#[derive(Debug, Clone, Copy)]
+struct NonceState {
+    account: Pubkey,
+    hash: Hash,
+}
+
+/// Builds and signs an execution transaction with a [`TxStrategy`]
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    instructions: Vec<Instruction>,
+    payer: Pubkey,
+    strategy: TxStrategy,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    nonce: Option<NonceState>,
+}
+
+impl TransactionBuilder {
+    pub fn new(instructions: &[Instruction], payer: Pubkey) -> Self {
+        Self {
+            instructions: instructions.to_vec(),
+            payer,
+            strategy: TxStrategy::default(),
+            lookup_tables: Vec::new(),
+            nonce: None,
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: TxStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Lookup tables for [`TxStrategy::V0WithAlt`]
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    /// Nonce account and its current hash for [`TxStrategy::DurableNonce`];
+    /// the payer must be its authority
+    pub fn with_nonce(mut self, account: Pubkey, hash: Hash) -> Self {
+        self.nonce = Some(NonceState { account, hash });
+        self
+    }
+
+    pub fn strategy(&self) -> TxStrategy {
+        self.strategy
+    }
+
+    /// Build and sign with the chosen strategy
+    ///
+    /// `recent_blockhash` is ignored by [`TxStrategy::DurableNonce`], which
+    /// uses the nonce hash instead.
+    pub fn build(&self, signer: &Keypair, recent_blockhash: Hash) -> Result<VersionedTransaction> {
+        if signer.pubkey() != self.payer {
+            bail!(
+                "Signer {} is not the fee payer {}",
+                signer.pubkey(),
+                self.payer
+            );
+        }
+        match self.strategy {
+            TxStrategy::LegacyBlockhash => Ok(self.build_legacy(signer, recent_blockhash).into()),
+            TxStrategy::V0WithAlt => self.build_v0(signer, recent_blockhash),
+            TxStrategy::DurableNonce => self.build_durable_nonce(signer).map(Into::into),
+        }
+    }
+
+    /// Build and sign a legacy transaction with `recent_blockhash`
+    pub fn build_legacy(&self, signer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let message = Message::new(&self.instructions, Some(&self.payer));
+        Transaction::new(&[signer], message, recent_blockhash)
+    }
+
+    fn build_v0(&self, signer: &Keypair, recent_blockhash: Hash) -> Result<VersionedTransaction> {
+        let message = v0::Message::try_compile(
+            &self.payer,
+            &self.instructions,
+            &self.lookup_tables,
+            recent_blockhash,
+        )
+        .map_err(|e| anyhow!("Failed to compile v0 message: {}", e))?;
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &[signer])
+            .map_err(|e| anyhow!("Failed to sign v0 transaction: {}", e))
+    }
+
+    fn build_durable_nonce(&self, signer: &Keypair) -> Result<Transaction> {
+        let nonce = self
+            .nonce
+            .ok_or_else(|| anyhow!("Durable nonce strategy needs a nonce account"))?;
+
+        // The runtime only accepts a nonce lifetime if its advance comes first
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 1);
+        instructions.push(solana_system_interface::instruction::advance_nonce_account(
+            &nonce.account,
+            &self.payer,
+        ));
+        instructions.extend(self.instructions.iter().cloned());
+
+        let message = Message::new(&instructions, Some(&self.payer));
+        Ok(Transaction::new(&[signer], message, nonce.hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn instructions(accounts: usize) -> Vec<Instruction> {
+        vec![Instruction::new_with_bytes(
+            antegen_thread_program::ID,
+            &[1, 2, 3],
+            (0..accounts)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .collect(),
+        )]
+    }
+
+    fn account_keys(instructions: &[Instruction]) -> Vec<Pubkey> {
+        instructions[0].accounts.iter().map(|m| m.pubkey).collect()
+    }
+
+    #[test]
+    fn test_legacy_strategy() {
+        let payer = Keypair::new();
+        let ixs = instructions(3);
+        let blockhash = Hash::new_unique();
+        let tx = TransactionBuilder::new(&ixs, payer.pubkey())
+            .build(&payer, blockhash)
+            .unwrap();
+
+        assert!(matches!(tx.message, VersionedMessage::Legacy(_)));
+        assert_eq!(*tx.message.recent_blockhash(), blockhash);
+        assert_eq!(tx.message.static_account_keys()[0], payer.pubkey());
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+
+        // The same message the legacy path has always sent
+        let legacy = TransactionBuilder::new(&ixs, payer.pubkey()).build_legacy(&payer, blockhash);
+        assert_eq!(legacy.message, Message::new(&ixs, Some(&payer.pubkey())));
+        assert_eq!(tx.signatures, legacy.signatures);
+    }
+
+    #[test]
+    fn test_v0_strategy_resolves_lookup_tables() {
+        let payer = Keypair::new();
+        let ixs = instructions(40);
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: account_keys(&ixs),
+        };
+        let tx = TransactionBuilder::new(&ixs, payer.pubkey())
+            .with_strategy(TxStrategy::V0WithAlt)
+            .with_lookup_tables(vec![table.clone()])
+            .build(&payer, Hash::new_unique())
+            .unwrap();
+
+        let VersionedMessage::V0(message) = &tx.message else {
+            panic!("expected a v0 message");
+        };
+        // Only the payer and program stay in the message itself
+        assert_eq!(message.account_keys.len(), 2);
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, table.key);
+        assert_eq!(message.address_table_lookups[0].writable_indexes.len(), 40);
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+    }
+
+    #[test]
+    fn test_durable_nonce_strategy_advances_nonce_first() {
+        let payer = Keypair::new();
+        let ixs = instructions(2);
+        let nonce_account = Pubkey::new_unique();
+        let nonce_hash = Hash::new_unique();
+        let builder = TransactionBuilder::new(&ixs, payer.pubkey())
+            .with_strategy(TxStrategy::DurableNonce)
+            .with_nonce(nonce_account, nonce_hash);
+        let tx = builder.build(&payer, Hash::new_unique()).unwrap();
+
+        let VersionedMessage::Legacy(message) = &tx.message else {
+            panic!("expected a legacy message");
+        };
+        assert_eq!(message.recent_blockhash, nonce_hash);
+        assert_eq!(message.instructions.len(), 2);
+
+        // First instruction: the system program advancing the nonce,
+        // authorized by the payer
+        let advance = &message.instructions[0];
+        assert_eq!(
+            message.account_keys[advance.program_id_index as usize],
+            solana_system_interface::program::ID
+        );
+        let expected = solana_system_interface::instruction::advance_nonce_account(
+            &nonce_account,
+            &payer.pubkey(),
+        );
+        assert_eq!(advance.data, expected.data);
+        let advance_accounts: Vec<Pubkey> = advance
+            .accounts
+            .iter()
+            .map(|i| message.account_keys[*i as usize])
+            .collect();
+        assert_eq!(advance_accounts[0], nonce_account);
+        assert_eq!(advance_accounts[2], payer.pubkey());
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+
+        // Without a nonce there is nothing to build against
+        let missing = TransactionBuilder::new(&ixs, payer.pubkey())
+            .with_strategy(TxStrategy::DurableNonce)
+            .build(&payer, Hash::new_unique());
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_signer_must_be_payer() {
+        let ixs = instructions(1);
+        let result = TransactionBuilder::new(&ixs, Pubkey::new_unique())
+            .build(&Keypair::new(), Hash::new_unique());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_select_strategy() {
+        let all = AvailableStrategies {
+            lookup_tables: true,
+            durable_nonce: true,
+        };
+        let small = ThreadTxProfile::new(&instructions(5), Duration::ZERO);
+        let large = ThreadTxProfile::new(&instructions(40), Duration::ZERO);
+        assert_eq!(small.account_count, 6);
+        assert_eq!(large.account_count, 41);
+
+        assert_eq!(
+            TxStrategy::select(&small, &all),
+            TxStrategy::LegacyBlockhash
+        );
+        assert_eq!(TxStrategy::select(&large, &all), TxStrategy::V0WithAlt);
+        let delayed = ThreadTxProfile {
+            expected_queue_delay: BLOCKHASH_LIFETIME,
+            ..large
+        };
+        assert_eq!(TxStrategy::select(&delayed, &all), TxStrategy::DurableNonce);
+
+        // Only strategies the executor is set up for are chosen
+        let none = AvailableStrategies::default();
+        assert_eq!(
+            TxStrategy::select(&large, &none),
+            TxStrategy::LegacyBlockhash
+        );
+        assert_eq!(
+            TxStrategy::select(&delayed, &none),
+            TxStrategy::LegacyBlockhash
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! Write-ahead log of submitted transactions
+//!
+//! Every signed transaction is appended to a JSON lines file before it is
+//! sent, and again once its outcome is known. Each line is flushed to disk
+//! before the client moves on, so after a crash the log still names every
+//! transaction that may have landed.
+//!
+//! On startup, submissions without a recorded outcome are looked up on chain
+//! and their outcome appended, marked as reconciled.
+//!
+//! The file is rotated to `<path>.1` once it grows past the configured size;
+//! only the current and the previous file are kept.
+
+use crate::config::TxLogConfig;
+use crate::fees::priority_fee_lamports;
+use crate::rpc::RpcPool;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signature};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Base fee charged per transaction signature, in lamports
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+/// Most signatures `getSignatureStatuses` accepts per request
+const STATUS_BATCH_SIZE: usize = 256;
+
+/// What a log line records about a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxPhase {
+    /// Signed and about to be sent
+    Submitted,
+    /// Landed successfully
+    Confirmed,
+    /// Failed on chain, in preflight or could not be sent
+    Failed,
+    /// Never seen confirmed; the client gave up waiting or moved on to a
+    /// new blockhash
+    Unconfirmed,
+}
+
+impl TxPhase {
+    pub fn is_outcome(self) -> bool {
+        self != TxPhase::Submitted
+    }
+}
+
+/// One line of the transaction log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxLogEntry {
+    /// Unix seconds the line was written
+    pub timestamp: i64,
+    pub phase: TxPhase,
+    pub signature: String,
+    /// Thread executed; for packed transactions, the first thread packed
+    pub thread: String,
+    pub fiber_cursor: u8,
+    /// Latest slot the client had seen when the line was written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Estimated fee in lamports: base fee plus priority fee
+    pub fee: u64,
+    /// Compute unit price in micro-lamports
+    pub cu_price: u64,
+    /// Submission path: "tpu", "rpc", "both" or "broadcast"
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Outcome was recovered from chain state on startup
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reconciled: bool,
+}
+
+/// A transaction about to be sent, from which its log lines are built
+#[derive(Debug, Clone)]
+pub struct TxSubmission {
+    pub signature: Signature,
+    pub thread: Pubkey,
+    pub fiber_cursor: u8,
+    pub path: &'static str,
+    pub fee: u64,
+    pub cu_price: u64,
+}
+
+impl TxSubmission {
+    /// Describe a transaction signed once and built from `instructions`,
+    /// reading the fee from its compute budget instructions
+    pub fn new(
+        signature: Signature,
+        thread: Pubkey,
+        fiber_cursor: u8,
+        path: &'static str,
+        instructions: &[Instruction],
+    ) -> Self {
+        let (cu_limit, cu_price) = compute_budget(instructions);
+        Self {
+            signature,
+            thread,
+            fiber_cursor,
+            path,
+            fee: LAMPORTS_PER_SIGNATURE + priority_fee_lamports(cu_price, cu_limit),
+            cu_price,
+        }
+    }
+
+    /// Log line for this submission in `phase`
+    pub fn entry(&self, phase: TxPhase, slot: Option<u64>, error: Option<&str>) -> TxLogEntry {
+        TxLogEntry {
+            timestamp: chrono::Utc::now().timestamp(),
+            phase,
+            signature: self.signature.to_string(),
+            thread: self.thread.to_string(),
+            fiber_cursor: self.fiber_cursor,
+            slot,
+            fee: self.fee,
+            cu_price: self.cu_price,
+            path: self.path.to_string(),
+            error: error.map(str::to_string),
+            reconciled: false,
+        }
+    }
+}
+
+/// Compute unit limit and price set by `instructions`
+///
+/// The runtime default limit isn't filled in; a transaction without a limit
+/// instruction is estimated with a limit of 0.
+fn compute_budget(instructions: &[Instruction]) -> (u32, u64) {
+    let mut cu_limit = 0;
+    let mut cu_price = 0;
+    for ix in instructions
+        .iter()
+        .filter(|ix| ix.program_id == solana_compute_budget_interface::ID)
+    {
+        match ix.data.split_first() {
+            // SetComputeUnitLimit(u32)
+            Some((2, rest)) if rest.len() >= 4 => {
+                cu_limit = u32::from_le_bytes(rest[..4].try_into().unwrap());
+            }
+            // SetComputeUnitPrice(u64)
+            Some((3, rest)) if rest.len() >= 8 => {
+                cu_price = u64::from_le_bytes(rest[..8].try_into().unwrap());
+            }
+            _ => {}
+        }
+    }
+    (cu_limit, cu_price)
+}
+
+struct Writer {
+    file: File,
+    len: u64,
+}
+
+/// Append-only transaction log
+pub struct TxLog {
+    path: PathBuf,
+    max_bytes: u64,
+    writer: Mutex<Option<Writer>>,
+}
+
+impl TxLog {
+    /// Open the log at `path`, rotating once it grows past `max_bytes`
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let writer = open_writer(&path)?;
+        Ok(Self {
+            path,
+            max_bytes,
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+
+    /// Open the log described by the client config
+    pub fn from_config(config: &TxLogConfig) -> Result<Self> {
+        let path = shellexpand::tilde(&config.path).to_string();
+        Self::open(path, config.max_bytes)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entry` and flush it to disk
+    pub fn append(&self, entry: &TxLogEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let mut writer = self.writer.lock();
+        if let Some(current) = writer.as_ref() {
+            if current.len > 0 && current.len + line.len() as u64 > self.max_bytes {
+                // Close before renaming; a failed reopen is retried on the next append
+                *writer = None;
+                fs::rename(&self.path, rotated_path(&self.path)).with_context(|| {
+                    format!("Failed to rotate transaction log: {}", self.path.display())
+                })?;
+            }
+        }
+        if writer.is_none() {
+            *writer = Some(open_writer(&self.path)?);
+        }
+
+        let current = writer.as_mut().expect("writer opened above");
+        current
+            .file
+            .write_all(&line)
+            .and_then(|()| current.file.sync_data())
+            .with_context(|| format!("Failed to write transaction log: {}", self.path.display()))?;
+        current.len += line.len() as u64;
+        Ok(())
+    }
+
+    /// Submissions with no recorded outcome, oldest first
+    ///
+    /// Reads the rotated file too, so an outcome logged after a rotation
+    /// still resolves its submission.
+    pub fn unresolved(&self) -> Result<Vec<TxLogEntry>> {
+        let mut pending: Vec<TxLogEntry> = Vec::new();
+        let mut resolved: HashMap<String, TxPhase> = HashMap::new();
+        for path in [rotated_path(&self.path), self.path.clone()] {
+            for entry in read_entries(&path)? {
+                if entry.phase.is_outcome() {
+                    resolved.insert(entry.signature, entry.phase);
+                } else {
+                    pending.push(entry);
+                }
+            }
+        }
+        pending.retain(|entry| !resolved.contains_key(&entry.signature));
+        Ok(pending)
+    }
+
+    /// Look up `pending` submissions on chain and log their outcomes
+    ///
+    /// Take `pending` from [`TxLog::unresolved`] before anything is submitted,
+    /// or in-flight transactions would be reconciled too. Signatures the node
+    /// doesn't know are logged as unconfirmed. Returns the number of
+    /// submissions reconciled.
+    pub async fn reconcile(&self, rpc_client: &RpcPool, pending: &[TxLogEntry]) -> Result<usize> {
+        let mut reconciled = 0;
+
+        for chunk in pending.chunks(STATUS_BATCH_SIZE) {
+            let signatures = chunk
+                .iter()
+                .map(|entry| entry.signature.parse::<Signature>())
+                .collect::<Result<Vec<_>, _>>()
+                .context("Invalid signature in transaction log")?;
+            let statuses = rpc_client.get_signature_statuses(&signatures).await?;
+
+            for (submitted, status) in chunk.iter().zip(statuses) {
+                let (phase, slot, error) = match status {
+                    Some(status) => match status.err {
+                        Some(err) => (TxPhase::Failed, Some(status.slot), Some(err.to_string())),
+                        None => (TxPhase::Confirmed, Some(status.slot), None),
+                    },
+                    None => (TxPhase::Unconfirmed, None, None),
+                };
+                self.append(&TxLogEntry {
+                    timestamp: chrono::Utc::now().timestamp(),
+                    phase,
+                    slot,
+                    error,
+                    reconciled: true,
+                    ..submitted.clone()
+                })?;
+                reconciled += 1;
+            }
+        }
+        Ok(reconciled)
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+fn open_writer(path: &Path) -> Result<Writer> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open transaction log: {}", path.display()))?;
+    let len = file.metadata()?.len();
+    Ok(Writer { file, len })
+}
+
+/// Entries in `path`, skipping lines that don't parse (a write torn by a crash)
+fn read_entries(path: &Path) -> Result<Vec<TxLogEntry>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read transaction log: {}", path.display()))
+        }
+    };
+
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => log::debug!("Skipping malformed transaction log line: {}", e),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::spawn_mock_rpc;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use tempfile::TempDir;
+
+    fn submission(seed: u8) -> TxSubmission {
+        TxSubmission::new(
+            Signature::from([seed; 64]),
+            Pubkey::new_unique(),
+            2,
+            "tpu",
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+                ComputeBudgetInstruction::set_compute_unit_price(10_000),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_fee_from_compute_budget() {
+        let submission = submission(1);
+        assert_eq!(submission.cu_price, 10_000);
+        // 5000 base + 200k CU at 10k micro-lamports
+        assert_eq!(submission.fee, 7000);
+    }
+
+    #[test]
+    fn test_unresolved_submissions() {
+        let dir = TempDir::new().unwrap();
+        let log = TxLog::open(dir.path().join("tx-log.jsonl"), 1 << 20).unwrap();
+        let landed = submission(1);
+        let pending = submission(2);
+
+        log.append(&landed.entry(TxPhase::Submitted, Some(10), None))
+            .unwrap();
+        log.append(&pending.entry(TxPhase::Submitted, Some(11), None))
+            .unwrap();
+        log.append(&landed.entry(TxPhase::Confirmed, Some(12), None))
+            .unwrap();
+
+        let unresolved = log.unresolved().unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].signature, pending.signature.to_string());
+        assert_eq!(unresolved[0].fiber_cursor, 2);
+        assert_eq!(unresolved[0].path, "tpu");
+    }
+
+    #[test]
+    fn test_rotation_keeps_previous_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tx-log.jsonl");
+        let submission = submission(1);
+        let line_len = serde_json::to_vec(&submission.entry(TxPhase::Submitted, None, None))
+            .unwrap()
+            .len() as u64;
+        // Room for one line per file
+        let log = TxLog::open(&path, line_len + 1).unwrap();
+
+        log.append(&submission.entry(TxPhase::Submitted, None, None))
+            .unwrap();
+        log.append(&submission.entry(TxPhase::Unconfirmed, None, None))
+            .unwrap();
+
+        assert_eq!(read_entries(&rotated_path(&path)).unwrap().len(), 1);
+        assert_eq!(read_entries(&path).unwrap().len(), 1);
+        // The outcome in the new file resolves the submission in the rotated one
+        assert!(log.unresolved().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_records_outcomes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tx-log.jsonl");
+        let log = TxLog::open(&path, 1 << 20).unwrap();
+        let landed = submission(1);
+        let dropped = submission(2);
+        log.append(&landed.entry(TxPhase::Submitted, None, None))
+            .unwrap();
+        log.append(&dropped.entry(TxPhase::Submitted, None, None))
+            .unwrap();
+
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":null,"err":null,"status":{"Ok":null},"confirmationStatus":"finalized"},null]}}"#,
+        )
+        .await;
+        let rpc = RpcPool::with_url(url).unwrap();
+
+        let pending = log.unresolved().unwrap();
+        assert_eq!(log.reconcile(&rpc, &pending).await.unwrap(), 2);
+        assert!(log.unresolved().unwrap().is_empty());
+
+        let entries = read_entries(&path).unwrap();
+        assert_eq!(entries[2].phase, TxPhase::Confirmed);
+        assert_eq!(entries[2].slot, Some(8));
+        assert!(entries[2].reconciled);
+        assert_eq!(entries[3].phase, TxPhase::Unconfirmed);
+    }
+}
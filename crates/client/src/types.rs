@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey};
 
 /// Account update message sent from datasources to processor
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountUpdate {
     pub pubkey: Pubkey,
     pub data: Vec<u8>,
@@ -16,6 +16,31 @@ impl AccountUpdate {
     pub fn new(pubkey: Pubkey, data: Vec<u8>, slot: u64) -> Self {
         Self { pubkey, data, slot }
     }
+
+    /// Clock sysvar update at `slot`, laid out the way the validator writes it
+    pub fn new_clock(slot: u64, unix_timestamp: i64) -> Self {
+        let clock = Clock {
+            slot,
+            epoch_start_timestamp: unix_timestamp,
+            epoch: 0,
+            leader_schedule_epoch: 0,
+            unix_timestamp,
+        };
+        let data = bincode::serialize(&clock).expect("Clock is plain data");
+        Self::new(solana_sdk::sysvar::clock::ID, data, slot)
+    }
+}
+
+/// Event sent from the Geyser plugin to the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PluginEvent {
+    /// Account update; `is_startup` is set while the validator streams its snapshot
+    AccountUpdate {
+        update: AccountUpdate,
+        is_startup: bool,
+    },
+    /// The validator finished streaming startup snapshot accounts
+    EndOfStartup,
 }
 
 /// Processor messages that can be sent to the submitter
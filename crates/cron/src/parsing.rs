@@ -20,6 +20,7 @@ impl FromStr for Schedule {
     fn from_str(expression: &str) -> Result<Self, Self::Err> {
         match schedule(expression) {
             Ok((_, schedule_fields)) => {
+                schedule_fields.validate_days_of_month()?;
                 Ok(Schedule::new(String::from(expression), schedule_fields))
             } // Extract from nom tuple
             Err(_) => Err(ErrorKind::Expression("Invalid cron expression.".to_owned()).into()), //TODO: Details
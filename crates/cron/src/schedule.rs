@@ -3,6 +3,7 @@ use chrono::{DateTime, Datelike, Timelike};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::ops::Bound::{Included, Unbounded};
 
+use crate::error::{Error, ErrorKind};
 use crate::ordinal::*;
 use crate::queries::*;
 use crate::time_unit::*;
@@ -37,6 +38,10 @@ impl Schedule {
             .range((Included(query.year_lower_bound()), Unbounded))
             .cloned()
         {
+            // A later year starts from January, not the initial date's month
+            if year > query.year_lower_bound() {
+                query.reset_month();
+            }
             let month_start = query.month_lower_bound();
             if !self.fields.months.ordinals().contains(&month_start) {
                 query.reset_month();
@@ -136,6 +141,10 @@ impl Schedule {
             .rev()
             .cloned()
         {
+            // An earlier year ends in December, not the initial date's month
+            if year < query.year_upper_bound() {
+                query.reset_month();
+            }
             let month_start = query.month_upper_bound();
 
             if !self.fields.months.ordinals().contains(&month_start) {
@@ -291,6 +300,22 @@ impl Schedule {
             && self.fields.seconds.includes(date_time.second() as Ordinal)
     }
 
+    /// Returns true if `datetime`, truncated to the second, is a fire time.
+    ///
+    /// Every field is matched against the wall clock in `datetime`'s own
+    /// timezone, the same way `next_after` produces fire times. Unlike
+    /// `after`, this doesn't search: a time between fire times is `false`.
+    pub fn is_active_at<Z>(&self, datetime: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        let datetime = datetime
+            .clone()
+            .with_nanosecond(0)
+            .unwrap_or_else(|| datetime.clone());
+        self.includes(datetime)
+    }
+
     /// Returns a [TimeUnitSpec](trait.TimeUnitSpec.html) describing the years included
     /// in this [Schedule](struct.Schedule.html).
     pub fn years(&self) -> &impl TimeUnitSpec {
@@ -336,8 +361,34 @@ impl Schedule {
     pub fn timeunitspec_eq(&self, other: &Schedule) -> bool {
         self.fields == other.fields
     }
+
+    /// Returns true if the schedule fires at least once within the supported
+    /// year range (1970-2100).
+    ///
+    /// Catches combinations that parse but can never fire, e.g. Feb 29 limited
+    /// to non-leap years, or a day of month that never falls on the selected
+    /// weekdays within the selected years.
+    pub fn is_satisfiable(&self) -> bool {
+        DateTime::from_timestamp(0, 0).is_some_and(|epoch| self.next_after(&epoch).is_some())
+    }
+
+    /// Returns true if the schedule fires within a bounded window after `after`.
+    ///
+    /// The window spans 28 years, a full cycle of weekday and leap-year
+    /// alignments, so rare but valid schedules like Feb 29 on a Monday remain
+    /// satisfiable.
+    pub fn is_satisfiable_after<Z>(&self, after: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.next_after(after)
+            .is_some_and(|next| next.year() - after.year() <= SATISFIABILITY_WINDOW_YEARS)
+    }
 }
 
+/// Years probed by `Schedule::is_satisfiable_after`
+const SATISFIABILITY_WINDOW_YEARS: i32 = 28;
+
 impl Display for Schedule {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", self.source)
@@ -383,6 +434,40 @@ impl ScheduleFields {
     }
 }
 
+impl ScheduleFields {
+    /// Reject schedules whose days of month never occur in any selected month
+    /// (e.g. Feb 31). Feb 29 counts as possible; whether a leap year is
+    /// actually selected is left to `Schedule::is_satisfiable`.
+    pub(crate) fn validate_days_of_month(&self) -> Result<(), Error> {
+        let months = self.months.ordinals();
+        let days = self.days_of_month.ordinals();
+        let possible = months.iter().any(|&month| {
+            let longest = if month == 2 {
+                29
+            } else {
+                days_in_month(month, 1)
+            };
+            days.iter().any(|&day| day <= longest)
+        });
+        if possible {
+            return Ok(());
+        }
+
+        let join = |set: &OrdinalSet| {
+            set.iter()
+                .map(|ordinal| ordinal.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        Err(ErrorKind::Expression(format!(
+            "day of month {} never occurs in month {}",
+            join(&days),
+            join(&months)
+        ))
+        .into())
+    }
+}
+
 pub struct ScheduleIterator<'a, Z>
 where
     Z: TimeZone,
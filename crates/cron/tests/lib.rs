@@ -246,4 +246,127 @@ mod tests {
         assert!(schedule.minutes().is_all());
         assert!(schedule.seconds().is_all());
     }
+
+    #[test]
+    fn test_impossible_day_of_month_rejected() {
+        let error = Schedule::from_str("0 0 0 31 2 *").unwrap_err();
+        assert!(error.to_string().contains("never occurs"));
+        assert!(Schedule::from_str("0 0 0 30,31 Feb *").is_err());
+        assert!(Schedule::from_str("0 0 0 31 Apr,Jun,Sep,Nov *").is_err());
+
+        // One possible month is enough
+        assert!(Schedule::from_str("0 0 0 31 Feb,Mar *").is_ok());
+    }
+
+    #[test]
+    fn test_leap_day_satisfiable() {
+        let schedule = Schedule::from_str("0 0 0 29 2 *").unwrap();
+        assert!(schedule.is_satisfiable());
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        assert!(schedule.is_satisfiable_after(&start));
+        assert_eq!(
+            schedule.next_after(&start),
+            Some(Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap())
+        );
+
+        // Rare: Feb 29 that is also a Monday
+        let schedule = Schedule::from_str("0 0 0 29 2 Mon").unwrap();
+        assert!(schedule.is_satisfiable_after(&start));
+    }
+
+    #[test]
+    fn test_unsatisfiable_years() {
+        // Parses (Feb 29 exists) but 2025-2027 has no leap year
+        let schedule = Schedule::from_str("0 0 0 29 2 * 2025-2027").unwrap();
+        assert!(!schedule.is_satisfiable());
+
+        // Fired only in the past
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert!(schedule.is_satisfiable());
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert!(!schedule.is_satisfiable_after(&start));
+    }
+
+    #[test]
+    fn test_year_after_start_year_starts_in_january() {
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        let start = Utc.with_ymd_and_hms(2019, 12, 22, 7, 33, 20).unwrap();
+        let expected = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(schedule.next_after(&start), Some(expected));
+
+        let schedule = Schedule::from_str("0 0 0 31 12 * 2019").unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 3, 1, 0, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2019, 12, 31, 0, 0, 0).unwrap();
+        assert_eq!(schedule.prev_before(&end), Some(expected));
+    }
+
+    #[test]
+    fn test_is_active_at_exact_fire_time() {
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        // Monday
+        let fire = Utc.with_ymd_and_hms(2024, 6, 3, 9, 30, 0).unwrap();
+        assert!(schedule.is_active_at(&fire));
+        assert!(!schedule.is_active_at(&(fire - Duration::seconds(1))));
+        assert!(!schedule.is_active_at(&(fire + Duration::seconds(1))));
+
+        // Sub-second precision is dropped
+        assert!(schedule.is_active_at(&(fire + Duration::milliseconds(999))));
+
+        // Right time, wrong weekday (Saturday)
+        let saturday = Utc.with_ymd_and_hms(2024, 6, 8, 9, 30, 0).unwrap();
+        assert!(!schedule.is_active_at(&saturday));
+
+        // Agrees with the fire times `after` produces
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        for fire in schedule.after(&start).take(10) {
+            assert!(schedule.is_active_at(&fire));
+        }
+    }
+
+    #[test]
+    fn test_is_active_at_year_boundary() {
+        let schedule = Schedule::from_str("@yearly").unwrap();
+        let new_year = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert!(schedule.is_active_at(&new_year));
+        assert!(!schedule.is_active_at(&(new_year - Duration::seconds(1))));
+    }
+
+    #[test]
+    fn test_is_active_at_dst_transitions() {
+        // US Eastern: EST is UTC-5, EDT is UTC-4
+        let est = FixedOffset::west_opt(5 * 3600).unwrap();
+        let edt = FixedOffset::west_opt(4 * 3600).unwrap();
+
+        // Spring forward, 2024-03-10: 01:59:59 EST is followed by 03:00:00 EDT,
+        // so a 02:30 wall-clock schedule has no fire time that day
+        let schedule = Schedule::from_str("0 30 2 * * *").unwrap();
+        let transition = Utc.with_ymd_and_hms(2024, 3, 10, 7, 0, 0).unwrap();
+        for offset in -3600..3600 {
+            let instant = transition + Duration::seconds(offset);
+            let local = if offset < 0 {
+                instant.with_timezone(&est)
+            } else {
+                instant.with_timezone(&edt)
+            };
+            assert!(!schedule.is_active_at(&local), "fired at {}", local);
+        }
+
+        // Fall back, 2024-11-03: 01:30 happens in EDT, then again in EST
+        let schedule = Schedule::from_str("0 30 1 * * *").unwrap();
+        let first = Utc
+            .with_ymd_and_hms(2024, 11, 3, 5, 30, 0)
+            .unwrap()
+            .with_timezone(&edt);
+        let second = Utc
+            .with_ymd_and_hms(2024, 11, 3, 6, 30, 0)
+            .unwrap()
+            .with_timezone(&est);
+        assert!(schedule.is_active_at(&first));
+        assert!(schedule.is_active_at(&second));
+
+        // The same instants in UTC are not 01:30
+        assert!(!schedule.is_active_at(&first.with_timezone(&Utc)));
+        assert!(!schedule.is_active_at(&(second - Duration::seconds(1))));
+    }
 }
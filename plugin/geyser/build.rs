@@ -0,0 +1,47 @@
+//! Embeds build metadata so `antegen geyser init` can check that the plugin
+//! matches the validator it is loaded into.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let workspace_manifest = Path::new(&manifest_dir).join("../../Cargo.toml");
+    println!("cargo:rerun-if-changed={}", workspace_manifest.display());
+
+    let agave_version = std::fs::read_to_string(&workspace_manifest)
+        .ok()
+        .and_then(|manifest| pinned_version(&manifest, "agave-geyser-plugin-interface"))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!(
+        "cargo:rustc-env=ANTEGEN_ABI_INFO={{\"plugin_version\":\"{}\",\"agave_version\":\"{}\",\"rustc_version\":\"{}\"}}",
+        std::env::var("CARGO_PKG_VERSION").unwrap(),
+        agave_version,
+        rustc_version.replace('"', "'"),
+    );
+}
+
+/// Version of a workspace dependency, e.g. `name = "=3.1.4"` -> `3.1.4`
+fn pinned_version(manifest: &str, name: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != name {
+            return None;
+        }
+        let version = value.trim();
+        let version = match version.strip_prefix('{') {
+            Some(table) => table.split("version").nth(1)?.split('"').nth(1)?,
+            None => version.trim_matches('"'),
+        };
+        Some(version.trim_start_matches(['=', '^', '~']).to_string())
+    })
+}
@@ -0,0 +1,121 @@
+//! Which account updates the plugin forwards
+//!
+//! The validator calls `update_account` for every account write. Only
+//! accounts owned by the thread program and the clock sysvar are of use to
+//! the client, so everything else is dropped on the raw pubkey and owner
+//! bytes, before any parsing or copying of the account data.
+//!
+//! agave-geyser-plugin-interface has no hook for handing the validator an
+//! owner allowlist, so the validator still serializes every write into the
+//! plugin and filtering happens here.
+
+use solana_program::pubkey::Pubkey;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Owner allowlist plus individually watched accounts
+#[derive(Debug)]
+pub struct AccountFilter {
+    owners: Vec<[u8; 32]>,
+    accounts: Vec<[u8; 32]>,
+    seen: AtomicU64,
+    forwarded: AtomicU64,
+}
+
+impl AccountFilter {
+    /// Accounts owned by `program_ids`, plus the clock sysvar
+    pub fn new(program_ids: &[Pubkey]) -> Self {
+        Self {
+            owners: program_ids.iter().map(|id| id.to_bytes()).collect(),
+            accounts: vec![solana_program::sysvar::clock::ID.to_bytes()],
+            seen: AtomicU64::new(0),
+            forwarded: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether the update of `pubkey`, owned by `owner`, is forwarded
+    pub fn matches(&self, pubkey: &[u8], owner: &[u8]) -> bool {
+        self.seen.fetch_add(1, Ordering::Relaxed);
+        let matches = self.owners.iter().any(|id| id.as_slice() == owner)
+            || self.accounts.iter().any(|id| id.as_slice() == pubkey);
+        if matches {
+            self.forwarded.fetch_add(1, Ordering::Relaxed);
+        }
+        matches
+    }
+
+    /// Updates checked and updates forwarded so far
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.seen.load(Ordering::Relaxed),
+            self.forwarded.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Owner programs on the allowlist
+    pub fn owners(&self) -> Vec<Pubkey> {
+        self.owners
+            .iter()
+            .map(|id| Pubkey::new_from_array(*id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A slot's worth of writes with a mainnet-like owner mix: token and
+    /// vote traffic dominates, thread accounts are a sliver
+    fn stream(thread_program: Pubkey) -> Vec<(Pubkey, Pubkey)> {
+        let token = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+        let system = Pubkey::new_unique();
+        let mut updates = Vec::new();
+        for i in 0..10_000 {
+            let owner = match i % 100 {
+                0 => thread_program,
+                1..=60 => token,
+                61..=90 => vote,
+                _ => system,
+            };
+            updates.push((Pubkey::new_unique(), owner));
+        }
+        updates.push((
+            solana_program::sysvar::clock::ID,
+            solana_program::sysvar::ID,
+        ));
+        updates
+    }
+
+    #[test]
+    fn test_filter_forwards_only_allowlisted_updates() {
+        let thread_program = Pubkey::new_unique();
+        let updates = stream(thread_program);
+
+        // Without a filter every update crosses into the client
+        let unfiltered = updates.len();
+
+        let filter = AccountFilter::new(&[thread_program]);
+        let forwarded = updates
+            .iter()
+            .filter(|(pubkey, owner)| filter.matches(pubkey.as_ref(), owner.as_ref()))
+            .count();
+
+        // 100 thread accounts and the clock
+        assert_eq!(unfiltered, 10_001);
+        assert_eq!(forwarded, 101);
+        assert_eq!(filter.stats(), (10_001, 101));
+    }
+
+    #[test]
+    fn test_filter_allows_several_programs() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let filter = AccountFilter::new(&[first, second]);
+
+        assert!(filter.matches(Pubkey::new_unique().as_ref(), first.as_ref()));
+        assert!(filter.matches(Pubkey::new_unique().as_ref(), second.as_ref()));
+        assert!(!filter.matches(Pubkey::new_unique().as_ref(), Pubkey::new_unique().as_ref()));
+        assert_eq!(filter.owners(), vec![first, second]);
+    }
+}
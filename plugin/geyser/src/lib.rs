@@ -1,17 +1,23 @@
 //! Antegen Geyser Plugin - Thin wrapper for validator integration
 //!
 //! This plugin is loaded by the Solana validator and forwards account updates
-//! to the Antegen client via PluginHandle.
+//! to the Antegen client via PluginHandle, or over a Unix socket to a separately
+//! running client when `datasources.geyser_socket` is set.
 
 use agave_geyser_plugin_interface::geyser_plugin_interface::{
     GeyserPlugin, GeyserPluginError, ReplicaAccountInfo, ReplicaAccountInfoVersions,
     Result as PluginResult,
 };
-use antegen_client::{AccountUpdate, ClientConfig, PluginHandle};
+use antegen_client::datasources::socket::SocketForwarder;
+use antegen_client::{AccountUpdate, ClientConfig, PluginEvent, PluginHandle, PriorityLevel};
 use solana_program::pubkey::Pubkey;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 
+mod filter;
+
+use filter::AccountFilter;
+
 #[derive(Debug, Default)]
 pub struct AntegenPlugin {
     inner: Option<Arc<Inner>>,
@@ -19,16 +25,57 @@ pub struct AntegenPlugin {
 
 struct Inner {
     _runtime: Arc<Runtime>, // Kept alive to prevent runtime drop while plugin is active
-    handle: PluginHandle,
-    program_id: Pubkey,
+    sink: Sink,
+    filter: AccountFilter,
+}
+
+/// Where account updates go
+enum Sink {
+    /// Client running inside the validator process
+    InProcess(PluginHandle),
+    /// Separately running client listening on a Unix socket
+    Socket(SocketForwarder),
+}
+
+impl Sink {
+    fn send_update(
+        &self,
+        update: AccountUpdate,
+        is_startup: bool,
+        priority: PriorityLevel,
+    ) -> anyhow::Result<()> {
+        match self {
+            // Snapshot accounts are bulk-loaded and must not be dropped;
+            // live updates are sent without blocking the validator
+            Sink::InProcess(handle) if is_startup => handle.send_startup_update(update),
+            Sink::InProcess(handle) => handle.try_send_update_with_priority(update, priority),
+            // The client re-derives the priority from the update
+            Sink::Socket(forwarder) => {
+                forwarder.forward(PluginEvent::AccountUpdate { update, is_startup })
+            }
+        }
+    }
+
+    fn end_of_startup(&self) -> anyhow::Result<()> {
+        match self {
+            Sink::InProcess(handle) => handle.notify_end_of_startup(),
+            Sink::Socket(forwarder) => forwarder.forward(PluginEvent::EndOfStartup),
+        }
+    }
 }
 
 impl std::fmt::Debug for Inner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Inner")
             .field("_runtime", &"Arc<Runtime>")
-            .field("handle", &"PluginHandle")
-            .field("program_id", &self.program_id)
+            .field(
+                "sink",
+                &match self.sink {
+                    Sink::InProcess(_) => "PluginHandle",
+                    Sink::Socket(_) => "SocketForwarder",
+                },
+            )
+            .field("filter", &self.filter)
             .finish()
     }
 }
@@ -73,19 +120,33 @@ impl GeyserPlugin for AntegenPlugin {
 
         log::info!("Created tokio runtime");
 
-        // Spawn client in plugin mode
-        let handle = runtime
-            .block_on(async { PluginHandle::spawn(config).await })
-            .map_err(|e| GeyserPluginError::ConfigFileReadError {
-                msg: format!("Failed to spawn client: {}", e),
-            })?;
+        let sink = if let Some(path) = config.datasources.geyser_socket.clone() {
+            // Forward to a separately running client
+            let forwarder = runtime.block_on(async { SocketForwarder::spawn(&path) });
+            log::info!("Forwarding account updates to client socket {}", path);
+            Sink::Socket(forwarder)
+        } else {
+            // Spawn client in plugin mode
+            let handle = runtime
+                .block_on(async { PluginHandle::spawn(config).await })
+                .map_err(|e| GeyserPluginError::ConfigFileReadError {
+                    msg: format!("Failed to spawn client: {}", e),
+                })?;
+            log::info!("Spawned Antegen client in plugin mode");
+            Sink::InProcess(handle)
+        };
 
-        log::info!("Spawned Antegen client in plugin mode");
+        let filter = AccountFilter::new(&[program_id]);
+        log::info!(
+            "Account filter: in-plugin, owners {:?} plus the clock sysvar \
+             (the geyser interface has no validator-side owner filter)",
+            filter.owners()
+        );
 
         self.inner = Some(Arc::new(Inner {
             _runtime: runtime,
-            handle,
-            program_id,
+            sink,
+            filter,
         }));
 
         log::info!("=== Antegen Plugin Loaded Successfully ===");
@@ -94,6 +155,10 @@ impl GeyserPlugin for AntegenPlugin {
 
     fn on_unload(&mut self) {
         log::info!("=== Antegen Plugin Unloading ===");
+        if let Some(inner) = &self.inner {
+            let (seen, forwarded) = inner.filter.stats();
+            log::info!("Forwarded {} of {} account updates", forwarded, seen);
+        }
         self.inner = None;
     }
 
@@ -101,7 +166,7 @@ impl GeyserPlugin for AntegenPlugin {
         &self,
         account: ReplicaAccountInfoVersions,
         slot: u64,
-        _is_startup: bool,
+        is_startup: bool,
     ) -> PluginResult<()> {
         let inner = match &self.inner {
             Some(inner) => inner.clone(),
@@ -139,38 +204,46 @@ impl GeyserPlugin for AntegenPlugin {
             },
         };
 
-        // Parse pubkeys
+        // Filter: only thread program accounts or clock sysvar
+        if !inner
+            .filter
+            .matches(account_info.pubkey, account_info.owner)
+        {
+            return Ok(());
+        }
+
         let pubkey = Pubkey::try_from(account_info.pubkey).map_err(|e| {
             GeyserPluginError::AccountsUpdateError {
                 msg: format!("Failed to parse account pubkey: {}", e),
             }
         })?;
 
-        let owner = Pubkey::try_from(account_info.owner).map_err(|e| {
-            GeyserPluginError::AccountsUpdateError {
-                msg: format!("Failed to parse owner pubkey: {}", e),
-            }
-        })?;
-
-        // Filter: only thread program accounts or clock sysvar
         let is_clock = pubkey == solana_program::sysvar::clock::ID;
-        let is_thread_account = owner == inner.program_id;
 
-        if !is_clock && !is_thread_account {
-            return Ok(());
-        }
-
-        // Create account update
+        // Create account update; the clock goes ahead of queued thread updates
         let update = AccountUpdate::new(pubkey, account_info.data.to_vec(), slot);
+        let priority = if is_clock {
+            PriorityLevel::Critical
+        } else {
+            PriorityLevel::Normal
+        };
 
-        // Send to client (non-blocking)
-        if let Err(e) = inner.handle.try_send_update(update) {
+        if let Err(e) = inner.sink.send_update(update, is_startup, priority) {
             log::warn!("Failed to send account update: {}", e);
         }
 
         Ok(())
     }
 
+    fn notify_end_of_startup(&self) -> PluginResult<()> {
+        if let Some(inner) = &self.inner {
+            if let Err(e) = inner.sink.end_of_startup() {
+                log::warn!("Failed to send end of startup: {}", e);
+            }
+        }
+        Ok(())
+    }
+
     fn account_data_notifications_enabled(&self) -> bool {
         true
     }
@@ -192,3 +265,11 @@ impl GeyserPlugin for AntegenPlugin {
 pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
     Box::into_raw(Box::new(AntegenPlugin::default()))
 }
+
+/// Build metadata (plugin, agave and rustc versions) read back by
+/// `antegen geyser init` to check the plugin against the host validator.
+/// The layout is `ANTEGEN_ABI_INFO:<json>\0`; see `build.rs`.
+#[used]
+#[no_mangle]
+pub static ANTEGEN_ABI_INFO: &[u8] =
+    concat!("ANTEGEN_ABI_INFO:", env!("ANTEGEN_ABI_INFO"), "\0").as_bytes();
@@ -22,4 +22,7 @@ pub enum AntegenFiberError {
 
     #[msg("Fiber account data is malformed or has unknown discriminator")]
     InvalidFiberData,
+
+    #[msg("Compute estimates are not supported on legacy fibers — close and recreate")]
+    LegacyFiberComputeEstimateUnsupported,
 }
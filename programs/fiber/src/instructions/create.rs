@@ -79,6 +79,7 @@ pub fn create(
                 state.last_executed = 0;
                 state.exec_count = 0;
                 state.lookup_tables = lookup_tables;
+                state.estimated_compute_units = None;
                 write_versioned(&fiber_info, &state)?;
             }
         }
@@ -153,16 +154,32 @@ pub fn initialize_fiber<'info>(
         last_executed: 0,
         exec_count: 0,
         lookup_tables,
+        estimated_compute_units: None,
     };
 
     write_versioned(&fiber_info, &state)
 }
 
+/// Write `state` in the current layout, growing a version 1 account to the
+/// current size first. The fiber must already hold the rent for that size.
 pub(crate) fn write_versioned(fiber_info: &AccountInfo, state: &FiberVersionedState) -> Result<()> {
+    let space = 8 + FiberVersionedState::INIT_SPACE;
+    if fiber_info.data_len() < space {
+        require!(
+            fiber_info.lamports() >= Rent::get()?.minimum_balance(space),
+            AntegenFiberError::InsufficientRent
+        );
+        fiber_info.resize(space)?;
+    }
+
     let mut data = fiber_info.try_borrow_mut_data()?;
     data[..8].copy_from_slice(FiberVersionedState::DISCRIMINATOR);
     let state_bytes = borsh::to_vec(state)?;
-    data[8..8 + state_bytes.len()].copy_from_slice(&state_bytes);
+    let end = 8 + state_bytes.len();
+    data[8..end].copy_from_slice(&state_bytes);
+    // Zero the tail so bytes left by a longer instruction never read back as
+    // a trailing field
+    data[end..].fill(0);
     Ok(())
 }
 
@@ -1,9 +1,11 @@
 pub mod close;
 pub mod create;
+pub mod record;
 pub mod swap;
 pub mod update;
 
 pub use close::*;
 pub use create::*;
+pub use record::*;
 pub use swap::*;
 pub use update::*;
@@ -0,0 +1,38 @@
+use crate::errors::AntegenFiberError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+use super::create::write_versioned;
+
+/// Accounts required by the `record_compute_units` instruction.
+/// Thread PDA is signer. Only V1 fibers have room for the estimate.
+#[derive(Accounts)]
+pub struct RecordComputeUnits<'info> {
+    /// Thread PDA - signer (via invoke_signed from Thread Program)
+    pub thread: Signer<'info>,
+
+    /// CHECK: shape-agnostic fiber account — validated manually below.
+    #[account(mut)]
+    pub fiber: UncheckedAccount<'info>,
+}
+
+pub fn record_compute_units(ctx: Context<RecordComputeUnits>, units: Option<u32>) -> Result<()> {
+    let fiber_info = ctx.accounts.fiber.to_account_info();
+
+    let read = {
+        let data = fiber_info.try_borrow_data()?;
+        Fiber::try_deserialize(&mut &data[..])?
+    };
+    require!(
+        read.thread() == ctx.accounts.thread.key(),
+        AntegenFiberError::InvalidFiberPDA
+    );
+
+    match read {
+        Fiber::Legacy(_) => Err(AntegenFiberError::LegacyFiberComputeEstimateUnsupported.into()),
+        Fiber::V1(mut state) => {
+            state.estimated_compute_units = units;
+            write_versioned(&fiber_info, &state)
+        }
+    }
+}
@@ -49,6 +49,7 @@ pub fn swap(ctx: Context<Swap>) -> Result<()> {
 
     let new_compiled = source_read.compiled_instruction().to_vec();
     let new_priority_fee = source_read.priority_fee();
+    let new_compute_estimate = source_read.estimated_compute_units();
 
     // Preserve target's on-disk shape: write back as legacy or V1 to match.
     match target_read {
@@ -65,6 +66,7 @@ pub fn swap(ctx: Context<Swap>) -> Result<()> {
             state.priority_fee = new_priority_fee;
             state.last_executed = 0;
             state.exec_count = 0;
+            state.estimated_compute_units = new_compute_estimate;
             // lookup_tables on target stay as they were — source's ALT set
             // pertains to source's instruction at construction, not what's
             // being copied in. Callers needing different ALTs should issue
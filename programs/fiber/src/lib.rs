@@ -46,6 +46,16 @@ pub mod antegen_fiber {
         instructions::update::update(ctx, fiber_index, instruction, priority_fee, lookup_tables)
     }
 
+    /// Records the compute units the fiber's instruction used, so executors
+    /// can size the compute budget without simulating. Thread PDA must be signer.
+    /// Pass `None` to clear the estimate, so the next exec records a fresh one.
+    pub fn record_compute_units(
+        ctx: Context<RecordComputeUnits>,
+        units: Option<u32>,
+    ) -> Result<()> {
+        instructions::record::record_compute_units(ctx, units)
+    }
+
     /// Closes a fiber account, returns rent to thread PDA.
     pub fn close(ctx: Context<Close>) -> Result<()> {
         instructions::close::close(ctx)
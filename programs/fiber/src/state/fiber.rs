@@ -5,7 +5,8 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 
 /// Current version stamped onto newly written `FiberVersionedState` accounts.
-pub const CURRENT_FIBER_VERSION: u8 = 1;
+/// Version 1 ends at `lookup_tables`; version 2 appends `estimated_compute_units`.
+pub const CURRENT_FIBER_VERSION: u8 = 2;
 
 /// Per-fiber hard cap on lookup tables — matches Solana's v0-tx ALT cap.
 pub const MAX_LOOKUP_TABLES_PER_FIBER: usize = 4;
@@ -38,11 +39,12 @@ pub struct FiberState {
 impl FiberState {
     /// Derive the pubkey of a fiber account.
     pub fn pubkey(thread: Pubkey, fiber_index: u8) -> Pubkey {
-        Pubkey::find_program_address(
-            &[SEED_THREAD_FIBER, thread.as_ref(), &[fiber_index]],
-            &crate::ID,
-        )
-        .0
+        Self::compute_pda(&thread, fiber_index).0
+    }
+
+    /// Derive the address and bump of a thread's fiber at `index`.
+    pub fn compute_pda(thread: &Pubkey, index: u8) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SEED_THREAD_FIBER, thread.as_ref(), &[index]], &crate::ID)
     }
 }
 
@@ -58,7 +60,7 @@ impl FiberInstructionProcessor for FiberState {
 #[account]
 #[derive(Debug, InitSpace)]
 pub struct FiberVersionedState {
-    /// State version. Currently 1.
+    /// State version. Currently 2.
     pub version: u8,
     /// The thread this fiber belongs to
     pub thread: Pubkey,
@@ -74,12 +76,29 @@ pub struct FiberVersionedState {
     /// Address Lookup Tables consumed by this fiber. Capped at 4 (Solana v0 limit).
     #[max_len(4)]
     pub lookup_tables: Vec<Pubkey>,
+    /// Compute units the first `exec_thread` of this instruction used, recorded
+    /// once by the Thread Program. Cleared when the instruction is replaced.
+    pub estimated_compute_units: Option<u32>,
 }
 
 impl FiberVersionedState {
     pub fn pubkey(thread: Pubkey, fiber_index: u8) -> Pubkey {
         FiberState::pubkey(thread, fiber_index)
     }
+
+    /// Lamports `fiber` lacks to be rent-exempt at the current size: the
+    /// whole rent for a new fiber, the difference for a version 1 fiber its
+    /// next write grows. Legacy fibers are never resized and need none.
+    pub fn rent_shortfall(fiber: &AccountInfo) -> Result<u64> {
+        if fiber
+            .try_borrow_data()?
+            .starts_with(FiberState::DISCRIMINATOR)
+        {
+            return Ok(0);
+        }
+        let minimum_balance = Rent::get()?.minimum_balance(8 + Self::INIT_SPACE);
+        Ok(minimum_balance.saturating_sub(fiber.lamports()))
+    }
 }
 
 impl FiberInstructionProcessor for FiberVersionedState {
@@ -88,6 +107,38 @@ impl FiberInstructionProcessor for FiberVersionedState {
     }
 }
 
+/// `FiberVersionedState` as written at version 1, which ends at
+/// `lookup_tables`. Whatever follows it on disk is not a compute estimate.
+/// Version 1 accounts are grown to the current size on their next write.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FiberVersionedStateV1 {
+    pub version: u8,
+    pub thread: Pubkey,
+    #[max_len(1024)]
+    pub compiled_instruction: Vec<u8>,
+    pub last_executed: i64,
+    pub exec_count: u64,
+    pub priority_fee: u64,
+    #[max_len(4)]
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+impl FiberVersionedStateV1 {
+    /// Carries the version 1 fields over; the fiber has no compute estimate.
+    pub fn into_current(self) -> FiberVersionedState {
+        FiberVersionedState {
+            version: CURRENT_FIBER_VERSION,
+            thread: self.thread,
+            compiled_instruction: self.compiled_instruction,
+            last_executed: self.last_executed,
+            exec_count: self.exec_count,
+            priority_fee: self.priority_fee,
+            lookup_tables: self.lookup_tables,
+            estimated_compute_units: None,
+        }
+    }
+}
+
 /// Discriminator-tagged read view over either fiber shape on disk.
 ///
 /// Implements [`AccountDeserialize`] so callers use the same
@@ -111,7 +162,17 @@ impl anchor_lang::AccountDeserialize for Fiber {
         }
         let disc = &buf[..8];
         if disc == FiberVersionedState::DISCRIMINATOR {
-            let state = FiberVersionedState::try_deserialize(buf)?;
+            // The version follows the discriminator; version 1 is shorter
+            let state = if buf
+                .get(8)
+                .is_some_and(|&version| version < CURRENT_FIBER_VERSION)
+            {
+                FiberVersionedStateV1::deserialize(&mut &buf[8..])
+                    .map_err(|_| error!(AntegenFiberError::InvalidFiberData))?
+                    .into_current()
+            } else {
+                FiberVersionedState::try_deserialize(buf)?
+            };
             Ok(Self::V1(state))
         } else if disc == FiberState::DISCRIMINATOR {
             let state = FiberState::try_deserialize(buf)?;
@@ -154,6 +215,14 @@ impl Fiber {
             Self::V1(s) => &s.lookup_tables,
         }
     }
+
+    /// Recorded compute estimate. Legacy fibers never carry one.
+    pub fn estimated_compute_units(&self) -> Option<u32> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::V1(s) => s.estimated_compute_units,
+        }
+    }
 }
 
 impl FiberInstructionProcessor for Fiber {
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Most compiled instruction bytes a fiber account holds
+pub const MAX_COMPILED_INSTRUCTION_SIZE: usize = 1024;
 
 /// Serializable version of Solana's Instruction for easier handling
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
@@ -72,6 +75,27 @@ pub struct CompiledInstructionV0 {
     pub accounts: Vec<Pubkey>,
 }
 
+/// Serialized size of `instruction` once compiled, without compiling it
+///
+/// Equals the length of the Borsh-serialized [`compile_instruction`] output,
+/// which a fiber stores in at most [`MAX_COMPILED_INSTRUCTION_SIZE`] bytes.
+pub fn compiled_instruction_size(instruction: &SerializableInstruction) -> usize {
+    let unique_accounts = instruction
+        .accounts
+        .iter()
+        .map(|acc| acc.pubkey)
+        .chain(std::iter::once(instruction.program_id))
+        .collect::<HashSet<_>>()
+        .len();
+
+    3 // num_ro_signers, num_rw_signers, num_rw
+        + 4 // instructions
+        + 1 // program_id_index
+        + 4 + instruction.accounts.len() // account indices
+        + 4 + instruction.data.len() // data
+        + 4 + unique_accounts * 32 // accounts
+}
+
 /// Compile an instruction into a space-efficient format
 pub fn compile_instruction(instruction: Instruction) -> Result<CompiledInstructionV0> {
     let mut pubkeys_to_metadata: HashMap<Pubkey, AccountMeta> = HashMap::new();
@@ -215,6 +215,172 @@ fn test_sentinel_does_not_promote_program_id() {
     assert!(!real_meta.is_signer);
 }
 
+// ============================================================================
+// randomized round-trip and size tests
+// ============================================================================
+
+/// Deterministic splitmix64, so a failing case reproduces from its seed
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn flag(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+}
+
+/// Instruction drawing its accounts from a small pool, so pubkeys repeat
+/// with differing flags, and now and then passing the program ID itself
+fn random_instruction(rng: &mut Rng) -> Instruction {
+    let program_id = Pubkey::new_unique();
+    let pool: Vec<Pubkey> = (0..1 + rng.below(8))
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let accounts = (0..rng.below(25))
+        .map(|_| {
+            let pubkey = if rng.below(8) == 0 {
+                program_id
+            } else {
+                pool[rng.below(pool.len())]
+            };
+            AccountMeta {
+                pubkey,
+                is_signer: rng.flag(),
+                is_writable: rng.flag(),
+            }
+        })
+        .collect();
+    let data = (0..rng.below(257)).map(|_| rng.next() as u8).collect();
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// What decompiling gives back: every meta of a pubkey carries the union of
+/// its flags, and the program ID as an account is read-only, non-signer
+fn expected_roundtrip(ix: &Instruction) -> Instruction {
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|meta| {
+            let same = || {
+                ix.accounts
+                    .iter()
+                    .filter(|other| other.pubkey == meta.pubkey)
+            };
+            let merged = meta.pubkey != ix.program_id;
+            AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: merged && same().any(|other| other.is_signer),
+                is_writable: merged && same().any(|other| other.is_writable),
+            }
+        })
+        .collect();
+    Instruction {
+        program_id: ix.program_id,
+        accounts,
+        data: ix.data.clone(),
+    }
+}
+
+#[test]
+fn test_randomized_roundtrip() {
+    for seed in 0..500 {
+        let mut rng = Rng(seed);
+        let ix = random_instruction(&mut rng);
+        let expected = expected_roundtrip(&ix);
+        let has_duplicates = ix
+            .accounts
+            .iter()
+            .enumerate()
+            .any(|(i, meta)| ix.accounts[..i].iter().any(|m| m.pubkey == meta.pubkey));
+
+        let compiled = compile_instruction(ix.clone()).unwrap();
+        let decompiled = decompile_instruction(&compiled).unwrap();
+        assert_eq!(decompiled, expected, "seed {}", seed);
+
+        // Without repeated pubkeys or the program ID among the accounts, the
+        // round-trip is lossless
+        if !has_duplicates && ix.accounts.iter().all(|m| m.pubkey != ix.program_id) {
+            assert_eq!(decompiled, ix, "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn test_randomized_compiled_size() {
+    for seed in 0..500 {
+        let ix = random_instruction(&mut Rng(seed));
+        let serializable = SerializableInstruction::from(ix.clone());
+
+        let compiled = compile_instruction(ix).unwrap();
+        assert_eq!(
+            compiled_instruction_size(&serializable),
+            borsh::to_vec(&compiled).unwrap().len(),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn test_compiled_size_edge_cases() {
+    let program_id = Pubkey::new_unique();
+    let size = |ix: &Instruction| {
+        let expected = borsh::to_vec(&compile_instruction(ix.clone()).unwrap())
+            .unwrap()
+            .len();
+        assert_eq!(
+            compiled_instruction_size(&SerializableInstruction::from(ix.clone())),
+            expected
+        );
+        expected
+    };
+
+    // No accounts: the program ID alone
+    let empty = Instruction {
+        program_id,
+        accounts: vec![],
+        data: vec![],
+    };
+    assert_eq!(size(&empty), 3 + 4 + 1 + 4 + 4 + 4 + 32);
+
+    // A repeated meta costs an index byte, not another pubkey
+    let key = Pubkey::new_unique();
+    let once = Instruction {
+        accounts: vec![AccountMeta::new(key, false)],
+        ..empty.clone()
+    };
+    let twice = Instruction {
+        accounts: vec![
+            AccountMeta::new(key, false),
+            AccountMeta::new_readonly(key, true),
+        ],
+        ..empty.clone()
+    };
+    assert_eq!(size(&twice), size(&once) + 1);
+
+    // Largest data a fiber holds alongside just the program ID
+    let full = Instruction {
+        data: vec![0; MAX_COMPILED_INSTRUCTION_SIZE - size(&empty)],
+        ..empty
+    };
+    assert_eq!(size(&full), MAX_COMPILED_INSTRUCTION_SIZE);
+}
+
 // ============================================================================
 // get_instruction tests (PAYER_PUBKEY replacement)
 // ============================================================================
@@ -376,6 +542,7 @@ fn test_fiber_read_dispatches_v1_buffer() {
         exec_count: 3,
         priority_fee: 500,
         lookup_tables: vec![alt_a, alt_b],
+        estimated_compute_units: Some(12_000),
     };
     let buf = craft_v1_buffer(&state);
 
@@ -385,6 +552,35 @@ fn test_fiber_read_dispatches_v1_buffer() {
     assert_eq!(read.compiled_instruction(), &[9, 9, 9]);
     assert_eq!(read.priority_fee(), 500);
     assert_eq!(read.lookup_tables(), &[alt_a, alt_b]);
+    assert_eq!(read.estimated_compute_units(), Some(12_000));
+}
+
+#[test]
+fn test_fiber_read_version_1_ignores_tail() {
+    let thread = Pubkey::new_unique();
+    let alt = Pubkey::new_unique();
+    let state = FiberVersionedStateV1 {
+        version: 1,
+        thread,
+        compiled_instruction: vec![4, 5, 6],
+        last_executed: 10,
+        exec_count: 2,
+        priority_fee: 300,
+        lookup_tables: vec![alt],
+    };
+    let mut buf = Vec::new();
+    buf.extend_from_slice(FiberVersionedState::DISCRIMINATOR);
+    state.serialize(&mut buf).unwrap();
+    // Left over from a longer write: reads as Some(7) in the current layout
+    buf.extend_from_slice(&[1, 7, 0, 0, 0]);
+
+    let read = Fiber::try_deserialize(&mut &buf[..]).unwrap();
+    assert!(!read.is_legacy());
+    assert_eq!(read.thread(), thread);
+    assert_eq!(read.compiled_instruction(), &[4, 5, 6]);
+    assert_eq!(read.priority_fee(), 300);
+    assert_eq!(read.lookup_tables(), &[alt]);
+    assert_eq!(read.estimated_compute_units(), None);
 }
 
 #[test]
@@ -429,6 +625,7 @@ fn test_fiber_versioned_state_roundtrip_empty_lookup_tables() {
         exec_count: 0,
         priority_fee: 0,
         lookup_tables: vec![],
+        estimated_compute_units: None,
     };
     let buf = craft_v1_buffer(&state);
     let read = Fiber::try_deserialize(&mut &buf[..]).unwrap();
@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 pub const SEED_CONFIG: &[u8] = b"thread_config";
 pub const SEED_THREAD: &[u8] = b"thread";
 pub const SEED_NONCE: &[u8] = b"thread_nonce";
@@ -5,3 +7,33 @@ pub const SEED_NONCE: &[u8] = b"thread_nonce";
 pub const TRANSACTION_BASE_FEE_REIMBURSEMENT: u64 = 5_000;
 pub const THREAD_MINIMUM_FEE: u64 = 1_000;
 pub const CLAIM_WINDOW_SECONDS: i64 = 30;
+
+/// Slot duration assumed when converting slot distances to wall-clock time
+pub const ESTIMATED_MS_PER_SLOT: i64 = 400;
+/// Mainnet epoch length, used to estimate when an epoch starts
+pub const ESTIMATED_SLOTS_PER_EPOCH: u64 = 432_000;
+
+/// Most fibers a thread can hold (the capacity of `Thread::fiber_ids`)
+pub const MAX_FIBERS: usize = 50;
+
+/// Default `ThreadConfig::max_chain_depth`
+pub const DEFAULT_MAX_CHAIN_DEPTH: u8 = 16;
+
+/// Default `ThreadConfig::streak_half_life_execs`
+pub const DEFAULT_STREAK_HALF_LIFE_EXECS: u64 = 1_000;
+
+/// Most fibers `create_thread_with_fibers` creates in one instruction
+pub const MAX_INITIAL_FIBERS: usize = 8;
+/// Total compiled size limit of the instructions passed to
+/// `create_thread_with_fibers`, keeping the transaction under 1232 bytes
+pub const MAX_INITIAL_INSTRUCTIONS_SIZE: usize = 768;
+
+/// Loaders that deploy, upgrade and close programs; fibers may not invoke them
+pub const PROGRAM_LOADERS: [Pubkey; 4] = [
+    pubkey!("BPFLoader1111111111111111111111111111111111"),
+    pubkey!("BPFLoader2111111111111111111111111111111111"),
+    pubkey!("BPFLoaderUpgradeab1e11111111111111111111111"),
+    pubkey!("LoaderV411111111111111111111111111111111111"),
+];
+/// Native Config program, which holds cluster-wide configuration accounts
+pub const CONFIG_PROGRAM_ID: Pubkey = pubkey!("Config1111111111111111111111111111111111111");
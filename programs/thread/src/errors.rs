@@ -143,6 +143,51 @@ pub enum AntegenThreadError {
 
     #[msg("Thread has not signaled close - fiber_signal must be Signal::Close")]
     CloseNotSignaled,
+
+    #[msg("Goto signal must target a fiber after the current one in the chain")]
+    InvalidGotoTarget,
+
+    #[msg("Thread cannot reimburse the executor and remain rent-exempt")]
+    ThreadCannotReimburse,
+
+    #[msg("No admin handover is pending")]
+    NoPendingAdmin,
+
+    #[msg("Signer is not the pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("Thread already holds the maximum number of fibers")]
+    TooManyFibers,
+
+    #[msg("Too many initial fiber instructions, or their total size exceeds the limit")]
+    InitialInstructionsTooLarge,
+
+    #[msg("Fiber chain executed more fibers in one slot than the config allows")]
+    ChainDepthExceeded,
+
+    #[msg("Fiber chain revisited a fiber it already executed in this slot")]
+    ChainCycleDetected,
+
+    #[msg("Fiber is disabled after repeated failures")]
+    FiberDisabled,
+
+    #[msg("Fiber failure report does not match a thread stuck on that fiber")]
+    InvalidFailureReport,
+
+    #[msg("Epoch interval must be at least one epoch")]
+    InvalidEpochInterval,
+
+    #[msg("Fibers may not invoke a program loader")]
+    InvalidFiberTarget,
+
+    #[msg("Fiber instruction marks the Config account writable")]
+    DangerousAccountPermission,
+
+    #[msg("Fiber instruction has no data")]
+    EmptyInstructionData,
+
+    #[msg("Thread uses an older account layout; run migrate_thread first")]
+    ThreadNotMigrated,
 }
 
 /// Alias for AntegenThreadError
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when the config admin proposes a successor
+#[event]
+pub struct AdminProposed {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// Emitted when the pending admin accepts and takes over the config
+#[event]
+pub struct AdminAccepted {
+    pub config: Pubkey,
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+/// Emitted when the config admin withdraws a pending proposal
+#[event]
+pub struct AdminProposalCancelled {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// Emitted when the config admin force deletes a thread
+#[event]
+pub struct ThreadDeleted {
+    pub thread: Pubkey,
+    /// The thread authority, who receives the reclaimed lamports
+    pub rent_recipient: Pubkey,
+    /// Fiber accounts closed alongside the thread
+    pub fibers_closed: u8,
+    /// Rent reclaimed from those fibers
+    pub fiber_lamports: u64,
+    /// Total lamports sent to `rent_recipient`, fiber rent included
+    pub reclaimed: u64,
+}
+
+/// Emitted when a fiber is disabled after too many reported failures
+#[event]
+pub struct FiberAutoDisabled {
+    pub thread: Pubkey,
+    pub fiber_index: u8,
+    /// Consecutive failures that reached the config's `max_fiber_failures`
+    pub failures: u8,
+}
+
+/// Emitted by every successful `exec_thread`, chained fibers included
+#[event]
+pub struct ThreadExecuted {
+    pub thread: Pubkey,
+    pub executor: Pubkey,
+    pub fiber_index: u8,
+    /// The thread's `exec_count` after this exec; with `thread`, see
+    /// [`crate::utils::exec_key`], it identifies the exec across replays
+    pub exec_sequence: u64,
+    pub slot: u64,
+}
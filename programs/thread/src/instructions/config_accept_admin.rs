@@ -0,0 +1,44 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Accounts required by the `accept_admin` instruction.
+#[derive(Accounts)]
+pub struct ConfigAcceptAdmin<'info> {
+    /// The proposed admin taking over the config
+    pub pending_admin: Signer<'info>,
+
+    /// The config account being handed over
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ThreadConfig>,
+}
+
+pub fn config_accept_admin(ctx: Context<ConfigAcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let signer = ctx.accounts.pending_admin.key();
+
+    let pending_admin = config
+        .pending_admin
+        .ok_or(AntegenThreadError::NoPendingAdmin)?;
+    require_keys_eq!(
+        signer,
+        pending_admin,
+        AntegenThreadError::InvalidPendingAdmin
+    );
+
+    let previous_admin = config.admin;
+    config.admin = pending_admin;
+    config.pending_admin = None;
+    msg!("Config admin updated to: {}", pending_admin);
+
+    emit!(AdminAccepted {
+        config: config.key(),
+        previous_admin,
+        admin: pending_admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Accounts required by the `cancel_admin_proposal` instruction.
+#[derive(Accounts)]
+pub struct ConfigCancelAdmin<'info> {
+    /// The current config admin
+    #[account(
+        constraint = admin.key() == config.admin @ AntegenThreadError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// The config account with the pending proposal
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ThreadConfig>,
+}
+
+pub fn config_cancel_admin(ctx: Context<ConfigCancelAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let pending_admin = config
+        .pending_admin
+        .take()
+        .ok_or(AntegenThreadError::NoPendingAdmin)?;
+    msg!("Config admin handover to {} cancelled", pending_admin);
+
+    emit!(AdminProposalCancelled {
+        config: config.key(),
+        admin: config.admin,
+        pending_admin,
+    });
+
+    Ok(())
+}
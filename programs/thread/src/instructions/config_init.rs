@@ -27,7 +27,7 @@ pub fn config_init(ctx: Context<ConfigInit>) -> Result<()> {
     let admin = &ctx.accounts.admin;
 
     // Initialize with default values
-    config.version = 1;
+    config.version = CURRENT_CONFIG_VERSION;
     config.bump = ctx.bumps.config;
     config.admin = admin.key();
     config.paused = false;
@@ -36,6 +36,13 @@ pub fn config_init(ctx: Context<ConfigInit>) -> Result<()> {
     config.core_team_bps = 1000; // 10% to core team
     config.grace_period_seconds = 5; // 5 second grace period
     config.fee_decay_seconds = 295; // 295 second decay (total 300s = 5 minutes)
+    config.waive_core_team_on_forgo = false; // Core team fee honored when commission is forgone
+    config.fee_payer_mode = FeePayerMode::ExecutorPays; // Executor pays, recoups via commission
+    config.base_fee_lamports = 5000; // Base signature fee
+    config.pending_admin = None;
+    config.max_chain_depth = DEFAULT_MAX_CHAIN_DEPTH; // Chained fibers per slot
+    config.max_fiber_failures = 0; // Failing fibers are never auto-disabled
+    config.streak_half_life_execs = DEFAULT_STREAK_HALF_LIFE_EXECS; // Commission halves every 1000 execs
 
     msg!("Thread config initialized with admin: {}", admin.key());
 
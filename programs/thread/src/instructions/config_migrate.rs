@@ -0,0 +1,87 @@
+use crate::{constants::*, errors::*, state::*};
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+/// Accounts required by the `migrate_config` instruction.
+#[derive(Accounts)]
+pub struct ConfigMigrate<'info> {
+    /// The config admin. Pays rent for any added space.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The config account to migrate. Read by hand, since an older layout
+    /// does not deserialize as the current `ThreadConfig`.
+    /// CHECK: discriminator and layout are checked in the handler
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn config_migrate(ctx: Context<ConfigMigrate>) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let config_info = ctx.accounts.config.to_account_info();
+
+    // The version follows the discriminator; older versions have a shorter layout
+    let (previous_version, config) = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.len() >= 16 && data.starts_with(ThreadConfig::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let version = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let config = if version < CURRENT_CONFIG_VERSION {
+            ThreadConfigV1::deserialize(&mut &data[8..])
+                .map_err(|_| ErrorCode::AccountDidNotDeserialize)?
+                .into_current()
+        } else {
+            ThreadConfig::try_deserialize(&mut &data[..])?
+        };
+        (version, config)
+    };
+
+    require_keys_eq!(
+        admin.key(),
+        config.admin,
+        AntegenThreadError::InvalidAuthority
+    );
+
+    // Grow the account to the current size, topping up rent from the admin
+    let space = ThreadConfig::space();
+    let minimum_balance = Rent::get()?.minimum_balance(space);
+    let shortfall = minimum_balance.saturating_sub(config_info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                anchor_lang::system_program::ID,
+                Transfer {
+                    from: admin.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    if config_info.data_len() < space {
+        config_info.resize(space)?;
+    }
+
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Migrated thread config from version {} to {}",
+        previous_version,
+        CURRENT_CONFIG_VERSION
+    );
+
+    Ok(())
+}
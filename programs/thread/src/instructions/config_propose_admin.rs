@@ -0,0 +1,36 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Accounts required by the `propose_admin` instruction.
+#[derive(Accounts)]
+pub struct ConfigProposeAdmin<'info> {
+    /// The current config admin
+    #[account(
+        constraint = admin.key() == config.admin @ AntegenThreadError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// The config account whose admin is being handed over
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ThreadConfig>,
+}
+
+pub fn config_propose_admin(ctx: Context<ConfigProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Replaces any earlier proposal
+    config.pending_admin = Some(new_admin);
+    msg!("Config admin handover proposed to: {}", new_admin);
+
+    emit!(AdminProposed {
+        config: config.key(),
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
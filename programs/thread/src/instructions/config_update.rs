@@ -11,6 +11,12 @@ pub struct ConfigUpdateParams {
     pub core_team_bps: Option<u64>,
     pub grace_period_seconds: Option<i64>,
     pub fee_decay_seconds: Option<i64>,
+    pub waive_core_team_on_forgo: Option<bool>,
+    pub fee_payer_mode: Option<FeePayerMode>,
+    pub base_fee_lamports: Option<u64>,
+    pub max_chain_depth: Option<u8>,
+    pub max_fiber_failures: Option<u8>,
+    pub streak_half_life_execs: Option<u64>,
 }
 
 /// Accounts required by the `config_update` instruction.
@@ -35,9 +41,11 @@ pub struct ConfigUpdate<'info> {
 pub fn config_update(ctx: Context<ConfigUpdate>, params: ConfigUpdateParams) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
-    // Update admin if provided
+    // Update admin if provided (prefer propose_admin/accept_admin, which
+    // cannot hand control to a key nobody holds)
     if let Some(new_admin) = params.admin {
         config.admin = new_admin;
+        config.pending_admin = None;
         msg!("Config admin updated to: {}", new_admin);
     }
 
@@ -91,6 +99,36 @@ pub fn config_update(ctx: Context<ConfigUpdate>, params: ConfigUpdateParams) ->
         msg!("Fee decay period updated to: {} seconds", decay_period);
     }
 
+    if let Some(waive) = params.waive_core_team_on_forgo {
+        config.waive_core_team_on_forgo = waive;
+        msg!("Waive core team fee on forgo updated to: {}", waive);
+    }
+
+    if let Some(fee_payer_mode) = params.fee_payer_mode {
+        config.fee_payer_mode = fee_payer_mode;
+        msg!("Fee payer mode updated to: {:?}", fee_payer_mode);
+    }
+
+    if let Some(base_fee_lamports) = params.base_fee_lamports {
+        config.base_fee_lamports = base_fee_lamports;
+        msg!("Base fee updated to: {} lamports", base_fee_lamports);
+    }
+
+    if let Some(max_chain_depth) = params.max_chain_depth {
+        config.max_chain_depth = max_chain_depth;
+        msg!("Max chain depth updated to: {}", max_chain_depth);
+    }
+
+    if let Some(max_fiber_failures) = params.max_fiber_failures {
+        config.max_fiber_failures = max_fiber_failures;
+        msg!("Max fiber failures updated to: {}", max_fiber_failures);
+    }
+
+    if let Some(half_life) = params.streak_half_life_execs {
+        config.streak_half_life_execs = half_life;
+        msg!("Streak half-life updated to: {} execs", half_life);
+    }
+
     // Validate that total fees equal 100%
     let total_fees = config.executor_fee_bps + config.core_team_bps;
     require!(
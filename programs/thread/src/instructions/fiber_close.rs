@@ -15,6 +15,7 @@ pub struct FiberClose<'info> {
     /// The thread to remove the fiber from
     #[account(
         mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         constraint = thread.fiber_ids.contains(&fiber_index) @ AntegenThreadError::InvalidFiberIndex,
         seeds = [
             SEED_THREAD,
@@ -43,6 +44,7 @@ pub fn fiber_close(ctx: Context<FiberClose>, fiber_index: u8) -> Result<()> {
     }
 
     thread.fiber_ids.retain(|&x| x != fiber_index);
+    thread.set_fiber_disabled(fiber_index, false);
     if thread.fiber_ids.is_empty() {
         thread.fiber_cursor = 0;
     }
@@ -1,6 +1,6 @@
 use crate::{errors::*, *};
 use anchor_lang::prelude::*;
-use antegen_fiber_program::state::SerializableInstruction;
+use antegen_fiber_program::state::{FiberVersionedState, SerializableInstruction};
 
 /// Accounts required by the `fiber_create` instruction.
 /// Validates authority, pre-funds fiber from thread, CPIs to Fiber Program to create,
@@ -17,6 +17,7 @@ pub struct FiberCreate<'info> {
     /// The thread to add the fiber to
     #[account(
         mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         seeds = [
             SEED_THREAD,
             thread.authority.as_ref(),
@@ -37,6 +38,43 @@ pub struct FiberCreate<'info> {
     pub system_program: Program<'info, System>,
 }
 
+impl FiberCreate<'_> {
+    /// Reject fiber instructions that are dangerous or likely mis-built.
+    ///
+    /// These are advisory checks, not a security boundary: a fiber can still
+    /// do anything its thread is allowed to sign for.
+    pub fn validate_instruction(instruction: &SerializableInstruction) -> Result<()> {
+        // Prevent thread_delete instructions in fibers
+        if instruction.program_id.eq(&crate::ID)
+            && instruction.data.len().ge(&8)
+            && instruction.data[..8].eq(crate::instruction::DeleteThread::DISCRIMINATOR)
+        {
+            return Err(AntegenThreadError::InvalidInstruction.into());
+        }
+
+        // No program deploys, upgrades or closes
+        require!(
+            !PROGRAM_LOADERS.contains(&instruction.program_id),
+            AntegenThreadError::InvalidFiberTarget
+        );
+
+        require!(
+            !instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.is_writable && meta.pubkey.eq(&CONFIG_PROGRAM_ID)),
+            AntegenThreadError::DangerousAccountPermission
+        );
+
+        require!(
+            !instruction.data.is_empty(),
+            AntegenThreadError::EmptyInstructionData
+        );
+
+        Ok(())
+    }
+}
+
 pub fn fiber_create(
     ctx: Context<FiberCreate>,
     fiber_index: u8,
@@ -44,43 +82,69 @@ pub fn fiber_create(
     priority_fee: u64,
     lookup_tables: Vec<Pubkey>,
 ) -> Result<()> {
-    let thread = &mut ctx.accounts.thread;
-
-    // Prevent thread_delete instructions in fibers
-    if instruction.program_id.eq(&crate::ID)
-        && instruction.data.len().ge(&8)
-        && instruction.data[..8].eq(crate::instruction::DeleteThread::DISCRIMINATOR)
-    {
-        return Err(AntegenThreadError::InvalidInstruction.into());
+    create_thread_fiber(
+        &mut ctx.accounts.thread,
+        &ctx.accounts.fiber.to_account_info(),
+        &ctx.accounts.fiber_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        fiber_index,
+        instruction,
+        priority_fee,
+        lookup_tables,
+    )
+}
+
+/// Parameters for a fiber created alongside other thread changes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FiberCreateParams {
+    pub instruction: SerializableInstruction,
+    pub priority_fee: u64,
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+/// Create fiber `fiber_index` of `thread` via CPI to the Fiber Program.
+///
+/// Funds the fiber's rent from the thread if the account is new or still in
+/// the version 1 layout, and tracks the fiber in `fiber_ids`, up to
+/// [`MAX_FIBERS`] per thread.
+pub(crate) fn create_thread_fiber<'info>(
+    thread: &mut Account<'info, Thread>,
+    fiber: &AccountInfo<'info>,
+    fiber_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    fiber_index: u8,
+    instruction: SerializableInstruction,
+    priority_fee: u64,
+    lookup_tables: Vec<Pubkey>,
+) -> Result<()> {
+    FiberCreate::validate_instruction(&instruction)?;
+
+    if !thread.fiber_ids.contains(&fiber_index) && thread.fiber_ids.len() >= MAX_FIBERS {
+        return Err(AntegenThreadError::TooManyFibers.into());
     }
 
-    // Conditional pre-funding: only pre-fund if fiber account is not yet initialized
-    if ctx.accounts.fiber.to_account_info().data_len() == 0 {
-        let space = 8 + antegen_fiber_program::state::FiberVersionedState::INIT_SPACE;
-        let rent_lamports = Rent::get()?.minimum_balance(space);
-        **thread.to_account_info().try_borrow_mut_lamports()? -= rent_lamports;
-        **ctx
-            .accounts
-            .fiber
-            .to_account_info()
-            .try_borrow_mut_lamports()? += rent_lamports;
+    // Pre-fund a new fiber, or the growth of a version 1 fiber
+    let shortfall = FiberVersionedState::rent_shortfall(fiber)?;
+    if shortfall > 0 {
+        **thread.to_account_info().try_borrow_mut_lamports()? -= shortfall;
+        **fiber.try_borrow_mut_lamports()? += shortfall;
     }
 
     thread.sign(|seeds| {
         antegen_fiber_program::cpi::create(
             CpiContext::new_with_signer(
-                ctx.accounts.fiber_program.key(),
+                fiber_program.key(),
                 antegen_fiber_program::cpi::accounts::Create {
                     thread: thread.to_account_info(),
-                    fiber: ctx.accounts.fiber.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
+                    fiber: fiber.clone(),
+                    system_program: system_program.clone(),
                 },
                 &[seeds],
             ),
             fiber_index,
             instruction,
             priority_fee,
-            lookup_tables.clone(),
+            lookup_tables,
         )
     })?;
 
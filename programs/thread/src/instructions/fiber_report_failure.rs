@@ -0,0 +1,129 @@
+use crate::{errors::*, events::*, state::*, *};
+use anchor_lang::prelude::*;
+
+/// Accounts required by the `fiber_report_failure` instruction.
+/// Pass the same trigger accounts as `thread_exec` in remaining_accounts.
+#[derive(Accounts)]
+pub struct FiberReportFailure<'info> {
+    /// The executor whose exec of the fiber failed
+    pub executor: Signer<'info>,
+
+    /// The thread the failing fiber belongs to
+    #[account(
+        mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
+        seeds = [
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+        ],
+        bump = thread.bump,
+    )]
+    pub thread: Account<'info, Thread>,
+
+    /// The config holding the failure threshold
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ThreadConfig>,
+}
+
+/// Record a failed execution of fiber `fiber_cursor`.
+///
+/// A failed exec reverts with its CPI, so the program cannot see the failure
+/// itself, and the reporter's word is not taken for it. What the program can
+/// check is that the thread is stuck on that fiber: the fiber is the one
+/// `thread_exec` would run next, and the thread has gone a whole commission
+/// window (grace plus decay) past due, or past its last chained exec,
+/// without any executor moving it on. Each further report needs another
+/// window of the same stall, and any successful exec clears the count, so
+/// a thread that executes cannot be reported into disabling one of its
+/// fibers.
+///
+/// Reaching the config's `max_fiber_failures` (0 never disables) disables the
+/// fiber and moves the thread past it; a chain targeting it ends there. The
+/// thread authority re-enables the fiber with `update_fiber`.
+pub fn fiber_report_failure(ctx: Context<FiberReportFailure>, fiber_cursor: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    let thread = &mut ctx.accounts.thread;
+    let thread_pubkey = thread.key();
+
+    require!(
+        !thread.is_paused_at(clock.unix_timestamp),
+        AntegenThreadError::ThreadPaused
+    );
+    require!(!config.paused, AntegenThreadError::GlobalPauseActive);
+    require!(
+        thread.fiber_ids.contains(&fiber_cursor) && !thread.is_fiber_disabled(fiber_cursor),
+        AntegenThreadError::InvalidFailureReport
+    );
+
+    // The reported fiber must be the one the thread is waiting to execute
+    let is_chained = thread.fiber_signal.is_chaining();
+    let (expected, time_since_ready) = match thread.fiber_signal {
+        Signal::Goto { index } => (index, thread.seconds_since_chain_step(clock.slot)),
+        Signal::Chain => (
+            thread.next_enabled_fiber(thread.fiber_cursor),
+            thread.seconds_since_chain_step(clock.slot),
+        ),
+        Signal::Close => return Err(AntegenThreadError::InvalidFailureReport.into()),
+        _ => (
+            thread.fiber_cursor,
+            thread.validate_trigger(&clock, ctx.remaining_accounts, &thread_pubkey)?,
+        ),
+    };
+    require!(
+        fiber_cursor.eq(&expected),
+        AntegenThreadError::InvalidFailureReport
+    );
+
+    // Nobody has executed the thread for a whole commission window, and
+    // another window has passed since the last report
+    let window_seconds = config
+        .grace_period_seconds
+        .saturating_add(config.fee_decay_seconds)
+        .max(0);
+    let window_slots = (window_seconds.saturating_mul(1000) / ESTIMATED_MS_PER_SLOT).max(1) as u64;
+    require!(
+        time_since_ready > window_seconds,
+        AntegenThreadError::InvalidFailureReport
+    );
+    require!(
+        thread.last_failure_slot == 0
+            || clock.slot >= thread.last_failure_slot.saturating_add(window_slots),
+        AntegenThreadError::InvalidFailureReport
+    );
+
+    let disabled = thread.record_fiber_failure(fiber_cursor, clock.slot, config.max_fiber_failures);
+    if !disabled {
+        msg!(
+            "Fiber {} failure {} of {}",
+            fiber_cursor,
+            thread.fiber_failures,
+            config.max_fiber_failures
+        );
+        return Ok(());
+    }
+
+    msg!(
+        "Fiber {} disabled after {} consecutive failures",
+        fiber_cursor,
+        config.max_fiber_failures
+    );
+    if is_chained {
+        thread.fiber_signal = Signal::None;
+    }
+    if is_chained || thread.fiber_cursor == fiber_cursor {
+        thread.fiber_cursor = thread.next_enabled_fiber(fiber_cursor);
+    }
+
+    emit!(FiberAutoDisabled {
+        thread: thread_pubkey,
+        fiber_index: fiber_cursor,
+        failures: config.max_fiber_failures,
+    });
+
+    Ok(())
+}
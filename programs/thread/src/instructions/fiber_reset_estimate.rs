@@ -0,0 +1,71 @@
+use crate::{errors::AntegenThreadError, *};
+use anchor_lang::prelude::*;
+use antegen_fiber_program::state::FiberVersionedState;
+
+/// Accounts required by the `reset_fiber_compute_estimate` instruction.
+/// Validates authority, CPIs to Fiber Program to clear the fiber's estimate.
+/// Thread PDA pays to grow a version 1 fiber to the current layout.
+#[derive(Accounts)]
+#[instruction(fiber_index: u8)]
+pub struct FiberResetEstimate<'info> {
+    /// The authority of the thread or the thread itself
+    #[account(
+        constraint = authority.key().eq(&thread.authority) || authority.key().eq(&thread.key())
+    )]
+    pub authority: Signer<'info>,
+
+    /// The thread the fiber belongs to
+    #[account(
+        mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
+        constraint = thread.fiber_ids.contains(&fiber_index) @ AntegenThreadError::InvalidFiberIndex,
+        seeds = [
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+        ],
+        bump = thread.bump,
+    )]
+    pub thread: Account<'info, Thread>,
+
+    /// CHECK: fiber account to reset (owned by Fiber Program), validated by seeds
+    #[account(
+        mut,
+        seeds = [SEED_THREAD_FIBER, thread.key().as_ref(), &[fiber_index]],
+        bump,
+        seeds::program = antegen_fiber_program::ID,
+    )]
+    pub fiber: UncheckedAccount<'info>,
+
+    /// The Fiber Program for CPI
+    pub fiber_program: Program<'info, antegen_fiber_program::program::AntegenFiber>,
+}
+
+pub fn fiber_reset_estimate(ctx: Context<FiberResetEstimate>, fiber_index: u8) -> Result<()> {
+    let thread = &ctx.accounts.thread;
+
+    let fiber_info = ctx.accounts.fiber.to_account_info();
+    let shortfall = FiberVersionedState::rent_shortfall(&fiber_info)?;
+    if shortfall > 0 {
+        **thread.to_account_info().try_borrow_mut_lamports()? -= shortfall;
+        **fiber_info.try_borrow_mut_lamports()? += shortfall;
+    }
+
+    thread.sign(|seeds| {
+        antegen_fiber_program::cpi::record_compute_units(
+            CpiContext::new_with_signer(
+                ctx.accounts.fiber_program.key(),
+                antegen_fiber_program::cpi::accounts::RecordComputeUnits {
+                    thread: thread.to_account_info(),
+                    fiber: ctx.accounts.fiber.to_account_info(),
+                },
+                &[seeds],
+            ),
+            None,
+        )
+    })?;
+
+    msg!("Fiber {} compute estimate reset", fiber_index);
+
+    Ok(())
+}
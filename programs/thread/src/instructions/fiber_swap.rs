@@ -1,9 +1,11 @@
 use crate::{errors::AntegenThreadError, *};
 use anchor_lang::prelude::*;
+use antegen_fiber_program::state::FiberVersionedState;
 
 /// Accounts required by the `swap_fiber` instruction.
 /// Copies source fiber's instruction into target, closes source.
 /// Validates authority, CPIs to Fiber Program to swap, updates thread fiber tracking.
+/// Thread PDA pays to grow a version 1 target to the current layout.
 #[derive(Accounts)]
 #[instruction(source_fiber_index: u8)]
 pub struct FiberSwap<'info> {
@@ -16,6 +18,7 @@ pub struct FiberSwap<'info> {
     /// The thread that owns both fibers
     #[account(
         mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         constraint = thread.fiber_ids.contains(&source_fiber_index) @ AntegenThreadError::InvalidFiberIndex,
         seeds = [
             SEED_THREAD,
@@ -43,6 +46,13 @@ pub struct FiberSwap<'info> {
 pub fn fiber_swap(ctx: Context<FiberSwap>, source_fiber_index: u8) -> Result<()> {
     let thread = &mut ctx.accounts.thread;
 
+    let target_info = ctx.accounts.target.to_account_info();
+    let shortfall = FiberVersionedState::rent_shortfall(&target_info)?;
+    if shortfall > 0 {
+        **thread.to_account_info().try_borrow_mut_lamports()? -= shortfall;
+        **target_info.try_borrow_mut_lamports()? += shortfall;
+    }
+
     // CPI to Fiber Program's swap_fiber
     thread.sign(|seeds| {
         antegen_fiber_program::cpi::swap(CpiContext::new_with_signer(
@@ -58,6 +68,7 @@ pub fn fiber_swap(ctx: Context<FiberSwap>, source_fiber_index: u8) -> Result<()>
 
     // Remove source fiber index from fiber_ids
     thread.fiber_ids.retain(|&x| x != source_fiber_index);
+    thread.set_fiber_disabled(source_fiber_index, false);
 
     // Adjust cursor if it was pointing to the removed source
     if thread.fiber_cursor == source_fiber_index {
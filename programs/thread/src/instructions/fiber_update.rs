@@ -1,6 +1,9 @@
 use crate::{errors::*, *};
 use anchor_lang::prelude::*;
-use antegen_fiber_program::{program::AntegenFiber, state::SerializableInstruction};
+use antegen_fiber_program::{
+    program::AntegenFiber,
+    state::{FiberVersionedState, SerializableInstruction},
+};
 
 /// Accounts required by the `fiber_update` instruction.
 /// Validates authority, CPIs to Fiber Program to update (or init) the fiber.
@@ -17,6 +20,7 @@ pub struct FiberUpdate<'info> {
     /// The thread the fiber belongs to
     #[account(
         mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         seeds = [SEED_THREAD, thread.authority.as_ref(), thread.id.as_slice()],
         bump = thread.bump,
     )]
@@ -40,14 +44,8 @@ pub fn fiber_update(
     track: bool,
     lookup_tables: Option<Vec<Pubkey>>,
 ) -> Result<()> {
-    // Prevent thread_delete instructions in fibers
     if let Some(ref ix) = instruction {
-        if ix.program_id.eq(&crate::ID)
-            && ix.data.len().ge(&8)
-            && ix.data[..8].eq(crate::instruction::DeleteThread::DISCRIMINATOR)
-        {
-            return Err(AntegenThreadError::InvalidInstruction.into());
-        }
+        FiberCreate::validate_instruction(ix)?;
     }
 
     let thread = &mut ctx.accounts.thread;
@@ -61,13 +59,21 @@ pub fn fiber_update(
         }
     }
 
-    // Pre-fund fiber account from thread PDA if not yet initialized
+    // Give a fiber disabled after repeated failures another chance
+    if thread.is_fiber_disabled(fiber_index) {
+        thread.set_fiber_disabled(fiber_index, false);
+        msg!("Fiber {} re-enabled", fiber_index);
+    }
+    if thread.failing_fiber == fiber_index {
+        thread.fiber_failures = 0;
+    }
+
+    // Pre-fund fiber account from thread PDA if new or still in the version 1 layout
     let fiber_info = ctx.accounts.fiber.to_account_info();
-    if fiber_info.data_len().eq(&0) {
-        let space = 8 + antegen_fiber_program::state::FiberVersionedState::INIT_SPACE;
-        let rent_lamports = Rent::get()?.minimum_balance(space);
-        **thread.to_account_info().try_borrow_mut_lamports()? -= rent_lamports;
-        **fiber_info.try_borrow_mut_lamports()? += rent_lamports;
+    let shortfall = FiberVersionedState::rent_shortfall(&fiber_info)?;
+    if shortfall > 0 {
+        **thread.to_account_info().try_borrow_mut_lamports()? -= shortfall;
+        **fiber_info.try_borrow_mut_lamports()? += shortfall;
     }
 
     // CPI to Fiber Program's update_fiber
@@ -1,7 +1,13 @@
+pub mod config_accept_admin;
+pub mod config_cancel_admin;
 pub mod config_init;
+pub mod config_migrate;
+pub mod config_propose_admin;
 pub mod config_update;
 pub mod fiber_close;
 pub mod fiber_create;
+pub mod fiber_report_failure;
+pub mod fiber_reset_estimate;
 pub mod fiber_swap;
 pub mod fiber_update;
 pub mod thread_close;
@@ -9,13 +15,20 @@ pub mod thread_create;
 pub mod thread_delete;
 pub mod thread_exec;
 pub mod thread_memo;
+pub mod thread_migrate;
 pub mod thread_update;
 pub mod thread_withdraw;
 
+pub use config_accept_admin::*;
+pub use config_cancel_admin::*;
 pub use config_init::*;
+pub use config_migrate::*;
+pub use config_propose_admin::*;
 pub use config_update::*;
 pub use fiber_close::*;
 pub use fiber_create::*;
+pub use fiber_report_failure::*;
+pub use fiber_reset_estimate::*;
 pub use fiber_swap::*;
 pub use fiber_update::*;
 pub use thread_close::*;
@@ -23,5 +36,6 @@ pub use thread_create::*;
 pub use thread_delete::*;
 pub use thread_exec::*;
 pub use thread_memo::*;
+pub use thread_migrate::*;
 pub use thread_update::*;
 pub use thread_withdraw::*;
@@ -1,6 +1,9 @@
 use crate::{
     errors::AntegenThreadError,
-    state::{compile_instruction, Schedule, SerializableInstruction, Signal, ThreadSeeds, Trigger},
+    state::{
+        compile_instruction, compiled_instruction_size, Schedule, SerializableInstruction, Signal,
+        ThreadSeeds, Trigger,
+    },
     utils::next_timestamp,
     *,
 };
@@ -10,6 +13,7 @@ use anchor_lang::{
     system_program::{create_nonce_account, transfer, CreateNonceAccount, Transfer},
     InstructionData, ToAccountMetas,
 };
+use antegen_fiber_program::state::FiberState;
 use solana_nonce::state::State;
 
 /// Accounts required by the `thread_create` instruction.
@@ -62,8 +66,8 @@ pub struct ThreadCreate<'info> {
     pub fiber_program: Option<Program<'info, antegen_fiber_program::program::AntegenFiber>>,
 }
 
-pub fn thread_create(
-    ctx: Context<ThreadCreate>,
+pub fn thread_create<'info>(
+    ctx: Context<'info, ThreadCreate<'info>>,
     amount: u64,
     id: ThreadId,
     trigger: Trigger,
@@ -71,6 +75,83 @@ pub fn thread_create(
     instruction: Option<SerializableInstruction>,
     priority_fee: Option<u64>,
     lookup_tables: Vec<Pubkey>,
+) -> Result<()> {
+    let fibers = match instruction {
+        Some(instruction) => {
+            let fiber = ctx
+                .accounts
+                .fiber
+                .as_ref()
+                .ok_or(AntegenThreadError::MissingFiberAccount)?
+                .to_account_info();
+            vec![(fiber, instruction)]
+        }
+        None => Vec::new(),
+    };
+    init_thread(
+        ctx,
+        amount,
+        id,
+        trigger,
+        paused,
+        fibers,
+        priority_fee.unwrap_or(0),
+        lookup_tables,
+    )
+}
+
+/// Create a thread with fibers 0..n, one per instruction, whose accounts are
+/// passed via remaining_accounts in order.
+pub fn thread_create_with_fibers<'info>(
+    ctx: Context<'info, ThreadCreate<'info>>,
+    amount: u64,
+    id: ThreadId,
+    trigger: Trigger,
+    paused: Option<bool>,
+    instructions: Vec<SerializableInstruction>,
+    priority_fee: Option<u64>,
+    lookup_tables: Vec<Pubkey>,
+) -> Result<()> {
+    if instructions.len() > MAX_INITIAL_FIBERS
+        || instructions
+            .iter()
+            .map(compiled_instruction_size)
+            .sum::<usize>()
+            > MAX_INITIAL_INSTRUCTIONS_SIZE
+    {
+        return Err(AntegenThreadError::InitialInstructionsTooLarge.into());
+    }
+    if ctx.remaining_accounts.len() != instructions.len() {
+        return Err(AntegenThreadError::MissingFiberAccount.into());
+    }
+
+    let fibers = ctx
+        .remaining_accounts
+        .iter()
+        .cloned()
+        .zip(instructions)
+        .collect();
+    init_thread(
+        ctx,
+        amount,
+        id,
+        trigger,
+        paused,
+        fibers,
+        priority_fee.unwrap_or(0),
+        lookup_tables,
+    )
+}
+
+fn init_thread<'info>(
+    ctx: Context<'info, ThreadCreate<'info>>,
+    amount: u64,
+    id: ThreadId,
+    trigger: Trigger,
+    paused: Option<bool>,
+    fibers: Vec<(AccountInfo<'info>, SerializableInstruction)>,
+    priority_fee: u64,
+    lookup_tables: Vec<Pubkey>,
 ) -> Result<()> {
     let authority: &Signer = &ctx.accounts.authority;
     let payer: &Signer = &ctx.accounts.payer;
@@ -155,6 +236,11 @@ pub fn thread_create(
             prev: clock.epoch,
             next: *epoch,
         },
+        Trigger::EpochInterval { every } => Schedule::Block {
+            prev: clock.epoch,
+            next: crate::utils::next_epoch(clock.epoch, *every)
+                .ok_or(AntegenThreadError::InvalidEpochInterval)?,
+        },
         Trigger::Interval {
             seconds, jitter, ..
         } => {
@@ -176,6 +262,16 @@ pub fn thread_create(
     thread.exec_count = 0;
     thread.last_executor = Pubkey::default();
     thread.fiber_signal = Signal::None;
+    thread.fee_payer_mode = None;
+    thread.consecutive_execs = 0;
+    thread.chain_slot = 0;
+    thread.chain_depth = 0;
+    thread.chain_visited = [0; 4];
+    thread.failing_fiber = 0;
+    thread.fiber_failures = 0;
+    thread.last_failure_slot = 0;
+    thread.disabled_fibers = [0; 4];
+    thread.resume_at = None;
 
     // Build and store pre-compiled thread_close instruction for self-closing
     let close_ix = Instruction {
@@ -206,64 +302,37 @@ pub fn thread_create(
         amount,
     )?;
 
-    // Optionally create fiber index 0 via CPI to fiber program
-    if let Some(instruction) = instruction {
-        // Prevent thread_delete instructions in fibers (same check as fiber_create)
-        if instruction.program_id.eq(&crate::ID)
-            && instruction.data.len().ge(&8)
-            && instruction.data[..8].eq(crate::instruction::DeleteThread::DISCRIMINATOR)
-        {
-            return Err(AntegenThreadError::InvalidInstruction.into());
-        }
-
-        // Require fiber and fiber_program accounts
-        let fiber = ctx
-            .accounts
-            .fiber
-            .as_ref()
-            .ok_or(AntegenThreadError::MissingFiberAccount)?;
-        let fiber_program = ctx
-            .accounts
-            .fiber_program
-            .as_ref()
-            .ok_or(AntegenThreadError::MissingFiberAccount)?;
-
-        let priority_fee = priority_fee.unwrap_or(0);
+    // Create fibers 0..n via CPI to fiber program
+    thread.fiber_next_id = 0;
+    thread.fiber_ids = Vec::new();
+    thread.fiber_cursor = 0;
+    if fibers.is_empty() {
+        // No default fiber — users add fibers separately via create_fiber
+        return Ok(());
+    }
 
-        // Conditional pre-funding: only pre-fund if fiber account is not yet initialized
-        if fiber.to_account_info().data_len() == 0 {
-            let space = 8 + antegen_fiber_program::state::FiberVersionedState::INIT_SPACE;
-            let rent_lamports = Rent::get()?.minimum_balance(space);
-            **thread.to_account_info().try_borrow_mut_lamports()? -= rent_lamports;
-            **fiber.to_account_info().try_borrow_mut_lamports()? += rent_lamports;
+    let fiber_program = ctx
+        .accounts
+        .fiber_program
+        .as_ref()
+        .ok_or(AntegenThreadError::MissingFiberAccount)?
+        .to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    for (fiber_index, (fiber, instruction)) in fibers.into_iter().enumerate() {
+        let fiber_index = fiber_index as u8;
+        if fiber.key() != FiberState::pubkey(thread_pubkey, fiber_index) {
+            return Err(AntegenThreadError::InvalidFiberAccount.into());
         }
-
-        thread.sign(|seeds| {
-            antegen_fiber_program::cpi::create(
-                CpiContext::new_with_signer(
-                    fiber_program.key(),
-                    antegen_fiber_program::cpi::accounts::Create {
-                        thread: thread.to_account_info(),
-                        fiber: fiber.to_account_info(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                    &[seeds],
-                ),
-                0, // fiber_index = 0
-                instruction.clone(),
-                priority_fee,
-                lookup_tables.clone(),
-            )
-        })?;
-
-        thread.fiber_next_id = 1;
-        thread.fiber_ids = vec![0];
-        thread.fiber_cursor = 0;
-    } else {
-        // No default fiber — users add fibers separately via create_fiber
-        thread.fiber_next_id = 0;
-        thread.fiber_ids = Vec::new();
-        thread.fiber_cursor = 0;
+        create_thread_fiber(
+            thread,
+            &fiber,
+            &fiber_program,
+            &system_program,
+            fiber_index,
+            instruction,
+            priority_fee,
+            lookup_tables.clone(),
+        )?;
     }
 
     Ok(())
@@ -1,17 +1,27 @@
-use crate::{errors::AntegenThreadError, state::ThreadConfig, *};
+use crate::{errors::AntegenThreadError, events::ThreadDeleted, state::ThreadConfig, *};
 use anchor_lang::prelude::*;
+use antegen_fiber_program::{cpi::close, state::FiberState};
 
 /// Force delete a thread - admin only, skips all checks.
 /// Used for cleaning up stuck/broken threads that may not deserialize.
+///
+/// The thread's lamports go back to its authority, who paid its rent. Fiber
+/// accounts may be passed via remaining_accounts to reclaim their rent too.
+/// Either none or all of the thread's fibers must be passed, and each must be
+/// a fiber PDA of this thread.
 #[derive(Accounts)]
 pub struct ThreadDelete<'info> {
     /// The config admin (must sign)
     #[account(
-        mut,
         constraint = admin.key() == config.admin @ AntegenThreadError::InvalidConfigAdmin,
     )]
     pub admin: Signer<'info>,
 
+    /// Receives the thread's lamports and any reclaimed fiber rent; must be
+    /// the thread authority, checked in the handler
+    #[account(mut)]
+    pub rent_recipient: SystemAccount<'info>,
+
     /// The config account
     #[account(
         seeds = [SEED_CONFIG],
@@ -22,20 +32,91 @@ pub struct ThreadDelete<'info> {
     /// CHECK: The thread to delete - unchecked so we can close broken/undeserializable accounts
     #[account(mut)]
     pub thread: UncheckedAccount<'info>,
+
+    /// The Fiber Program (required when closing fibers via remaining_accounts)
+    pub fiber_program: Option<Program<'info, antegen_fiber_program::program::AntegenFiber>>,
 }
 
-pub fn thread_delete(ctx: Context<ThreadDelete>) -> Result<()> {
-    let admin = &ctx.accounts.admin;
-    let thread = &ctx.accounts.thread;
+pub fn thread_delete<'info>(ctx: Context<'info, ThreadDelete<'info>>) -> Result<()> {
+    let rent_recipient = &ctx.accounts.rent_recipient;
+    let thread_info = ctx.accounts.thread.to_account_info();
+    let thread_key = thread_info.key();
+
+    // Read from the raw data, so threads that no longer deserialize qualify
+    let authority = Thread::authority_from_data(&thread_info.try_borrow_data()?)
+        .ok_or(AntegenThreadError::InvalidThreadState)?;
+    require_keys_eq!(
+        rent_recipient.key(),
+        authority,
+        AntegenThreadError::InvalidAuthority
+    );
+
+    let mut fiber_lamports: u64 = 0;
+    let fibers_closed = ctx.remaining_accounts.len();
+
+    if !ctx.remaining_accounts.is_empty() {
+        // Closing fibers needs the thread to sign, so it must deserialize
+        let thread = Thread::try_deserialize(&mut &thread_info.data.borrow()[..])?;
+        let fiber_program = ctx
+            .accounts
+            .fiber_program
+            .as_ref()
+            .ok_or(AntegenThreadError::MissingFiberAccounts)?;
 
-    // Transfer all lamports from thread to admin
-    let thread_lamports = thread.lamports();
-    **thread.try_borrow_mut_lamports()? -= thread_lamports;
-    **admin.try_borrow_mut_lamports()? += thread_lamports;
+        let mut remaining_ids = thread.fiber_ids.clone();
+        for account in ctx.remaining_accounts.iter() {
+            // Only fiber PDAs derived from this thread are accepted
+            let account_key = account.key();
+            let pos = remaining_ids
+                .iter()
+                .position(|&idx| FiberState::pubkey(thread_key, idx) == account_key)
+                .ok_or(AntegenThreadError::InvalidFiberAccount)?;
+            remaining_ids.remove(pos);
+
+            fiber_lamports = fiber_lamports
+                .checked_add(account.lamports())
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // Fiber rent returns to the thread PDA, swept below
+            thread.sign(|seeds| {
+                close(CpiContext::new_with_signer(
+                    fiber_program.key(),
+                    antegen_fiber_program::cpi::accounts::Close {
+                        thread: thread_info.clone(),
+                        fiber: account.to_account_info(),
+                    },
+                    &[seeds],
+                ))
+            })?;
+        }
+
+        // All or nothing: a partial set would strand the rest without a signer
+        require!(
+            remaining_ids.is_empty(),
+            AntegenThreadError::MissingFiberAccounts
+        );
+    }
+
+    // Transfer all lamports from thread to the rent recipient
+    let reclaimed = thread_info.lamports();
+    **thread_info.try_borrow_mut_lamports()? -= reclaimed;
+    **rent_recipient.try_borrow_mut_lamports()? += reclaimed;
 
     // Zero out account data to mark as closed
-    thread.try_borrow_mut_data()?.fill(0);
+    thread_info.try_borrow_mut_data()?.fill(0);
+
+    msg!(
+        "Deleting thread (admin), {} lamports to {}",
+        reclaimed,
+        rent_recipient.key()
+    );
+    emit!(ThreadDeleted {
+        thread: thread_key,
+        rent_recipient: rent_recipient.key(),
+        fibers_closed: fibers_closed as u8,
+        fiber_lamports,
+        reclaimed,
+    });
 
-    msg!("Deleting thread (admin)");
     Ok(())
 }
@@ -1,13 +1,17 @@
 use crate::{
     errors::*,
-    state::{decompile_instruction, CompiledInstructionV0, Signal},
+    events::ThreadExecuted,
+    state::{decompile_instruction, CompiledInstructionV0, FeePayerMode, Signal},
     *,
 };
 use anchor_lang::{
     prelude::*,
-    solana_program::program::{get_return_data, invoke_signed},
+    solana_program::{
+        compute_units::sol_remaining_compute_units,
+        program::{get_return_data, invoke_signed},
+    },
 };
-use antegen_fiber_program::state::{Fiber, FiberInstructionProcessor};
+use antegen_fiber_program::state::{Fiber, FiberInstructionProcessor, FiberVersionedState};
 
 /// Accounts required by the `thread_exec` instruction.
 #[derive(Accounts)]
@@ -22,13 +26,13 @@ pub struct ThreadExec<'info> {
     #[account(
         mut,
         dup,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         seeds = [
             SEED_THREAD,
             thread.authority.as_ref(),
             thread.id.as_slice(),
         ],
         bump = thread.bump,
-        constraint = !thread.paused @ AntegenThreadError::ThreadPaused,
         constraint = !thread.fiber_ids.is_empty() @ AntegenThreadError::InvalidThreadState,
     )]
     pub thread: Box<Account<'info, Thread>>,
@@ -69,6 +73,8 @@ pub fn thread_exec<'info>(
     forgo_commission: bool,
     fiber_cursor: u8,
 ) -> Result<()> {
+    let compute_start = sol_remaining_compute_units();
+
     // ── Setup ──
     // Collect all named AccountInfos before taking mutable field borrows.
     // Avoids Anchor's lifetime-invariance conflict when building CPI account lists.
@@ -83,11 +89,22 @@ pub fn thread_exec<'info>(
     let executor_lamports_start: u64 = executor.lamports();
     let thread_pubkey = thread.key();
 
+    require!(
+        !thread.is_paused_at(clock.unix_timestamp),
+        AntegenThreadError::ThreadPaused
+    );
     require!(
         !ctx.accounts.config.paused,
         AntegenThreadError::GlobalPauseActive
     );
 
+    // A pause whose resume_at has passed ends with this exec
+    if thread.paused {
+        msg!("Resuming thread paused until {:?}", thread.resume_at);
+        thread.paused = false;
+        thread.resume_at = None;
+    }
+
     // ── Close path (early return) ──
     if thread.fiber_signal == Signal::Close {
         let compiled = CompiledInstructionV0::try_from_slice(&thread.close_fiber)?;
@@ -113,15 +130,27 @@ pub fn thread_exec<'info>(
     }
 
     // ── Chaining detection ──
-    let is_chained = thread.fiber_signal.eq(&Signal::Chain);
+    let is_chained = thread.fiber_signal.is_chaining();
+
+    // A Goto chain must execute exactly the fiber it targeted
+    if let Signal::Goto { index } = thread.fiber_signal {
+        require!(fiber_cursor.eq(&index), AntegenThreadError::WrongFiberIndex);
+    }
 
     // Sync fiber_cursor so advance_to_next_fiber works correctly
     if is_chained {
         thread.fiber_cursor = fiber_cursor;
     }
 
+    // Bound how far a chain can run within one slot
+    thread.record_chain_step(clock.slot, fiber_cursor, is_chained, config.max_chain_depth)?;
+
     // ── Pre-execution checks ──
     thread.validate_for_execution()?;
+    require!(
+        !thread.is_fiber_disabled(fiber_cursor),
+        AntegenThreadError::FiberDisabled
+    );
 
     thread.advance_nonce_if_required(
         &thread.to_account_info(),
@@ -200,27 +229,79 @@ pub fn thread_exec<'info>(
         }
     };
 
-    // Downgrade Chain → None if cursor is on last fiber (nowhere to chain to)
-    let last_fiber = thread.fiber_ids.last().copied().unwrap_or(fiber_cursor);
-    let signal = if signal.eq(&Signal::Chain) && last_fiber.eq(&fiber_cursor) {
+    // Downgrade Chain → None if no enabled fiber follows the cursor (nowhere to chain to)
+    let last_fiber = thread
+        .fiber_ids
+        .iter()
+        .rev()
+        .copied()
+        .find(|&index| !thread.is_fiber_disabled(index))
+        .unwrap_or(fiber_cursor);
+    let signal = if signal.eq(&Signal::Chain) && last_fiber.le(&fiber_cursor) {
         Signal::None
     } else {
         signal
     };
 
+    // Goto must jump forward to an existing fiber (no cycles within one exec)
+    // A Goto to a disabled fiber ends the chain at the next enabled one instead
+    let signal = match signal {
+        Signal::Goto { index } => {
+            thread.validate_goto(fiber_cursor, index)?;
+            if thread.is_fiber_disabled(index) {
+                msg!("Goto target fiber {} is disabled, ending chain", index);
+                Signal::Next {
+                    index: thread.next_enabled_fiber(index),
+                }
+            } else {
+                signal
+            }
+        }
+        _ => signal,
+    };
+
     // ── Payments (when chain ends) ──
-    if signal.ne(&Signal::Chain) {
+    if !signal.is_chaining() {
+        // An exec past the commission window shows the thread went unexecuted
+        // for the whole window, which ends the streak; reports cannot
+        if time_since_ready > config.grace_period_seconds + config.fee_decay_seconds {
+            thread.consecutive_execs = 0;
+        }
+
         let balance_change = executor.lamports() as i64 - executor_lamports_start as i64;
-        let payments =
-            config.calculate_payments(time_since_ready, balance_change, forgo_commission);
+        let fee_payer_mode = thread.effective_fee_payer_mode(config);
+        let payments = config.calculate_payments(
+            time_since_ready,
+            balance_change,
+            forgo_commission,
+            fee_payer_mode,
+            thread.consecutive_execs,
+        );
+
+        // A reimbursing thread must stay rent-exempt after paying out
+        if fee_payer_mode == FeePayerMode::ThreadReimburses {
+            let thread_info = thread.to_account_info();
+            let total_payout = payments.fee_payer_reimbursement
+                + payments.executor_commission
+                + payments.core_team_fee;
+            let rent_minimum = Rent::get()?.minimum_balance(thread_info.data_len());
+            require!(
+                thread_info
+                    .lamports()
+                    .checked_sub(total_payout)
+                    .is_some_and(|remaining| remaining >= rent_minimum),
+                AntegenThreadError::ThreadCannotReimburse
+            );
+        }
 
         if forgo_commission && payments.executor_commission.eq(&0) {
             let effective_commission = config.calculate_effective_commission(time_since_ready);
             let forgone = config.calculate_executor_fee(effective_commission);
             msg!(
-                "Executed {}s after trigger, forgoing {} commission",
+                "Executed {}s after trigger, forgoing {} commission (core team fee: {})",
                 time_since_ready,
-                forgone
+                forgone,
+                payments.core_team_fee
             );
         } else {
             msg!("Executed {}s after trigger", time_since_ready);
@@ -232,17 +313,20 @@ pub fn thread_exec<'info>(
             &ctx.accounts.admin.to_account_info(),
             &payments,
         )?;
+
+        // The discount above used the streak so far; this exec extends it
+        thread.consecutive_execs = thread.consecutive_execs.saturating_add(1);
     }
 
     // ── Apply signal to thread state ──
     // Capture original trigger before signal processing may change it
     let fired_trigger = thread.trigger.clone();
 
-    // Only persist Chain/Close — the executor needs these between transactions.
+    // Only persist Chain/Goto/Close — the executor needs these between transactions.
     // All other signals are consumed inline and fiber_signal resets to None.
     thread.fiber_signal = Signal::None;
     match &signal {
-        Signal::Chain | Signal::Close => {
+        Signal::Chain | Signal::Goto { .. } | Signal::Close => {
             thread.fiber_signal = signal.clone();
         }
         Signal::Next { index } => {
@@ -277,15 +361,20 @@ pub fn thread_exec<'info>(
         }
     }
 
+    // Next and Update may name a disabled fiber; move on to an enabled one
+    if !signal.is_chaining() && thread.is_fiber_disabled(thread.fiber_cursor) {
+        thread.advance_to_next_fiber();
+    }
+
     // Immediate triggers: auto-close after fiber completes (unless chaining)
-    if matches!(fired_trigger, Trigger::Immediate { .. }) && signal != Signal::Chain {
+    if matches!(fired_trigger, Trigger::Immediate { .. }) && !signal.is_chaining() {
         thread.fiber_signal = Signal::Close;
     }
 
     // Timestamp triggers: auto-pause after firing (unless chaining or signal
     // explicitly set paused to false — e.g. rental_close activating a queued
     // rental needs the thread to stay alive).
-    if matches!(fired_trigger, Trigger::Timestamp { .. }) && signal != Signal::Chain {
+    if matches!(fired_trigger, Trigger::Timestamp { .. }) && !signal.is_chaining() {
         let signal_unpaused = matches!(
             &signal,
             Signal::Update {
@@ -299,13 +388,74 @@ pub fn thread_exec<'info>(
     }
 
     // ── Finalize ──
-    if signal != Signal::Chain {
+    if !signal.is_chaining() {
         thread.update_schedule(&clock, ctx.remaining_accounts, &thread_pubkey)?;
     }
 
     // Fiber stats not updated — fiber is owned by Fiber Program
     thread.exec_count += 1;
     thread.last_executor = executor.key();
+    emit!(ThreadExecuted {
+        thread: thread_pubkey,
+        executor: executor.key(),
+        fiber_index: fiber_cursor,
+        exec_sequence: thread.exec_count,
+        slot: clock.slot,
+    });
+
+    // A successful exec ends any run of reported fiber failures
+    thread.fiber_failures = 0;
+
+    // ── Record compute estimate ──
+    // One-time write, only when the executor opted in by passing the fiber
+    // writable and the Fiber Program in remaining_accounts
+    let fiber_program_present = ctx
+        .remaining_accounts
+        .iter()
+        .any(|ai| ai.key.eq(&antegen_fiber_program::ID));
+    if fiber.is_writable
+        && fiber_program_present
+        && !fiber_read.is_legacy()
+        && fiber_read.estimated_compute_units().is_none()
+    {
+        // The fiber may have updated or closed itself during the CPI
+        let unchanged = Fiber::try_deserialize(&mut &fiber.data.borrow()[..]).is_ok_and(|f| {
+            f.estimated_compute_units().is_none()
+                && f.compiled_instruction() == fiber_read.compiled_instruction()
+        });
+        // A version 1 fiber grows to hold the estimate, funded by the thread;
+        // skip recording rather than leave the thread short of rent
+        let fiber_info = fiber.to_account_info();
+        let thread_info = thread.to_account_info();
+        let shortfall = FiberVersionedState::rent_shortfall(&fiber_info)?;
+        let thread_minimum = Rent::get()?.minimum_balance(thread_info.data_len());
+        let affordable = thread_info
+            .lamports()
+            .checked_sub(shortfall)
+            .is_some_and(|remaining| remaining >= thread_minimum);
+        if unchanged && affordable {
+            if shortfall > 0 {
+                **thread_info.try_borrow_mut_lamports()? -= shortfall;
+                **fiber_info.try_borrow_mut_lamports()? += shortfall;
+            }
+            let units = compute_start.saturating_sub(sol_remaining_compute_units());
+            let units = u32::try_from(units).unwrap_or(u32::MAX);
+            msg!("Recording compute estimate: {} units", units);
+            thread.sign(|seeds| {
+                antegen_fiber_program::cpi::record_compute_units(
+                    CpiContext::new_with_signer(
+                        antegen_fiber_program::ID,
+                        antegen_fiber_program::cpi::accounts::RecordComputeUnits {
+                            thread: thread.to_account_info(),
+                            fiber: fiber.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    Some(units),
+                )
+            })?;
+        }
+    }
 
     Ok(())
 }
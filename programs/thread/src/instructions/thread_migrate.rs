@@ -0,0 +1,95 @@
+use crate::{errors::*, state::*, *};
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+/// Accounts required by the `thread_migrate` instruction.
+#[derive(Accounts)]
+pub struct ThreadMigrate<'info> {
+    /// The authority (owner) of the thread. Pays rent for any added space.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The thread to migrate, grown to the current layout size. Read by hand,
+    /// since an older layout does not deserialize as the current `Thread`.
+    /// CHECK: discriminator, layout, authority and address are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub thread: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn thread_migrate(ctx: Context<ThreadMigrate>) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let thread_info = ctx.accounts.thread.to_account_info();
+
+    // The version follows the discriminator; older versions have a shorter layout
+    let (previous_version, mut thread) = {
+        let data = thread_info.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data.starts_with(Thread::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let version = data[8];
+        let thread = if version < CURRENT_THREAD_VERSION {
+            ThreadV1::deserialize(&mut &data[8..])
+                .map_err(|_| ErrorCode::AccountDidNotDeserialize)?
+                .into_current()
+        } else {
+            Thread::try_deserialize(&mut &data[..])?
+        };
+        (version, thread)
+    };
+
+    require_keys_eq!(
+        authority.key(),
+        thread.authority,
+        AntegenThreadError::InvalidAuthority
+    );
+    let address = Pubkey::create_program_address(
+        &[
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+            &[thread.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| ErrorCode::ConstraintSeeds)?;
+    require_keys_eq!(address, thread_info.key(), ErrorCode::ConstraintSeeds);
+
+    // Grow the account to the current size, topping up rent from the authority
+    let space = 8 + Thread::INIT_SPACE;
+    let minimum_balance = Rent::get()?.minimum_balance(space);
+    let shortfall = minimum_balance.saturating_sub(thread_info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                anchor_lang::system_program::ID,
+                Transfer {
+                    from: authority.to_account_info(),
+                    to: thread_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    if thread_info.data_len() < space {
+        thread_info.resize(space)?;
+    }
+
+    thread.version = CURRENT_THREAD_VERSION;
+    thread.try_serialize(&mut &mut thread_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Migrated thread {} from version {} to {}",
+        thread_info.key(),
+        previous_version,
+        CURRENT_THREAD_VERSION
+    );
+
+    Ok(())
+}
@@ -1,13 +1,18 @@
-use crate::{state::*, utils::next_timestamp, *};
+use crate::{errors::*, state::*, utils::next_timestamp, *};
 use anchor_lang::prelude::*;
+use antegen_fiber_program::state::FiberState;
 
 /// Parameters for updating a thread
 #[derive(AnchorSerialize, AnchorDeserialize, Default)]
 pub struct ThreadUpdateParams {
     /// Explicitly set the paused state (not a toggle)
     pub paused: Option<bool>,
+    /// Resume the paused thread on its own at this unix timestamp
+    pub resume_at: Option<i64>,
     /// Update the thread's trigger
     pub trigger: Option<Trigger>,
+    /// Set (`Some(Some(mode))`) or clear (`Some(None)`) the fee payer mode override
+    pub fee_payer_mode: Option<Option<FeePayerMode>>,
 }
 
 /// Accounts required by the `thread_update` instruction.
@@ -20,6 +25,7 @@ pub struct ThreadUpdate<'info> {
     /// The thread to be updated.
     #[account(
         mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
         constraint = authority.key().eq(&thread.authority),
         seeds = [
             SEED_THREAD,
@@ -31,12 +37,91 @@ pub struct ThreadUpdate<'info> {
     pub thread: Account<'info, Thread>,
 }
 
+/// Accounts required by the `update_thread_atomic` instruction.
+/// The new fibers' accounts are passed via remaining_accounts, in order.
+#[derive(Accounts)]
+pub struct ThreadUpdateAtomic<'info> {
+    /// The authority (owner) of the thread.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The thread to be updated.
+    #[account(
+        mut,
+        constraint = thread.version == CURRENT_THREAD_VERSION @ AntegenThreadError::ThreadNotMigrated,
+        constraint = authority.key().eq(&thread.authority),
+        seeds = [
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+        ],
+        bump = thread.bump,
+    )]
+    pub thread: Account<'info, Thread>,
+
+    /// The Fiber Program for CPI
+    pub fiber_program: Program<'info, antegen_fiber_program::program::AntegenFiber>,
+
+    #[account(address = anchor_lang::system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
 pub fn thread_update(ctx: Context<ThreadUpdate>, params: ThreadUpdateParams) -> Result<()> {
+    apply_thread_update(&mut ctx.accounts.thread, params)
+}
+
+/// Apply `params` and append `fibers` after the thread's existing fibers,
+/// all in one instruction so no execution sees a partial update.
+pub fn thread_update_atomic<'info>(
+    ctx: Context<'info, ThreadUpdateAtomic<'info>>,
+    params: ThreadUpdateParams,
+    fibers: Vec<FiberCreateParams>,
+) -> Result<()> {
+    if ctx.remaining_accounts.len() != fibers.len() {
+        return Err(AntegenThreadError::MissingFiberAccount.into());
+    }
+    if ctx.accounts.thread.fiber_ids.len() + fibers.len() > MAX_FIBERS {
+        return Err(AntegenThreadError::TooManyFibers.into());
+    }
+
     let thread = &mut ctx.accounts.thread;
+    let thread_pubkey = thread.key();
+    let fiber_program = ctx.accounts.fiber_program.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    for (fiber, params) in ctx.remaining_accounts.iter().zip(fibers) {
+        let fiber_index = thread.fiber_next_id;
+        if fiber_index == u8::MAX {
+            return Err(AntegenThreadError::TooManyFibers.into());
+        }
+        if fiber.key() != FiberState::pubkey(thread_pubkey, fiber_index) {
+            return Err(AntegenThreadError::InvalidFiberAccount.into());
+        }
+        create_thread_fiber(
+            thread,
+            fiber,
+            &fiber_program,
+            &system_program,
+            fiber_index,
+            params.instruction,
+            params.priority_fee,
+            params.lookup_tables,
+        )?;
+    }
 
+    apply_thread_update(thread, params)
+}
+
+fn apply_thread_update(thread: &mut Account<Thread>, params: ThreadUpdateParams) -> Result<()> {
     // Update paused state if provided (explicit, not toggle)
     if let Some(paused) = params.paused {
         thread.paused = paused;
+        thread.resume_at = None;
+    }
+
+    // A resume time only applies to a paused thread
+    if let Some(resume_at) = params.resume_at {
+        require!(thread.paused, AntegenThreadError::InvalidThreadState);
+        thread.resume_at = Some(resume_at);
     }
 
     // Update the trigger if provided
@@ -77,6 +162,11 @@ pub fn thread_update(ctx: Context<ThreadUpdate>, params: ThreadUpdateParams) ->
                 prev: clock.epoch,
                 next: *epoch,
             },
+            Trigger::EpochInterval { every } => Schedule::Block {
+                prev: clock.epoch,
+                next: crate::utils::next_epoch(clock.epoch, *every)
+                    .ok_or(AntegenThreadError::InvalidEpochInterval)?,
+            },
             Trigger::Interval {
                 seconds, jitter, ..
             } => {
@@ -98,10 +188,16 @@ pub fn thread_update(ctx: Context<ThreadUpdate>, params: ThreadUpdateParams) ->
         };
     }
 
+    // Update the fee payer mode override if provided
+    if let Some(fee_payer_mode) = params.fee_payer_mode {
+        thread.fee_payer_mode = fee_payer_mode;
+    }
+
     // If trigger was updated but paused was NOT explicitly set, auto-unpause.
     // Changing the trigger implies the user wants the thread running.
     if params.trigger.is_some() && params.paused.is_none() {
         thread.paused = false;
+        thread.resume_at = None;
     }
 
     Ok(())
@@ -1,6 +1,8 @@
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod pda;
 pub mod state;
 pub mod utils;
 
@@ -30,6 +32,8 @@ declare_id!("AgTv5w1UvUb6zeqkThwMrztGu9hpepBu8YLghuR4dpSx");
 pub enum ThreadId {
     Bytes(Vec<u8>),
     Pubkey(Pubkey),
+    /// A 32-byte hash, e.g. of a name too long to be a seed
+    Hash([u8; 32]),
 }
 
 impl AsRef<[u8]> for ThreadId {
@@ -37,6 +41,7 @@ impl AsRef<[u8]> for ThreadId {
         match self {
             ThreadId::Bytes(bytes) => bytes.as_ref(),
             ThreadId::Pubkey(pubkey) => pubkey.as_ref(),
+            ThreadId::Hash(hash) => hash.as_ref(),
         }
     }
 }
@@ -45,14 +50,14 @@ impl ThreadId {
     pub fn len(&self) -> usize {
         match self {
             ThreadId::Bytes(bytes) => bytes.len(),
-            ThreadId::Pubkey(_) => 32,
+            ThreadId::Pubkey(_) | ThreadId::Hash(_) => 32,
         }
     }
 
     pub fn is_empty(&self) -> bool {
         match self {
             ThreadId::Bytes(bytes) => bytes.is_empty(),
-            ThreadId::Pubkey(_) => false,
+            ThreadId::Pubkey(_) | ThreadId::Hash(_) => false,
         }
     }
 
@@ -60,6 +65,7 @@ impl ThreadId {
         match self {
             ThreadId::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
             ThreadId::Pubkey(pubkey) => pubkey.to_string(),
+            ThreadId::Hash(hash) => hash.iter().map(|b| format!("{:02x}", b)).collect(),
         }
     }
 }
@@ -87,6 +93,7 @@ impl From<ThreadId> for Vec<u8> {
         match id {
             ThreadId::Bytes(bytes) => bytes,
             ThreadId::Pubkey(pubkey) => pubkey.to_bytes().to_vec(),
+            ThreadId::Hash(hash) => hash.to_vec(),
         }
     }
 }
@@ -105,6 +112,28 @@ pub mod antegen_thread {
         config_update(ctx, params)
     }
 
+    /// Migrates the global thread configuration to the current layout and version.
+    /// Grows the account to the current size; the admin pays any added rent.
+    pub fn migrate_config(ctx: Context<ConfigMigrate>) -> Result<()> {
+        config_migrate(ctx)
+    }
+
+    /// Proposes a new admin for the global thread configuration.
+    /// Takes effect only once the proposed admin calls `accept_admin`.
+    pub fn propose_admin(ctx: Context<ConfigProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        config_propose_admin(ctx, new_admin)
+    }
+
+    /// Accepts a pending admin proposal; must be signed by the proposed admin.
+    pub fn accept_admin(ctx: Context<ConfigAcceptAdmin>) -> Result<()> {
+        config_accept_admin(ctx)
+    }
+
+    /// Cancels a pending admin proposal - current admin only.
+    pub fn cancel_admin_proposal(ctx: Context<ConfigCancelAdmin>) -> Result<()> {
+        config_cancel_admin(ctx)
+    }
+
     /// Creates a fiber (instruction) for a thread via CPI to Fiber Program.
     /// `lookup_tables` is capped at 4 per fiber (Solana v0 transaction limit).
     pub fn create_fiber(
@@ -128,6 +157,7 @@ pub mod antegen_thread {
     /// Pass `None` for `instruction` to wipe the compiled instruction (idle).
     /// Pass `None` for `lookup_tables` to leave them unchanged; `Some(vec)`
     /// atomically replaces. Legacy fibers reject non-empty lookup_tables.
+    /// Re-enables the fiber if it was disabled after repeated failures.
     pub fn update_fiber(
         ctx: Context<FiberUpdate>,
         fiber_index: u8,
@@ -146,6 +176,22 @@ pub mod antegen_thread {
         )
     }
 
+    /// Clears a fiber's recorded compute estimate so the next exec records a
+    /// fresh one. Call after changing the fiber's instruction.
+    pub fn reset_fiber_compute_estimate(
+        ctx: Context<FiberResetEstimate>,
+        fiber_index: u8,
+    ) -> Result<()> {
+        fiber_reset_estimate(ctx, fiber_index)
+    }
+
+    /// Records a failed execution of the fiber a thread has been stuck on for
+    /// a whole commission window. Disables the fiber once the config's
+    /// `max_fiber_failures` is reached.
+    pub fn report_fiber_failure(ctx: Context<FiberReportFailure>, fiber_cursor: u8) -> Result<()> {
+        fiber_report_failure(ctx, fiber_cursor)
+    }
+
     /// Swaps source fiber's instruction into target fiber, closes source.
     /// Target keeps its PDA/index, source is deleted.
     pub fn swap_fiber(ctx: Context<FiberSwap>, source_fiber_index: u8) -> Result<()> {
@@ -156,8 +202,8 @@ pub mod antegen_thread {
     /// Optionally creates fiber index 0 if `instruction` is provided.
     /// `lookup_tables` is forwarded to fiber_0 when one is created;
     /// it is ignored when `instruction` is `None`.
-    pub fn create_thread(
-        ctx: Context<ThreadCreate>,
+    pub fn create_thread<'info>(
+        ctx: Context<'info, ThreadCreate<'info>>,
         amount: u64,
         id: ThreadId,
         trigger: Trigger,
@@ -178,6 +224,32 @@ pub mod antegen_thread {
         )
     }
 
+    /// Creates a new transaction thread with fibers 0..n, one per instruction.
+    /// Fiber accounts are passed as remaining accounts in index order.
+    /// At most `MAX_INITIAL_FIBERS` instructions totalling
+    /// `MAX_INITIAL_INSTRUCTIONS_SIZE` compiled bytes.
+    pub fn create_thread_with_fibers<'info>(
+        ctx: Context<'info, ThreadCreate<'info>>,
+        amount: u64,
+        id: ThreadId,
+        trigger: Trigger,
+        paused: Option<bool>,
+        instructions: Vec<SerializableInstruction>,
+        priority_fee: Option<u64>,
+        lookup_tables: Vec<Pubkey>,
+    ) -> Result<()> {
+        thread_create_with_fibers(
+            ctx,
+            amount,
+            id,
+            trigger,
+            paused,
+            instructions,
+            priority_fee,
+            lookup_tables,
+        )
+    }
+
     /// Closes an existing thread account and returns the lamports to the owner.
     /// Requires authority (owner) or thread itself to sign.
     /// External fiber accounts should be passed via remaining_accounts.
@@ -200,11 +272,29 @@ pub mod antegen_thread {
         thread_update(ctx, params)
     }
 
+    /// Updates the thread and appends fibers in one instruction, so the
+    /// thread never runs a new trigger against an incomplete fiber set.
+    /// New fibers take the next free indices; their accounts are passed via
+    /// remaining_accounts, in order.
+    pub fn update_thread_atomic<'info>(
+        ctx: Context<'info, ThreadUpdateAtomic<'info>>,
+        params: ThreadUpdateParams,
+        fibers: Vec<FiberCreateParams>,
+    ) -> Result<()> {
+        thread_update_atomic(ctx, params, fibers)
+    }
+
     /// Allows an owner to withdraw from a thread's lamport balance.
     pub fn withdraw_thread(ctx: Context<ThreadWithdraw>, amount: u64) -> Result<()> {
         thread_withdraw(ctx, amount)
     }
 
+    /// Migrates a thread account to the current layout and version.
+    /// Grows the account to the current size; the authority pays any added rent.
+    pub fn migrate_thread(ctx: Context<ThreadMigrate>) -> Result<()> {
+        thread_migrate(ctx)
+    }
+
     /// Memo instruction that logs a message (replacement for spl-memo).
     /// Used for tracking thread fiber execution in logs without external dependencies.
     /// Optionally emits a signal for testing signal behaviors.
@@ -218,7 +308,10 @@ pub mod antegen_thread {
 
     /// Deletes a thread - admin only, skips all checks.
     /// Used for cleaning up stuck/broken threads.
-    pub fn delete_thread(ctx: Context<ThreadDelete>) -> Result<()> {
+    /// Lamports go to `rent_recipient`, which must be the thread authority;
+    /// fibers passed via remaining_accounts
+    /// (all of them, or none) are closed and their rent included.
+    pub fn delete_thread<'info>(ctx: Context<'info, ThreadDelete<'info>>) -> Result<()> {
         thread_delete(ctx)
     }
 }
@@ -0,0 +1,23 @@
+//! Canonical PDA derivation for thread and fiber accounts.
+//!
+//! Pure functions, usable off-chain without RPC, so integrators never have to
+//! hand-roll the seeds.
+
+use crate::{constants::SEED_THREAD, ThreadId};
+use anchor_lang::prelude::Pubkey;
+use antegen_fiber_program::state::FiberState;
+
+/// Derive the address and bump of the thread `authority` creates with `id`.
+///
+/// Panics if a `Bytes` id is longer than 32 bytes, which `create_thread` rejects.
+pub fn thread_pubkey(authority: &Pubkey, id: &ThreadId) -> (Pubkey, u8) {
+    assert!(id.len() <= 32, "Thread ID must not exceed 32 bytes");
+    Pubkey::find_program_address(&[SEED_THREAD, authority.as_ref(), id.as_ref()], &crate::ID)
+}
+
+/// Derive the address and bump of a thread's fiber at `index`.
+///
+/// Fibers are owned by the Fiber Program, so this uses its program ID.
+pub fn fiber_pubkey(thread: &Pubkey, index: u8) -> (Pubkey, u8) {
+    FiberState::compute_pda(thread, index)
+}
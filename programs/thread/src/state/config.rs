@@ -6,8 +6,13 @@ pub trait CommissionCalculator {
     fn calculate_effective_commission(&self, time_since_ready: i64) -> u64;
     fn calculate_executor_fee(&self, effective_commission: u64) -> u64;
     fn calculate_core_team_fee(&self, effective_commission: u64) -> u64;
+    fn calculate_streak_multiplier(&self, consecutive_execs: u64) -> f64;
 }
 
+/// Current version of the ThreadConfig structure.
+/// Version 1 ends at `fee_decay_seconds`; version 2 appends the fields after it.
+pub const CURRENT_CONFIG_VERSION: u64 = 2;
+
 /// Struct to hold payment details
 #[derive(Debug)]
 pub struct PaymentDetails {
@@ -16,6 +21,19 @@ pub struct PaymentDetails {
     pub core_team_fee: u64,
 }
 
+/// Who bears the transaction fee of an execution
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub enum FeePayerMode {
+    /// The executor pays the fee and recoups it through commission; the thread
+    /// only refunds what the fiber drew from the executor
+    #[default]
+    ExecutorPays,
+    /// The thread reimburses `base_fee_lamports` to the executor on every exec
+    ThreadReimburses,
+}
+
 /// Trait for processing payments
 pub trait PaymentProcessor {
     fn calculate_payments(
@@ -23,6 +41,8 @@ pub trait PaymentProcessor {
         time_since_ready: i64,
         balance_change: i64,
         forgo_commission: bool,
+        fee_payer_mode: FeePayerMode,
+        consecutive_execs: u64,
     ) -> PaymentDetails;
 
     fn should_pay(&self, balance_change: i64) -> bool {
@@ -62,6 +82,21 @@ pub struct ThreadConfig {
     pub grace_period_seconds: i64,
     /// Decay period in seconds after grace (commission decays to 0)
     pub fee_decay_seconds: i64,
+    /// Waive the core team fee as well when the executor forgoes commission
+    pub waive_core_team_on_forgo: bool,
+    /// Default fee payer mode for threads without an override
+    pub fee_payer_mode: FeePayerMode,
+    /// Base transaction fee reimbursed per exec in `ThreadReimburses` mode
+    pub base_fee_lamports: u64,
+    /// Admin proposed by `propose_admin`, awaiting `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    /// Most fibers a chain may execute within one slot (0 disables the limit)
+    pub max_chain_depth: u8,
+    /// Reported consecutive failures that disable a fiber (0 disables the check)
+    pub max_fiber_failures: u8,
+    /// Consecutive executions over which the executor commission halves
+    /// (0 disables the streak discount)
+    pub streak_half_life_execs: u64,
 }
 
 impl ThreadConfig {
@@ -102,6 +137,25 @@ impl CommissionCalculator for ThreadConfig {
     fn calculate_core_team_fee(&self, effective_commission: u64) -> u64 {
         (effective_commission * self.core_team_bps) / 10_000
     }
+
+    /// Share of the executor commission paid after `consecutive_execs`
+    /// successful executions in a row. The commission halves every
+    /// `streak_half_life_execs` executions of the streak:
+    ///
+    /// ```text
+    /// multiplier = 0.5 ^ (consecutive_execs / streak_half_life_execs)
+    /// ```
+    ///
+    /// The executor commission is `floor(executor_fee * multiplier)`; the core
+    /// team fee is not discounted. The first exec of a streak
+    /// (`consecutive_execs == 0`) pays in full, and a `streak_half_life_execs`
+    /// of 0 turns the discount off.
+    fn calculate_streak_multiplier(&self, consecutive_execs: u64) -> f64 {
+        if self.streak_half_life_execs == 0 || consecutive_execs == 0 {
+            return 1.0;
+        }
+        0.5f64.powf(consecutive_execs as f64 / self.streak_half_life_execs as f64)
+    }
 }
 
 impl PaymentProcessor for ThreadConfig {
@@ -110,25 +164,38 @@ impl PaymentProcessor for ThreadConfig {
         time_since_ready: i64,
         balance_change: i64,
         forgo_commission: bool,
+        fee_payer_mode: FeePayerMode,
+        consecutive_execs: u64,
     ) -> PaymentDetails {
         // Calculate effective commission
         let effective_commission = self.calculate_effective_commission(time_since_ready);
 
-        // Calculate reimbursement and commission for executor
-        let (fee_payer_reimbursement, executor_commission) = if self.should_pay(balance_change) {
-            let reimbursement = self.calculate_reimbursement(balance_change);
-            let commission = if !forgo_commission {
-                self.calculate_executor_fee(effective_commission)
-            } else {
-                0
-            };
-            (reimbursement, commission)
+        // Calculate reimbursement for the fee payer
+        let fee_payer_reimbursement = match fee_payer_mode {
+            FeePayerMode::ExecutorPays if self.should_pay(balance_change) => {
+                self.calculate_reimbursement(balance_change)
+            }
+            FeePayerMode::ExecutorPays => 0,
+            // Base fee on every exec, plus anything the fiber drew from the executor
+            FeePayerMode::ThreadReimburses => self
+                .base_fee_lamports
+                .saturating_add(balance_change.min(0).unsigned_abs()),
+        };
+
+        // Calculate commission for executor, discounted by the thread's execution streak
+        let executor_commission = if self.should_pay(balance_change) && !forgo_commission {
+            let executor_fee = self.calculate_executor_fee(effective_commission);
+            (executor_fee as f64 * self.calculate_streak_multiplier(consecutive_execs)) as u64
         } else {
-            (0, 0)
+            0
         };
 
-        // Calculate core team fee
-        let core_team_fee = self.calculate_core_team_fee(effective_commission);
+        // Calculate core team fee (waived together with the commission if configured)
+        let core_team_fee = if forgo_commission && self.waive_core_team_on_forgo {
+            0
+        } else {
+            self.calculate_core_team_fee(effective_commission)
+        };
 
         PaymentDetails {
             fee_payer_reimbursement,
@@ -0,0 +1,109 @@
+//! Account layouts that predate the current version, kept so the migrate
+//! instructions can read accounts written by older program releases.
+
+use crate::{constants::*, state::*};
+use anchor_lang::prelude::*;
+
+/// `ThreadConfig` as written by version 1 of the program
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct ThreadConfigV1 {
+    pub version: u64,
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub commission_fee: u64,
+    pub executor_fee_bps: u64,
+    pub core_team_bps: u64,
+    pub grace_period_seconds: i64,
+    pub fee_decay_seconds: i64,
+}
+
+impl ThreadConfigV1 {
+    /// Carries the version 1 settings over; fields added since take the
+    /// defaults `init_config` would give them.
+    pub fn into_current(self) -> ThreadConfig {
+        ThreadConfig {
+            version: CURRENT_CONFIG_VERSION,
+            bump: self.bump,
+            admin: self.admin,
+            paused: self.paused,
+            commission_fee: self.commission_fee,
+            executor_fee_bps: self.executor_fee_bps,
+            core_team_bps: self.core_team_bps,
+            grace_period_seconds: self.grace_period_seconds,
+            fee_decay_seconds: self.fee_decay_seconds,
+            waive_core_team_on_forgo: false,
+            fee_payer_mode: FeePayerMode::ExecutorPays,
+            base_fee_lamports: TRANSACTION_BASE_FEE_REIMBURSEMENT,
+            pending_admin: None,
+            max_chain_depth: DEFAULT_MAX_CHAIN_DEPTH,
+            max_fiber_failures: 0,
+            streak_half_life_execs: DEFAULT_STREAK_HALF_LIFE_EXECS,
+        }
+    }
+}
+
+/// `Thread` as written by version 1 of the program, which ends at `close_fiber`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug)]
+pub struct ThreadV1 {
+    pub version: u8,
+    pub bump: u8,
+    pub authority: Pubkey,
+    #[max_len(32)]
+    pub id: Vec<u8>,
+    #[max_len(64)]
+    pub name: String,
+    pub created_at: i64,
+    pub trigger: Trigger,
+    pub schedule: Schedule,
+    #[max_len(50)]
+    pub fiber_ids: Vec<u8>,
+    pub fiber_cursor: u8,
+    pub fiber_next_id: u8,
+    pub fiber_signal: Signal,
+    pub paused: bool,
+    pub exec_count: u64,
+    pub last_executor: Pubkey,
+    pub nonce_account: Pubkey,
+    #[max_len(44)]
+    pub last_nonce: String,
+    #[max_len(256)]
+    pub close_fiber: Vec<u8>,
+}
+
+impl ThreadV1 {
+    /// Carries the version 1 state over; fields added since start out empty,
+    /// as they do for a newly created thread.
+    pub fn into_current(self) -> Thread {
+        Thread {
+            version: CURRENT_THREAD_VERSION,
+            bump: self.bump,
+            authority: self.authority,
+            id: self.id,
+            name: self.name,
+            created_at: self.created_at,
+            trigger: self.trigger,
+            schedule: self.schedule,
+            fiber_ids: self.fiber_ids,
+            fiber_cursor: self.fiber_cursor,
+            fiber_next_id: self.fiber_next_id,
+            fiber_signal: self.fiber_signal,
+            paused: self.paused,
+            exec_count: self.exec_count,
+            last_executor: self.last_executor,
+            nonce_account: self.nonce_account,
+            last_nonce: self.last_nonce,
+            close_fiber: self.close_fiber,
+            fee_payer_mode: None,
+            consecutive_execs: 0,
+            chain_slot: 0,
+            chain_depth: 0,
+            chain_visited: [0; 4],
+            failing_fiber: 0,
+            fiber_failures: 0,
+            last_failure_slot: 0,
+            disabled_fibers: [0; 4],
+            resume_at: None,
+        }
+    }
+}
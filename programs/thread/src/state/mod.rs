@@ -1,7 +1,9 @@
 mod config;
 mod fiber;
+mod legacy;
 mod thread;
 
 pub use config::*;
 pub use fiber::*;
+pub use legacy::*;
 pub use thread::*;
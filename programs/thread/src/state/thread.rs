@@ -1,16 +1,19 @@
 use crate::{errors::AntegenThreadError, *};
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
-use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+use chrono::{DateTime, Utc};
+use std::{collections::hash_map::DefaultHasher, hash::Hasher, str::FromStr};
 
 // Re-export types from Fiber Program
 pub use antegen_fiber_program::state::{
-    compile_instruction, decompile_instruction, CompiledInstructionData, CompiledInstructionV0,
-    SerializableAccountMeta, SerializableInstruction,
+    compile_instruction, compiled_instruction_size, decompile_instruction, CompiledInstructionData,
+    CompiledInstructionV0, SerializableAccountMeta, SerializableInstruction,
+    MAX_COMPILED_INSTRUCTION_SIZE,
 };
 pub use antegen_fiber_program::{PAYER_PUBKEY, SEED_THREAD_FIBER};
 
 /// Current version of the Thread structure.
-pub const CURRENT_THREAD_VERSION: u8 = 1;
+/// Version 1 ends at `close_fiber`; version 2 appends the fields after it.
+pub const CURRENT_THREAD_VERSION: u8 = 2;
 
 /// The triggering conditions of a thread.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq, Debug)]
@@ -67,6 +70,10 @@ pub enum Trigger {
 
     /// Allows a thread to be kicked off according to an epoch number.
     Epoch { epoch: u64 },
+
+    /// Allows a thread to be kicked off once every `every` epochs, at epochs
+    /// divisible by `every`. The last epoch it fired in is kept in `Schedule::Block::prev`.
+    EpochInterval { every: u64 },
 }
 
 /// Tracks the execution schedule - when the thread last ran and when it should run next
@@ -79,7 +86,7 @@ pub enum Schedule {
     /// For time-based triggers (Immediate, Timestamp, Interval, Cron)
     Timed { prev: i64, next: i64 },
 
-    /// For block-based triggers (Slot, Epoch)
+    /// For block-based triggers (Slot, Epoch, EpochInterval)
     Block { prev: u64, next: u64 },
 }
 
@@ -100,6 +107,88 @@ pub enum Signal {
         trigger: Option<Trigger>,
         index: Option<u8>,
     },
+    Goto {
+        index: u8, // Chain to a specific later fiber (same tx), skipping those in between
+    },
+}
+
+impl Signal {
+    /// Whether this signal continues the current chain in the same transaction.
+    pub fn is_chaining(&self) -> bool {
+        matches!(self, Signal::Chain | Signal::Goto { .. })
+    }
+}
+
+/// Why [`Thread::check_executable`] found a thread executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutableBy {
+    /// Its trigger condition is met
+    Trigger,
+    /// The previous fiber chained to the next one, which skips the trigger
+    Chain,
+    /// A fiber signalled the thread to close itself
+    Close,
+}
+
+/// Why [`Thread::check_executable`] found a thread not executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotExecutable {
+    /// The thread config pauses every thread
+    GlobalPause,
+    /// The thread is paused, until `resume_at` if set
+    Paused { resume_at: Option<i64> },
+    /// The thread has no fibers to execute
+    NoFibers,
+    /// The fiber at the cursor is not one of the thread's fibers
+    MissingFiber(u8),
+    /// The fiber at the cursor was disabled after repeated failures
+    FiberDisabled(u8),
+    /// The trigger is due at this unix timestamp, jitter included
+    NotDueUntil(i64),
+    /// The trigger is due at this slot
+    NotDueUntilSlot(u64),
+    /// The trigger is due at this epoch
+    NotDueUntilEpoch(u64),
+    /// An interval or cron trigger without a next time scheduled
+    Unscheduled,
+    /// Account triggers depend on the watched account's data
+    AccountTrigger,
+    /// The stored bump does not derive the thread address
+    InvalidAddress,
+    /// The account still has this older layout version and must be migrated
+    NotMigrated(u8),
+}
+
+impl std::fmt::Display for NotExecutable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotExecutable::GlobalPause => write!(f, "all threads are paused by the config"),
+            NotExecutable::Paused { resume_at: None } => write!(f, "paused until unpaused"),
+            NotExecutable::Paused {
+                resume_at: Some(at),
+            } => write!(f, "paused until {}", at),
+            NotExecutable::NoFibers => write!(f, "no fibers to execute"),
+            NotExecutable::MissingFiber(index) => {
+                write!(f, "fiber {} at the cursor is missing", index)
+            }
+            NotExecutable::FiberDisabled(index) => write!(f, "fiber {} is disabled", index),
+            NotExecutable::NotDueUntil(at) => write!(f, "trigger not due until {}", at),
+            NotExecutable::NotDueUntilSlot(slot) => {
+                write!(f, "trigger not due until slot {}", slot)
+            }
+            NotExecutable::NotDueUntilEpoch(epoch) => {
+                write!(f, "trigger not due until epoch {}", epoch)
+            }
+            NotExecutable::Unscheduled => write!(f, "no next execution scheduled"),
+            NotExecutable::AccountTrigger => {
+                write!(f, "account trigger, fires when the watched account changes")
+            }
+            NotExecutable::InvalidAddress => write!(f, "bump does not derive the thread address"),
+            NotExecutable::NotMigrated(version) => {
+                write!(f, "account layout version {}, run migrate_thread", version)
+            }
+        }
+    }
 }
 
 /// Tracks the current state of a transaction thread on Solana.
@@ -130,7 +219,8 @@ pub struct Thread {
     // Lifecycle
     pub paused: bool,
 
-    // Execution tracking
+    // Execution tracking; exec_count goes up by one per exec_thread, chained
+    // fibers included, and is the exec sequence in ThreadExecuted
     pub exec_count: u64,
     pub last_executor: Pubkey,
 
@@ -142,6 +232,27 @@ pub struct Thread {
     // Pre-compiled thread_delete instruction for self-closing
     #[max_len(256)]
     pub close_fiber: Vec<u8>,
+
+    // Fee payer mode override (None = use the config default)
+    pub fee_payer_mode: Option<FeePayerMode>,
+
+    // Executions in a row within the commission window, discounts the executor commission
+    pub consecutive_execs: u64,
+
+    // Fiber chain within the current slot: its slot, length and executed fibers (bitmap)
+    pub chain_slot: u64,
+    pub chain_depth: u8,
+    pub chain_visited: [u64; 4],
+
+    // Reported consecutive failures of one fiber, the slot of the last report,
+    // and fibers disabled after too many failures (bitmap)
+    pub failing_fiber: u8,
+    pub fiber_failures: u8,
+    pub last_failure_slot: u64,
+    pub disabled_fibers: [u64; 4],
+
+    // When a paused thread resumes on its own (None = paused until unpaused)
+    pub resume_at: Option<i64>,
 }
 
 impl Thread {
@@ -153,42 +264,192 @@ impl Thread {
         Pubkey::find_program_address(&[SEED_THREAD, authority.as_ref(), id_bytes], &crate::ID).0
     }
 
+    /// Read the authority of a thread account without deserializing it.
+    ///
+    /// Every layout version starts with version, bump and authority, so this
+    /// also works for threads that no longer deserialize.
+    pub fn authority_from_data(data: &[u8]) -> Option<Pubkey> {
+        if !data.starts_with(<Thread as anchor_lang::Discriminator>::DISCRIMINATOR) {
+            return None;
+        }
+        data.get(10..42)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+    }
+
+    /// Derive the address and bump of the thread `authority` creates with `id`.
+    ///
+    /// Same seeds as the `thread` account constraint in `create_thread`.
+    pub fn compute_pda(authority: &Pubkey, id: &ThreadId) -> (Pubkey, u8) {
+        crate::pda::thread_pubkey(authority, id)
+    }
+
     /// Check if this thread has a nonce account.
     pub fn has_nonce_account(&self) -> bool {
         self.nonce_account != anchor_lang::solana_program::system_program::ID
             && self.nonce_account != crate::ID
     }
 
-    /// Advance fiber_cursor to the next fiber in the sequence.
+    /// Advance fiber_cursor to the next enabled fiber in the sequence.
     pub fn advance_to_next_fiber(&mut self) {
-        if self.fiber_ids.is_empty() {
-            self.fiber_cursor = 0;
-            return;
+        self.fiber_cursor = self.next_enabled_fiber(self.fiber_cursor);
+    }
+
+    /// The fiber after `current` in the sequence, wrapping to the beginning.
+    ///
+    /// Disabled fibers are skipped, unless every fiber is disabled. If
+    /// `current` is not a fiber of this thread, the search starts from the
+    /// first fiber instead.
+    pub fn next_enabled_fiber(&self, current: u8) -> u8 {
+        let len = self.fiber_ids.len();
+        if len == 0 {
+            return 0;
         }
 
-        // Find current index position in fiber_ids vec
-        if let Some(current_pos) = self.fiber_ids.iter().position(|&x| x == self.fiber_cursor) {
-            // Move to next fiber, or wrap to beginning
-            let next_pos = (current_pos + 1) % self.fiber_ids.len();
-            self.fiber_cursor = self.fiber_ids[next_pos];
+        let candidates: Vec<u8> = match self.fiber_ids.iter().position(|&x| x == current) {
+            Some(current_pos) => (1..=len)
+                .map(|step| self.fiber_ids[(current_pos + step) % len])
+                .collect(),
+            None => self.fiber_ids.clone(),
+        };
+        candidates
+            .iter()
+            .copied()
+            .find(|&index| !self.is_fiber_disabled(index))
+            .unwrap_or(candidates[0])
+    }
+
+    /// Whether fiber `index` was disabled after repeated failures.
+    pub fn is_fiber_disabled(&self, index: u8) -> bool {
+        self.disabled_fibers[(index / 64) as usize] & (1u64 << (index % 64)) != 0
+    }
+
+    /// Disable or re-enable fiber `index`.
+    pub fn set_fiber_disabled(&mut self, index: u8, disabled: bool) {
+        let word = (index / 64) as usize;
+        let bit = 1u64 << (index % 64);
+        if disabled {
+            self.disabled_fibers[word] |= bit;
         } else {
-            // Current fiber_cursor not found, reset to first fiber
-            self.fiber_cursor = self.fiber_ids.first().copied().unwrap_or(0);
+            self.disabled_fibers[word] &= !bit;
+        }
+    }
+
+    /// Indices of the fibers disabled after repeated failures.
+    pub fn disabled_fiber_ids(&self) -> Vec<u8> {
+        self.fiber_ids
+            .iter()
+            .copied()
+            .filter(|&index| self.is_fiber_disabled(index))
+            .collect()
+    }
+
+    /// Count a reported failure of fiber `index`.
+    ///
+    /// The execution streak is left alone: a report is not proof that an
+    /// exec failed, so it must not win an executor back the full commission.
+    /// A report for a different fiber than the last one restarts the count.
+    /// Once the count reaches `max_failures` (0 never disables) the fiber is
+    /// disabled, the count is cleared and `true` is returned.
+    pub fn record_fiber_failure(&mut self, index: u8, slot: u64, max_failures: u8) -> bool {
+        if self.failing_fiber != index {
+            self.failing_fiber = index;
+            self.fiber_failures = 0;
+        }
+        self.fiber_failures = self.fiber_failures.saturating_add(1);
+        self.last_failure_slot = slot;
+
+        if max_failures == 0 || self.fiber_failures < max_failures {
+            return false;
         }
+        self.set_fiber_disabled(index, true);
+        self.fiber_failures = 0;
+        true
+    }
+
+    /// Whether the thread is paused at `unix_timestamp`.
+    ///
+    /// A pause with a `resume_at` ends once that moment has passed, without
+    /// a transaction to unpause the thread.
+    pub fn is_paused_at(&self, unix_timestamp: i64) -> bool {
+        self.paused
+            && !self
+                .resume_at
+                .is_some_and(|resume_at| unix_timestamp >= resume_at)
+    }
+
+    /// The fee payer mode in effect: the thread's override, else the config default.
+    pub fn effective_fee_payer_mode(&self, config: &ThreadConfig) -> FeePayerMode {
+        self.fee_payer_mode.unwrap_or(config.fee_payer_mode)
+    }
+
+    /// Estimated seconds from the slot of the current chain's last exec to `slot`.
+    pub fn seconds_since_chain_step(&self, slot: u64) -> i64 {
+        let slots = slot.saturating_sub(self.chain_slot).min(i64::MAX as u64) as i64;
+        slots.saturating_mul(ESTIMATED_MS_PER_SLOT) / 1000
+    }
+
+    /// Record that fiber `cursor` is about to execute as part of a chain.
+    ///
+    /// A triggered (non-chained) execution, or a chained one in a later slot,
+    /// starts a new chain. Within a slot, a chain fails if it revisits a fiber
+    /// it already executed, or grows past `max_depth` fibers (0 disables the
+    /// depth limit). Chain continuations name their fiber themselves, so a
+    /// misbehaving executor could otherwise loop a thread's fibers.
+    pub fn record_chain_step(
+        &mut self,
+        slot: u64,
+        cursor: u8,
+        is_chained: bool,
+        max_depth: u8,
+    ) -> Result<()> {
+        let word = (cursor / 64) as usize;
+        let bit = 1u64 << (cursor % 64);
+
+        if !is_chained || self.chain_slot != slot {
+            self.chain_slot = slot;
+            self.chain_depth = 1;
+            self.chain_visited = [0; 4];
+            self.chain_visited[word] = bit;
+            return Ok(());
+        }
+
+        require!(
+            self.chain_visited[word] & bit == 0,
+            AntegenThreadError::ChainCycleDetected
+        );
+        let depth = self.chain_depth.saturating_add(1);
+        require!(
+            max_depth == 0 || depth <= max_depth,
+            AntegenThreadError::ChainDepthExceeded
+        );
+        self.chain_depth = depth;
+        self.chain_visited[word] |= bit;
+        Ok(())
+    }
+
+    /// Validate a Goto signal from fiber `current` targeting fiber `target`.
+    ///
+    /// The target must exist and come after `current` in `fiber_ids`. Chain
+    /// never wraps either, so a chain only ever moves forward and cannot
+    /// revisit a fiber within a single execution.
+    pub fn validate_goto(&self, current: u8, target: u8) -> Result<()> {
+        let target_pos = self
+            .fiber_ids
+            .iter()
+            .position(|&x| x == target)
+            .ok_or(AntegenThreadError::InvalidFiberIndex)?;
+        let current_pos = self.fiber_ids.iter().position(|&x| x == current);
+        require!(
+            current_pos.is_some_and(|pos| target_pos > pos),
+            AntegenThreadError::InvalidGotoTarget
+        );
+        Ok(())
     }
 
     /// Get the next fiber index in sequence (without mutating).
     /// Used to validate Chain signals target the correct consecutive fiber.
     pub fn next_fiber_index(&self) -> u8 {
-        if self.fiber_ids.is_empty() {
-            return 0;
-        }
-        if let Some(current_pos) = self.fiber_ids.iter().position(|&x| x == self.fiber_cursor) {
-            let next_pos = (current_pos + 1) % self.fiber_ids.len();
-            self.fiber_ids[next_pos]
-        } else {
-            self.fiber_ids.first().copied().unwrap_or(0)
-        }
+        self.next_enabled_fiber(self.fiber_cursor)
     }
 
     /// Get the fiber PDA for the current fiber_cursor
@@ -207,19 +468,7 @@ impl Thread {
 
     /// Get the next fiber PDA (for the next fiber_cursor in the sequence)
     pub fn next_fiber(&self, thread_pubkey: &Pubkey) -> Pubkey {
-        // Calculate next index based on fiber_ids sequence
-        let next_index = if self.fiber_ids.is_empty() {
-            0
-        } else if let Some(current_pos) =
-            self.fiber_ids.iter().position(|&x| x == self.fiber_cursor)
-        {
-            let next_pos = (current_pos + 1) % self.fiber_ids.len();
-            self.fiber_ids[next_pos]
-        } else {
-            self.fiber_ids.first().copied().unwrap_or(0)
-        };
-
-        self.fiber_at_index(thread_pubkey, next_index)
+        self.fiber_at_index(thread_pubkey, self.next_fiber_index())
     }
 
     /// Check if thread is ready to execute based on schedule
@@ -229,7 +478,7 @@ impl Thread {
             Schedule::Block { next, .. } => {
                 match &self.trigger {
                     Trigger::Slot { .. } => current_slot >= *next,
-                    Trigger::Epoch { .. } => {
+                    Trigger::Epoch { .. } | Trigger::EpochInterval { .. } => {
                         // For epoch triggers, we'd need epoch info
                         // This is a simplified check
                         false
@@ -244,6 +493,149 @@ impl Thread {
         }
     }
 
+    /// Predict the unix timestamp of the next execution, client-side.
+    ///
+    /// Time-based triggers use `schedule.next`, which already has jitter
+    /// baked in. Slot and epoch triggers are estimated from the current slot
+    /// assuming 400ms slots and mainnet-length epochs. Returns `None` for
+    /// paused threads, account and immediate triggers, and triggers whose
+    /// moment has already passed. A thread paused until `resume_at` is
+    /// predicted to run no earlier than that.
+    pub fn next_trigger_at(&self, clock_unix_timestamp: i64, clock_slot: u64) -> Option<i64> {
+        let resume_at = if self.is_paused_at(clock_unix_timestamp) {
+            self.resume_at?
+        } else {
+            i64::MIN
+        };
+
+        // Overdue recurring triggers fire as soon as an executor picks them up
+        let scheduled = || match self.schedule {
+            Schedule::Timed { next, .. } => Some(next.max(clock_unix_timestamp)),
+            _ => None,
+        };
+        let slot_eta = |target: u64| -> Option<i64> {
+            let slots = target.checked_sub(clock_slot).filter(|&s| s > 0)?;
+            Some(clock_unix_timestamp + slots as i64 * ESTIMATED_MS_PER_SLOT / 1000)
+        };
+
+        let next = match &self.trigger {
+            Trigger::Account { .. } | Trigger::Immediate { .. } => None,
+            Trigger::Timestamp { unix_ts, .. } => {
+                (*unix_ts > clock_unix_timestamp && self.exec_count == 0).then_some(*unix_ts)
+            }
+            Trigger::Interval { .. } => scheduled(),
+            // Cron threads not yet scheduled fall back to the next occurrence from now
+            Trigger::Cron { schedule, .. } => scheduled().or_else(|| {
+                let after = DateTime::<Utc>::from_timestamp(clock_unix_timestamp, 0)?;
+                antegen_cron::Schedule::from_str(schedule)
+                    .ok()?
+                    .next_after(&after)
+                    .map(|next| next.timestamp())
+            }),
+            Trigger::Slot { slot } => slot_eta(*slot),
+            Trigger::Epoch { epoch } => slot_eta(epoch.saturating_mul(ESTIMATED_SLOTS_PER_EPOCH)),
+            Trigger::EpochInterval { .. } => match self.schedule {
+                Schedule::Block { next, .. } => {
+                    slot_eta(next.saturating_mul(ESTIMATED_SLOTS_PER_EPOCH))
+                }
+                _ => None,
+            },
+        };
+        next.map(|next| next.max(resume_at))
+    }
+
+    /// Whether `thread_exec` would accept this thread at `clock`, client-side.
+    ///
+    /// See [`Self::check_executable`] for the reason when it would not.
+    pub fn is_executable(&self, clock: &Clock, config: &ThreadConfig) -> bool {
+        self.check_executable(clock, config).is_ok()
+    }
+
+    /// Why `thread_exec` would accept or reject this thread at `clock`,
+    /// client-side.
+    ///
+    /// Mirrors the program's checks in order: global and thread pause, the
+    /// close path, chained executions (which skip the trigger), fiber state
+    /// and disabled fibers, then the trigger condition from
+    /// `validate_trigger`. Account triggers depend on the watched account's
+    /// data, which the clock alone cannot tell, so they are never reported
+    /// executable.
+    pub fn check_executable(
+        &self,
+        clock: &Clock,
+        config: &ThreadConfig,
+    ) -> std::result::Result<ExecutableBy, NotExecutable> {
+        if self.version != CURRENT_THREAD_VERSION {
+            return Err(NotExecutable::NotMigrated(self.version));
+        }
+        if config.paused {
+            return Err(NotExecutable::GlobalPause);
+        }
+        if self.is_paused_at(clock.unix_timestamp) {
+            return Err(NotExecutable::Paused {
+                resume_at: self.resume_at,
+            });
+        }
+        if self.fiber_ids.is_empty() {
+            return Err(NotExecutable::NoFibers);
+        }
+        if self.fiber_signal == Signal::Close {
+            return Ok(ExecutableBy::Close);
+        }
+        if self.fiber_signal.is_chaining() {
+            return Ok(ExecutableBy::Chain);
+        }
+        if !self.fiber_ids.contains(&self.fiber_cursor) {
+            return Err(NotExecutable::MissingFiber(self.fiber_cursor));
+        }
+        if self.is_fiber_disabled(self.fiber_cursor) {
+            return Err(NotExecutable::FiberDisabled(self.fiber_cursor));
+        }
+
+        let due_at = |at: i64| {
+            (clock.unix_timestamp >= at)
+                .then_some(ExecutableBy::Trigger)
+                .ok_or(NotExecutable::NotDueUntil(at))
+        };
+        match &self.trigger {
+            Trigger::Immediate { .. } => Ok(ExecutableBy::Trigger),
+            Trigger::Timestamp { unix_ts, jitter } => {
+                let jitter_offset = if *jitter == 0 {
+                    0
+                } else {
+                    let thread_pubkey = Pubkey::create_program_address(
+                        &[SEED_THREAD, self.authority.as_ref(), &self.id, &[self.bump]],
+                        &crate::ID,
+                    )
+                    .map_err(|_| NotExecutable::InvalidAddress)?;
+                    crate::utils::calculate_jitter_offset(
+                        self.get_last_started_at(),
+                        &thread_pubkey,
+                        *jitter,
+                    )
+                };
+                due_at(unix_ts.saturating_add(jitter_offset))
+            }
+            Trigger::Slot { slot } => (clock.slot >= *slot)
+                .then_some(ExecutableBy::Trigger)
+                .ok_or(NotExecutable::NotDueUntilSlot(*slot)),
+            Trigger::Epoch { epoch } => (clock.epoch >= *epoch)
+                .then_some(ExecutableBy::Trigger)
+                .ok_or(NotExecutable::NotDueUntilEpoch(*epoch)),
+            Trigger::EpochInterval { .. } => match self.schedule {
+                Schedule::Block { next, .. } => (clock.epoch >= next)
+                    .then_some(ExecutableBy::Trigger)
+                    .ok_or(NotExecutable::NotDueUntilEpoch(next)),
+                _ => Err(NotExecutable::Unscheduled),
+            },
+            Trigger::Interval { .. } | Trigger::Cron { .. } => match self.schedule {
+                Schedule::Timed { next, .. } => due_at(next),
+                _ => Err(NotExecutable::Unscheduled),
+            },
+            Trigger::Account { .. } => Err(NotExecutable::AccountTrigger),
+        }
+    }
+
     /// Validate that the thread is ready for execution
     pub fn validate_for_execution(&self) -> Result<()> {
         // Check that thread has fibers
@@ -360,7 +752,7 @@ impl TriggerProcessor for Thread {
                     AntegenThreadError::TriggerConditionFailed
                 );
                 // Approximate when slot was reached (assuming 400ms per slot)
-                clock.unix_timestamp - ((clock.slot - slot) as i64 * 400 / 1000)
+                clock.unix_timestamp - ((clock.slot - slot) as i64 * ESTIMATED_MS_PER_SLOT / 1000)
             }
 
             Trigger::Epoch { epoch } => {
@@ -371,6 +763,20 @@ impl TriggerProcessor for Thread {
                 clock.unix_timestamp
             }
 
+            Trigger::EpochInterval { .. } => {
+                // schedule.next is the next qualifying epoch after the last one fired in
+                let next_epoch = match self.schedule {
+                    Schedule::Block { next, .. } => next,
+                    _ => return Err(AntegenThreadError::TriggerConditionFailed.into()),
+                };
+
+                require!(
+                    clock.epoch >= next_epoch,
+                    AntegenThreadError::TriggerConditionFailed
+                );
+                clock.unix_timestamp
+            }
+
             Trigger::Interval {
                 seconds: _,
                 skippable: _,
@@ -514,6 +920,11 @@ impl TriggerProcessor for Thread {
                 prev: clock.epoch,
                 next: *epoch,
             },
+            Trigger::EpochInterval { every } => Schedule::Block {
+                prev: clock.epoch,
+                next: crate::utils::next_epoch(clock.epoch, *every)
+                    .ok_or(AntegenThreadError::InvalidEpochInterval)?,
+            },
             Trigger::Interval {
                 seconds, jitter, ..
             } => {
@@ -13,6 +13,16 @@ pub fn next_timestamp(after: i64, schedule: String) -> Option<i64> {
         .map(|datetime| datetime.timestamp())
 }
 
+/// Calculate the first epoch after `current` that is a multiple of `every`
+///
+/// Returns `None` when `every` is zero or the result would overflow.
+pub fn next_epoch(current: u64, every: u64) -> Option<u64> {
+    current
+        .checked_div(every)?
+        .checked_add(1)?
+        .checked_mul(every)
+}
+
 /// Calculate deterministic jitter offset using prev timestamp and thread pubkey
 /// This creates a feedback loop where each execution's timing affects the next jitter
 pub fn calculate_jitter_offset(prev_timestamp: i64, thread_pubkey: &Pubkey, jitter: u64) -> i64 {
@@ -28,6 +38,17 @@ pub fn calculate_jitter_offset(prev_timestamp: i64, thread_pubkey: &Pubkey, jitt
     (hash % jitter) as i64
 }
 
+/// Idempotency key of one exec: the thread followed by its exec sequence
+/// (little-endian), as emitted in [`crate::events::ThreadExecuted`]
+///
+/// The same exec seen again through an RPC replay has the same key.
+pub fn exec_key(thread: &Pubkey, sequence: u64) -> [u8; 40] {
+    let mut key = [0u8; 40];
+    key[..32].copy_from_slice(thread.as_ref());
+    key[32..].copy_from_slice(&sequence.to_le_bytes());
+    key
+}
+
 /// Safely transfer lamports from one account to another
 pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
     if amount == 0 {
@@ -64,6 +64,7 @@ pub fn deserialize_fiber(
             exec_count: s.exec_count,
             priority_fee: s.priority_fee,
             lookup_tables: Vec::new(),
+            estimated_compute_units: None,
         },
         Fiber::V1(s) => s,
     }
@@ -8,9 +8,10 @@ use super::setup::{FIBER_PROGRAM_ID, PROGRAM_ID};
 
 // Re-export program types used by tests
 pub use antegen_thread_program::instructions::config_update::ConfigUpdateParams;
+pub use antegen_thread_program::instructions::fiber_create::FiberCreateParams;
 pub use antegen_thread_program::instructions::thread_update::ThreadUpdateParams;
+pub use antegen_thread_program::state::{FeePayerMode, Signal, Trigger};
 use antegen_thread_program::state::{SerializableAccountMeta, SerializableInstruction};
-pub use antegen_thread_program::state::{Signal, Trigger};
 pub use antegen_thread_program::ThreadId;
 
 // ============================================================================
@@ -46,6 +47,55 @@ pub fn build_update_config(
     }
 }
 
+pub fn build_migrate_config(admin: &Pubkey, config: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::ConfigMigrate {
+            admin: *admin,
+            config: *config,
+            system_program: solana_system_interface::program::ID,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::MigrateConfig {}.data(),
+    }
+}
+
+pub fn build_propose_admin(admin: &Pubkey, config: &Pubkey, new_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::ConfigProposeAdmin {
+            admin: *admin,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::ProposeAdmin { new_admin }.data(),
+    }
+}
+
+pub fn build_accept_admin(pending_admin: &Pubkey, config: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::ConfigAcceptAdmin {
+            pending_admin: *pending_admin,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::AcceptAdmin.data(),
+    }
+}
+
+pub fn build_cancel_admin_proposal(admin: &Pubkey, config: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::ConfigCancelAdmin {
+            admin: *admin,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::CancelAdminProposal.data(),
+    }
+}
+
 // ============================================================================
 // Thread Instructions
 // ============================================================================
@@ -116,6 +166,48 @@ pub fn build_create_thread_with_alts(
     }
 }
 
+pub fn build_create_thread_with_fibers(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    thread: &Pubkey,
+    amount: u64,
+    id: ThreadId,
+    trigger: Trigger,
+    instructions: Vec<SerializableInstruction>,
+    fibers: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = antegen_thread_program::accounts::ThreadCreate {
+        authority: *authority,
+        payer: *payer,
+        thread: *thread,
+        nonce_account: None,
+        recent_blockhashes: None,
+        rent: None,
+        system_program: solana_system_interface::program::ID,
+        fiber: None,
+        fiber_program: Some(FIBER_PROGRAM_ID),
+    }
+    .to_account_metas(None);
+    for fiber in fibers {
+        accounts.push(AccountMeta::new(fiber, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: antegen_thread_program::instruction::CreateThreadWithFibers {
+            amount,
+            id,
+            trigger,
+            paused: None,
+            instructions,
+            priority_fee: None,
+            lookup_tables: Vec::new(),
+        }
+        .data(),
+    }
+}
+
 pub fn build_update_thread(
     authority: &Pubkey,
     thread: &Pubkey,
@@ -132,6 +224,33 @@ pub fn build_update_thread(
     }
 }
 
+/// Build update_thread_atomic; `fibers` are (fiber account, params) pairs
+/// for the thread's next free indices, in order.
+pub fn build_update_thread_atomic(
+    authority: &Pubkey,
+    thread: &Pubkey,
+    params: ThreadUpdateParams,
+    fibers: Vec<(Pubkey, FiberCreateParams)>,
+) -> Instruction {
+    let mut accounts = antegen_thread_program::accounts::ThreadUpdateAtomic {
+        authority: *authority,
+        thread: *thread,
+        fiber_program: FIBER_PROGRAM_ID,
+        system_program: solana_system_interface::program::ID,
+    }
+    .to_account_metas(None);
+    let (fiber_accounts, fibers): (Vec<_>, Vec<_>) = fibers.into_iter().unzip();
+    for fiber in fiber_accounts {
+        accounts.push(AccountMeta::new(fiber, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: antegen_thread_program::instruction::UpdateThreadAtomic { params, fibers }.data(),
+    }
+}
+
 pub fn build_withdraw_thread(
     authority: &Pubkey,
     pay_to: &Pubkey,
@@ -150,6 +269,19 @@ pub fn build_withdraw_thread(
     }
 }
 
+pub fn build_migrate_thread(authority: &Pubkey, thread: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::ThreadMigrate {
+            authority: *authority,
+            thread: *thread,
+            system_program: solana_system_interface::program::ID,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::MigrateThread {}.data(),
+    }
+}
+
 pub fn build_close_thread(
     authority: &Pubkey,
     close_to: &Pubkey,
@@ -182,15 +314,44 @@ pub fn build_close_thread(
     }
 }
 
-pub fn build_delete_thread(admin: &Pubkey, config: &Pubkey, thread: &Pubkey) -> Instruction {
+pub fn build_delete_thread(
+    admin: &Pubkey,
+    authority: &Pubkey,
+    config: &Pubkey,
+    thread: &Pubkey,
+) -> Instruction {
+    build_delete_thread_with_fibers(admin, authority, config, thread, &[])
+}
+
+pub fn build_delete_thread_with_fibers(
+    admin: &Pubkey,
+    rent_recipient: &Pubkey,
+    config: &Pubkey,
+    thread: &Pubkey,
+    fiber_accounts: &[Pubkey],
+) -> Instruction {
+    let fiber_program = if fiber_accounts.is_empty() {
+        None
+    } else {
+        Some(FIBER_PROGRAM_ID)
+    };
+
+    let mut accounts = antegen_thread_program::accounts::ThreadDelete {
+        admin: *admin,
+        rent_recipient: *rent_recipient,
+        config: *config,
+        thread: *thread,
+        fiber_program,
+    }
+    .to_account_metas(None);
+
+    for fiber in fiber_accounts {
+        accounts.push(AccountMeta::new(*fiber, false));
+    }
+
     Instruction {
         program_id: PROGRAM_ID,
-        accounts: antegen_thread_program::accounts::ThreadDelete {
-            admin: *admin,
-            config: *config,
-            thread: *thread,
-        }
-        .to_account_metas(None),
+        accounts,
         data: antegen_thread_program::instruction::DeleteThread.data(),
     }
 }
@@ -318,6 +479,25 @@ pub fn build_update_fiber_full(
     }
 }
 
+pub fn build_reset_fiber_compute_estimate(
+    authority: &Pubkey,
+    thread: &Pubkey,
+    fiber: &Pubkey,
+    fiber_index: u8,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::FiberResetEstimate {
+            authority: *authority,
+            thread: *thread,
+            fiber: *fiber,
+            fiber_program: FIBER_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::ResetFiberComputeEstimate { fiber_index }.data(),
+    }
+}
+
 pub fn build_swap_fiber(
     authority: &Pubkey,
     thread: &Pubkey,
@@ -339,6 +519,24 @@ pub fn build_swap_fiber(
     }
 }
 
+pub fn build_report_fiber_failure(
+    executor: &Pubkey,
+    thread: &Pubkey,
+    config: &Pubkey,
+    fiber_cursor: u8,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: antegen_thread_program::accounts::FiberReportFailure {
+            executor: *executor,
+            thread: *thread,
+            config: *config,
+        }
+        .to_account_metas(None),
+        data: antegen_thread_program::instruction::ReportFiberFailure { fiber_cursor }.data(),
+    }
+}
+
 // ============================================================================
 // Exec / Memo Instructions
 // ============================================================================
@@ -0,0 +1,202 @@
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::*;
+
+fn send(
+    svm: &mut litesvm::LiteSVM,
+    signer: &Keypair,
+    ix: Instruction,
+) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata> {
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    let result = svm.send_transaction(tx);
+    svm.expire_blockhash();
+    result
+}
+
+fn funded_keypair(svm: &mut litesvm::LiteSVM) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), DEFAULT_AIRDROP).unwrap();
+    keypair
+}
+
+#[test]
+fn test_propose_and_accept_admin() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let new_admin = funded_keypair(&mut svm);
+    let (config_pubkey, _) = config_pda();
+
+    let meta = send(
+        &mut svm,
+        &admin,
+        build_propose_admin(&admin.pubkey(), &config_pubkey, new_admin.pubkey()),
+    )
+    .unwrap();
+    assert!(meta.logs.iter().any(|l| l.starts_with("Program data: ")));
+
+    // Proposal alone does not hand over control
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.pending_admin, Some(new_admin.pubkey()));
+
+    send(
+        &mut svm,
+        &new_admin,
+        build_accept_admin(&new_admin.pubkey(), &config_pubkey),
+    )
+    .unwrap();
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // Old admin is locked out, new admin is in
+    let result = send(
+        &mut svm,
+        &admin,
+        build_update_config(
+            &admin.pubkey(),
+            &config_pubkey,
+            ConfigUpdateParams {
+                commission_fee: Some(3000),
+                ..Default::default()
+            },
+        ),
+    );
+    assert!(result.is_err());
+    send(
+        &mut svm,
+        &new_admin,
+        build_update_config(
+            &new_admin.pubkey(),
+            &config_pubkey,
+            ConfigUpdateParams {
+                commission_fee: Some(3000),
+                ..Default::default()
+            },
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_propose_admin_non_admin_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let (config_pubkey, _) = config_pda();
+
+    let result = send(
+        &mut svm,
+        &payer,
+        build_propose_admin(&payer.pubkey(), &config_pubkey, payer.pubkey()),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_accept_admin_wrong_signer_fails() {
+    let (mut svm, admin, payer) = create_test_env();
+    let new_admin = funded_keypair(&mut svm);
+    let (config_pubkey, _) = config_pda();
+
+    send(
+        &mut svm,
+        &admin,
+        build_propose_admin(&admin.pubkey(), &config_pubkey, new_admin.pubkey()),
+    )
+    .unwrap();
+
+    // Neither a third party nor the current admin can accept
+    for signer in [&payer, &admin] {
+        let result = send(
+            &mut svm,
+            signer,
+            build_accept_admin(&signer.pubkey(), &config_pubkey),
+        );
+        assert!(result.is_err());
+    }
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.pending_admin, Some(new_admin.pubkey()));
+}
+
+#[test]
+fn test_accept_admin_without_proposal_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let (config_pubkey, _) = config_pda();
+
+    let result = send(
+        &mut svm,
+        &payer,
+        build_accept_admin(&payer.pubkey(), &config_pubkey),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cancel_admin_proposal() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let new_admin = funded_keypair(&mut svm);
+    let (config_pubkey, _) = config_pda();
+
+    send(
+        &mut svm,
+        &admin,
+        build_propose_admin(&admin.pubkey(), &config_pubkey, new_admin.pubkey()),
+    )
+    .unwrap();
+    send(
+        &mut svm,
+        &admin,
+        build_cancel_admin_proposal(&admin.pubkey(), &config_pubkey),
+    )
+    .unwrap();
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // The cancelled proposal can no longer be accepted
+    let result = send(
+        &mut svm,
+        &new_admin,
+        build_accept_admin(&new_admin.pubkey(), &config_pubkey),
+    );
+    assert!(result.is_err());
+
+    // Nothing left to cancel
+    let result = send(
+        &mut svm,
+        &admin,
+        build_cancel_admin_proposal(&admin.pubkey(), &config_pubkey),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cancel_admin_proposal_non_admin_fails() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let new_admin = funded_keypair(&mut svm);
+    let (config_pubkey, _) = config_pda();
+
+    send(
+        &mut svm,
+        &admin,
+        build_propose_admin(&admin.pubkey(), &config_pubkey, new_admin.pubkey()),
+    )
+    .unwrap();
+
+    // Only the current admin may withdraw, not the proposed one
+    let result = send(
+        &mut svm,
+        &new_admin,
+        build_cancel_admin_proposal(&new_admin.pubkey(), &config_pubkey),
+    );
+    assert!(result.is_err());
+}
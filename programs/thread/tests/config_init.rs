@@ -9,7 +9,10 @@ fn test_config_init_success() {
     let (config_pubkey, _) = config_pda();
 
     let config = deserialize_config(&svm, &config_pubkey);
-    assert_eq!(config.version, 1);
+    assert_eq!(
+        config.version,
+        antegen_thread_program::state::CURRENT_CONFIG_VERSION
+    );
     assert_eq!(config.admin, admin.pubkey());
     assert!(!config.paused);
     assert_eq!(config.commission_fee, 1000);
@@ -17,6 +20,9 @@ fn test_config_init_success() {
     assert_eq!(config.core_team_bps, 1000);
     assert_eq!(config.grace_period_seconds, 5);
     assert_eq!(config.fee_decay_seconds, 295);
+    assert_eq!(config.max_chain_depth, 16);
+    assert_eq!(config.max_fiber_failures, 0);
+    assert_eq!(config.streak_half_life_execs, 1_000);
 }
 
 #[test]
@@ -0,0 +1,135 @@
+use anchor_lang::{AnchorSerialize, Discriminator};
+use antegen_thread_program::state::{ThreadConfig, ThreadConfigV1, CURRENT_CONFIG_VERSION};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::*;
+
+/// Replace the config with one written by version 1 of the program.
+fn make_v1_config(svm: &mut litesvm::LiteSVM, admin: &Keypair) {
+    let (config_pubkey, bump) = config_pda();
+    let legacy = ThreadConfigV1 {
+        version: 1,
+        bump,
+        admin: admin.pubkey(),
+        paused: true,
+        commission_fee: 2000,
+        executor_fee_bps: 8000,
+        core_team_bps: 2000,
+        grace_period_seconds: 10,
+        fee_decay_seconds: 120,
+    };
+    let mut data = ThreadConfig::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(config_pubkey, account).unwrap();
+}
+
+fn send_migrate(
+    svm: &mut litesvm::LiteSVM,
+    admin: &Keypair,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_migrate_config(&admin.pubkey(), &config_pubkey);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+    svm.send_transaction(tx).map(|_| ())
+}
+
+fn send_update(
+    svm: &mut litesvm::LiteSVM,
+    admin: &Keypair,
+    params: ConfigUpdateParams,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_update_config(&admin.pubkey(), &config_pubkey, params);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+    svm.send_transaction(tx).map(|_| ())
+}
+
+#[test]
+fn test_config_migrate_legacy_account() {
+    let (mut svm, admin, _payer) = create_test_env();
+    make_v1_config(&mut svm, &admin);
+    let (config_pubkey, _) = config_pda();
+
+    // A version 1 account does not deserialize as the current layout
+    let params = ConfigUpdateParams {
+        paused: Some(false),
+        ..Default::default()
+    };
+    assert!(send_update(&mut svm, &admin, params).is_err());
+
+    send_migrate(&mut svm, &admin).unwrap();
+
+    let account = svm.get_account(&config_pubkey).unwrap();
+    assert_eq!(account.data.len(), ThreadConfig::space());
+    assert_eq!(
+        account.lamports,
+        svm.minimum_balance_for_rent_exemption(ThreadConfig::space())
+    );
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+    // Version 1 settings carried over
+    assert_eq!(config.admin, admin.pubkey());
+    assert!(config.paused);
+    assert_eq!(config.commission_fee, 2000);
+    assert_eq!(config.executor_fee_bps, 8000);
+    assert_eq!(config.core_team_bps, 2000);
+    assert_eq!(config.grace_period_seconds, 10);
+    assert_eq!(config.fee_decay_seconds, 120);
+    // Newer fields take the init_config defaults
+    assert!(!config.waive_core_team_on_forgo);
+    assert_eq!(config.fee_payer_mode, FeePayerMode::ExecutorPays);
+    assert_eq!(config.base_fee_lamports, 5000);
+    assert_eq!(config.pending_admin, None);
+    assert_eq!(config.max_chain_depth, 16);
+    assert_eq!(config.max_fiber_failures, 0);
+    assert_eq!(config.streak_half_life_execs, 1_000);
+
+    // Updates work once migrated
+    let params = ConfigUpdateParams {
+        paused: Some(false),
+        ..Default::default()
+    };
+    send_update(&mut svm, &admin, params).unwrap();
+    assert!(!deserialize_config(&svm, &config_pubkey).paused);
+}
+
+#[test]
+fn test_config_migrate_current_account_is_noop() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let (config_pubkey, _) = config_pda();
+    let before = svm.get_account(&config_pubkey).unwrap();
+
+    send_migrate(&mut svm, &admin).unwrap();
+
+    let after = svm.get_account(&config_pubkey).unwrap();
+    assert_eq!(after.data, before.data);
+    assert_eq!(after.lamports, before.lamports);
+}
+
+#[test]
+fn test_config_migrate_admin_only() {
+    let (mut svm, admin, payer) = create_test_env();
+    make_v1_config(&mut svm, &admin);
+    let (config_pubkey, _) = config_pda();
+
+    // payer is not admin
+    assert!(send_migrate(&mut svm, &payer).is_err());
+
+    let account = svm.get_account(&config_pubkey).unwrap();
+    assert!(account.data.len() < ThreadConfig::space());
+}
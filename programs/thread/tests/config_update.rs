@@ -218,6 +218,68 @@ fn test_config_update_pause_state() {
     assert!(config.paused);
 }
 
+#[test]
+fn test_config_update_waive_core_team_on_forgo() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let (config_pubkey, _) = config_pda();
+    assert!(!deserialize_config(&svm, &config_pubkey).waive_core_team_on_forgo);
+
+    send_update(
+        &mut svm,
+        &admin,
+        ConfigUpdateParams {
+            waive_core_team_on_forgo: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert!(config.waive_core_team_on_forgo);
+}
+
+#[test]
+fn test_config_update_max_chain_depth() {
+    let (mut svm, admin, _payer) = create_test_env();
+    send_update(
+        &mut svm,
+        &admin,
+        ConfigUpdateParams {
+            max_chain_depth: Some(0),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.max_chain_depth, 0, "0 disables the depth limit");
+}
+
+#[test]
+fn test_config_update_fee_payer_mode() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let (config_pubkey, _) = config_pda();
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.fee_payer_mode, FeePayerMode::ExecutorPays);
+    assert_eq!(config.base_fee_lamports, 5000);
+
+    send_update(
+        &mut svm,
+        &admin,
+        ConfigUpdateParams {
+            fee_payer_mode: Some(FeePayerMode::ThreadReimburses),
+            base_fee_lamports: Some(10_000),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.fee_payer_mode, FeePayerMode::ThreadReimburses);
+    assert_eq!(config.base_fee_lamports, 10_000);
+}
+
 #[test]
 fn test_config_update_multiple_params() {
     let (mut svm, admin, _payer) = create_test_env();
@@ -239,3 +301,41 @@ fn test_config_update_multiple_params() {
     assert_eq!(config.grace_period_seconds, 10);
     assert_eq!(config.fee_decay_seconds, 100);
 }
+
+#[test]
+fn test_config_update_max_fiber_failures() {
+    let (mut svm, admin, _payer) = create_test_env();
+    send_update(
+        &mut svm,
+        &admin,
+        ConfigUpdateParams {
+            max_fiber_failures: Some(5),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.max_fiber_failures, 5);
+}
+
+#[test]
+fn test_config_update_streak_half_life() {
+    let (mut svm, admin, _payer) = create_test_env();
+    send_update(
+        &mut svm,
+        &admin,
+        ConfigUpdateParams {
+            streak_half_life_execs: Some(50),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // Independent of the fee decay window
+    let (config_pubkey, _) = config_pda();
+    let config = deserialize_config(&svm, &config_pubkey);
+    assert_eq!(config.streak_half_life_execs, 50);
+    assert_eq!(config.fee_decay_seconds, 295);
+}
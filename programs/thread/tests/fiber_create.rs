@@ -1,4 +1,5 @@
 use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
@@ -163,7 +164,12 @@ fn test_fiber_create_prevents_delete_thread() {
     let (config_pubkey, _) = config_pda();
 
     // Build a delete_thread instruction as the fiber content
-    let delete_ix = build_delete_thread(&authority.pubkey(), &config_pubkey, &thread_pubkey);
+    let delete_ix = build_delete_thread(
+        &authority.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let serializable = make_serializable_instruction(&delete_ix);
 
     let (fiber_pubkey, _) = fiber_pda(&thread_pubkey, 0);
@@ -363,3 +369,128 @@ fn test_fiber_create_compiled_roundtrip() {
     );
     assert!(compiled.is_ok());
 }
+
+// ============================================================================
+// Instruction validation tests
+// ============================================================================
+
+/// Send a create_fiber at index 0 holding `instruction`.
+fn send_create_fiber_with_instruction(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    thread: &Pubkey,
+    instruction: &Instruction,
+) -> Result<Pubkey, litesvm::types::FailedTransactionMetadata> {
+    let (fiber_pubkey, _) = fiber_pda(thread, 0);
+    let ix = build_create_fiber(
+        &authority.pubkey(),
+        thread,
+        &fiber_pubkey,
+        0,
+        make_serializable_instruction(instruction),
+        0,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).map(|_| fiber_pubkey)
+}
+
+fn assert_rejected_with(
+    result: Result<Pubkey, litesvm::types::FailedTransactionMetadata>,
+    error: &str,
+) {
+    let err = result.expect_err("fiber instruction should be rejected");
+    assert!(
+        err.meta.logs.iter().any(|l| l.contains(error)),
+        "expected {} in {:?}",
+        error,
+        err.meta.logs
+    );
+}
+
+#[test]
+fn test_fiber_create_rejects_program_loaders() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = setup_thread(&mut svm, &authority, &payer, "fc-loader");
+    for loader in antegen_thread_program::PROGRAM_LOADERS {
+        let upgrade_ix = Instruction::new_with_bytes(loader, &[3, 0, 0, 0], vec![]);
+        let result = send_create_fiber_with_instruction(
+            &mut svm,
+            &authority,
+            &payer,
+            &thread_pubkey,
+            &upgrade_ix,
+        );
+        assert_rejected_with(result, "InvalidFiberTarget");
+    }
+    assert!(deserialize_thread(&svm, &thread_pubkey)
+        .fiber_ids
+        .is_empty());
+}
+
+#[test]
+fn test_fiber_create_rejects_writable_config_account() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = setup_thread(&mut svm, &authority, &payer, "fc-cfg");
+    let mut memo_ix = make_memo_instruction("fiber-cfg", None);
+    memo_ix.accounts.push(AccountMeta::new(
+        antegen_thread_program::CONFIG_PROGRAM_ID,
+        false,
+    ));
+    let result =
+        send_create_fiber_with_instruction(&mut svm, &authority, &payer, &thread_pubkey, &memo_ix);
+    assert_rejected_with(result, "DangerousAccountPermission");
+
+    // Reading it is fine
+    memo_ix.accounts.last_mut().unwrap().is_writable = false;
+    send_create_fiber_with_instruction(&mut svm, &authority, &payer, &thread_pubkey, &memo_ix)
+        .expect("read-only Config account should be allowed");
+}
+
+#[test]
+fn test_fiber_create_rejects_empty_instruction_data() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = setup_thread(&mut svm, &authority, &payer, "fc-empty");
+    let empty_ix = Instruction::new_with_bytes(PROGRAM_ID, &[], vec![]);
+    let result =
+        send_create_fiber_with_instruction(&mut svm, &authority, &payer, &thread_pubkey, &empty_ix);
+    assert_rejected_with(result, "EmptyInstructionData");
+}
+
+#[test]
+fn test_fiber_create_allows_cpi_instruction() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = setup_thread(&mut svm, &authority, &payer, "fc-cpi");
+    let transfer_ix =
+        solana_system_interface::instruction::transfer(&thread_pubkey, &Pubkey::new_unique(), 1);
+    let fiber_pubkey = send_create_fiber_with_instruction(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        &transfer_ix,
+    )
+    .expect("system transfer fiber should be allowed");
+
+    let fiber = deserialize_fiber(&svm, &fiber_pubkey);
+    assert_eq!(fiber.thread, thread_pubkey);
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).fiber_ids, vec![0]);
+}
@@ -342,7 +342,12 @@ fn test_fiber_update_prevents_delete_thread() {
         setup_thread_with_fiber_account(&mut svm, &authority, &payer, "fu-del");
     let (config_pubkey, _) = config_pda();
 
-    let delete_ix = build_delete_thread(&authority.pubkey(), &config_pubkey, &thread_pubkey);
+    let delete_ix = build_delete_thread(
+        &authority.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let serializable = make_serializable_instruction(&delete_ix);
     let ix = build_update_fiber(
         &authority.pubkey(),
@@ -1,13 +1,16 @@
 use antegen_thread_program::{
     constants::*,
+    pda::{fiber_pubkey, thread_pubkey},
     state::{
-        compile_instruction, decompile_instruction, CommissionCalculator, FiberState,
-        PaymentProcessor, Schedule, Signal, Thread, ThreadConfig, Trigger, CURRENT_THREAD_VERSION,
-        SEED_THREAD_FIBER,
+        compile_instruction, decompile_instruction, CommissionCalculator, ExecutableBy,
+        FeePayerMode, FiberState, NotExecutable, PaymentProcessor, Schedule, Signal, Thread,
+        ThreadConfig, Trigger, TriggerProcessor, CURRENT_THREAD_VERSION, SEED_THREAD_FIBER,
     },
-    utils::{calculate_jitter_offset, next_timestamp},
+    utils::{calculate_jitter_offset, next_epoch, next_timestamp},
+    ThreadId,
 };
 use solana_sdk::{
+    clock::Clock,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
@@ -39,6 +42,16 @@ fn make_thread(fiber_ids: Vec<u8>, fiber_cursor: u8) -> Thread {
         nonce_account: PROGRAM_ID, // sentinel for no nonce
         last_nonce: String::new(),
         close_fiber: Vec::new(),
+        fee_payer_mode: None,
+        consecutive_execs: 0,
+        chain_slot: 0,
+        chain_depth: 0,
+        chain_visited: [0; 4],
+        failing_fiber: 0,
+        fiber_failures: 0,
+        last_failure_slot: 0,
+        disabled_fibers: [0; 4],
+        resume_at: None,
     }
 }
 
@@ -79,6 +92,29 @@ fn test_advance_to_next_fiber_cursor_not_found() {
     assert_eq!(thread.fiber_cursor, 0); // resets to first
 }
 
+#[test]
+fn test_advance_to_next_fiber_skips_disabled() {
+    let mut thread = make_thread(vec![0, 1, 2, 200], 0);
+    thread.set_fiber_disabled(1, true);
+    thread.set_fiber_disabled(200, true);
+    thread.advance_to_next_fiber();
+    assert_eq!(thread.fiber_cursor, 2);
+    thread.advance_to_next_fiber();
+    assert_eq!(thread.fiber_cursor, 0); // wraps past 200
+    assert_eq!(thread.next_fiber_index(), 2);
+    assert_eq!(thread.disabled_fiber_ids(), vec![1, 200]);
+
+    // With every fiber disabled the plain sequence is kept
+    thread.set_fiber_disabled(0, true);
+    thread.set_fiber_disabled(2, true);
+    thread.advance_to_next_fiber();
+    assert_eq!(thread.fiber_cursor, 1);
+
+    thread.set_fiber_disabled(2, false);
+    assert!(!thread.is_fiber_disabled(2));
+    assert_eq!(thread.next_fiber_index(), 2);
+}
+
 // ============================================================================
 // Thread::has_nonce_account tests
 // ============================================================================
@@ -126,6 +162,38 @@ fn test_validate_valid_cursor() {
     assert!(thread.validate_for_execution().is_ok());
 }
 
+// ============================================================================
+// Thread::validate_goto tests
+// ============================================================================
+
+#[test]
+fn test_validate_goto_forward() {
+    let thread = make_thread(vec![0, 1, 2, 3], 0);
+    assert!(thread.validate_goto(0, 2).is_ok());
+    assert!(thread.validate_goto(2, 3).is_ok());
+}
+
+#[test]
+fn test_validate_goto_missing_target() {
+    let thread = make_thread(vec![0, 1, 2], 0);
+    assert!(thread.validate_goto(0, 5).is_err());
+}
+
+#[test]
+fn test_validate_goto_backward_or_self() {
+    let thread = make_thread(vec![0, 1, 2], 0);
+    assert!(thread.validate_goto(2, 0).is_err());
+    assert!(thread.validate_goto(1, 1).is_err());
+}
+
+#[test]
+fn test_signal_is_chaining() {
+    assert!(Signal::Chain.is_chaining());
+    assert!(Signal::Goto { index: 1 }.is_chaining());
+    assert!(!Signal::Close.is_chaining());
+    assert!(!Signal::Next { index: 1 }.is_chaining());
+}
+
 // ============================================================================
 // Thread::is_ready tests
 // ============================================================================
@@ -163,6 +231,257 @@ fn test_is_ready_block_slot() {
     assert!(!thread.is_ready(99, 0)); // not yet
 }
 
+// ============================================================================
+// Thread::next_trigger_at tests
+// ============================================================================
+
+#[test]
+fn test_next_trigger_at_timed() {
+    let mut thread = make_thread(vec![0], 0);
+    thread.trigger = Trigger::Interval {
+        seconds: 60,
+        skippable: false,
+        jitter: 0,
+    };
+    thread.schedule = Schedule::Timed {
+        prev: 1_000,
+        next: 1_060,
+    };
+    assert_eq!(thread.next_trigger_at(1_010, 0), Some(1_060));
+    assert_eq!(thread.next_trigger_at(1_100, 0), Some(1_100)); // overdue fires now
+
+    thread.paused = true;
+    assert_eq!(thread.next_trigger_at(1_010, 0), None);
+}
+
+#[test]
+fn test_paused_until_resume_at() {
+    let mut thread = make_thread(vec![0], 0);
+    thread.trigger = Trigger::Interval {
+        seconds: 60,
+        skippable: false,
+        jitter: 0,
+    };
+    thread.schedule = Schedule::Timed {
+        prev: 1_000,
+        next: 1_060,
+    };
+    thread.paused = true;
+    thread.resume_at = Some(1_500);
+
+    assert!(thread.is_paused_at(1_499));
+    assert!(!thread.is_paused_at(1_500));
+    assert_eq!(thread.next_trigger_at(1_010, 0), Some(1_500)); // held until resume
+    assert_eq!(thread.next_trigger_at(1_600, 0), Some(1_600));
+
+    thread.paused = false;
+    assert!(!thread.is_paused_at(1_000));
+}
+
+#[test]
+fn test_next_trigger_at_cron() {
+    let mut thread = make_thread(vec![0], 0);
+    let schedule = "0 * * * * * *".to_string(); // every minute
+    thread.trigger = Trigger::Cron {
+        schedule: schedule.clone(),
+        skippable: true,
+        jitter: 0,
+    };
+    thread.schedule = Schedule::OnChange { prev: 0 };
+    assert_eq!(
+        thread.next_trigger_at(1_700_000_030, 0),
+        next_timestamp(1_700_000_030, schedule)
+    );
+}
+
+#[test]
+fn test_next_trigger_at_one_shot() {
+    let mut thread = make_thread(vec![0], 0);
+    assert_eq!(thread.next_trigger_at(1_000, 0), None); // immediate
+
+    thread.trigger = Trigger::Timestamp {
+        unix_ts: 2_000,
+        jitter: 0,
+    };
+    assert_eq!(thread.next_trigger_at(1_000, 0), Some(2_000));
+    assert_eq!(thread.next_trigger_at(2_001, 0), None); // expired
+    thread.exec_count = 1;
+    assert_eq!(thread.next_trigger_at(1_000, 0), None); // already fired
+}
+
+#[test]
+fn test_next_trigger_at_slot_and_epoch() {
+    let mut thread = make_thread(vec![0], 0);
+    thread.trigger = Trigger::Slot { slot: 1_100 };
+    assert_eq!(thread.next_trigger_at(1_000, 1_000), Some(1_040)); // 100 slots * 400ms
+    assert_eq!(thread.next_trigger_at(1_000, 1_100), None);
+
+    thread.trigger = Trigger::Epoch { epoch: 2 };
+    let epoch_start = 2 * ESTIMATED_SLOTS_PER_EPOCH;
+    assert_eq!(thread.next_trigger_at(1_000, epoch_start - 10), Some(1_004));
+    assert_eq!(thread.next_trigger_at(1_000, epoch_start), None);
+}
+
+// ============================================================================
+// Trigger::EpochInterval tests
+// ============================================================================
+
+#[test]
+fn test_next_epoch_alignment() {
+    assert_eq!(next_epoch(0, 1), Some(1));
+    assert_eq!(next_epoch(7, 1), Some(8));
+    assert_eq!(next_epoch(7, 5), Some(10));
+    assert_eq!(next_epoch(10, 5), Some(15)); // already on a boundary
+    assert_eq!(next_epoch(7, 0), None);
+    assert_eq!(next_epoch(u64::MAX, 2), None);
+}
+
+#[test]
+fn test_epoch_interval_rollover() {
+    let config = make_config();
+    let thread_pubkey = Pubkey::new_unique();
+    let clock_at = |epoch: u64| Clock {
+        slot: epoch * ESTIMATED_SLOTS_PER_EPOCH + 10,
+        epoch,
+        unix_timestamp: 1_000 + epoch as i64,
+        ..Clock::default()
+    };
+
+    // Created in epoch 7, firing every 2 epochs
+    let mut thread = make_thread(vec![0], 0);
+    thread.trigger = Trigger::EpochInterval { every: 2 };
+    thread.schedule = Schedule::Block { prev: 7, next: 8 };
+
+    // Not due until the epoch rolls over
+    let clock = clock_at(7);
+    assert!(thread
+        .validate_trigger(&clock, &[], &thread_pubkey)
+        .is_err());
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::NotDueUntilEpoch(8))
+    );
+    assert_eq!(
+        thread.next_trigger_at(clock.unix_timestamp, clock.slot),
+        Some(
+            clock.unix_timestamp
+                + (ESTIMATED_SLOTS_PER_EPOCH as i64 - 10) * ESTIMATED_MS_PER_SLOT / 1000
+        )
+    );
+
+    // Fires at the boundary and records the epoch it fired in
+    let clock = clock_at(8);
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Ok(ExecutableBy::Trigger)
+    );
+    assert!(thread.validate_trigger(&clock, &[], &thread_pubkey).is_ok());
+    thread.update_schedule(&clock, &[], &thread_pubkey).unwrap();
+    assert_eq!(thread.schedule, Schedule::Block { prev: 8, next: 10 });
+
+    // Not again within the same epoch, nor the skipped one
+    assert!(thread
+        .validate_trigger(&clock, &[], &thread_pubkey)
+        .is_err());
+    assert!(thread
+        .validate_trigger(&clock_at(9), &[], &thread_pubkey)
+        .is_err());
+
+    // A late execution still lands on the next aligned epoch
+    let clock = clock_at(11);
+    assert!(thread.validate_trigger(&clock, &[], &thread_pubkey).is_ok());
+    thread.update_schedule(&clock, &[], &thread_pubkey).unwrap();
+    assert_eq!(thread.schedule, Schedule::Block { prev: 11, next: 12 });
+}
+
+#[test]
+fn test_check_executable_reasons() {
+    let config = make_config();
+    let clock = Clock {
+        slot: 1_000,
+        epoch: 1,
+        unix_timestamp: 1_000,
+        ..Clock::default()
+    };
+    let mut thread = make_thread(vec![0, 1], 0);
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Ok(ExecutableBy::Trigger)
+    );
+
+    thread.trigger = Trigger::Interval {
+        seconds: 60,
+        skippable: false,
+        jitter: 0,
+    };
+    thread.schedule = Schedule::Timed {
+        prev: 1_000,
+        next: 1_060,
+    };
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::NotDueUntil(1_060))
+    );
+    thread.schedule = Schedule::OnChange { prev: 0 };
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::Unscheduled)
+    );
+
+    thread.trigger = Trigger::Slot { slot: 1_100 };
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::NotDueUntilSlot(1_100))
+    );
+    thread.trigger = Trigger::Epoch { epoch: 2 };
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::NotDueUntilEpoch(2))
+    );
+
+    // A chained fiber skips the trigger, a disabled one blocks it
+    thread.fiber_signal = Signal::Chain;
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Ok(ExecutableBy::Chain)
+    );
+    thread.fiber_signal = Signal::None;
+    thread.trigger = Trigger::Immediate { jitter: 0 };
+    thread.disabled_fibers[0] = 1;
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::FiberDisabled(0))
+    );
+    thread.fiber_cursor = 5;
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::MissingFiber(5))
+    );
+
+    thread.paused = true;
+    thread.resume_at = Some(1_500);
+    assert_eq!(
+        thread.check_executable(&clock, &config),
+        Err(NotExecutable::Paused {
+            resume_at: Some(1_500)
+        })
+    );
+    let mut paused_config = make_config();
+    paused_config.paused = true;
+    assert_eq!(
+        thread.check_executable(&clock, &paused_config),
+        Err(NotExecutable::GlobalPause)
+    );
+    assert!(!thread.is_executable(&clock, &config));
+
+    // A version 1 account is rejected before anything else
+    thread.version = 1;
+    assert_eq!(
+        thread.check_executable(&clock, &paused_config),
+        Err(NotExecutable::NotMigrated(1))
+    );
+}
+
 // ============================================================================
 // compile_instruction / decompile_instruction roundtrip tests
 // ============================================================================
@@ -231,6 +550,13 @@ fn make_config() -> ThreadConfig {
         core_team_bps: 1000,
         grace_period_seconds: 5,
         fee_decay_seconds: 295,
+        waive_core_team_on_forgo: false,
+        fee_payer_mode: FeePayerMode::ExecutorPays,
+        base_fee_lamports: 5000,
+        pending_admin: None,
+        max_chain_depth: 16,
+        max_fiber_failures: 0,
+        streak_half_life_execs: 1_000,
     }
 }
 
@@ -265,7 +591,7 @@ fn test_commission_expired() {
 #[test]
 fn test_payment_normal() {
     let config = make_config();
-    let payments = config.calculate_payments(0, -5000, false);
+    let payments = config.calculate_payments(0, -5000, false, FeePayerMode::ExecutorPays, 0);
     // Within grace, full commission
     assert_eq!(payments.fee_payer_reimbursement, 5000); // abs(-5000)
     assert!(payments.executor_commission > 0);
@@ -275,22 +601,219 @@ fn test_payment_normal() {
 #[test]
 fn test_payment_forgo_commission() {
     let config = make_config();
-    let payments = config.calculate_payments(0, -5000, true); // forgo
+    let payments = config.calculate_payments(0, -5000, true, FeePayerMode::ExecutorPays, 0); // forgo
     assert_eq!(payments.executor_commission, 0);
     assert!(payments.core_team_fee > 0); // core team always gets paid
 }
 
+#[test]
+fn test_payment_forgo_commission_core_team_honored() {
+    let config = make_config();
+    let payments = config.calculate_payments(0, 0, true, FeePayerMode::ExecutorPays, 0);
+    assert_eq!(payments.fee_payer_reimbursement, 5000);
+    assert_eq!(payments.executor_commission, 0);
+    assert_eq!(payments.core_team_fee, 100); // 10% of 1000
+}
+
+#[test]
+fn test_payment_forgo_commission_core_team_waived() {
+    let mut config = make_config();
+    config.waive_core_team_on_forgo = true;
+    let payments = config.calculate_payments(0, 0, true, FeePayerMode::ExecutorPays, 0);
+    assert_eq!(payments.fee_payer_reimbursement, 5000);
+    assert_eq!(payments.executor_commission, 0);
+    assert_eq!(payments.core_team_fee, 0);
+
+    // Flag only applies when commission is actually forgone
+    let payments = config.calculate_payments(0, 0, false, FeePayerMode::ExecutorPays, 0);
+    assert_eq!(payments.executor_commission, 900);
+    assert_eq!(payments.core_team_fee, 100);
+}
+
 #[test]
 fn test_payment_no_payment_positive_balance() {
     let config = make_config();
     // Positive balance change means inner instruction already paid
-    let payments = config.calculate_payments(0, 5000, false);
+    let payments = config.calculate_payments(0, 5000, false, FeePayerMode::ExecutorPays, 0);
     assert_eq!(payments.fee_payer_reimbursement, 0);
     assert_eq!(payments.executor_commission, 0);
     // Core team fee still calculated
     assert!(payments.core_team_fee > 0);
 }
 
+#[test]
+fn test_payment_thread_reimburses_base_fee() {
+    let config = make_config();
+    // Base fee reimbursed even when the fiber left the executor untouched
+    let payments = config.calculate_payments(0, 0, false, FeePayerMode::ThreadReimburses, 0);
+    assert_eq!(payments.fee_payer_reimbursement, 5000);
+    assert_eq!(payments.executor_commission, 900);
+
+    // Lamports drawn from the executor are refunded on top of the base fee
+    let payments = config.calculate_payments(0, -2000, false, FeePayerMode::ThreadReimburses, 0);
+    assert_eq!(payments.fee_payer_reimbursement, 7000);
+
+    // ...and the base fee is still owed when the fiber paid the executor
+    let payments = config.calculate_payments(0, 5000, false, FeePayerMode::ThreadReimburses, 0);
+    assert_eq!(payments.fee_payer_reimbursement, 5000);
+    assert_eq!(payments.executor_commission, 0);
+}
+
+#[test]
+fn test_streak_multiplier_curve() {
+    let mut config = make_config();
+    // Halves every streak_half_life_execs (1000) consecutive execs
+    assert_eq!(config.calculate_streak_multiplier(0), 1.0);
+    assert_eq!(config.calculate_streak_multiplier(1_000), 0.5);
+    assert_eq!(config.calculate_streak_multiplier(2_000), 0.25);
+
+    // The fee decay window plays no part
+    config.fee_decay_seconds = 0;
+    assert_eq!(config.calculate_streak_multiplier(1_000), 0.5);
+
+    config.streak_half_life_execs = 1;
+    assert_eq!(config.calculate_streak_multiplier(1), 0.5);
+    assert_eq!(config.calculate_streak_multiplier(2), 0.25);
+    assert_eq!(config.calculate_streak_multiplier(3), 0.125);
+
+    // No half-life, no discount
+    config.streak_half_life_execs = 0;
+    assert_eq!(config.calculate_streak_multiplier(10), 1.0);
+}
+
+#[test]
+fn test_payment_streak_discount() {
+    let mut config = make_config();
+    config.streak_half_life_execs = 2;
+
+    // 900 * 0.5^(n / 2)
+    let commissions: Vec<u64> = [0, 1, 2, 4]
+        .iter()
+        .map(|&n| {
+            config
+                .calculate_payments(0, 0, false, FeePayerMode::ExecutorPays, n)
+                .executor_commission
+        })
+        .collect();
+    assert_eq!(commissions, vec![900, 636, 450, 225]);
+
+    // The core team fee is not discounted
+    let payments = config.calculate_payments(0, 0, false, FeePayerMode::ExecutorPays, 5);
+    assert_eq!(payments.core_team_fee, 100);
+}
+
+#[test]
+fn test_fiber_failure_keeps_streak() {
+    let mut thread = make_thread(vec![0], 0);
+    thread.consecutive_execs = 12;
+
+    // A report is only an executor's word, so the streak survives it,
+    // disabling or not
+    assert!(!thread.record_fiber_failure(0, 10, 0));
+    assert_eq!(thread.consecutive_execs, 12);
+    assert!(thread.record_fiber_failure(0, 20, 2));
+    assert_eq!(thread.consecutive_execs, 12);
+}
+
+#[test]
+fn test_seconds_since_chain_step() {
+    let mut thread = make_thread(vec![0, 1], 0);
+    thread.record_chain_step(100, 0, false, 0).unwrap();
+
+    // 400ms slots
+    assert_eq!(thread.seconds_since_chain_step(100), 0);
+    assert_eq!(thread.seconds_since_chain_step(850), 300);
+    // An earlier slot never counts as time passed
+    assert_eq!(thread.seconds_since_chain_step(50), 0);
+}
+
+#[test]
+fn test_record_chain_step_cycle_and_depth() {
+    let mut thread = make_thread(vec![0, 1, 2, 3], 0);
+
+    // A triggered exec starts the chain
+    thread.record_chain_step(10, 0, false, 3).unwrap();
+    thread.record_chain_step(10, 1, true, 3).unwrap();
+    assert_eq!(thread.chain_depth, 2);
+
+    // Revisiting fiber 0 in the same slot is a cycle
+    assert!(thread.record_chain_step(10, 0, true, 3).is_err());
+
+    thread.record_chain_step(10, 2, true, 3).unwrap();
+    assert!(thread.record_chain_step(10, 3, true, 3).is_err());
+
+    // 0 disables the depth limit, not the cycle check
+    thread.record_chain_step(10, 3, true, 0).unwrap();
+    assert_eq!(thread.chain_depth, 4);
+    assert!(thread.record_chain_step(10, 3, true, 0).is_err());
+}
+
+#[test]
+fn test_record_chain_step_resets() {
+    let mut thread = make_thread(vec![0, 200], 0);
+    thread.record_chain_step(10, 0, false, 2).unwrap();
+    thread.record_chain_step(10, 200, true, 2).unwrap();
+
+    // A chain continuing in a later slot starts over
+    thread.record_chain_step(11, 0, true, 2).unwrap();
+    assert_eq!(thread.chain_slot, 11);
+    assert_eq!(thread.chain_depth, 1);
+
+    // So does a triggered exec in the same slot
+    thread.record_chain_step(11, 200, true, 2).unwrap();
+    thread.record_chain_step(11, 200, false, 2).unwrap();
+    assert_eq!(thread.chain_depth, 1);
+    thread.record_chain_step(11, 0, true, 2).unwrap();
+}
+
+#[test]
+fn test_record_fiber_failure() {
+    let mut thread = make_thread(vec![0, 1], 0);
+    assert!(!thread.record_fiber_failure(0, 10, 3));
+    assert!(!thread.record_fiber_failure(0, 20, 3));
+    assert_eq!(thread.fiber_failures, 2);
+    assert_eq!(thread.last_failure_slot, 20);
+
+    // A report for another fiber restarts the count
+    assert!(!thread.record_fiber_failure(1, 30, 3));
+    assert_eq!(thread.failing_fiber, 1);
+    assert_eq!(thread.fiber_failures, 1);
+
+    assert!(!thread.record_fiber_failure(1, 40, 3));
+    assert!(thread.record_fiber_failure(1, 50, 3));
+    assert!(thread.is_fiber_disabled(1));
+    assert_eq!(thread.fiber_failures, 0);
+
+    // 0 never disables
+    for slot in 0..10 {
+        assert!(!thread.record_fiber_failure(0, slot, 0));
+    }
+    assert!(!thread.is_fiber_disabled(0));
+}
+
+#[test]
+fn test_effective_fee_payer_mode() {
+    let mut config = make_config();
+    let mut thread = make_thread(vec![0], 0);
+    assert_eq!(
+        thread.effective_fee_payer_mode(&config),
+        FeePayerMode::ExecutorPays
+    );
+
+    config.fee_payer_mode = FeePayerMode::ThreadReimburses;
+    assert_eq!(
+        thread.effective_fee_payer_mode(&config),
+        FeePayerMode::ThreadReimburses
+    );
+
+    // Per-thread override wins over the config default
+    thread.fee_payer_mode = Some(FeePayerMode::ExecutorPays);
+    assert_eq!(
+        thread.effective_fee_payer_mode(&config),
+        FeePayerMode::ExecutorPays
+    );
+}
+
 // ============================================================================
 // calculate_jitter_offset tests
 // ============================================================================
@@ -358,3 +881,87 @@ fn test_fiber_pda_derivation() {
     let derived = FiberState::pubkey(thread, index);
     assert_eq!(derived, expected);
 }
+
+#[test]
+fn test_thread_pubkey_bytes_id() {
+    let authority = Pubkey::new_unique();
+    let expected =
+        Pubkey::find_program_address(&[SEED_THREAD, authority.as_ref(), b"payroll"], &PROGRAM_ID);
+    let id = ThreadId::Bytes(b"payroll".to_vec());
+    assert_eq!(thread_pubkey(&authority, &id), expected);
+}
+
+#[test]
+fn test_thread_pubkey_pubkey_id() {
+    let authority = Pubkey::new_unique();
+    let id_pubkey = Pubkey::new_unique();
+    let expected = Pubkey::find_program_address(
+        &[SEED_THREAD, authority.as_ref(), id_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+    assert_eq!(
+        thread_pubkey(&authority, &ThreadId::Pubkey(id_pubkey)),
+        expected
+    );
+}
+
+#[test]
+fn test_thread_pubkey_hash_id() {
+    let authority = Pubkey::new_unique();
+    let hash = [7u8; 32];
+    let expected =
+        Pubkey::find_program_address(&[SEED_THREAD, authority.as_ref(), &hash], &PROGRAM_ID);
+    assert_eq!(thread_pubkey(&authority, &ThreadId::Hash(hash)), expected);
+    assert_eq!(ThreadId::Hash(hash).to_name().len(), 64);
+}
+
+#[test]
+fn test_fiber_pubkey() {
+    let thread = Pubkey::new_unique();
+    for index in [0u8, 3, 255] {
+        let expected = Pubkey::find_program_address(
+            &[SEED_THREAD_FIBER, thread.as_ref(), &[index]],
+            &FIBER_PROGRAM_ID,
+        );
+        assert_eq!(fiber_pubkey(&thread, index), expected);
+        assert_eq!(
+            fiber_pubkey(&thread, index).0,
+            FiberState::pubkey(thread, index)
+        );
+    }
+}
+
+#[test]
+fn test_thread_compute_pda() {
+    let authority = Pubkey::new_unique();
+    for id in [
+        ThreadId::from("payroll"),
+        ThreadId::Pubkey(Pubkey::new_unique()),
+        ThreadId::Hash([9u8; 32]),
+    ] {
+        // Same seeds and program as the `thread` account constraint in create_thread
+        let expected = Pubkey::find_program_address(
+            &[SEED_THREAD, authority.as_ref(), id.as_ref()],
+            &PROGRAM_ID,
+        );
+        assert_eq!(Thread::compute_pda(&authority, &id), expected);
+        assert_eq!(
+            Thread::compute_pda(&authority, &id).0,
+            Thread::pubkey(authority, &id)
+        );
+    }
+}
+
+#[test]
+fn test_fiber_compute_pda() {
+    let thread = Pubkey::new_unique();
+    for index in [0u8, 1, 255] {
+        // Same seeds and program as the `fiber` account constraint in the Fiber Program
+        let expected = Pubkey::find_program_address(
+            &[SEED_THREAD_FIBER, thread.as_ref(), &[index]],
+            &FIBER_PROGRAM_ID,
+        );
+        assert_eq!(FiberState::compute_pda(&thread, index), expected);
+        assert_eq!(fiber_pubkey(&thread, index), expected);
+    }
+}
@@ -206,6 +206,63 @@ fn test_create_thread_epoch_trigger() {
     }
 }
 
+#[test]
+fn test_create_thread_epoch_interval_trigger() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let clock = get_clock(&svm);
+    let (thread_pubkey, _) = create_thread_helper(
+        &mut svm,
+        &authority,
+        &payer,
+        "epoch-every-test",
+        Trigger::EpochInterval { every: 3 },
+        100_000,
+    );
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    match thread.schedule {
+        antegen_thread_program::state::Schedule::Block { prev, next } => {
+            assert_eq!(prev, clock.epoch);
+            assert_eq!(next, (clock.epoch / 3 + 1) * 3);
+        }
+        _ => panic!("Expected Block schedule"),
+    }
+}
+
+#[test]
+fn test_create_thread_epoch_interval_zero_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_id = ThreadId::Bytes(b"epoch-zero".to_vec());
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), b"epoch-zero");
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        100_000,
+        thread_id,
+        Trigger::EpochInterval { every: 0 },
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Epoch interval of 0 should fail");
+    assert!(!account_exists(&svm, &thread_pubkey));
+}
+
 #[test]
 fn test_create_thread_account_trigger() {
     let (mut svm, _admin, payer) = create_test_env();
@@ -335,6 +392,43 @@ fn test_create_thread_id_pubkey() {
     assert_eq!(thread.id.len(), 32);
 }
 
+#[test]
+fn test_create_thread_id_hash() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let hash = [42u8; 32];
+    let thread_id = ThreadId::Hash(hash);
+    let (thread_pubkey, bump) =
+        antegen_thread_program::pda::thread_pubkey(&authority.pubkey(), &thread_id);
+
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        100_000,
+        thread_id,
+        Trigger::Immediate { jitter: 0 },
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.id, hash);
+    assert_eq!(thread.bump, bump);
+    assert_eq!(thread.name, "2a".repeat(32));
+}
+
 #[test]
 fn test_create_thread_pda_derivation() {
     let (mut svm, _admin, payer) = create_test_env();
@@ -371,7 +465,10 @@ fn test_create_thread_initial_state() {
     );
 
     let thread = deserialize_thread(&svm, &thread_pubkey);
-    assert_eq!(thread.version, 1);
+    assert_eq!(
+        thread.version,
+        antegen_thread_program::state::CURRENT_THREAD_VERSION
+    );
     assert!(!thread.paused);
     assert_eq!(thread.exec_count, 0);
     assert_eq!(thread.fiber_cursor, 0);
@@ -627,3 +724,156 @@ fn test_create_thread_with_fiber_no_accounts_fails() {
         "Should fail when instruction provided but fiber accounts missing"
     );
 }
+
+/// Send `create_thread_with_fibers` with `instructions`, passing `fibers` as
+/// the fiber accounts.
+fn create_thread_with_fibers(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    thread_pubkey: &Pubkey,
+    id: &str,
+    instructions: Vec<antegen_thread_program::state::SerializableInstruction>,
+    fibers: Vec<Pubkey>,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let ix = build_create_thread_with_fibers(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        thread_pubkey,
+        100_000_000, // enough to fund every fiber
+        ThreadId::Bytes(id.as_bytes().to_vec()),
+        Trigger::Immediate { jitter: 0 },
+        instructions,
+        fibers,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).map(|_| ())
+}
+
+/// Smallest instruction a fiber can hold: no accounts, one byte of data
+fn empty_instruction() -> antegen_thread_program::state::SerializableInstruction {
+    antegen_thread_program::state::SerializableInstruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![],
+        data: vec![0],
+    }
+}
+
+#[test]
+fn test_create_thread_with_max_initial_fibers() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let id = "multi-fiber";
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+    let count = antegen_thread_program::MAX_INITIAL_FIBERS as u8;
+    let fibers: Vec<Pubkey> = (0..count).map(|i| fiber_pda(&thread_pubkey, i).0).collect();
+
+    create_thread_with_fibers(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        id,
+        (0..count).map(|_| empty_instruction()).collect(),
+        fibers.clone(),
+    )
+    .expect("create_thread_with_fibers at MAX_INITIAL_FIBERS should succeed");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.fiber_ids, (0..count).collect::<Vec<u8>>());
+    assert_eq!(thread.fiber_next_id, count);
+    assert_eq!(thread.fiber_cursor, 0);
+    for fiber_pubkey in &fibers {
+        let fiber = deserialize_fiber(&svm, fiber_pubkey);
+        assert_eq!(fiber.thread, thread_pubkey);
+        assert!(!fiber.compiled_instruction.is_empty());
+    }
+}
+
+#[test]
+fn test_create_thread_with_too_many_initial_fibers_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let id = "too-many";
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+    let count = antegen_thread_program::MAX_INITIAL_FIBERS as u8 + 1;
+
+    let result = create_thread_with_fibers(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        id,
+        (0..count).map(|_| empty_instruction()).collect(),
+        (0..count).map(|i| fiber_pda(&thread_pubkey, i).0).collect(),
+    );
+    assert!(result.is_err(), "More than MAX_INITIAL_FIBERS should fail");
+    assert!(!account_exists(&svm, &thread_pubkey));
+}
+
+#[test]
+fn test_create_thread_with_oversized_initial_instructions_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let id = "too-big";
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+    let mut instruction = empty_instruction();
+    instruction.data = vec![0; antegen_thread_program::MAX_INITIAL_INSTRUCTIONS_SIZE];
+    assert!(
+        antegen_thread_program::state::compiled_instruction_size(&instruction)
+            > antegen_thread_program::MAX_INITIAL_INSTRUCTIONS_SIZE
+    );
+
+    let result = create_thread_with_fibers(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        id,
+        vec![instruction],
+        vec![fiber_pda(&thread_pubkey, 0).0],
+    );
+    assert!(
+        result.is_err(),
+        "Oversized initial instructions should fail"
+    );
+    assert!(!account_exists(&svm, &thread_pubkey));
+}
+
+#[test]
+fn test_create_thread_with_fibers_wrong_fiber_account_fails() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let id = "wrong-pda";
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+
+    // Fibers passed out of order
+    let result = create_thread_with_fibers(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        id,
+        vec![empty_instruction(), empty_instruction()],
+        vec![
+            fiber_pda(&thread_pubkey, 1).0,
+            fiber_pda(&thread_pubkey, 0).0,
+        ],
+    );
+    assert!(result.is_err(), "Fiber accounts out of order should fail");
+    assert!(!account_exists(&svm, &thread_pubkey));
+}
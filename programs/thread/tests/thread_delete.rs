@@ -19,7 +19,7 @@ fn create_thread(
         &authority.pubkey(),
         &payer.pubkey(),
         &thread_pubkey,
-        10_000_000, // enough to fund 1 fiber
+        20_000_000, // enough to fund up to 2 fibers
         thread_id,
         Trigger::Immediate { jitter: 0 },
         None,
@@ -46,7 +46,12 @@ fn test_thread_delete_admin_success() {
     let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-1");
     let (config_pubkey, _) = config_pda();
 
-    let ix = build_delete_thread(&admin.pubkey(), &config_pubkey, &thread_pubkey);
+    let ix = build_delete_thread(
+        &admin.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
     svm.send_transaction(tx).unwrap();
@@ -64,7 +69,12 @@ fn test_thread_delete_non_admin_fails() {
     let (config_pubkey, _) = config_pda();
 
     // authority is not admin
-    let ix = build_delete_thread(&authority.pubkey(), &config_pubkey, &thread_pubkey);
+    let ix = build_delete_thread(
+        &authority.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(
         &[ix],
@@ -107,7 +117,12 @@ fn test_thread_delete_skips_fiber_checks() {
 
     // Admin delete skips fiber checks
     let (config_pubkey, _) = config_pda();
-    let ix = build_delete_thread(&admin.pubkey(), &config_pubkey, &thread_pubkey);
+    let ix = build_delete_thread(
+        &admin.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
     svm.send_transaction(tx).unwrap();
@@ -115,7 +130,7 @@ fn test_thread_delete_skips_fiber_checks() {
 }
 
 #[test]
-fn test_thread_delete_returns_rent_to_admin() {
+fn test_thread_delete_returns_rent_to_authority() {
     let (mut svm, admin, payer) = create_test_env();
     let authority = Keypair::new();
     svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
@@ -123,18 +138,164 @@ fn test_thread_delete_returns_rent_to_admin() {
     let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-rent");
     let (config_pubkey, _) = config_pda();
 
-    let admin_before = get_balance(&svm, &admin.pubkey());
+    let authority_before = get_balance(&svm, &authority.pubkey());
     let thread_balance = get_balance(&svm, &thread_pubkey);
 
-    let ix = build_delete_thread(&admin.pubkey(), &config_pubkey, &thread_pubkey);
+    let ix = build_delete_thread(
+        &admin.pubkey(),
+        &authority.pubkey(),
+        &config_pubkey,
+        &thread_pubkey,
+    );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
     svm.send_transaction(tx).unwrap();
 
-    let admin_after = get_balance(&svm, &admin.pubkey());
-    // Admin should gain the thread's balance minus tx fee
-    let gained = admin_after as i64 - admin_before as i64;
-    assert!(gained > 0);
-    // Should be close to thread_balance (minus tx fee ~5000)
-    assert!(gained as u64 > thread_balance.saturating_sub(10_000));
+    // The admin pays the fee, the authority gets the whole balance back
+    assert_eq!(
+        get_balance(&svm, &authority.pubkey()),
+        authority_before + thread_balance
+    );
+}
+
+#[test]
+fn test_thread_delete_rejects_other_recipient() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-recipient");
+    let thread_balance = get_balance(&svm, &thread_pubkey);
+
+    // The admin cannot send the authority's rent to itself
+    let err = send_delete(&mut svm, &admin, &admin.pubkey(), &thread_pubkey, &[])
+        .expect_err("a recipient other than the authority should be rejected");
+    assert!(err.meta.logs.iter().any(|l| l.contains("InvalidAuthority")));
+    assert!(account_exists(&svm, &thread_pubkey));
+    assert_eq!(get_balance(&svm, &thread_pubkey), thread_balance);
+}
+
+fn add_fiber(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    thread_pubkey: &Pubkey,
+    index: u8,
+) -> Pubkey {
+    let (fiber_pubkey, _) = fiber_pda(thread_pubkey, index);
+    let memo_ix = make_memo_instruction(&format!("fiber {}", index), None);
+    let ix = build_create_fiber(
+        &authority.pubkey(),
+        thread_pubkey,
+        &fiber_pubkey,
+        index,
+        make_serializable_instruction(&memo_ix),
+        0,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+    fiber_pubkey
+}
+
+fn send_delete(
+    svm: &mut litesvm::LiteSVM,
+    admin: &Keypair,
+    rent_recipient: &Pubkey,
+    thread_pubkey: &Pubkey,
+    fibers: &[Pubkey],
+) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_delete_thread_with_fibers(
+        &admin.pubkey(),
+        rent_recipient,
+        &config_pubkey,
+        thread_pubkey,
+        fibers,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+    svm.send_transaction(tx)
+}
+
+#[test]
+fn test_thread_delete_reclaims_fiber_rent_to_authority() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-reclaim");
+    let fibers = [
+        add_fiber(&mut svm, &authority, &payer, &thread_pubkey, 0),
+        add_fiber(&mut svm, &authority, &payer, &thread_pubkey, 1),
+    ];
+
+    let recipient = authority.pubkey();
+    let expected = get_balance(&svm, &recipient)
+        + get_balance(&svm, &thread_pubkey)
+        + fibers.iter().map(|f| get_balance(&svm, f)).sum::<u64>();
+
+    let meta = send_delete(&mut svm, &admin, &recipient, &thread_pubkey, &fibers).unwrap();
+    assert!(meta.logs.iter().any(|l| l.starts_with("Program data: ")));
+
+    assert!(!account_exists(&svm, &thread_pubkey));
+    for fiber in &fibers {
+        assert!(!account_exists(&svm, fiber));
+    }
+    assert_eq!(get_balance(&svm, &recipient), expected);
+}
+
+#[test]
+fn test_thread_delete_rejects_foreign_account() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-victim");
+    add_fiber(&mut svm, &authority, &payer, &thread_pubkey, 0);
+
+    // A fiber belonging to another thread
+    let other_thread = create_thread(&mut svm, &authority, &payer, "td-other");
+    let foreign_fiber = add_fiber(&mut svm, &authority, &payer, &other_thread, 0);
+    let foreign_balance = get_balance(&svm, &foreign_fiber);
+
+    let result = send_delete(
+        &mut svm,
+        &admin,
+        &authority.pubkey(),
+        &thread_pubkey,
+        &[foreign_fiber],
+    );
+    assert!(result.is_err());
+    assert!(account_exists(&svm, &thread_pubkey));
+    assert_eq!(get_balance(&svm, &foreign_fiber), foreign_balance);
+}
+
+#[test]
+fn test_thread_delete_partial_fibers_fails() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "td-partial");
+    let fiber_0 = add_fiber(&mut svm, &authority, &payer, &thread_pubkey, 0);
+    let fiber_1 = add_fiber(&mut svm, &authority, &payer, &thread_pubkey, 1);
+
+    // Only one of two fibers passed: all or nothing
+    let result = send_delete(
+        &mut svm,
+        &admin,
+        &authority.pubkey(),
+        &thread_pubkey,
+        &[fiber_0],
+    );
+    assert!(result.is_err());
+    assert!(account_exists(&svm, &thread_pubkey));
+    assert!(account_exists(&svm, &fiber_0));
+    assert!(account_exists(&svm, &fiber_1));
 }
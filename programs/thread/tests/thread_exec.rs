@@ -1,5 +1,8 @@
+use anchor_lang::{AnchorSerialize, Discriminator, Space};
+use antegen_fiber_program::state::{FiberVersionedState, FiberVersionedStateV1};
+use antegen_thread_program::{events::ThreadExecuted, utils::exec_key};
 use solana_sdk::{
-    instruction::AccountMeta,
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
@@ -184,6 +187,83 @@ fn test_exec_thread_paused_fails() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_exec_thread_resumes_after_resume_at() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-resume-at",
+        Trigger::Immediate { jitter: 0 },
+        "test",
+        None,
+    );
+
+    // Pause for 100 seconds
+    let resume_at = get_clock(&svm).unix_timestamp + 100;
+    let update_ix = build_update_thread(
+        &authority.pubkey(),
+        &thread_pubkey,
+        ThreadUpdateParams {
+            paused: Some(true),
+            resume_at: Some(resume_at),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[update_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let remaining = build_remaining_accounts(&executor.pubkey());
+    let exec = |svm: &mut litesvm::LiteSVM| {
+        let ix = build_exec_thread(
+            &executor.pubkey(),
+            &thread_pubkey,
+            &fiber_pubkey,
+            &config_pubkey,
+            &admin.pubkey(),
+            false,
+            0,
+            &remaining,
+        );
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&executor.pubkey()),
+            &[&executor],
+            blockhash,
+        );
+        svm.send_transaction(tx)
+    };
+
+    // Still paused before resume_at
+    let err = exec(&mut svm).unwrap_err();
+    assert!(err.meta.logs.iter().any(|l| l.contains("ThreadPaused")));
+
+    // Once resume_at passes the exec goes through and ends the pause
+    advance_clock(&mut svm, 100);
+    exec(&mut svm).unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+    assert!(!thread.paused);
+    assert_eq!(thread.resume_at, None);
+}
+
 #[test]
 fn test_exec_thread_global_pause_fails() {
     let (mut svm, admin, payer) = create_test_env();
@@ -464,6 +544,205 @@ fn test_exec_thread_interval_trigger() {
     assert_eq!(thread.exec_count, 1);
 }
 
+#[test]
+fn test_exec_thread_interval_at_predicted_time() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-predict",
+        Trigger::Interval {
+            seconds: 30,
+            skippable: false,
+            jitter: 0,
+        },
+        "test",
+        None,
+    );
+
+    for exec_count in 1..=2 {
+        let clock = get_clock(&svm);
+        let thread = deserialize_thread(&svm, &thread_pubkey);
+        let predicted = thread
+            .next_trigger_at(clock.unix_timestamp, clock.slot)
+            .expect("interval thread should have a next trigger");
+        assert_eq!(predicted, clock.unix_timestamp + 30);
+
+        // Execute exactly at the predicted time
+        advance_clock(&mut svm, predicted - clock.unix_timestamp);
+        let remaining = build_remaining_accounts(&executor.pubkey());
+        let ix = build_exec_thread(
+            &executor.pubkey(),
+            &thread_pubkey,
+            &fiber_pubkey,
+            &config_pubkey,
+            &admin.pubkey(),
+            false,
+            0,
+            &remaining,
+        );
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&executor.pubkey()),
+            &[&executor],
+            blockhash,
+        );
+        svm.send_transaction(tx).unwrap();
+
+        // The on-chain execution time matches the prediction
+        let thread = deserialize_thread(&svm, &thread_pubkey);
+        assert_eq!(thread.exec_count, exec_count);
+        match thread.schedule {
+            antegen_thread_program::state::Schedule::Timed { prev, .. } => {
+                assert_eq!(prev, predicted)
+            }
+            other => panic!("unexpected schedule {:?}", other),
+        }
+    }
+}
+
+/// `Thread::is_executable` must agree with `thread_exec` for every thread at
+/// every clock position, before and after each trigger comes due.
+#[test]
+fn test_is_executable_matches_exec() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let clock = get_clock(&svm);
+    let triggers = [
+        ("exe-immediate", Trigger::Immediate { jitter: 0 }),
+        (
+            "exe-timestamp",
+            Trigger::Timestamp {
+                unix_ts: clock.unix_timestamp + 25,
+                jitter: 0,
+            },
+        ),
+        (
+            "exe-ts-jitter",
+            Trigger::Timestamp {
+                unix_ts: clock.unix_timestamp + 25,
+                jitter: 30,
+            },
+        ),
+        (
+            "exe-interval",
+            Trigger::Interval {
+                seconds: 40,
+                skippable: false,
+                jitter: 0,
+            },
+        ),
+        (
+            "exe-slot",
+            Trigger::Slot {
+                slot: clock.slot + 100,
+            },
+        ),
+    ];
+    let threads: Vec<(Pubkey, Pubkey)> = triggers
+        .into_iter()
+        .map(|(id, trigger)| {
+            setup_exec_thread(
+                &mut svm,
+                &authority,
+                &payer,
+                &admin.pubkey(),
+                id,
+                trigger,
+                "test",
+                None,
+            )
+        })
+        .collect();
+
+    let mut executable = 0;
+    let mut blocked = 0;
+    for step in 0..8 {
+        let clock = get_clock(&svm);
+        let config = deserialize_config(&svm, &config_pubkey);
+        for (thread_pubkey, fiber_pubkey) in &threads {
+            let thread = deserialize_thread(&svm, thread_pubkey);
+            let predicted = thread.is_executable(&clock, &config);
+
+            let remaining = build_remaining_accounts(&executor.pubkey());
+            let ix = build_exec_thread(
+                &executor.pubkey(),
+                thread_pubkey,
+                fiber_pubkey,
+                &config_pubkey,
+                &admin.pubkey(),
+                false,
+                0,
+                &remaining,
+            );
+            let blockhash = svm.latest_blockhash();
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&executor.pubkey()),
+                &[&executor],
+                blockhash,
+            );
+            let simulated = svm.simulate_transaction(tx).is_ok();
+
+            assert_eq!(
+                predicted, simulated,
+                "thread {} at step {} (ts={}, slot={})",
+                thread.name, step, clock.unix_timestamp, clock.slot
+            );
+            if predicted {
+                executable += 1;
+            } else {
+                blocked += 1;
+            }
+        }
+
+        advance_clock(&mut svm, 10);
+        warp_to_slot(&mut svm, clock.slot + 20);
+    }
+    // Both outcomes were exercised
+    assert!(executable > 0 && blocked > 0);
+
+    // Under a global pause nothing is executable, matching the program
+    let update_ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            paused: Some(true),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[update_ix],
+        Some(&admin.pubkey()),
+        &[&admin],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let clock = get_clock(&svm);
+    let config = deserialize_config(&svm, &config_pubkey);
+    for (thread_pubkey, _) in &threads {
+        let thread = deserialize_thread(&svm, thread_pubkey);
+        assert!(!thread.is_executable(&clock, &config));
+    }
+}
+
 #[test]
 fn test_exec_thread_slot_trigger() {
     let (mut svm, admin, payer) = create_test_env();
@@ -609,6 +888,99 @@ fn test_exec_thread_forgo_commission() {
     assert_eq!(thread.exec_count, 1);
 }
 
+/// Execute a thread with commission forgone and return the lamport deltas of
+/// (thread, executor, admin) across the exec transaction.
+fn exec_forgone_balance_deltas(waive_core_team_on_forgo: bool, id: &str) -> (i64, i64, i64) {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let update_ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            waive_core_team_on_forgo: Some(waive_core_team_on_forgo),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[update_ix],
+        Some(&admin.pubkey()),
+        &[&admin],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        id,
+        Trigger::Immediate { jitter: 0 },
+        "test",
+        None,
+    );
+
+    let thread_before = get_balance(&svm, &thread_pubkey) as i64;
+    let executor_before = get_balance(&svm, &executor.pubkey()) as i64;
+    let admin_before = get_balance(&svm, &admin.pubkey()) as i64;
+
+    let remaining = build_remaining_accounts(&executor.pubkey());
+    let ix = build_exec_thread(
+        &executor.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        &config_pubkey,
+        &admin.pubkey(),
+        true, // forgo commission
+        0,
+        &remaining,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&executor.pubkey()),
+        &[&executor],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    (
+        get_balance(&svm, &thread_pubkey) as i64 - thread_before,
+        get_balance(&svm, &executor.pubkey()) as i64 - executor_before,
+        get_balance(&svm, &admin.pubkey()) as i64 - admin_before,
+    )
+}
+
+#[test]
+fn test_exec_thread_forgo_commission_balances_core_team_honored() {
+    let (thread_delta, executor_delta, admin_delta) =
+        exec_forgone_balance_deltas(false, "exec-forgo-honor");
+
+    // Default config: 1000 lamport commission, 10% core team -> 100 lamports.
+    // Executor's 5000 lamport signature fee is reimbursed, commission forgone.
+    assert_eq!(thread_delta, -(5000 + 100));
+    assert_eq!(executor_delta, 0);
+    assert_eq!(admin_delta, 100);
+}
+
+#[test]
+fn test_exec_thread_forgo_commission_balances_core_team_waived() {
+    let (thread_delta, executor_delta, admin_delta) =
+        exec_forgone_balance_deltas(true, "exec-forgo-waive");
+
+    // Core team fee waived together with the commission: only the
+    // reimbursement leaves the thread.
+    assert_eq!(thread_delta, -5000);
+    assert_eq!(executor_delta, 0);
+    assert_eq!(admin_delta, 0);
+}
+
 #[test]
 fn test_exec_thread_signal_close() {
     let (mut svm, admin, payer) = create_test_env();
@@ -868,179 +1240,198 @@ fn test_exec_thread_signal_chain() {
     );
 }
 
-#[test]
-fn test_exec_thread_signal_update_trigger() {
-    let (mut svm, admin, payer) = create_test_env();
-    let authority = Keypair::new();
-    let executor = Keypair::new();
-    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
-    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
-
-    let (config_pubkey, _) = config_pda();
-    let new_trigger = Trigger::Timestamp {
-        unix_ts: 999_999_999,
-        jitter: 0,
-    };
-
-    // Use Interval trigger, fiber will signal to change it to Timestamp
-    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
-        &mut svm,
-        &authority,
-        &payer,
-        &admin.pubkey(),
-        "exec-sig-trig",
+/// Create an Interval-triggered thread with one memo fiber per entry in
+/// `signals`, each returning the given signal. Returns (thread, fibers).
+fn setup_multi_fiber_thread(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    id: &str,
+    signals: Vec<Option<Signal>>,
+) -> (Pubkey, Vec<Pubkey>) {
+    let thread_id = ThreadId::Bytes(id.as_bytes().to_vec());
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        100_000_000, // extra for fiber creations + rent
+        thread_id,
         Trigger::Interval {
             seconds: 10,
             skippable: false,
             jitter: 0,
         },
-        "trigger-test",
-        Some(Signal::Update {
-            paused: None,
-            trigger: Some(new_trigger.clone()),
-            index: None,
-        }),
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
     );
+    svm.send_transaction(tx)
+        .expect("create_thread should succeed");
 
-    // Advance past interval
-    advance_clock(&mut svm, 15);
+    let mut fibers = Vec::new();
+    for (i, signal) in signals.into_iter().enumerate() {
+        let index = i as u8;
+        let (fiber_pubkey, _) = fiber_pda(&thread_pubkey, index);
+        let memo_ix = make_memo_instruction(&format!("fiber-{}", index), signal);
+        let serializable = make_serializable_instruction(&memo_ix);
+        let ix = build_create_fiber(
+            &authority.pubkey(),
+            &thread_pubkey,
+            &fiber_pubkey,
+            index,
+            serializable,
+            0,
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[payer, authority],
+            blockhash,
+        );
+        svm.send_transaction(tx)
+            .expect("create_fiber should succeed");
+        fibers.push(fiber_pubkey);
+    }
+
+    (thread_pubkey, fibers)
+}
 
+/// Send a single exec_thread for `fiber` at `fiber_cursor`.
+fn send_exec(
+    svm: &mut litesvm::LiteSVM,
+    executor: &Keypair,
+    admin: &Pubkey,
+    thread_pubkey: &Pubkey,
+    fiber_pubkey: &Pubkey,
+    fiber_cursor: u8,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
     let remaining = build_remaining_accounts(&executor.pubkey());
     let ix = build_exec_thread(
         &executor.pubkey(),
-        &thread_pubkey,
-        &fiber_pubkey,
+        thread_pubkey,
+        fiber_pubkey,
         &config_pubkey,
-        &admin.pubkey(),
+        admin,
         false,
-        0,
+        fiber_cursor,
         &remaining,
     );
     let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&executor.pubkey()),
-        &[&executor],
-        blockhash,
-    );
-    svm.send_transaction(tx).unwrap();
-
-    let thread = deserialize_thread(&svm, &thread_pubkey);
-    assert_eq!(thread.exec_count, 1);
-    assert!(!thread.paused, "Thread should not be paused");
-    assert_eq!(
-        thread.trigger, new_trigger,
-        "Trigger should be updated to Timestamp"
-    );
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&executor.pubkey()), &[executor], blockhash);
+    svm.send_transaction(tx).map(|_| ())
 }
 
-/// Test that exec works when the CPI target program_id also appears as a
-/// regular account in the instruction (simulates Anchor's None-placeholder
-/// collision where Option<UncheckedAccount>::None emits program_id as account).
 #[test]
-fn test_exec_with_program_id_in_accounts() {
-    let (mut svm, admin, payer) = create_test_env();
+fn test_exec_thread_signal_goto_forward() {
+    let (mut svm, admin, _payer) = create_test_env();
     let authority = Keypair::new();
     let executor = Keypair::new();
+    let payer = Keypair::new();
     svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
     svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
 
-    let (config_pubkey, _) = config_pda();
-
-    // Build a memo instruction where PROGRAM_ID appears as both the CPI target
-    // AND as an extra readonly account (simulating Anchor's None placeholder).
-    let payer_pubkey = solana_sdk::pubkey!("AntegenPayer1111111111111111111111111111111");
-    let memo_ix = build_thread_memo(&payer_pubkey, "collision-test", None);
+    // Fiber 0 skips fiber 1 and jumps straight to fiber 2
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-goto",
+        vec![Some(Signal::Goto { index: 2 }), None, None],
+    );
 
-    // Add PROGRAM_ID as an extra readonly non-signer account
-    let mut modified_accounts = memo_ix.accounts.clone();
-    modified_accounts.push(AccountMeta::new_readonly(PROGRAM_ID, false));
+    advance_clock(&mut svm, 15);
 
-    let collision_ix = solana_sdk::instruction::Instruction {
-        program_id: memo_ix.program_id, // = PROGRAM_ID
-        accounts: modified_accounts,
-        data: memo_ix.data.clone(),
-    };
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+    )
+    .expect("exec fiber 0 should succeed");
 
-    let serializable = make_serializable_instruction(&collision_ix);
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.fiber_signal, Signal::Goto { index: 2 });
 
-    // Create thread + fiber with this collision instruction
-    let thread_id = ThreadId::Bytes(b"exec-collision".to_vec());
-    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), b"exec-collision");
-    let ix = build_create_thread(
-        &authority.pubkey(),
-        &payer.pubkey(),
+    // The skipped fiber cannot be executed as the continuation
+    let result = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
         &thread_pubkey,
-        10_000_000,
-        thread_id,
-        Trigger::Immediate { jitter: 0 },
-        None,
-        None,
-        None,
+        &fibers[1],
+        1,
     );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&payer.pubkey()),
-        &[&payer, &authority],
-        blockhash,
+    assert!(
+        result.is_err(),
+        "Goto continuation must run the target fiber"
     );
-    svm.send_transaction(tx)
-        .expect("create_thread should succeed");
 
-    let (fiber_pubkey, _) = fiber_pda(&thread_pubkey, 0);
-    let ix = build_create_fiber(
-        &authority.pubkey(),
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
         &thread_pubkey,
-        &fiber_pubkey,
-        0,
-        serializable,
-        0,
-    );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&payer.pubkey()),
-        &[&payer, &authority],
-        blockhash,
+        &fibers[2],
+        2,
+    )
+    .expect("exec fiber 2 (goto target) should succeed");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 2, "Fiber 1 should have been skipped");
+    assert_eq!(thread.fiber_signal, Signal::None);
+    assert_eq!(thread.fiber_cursor, 0, "Cursor should wrap after fiber 2");
+}
+
+#[test]
+fn test_exec_thread_signal_goto_missing_index_fails() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-goto-missing",
+        vec![Some(Signal::Goto { index: 5 }), None],
     );
-    svm.send_transaction(tx)
-        .expect("create_fiber should succeed");
 
-    // Build remaining accounts: PROGRAM_ID (CPI target + collision account) + executor
-    let remaining = vec![
-        AccountMeta::new_readonly(PROGRAM_ID, false),
-        AccountMeta::new_readonly(executor.pubkey(), false),
-    ];
-    let ix = build_exec_thread(
-        &executor.pubkey(),
-        &thread_pubkey,
-        &fiber_pubkey,
-        &config_pubkey,
+    advance_clock(&mut svm, 15);
+
+    let result = send_exec(
+        &mut svm,
+        &executor,
         &admin.pubkey(),
-        false,
+        &thread_pubkey,
+        &fibers[0],
         0,
-        &remaining,
     );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&executor.pubkey()),
-        &[&executor],
-        blockhash,
+    assert!(
+        result.is_err(),
+        "Goto to a missing fiber should be rejected"
     );
-    svm.send_transaction(tx)
-        .expect("exec with program_id-as-account should succeed");
-
-    let thread = deserialize_thread(&svm, &thread_pubkey);
-    assert_eq!(thread.exec_count, 1);
 }
 
-/// Simulates the srsly rental_close inline-activation flow:
-/// Timestamp fires -> fiber 0 chains -> fiber 1 returns Signal::Update { paused: false, trigger: Interval }
-/// Thread should NOT be auto-paused because fiber 1 explicitly set paused: false.
 #[test]
-fn test_exec_timestamp_chain_then_update_unpause() {
+fn test_exec_thread_signal_goto_cycle_fails() {
     let (mut svm, admin, _payer) = create_test_env();
     let authority = Keypair::new();
     let executor = Keypair::new();
@@ -1049,103 +1440,72 @@ fn test_exec_timestamp_chain_then_update_unpause() {
     svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
     svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
 
-    let clock = get_clock(&svm);
-    let target_ts = clock.unix_timestamp + 10;
-    let (config_pubkey, _) = config_pda();
-
-    // Create thread with Timestamp trigger (simulates contract_process switching to Timestamp)
-    let thread_id = ThreadId::Bytes(b"ts-chain-unpause".to_vec());
-    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), b"ts-chain-unpause");
-    let ix = build_create_thread(
-        &authority.pubkey(),
-        &payer.pubkey(),
-        &thread_pubkey,
-        100_000_000,
-        thread_id,
-        Trigger::Timestamp {
-            unix_ts: target_ts,
-            jitter: 0,
-        },
-        None,
-        None,
-        None,
-    );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&payer.pubkey()),
-        &[&payer, &authority],
-        blockhash,
+    // Fiber 0 chains to fiber 1, which tries to jump back to fiber 0
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-goto-cycle",
+        vec![Some(Signal::Chain), Some(Signal::Goto { index: 0 })],
     );
-    svm.send_transaction(tx)
-        .expect("create_thread should succeed");
 
-    // Fiber 0: returns Signal::Chain (simulates contract_process seeing expired rental)
-    let (fiber0_pubkey, _) = fiber_pda(&thread_pubkey, 0);
-    let chain_ix = make_memo_instruction("controller", Some(Signal::Chain));
-    let serializable = make_serializable_instruction(&chain_ix);
-    let ix = build_create_fiber(
-        &authority.pubkey(),
+    advance_clock(&mut svm, 15);
+
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
         &thread_pubkey,
-        &fiber0_pubkey,
-        0,
-        serializable,
+        &fibers[0],
         0,
-    );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&payer.pubkey()),
-        &[&payer, &authority],
-        blockhash,
-    );
-    svm.send_transaction(tx)
-        .expect("create_fiber_0 should succeed");
+    )
+    .expect("exec fiber 0 should succeed");
 
-    // Fiber 1: returns Signal::Update { paused: false, trigger: Interval }
-    // (simulates rental_close activating queued rental and restaging with cron/interval trigger)
-    let new_trigger = Trigger::Interval {
-        seconds: 60,
-        skippable: false,
-        jitter: 0,
-    };
-    let (fiber1_pubkey, _) = fiber_pda(&thread_pubkey, 1);
-    let update_ix = make_memo_instruction(
-        "close-activate",
-        Some(Signal::Update {
-            paused: Some(false),
-            trigger: Some(new_trigger.clone()),
-            index: Some(0),
-        }),
-    );
-    let serializable = make_serializable_instruction(&update_ix);
-    let ix = build_create_fiber(
-        &authority.pubkey(),
+    let result = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
         &thread_pubkey,
-        &fiber1_pubkey,
+        &fibers[1],
         1,
-        serializable,
-        0,
     );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&payer.pubkey()),
-        &[&payer, &authority],
-        blockhash,
+    assert!(
+        result.is_err(),
+        "Goto back to a visited fiber should be rejected"
+    );
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+    assert_eq!(thread.fiber_signal, Signal::Chain);
+}
+
+#[test]
+fn test_exec_thread_return_data_captured() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    // The fiber is a thread_memo returning Signal::Repeat
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-return-data",
+        vec![Some(Signal::Repeat)],
     );
-    svm.send_transaction(tx)
-        .expect("create_fiber_1 should succeed");
 
-    // Advance past Timestamp target
     advance_clock(&mut svm, 15);
 
-    // Execute fiber 0 -> Signal::Chain
+    let (config_pubkey, _) = config_pda();
     let remaining = build_remaining_accounts(&executor.pubkey());
     let ix = build_exec_thread(
         &executor.pubkey(),
         &thread_pubkey,
-        &fiber0_pubkey,
+        &fibers[0],
         &config_pubkey,
         &admin.pubkey(),
         false,
@@ -1159,43 +1519,1277 @@ fn test_exec_timestamp_chain_then_update_unpause() {
         &[&executor],
         blockhash,
     );
-    svm.send_transaction(tx)
+    let meta = svm
+        .send_transaction(tx)
         .expect("exec fiber 0 should succeed");
 
+    // The client reads the fiber's return data back from the runtime's
+    // `Program return: <program> <base64>` log; Aw== is Signal::Repeat
+    assert_eq!(borsh::to_vec(&Signal::Repeat).unwrap(), vec![3]);
+    let expected = format!("Program return: {} Aw==", PROGRAM_ID);
+    assert!(meta.logs.iter().any(|l| *l == expected));
+
     let thread = deserialize_thread(&svm, &thread_pubkey);
-    assert_eq!(thread.fiber_signal, Signal::Chain);
+    assert_eq!(thread.fiber_signal, Signal::Repeat);
+}
 
-    // Execute fiber 1 (chained) -> Signal::Update { paused: false, trigger: Interval }
-    let remaining = build_remaining_accounts(&executor.pubkey());
-    let ix = build_exec_thread(
-        &executor.pubkey(),
-        &thread_pubkey,
-        &fiber1_pubkey,
-        &config_pubkey,
-        &admin.pubkey(),
-        false,
-        1,
-        &remaining,
-    );
-    let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new_signed_with_payer(
-        &[ix],
-        Some(&executor.pubkey()),
-        &[&executor],
-        blockhash,
-    );
-    svm.send_transaction(tx)
-        .expect("exec fiber 1 (chained) should succeed");
+/// Decode the `ThreadExecuted` events in a transaction's logs, in order.
+fn thread_executed_events(logs: &[String]) -> Vec<ThreadExecuted> {
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+    use base64::prelude::*;
+
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+        .filter_map(|bytes| {
+            let data = bytes.strip_prefix(ThreadExecuted::DISCRIMINATOR)?;
+            ThreadExecuted::deserialize(&mut &data[..]).ok()
+        })
+        .collect()
+}
 
-    let thread = deserialize_thread(&svm, &thread_pubkey);
-    assert_eq!(thread.exec_count, 2);
-    assert!(
-        !thread.paused,
-        "Thread must NOT be auto-paused when chained fiber explicitly set paused: false"
+#[test]
+fn test_exec_thread_emits_exec_sequence() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-sequence",
+        vec![Some(Signal::Chain), None],
+    );
+    let (config_pubkey, _) = config_pda();
+    let exec = |svm: &mut litesvm::LiteSVM, cursor: usize| -> Vec<ThreadExecuted> {
+        let remaining = build_remaining_accounts(&executor.pubkey());
+        let ix = build_exec_thread(
+            &executor.pubkey(),
+            &thread_pubkey,
+            &fibers[cursor],
+            &config_pubkey,
+            &admin.pubkey(),
+            false,
+            cursor as u8,
+            &remaining,
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&executor.pubkey()),
+            &[&executor],
+            blockhash,
+        );
+        let meta = svm.send_transaction(tx).expect("exec should succeed");
+        thread_executed_events(&meta.logs)
+    };
+
+    // One event and one increment per exec_thread, chained fiber included
+    advance_clock(&mut svm, 15);
+    let mut sequences = Vec::new();
+    for cursor in 0..2 {
+        let events = exec(&mut svm, cursor);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].thread, thread_pubkey);
+        assert_eq!(events[0].executor, executor.pubkey());
+        assert_eq!(events[0].fiber_index, cursor as u8);
+        sequences.push(events[0].exec_sequence);
+    }
+    assert_eq!(sequences, vec![1, 2]);
+
+    // The next scheduled run carries on from there
+    advance_clock(&mut svm, 15);
+    svm.expire_blockhash();
+    let events = exec(&mut svm, 0);
+    assert_eq!(events[0].exec_sequence, 3);
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).exec_count, 3);
+
+    let key = exec_key(&thread_pubkey, 3);
+    assert_eq!(&key[..32], thread_pubkey.as_ref());
+    assert_ne!(key, exec_key(&thread_pubkey, 2));
+}
+
+#[test]
+fn test_exec_thread_chain_cycle_fails() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-chain-cycle",
+        vec![Some(Signal::Chain), Some(Signal::Chain), None],
+    );
+
+    advance_clock(&mut svm, 15);
+
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+    )
+    .expect("exec fiber 0 should succeed");
+
+    // The executor picks a Chain continuation's cursor; running fiber 0 again
+    // in the same slot would loop the thread
+    svm.expire_blockhash();
+    let err = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+    )
+    .expect_err("Chain back to an executed fiber should be rejected");
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|l| l.contains("ChainCycleDetected")));
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+    assert_eq!(thread.fiber_signal, Signal::Chain);
+
+    // The forward continuation still runs
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[1],
+        1,
+    )
+    .expect("exec fiber 1 should succeed");
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).chain_depth, 2);
+}
+
+#[test]
+fn test_exec_thread_chain_depth_limit() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            max_chain_depth: Some(2),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+    svm.send_transaction(tx).unwrap();
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "exec-chain-depth",
+        vec![Some(Signal::Chain), Some(Signal::Chain), None],
+    );
+
+    advance_clock(&mut svm, 15);
+
+    for (cursor, fiber) in fibers.iter().take(2).enumerate() {
+        send_exec(
+            &mut svm,
+            &executor,
+            &admin.pubkey(),
+            &thread_pubkey,
+            fiber,
+            cursor as u8,
+        )
+        .expect("exec within the depth limit should succeed");
+    }
+
+    // A third fiber in the same slot exceeds the limit
+    let err = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[2],
+        2,
+    )
+    .expect_err("chain past max_chain_depth should be rejected");
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|l| l.contains("ChainDepthExceeded")));
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).exec_count, 2);
+
+    // The chain resumes in a later slot
+    let slot = get_clock(&svm).slot;
+    warp_to_slot(&mut svm, slot + 1);
+    svm.expire_blockhash();
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[2],
+        2,
+    )
+    .expect("chain should continue in the next slot");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 3);
+    assert_eq!(thread.fiber_signal, Signal::None);
+}
+
+#[test]
+fn test_exec_thread_signal_update_trigger() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+    let new_trigger = Trigger::Timestamp {
+        unix_ts: 999_999_999,
+        jitter: 0,
+    };
+
+    // Use Interval trigger, fiber will signal to change it to Timestamp
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-sig-trig",
+        Trigger::Interval {
+            seconds: 10,
+            skippable: false,
+            jitter: 0,
+        },
+        "trigger-test",
+        Some(Signal::Update {
+            paused: None,
+            trigger: Some(new_trigger.clone()),
+            index: None,
+        }),
     );
+
+    // Advance past interval
+    advance_clock(&mut svm, 15);
+
+    let remaining = build_remaining_accounts(&executor.pubkey());
+    let ix = build_exec_thread(
+        &executor.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        &config_pubkey,
+        &admin.pubkey(),
+        false,
+        0,
+        &remaining,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&executor.pubkey()),
+        &[&executor],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+    assert!(!thread.paused, "Thread should not be paused");
     assert_eq!(
         thread.trigger, new_trigger,
-        "Trigger should be updated to Interval"
+        "Trigger should be updated to Timestamp"
     );
-    assert_eq!(thread.fiber_cursor, 0, "Cursor should be reset to 0");
+}
+
+/// Test that exec works when the CPI target program_id also appears as a
+/// regular account in the instruction (simulates Anchor's None-placeholder
+/// collision where Option<UncheckedAccount>::None emits program_id as account).
+#[test]
+fn test_exec_with_program_id_in_accounts() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (config_pubkey, _) = config_pda();
+
+    // Build a memo instruction where PROGRAM_ID appears as both the CPI target
+    // AND as an extra readonly account (simulating Anchor's None placeholder).
+    let payer_pubkey = solana_sdk::pubkey!("AntegenPayer1111111111111111111111111111111");
+    let memo_ix = build_thread_memo(&payer_pubkey, "collision-test", None);
+
+    // Add PROGRAM_ID as an extra readonly non-signer account
+    let mut modified_accounts = memo_ix.accounts.clone();
+    modified_accounts.push(AccountMeta::new_readonly(PROGRAM_ID, false));
+
+    let collision_ix = Instruction {
+        program_id: memo_ix.program_id, // = PROGRAM_ID
+        accounts: modified_accounts,
+        data: memo_ix.data.clone(),
+    };
+
+    let serializable = make_serializable_instruction(&collision_ix);
+
+    // Create thread + fiber with this collision instruction
+    let thread_id = ThreadId::Bytes(b"exec-collision".to_vec());
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), b"exec-collision");
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        10_000_000,
+        thread_id,
+        Trigger::Immediate { jitter: 0 },
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("create_thread should succeed");
+
+    let (fiber_pubkey, _) = fiber_pda(&thread_pubkey, 0);
+    let ix = build_create_fiber(
+        &authority.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        0,
+        serializable,
+        0,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("create_fiber should succeed");
+
+    // Build remaining accounts: PROGRAM_ID (CPI target + collision account) + executor
+    let remaining = vec![
+        AccountMeta::new_readonly(PROGRAM_ID, false),
+        AccountMeta::new_readonly(executor.pubkey(), false),
+    ];
+    let ix = build_exec_thread(
+        &executor.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        &config_pubkey,
+        &admin.pubkey(),
+        false,
+        0,
+        &remaining,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&executor.pubkey()),
+        &[&executor],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("exec with program_id-as-account should succeed");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+}
+
+/// Simulates the srsly rental_close inline-activation flow:
+/// Timestamp fires -> fiber 0 chains -> fiber 1 returns Signal::Update { paused: false, trigger: Interval }
+/// Thread should NOT be auto-paused because fiber 1 explicitly set paused: false.
+#[test]
+fn test_exec_timestamp_chain_then_update_unpause() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let clock = get_clock(&svm);
+    let target_ts = clock.unix_timestamp + 10;
+    let (config_pubkey, _) = config_pda();
+
+    // Create thread with Timestamp trigger (simulates contract_process switching to Timestamp)
+    let thread_id = ThreadId::Bytes(b"ts-chain-unpause".to_vec());
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), b"ts-chain-unpause");
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        100_000_000,
+        thread_id,
+        Trigger::Timestamp {
+            unix_ts: target_ts,
+            jitter: 0,
+        },
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("create_thread should succeed");
+
+    // Fiber 0: returns Signal::Chain (simulates contract_process seeing expired rental)
+    let (fiber0_pubkey, _) = fiber_pda(&thread_pubkey, 0);
+    let chain_ix = make_memo_instruction("controller", Some(Signal::Chain));
+    let serializable = make_serializable_instruction(&chain_ix);
+    let ix = build_create_fiber(
+        &authority.pubkey(),
+        &thread_pubkey,
+        &fiber0_pubkey,
+        0,
+        serializable,
+        0,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("create_fiber_0 should succeed");
+
+    // Fiber 1: returns Signal::Update { paused: false, trigger: Interval }
+    // (simulates rental_close activating queued rental and restaging with cron/interval trigger)
+    let new_trigger = Trigger::Interval {
+        seconds: 60,
+        skippable: false,
+        jitter: 0,
+    };
+    let (fiber1_pubkey, _) = fiber_pda(&thread_pubkey, 1);
+    let update_ix = make_memo_instruction(
+        "close-activate",
+        Some(Signal::Update {
+            paused: Some(false),
+            trigger: Some(new_trigger.clone()),
+            index: Some(0),
+        }),
+    );
+    let serializable = make_serializable_instruction(&update_ix);
+    let ix = build_create_fiber(
+        &authority.pubkey(),
+        &thread_pubkey,
+        &fiber1_pubkey,
+        1,
+        serializable,
+        0,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("create_fiber_1 should succeed");
+
+    // Advance past Timestamp target
+    advance_clock(&mut svm, 15);
+
+    // Execute fiber 0 -> Signal::Chain
+    let remaining = build_remaining_accounts(&executor.pubkey());
+    let ix = build_exec_thread(
+        &executor.pubkey(),
+        &thread_pubkey,
+        &fiber0_pubkey,
+        &config_pubkey,
+        &admin.pubkey(),
+        false,
+        0,
+        &remaining,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&executor.pubkey()),
+        &[&executor],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("exec fiber 0 should succeed");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.fiber_signal, Signal::Chain);
+
+    // Execute fiber 1 (chained) -> Signal::Update { paused: false, trigger: Interval }
+    let remaining = build_remaining_accounts(&executor.pubkey());
+    let ix = build_exec_thread(
+        &executor.pubkey(),
+        &thread_pubkey,
+        &fiber1_pubkey,
+        &config_pubkey,
+        &admin.pubkey(),
+        false,
+        1,
+        &remaining,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&executor.pubkey()),
+        &[&executor],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("exec fiber 1 (chained) should succeed");
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 2);
+    assert!(
+        !thread.paused,
+        "Thread must NOT be auto-paused when chained fiber explicitly set paused: false"
+    );
+    assert_eq!(
+        thread.trigger, new_trigger,
+        "Trigger should be updated to Interval"
+    );
+    assert_eq!(thread.fiber_cursor, 0, "Cursor should be reset to 0");
+}
+
+/// Switch the config default to thread-reimbursed fees.
+fn set_thread_reimburses(svm: &mut litesvm::LiteSVM, admin: &Keypair) {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            fee_payer_mode: Some(FeePayerMode::ThreadReimburses),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test]
+fn test_exec_thread_reimburses_base_fee() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    set_thread_reimburses(&mut svm, &admin);
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-reimburse",
+        Trigger::Immediate { jitter: 0 },
+        "test",
+        None,
+    );
+
+    let thread_before = get_balance(&svm, &thread_pubkey) as i64;
+    let executor_before = get_balance(&svm, &executor.pubkey()) as i64;
+
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        0,
+    )
+    .expect("exec should succeed");
+
+    // Base fee (5000) + executor commission (900) + core team fee (100)
+    assert_eq!(
+        get_balance(&svm, &thread_pubkey) as i64 - thread_before,
+        -(5000 + 900 + 100)
+    );
+    // Signature fee refunded, commission kept
+    assert_eq!(
+        get_balance(&svm, &executor.pubkey()) as i64 - executor_before,
+        900
+    );
+}
+
+#[test]
+fn test_exec_thread_reimburse_rent_exemption_fails() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    set_thread_reimburses(&mut svm, &admin);
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-reimburse-poor",
+        Trigger::Immediate { jitter: 0 },
+        "test",
+        None,
+    );
+
+    // Leave the thread 3000 lamports above rent exemption: short of the 6000 payout
+    let data_len = svm.get_account(&thread_pubkey).unwrap().data.len();
+    let rent_minimum = svm.minimum_balance_for_rent_exemption(data_len);
+    let excess = get_balance(&svm, &thread_pubkey) - rent_minimum - 3000;
+    let ix = build_withdraw_thread(
+        &authority.pubkey(),
+        &authority.pubkey(),
+        &thread_pubkey,
+        excess,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).expect("withdraw should succeed");
+
+    let result = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        0,
+    );
+    assert!(
+        result.is_err(),
+        "Thread that cannot stay rent-exempt must not reimburse"
+    );
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).exec_count, 0);
+
+    // A per-thread ExecutorPays override skips the reimbursement check
+    let ix = build_update_thread(
+        &authority.pubkey(),
+        &thread_pubkey,
+        ThreadUpdateParams {
+            fee_payer_mode: Some(Some(FeePayerMode::ExecutorPays)),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).expect("update should succeed");
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).fee_payer_mode,
+        Some(FeePayerMode::ExecutorPays)
+    );
+}
+
+#[test]
+fn test_exec_thread_streak_discount() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    // Reimbursed fees make the executor's balance change exactly its commission;
+    // a half-life of 1 halves the commission with every exec of the streak, and
+    // a 1s decay closes the commission window 6s after the thread is due
+    let (config_pubkey, _) = config_pda();
+    let ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            fee_payer_mode: Some(FeePayerMode::ThreadReimburses),
+            fee_decay_seconds: Some(1),
+            streak_half_life_execs: Some(1),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+    svm.send_transaction(tx).unwrap();
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-streak",
+        Trigger::Interval {
+            seconds: 30,
+            skippable: false,
+            jitter: 0,
+        },
+        "test",
+        None,
+    );
+
+    // Advance until `late` seconds after the thread becomes due
+    let advance_to_due = |svm: &mut litesvm::LiteSVM, late: i64| {
+        let clock = get_clock(svm);
+        let next = deserialize_thread(svm, &thread_pubkey)
+            .next_trigger_at(clock.unix_timestamp, clock.slot)
+            .unwrap();
+        advance_clock(svm, next - clock.unix_timestamp + late);
+        svm.expire_blockhash();
+    };
+    // Execute `late` seconds after the thread becomes due; returns the commission
+    let exec_after = |svm: &mut litesvm::LiteSVM, late: i64| -> i64 {
+        advance_to_due(svm, late);
+        let before = get_balance(svm, &executor.pubkey()) as i64;
+        send_exec(
+            svm,
+            &executor,
+            &admin.pubkey(),
+            &thread_pubkey,
+            &fiber_pubkey,
+            0,
+        )
+        .expect("exec should succeed");
+        get_balance(svm, &executor.pubkey()) as i64 - before
+    };
+
+    // On time: 900 * 0.5^n for n = 0, 1, 2, 3
+    let commissions: Vec<i64> = (0..4).map(|_| exec_after(&mut svm, 0)).collect();
+    assert_eq!(commissions, vec![900, 450, 225, 112]);
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).consecutive_execs,
+        4
+    );
+
+    // A failure report needs the thread left unexecuted past the commission
+    // window, but is no proof that an exec failed and leaves the streak alone
+    advance_to_due(&mut svm, 7);
+    send_report(&mut svm, &executor, &thread_pubkey, 0).expect("report should succeed");
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).consecutive_execs,
+        4
+    );
+
+    // An exec past the commission window earns nothing and shows the thread
+    // went unexecuted, so the streak restarts with it
+    assert_eq!(exec_after(&mut svm, 7), 0);
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).consecutive_execs,
+        1
+    );
+    assert_eq!(exec_after(&mut svm, 0), 450);
+}
+
+/// Exec with the fiber writable and the Fiber Program passed, opting in to
+/// recording the fiber's compute estimate.
+fn send_exec_recording_estimate(
+    svm: &mut litesvm::LiteSVM,
+    executor: &Keypair,
+    admin: &Pubkey,
+    thread_pubkey: &Pubkey,
+    fiber_pubkey: &Pubkey,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
+    let mut remaining = build_remaining_accounts(&executor.pubkey());
+    remaining.push(AccountMeta::new_readonly(FIBER_PROGRAM_ID, false));
+    let mut ix = build_exec_thread(
+        &executor.pubkey(),
+        thread_pubkey,
+        fiber_pubkey,
+        &config_pubkey,
+        admin,
+        false,
+        0,
+        &remaining,
+    );
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == *fiber_pubkey {
+            meta.is_writable = true;
+        }
+    }
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&executor.pubkey()), &[executor], blockhash);
+    svm.send_transaction(tx).map(|_| ())
+}
+
+#[test]
+fn test_exec_thread_records_compute_estimate_once() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-cu",
+        Trigger::Interval {
+            seconds: 30,
+            skippable: false,
+            jitter: 0,
+        },
+        "test",
+        None,
+    );
+    assert_eq!(
+        deserialize_fiber(&svm, &fiber_pubkey).estimated_compute_units,
+        None
+    );
+
+    // A read-only fiber is never written
+    advance_clock(&mut svm, 35);
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        0,
+    )
+    .expect("exec should succeed");
+    assert_eq!(
+        deserialize_fiber(&svm, &fiber_pubkey).estimated_compute_units,
+        None
+    );
+
+    advance_clock(&mut svm, 35);
+    send_exec_recording_estimate(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+    )
+    .expect("exec should succeed");
+    let estimate = deserialize_fiber(&svm, &fiber_pubkey)
+        .estimated_compute_units
+        .expect("estimate should be recorded");
+    assert!(estimate > 0);
+
+    // Later execs reuse the stored estimate instead of measuring again
+    advance_clock(&mut svm, 35);
+    svm.expire_blockhash();
+    send_exec_recording_estimate(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+    )
+    .expect("exec should succeed");
+    let fiber = deserialize_fiber(&svm, &fiber_pubkey);
+    assert_eq!(fiber.estimated_compute_units, Some(estimate));
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).exec_count, 3);
+}
+
+/// Rewrite `fiber` as a version 1 program would have left it: the version 1
+/// layout at the version 1 size, followed by bytes that would read back as a
+/// compute estimate in the current layout.
+fn write_version_1_fiber(svm: &mut litesvm::LiteSVM, fiber: &Pubkey) {
+    let current = deserialize_fiber(svm, fiber);
+    let legacy = FiberVersionedStateV1 {
+        version: 1,
+        thread: current.thread,
+        compiled_instruction: current.compiled_instruction,
+        last_executed: current.last_executed,
+        exec_count: current.exec_count,
+        priority_fee: current.priority_fee,
+        lookup_tables: current.lookup_tables,
+    };
+    let space = 8 + FiberVersionedStateV1::INIT_SPACE;
+    let mut data = FiberVersionedState::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data.push(1);
+    data.extend_from_slice(&7u32.to_le_bytes());
+    data.resize(space, 0);
+
+    let mut account = svm.get_account(fiber).unwrap();
+    account.lamports = svm.minimum_balance_for_rent_exemption(space);
+    account.data = data;
+    svm.set_account(*fiber, account).unwrap();
+}
+
+#[test]
+fn test_exec_thread_records_estimate_on_version_1_fiber() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-cu-v1",
+        Trigger::Interval {
+            seconds: 30,
+            skippable: false,
+            jitter: 0,
+        },
+        "test",
+        None,
+    );
+    let compiled = deserialize_fiber(&svm, &fiber_pubkey).compiled_instruction;
+    write_version_1_fiber(&mut svm, &fiber_pubkey);
+
+    // The bytes after the version 1 layout are not an estimate
+    assert_eq!(
+        deserialize_fiber(&svm, &fiber_pubkey).estimated_compute_units,
+        None
+    );
+
+    // Recording grows the fiber to the current layout, the thread paying the rent
+    advance_clock(&mut svm, 35);
+    send_exec_recording_estimate(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+    )
+    .expect("exec should succeed");
+
+    let space = 8 + FiberVersionedState::INIT_SPACE;
+    let account = svm.get_account(&fiber_pubkey).unwrap();
+    assert_eq!(account.data.len(), space);
+    assert_eq!(
+        account.lamports,
+        svm.minimum_balance_for_rent_exemption(space)
+    );
+    let fiber = deserialize_fiber(&svm, &fiber_pubkey);
+    assert_eq!(fiber.version, 2);
+    assert_eq!(fiber.compiled_instruction, compiled);
+    assert!(fiber.estimated_compute_units.is_some_and(|units| units > 0));
+}
+
+#[test]
+fn test_reset_fiber_compute_estimate() {
+    let (mut svm, admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let (thread_pubkey, fiber_pubkey) = setup_exec_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        &admin.pubkey(),
+        "exec-cu-reset",
+        Trigger::Interval {
+            seconds: 30,
+            skippable: false,
+            jitter: 0,
+        },
+        "test",
+        None,
+    );
+    advance_clock(&mut svm, 35);
+    send_exec_recording_estimate(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+    )
+    .expect("exec should succeed");
+    assert!(deserialize_fiber(&svm, &fiber_pubkey)
+        .estimated_compute_units
+        .is_some());
+
+    let send = |svm: &mut litesvm::LiteSVM, ix: Instruction, signer: &Keypair| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer, signer],
+            blockhash,
+        );
+        svm.send_transaction(tx).map(|_| ())
+    };
+
+    // Updating the instruction leaves the estimate in place
+    let memo_ix = make_memo_instruction("different work", None);
+    let ix = build_update_fiber(
+        &authority.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+        0,
+        make_serializable_instruction(&memo_ix),
+        None,
+        false,
+    );
+    send(&mut svm, ix, &authority).expect("update_fiber should succeed");
+    assert!(deserialize_fiber(&svm, &fiber_pubkey)
+        .estimated_compute_units
+        .is_some());
+
+    // Only the thread authority can reset it
+    let ix =
+        build_reset_fiber_compute_estimate(&executor.pubkey(), &thread_pubkey, &fiber_pubkey, 0);
+    assert!(send(&mut svm, ix, &executor).is_err());
+
+    let ix =
+        build_reset_fiber_compute_estimate(&authority.pubkey(), &thread_pubkey, &fiber_pubkey, 0);
+    send(&mut svm, ix, &authority).expect("reset should succeed");
+    assert_eq!(
+        deserialize_fiber(&svm, &fiber_pubkey).estimated_compute_units,
+        None
+    );
+
+    // The next exec measures the new instruction
+    advance_clock(&mut svm, 35);
+    send_exec_recording_estimate(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fiber_pubkey,
+    )
+    .expect("exec should succeed");
+    assert!(deserialize_fiber(&svm, &fiber_pubkey)
+        .estimated_compute_units
+        .is_some());
+}
+
+/// Send a report_fiber_failure for `fiber_cursor`.
+fn send_report(
+    svm: &mut litesvm::LiteSVM,
+    executor: &Keypair,
+    thread_pubkey: &Pubkey,
+    fiber_cursor: u8,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_report_fiber_failure(
+        &executor.pubkey(),
+        thread_pubkey,
+        &config_pubkey,
+        fiber_cursor,
+    );
+    svm.expire_blockhash();
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&executor.pubkey()), &[executor], blockhash);
+    svm.send_transaction(tx).map(|_| ())
+}
+
+fn set_max_fiber_failures(svm: &mut litesvm::LiteSVM, admin: &Keypair, max_fiber_failures: u8) {
+    let (config_pubkey, _) = config_pda();
+    let ix = build_update_config(
+        &admin.pubkey(),
+        &config_pubkey,
+        ConfigUpdateParams {
+            max_fiber_failures: Some(max_fiber_failures),
+            ..Default::default()
+        },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+    svm.send_transaction(tx).unwrap();
+}
+
+#[test]
+fn test_report_fiber_failure_disables_fiber() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+    set_max_fiber_failures(&mut svm, &admin, 2);
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "report-disable",
+        vec![None, None],
+    );
+
+    // Unexecuted for longer than the 300s commission window
+    advance_clock(&mut svm, 315);
+    let slot = get_clock(&svm).slot;
+
+    send_report(&mut svm, &executor, &thread_pubkey, 0).expect("first report should succeed");
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.failing_fiber, 0);
+    assert_eq!(thread.fiber_failures, 1);
+    assert!(!thread.is_fiber_disabled(0));
+
+    // Reports are spaced a commission window (750 slots) apart
+    warp_to_slot(&mut svm, slot + 700);
+    let err = send_report(&mut svm, &executor, &thread_pubkey, 0)
+        .expect_err("report within the commission window should be rejected");
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|l| l.contains("InvalidFailureReport")));
+
+    warp_to_slot(&mut svm, slot + 750);
+    send_report(&mut svm, &executor, &thread_pubkey, 0).expect("second report should succeed");
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert!(thread.is_fiber_disabled(0));
+    assert_eq!(thread.disabled_fiber_ids(), vec![0]);
+    assert_eq!(thread.fiber_failures, 0);
+    assert_eq!(thread.fiber_cursor, 1, "Cursor should move past the fiber");
+
+    let err = send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+    )
+    .expect_err("disabled fiber should not execute");
+    assert!(err.meta.logs.iter().any(|l| l.contains("FiberDisabled")));
+
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[1],
+        1,
+    )
+    .expect("other fibers keep running");
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.exec_count, 1);
+    assert_eq!(
+        thread.fiber_cursor, 1,
+        "Cursor should skip the disabled fiber"
+    );
+
+    // The owner re-enables the fiber by updating it
+    let memo_ix = make_memo_instruction("fiber-0", None);
+    let ix = build_update_fiber(
+        &authority.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+        make_serializable_instruction(&memo_ix),
+        None,
+        false,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+    svm.send_transaction(tx)
+        .expect("update_fiber should succeed");
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert!(!thread.is_fiber_disabled(0));
+    assert!(thread.disabled_fiber_ids().is_empty());
+}
+
+#[test]
+fn test_report_fiber_failure_rejected() {
+    let (mut svm, admin, _payer) = create_test_env();
+    let authority = Keypair::new();
+    let executor = Keypair::new();
+    let payer = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&executor.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&payer.pubkey(), DEFAULT_AIRDROP * 2).unwrap();
+
+    let (thread_pubkey, fibers) = setup_multi_fiber_thread(
+        &mut svm,
+        &authority,
+        &payer,
+        "report-rejected",
+        vec![None, None],
+    );
+    let rejected = |svm: &mut litesvm::LiteSVM, fiber_cursor: u8, error: &str| {
+        let err = send_report(svm, &executor, &thread_pubkey, fiber_cursor)
+            .expect_err("report should be rejected");
+        assert!(
+            err.meta.logs.iter().any(|l| l.contains(error)),
+            "expected {}",
+            error
+        );
+    };
+
+    advance_clock(&mut svm, 15);
+
+    set_max_fiber_failures(&mut svm, &admin, 3);
+
+    // A thread merely due is not stuck: executors still have the commission
+    // window to run it
+    rejected(&mut svm, 0, "InvalidFailureReport");
+
+    // Only the fiber the thread is waiting on can be reported
+    advance_clock(&mut svm, 300);
+    rejected(&mut svm, 1, "InvalidFailureReport");
+    send_report(&mut svm, &executor, &thread_pubkey, 0).expect("report should succeed");
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).fiber_failures, 1);
+
+    // A successful exec clears the count, and the thread is no longer due
+    send_exec(
+        &mut svm,
+        &executor,
+        &admin.pubkey(),
+        &thread_pubkey,
+        &fibers[0],
+        0,
+    )
+    .expect("exec should succeed");
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).fiber_failures, 0);
+
+    let slot = get_clock(&svm).slot;
+    warp_to_slot(&mut svm, slot + 100);
+    rejected(&mut svm, 1, "TriggerConditionFailed");
 }
@@ -64,6 +64,18 @@ fn test_thread_memo_signal_next() {
     .unwrap();
 }
 
+#[test]
+fn test_thread_memo_signal_goto() {
+    let (mut svm, _admin, payer) = create_test_env();
+    send_memo(
+        &mut svm,
+        &payer,
+        "signal-goto",
+        Some(Signal::Goto { index: 2 }),
+    )
+    .unwrap();
+}
+
 #[test]
 fn test_thread_memo_no_signal() {
     let (mut svm, _admin, payer) = create_test_env();
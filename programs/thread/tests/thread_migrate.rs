@@ -0,0 +1,238 @@
+use anchor_lang::{AccountDeserialize, AnchorSerialize, Discriminator, Space};
+use antegen_thread_program::state::{Thread, ThreadV1, CURRENT_THREAD_VERSION};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::*;
+
+/// Size of a thread account allocated with the current layout
+const THREAD_SPACE: usize = 8 + Thread::INIT_SPACE;
+/// Size of a thread account allocated by version 1 of the program
+const V1_THREAD_SPACE: usize = 8 + ThreadV1::INIT_SPACE;
+
+fn create_thread(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    id: &str,
+) -> Pubkey {
+    let thread_id = ThreadId::Bytes(id.as_bytes().to_vec());
+    let (thread_pubkey, _) = thread_pda(&authority.pubkey(), id.as_bytes());
+    let ix = build_create_thread(
+        &authority.pubkey(),
+        &payer.pubkey(),
+        &thread_pubkey,
+        1_000_000,
+        thread_id,
+        Trigger::Interval {
+            seconds: 60,
+            skippable: false,
+            jitter: 0,
+        },
+        None,
+        None,
+        None,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).unwrap();
+    thread_pubkey
+}
+
+/// Rewrite a thread as version 1 of the program wrote it: the version 1
+/// layout in a version 1 sized allocation, the tail past the data filled
+/// with `tail`.
+fn make_v1(svm: &mut litesvm::LiteSVM, thread: &Pubkey, tail: u8) {
+    let current = deserialize_thread(svm, thread);
+    let legacy = ThreadV1 {
+        version: 1,
+        bump: current.bump,
+        authority: current.authority,
+        id: current.id,
+        name: current.name,
+        created_at: current.created_at,
+        trigger: current.trigger,
+        schedule: current.schedule,
+        fiber_ids: current.fiber_ids,
+        fiber_cursor: current.fiber_cursor,
+        fiber_next_id: current.fiber_next_id,
+        fiber_signal: current.fiber_signal,
+        paused: current.paused,
+        // Executions under version 1
+        exec_count: 7,
+        last_executor: current.last_executor,
+        nonce_account: current.nonce_account,
+        last_nonce: current.last_nonce,
+        close_fiber: current.close_fiber,
+    };
+    let mut data = Thread::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data.resize(V1_THREAD_SPACE, tail);
+
+    let mut account = svm.get_account(thread).unwrap();
+    account.lamports = svm.minimum_balance_for_rent_exemption(V1_THREAD_SPACE);
+    account.data = data;
+    svm.set_account(*thread, account).unwrap();
+}
+
+fn send_migrate(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    thread: &Pubkey,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let ix = build_migrate_thread(&authority.pubkey(), thread);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).map(|_| ())
+}
+
+#[test]
+fn test_thread_migrate_legacy_account() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "tm-legacy");
+    let before = deserialize_thread(&svm, &thread_pubkey);
+    make_v1(&mut svm, &thread_pubkey, 0);
+    assert!(V1_THREAD_SPACE < THREAD_SPACE);
+
+    send_migrate(&mut svm, &authority, &thread_pubkey).unwrap();
+
+    let account = svm.get_account(&thread_pubkey).unwrap();
+    assert_eq!(account.data.len(), THREAD_SPACE);
+    assert!(account.lamports >= svm.minimum_balance_for_rent_exemption(THREAD_SPACE));
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.version, CURRENT_THREAD_VERSION);
+    assert_eq!(thread.fee_payer_mode, None);
+    assert_eq!(thread.consecutive_execs, 0);
+    assert_eq!(thread.disabled_fibers, [0; 4]);
+    assert_eq!(thread.resume_at, None);
+    // The exec sequence continues from the executions so far
+    assert_eq!(thread.exec_count, 7);
+    // Existing state carried over untouched
+    assert_eq!(thread.id, before.id);
+    assert_eq!(thread.name, before.name);
+    assert_eq!(thread.trigger, before.trigger);
+    assert_eq!(thread.schedule, before.schedule);
+    assert_eq!(thread.authority, before.authority);
+    assert_eq!(thread.fiber_ids, before.fiber_ids);
+    assert_eq!(thread.close_fiber, before.close_fiber);
+}
+
+#[test]
+fn test_thread_migrate_legacy_account_with_stale_tail() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "tm-stale");
+    let before = deserialize_thread(&svm, &thread_pubkey);
+    // Bytes left past the data by an earlier, longer write
+    make_v1(&mut svm, &thread_pubkey, 0xff);
+
+    // The stale tail does not read as the fields version 2 appends
+    let account = svm.get_account(&thread_pubkey).unwrap();
+    assert!(Thread::try_deserialize(&mut account.data.as_slice()).is_err());
+
+    send_migrate(&mut svm, &authority, &thread_pubkey).unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.version, CURRENT_THREAD_VERSION);
+    assert_eq!(thread.fee_payer_mode, None);
+    assert_eq!(thread.resume_at, None);
+    assert_eq!(thread.name, before.name);
+}
+
+#[test]
+fn test_thread_migrate_current_account_is_noop() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "tm-current");
+    let before = svm.get_account(&thread_pubkey).unwrap();
+
+    send_migrate(&mut svm, &authority, &thread_pubkey).unwrap();
+
+    let after = svm.get_account(&thread_pubkey).unwrap();
+    assert_eq!(after.data, before.data);
+    assert_eq!(after.lamports, before.lamports);
+}
+
+#[test]
+fn test_thread_migrate_authority_only() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let bad_authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&bad_authority.pubkey(), DEFAULT_AIRDROP)
+        .unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "tm-auth");
+    make_v1(&mut svm, &thread_pubkey, 0);
+
+    let result = send_migrate(&mut svm, &bad_authority, &thread_pubkey);
+    assert!(result.is_err());
+    let account = svm.get_account(&thread_pubkey).unwrap();
+    assert_eq!(account.data.len(), V1_THREAD_SPACE);
+    assert_eq!(account.data[8], 1);
+}
+
+#[test]
+fn test_unmigrated_thread_rejected_until_migrated() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread(&mut svm, &authority, &payer, "tm-gate");
+    make_v1(&mut svm, &thread_pubkey, 0);
+
+    let send_update = |svm: &mut litesvm::LiteSVM| {
+        let ix = build_update_thread(
+            &authority.pubkey(),
+            &thread_pubkey,
+            ThreadUpdateParams {
+                paused: Some(true),
+                ..Default::default()
+            },
+        );
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            blockhash,
+        );
+        svm.send_transaction(tx)
+    };
+
+    // Writing version 2 fields into a version 1 account would be lost on migrate
+    let err = send_update(&mut svm).expect_err("unmigrated thread should be rejected");
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|l| l.contains("ThreadNotMigrated")));
+    assert_eq!(svm.get_account(&thread_pubkey).unwrap().data[8], 1);
+
+    send_migrate(&mut svm, &authority, &thread_pubkey).unwrap();
+    send_update(&mut svm).expect("migrated thread should update");
+    assert!(deserialize_thread(&svm, &thread_pubkey).paused);
+}
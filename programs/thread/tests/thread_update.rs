@@ -128,6 +128,86 @@ fn test_thread_update_unpause() {
     assert!(!deserialize_thread(&svm, &thread_pubkey).paused);
 }
 
+#[test]
+fn test_thread_update_pause_until() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-pause-until",
+        Trigger::Immediate { jitter: 0 },
+    );
+    let resume_at = get_clock(&svm).unix_timestamp + 3600;
+    send_update(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams {
+            paused: Some(true),
+            resume_at: Some(resume_at),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert!(thread.paused);
+    assert_eq!(thread.resume_at, Some(resume_at));
+
+    // Unpausing drops the pending resume time
+    send_update(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams {
+            paused: Some(false),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert!(!thread.paused);
+    assert_eq!(thread.resume_at, None);
+}
+
+#[test]
+fn test_thread_update_resume_at_requires_pause() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-resume-unpaused",
+        Trigger::Immediate { jitter: 0 },
+    );
+    let err = send_update(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams {
+            resume_at: Some(get_clock(&svm).unix_timestamp + 3600),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|l| l.contains("InvalidThreadState")));
+    assert_eq!(deserialize_thread(&svm, &thread_pubkey).resume_at, None);
+}
+
 #[test]
 fn test_thread_update_trigger_immediate() {
     let (mut svm, _admin, payer) = create_test_env();
@@ -396,6 +476,7 @@ fn test_thread_update_both_params() {
                 skippable: false,
                 jitter: 0,
             }),
+            ..Default::default()
         },
     )
     .unwrap();
@@ -487,6 +568,7 @@ fn test_thread_update_trigger_with_explicit_pause_stays_paused() {
                 unix_ts: 1900000000,
                 jitter: 0,
             }),
+            ..Default::default()
         },
     )
     .unwrap();
@@ -496,3 +578,224 @@ fn test_thread_update_trigger_with_explicit_pause_stays_paused() {
         "Thread should stay paused when paused is explicitly set"
     );
 }
+
+// ============================================================================
+// update_thread_atomic
+// ============================================================================
+
+fn memo_fiber(memo: &str) -> FiberCreateParams {
+    FiberCreateParams {
+        instruction: make_serializable_instruction(&make_memo_instruction(memo, None)),
+        priority_fee: 0,
+        lookup_tables: Vec::new(),
+    }
+}
+
+fn send_update_atomic(
+    svm: &mut litesvm::LiteSVM,
+    authority: &Keypair,
+    payer: &Keypair,
+    thread: &Pubkey,
+    params: ThreadUpdateParams,
+    fibers: Vec<(Pubkey, FiberCreateParams)>,
+) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let ix = build_update_thread_atomic(&authority.pubkey(), thread, params, fibers);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    svm.send_transaction(tx).map(|_| ())
+}
+
+#[test]
+fn test_thread_update_atomic_trigger_and_fibers() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-atomic",
+        Trigger::Immediate { jitter: 0 },
+    );
+    // Rent for the new fibers comes out of the thread
+    svm.airdrop(&thread_pubkey, 30_000_000).unwrap();
+    let (fiber_0, _) = fiber_pda(&thread_pubkey, 0);
+    let (fiber_1, _) = fiber_pda(&thread_pubkey, 1);
+    send_update_atomic(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams {
+            trigger: Some(Trigger::Interval {
+                seconds: 60,
+                skippable: false,
+                jitter: 0,
+            }),
+            ..Default::default()
+        },
+        vec![
+            (fiber_0, memo_fiber("step-0")),
+            (fiber_1, memo_fiber("step-1")),
+        ],
+    )
+    .unwrap();
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert_eq!(thread.fiber_ids, vec![0, 1]);
+    assert_eq!(thread.fiber_next_id, 2);
+    assert!(matches!(
+        thread.trigger,
+        antegen_thread_program::state::Trigger::Interval { seconds: 60, .. }
+    ));
+    assert!(account_exists(&svm, &fiber_0));
+    assert!(account_exists(&svm, &fiber_1));
+
+    // Later fibers append after the existing ones
+    let (fiber_2, _) = fiber_pda(&thread_pubkey, 2);
+    send_update_atomic(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams::default(),
+        vec![(fiber_2, memo_fiber("step-2"))],
+    )
+    .unwrap();
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).fiber_ids,
+        vec![0, 1, 2]
+    );
+}
+
+#[test]
+fn test_thread_update_atomic_authority_only() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    let bad_authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+    svm.airdrop(&bad_authority.pubkey(), DEFAULT_AIRDROP)
+        .unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-atomic-auth",
+        Trigger::Immediate { jitter: 0 },
+    );
+    let (fiber_0, _) = fiber_pda(&thread_pubkey, 0);
+    let result = send_update_atomic(
+        &mut svm,
+        &bad_authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams::default(),
+        vec![(fiber_0, memo_fiber("step-0"))],
+    );
+    assert!(result.is_err());
+    assert!(!account_exists(&svm, &fiber_0));
+}
+
+#[test]
+fn test_thread_update_atomic_wrong_fiber_account_changes_nothing() {
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-atomic-pda",
+        Trigger::Immediate { jitter: 0 },
+    );
+    // Fiber 1's account where fiber 0 is next
+    let (fiber_1, _) = fiber_pda(&thread_pubkey, 1);
+    let result = send_update_atomic(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams {
+            trigger: Some(Trigger::Timestamp {
+                unix_ts: 1900000000,
+                jitter: 0,
+            }),
+            ..Default::default()
+        },
+        vec![(fiber_1, memo_fiber("step-0"))],
+    );
+    assert!(result.is_err());
+
+    let thread = deserialize_thread(&svm, &thread_pubkey);
+    assert!(thread.fiber_ids.is_empty());
+    assert!(matches!(
+        thread.trigger,
+        antegen_thread_program::state::Trigger::Immediate { .. }
+    ));
+}
+
+#[test]
+fn test_thread_update_atomic_enforces_fiber_limit() {
+    use anchor_lang::AccountSerialize;
+    use antegen_thread_program::MAX_FIBERS;
+
+    let (mut svm, _admin, payer) = create_test_env();
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), DEFAULT_AIRDROP).unwrap();
+
+    let thread_pubkey = create_thread_for_update(
+        &mut svm,
+        &authority,
+        &payer,
+        "tu-atomic-limit",
+        Trigger::Immediate { jitter: 0 },
+    );
+    svm.airdrop(&thread_pubkey, 30_000_000).unwrap();
+
+    // A thread one fiber short of the limit
+    let mut thread = deserialize_thread(&svm, &thread_pubkey);
+    thread.fiber_ids = (0..MAX_FIBERS as u8 - 1).collect();
+    thread.fiber_next_id = MAX_FIBERS as u8 - 1;
+    let mut account = svm.get_account(&thread_pubkey).unwrap();
+    let mut data = Vec::with_capacity(account.data.len());
+    thread.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    svm.set_account(thread_pubkey, account).unwrap();
+
+    let next = MAX_FIBERS as u8 - 1;
+    let (fiber_a, _) = fiber_pda(&thread_pubkey, next);
+    let (fiber_b, _) = fiber_pda(&thread_pubkey, next + 1);
+    let result = send_update_atomic(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams::default(),
+        vec![(fiber_a, memo_fiber("a")), (fiber_b, memo_fiber("b"))],
+    );
+    assert!(result.is_err());
+    assert!(!account_exists(&svm, &fiber_a));
+
+    // Exactly at the limit is fine
+    send_update_atomic(
+        &mut svm,
+        &authority,
+        &payer,
+        &thread_pubkey,
+        ThreadUpdateParams::default(),
+        vec![(fiber_a, memo_fiber("a"))],
+    )
+    .unwrap();
+    assert_eq!(
+        deserialize_thread(&svm, &thread_pubkey).fiber_ids.len(),
+        MAX_FIBERS
+    );
+}